@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Benchmarks how the cost of the graph edit distance scales with the size of the graphs
+//! and the cost of node edits, compared to the structural hamming distance.
+//!
+//! Run with `cargo bench --features testdata --bench graph_edit`.
+
+use std::{hint::black_box, time::Instant};
+
+use gadjid::{
+    graph_operations::{graph_edit_distance, shd},
+    testdata::Fixture,
+};
+
+fn seconds_of<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (result, start.elapsed().as_secs_f64())
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>10} {:>8} {:>8} {:>6} {:>12}",
+        "nodes", "node cost", "cost", "rewired", "exact", "seconds"
+    );
+    for n_nodes in [1_000, 10_000, 100_000] {
+        let fixture = Fixture {
+            name: "bench",
+            n_nodes,
+            expected_degree: 3.0,
+            perturbation: 0.1,
+            seed: n_nodes as u64,
+        };
+        let (truth, guess) = fixture.load().unwrap();
+
+        let ((_, n_errors), seconds) = seconds_of(|| shd(&truth, &guess));
+        println!(
+            "{n_nodes:>8} {:>10} {n_errors:>8} {:>8} {:>6} {seconds:>12.6}",
+            "(shd)", 0, true
+        );
+
+        for node_cost in [1, 2, 4] {
+            let (distance, seconds) = seconds_of(|| graph_edit_distance(&truth, &guess, node_cost));
+            println!(
+                "{n_nodes:>8} {node_cost:>10} {:>8} {:>8} {:>6} {seconds:>12.6}",
+                distance.cost,
+                distance.rewired_nodes.len(),
+                distance.is_exact
+            );
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Benchmarks the oset adjustment intervention distance on the 100-node graphs in `testgraphs/`,
+//! comparing each DAG (CPDAG) to each other DAG (CPDAG), and on random DAGs of growing density.
+//!
+//! Run with `cargo bench --features testdata --bench oset_aid`.
+
+use std::{hint::black_box, path::PathBuf, time::Instant};
+
+use gadjid::{graph_operations::oset_aid, testdata::read_mtx, PDAG};
+use rand::SeedableRng;
+
+fn seconds_of<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (result, start.elapsed().as_secs_f64())
+}
+
+fn main() {
+    println!(
+        "{:>16} {:>8} {:>8} {:>10} {:>12}",
+        "graphs", "nodes", "pairs", "mistakes", "seconds"
+    );
+
+    // anchors at parent directory of Cargo.toml
+    let testgraphs = PathBuf::from_iter(["..", "testgraphs"]);
+    for kind in ["DAG", "CPDAG"] {
+        let graphs =
+            Vec::from_iter((20..=29).map(|id| {
+                read_mtx(&testgraphs.join(format!("100-node-{kind}-{id}.mtx"))).unwrap()
+            }));
+        let (mut mistakes, mut seconds) = (0, 0.0);
+        for (i, truth) in graphs.iter().enumerate() {
+            let others = graphs.iter().enumerate().filter(|(j, _)| i != *j);
+            for (_, guess) in others {
+                let ((_, found), elapsed) = seconds_of(|| oset_aid(truth, guess));
+                mistakes += found;
+                seconds += elapsed;
+            }
+        }
+        let name = format!("100-node {kind}s");
+        println!(
+            "{name:>16} {:>8} {:>8} {mistakes:>10} {seconds:>12.6}",
+            100,
+            graphs.len() * (graphs.len() - 1)
+        );
+    }
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    for density in [0.1, 0.3, 0.5] {
+        let truth = PDAG::random_dag(density, 200, &mut rng);
+        let guess = PDAG::random_dag(density, 200, &mut rng);
+        let ((_, mistakes), seconds) = seconds_of(|| oset_aid(&truth, &guess));
+        let name = format!("density {density}");
+        println!(
+            "{name:>16} {:>8} {:>8} {mistakes:>10} {seconds:>12.6}",
+            200, 1
+        );
+    }
+}
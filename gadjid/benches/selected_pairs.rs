@@ -0,0 +1,80 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Benchmarks how the cost of the selected-pairs distances scales with the number of pairs
+//! and the size of the graphs, compared to the distances over all pairs.
+//!
+//! Run with `cargo bench --features testdata --bench selected_pairs`.
+
+use std::{hint::black_box, time::Instant};
+
+use gadjid::{
+    graph_operations::{
+        ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+        PairsError, SelectedPairs, SelectedPairsDistance,
+    },
+    testdata::Fixture,
+    PDAG,
+};
+use rand::{Rng, SeedableRng};
+
+type AllPairs = fn(&PDAG, &PDAG) -> (f64, usize);
+type Selected = fn(&PDAG, &PDAG, &[(usize, usize)]) -> Result<SelectedPairsDistance, PairsError>;
+
+const METRICS: [(&str, AllPairs, Selected); 3] = [
+    ("ancestor_aid", ancestor_aid, |truth, guess, pairs| {
+        ancestor_aid_with(truth, guess, SelectedPairs(pairs))
+    }),
+    ("oset_aid", oset_aid, |truth, guess, pairs| {
+        oset_aid_with(truth, guess, SelectedPairs(pairs))
+    }),
+    ("parent_aid", parent_aid, |truth, guess, pairs| {
+        parent_aid_with(truth, guess, SelectedPairs(pairs))
+    }),
+];
+
+/// Graphs up to this size are also compared over all pairs.
+const MAX_NODES_ALL_PAIRS: usize = 10_000;
+
+fn seconds_of<T>(f: impl FnOnce() -> T) -> f64 {
+    let start = Instant::now();
+    black_box(f());
+    start.elapsed().as_secs_f64()
+}
+
+fn main() {
+    println!(
+        "{:>8} {:>8} {:>14} {:>12}",
+        "nodes", "pairs", "metric", "seconds"
+    );
+    for n_nodes in [1_000, 10_000, 100_000] {
+        let fixture = Fixture {
+            name: "bench",
+            n_nodes,
+            expected_degree: 3.0,
+            perturbation: 0.1,
+            seed: n_nodes as u64,
+        };
+        let (truth, guess) = fixture.load().unwrap();
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+
+        for n_pairs in [1, 10, 100, 1_000] {
+            let pairs = Vec::from_iter((0..n_pairs).map(|_| loop {
+                let pair = (rng.gen_range(0..n_nodes), rng.gen_range(0..n_nodes));
+                if pair.0 != pair.1 {
+                    break pair;
+                }
+            }));
+            for (name, _, selected_pairs) in METRICS {
+                let seconds = seconds_of(|| selected_pairs(&truth, &guess, &pairs).unwrap());
+                println!("{n_nodes:>8} {n_pairs:>8} {name:>14} {seconds:>12.6}");
+            }
+        }
+
+        if n_nodes <= MAX_NODES_ALL_PAIRS {
+            let n_pairs = n_nodes * (n_nodes - 1);
+            for (name, all_pairs, _) in METRICS {
+                let seconds = seconds_of(|| all_pairs(&truth, &guess));
+                println!("{n_nodes:>8} {n_pairs:>8} {name:>14} {seconds:>12.6}");
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Benchmarks the distances on sparse random graphs of growing size, to compare the set backends
+//! of the graph searches: by default, visited states are marked in bitsets up to 10⁴ nodes,
+//! with the `hash-sets` feature, they are always marked in hash sets.
+//!
+//! Run with `cargo bench --bench set_backends` and `cargo bench --features hash-sets --bench set_backends`.
+
+use std::{hint::black_box, time::Instant};
+
+use gadjid::{graph_operations::Distance, PDAG};
+use rand::SeedableRng;
+
+fn seconds_of<T>(f: impl FnOnce() -> T) -> (T, f64) {
+    let start = Instant::now();
+    let result = black_box(f());
+    (result, start.elapsed().as_secs_f64())
+}
+
+fn main() {
+    let backend = if cfg!(feature = "hash-sets") {
+        "hash sets"
+    } else {
+        "bitsets up to 10^4 nodes"
+    };
+    println!("visited states are marked in {backend}");
+    println!(
+        "{:>14} {:>8} {:>8} {:>10} {:>12}",
+        "distance", "graphs", "nodes", "mistakes", "seconds"
+    );
+
+    let distances = [
+        Distance::AncestorAid,
+        Distance::OsetAid,
+        Distance::ParentAid,
+    ];
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    for n in [100, 1_000, 4_000] {
+        // about 3 adjacent nodes per node
+        let density = 3.0 / n as f64;
+        let dags = (
+            PDAG::random_dag(density, n, &mut rng),
+            PDAG::random_dag(density, n, &mut rng),
+        );
+        let pdags = (
+            PDAG::random_pdag(density, n, &mut rng),
+            PDAG::random_pdag(density, n, &mut rng),
+        );
+        for (kind, (truth, guess)) in [("DAGs", &dags), ("PDAGs", &pdags)] {
+            for distance in distances {
+                let name = distance.name();
+                let ((_, mistakes), seconds) = seconds_of(|| distance.compute(truth, guess));
+                println!("{name:>14} {kind:>8} {n:>8} {mistakes:>10} {seconds:>12.6}");
+            }
+        }
+    }
+}
@@ -0,0 +1,457 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Computes the intermediate results of the adjustment identification distances for a single
+//! (treatment set, effect) pair, such as the optimal adjustment set and the NAM and NVA sets,
+//! alongside the distances between the two graphs, compares the parent sets of each node,
+//! and checks a pair of graphs for signs of a bug in the pipeline that produced them.
+
+use std::fmt;
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{
+        ancestor_aid, get_d_pd_nam, get_nam, get_nam_nva, get_parents, get_possible_descendants,
+        get_proper_ancestors, optimal_adjustment_set_given_descendants, oset_aid, parent_aid, shd,
+    },
+    PDAG,
+};
+
+/// The analysis of a single (treatment set, effect) pair and an adjustment set,
+/// as returned by [`analyze_pair`].
+///
+/// With the `serde` feature, this implements `serde::Serialize`; the serialized fields
+/// only change alongside [`PairAnalysis::FORMAT_VERSION`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize))]
+pub struct PairAnalysis {
+    /// the ancestor adjustment identification distance between the graphs
+    pub ancestor_aid: (f64, usize),
+    /// the oset adjustment identification distance between the graphs
+    pub oset_aid: (f64, usize),
+    /// the parent adjustment identification distance between the graphs
+    pub parent_aid: (f64, usize),
+    /// the structural hamming distance between the graphs
+    pub shd: (f64, usize),
+    /// the (sorted) treatment nodes
+    pub t: Vec<usize>,
+    /// the single effect node
+    pub y: usize,
+    /// the (sorted) adjustment set nodes
+    pub z: Vec<usize>,
+    /// the (sorted) possible descendants of t in g_guess
+    pub possible_descendants_of_t_in_g_guess: Vec<usize>,
+    /// the (sorted) nodes onto which the effect of t is not amenable to adjustment-set identification in g_guess
+    pub not_amenable_in_g_guess_wrt_t: Vec<usize>,
+    /// the (sorted) proper ancestors of y in g_guess, w.r.t. the set t
+    pub proper_ancestors_of_y_in_g_guess_wrt_t: Vec<usize>,
+    /// the (sorted) optimal adjustment set in g_guess, w.r.t. the effect of t onto y
+    pub oset_for_t_onto_y_in_g_guess: Vec<usize>,
+    /// the (sorted) set of nodes for which the effect of t onto those nodes is not validly adjusted for in g_guess
+    /// by the parents of t in g_guess
+    pub not_validly_adjusted_for_in_g_guess_by_parents_of_t: Vec<usize>,
+    /// the (sorted) set of nodes for which the effect of t onto those nodes is not validly adjusted for in g_guess
+    /// by the optimal adjustment set for t onto y in g_guess
+    pub not_validly_adjusted_for_in_g_guess_by_oset_for_t_onto_y: Vec<usize>,
+    /// the (sorted) set of nodes for which the effect of t onto those nodes is not validly adjusted for in g_guess
+    /// by the empty set
+    pub not_validly_adjusted_for_in_g_guess_by_empty_set: Vec<usize>,
+    /// the (sorted) set of nodes for which the effect of t onto those nodes is not validly adjusted for in g_guess
+    /// by the set z
+    pub not_validly_adjusted_for_in_g_guess_by_z: Vec<usize>,
+}
+
+impl PairAnalysis {
+    /// The version of the fields of [`PairAnalysis`], which is increased whenever a field is
+    /// added, removed, renamed, or changes its meaning, so that stored analyses can be told apart.
+    pub const FORMAT_VERSION: u32 = 1;
+}
+
+fn hashset_to_sorted_vec(set: &FxHashSet<usize>) -> Vec<usize> {
+    let mut vec = Vec::from_iter(set.iter().copied());
+    vec.sort_unstable();
+    vec
+}
+
+/// Computes the distances between the `truth` and the `guess` DAG or CPDAG alongside
+/// the possible descendants, NAM set, optimal adjustment set, and NVA sets in the guess graph
+/// for the treatments `t`, the effect `y`, and the adjustment set `z`.
+pub fn analyze_pair(
+    truth: &PDAG,
+    guess: &PDAG,
+    t: &[usize],
+    y: usize,
+    z: &[usize],
+) -> PairAnalysis {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(!t.is_empty(), "there must be at least one treatment");
+    assert!(
+        t.iter().chain(z).chain([&y]).all(|v| *v < guess.n_nodes),
+        "all nodes must be in bounds"
+    );
+
+    let mut t = t.to_vec();
+    t.sort_unstable();
+    t.dedup();
+    let mut z = z.to_vec();
+    z.sort_unstable();
+    z.dedup();
+
+    let nva_sorted_vec = |adjustment_set: &FxHashSet<usize>| {
+        let (_, nva) = get_nam_nva(guess, &t, adjustment_set, None);
+        hashset_to_sorted_vec(&nva)
+    };
+
+    let (t_descendants, _, _) = get_d_pd_nam(guess, &t);
+    let oset = optimal_adjustment_set_given_descendants(guess, &t, &[y], &t_descendants);
+    let parents = get_parents(guess, t.iter());
+
+    PairAnalysis {
+        ancestor_aid: ancestor_aid(truth, guess),
+        oset_aid: oset_aid(truth, guess),
+        parent_aid: parent_aid(truth, guess),
+        shd: shd(truth, guess),
+        possible_descendants_of_t_in_g_guess: hashset_to_sorted_vec(&get_possible_descendants(
+            guess,
+            t.iter(),
+        )),
+        not_amenable_in_g_guess_wrt_t: hashset_to_sorted_vec(&get_nam(guess, &t, None)),
+        proper_ancestors_of_y_in_g_guess_wrt_t: hashset_to_sorted_vec(&get_proper_ancestors(
+            guess,
+            t.iter(),
+            [y].iter(),
+        )),
+        oset_for_t_onto_y_in_g_guess: hashset_to_sorted_vec(&oset),
+        not_validly_adjusted_for_in_g_guess_by_parents_of_t: nva_sorted_vec(&parents),
+        not_validly_adjusted_for_in_g_guess_by_oset_for_t_onto_y: nva_sorted_vec(&oset),
+        not_validly_adjusted_for_in_g_guess_by_empty_set: nva_sorted_vec(&FxHashSet::default()),
+        not_validly_adjusted_for_in_g_guess_by_z: nva_sorted_vec(&FxHashSet::from_iter(
+            z.iter().copied(),
+        )),
+        t,
+        y,
+        z,
+    }
+}
+
+/// The comparison of the parents of a single node in two graphs, as returned by [`parent_set_report`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize))]
+pub struct ParentSetComparison {
+    /// the node
+    pub node: usize,
+    /// the (sorted) parents of the node in the truth graph
+    pub true_parents: Vec<usize>,
+    /// the (sorted) parents of the node in the guess graph
+    pub guessed_parents: Vec<usize>,
+    /// the number of shared parents divided by the number of parents in either graph,
+    /// which is 1 if the node has no parents in both graphs
+    pub jaccard: f64,
+    /// whether the guessed parents are a valid adjustment set in the truth graph
+    /// for the effect of the node on each of its children in the truth graph,
+    /// which holds trivially if the node has no children there
+    pub valid_for_true_children: bool,
+}
+
+/// Compares the parents of each node in the `truth` and the `guess` DAG or CPDAG,
+/// connecting the edges of the graphs to the parent adjustment intervention distance:
+/// for each node, lists its true and guessed parents, their Jaccard similarity,
+/// and whether the guessed parents are a valid adjustment set for the effects of the node
+/// on its true children. Only directed edges are parents, so in CPDAGs,
+/// nodes with undirected edges may have fewer parents than in the DAGs they represent.
+pub fn parent_set_report(truth: &PDAG, guess: &PDAG) -> Vec<ParentSetComparison> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+
+    crate::rayon::build_global();
+
+    (0..truth.n_nodes)
+        .into_par_iter()
+        .map(|v| {
+            let true_parents = truth.parents_of(v).to_vec();
+            let guessed_parents = guess.parents_of(v).to_vec();
+            let shared = true_parents
+                .iter()
+                .filter(|p| guessed_parents.binary_search(p).is_ok())
+                .count();
+            let either = true_parents.len() + guessed_parents.len() - shared;
+
+            let true_children = FxHashSet::from_iter(truth.children_of(v).iter().copied());
+            let valid_for_true_children = true_children.is_empty() || {
+                let z = FxHashSet::from_iter(guessed_parents.iter().copied());
+                let (_, nva) = get_nam_nva(truth, &[v], &z, Some(&true_children));
+                nva.is_disjoint(&true_children)
+            };
+
+            ParentSetComparison {
+                node: v,
+                jaccard: if either == 0 {
+                    1.0
+                } else {
+                    shared as f64 / either as f64
+                },
+                true_parents,
+                guessed_parents,
+                valid_for_true_children,
+            }
+        })
+        .collect()
+}
+
+/// A guess graph is this many times denser or sparser than the truth graph,
+/// measured in edges, before [`validate_pair`] warns about it.
+pub const EXTREME_EDGE_RATIO: usize = 10;
+
+/// Suspicious properties of a pair of graphs, which make a distance between them likely misleading.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PairWarning {
+    /// The graphs have different numbers of nodes, so no distance between them can be computed.
+    NodeCountMismatch {
+        /// the number of nodes of the truth graph
+        truth: usize,
+        /// the number of nodes of the guess graph
+        guess: usize,
+    },
+    /// The truth graph has no edges.
+    EmptyTruth,
+    /// The guess graph has no edges.
+    EmptyGuess,
+    /// The graphs are identical, as if the truth was passed as the guess.
+    Identical,
+    /// One graph has more than [`EXTREME_EDGE_RATIO`] times as many edges as the other.
+    ExtremeDensityRatio {
+        /// the number of edges of the truth graph
+        truth_edges: usize,
+        /// the number of edges of the guess graph
+        guess_edges: usize,
+    },
+    /// The guess is the skeleton of the truth, with every edge of the truth undirected,
+    /// as if the edge directions had been lost on the way.
+    GuessIsSkeletonOfTruth,
+}
+
+impl fmt::Display for PairWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PairWarning::NodeCountMismatch { truth, guess } => write!(
+                f,
+                "the truth graph has {truth} nodes, but the guess graph has {guess} nodes"
+            ),
+            PairWarning::EmptyTruth => write!(f, "the truth graph has no edges"),
+            PairWarning::EmptyGuess => write!(f, "the guess graph has no edges"),
+            PairWarning::Identical => write!(f, "the truth and the guess graph are identical"),
+            PairWarning::ExtremeDensityRatio {
+                truth_edges,
+                guess_edges,
+            } => write!(
+                f,
+                "the truth graph has {truth_edges} edges, but the guess graph has {guess_edges} edges"
+            ),
+            PairWarning::GuessIsSkeletonOfTruth => write!(
+                f,
+                "the guess graph is the skeleton of the truth graph, all its edges are undirected"
+            ),
+        }
+    }
+}
+
+/// Checks the `truth` and the `guess` DAG or CPDAG for properties that hint at a bug in the
+/// pipeline that produced them, such as passing the truth as the guess, before a distance
+/// between them is reported. Returns the warnings in the order of the variants of [`PairWarning`];
+/// if the graphs have different numbers of nodes, the checks that compare them are skipped.
+///
+/// ```
+/// use gadjid::{
+///     analysis::{validate_pair, PairWarning},
+///     PDAG,
+/// };
+///
+/// // 0 -> 1
+/// let dag = PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]);
+/// // 0 -- 1
+/// let skeleton = PDAG::from_dense_row_to_col(vec![vec![0, 2], vec![0, 0]]);
+///
+/// assert_eq!(validate_pair(&dag, &dag), vec![PairWarning::Identical]);
+/// assert_eq!(
+///     validate_pair(&dag, &skeleton),
+///     vec![PairWarning::GuessIsSkeletonOfTruth]
+/// );
+/// ```
+pub fn validate_pair(truth: &PDAG, guess: &PDAG) -> Vec<PairWarning> {
+    let mut warnings = Vec::new();
+    if truth.n_nodes != guess.n_nodes {
+        warnings.push(PairWarning::NodeCountMismatch {
+            truth: truth.n_nodes,
+            guess: guess.n_nodes,
+        });
+    }
+    let truth_edges = truth.n_directed_edges + truth.n_undirected_edges;
+    let guess_edges = guess.n_directed_edges + guess.n_undirected_edges;
+    if truth_edges == 0 {
+        warnings.push(PairWarning::EmptyTruth);
+    }
+    if guess_edges == 0 {
+        warnings.push(PairWarning::EmptyGuess);
+    }
+    if truth.n_nodes != guess.n_nodes {
+        return warnings;
+    }
+    if truth == guess {
+        warnings.push(PairWarning::Identical);
+    }
+    let (fewer, more) = (truth_edges.min(guess_edges), truth_edges.max(guess_edges));
+    if fewer > 0 && more > EXTREME_EDGE_RATIO * fewer {
+        warnings.push(PairWarning::ExtremeDensityRatio {
+            truth_edges,
+            guess_edges,
+        });
+    }
+    if truth.n_directed_edges > 0
+        && guess.n_directed_edges == 0
+        && (0..truth.n_nodes).all(|v| adjacent_of(truth, v) == guess.adjacent_undirected_of(v))
+    {
+        warnings.push(PairWarning::GuessIsSkeletonOfTruth);
+    }
+    warnings
+}
+
+/// Returns the (sorted) nodes adjacent to `node` by an edge of any type.
+fn adjacent_of(graph: &PDAG, node: usize) -> Vec<usize> {
+    let mut adjacent = [
+        graph.parents_of(node),
+        graph.children_of(node),
+        graph.adjacent_undirected_of(node),
+    ]
+    .concat();
+    adjacent.sort_unstable();
+    adjacent
+}
+
+#[cfg(test)]
+mod test {
+    use crate::PDAG;
+
+    use super::{analyze_pair, parent_set_report, validate_pair, PairWarning};
+
+    #[test]
+    fn analyzes_pair_with_unsorted_sets() {
+        // 2 -> 0 -> 1, 2 -> 1, 3 -> 1
+        let dag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0], //
+            vec![0, 0, 0, 0],
+            vec![1, 1, 0, 0],
+            vec![0, 1, 0, 0],
+        ]);
+        let analysis = analyze_pair(&dag, &dag, &[0, 0], 1, &[3, 2, 3]);
+        assert_eq!(analysis.oset_aid, (0.0, 0));
+        assert_eq!((analysis.t, analysis.z), (vec![0], vec![2, 3]));
+        assert_eq!(analysis.oset_for_t_onto_y_in_g_guess, vec![2, 3]);
+        assert_eq!(
+            analysis.not_validly_adjusted_for_in_g_guess_by_empty_set,
+            vec![1, 2]
+        );
+        // the adjustment set is valid for the effect on 1, not for the effects on its own nodes
+        assert_eq!(
+            analysis.not_validly_adjusted_for_in_g_guess_by_z,
+            vec![2, 3]
+        );
+    }
+
+    #[test]
+    fn compares_parent_sets() {
+        // truth: 0 -> 1 -> 2, 3 -> 1, 3 -> 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0], //
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 1, 1, 0],
+        ]);
+        // guess: 0 -> 1 -> 2, 2 -> 3
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0], //
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![0, 0, 0, 0],
+        ]);
+        let report = parent_set_report(&truth, &guess);
+        assert_eq!(report.len(), 4);
+
+        // the guess misses the parent 3, which confounds the effect of 1 on 2
+        assert_eq!(report[1].true_parents, vec![0, 3]);
+        assert_eq!(report[1].guessed_parents, vec![0]);
+        assert_eq!(report[1].jaccard, 0.5);
+        assert!(!report[1].valid_for_true_children);
+
+        assert_eq!(
+            (report[2].jaccard, report[2].valid_for_true_children),
+            (0.5, true)
+        );
+        // the guessed parent 2 of 3 is a true child of 3
+        assert_eq!(report[3].guessed_parents, vec![2]);
+        assert_eq!(
+            (report[3].jaccard, report[3].valid_for_true_children),
+            (0.0, false)
+        );
+        assert_eq!(
+            (report[0].jaccard, report[0].valid_for_true_children),
+            (1.0, true)
+        );
+    }
+
+    #[test]
+    fn warns_about_suspicious_pairs() {
+        // 0 -> 1 -> 2
+        let chain = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        // 0 -> 1 <- 2
+        let collider = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 0],
+            vec![0, 1, 0],
+        ]);
+        let empty = PDAG::from_dense_row_to_col(vec![vec![0; 3]; 3]);
+        let two_nodes = PDAG::from_dense_row_to_col(vec![vec![0; 2]; 2]);
+
+        assert_eq!(validate_pair(&chain, &collider), vec![]);
+        assert_eq!(
+            validate_pair(&empty, &empty),
+            vec![
+                PairWarning::EmptyTruth,
+                PairWarning::EmptyGuess,
+                PairWarning::Identical
+            ]
+        );
+        assert_eq!(
+            validate_pair(&chain, &two_nodes),
+            vec![
+                PairWarning::NodeCountMismatch { truth: 3, guess: 2 },
+                PairWarning::EmptyGuess
+            ]
+        );
+
+        // a dense truth and a guess with a single edge
+        let mut dense = vec![vec![0; 12]; 12];
+        for (v, row) in dense.iter_mut().enumerate() {
+            row[v + 1..].fill(1);
+        }
+        let dense = PDAG::from_dense_row_to_col(dense);
+        let mut sparse = vec![vec![0; 12]; 12];
+        sparse[0][1] = 1;
+        let sparse = PDAG::from_dense_row_to_col(sparse);
+        assert_eq!(
+            validate_pair(&dense, &sparse),
+            vec![PairWarning::ExtremeDensityRatio {
+                truth_edges: 66,
+                guess_edges: 1
+            }]
+        );
+    }
+}
@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Reads the graph files of causal discovery benchmarks and writes distances as tables
+//! that merge into their results, so that gadjid can score the outputs of established benchmarks.
+//!
+//! Two formats are read: the adjacency matrix CSV files of the Benchpress benchmarking framework,
+//! with a header of node names and a `1` in row `i` and column `j` for an edge `i -> j`,
+//! where an edge coded in both directions is undirected; and the CSV score matrices of
+//! CauseMe challenge submissions, without header, whose entry in row `i` and column `j`
+//! is the confidence in a link `i -> j` and which are thresholded into a graph.
+
+use std::{error::Error, fmt};
+
+use crate::{
+    graph_operations::Distance,
+    tables::{Column, LongTable},
+    LoadError, PDAG,
+};
+
+/// The reason a benchmark file could not be read.
+#[derive(Debug, PartialEq)]
+pub enum BenchmarkError {
+    /// A line of the file is not in the expected format.
+    Malformed {
+        /// the offending line, counting from 1
+        line: usize,
+        /// what is wrong with it
+        reason: &'static str,
+    },
+    /// The coded graph could not be loaded, for example, because it is not acyclic.
+    Load(LoadError),
+}
+
+impl Error for BenchmarkError {}
+
+impl fmt::Display for BenchmarkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchmarkError::Malformed { line, reason } => write!(f, "line {line}: {reason}"),
+            BenchmarkError::Load(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+/// Splits a line of a CSV file into its trimmed fields, without surrounding quotes.
+fn fields(line: &str) -> impl Iterator<Item = &str> {
+    line.split(',')
+        .map(|field| field.trim().trim_matches('"').trim_matches('\''))
+}
+
+/// Parses the rows of a matrix from the non-empty, numbered lines of a CSV file,
+/// which must all have `n_columns` entries, or as many as the first row if not given.
+fn parse_rows<'a, T: std::str::FromStr>(
+    lines: impl Iterator<Item = (usize, &'a str)>,
+    n_columns: Option<usize>,
+) -> Result<Vec<Vec<T>>, BenchmarkError> {
+    let mut rows = Vec::new();
+    for (i, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+        let malformed = |reason| BenchmarkError::Malformed {
+            line: i + 1,
+            reason,
+        };
+        let row: Vec<T> = fields(line)
+            .map(|field| {
+                field
+                    .parse()
+                    .map_err(|_| malformed("entry is not a number"))
+            })
+            .collect::<Result<_, _>>()?;
+        let expected = n_columns.or(rows.first().map(Vec::len));
+        if expected.is_some_and(|len| len != row.len()) {
+            return Err(malformed("row has a different number of entries"));
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Loads the graph with an edge `i -> j` for each pair `i != j` that is `marked(i, j)` but not `marked(j, i)`,
+/// and an undirected edge `i -- j` for each pair that is marked in both directions.
+fn from_marks(
+    n_nodes: usize,
+    marked: impl Fn(usize, usize) -> bool,
+) -> Result<PDAG, BenchmarkError> {
+    let mut dense = vec![vec![0; n_nodes]; n_nodes];
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..n_nodes {
+        for j in (i + 1)..n_nodes {
+            match (marked(i, j), marked(j, i)) {
+                (true, true) => dense[i][j] = 2,
+                (true, false) => dense[i][j] = 1,
+                (false, true) => dense[j][i] = 1,
+                (false, false) => {}
+            }
+        }
+    }
+    PDAG::try_from_dense_row_to_col(dense).map_err(BenchmarkError::Load)
+}
+
+/// Reads a Benchpress adjacency matrix CSV file, with a header of node names and a row of `0`s and `1`s
+/// per node, where a `1` in row `i` and column `j` codes an edge `i -> j` and an edge coded in both
+/// directions is undirected. Returns the node names alongside the graph.
+///
+/// ```
+/// use gadjid::benchmarks::read_benchpress_csv;
+///
+/// let csv = "\"a\",\"b\",\"c\"\n0,1,0\n0,0,1\n0,1,0\n";
+/// let (names, graph) = read_benchpress_csv(csv).unwrap();
+/// assert_eq!(names, ["a", "b", "c"]);
+/// // a -> b -- c
+/// assert_eq!(graph.children_of(0), &[1]);
+/// assert_eq!(graph.adjacent_undirected_of(1), &[2]);
+/// ```
+pub fn read_benchpress_csv(contents: &str) -> Result<(Vec<String>, PDAG), BenchmarkError> {
+    let mut lines = contents.lines().enumerate();
+    let Some((_, header)) = lines.next() else {
+        return Err(BenchmarkError::Malformed {
+            line: 1,
+            reason: "file has no header",
+        });
+    };
+    let names = Vec::from_iter(fields(header).map(str::to_string));
+    let rows: Vec<Vec<u8>> = parse_rows(lines, Some(names.len()))?;
+    if rows.len() != names.len() {
+        return Err(BenchmarkError::Malformed {
+            line: rows.len() + 2,
+            reason: "matrix has not one row per node",
+        });
+    }
+    for (i, row) in rows.iter().enumerate() {
+        let reason = match row.iter().enumerate().find(|(_, value)| **value > 1) {
+            Some(_) => "entry is neither 0 nor 1",
+            None if row[i] != 0 => "entry on the diagonal codes a self-loop",
+            None => continue,
+        };
+        return Err(BenchmarkError::Malformed {
+            line: i + 2,
+            reason,
+        });
+    }
+    let graph = from_marks(names.len(), |i, j| rows[i][j] == 1)?;
+    Ok((names, graph))
+}
+
+/// Reads a CauseMe score matrix CSV file, without header, whose entry in row `i` and column `j`
+/// is the confidence in a link `i -> j`, and loads the graph of the links whose score is at least `threshold`,
+/// where a pair of nodes linked in both directions is adjacent by an undirected edge.
+/// The diagonal, which scores the dependence of a time series on its own past, is ignored.
+///
+/// ```
+/// use gadjid::benchmarks::read_score_matrix_csv;
+///
+/// let csv = "0.9,0.8,0.1\n0.0,0.7,0.6\n0.2,0.9,0.5\n";
+/// let graph = read_score_matrix_csv(csv, 0.5).unwrap();
+/// // 0 -> 1 -- 2
+/// assert_eq!(graph.children_of(0), &[1]);
+/// assert_eq!(graph.adjacent_undirected_of(1), &[2]);
+/// ```
+pub fn read_score_matrix_csv(contents: &str, threshold: f64) -> Result<PDAG, BenchmarkError> {
+    let rows: Vec<Vec<f64>> = parse_rows(contents.lines().enumerate(), None)?;
+    if rows.first().is_some_and(|row| row.len() != rows.len()) {
+        return Err(BenchmarkError::Malformed {
+            line: 1,
+            reason: "matrix is not square",
+        });
+    }
+    from_marks(rows.len(), |i, j| rows[i][j] >= threshold)
+}
+
+/// Computes the `distances` between the `truth` and each of the named `guesses`
+/// and returns them as a table in long format, with the columns `id`, `distance`, `normalized`,
+/// and `mistakes` and one row per guess and distance, which merges into the results of any benchmark.
+pub fn metrics_table(truth: &PDAG, guesses: &[(&str, &PDAG)], distances: &[Distance]) -> LongTable {
+    let (mut ids, mut names, mut normalized, mut mistakes) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    for (id, guess) in guesses {
+        for distance in distances {
+            let (distance_normalized, distance_mistakes) = distance.compute(truth, guess);
+            ids.push(id.to_string());
+            names.push(distance.name().to_string());
+            normalized.push(distance_normalized);
+            mistakes.push(distance_mistakes);
+        }
+    }
+    LongTable::new(vec![
+        ("id", Column::Text(ids)),
+        ("distance", Column::Text(names)),
+        ("normalized", Column::Float(normalized)),
+        ("mistakes", Column::Integer(mistakes)),
+    ])
+}
+
+/// Computes the `distances` between the `truth` and each of the named `guesses`
+/// and returns them as a CSV table in long format, see [`metrics_table`].
+///
+/// ```
+/// use gadjid::{benchmarks::metrics_table_csv, graph_operations::Distance, PDAG};
+///
+/// let truth = PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]);
+/// let guess = PDAG::from_dense_row_to_col(vec![vec![0, 0], vec![1, 0]]);
+/// assert_eq!(
+///     metrics_table_csv(&truth, &[("pc", &guess)], &[Distance::Shd]),
+///     "id,distance,normalized,mistakes\npc,shd,1,1\n"
+/// );
+/// ```
+pub fn metrics_table_csv(
+    truth: &PDAG,
+    guesses: &[(&str, &PDAG)],
+    distances: &[Distance],
+) -> String {
+    metrics_table(truth, guesses, distances).to_csv()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::Distance, LoadError, PDAG};
+
+    use super::{metrics_table_csv, read_benchpress_csv, read_score_matrix_csv, BenchmarkError};
+
+    /// Writes the graph as a Benchpress adjacency matrix CSV file, with an undirected edge in both directions.
+    fn to_benchpress_csv(graph: &PDAG) -> String {
+        let n = graph.n_nodes;
+        let mut csv = Vec::from_iter((0..n).map(|v| format!("\"X{v}\""))).join(",") + "\n";
+        for v in 0..n {
+            let row = Vec::from_iter((0..n).map(|w| {
+                let marked = graph.children_of(v).contains(&w)
+                    || graph.adjacent_undirected_of(v).contains(&w);
+                if marked {
+                    "1"
+                } else {
+                    "0"
+                }
+            }));
+            csv.push_str(&(row.join(",") + "\n"));
+        }
+        csv
+    }
+
+    #[test]
+    fn property_benchpress_roundtrip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..20 {
+            let graph = PDAG::random_pdag(0.3, n, &mut rng);
+            let (names, loaded) = read_benchpress_csv(&to_benchpress_csv(&graph)).unwrap();
+            assert_eq!(names.len(), n);
+            assert_eq!(loaded, graph);
+        }
+    }
+
+    #[test]
+    fn reports_malformed_files() {
+        assert_eq!(
+            read_benchpress_csv("a,b\n0,1\n").unwrap_err(),
+            BenchmarkError::Malformed {
+                line: 3,
+                reason: "matrix has not one row per node"
+            }
+        );
+        assert_eq!(
+            read_benchpress_csv("a,b\n1,0\n0,0\n").unwrap_err(),
+            BenchmarkError::Malformed {
+                line: 2,
+                reason: "entry on the diagonal codes a self-loop"
+            }
+        );
+        assert_eq!(
+            read_benchpress_csv("a,b\n0,x\n0,0\n").unwrap_err(),
+            BenchmarkError::Malformed {
+                line: 2,
+                reason: "entry is not a number"
+            }
+        );
+        assert!(matches!(
+            read_score_matrix_csv("0,1,0\n0,0,1\n1,0,0\n", 0.5),
+            Err(BenchmarkError::Load(LoadError::NotAcyclic))
+        ));
+        assert!(matches!(
+            read_score_matrix_csv("0,1\n0,0,1\n", 0.5),
+            Err(BenchmarkError::Malformed { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn writes_one_row_per_guess_and_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let truth = PDAG::random_pdag(0.3, 10, &mut rng);
+        let guesses = Vec::from_iter((0..3).map(|_| PDAG::random_pdag(0.3, 10, &mut rng)));
+        let named = Vec::from_iter(["a", "b", "c"].into_iter().zip(guesses.iter()));
+        let table = metrics_table_csv(&truth, &named, &Distance::ALL);
+        let lines = Vec::from_iter(table.lines());
+        assert_eq!(lines.len(), 1 + 3 * Distance::ALL.len());
+        let (normalized, mistakes) = Distance::OsetAid.compute(&truth, &guesses[1]);
+        assert!(lines.contains(&format!("b,oset_aid,{normalized},{mistakes}").as_str()));
+    }
+}
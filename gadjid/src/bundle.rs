@@ -0,0 +1,637 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Exports the graphs and the reported distances of an evaluation as a self-contained bundle,
+//! and verifies a bundle by recomputing its distances, for the reproducibility of published numbers.
+//!
+//! A bundle is an uncompressed POSIX tar archive holding the true and the guess graph as Matrix Market files
+//! (`truth.mtx` and `guess.mtx`, with entries `row column 1` for directed edges `row -> column`
+//! and `row column 2` for undirected edges, counting nodes from 1), the options of the evaluation,
+//! such as the edge direction of the input matrices (`options.json`), the reported distances
+//! (`results.json`), and the format version, the gadjid version, and the fingerprints
+//! of the graphs (`manifest.json`). Its files can be extracted and read with standard tools,
+//! but the JSON files are written in a fixed layout of one entry per line that is private to gadjid:
+//! [`verify_bundle`] only reads this layout, so the files are not meant to be edited.
+
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::Path,
+};
+
+use crate::{
+    graph_operations::Distance,
+    provenance::{json_string, parse_json_string},
+    EdgelistIterator, LoadError, PDAG,
+};
+
+/// The version of the layout of the bundle files, which is increased whenever they change.
+pub const BUNDLE_FORMAT_VERSION: u32 = 2;
+
+/// The reason exporting or verifying a bundle failed.
+#[derive(Debug)]
+pub enum BundleError {
+    /// Reading or writing a file of the bundle failed.
+    Io(std::io::Error),
+    /// The bundle is not a tar archive as written by [`export_bundle`].
+    NotAnArchive,
+    /// A file is missing from the bundle.
+    MissingFile {
+        /// the name of the file
+        file: &'static str,
+    },
+    /// The bundle was written in a format version other than [`BUNDLE_FORMAT_VERSION`].
+    UnsupportedFormatVersion {
+        /// the format version recorded in the manifest
+        found: String,
+    },
+    /// A file of the bundle is not in the format written by [`export_bundle`].
+    Malformed {
+        /// the name of the file
+        file: &'static str,
+        /// the (1-based) line that could not be read, or 0 if an entry is missing
+        line: usize,
+    },
+    /// A graph of the bundle could not be loaded.
+    Load(LoadError),
+    /// A graph of the bundle has fewer than the 2 nodes that the distances are defined for.
+    TooFewNodes {
+        /// the name of the graph file
+        file: &'static str,
+        /// the number of nodes of the graph
+        n_nodes: usize,
+    },
+    /// A graph of the bundle does not match the fingerprint recorded in the manifest,
+    /// so the graph file was changed after exporting.
+    FingerprintMismatch {
+        /// the name of the graph file
+        file: &'static str,
+    },
+    /// A distance is reported more than once.
+    DuplicateDistance(Distance),
+}
+
+impl Error for BundleError {}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Io(err) => write!(f, "bundle file could not be accessed: {err}"),
+            BundleError::NotAnArchive => {
+                write!(f, "the bundle is not a tar archive as written by export_bundle")
+            }
+            BundleError::MissingFile { file } => write!(f, "the bundle has no file {file}"),
+            BundleError::UnsupportedFormatVersion { found } => write!(
+                f,
+                "the bundle has format version {found}, but this gadjid version reads format version {BUNDLE_FORMAT_VERSION}"
+            ),
+            BundleError::Malformed { file, line } => {
+                write!(f, "line {line} of the bundle file {file} is malformed")
+            }
+            BundleError::Load(err) => write!(f, "bundled graph could not be loaded: {err}"),
+            BundleError::TooFewNodes { file, n_nodes } => write!(
+                f,
+                "the bundled graph {file} has {n_nodes} nodes, but the distances need at least 2"
+            ),
+            BundleError::FingerprintMismatch { file } => write!(
+                f,
+                "the bundled graph {file} does not match the fingerprint in the manifest"
+            ),
+            BundleError::DuplicateDistance(distance) => {
+                write!(f, "the distance {} is reported twice", distance.name())
+            }
+        }
+    }
+}
+
+impl From<std::io::Error> for BundleError {
+    fn from(err: std::io::Error) -> Self {
+        BundleError::Io(err)
+    }
+}
+
+/// A reported distance whose recomputed value differs, as returned by [`verify_bundle`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    /// the distance
+    pub distance: Distance,
+    /// the (normalized, total) distance reported in the bundle
+    pub reported: (f64, usize),
+    /// the (normalized, total) distance recomputed from the bundled graphs
+    pub recomputed: (f64, usize),
+}
+
+/// The outcome of recomputing the distances of a bundle, as returned by [`verify_bundle`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BundleVerification {
+    /// the gadjid version that exported the bundle
+    pub exported_with: String,
+    /// the names and values of the options of the evaluation, such as the edge direction
+    pub options: Vec<(String, String)>,
+    /// the distances that were recomputed
+    pub verified: Vec<Distance>,
+    /// the distances whose recomputed value differs from the reported one
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl BundleVerification {
+    /// Whether all reported distances are reproduced.
+    pub fn is_reproduced(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Returns the graph as a Matrix Market file, listing each undirected edge once.
+pub(crate) fn to_mtx(graph: &PDAG) -> String {
+    let mut entries = Vec::new();
+    for v in 0..graph.n_nodes {
+        let undirected = graph.adjacent_undirected_of(v).iter().filter(|w| v < **w);
+        entries.extend(graph.children_of(v).iter().map(|w| (v, *w, 1)));
+        entries.extend(undirected.map(|w| (v, *w, 2)));
+    }
+    entries.sort_unstable();
+    let mut mtx = format!(
+        "%%MatrixMarket matrix coordinate integer general\n{n} {n} {}\n",
+        entries.len(),
+        n = graph.n_nodes
+    );
+    for (v, w, edge_type) in entries {
+        mtx.push_str(&format!("{} {} {edge_type}\n", v + 1, w + 1));
+    }
+    mtx
+}
+
+fn from_mtx(mtx: &str, file: &'static str) -> Result<PDAG, BundleError> {
+    let malformed = |line| BundleError::Malformed { file, line };
+    let numbers = |line: &str| -> Option<Vec<usize>> {
+        line.split_whitespace().map(|s| s.parse().ok()).collect()
+    };
+    let mut lines = mtx.lines().enumerate().skip(1);
+    let n_nodes = match lines.next().map(|(i, line)| (i, numbers(line))) {
+        Some((_, Some(dims))) if dims.len() == 3 && dims[0] == dims[1] => dims[0],
+        Some((i, _)) => return Err(malformed(i + 1)),
+        None => return Err(malformed(2)),
+    };
+    let mut entries = Vec::new();
+    for (i, line) in lines {
+        match numbers(line).as_deref() {
+            Some(&[v, w, edge_type]) if v >= 1 && w >= 1 && (1..=2).contains(&edge_type) => {
+                entries.push((v - 1, w - 1, edge_type as i8));
+            }
+            _ => return Err(malformed(i + 1)),
+        }
+    }
+    entries.sort_unstable();
+    PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(n_nodes))
+        .map_err(BundleError::Load)
+}
+
+/// The 64-bit FNV-1a hash of a graph file, as 16 hexadecimal digits,
+/// which unlike the hashers of the standard library is stable across versions and platforms.
+pub(crate) fn fingerprint(contents: &str) -> String {
+    let hash = contents.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// The size of the header and data blocks of a tar archive.
+const TAR_BLOCK: usize = 512;
+
+/// Writes `value` as a NUL-terminated octal number that fills the header `field`.
+fn write_octal(field: &mut [u8], value: usize) {
+    let digits = format!("{value:0width$o}", width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+}
+
+/// The checksum of a tar header, the sum of its bytes with the checksum field taken as spaces.
+fn tar_checksum(header: &[u8]) -> usize {
+    let spaces = 8 * b' ' as usize;
+    spaces
+        + header[..148]
+            .iter()
+            .chain(&header[156..])
+            .map(|byte| *byte as usize)
+            .sum::<usize>()
+}
+
+/// Returns the `files` as an uncompressed POSIX tar (ustar) archive. All timestamps are 0,
+/// so that exporting the same evaluation twice gives the same archive.
+fn to_tar(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut tar = Vec::new();
+    for (name, contents) in files {
+        let mut header = [0; TAR_BLOCK];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        write_octal(&mut header[100..108], 0o644);
+        write_octal(&mut header[108..116], 0);
+        write_octal(&mut header[116..124], 0);
+        write_octal(&mut header[124..136], contents.len());
+        write_octal(&mut header[136..148], 0);
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        let checksum = format!("{:06o}\0 ", tar_checksum(&header));
+        header[148..156].copy_from_slice(checksum.as_bytes());
+
+        tar.extend_from_slice(&header);
+        tar.extend_from_slice(contents.as_bytes());
+        tar.resize(tar.len().next_multiple_of(TAR_BLOCK), 0);
+    }
+    // the end of the archive is marked by two zero blocks
+    tar.resize(tar.len() + 2 * TAR_BLOCK, 0);
+    tar
+}
+
+/// Reads the regular files of a tar archive as written by [`to_tar`], as (name, contents) pairs.
+fn from_tar(tar: &[u8]) -> Result<Vec<(String, String)>, BundleError> {
+    let text = |field: &[u8]| -> Option<String> {
+        let end = field
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(field.len());
+        String::from_utf8(field[..end].to_vec()).ok()
+    };
+    let mut files = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = tar.get(offset..offset + TAR_BLOCK) {
+        if header.iter().all(|byte| *byte == 0) {
+            return Ok(files);
+        }
+        let size =
+            text(&header[124..136]).and_then(|size| usize::from_str_radix(size.trim(), 8).ok());
+        let checksum =
+            text(&header[148..156]).and_then(|sum| usize::from_str_radix(sum.trim(), 8).ok());
+        let (Some(name), Some(size)) = (text(&header[..100]), size) else {
+            return Err(BundleError::NotAnArchive);
+        };
+        if &header[257..262] != b"ustar" || checksum != Some(tar_checksum(header)) {
+            return Err(BundleError::NotAnArchive);
+        }
+        let start = offset + TAR_BLOCK;
+        let contents = tar
+            .get(start..start + size)
+            .ok_or(BundleError::NotAnArchive)?;
+        // only regular files are read, other entries such as directories are skipped
+        if matches!(header[156], b'0' | 0) {
+            let contents =
+                String::from_utf8(contents.to_vec()).map_err(|_| BundleError::NotAnArchive)?;
+            files.push((name, contents));
+        }
+        offset = start + size.next_multiple_of(TAR_BLOCK);
+    }
+    Err(BundleError::NotAnArchive)
+}
+
+/// Returns the entries as the lines of a JSON object, `{}` if there are none.
+fn json_object(entries: &[String]) -> String {
+    match entries.is_empty() {
+        true => "{}\n".to_string(),
+        false => format!("{{\n  {}\n}}\n", entries.join(",\n  ")),
+    }
+}
+
+/// Returns the (1-based) line numbers and the entries of a JSON object written by [`json_object`],
+/// without the trailing commas.
+fn json_object_entries(json: &str) -> impl Iterator<Item = (usize, &str)> {
+    json.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim().trim_end_matches(',')))
+        .filter(|(_, line)| !matches!(*line, "" | "{" | "}" | "{}"))
+}
+
+/// Returns the (key, value) pairs of a JSON object of strings written by [`json_object`].
+fn parse_string_entries(
+    json: &str,
+    file: &'static str,
+) -> Result<Vec<(String, String)>, BundleError> {
+    json_object_entries(json)
+        .map(|(line, entry)| {
+            parse_json_string(entry)
+                .and_then(|(key, rest)| Some((key, parse_json_string(rest.strip_prefix(": ")?)?)))
+                .filter(|(_, (_, rest))| rest.is_empty())
+                .map(|(key, (value, _))| (key, value))
+                .ok_or(BundleError::Malformed { file, line })
+        })
+        .collect()
+}
+
+/// Writes a bundle of the `truth` and the `guess` DAG or CPDAG, the `options` of the evaluation,
+/// such as `("edge_direction", "from row to column")`, and the reported `results` as a tar archive
+/// to the file at `path`, so that [`verify_bundle`] can later recompute and check the results.
+/// Returns an error if a distance is reported twice or the file cannot be written.
+pub fn export_bundle(
+    path: impl AsRef<Path>,
+    truth: &PDAG,
+    guess: &PDAG,
+    options: &[(&str, &str)],
+    results: &[(Distance, (f64, usize))],
+) -> Result<(), BundleError> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    for (i, (distance, _)) in results.iter().enumerate() {
+        if results[..i].iter().any(|(other, _)| other == distance) {
+            return Err(BundleError::DuplicateDistance(*distance));
+        }
+    }
+
+    let (truth_mtx, guess_mtx) = (to_mtx(truth), to_mtx(guess));
+    let manifest = json_object(&[
+        format!(r#""format_version": "{BUNDLE_FORMAT_VERSION}""#),
+        format!(r#""gadjid_version": "{}""#, env!("CARGO_PKG_VERSION")),
+        r#""edge_encoding": "row -> column is 1, row -- column is 2""#.to_string(),
+        format!(r#""truth_fingerprint": "{}""#, fingerprint(&truth_mtx)),
+        format!(r#""guess_fingerprint": "{}""#, fingerprint(&guess_mtx)),
+    ]);
+    let options =
+        json_object(&Vec::from_iter(options.iter().map(|(name, value)| {
+            format!("{}: {}", json_string(name), json_string(value))
+        })));
+    let results = json_object(&Vec::from_iter(results.iter().map(
+        |(distance, (normalized, total))| {
+            format!(r#""{}": [{normalized}, {total}]"#, distance.name())
+        },
+    )));
+
+    fs::write(
+        path,
+        to_tar(&[
+            ("manifest.json", &manifest),
+            ("options.json", &options),
+            ("results.json", &results),
+            ("truth.mtx", &truth_mtx),
+            ("guess.mtx", &guess_mtx),
+        ]),
+    )?;
+    Ok(())
+}
+
+/// Verifies the bundle in the file at `path`, as written by [`export_bundle`],
+/// by checking its format version and the fingerprints of its graphs and recomputing its reported distances.
+/// Returns the distances whose recomputed value differs, or an error if the bundle cannot be read,
+/// was written in another format version, has a graph of fewer than 2 nodes,
+/// or a graph was changed after exporting.
+pub fn verify_bundle(path: impl AsRef<Path>) -> Result<BundleVerification, BundleError> {
+    let mut files = from_tar(&fs::read(path)?)?;
+    let mut take = |file: &'static str| -> Result<String, BundleError> {
+        let position = files.iter().position(|(name, _)| name == file);
+        let (_, contents) = files.swap_remove(position.ok_or(BundleError::MissingFile { file })?);
+        Ok(contents)
+    };
+    let manifest = parse_string_entries(&take("manifest.json")?, "manifest.json")?;
+    let manifest_value = |key: &str| -> Result<&str, BundleError> {
+        manifest
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value.as_str())
+            .ok_or(BundleError::Malformed {
+                file: "manifest.json",
+                line: 0,
+            })
+    };
+    let format_version = manifest_value("format_version")?;
+    if format_version != BUNDLE_FORMAT_VERSION.to_string() {
+        return Err(BundleError::UnsupportedFormatVersion {
+            found: format_version.to_string(),
+        });
+    }
+
+    let mut graphs = Vec::with_capacity(2);
+    for (file, key) in [
+        ("truth.mtx", "truth_fingerprint"),
+        ("guess.mtx", "guess_fingerprint"),
+    ] {
+        let mtx = take(file)?;
+        if fingerprint(&mtx) != manifest_value(key)? {
+            return Err(BundleError::FingerprintMismatch { file });
+        }
+        let graph = from_mtx(&mtx, file)?;
+        if graph.n_nodes < 2 {
+            return Err(BundleError::TooFewNodes {
+                file,
+                n_nodes: graph.n_nodes,
+            });
+        }
+        graphs.push(graph);
+    }
+    let (truth, guess) = (&graphs[0], &graphs[1]);
+    if truth.n_nodes != guess.n_nodes {
+        return Err(BundleError::Malformed {
+            file: "guess.mtx",
+            line: 2,
+        });
+    }
+
+    let options = parse_string_entries(&take("options.json")?, "options.json")?;
+    let results = take("results.json")?;
+    let mut reported = Vec::new();
+    for (line, entry) in json_object_entries(&results) {
+        let parsed = entry.split_once(": ").and_then(|(name, value)| {
+            let distance = Distance::from_name(name.strip_prefix('"')?.strip_suffix('"')?)?;
+            let value = value.strip_prefix('[')?.strip_suffix(']')?;
+            let (normalized, total) = value.split_once(", ")?;
+            Some((distance, (normalized.parse().ok()?, total.parse().ok()?)))
+        });
+        match parsed {
+            Some(result) => reported.push(result),
+            None => {
+                return Err(BundleError::Malformed {
+                    file: "results.json",
+                    line,
+                })
+            }
+        }
+    }
+
+    let mut mismatches = Vec::new();
+    for (distance, reported) in reported.iter().copied() {
+        let recomputed = distance.compute(truth, guess);
+        if recomputed != reported {
+            mismatches.push(Mismatch {
+                distance,
+                reported,
+                recomputed,
+            });
+        }
+    }
+    Ok(BundleVerification {
+        exported_with: manifest_value("gadjid_version")?.to_string(),
+        options,
+        verified: Vec::from_iter(reported.into_iter().map(|(distance, _)| distance)),
+        mismatches,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::PDAG;
+
+    use super::{
+        export_bundle, fingerprint, from_mtx, from_tar, to_mtx, to_tar, verify_bundle, BundleError,
+        Distance,
+    };
+
+    /// A path in the temporary directory that is unique to this process and the `name` of the test.
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("gadjid-{name}-{}.tar", std::process::id()))
+    }
+
+    #[test]
+    fn exported_bundles_verify() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_pdag(0.3, 12, &mut rng);
+        let guess = PDAG::random_pdag(0.3, 12, &mut rng);
+        let results = Vec::from_iter(
+            Distance::ALL
+                .into_iter()
+                .map(|distance| (distance, distance.compute(&truth, &guess))),
+        );
+        let options = [
+            ("edge_direction", "from row to column"),
+            ("note", "a \"quoted\" välue: \\ \u{1}"),
+        ];
+
+        let path = temp_path("bundle");
+        export_bundle(&path, &truth, &guess, &options, &results).unwrap();
+        let verification = verify_bundle(&path).unwrap();
+        assert!(verification.is_reproduced());
+        assert_eq!(verification.verified, Distance::ALL.to_vec());
+        assert_eq!(verification.exported_with, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            verification.options,
+            Vec::from_iter(
+                options
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string()))
+            )
+        );
+
+        // a wrongly reported distance is not reproduced
+        let (normalized, total) = results[1].1;
+        let misreported = [(Distance::OsetAid, (normalized, total + 1))];
+        export_bundle(&path, &truth, &guess, &options, &misreported).unwrap();
+        let verification = verify_bundle(&path).unwrap();
+        assert_eq!(verification.mismatches.len(), 1);
+        assert_eq!(verification.mismatches[0].recomputed, (normalized, total));
+
+        // changing a graph after exporting is detected
+        let mut files = from_tar(&std::fs::read(&path).unwrap()).unwrap();
+        let truth_mtx = to_mtx(&truth);
+        files
+            .iter_mut()
+            .find(|(name, _)| name == "guess.mtx")
+            .unwrap()
+            .1 = truth_mtx;
+        let files = Vec::from_iter(
+            files
+                .iter()
+                .map(|(name, contents)| (name.as_str(), contents.as_str())),
+        );
+        std::fs::write(&path, to_tar(&files)).unwrap();
+        assert!(matches!(
+            verify_bundle(&path),
+            Err(BundleError::FingerprintMismatch { file: "guess.mtx" })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bundles_without_results_or_options_verify() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let truth = PDAG::random_pdag(0.3, 5, &mut rng);
+        let guess = PDAG::random_pdag(0.3, 5, &mut rng);
+
+        let path = temp_path("empty-bundle");
+        export_bundle(&path, &truth, &guess, &[], &[]).unwrap();
+        let verification = verify_bundle(&path).unwrap();
+        assert!(verification.is_reproduced());
+        assert!(verification.verified.is_empty());
+        assert!(verification.options.is_empty());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bundles_of_other_format_versions_are_rejected() {
+        let manifest = "{\n  \"format_version\": \"1\"\n}\n";
+        let path = temp_path("old-bundle");
+        std::fs::write(&path, to_tar(&[("manifest.json", manifest)])).unwrap();
+        assert!(matches!(
+            verify_bundle(&path),
+            Err(BundleError::UnsupportedFormatVersion { found }) if found == "1"
+        ));
+
+        std::fs::write(&path, "not a tar archive").unwrap();
+        assert!(matches!(
+            verify_bundle(&path),
+            Err(BundleError::NotAnArchive)
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn bundles_of_single_node_graphs_are_rejected() {
+        let mtx = "%%MatrixMarket matrix coordinate integer general\n1 1 0\n";
+        let manifest = format!(
+            "{{\n  \"format_version\": \"2\",\n  \"truth_fingerprint\": \"{0}\",\n  \"guess_fingerprint\": \"{0}\"\n}}\n",
+            fingerprint(mtx)
+        );
+        let results = "{\n  \"shd\": [0, 0]\n}\n";
+        let path = temp_path("single-node-bundle");
+        std::fs::write(
+            &path,
+            to_tar(&[
+                ("manifest.json", &manifest),
+                ("options.json", "{}\n"),
+                ("results.json", results),
+                ("truth.mtx", mtx),
+                ("guess.mtx", mtx),
+            ]),
+        )
+        .unwrap();
+        assert!(matches!(
+            verify_bundle(&path),
+            Err(BundleError::TooFewNodes {
+                file: "truth.mtx",
+                n_nodes: 1
+            })
+        ));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn property_tar_roundtrip() {
+        let files = [
+            ("empty.json", ""),
+            ("block.mtx", &"0123456789abcdef".repeat(32) as &str),
+            (
+                "options.json",
+                "{\n  \"name\": \"välue \\\" with quotes\"\n}\n",
+            ),
+        ];
+        let tar = to_tar(&files);
+        assert_eq!(tar.len() % 512, 0);
+        let read = from_tar(&tar).unwrap();
+        assert_eq!(
+            Vec::from_iter(
+                read.iter()
+                    .map(|(name, contents)| (name.as_str(), contents.as_str()))
+            ),
+            files.to_vec()
+        );
+    }
+
+    #[test]
+    fn property_mtx_roundtrip() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 1..30 {
+            let pdag = PDAG::random_pdag(0.3, n, &mut rng);
+            let loaded = from_mtx(&to_mtx(&pdag), "graph.mtx").unwrap();
+            assert_eq!(
+                loaded.to_row_to_column_vecvec(),
+                pdag.to_row_to_column_vecvec()
+            );
+        }
+    }
+}
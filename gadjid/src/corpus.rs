@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: MPL-2.0
+//! The test graphs shipped in `testgraphs/`, bundled into the library so that the documented example
+//! numbers and the test suites of the bindings can be reproduced exactly without a checkout of the repository.
+//!
+//! The corpus holds ten random DAGs with 10 nodes (ids 10 to 19) and ten with 100 nodes (ids 20 to 29),
+//! each alongside its CPDAG with the same id, named like the files, for example `10-node-DAG-10`
+//! and `10-node-CPDAG-10`.
+//!
+//! ```
+//! use gadjid::{corpus, graph_operations::cpdag_of};
+//!
+//! assert_eq!(corpus::GRAPHS.len(), 40);
+//! let dag = corpus::load("10-node-DAG-10").unwrap();
+//! let cpdag = corpus::load("10-node-CPDAG-10").unwrap();
+//! assert_eq!(cpdag_of(&dag), cpdag);
+//! assert!(corpus::load("10-node-DAG-30").is_none());
+//! ```
+
+use crate::{EdgelistIterator, PDAG};
+
+/// Whether a graph of the corpus is a DAG or the CPDAG of the DAG with the same id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GraphKind {
+    /// a directed acyclic graph
+    DAG,
+    /// the completed partially directed acyclic graph of a DAG
+    CPDAG,
+}
+
+/// A graph of the corpus.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CorpusGraph {
+    /// the name of the graph, which is its file name in `testgraphs/` without the `.mtx` extension
+    pub name: &'static str,
+    /// the number of nodes of the graph
+    pub n_nodes: usize,
+    /// whether the graph is a DAG or a CPDAG
+    pub kind: GraphKind,
+    /// the id of the graph, shared by a DAG and its CPDAG
+    pub id: usize,
+    /// the contents of the Matrix Market file of the graph
+    pub mtx: &'static str,
+}
+
+impl CorpusGraph {
+    /// Loads the graph.
+    pub fn load(&self) -> PDAG {
+        parse_mtx(self.mtx).expect("graphs of the corpus are valid")
+    }
+}
+
+macro_rules! corpus_graph {
+    ($n_nodes:literal, $kind:ident, $id:literal) => {
+        CorpusGraph {
+            name: concat!(
+                stringify!($n_nodes),
+                "-node-",
+                stringify!($kind),
+                "-",
+                stringify!($id)
+            ),
+            n_nodes: $n_nodes,
+            kind: GraphKind::$kind,
+            id: $id,
+            mtx: include_str!(concat!(
+                "../../testgraphs/",
+                stringify!($n_nodes),
+                "-node-",
+                stringify!($kind),
+                "-",
+                stringify!($id),
+                ".mtx"
+            )),
+        }
+    };
+}
+
+/// All graphs of the corpus, the DAGs before the CPDAGs, by number of nodes and id.
+pub const GRAPHS: &[CorpusGraph] = &[
+    corpus_graph!(10, DAG, 10),
+    corpus_graph!(10, DAG, 11),
+    corpus_graph!(10, DAG, 12),
+    corpus_graph!(10, DAG, 13),
+    corpus_graph!(10, DAG, 14),
+    corpus_graph!(10, DAG, 15),
+    corpus_graph!(10, DAG, 16),
+    corpus_graph!(10, DAG, 17),
+    corpus_graph!(10, DAG, 18),
+    corpus_graph!(10, DAG, 19),
+    corpus_graph!(100, DAG, 20),
+    corpus_graph!(100, DAG, 21),
+    corpus_graph!(100, DAG, 22),
+    corpus_graph!(100, DAG, 23),
+    corpus_graph!(100, DAG, 24),
+    corpus_graph!(100, DAG, 25),
+    corpus_graph!(100, DAG, 26),
+    corpus_graph!(100, DAG, 27),
+    corpus_graph!(100, DAG, 28),
+    corpus_graph!(100, DAG, 29),
+    corpus_graph!(10, CPDAG, 10),
+    corpus_graph!(10, CPDAG, 11),
+    corpus_graph!(10, CPDAG, 12),
+    corpus_graph!(10, CPDAG, 13),
+    corpus_graph!(10, CPDAG, 14),
+    corpus_graph!(10, CPDAG, 15),
+    corpus_graph!(10, CPDAG, 16),
+    corpus_graph!(10, CPDAG, 17),
+    corpus_graph!(10, CPDAG, 18),
+    corpus_graph!(10, CPDAG, 19),
+    corpus_graph!(100, CPDAG, 20),
+    corpus_graph!(100, CPDAG, 21),
+    corpus_graph!(100, CPDAG, 22),
+    corpus_graph!(100, CPDAG, 23),
+    corpus_graph!(100, CPDAG, 24),
+    corpus_graph!(100, CPDAG, 25),
+    corpus_graph!(100, CPDAG, 26),
+    corpus_graph!(100, CPDAG, 27),
+    corpus_graph!(100, CPDAG, 28),
+    corpus_graph!(100, CPDAG, 29),
+];
+
+/// Returns the graph of the corpus with the given name, like `10-node-DAG-10`, if there is one.
+pub fn find(name: &str) -> Option<&'static CorpusGraph> {
+    GRAPHS.iter().find(|graph| graph.name == name)
+}
+
+/// Loads the graph of the corpus with the given name, like `10-node-DAG-10`, if there is one.
+pub fn load(name: &str) -> Option<PDAG> {
+    find(name).map(CorpusGraph::load)
+}
+
+/// Parses a PDAG from a 1-indexed Matrix Market file, either a pattern file with the directed edges
+/// of a DAG or an integer file with edge codes (1 for a directed, 2 for an undirected edge).
+pub(crate) fn parse_mtx(mtx: &str) -> Result<PDAG, String> {
+    let mut lines = mtx.lines().filter(|line| !line.starts_with('%'));
+
+    let header = lines.next().ok_or("missing dimensions")?;
+    let dims = Vec::from_iter(header.split_whitespace().map(str::parse::<usize>));
+    let n_nodes = match dims.as_slice() {
+        [Ok(rows), Ok(cols), Ok(_)] if rows == cols => *rows,
+        _ => return Err("malformed dimensions".to_string()),
+    };
+
+    let mut edges = Vec::new();
+    for line in lines {
+        let mut entries = line.split_whitespace();
+        let mut next_index = || {
+            entries
+                .next()
+                .and_then(|entry| entry.parse::<usize>().ok())
+                .filter(|index| (1..=n_nodes).contains(index))
+        };
+        let (Some(x), Some(y)) = (next_index(), next_index()) else {
+            return Err("malformed entry".to_string());
+        };
+        let edge_code = match entries.next().map(str::parse::<i8>) {
+            None => 1,
+            Some(Ok(code @ (1 | 2))) => code,
+            Some(_) => return Err("malformed edge code".to_string()),
+        };
+        edges.push((x - 1, y - 1, edge_code));
+    }
+    edges.sort();
+    PDAG::try_from_row_major(edges.into_iter().into_row_major_edgelist(n_nodes))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph_operations::{cpdag_of, parent_aid, shd};
+
+    use super::{find, load, parse_mtx, GraphKind, GRAPHS};
+
+    #[test]
+    fn bundles_every_testgraph() {
+        // anchors at parent directory of Cargo.toml
+        let testgraphs = std::path::PathBuf::from("..").join("testgraphs");
+        let mut files = Vec::from_iter(
+            std::fs::read_dir(&testgraphs)
+                .unwrap()
+                .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+                .filter_map(|file| file.strip_suffix(".mtx").map(str::to_string)),
+        );
+        files.sort();
+        let mut names = Vec::from_iter(GRAPHS.iter().map(|graph| graph.name.to_string()));
+        names.sort();
+        assert_eq!(names, files);
+
+        for graph in GRAPHS {
+            let path = testgraphs.join(format!("{}.mtx", graph.name));
+            let loaded = crate::test::load_pdag_from_mtx(path.to_str().unwrap());
+            assert_eq!(graph.load(), loaded);
+            assert_eq!(loaded.n_nodes, graph.n_nodes);
+            assert_eq!(find(graph.name), Some(graph));
+        }
+    }
+
+    #[test]
+    fn cpdags_belong_to_the_dags_with_the_same_id() {
+        for dag in GRAPHS.iter().filter(|graph| graph.kind == GraphKind::DAG) {
+            let name = dag.name.replace("DAG", "CPDAG");
+            assert_eq!(find(&name).unwrap().id, dag.id);
+            assert_eq!(cpdag_of(&dag.load()), load(&name).unwrap());
+        }
+    }
+
+    #[test]
+    fn reproduces_reference_numbers() {
+        // first row of testgraphs/SID-10-node-DAGs.csv
+        let truth = load("10-node-DAG-11").unwrap();
+        let guess = load("10-node-DAG-10").unwrap();
+        assert_eq!(shd(&truth, &guess).1, 23);
+        assert_eq!(parent_aid(&truth, &guess).1, 35);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(parse_mtx("").is_err());
+        assert!(parse_mtx("%%MatrixMarket\n2 3 1\n1 2\n").is_err());
+        assert!(parse_mtx("%%MatrixMarket\n2 2 1\n1 3\n").is_err());
+        assert!(parse_mtx("%%MatrixMarket\n2 2 1\n1 2 3\n").is_err());
+        assert!(parse_mtx("%%MatrixMarket\n2 2 2\n1 2\n2 1\n").is_err());
+    }
+}
@@ -16,11 +16,13 @@ where
 {
     /// Converts a triple-iterator into an EdgelistIterator.
     /// Assumes that the iterator yields edges in `(row, column, edgetype)` order, with `row`
-    /// varying the slowest. Will panic otherwise.
+    /// varying the slowest. Will panic during iteration otherwise,
+    /// and makes loading return [`LoadError::NotOrdered`](crate::LoadError::NotOrdered).
     fn into_row_major_edgelist(self, size: usize) -> Edgelist<RowMajorOrder, I>;
     /// Converts a triple-iterator into an EdgelistIterator.
     /// Assumes that the iterator yields edges in `(column, row, edgetype)` order, with `column`
-    /// varying the slowest. Will panic otherwise.
+    /// varying the slowest. Will panic during iteration otherwise,
+    /// and makes loading return [`LoadError::NotOrdered`](crate::LoadError::NotOrdered).
     fn into_column_major_edgelist(self, size: usize) -> Edgelist<ColumnMajorOrder, I>;
 }
 
@@ -83,7 +85,10 @@ impl<T: IterationLayoutTag> Edgelist<T, ConversionFromVecVecToTriple> {
         vecvec: Vec<Vec<i8>>,
     ) -> Edgelist<T, impl Iterator<Item = (usize, usize, i8)>> {
         let size = vecvec.len();
-        assert!(size == vecvec[0].len(), "adjacency matrix must be square");
+        assert!(
+            vecvec.iter().all(|row| row.len() == size),
+            "adjacency matrix must be square"
+        );
 
         // ugly but necessary type annotations
         type OrderConverter = fn((usize, (usize, i8))) -> (usize, usize, i8);
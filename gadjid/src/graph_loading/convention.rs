@@ -0,0 +1,706 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Detects which adjacency-matrix coding convention(s) a dense matrix is consistent with,
+//! to guard against silently wrong distances caused by passing a matrix in a different coding.
+
+use std::{error::Error, fmt};
+
+use crate::{graph_loading::edgelist::Edgelist, options::NamedOption, LoadError, PDAG};
+
+/// Adjacency-matrix coding conventions that [`detect_convention`] checks a matrix against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Convention {
+    /// gadjid coding: a `1` codes a directed edge and a `2` an undirected edge (coded at `[i,j]` and/or `[j,i]`);
+    /// whether a `1` at `[i,j]` codes `i -> j` or `j -> i` depends on the edge direction.
+    Gadjid,
+    /// pcalg `amat.cpdag` coding: `amat[a,b] = 0` and `amat[b,a] = 1` codes `a -> b`,
+    /// `amat[a,b] = amat[b,a] = 1` codes `a -- b`.
+    PcalgAmatCpdag,
+    /// pcalg `amat.pag` coding: `amat[a,b]` codes the edge mark at `b`
+    /// (`0` no edge, `1` circle, `2` arrowhead, `3` tail),
+    /// so `amat[a,b] = 2` and `amat[b,a] = 3` codes `a -> b`.
+    PcalgAmatPag,
+    /// Symmetric 0/1 coding of adjacencies: `amat[a,b] = amat[b,a] = 1` codes `a -- b`.
+    SymmetricBool,
+}
+
+impl fmt::Display for Convention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Irregularities noticed while inspecting an adjacency matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConventionWarning {
+    /// The matrix has no rows.
+    Empty,
+    /// The matrix is not square, row `row` has length `len` instead of the number of rows.
+    NotSquare {
+        /// the offending row
+        row: usize,
+        /// its length
+        len: usize,
+    },
+    /// A nonzero entry on the diagonal.
+    NonZeroDiagonal {
+        /// the node with a self-loop
+        node: usize,
+        /// the value on the diagonal
+        value: i8,
+    },
+    /// A value not used by any of the known conventions.
+    UnexpectedValue {
+        /// row of the entry
+        row: usize,
+        /// column of the entry
+        col: usize,
+        /// the unexpected value
+        value: i8,
+    },
+    /// A `2` at `[row, col]` with a `0` at `[col, row]`;
+    /// fine in gadjid coding, but in any other coding a `2` has to be matched on the other side.
+    OneSidedUndirected {
+        /// row of the `2`
+        row: usize,
+        /// column of the `2`
+        col: usize,
+    },
+    /// Both `[row, col]` and `[col, row]` are coded as `1` or `2` but with differing values.
+    MixedPair {
+        /// row of the entry
+        row: usize,
+        /// column of the entry
+        col: usize,
+        /// the values at `[row, col]` and `[col, row]`
+        values: (i8, i8),
+    },
+}
+
+impl fmt::Display for ConventionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConventionWarning::Empty => write!(f, "matrix is empty"),
+            ConventionWarning::NotSquare { row, len } => {
+                write!(f, "matrix is not square, row {row} has length {len}")
+            }
+            ConventionWarning::NonZeroDiagonal { node, value } => {
+                write!(f, "found nonzero value '{value}' on the diagonal at ({node}, {node})")
+            }
+            ConventionWarning::UnexpectedValue { row, col, value } => {
+                write!(f, "found value '{value}' at ({row}, {col}), which no known convention uses")
+            }
+            ConventionWarning::OneSidedUndirected { row, col } => write!(
+                f,
+                "found '2' at ({row}, {col}) but '0' at ({col}, {row}), undirected edge is coded on one side only"
+            ),
+            ConventionWarning::MixedPair { row, col, values } => write!(
+                f,
+                "found '{}' at ({row}, {col}) but '{}' at ({col}, {row})",
+                values.0, values.1
+            ),
+        }
+    }
+}
+
+/// The result of inspecting an adjacency matrix with [`detect_convention`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConventionReport {
+    /// The conventions the matrix is consistent with, in the order in which they are declared in [`Convention`].
+    pub consistent: Vec<Convention>,
+    /// Irregularities found in the matrix.
+    pub warnings: Vec<ConventionWarning>,
+}
+
+/// Inspects a dense adjacency matrix and reports which coding conventions it is consistent with,
+/// alongside warnings about irregularities such as asymmetrically coded undirected edges.
+///
+/// Only the coding is checked, not whether the coded graph is acyclic.
+pub fn detect_convention(matrix: &[Vec<i8>]) -> ConventionReport {
+    let mut warnings = Vec::new();
+
+    let n = matrix.len();
+    if n == 0 {
+        warnings.push(ConventionWarning::Empty);
+    }
+    for (row, values) in matrix.iter().enumerate() {
+        if values.len() != n {
+            warnings.push(ConventionWarning::NotSquare {
+                row,
+                len: values.len(),
+            });
+        }
+    }
+    if !warnings.is_empty() {
+        return ConventionReport {
+            consistent: vec![],
+            warnings,
+        };
+    }
+
+    let (mut gadjid, mut amat_cpdag, mut amat_pag, mut symmetric_bool) = (true, true, true, true);
+
+    for (node, values) in matrix.iter().enumerate() {
+        if values[node] != 0 {
+            warnings.push(ConventionWarning::NonZeroDiagonal {
+                node,
+                value: values[node],
+            });
+        }
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (matrix[i][j], matrix[j][i]);
+
+            for (row, col, value) in [(i, j, a), (j, i, b)] {
+                if !(0..=3).contains(&value) {
+                    warnings.push(ConventionWarning::UnexpectedValue { row, col, value });
+                }
+            }
+
+            gadjid &= matches!((a, b), (0, 0..=2) | (1 | 2, 0) | (2, 2));
+            amat_cpdag &= matches!((a, b), (0 | 1, 0 | 1));
+            amat_pag &= matches!((a, b), (0, 0) | (1..=3, 1..=3));
+            symmetric_bool &= matches!((a, b), (0, 0) | (1, 1));
+
+            match (a, b) {
+                (2, 0) => warnings.push(ConventionWarning::OneSidedUndirected { row: i, col: j }),
+                (0, 2) => warnings.push(ConventionWarning::OneSidedUndirected { row: j, col: i }),
+                (1, 2) | (2, 1) => warnings.push(ConventionWarning::MixedPair {
+                    row: i,
+                    col: j,
+                    values: (a, b),
+                }),
+                _ => (),
+            }
+        }
+    }
+
+    let diagonal_is_zero = (0..n).all(|node| matrix[node][node] == 0);
+    let consistent = [
+        (Convention::Gadjid, gadjid),
+        (Convention::PcalgAmatCpdag, amat_cpdag),
+        (Convention::PcalgAmatPag, amat_pag),
+        (Convention::SymmetricBool, symmetric_bool),
+    ]
+    .into_iter()
+    .filter(|(_, is_consistent)| diagonal_is_zero && *is_consistent)
+    .map(|(convention, _)| convention)
+    .collect();
+
+    ConventionReport {
+        consistent,
+        warnings,
+    }
+}
+
+#[derive(Debug)]
+/// Errors that can occur when loading a matrix according to a (detected) convention.
+pub enum ConventionError {
+    /// The matrix is not consistent with any known convention, see the warnings for why.
+    NoConsistentConvention(Vec<ConventionWarning>),
+    /// The matrix is consistent with several conventions that would load it as different graphs.
+    Ambiguous(Vec<Convention>),
+    /// The matrix codes an edge that cannot be represented in a DAG or CPDAG,
+    /// such as an edge with a circle mark in a PAG.
+    NotRepresentable {
+        /// the convention the matrix was read in
+        convention: Convention,
+        /// row of the offending entry
+        row: usize,
+        /// column of the offending entry
+        col: usize,
+    },
+    /// The matrix is not consistent with the requested convention.
+    Inconsistent(Convention),
+    /// The coded graph could not be loaded, for example, because it is not acyclic.
+    Load(LoadError),
+}
+
+impl fmt::Display for ConventionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConventionError::NoConsistentConvention(warnings) => {
+                write!(f, "matrix is not consistent with any known coding convention")?;
+                for warning in warnings {
+                    write!(f, "\n- {warning}")?;
+                }
+                Ok(())
+            }
+            ConventionError::Ambiguous(conventions) => {
+                write!(
+                    f,
+                    "matrix is consistent with several coding conventions that code different graphs: "
+                )?;
+                let names: Vec<String> = conventions.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", names.join(", "))
+            }
+            ConventionError::NotRepresentable {
+                convention,
+                row,
+                col,
+            } => write!(
+                f,
+                "edge at ({row}, {col}) in {convention} coding cannot be represented in a DAG or CPDAG"
+            ),
+            ConventionError::Inconsistent(convention) => {
+                write!(f, "matrix is not consistent with {convention} coding")
+            }
+            ConventionError::Load(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for ConventionError {}
+
+/// Loads a dense adjacency matrix coded in the given convention into a PDAG.
+/// The `row_to_col` edge direction is only used for [`Convention::Gadjid`] coded matrices,
+/// the other conventions fix the edge direction.
+pub fn load_with_convention(
+    matrix: &[Vec<i8>],
+    convention: Convention,
+    row_to_col: bool,
+) -> Result<PDAG, ConventionError> {
+    if !detect_convention(matrix).consistent.contains(&convention) {
+        return Err(ConventionError::Inconsistent(convention));
+    }
+
+    // translate into gadjid coding from row to column
+    let n = matrix.len();
+    let mut dense = vec![vec![0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (matrix[i][j], matrix[j][i]);
+            let (forward, backward) = match convention {
+                Convention::Gadjid if row_to_col => (a, b),
+                Convention::Gadjid => (b, a),
+                Convention::PcalgAmatCpdag => match (a, b) {
+                    (1, 1) => (2, 0),
+                    _ => (b, a),
+                },
+                Convention::PcalgAmatPag => match (a, b) {
+                    (0, 0) => (0, 0),
+                    (2, 3) => (1, 0),
+                    (3, 2) => (0, 1),
+                    (3, 3) => (2, 0),
+                    _ => {
+                        return Err(ConventionError::NotRepresentable {
+                            convention,
+                            row: i,
+                            col: j,
+                        })
+                    }
+                },
+                Convention::SymmetricBool => (2 * a, 0),
+            };
+            dense[i][j] = forward;
+            dense[j][i] = backward;
+        }
+    }
+
+    PDAG::try_from_row_major(Edgelist::from_vecvec(dense)).map_err(ConventionError::Load)
+}
+
+/// Detects the coding convention of a dense adjacency matrix via [`detect_convention`] and,
+/// if all conventions the matrix is consistent with agree on the coded graph, loads it.
+/// The `row_to_col` edge direction is only used for matrices consistent with [`Convention::Gadjid`] coding.
+///
+/// Returns the loaded graph together with the report, so warnings can be surfaced to the user.
+pub fn load_with_detected_convention(
+    matrix: &[Vec<i8>],
+    row_to_col: bool,
+) -> Result<(PDAG, ConventionReport), ConventionError> {
+    let report = detect_convention(matrix);
+    if report.consistent.is_empty() {
+        return Err(ConventionError::NoConsistentConvention(report.warnings));
+    }
+
+    let mut loaded: Vec<(Convention, PDAG)> = Vec::new();
+    let mut first_error = None;
+    for convention in report.consistent.iter().copied() {
+        match load_with_convention(matrix, convention, row_to_col) {
+            Ok(pdag) => loaded.push((convention, pdag)),
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    match loaded.split_first() {
+        None => Err(first_error.expect("at least one convention was tried")),
+        Some(((_, pdag), others)) => {
+            if others.iter().any(|(_, other)| other != pdag) {
+                return Err(ConventionError::Ambiguous(
+                    loaded
+                        .into_iter()
+                        .map(|(convention, _)| convention)
+                        .collect(),
+                ));
+            }
+            let (_, pdag) = loaded.swap_remove(0);
+            Ok((pdag, report))
+        }
+    }
+}
+
+/// A change made by [`sanitize_adjacency`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdjacencyFix {
+    /// A nonzero entry on the diagonal was set to `0`.
+    ZeroedDiagonal {
+        /// the node with a self-loop
+        node: usize,
+        /// the value that was on the diagonal
+        value: i8,
+    },
+    /// An undirected edge coded on one side only was coded on both sides,
+    /// by copying the `value` at `[row, col]` to `[col, row]`.
+    Symmetrized {
+        /// row of the coded side
+        row: usize,
+        /// column of the coded side
+        col: usize,
+        /// the code of the undirected edge
+        value: i8,
+    },
+}
+
+impl fmt::Display for AdjacencyFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdjacencyFix::ZeroedDiagonal { node, value } => {
+                write!(f, "set value '{value}' on the diagonal at ({node}, {node}) to '0'")
+            }
+            AdjacencyFix::Symmetrized { row, col, value } => write!(
+                f,
+                "copied '{value}' at ({row}, {col}) to ({col}, {row}), undirected edge was coded on one side only"
+            ),
+        }
+    }
+}
+
+/// An adjacency matrix fixed by [`sanitize_adjacency`], alongside the changes made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SanitizedAdjacency {
+    /// the fixed matrix
+    pub matrix: Vec<Vec<i8>>,
+    /// the changes made, in row-major order of the changed entries
+    pub fixes: Vec<AdjacencyFix>,
+}
+
+/// Fixes the irregularities of a dense adjacency matrix coded in the given `convention`
+/// that have an unambiguous fix, so that it loads: sets nonzero entries on the diagonal to `0`
+/// and, in [`Convention::Gadjid`] and [`Convention::SymmetricBool`] coding, copies the code of
+/// an undirected edge that is coded on one side only, a `2` or a `1` respectively, to the other side.
+/// All other entries are kept, so matrices that are inconsistent with the convention for other reasons,
+/// such as a `1` and a `2` coding the same pair, still fail to load.
+/// Returns [`ConventionError::NoConsistentConvention`] if the matrix is not square.
+///
+/// ```
+/// use gadjid::{sanitize_adjacency, AdjacencyFix, Convention};
+///
+/// // 0 -- 1 coded on one side only, and a self-loop at 2
+/// let matrix = vec![
+///     vec![0, 2, 0], //
+///     vec![0, 0, 1],
+///     vec![0, 0, 1],
+/// ];
+/// let sanitized = sanitize_adjacency(&matrix, Convention::Gadjid).unwrap();
+/// assert_eq!(sanitized.matrix, vec![vec![0, 2, 0], vec![2, 0, 1], vec![0, 0, 0]]);
+/// assert_eq!(
+///     sanitized.fixes,
+///     vec![
+///         AdjacencyFix::Symmetrized { row: 0, col: 1, value: 2 },
+///         AdjacencyFix::ZeroedDiagonal { node: 2, value: 1 },
+///     ]
+/// );
+/// ```
+pub fn sanitize_adjacency(
+    matrix: &[Vec<i8>],
+    convention: Convention,
+) -> Result<SanitizedAdjacency, ConventionError> {
+    let n = matrix.len();
+    let not_square = Vec::from_iter(matrix.iter().enumerate().filter_map(|(row, values)| {
+        (values.len() != n).then_some(ConventionWarning::NotSquare {
+            row,
+            len: values.len(),
+        })
+    }));
+    if !not_square.is_empty() {
+        return Err(ConventionError::NoConsistentConvention(not_square));
+    }
+
+    // the code of an undirected edge that may be completed on the other side
+    let undirected = match convention {
+        Convention::Gadjid => Some(2),
+        Convention::SymmetricBool => Some(1),
+        Convention::PcalgAmatCpdag | Convention::PcalgAmatPag => None,
+    };
+    let mut fixed = matrix.to_vec();
+    let mut fixes = Vec::new();
+    for row in 0..n {
+        for col in 0..n {
+            let value = matrix[row][col];
+            if row == col && value != 0 {
+                fixed[row][col] = 0;
+                fixes.push(AdjacencyFix::ZeroedDiagonal { node: row, value });
+            } else if row != col && Some(value) == undirected && matrix[col][row] == 0 {
+                fixed[col][row] = value;
+                fixes.push(AdjacencyFix::Symmetrized { row, col, value });
+            }
+        }
+    }
+    Ok(SanitizedAdjacency {
+        matrix: fixed,
+        fixes,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::PDAG;
+
+    use super::{
+        detect_convention, load_with_convention, load_with_detected_convention, sanitize_adjacency,
+        AdjacencyFix, Convention, ConventionError, ConventionWarning,
+    };
+
+    #[test]
+    fn detects_conventions() {
+        // 0 -> 1 -- 2 in gadjid coding from row to column
+        let gadjid = vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 2, 0],
+        ];
+        let report = detect_convention(&gadjid);
+        assert_eq!(report.consistent, vec![Convention::Gadjid]);
+        assert!(report.warnings.is_empty());
+
+        // a DAG coded with 0/1 can be read in gadjid and pcalg amat.cpdag coding
+        let dag = vec![
+            vec![0, 1, 1], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ];
+        let report = detect_convention(&dag);
+        assert_eq!(
+            report.consistent,
+            vec![Convention::Gadjid, Convention::PcalgAmatCpdag]
+        );
+
+        let skeleton = vec![
+            vec![0, 1, 0], //
+            vec![1, 0, 1],
+            vec![0, 1, 0],
+        ];
+        let report = detect_convention(&skeleton);
+        assert_eq!(
+            report.consistent,
+            vec![
+                Convention::PcalgAmatCpdag,
+                Convention::PcalgAmatPag,
+                Convention::SymmetricBool
+            ]
+        );
+
+        // 0 -> 1 <-> 2 in pcalg amat.pag coding
+        let pag = vec![
+            vec![0, 2, 0], //
+            vec![3, 0, 2],
+            vec![0, 2, 0],
+        ];
+        let report = detect_convention(&pag);
+        assert_eq!(report.consistent, vec![Convention::PcalgAmatPag]);
+    }
+
+    #[test]
+    fn warns_about_irregularities() {
+        let matrix = vec![
+            vec![1, 2, 1], //
+            vec![0, 0, 2],
+            vec![2, 0, 0],
+        ];
+        let report = detect_convention(&matrix);
+        assert!(report.consistent.is_empty());
+        assert_eq!(
+            report.warnings,
+            vec![
+                ConventionWarning::NonZeroDiagonal { node: 0, value: 1 },
+                ConventionWarning::OneSidedUndirected { row: 0, col: 1 },
+                ConventionWarning::MixedPair {
+                    row: 0,
+                    col: 2,
+                    values: (1, 2)
+                },
+                ConventionWarning::OneSidedUndirected { row: 1, col: 2 },
+            ]
+        );
+
+        let matrix = vec![vec![0, 5], vec![0, 0]];
+        let report = detect_convention(&matrix);
+        assert!(report.consistent.is_empty());
+        assert_eq!(
+            report.warnings,
+            vec![ConventionWarning::UnexpectedValue {
+                row: 0,
+                col: 1,
+                value: 5
+            }]
+        );
+
+        let matrix = vec![vec![0, 0], vec![0]];
+        let report = detect_convention(&matrix);
+        assert!(report.consistent.is_empty());
+        assert_eq!(
+            report.warnings,
+            vec![ConventionWarning::NotSquare { row: 1, len: 1 }]
+        );
+    }
+
+    #[test]
+    fn loads_in_all_conventions() {
+        // 0 -> 1 -- 2
+        let expected = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 0],
+        ]);
+
+        let gadjid_col_to_row = vec![
+            vec![0, 0, 0], //
+            vec![1, 0, 2],
+            vec![0, 0, 0],
+        ];
+        assert_eq!(
+            expected,
+            load_with_convention(&gadjid_col_to_row, Convention::Gadjid, false).unwrap()
+        );
+
+        let amat_cpdag = vec![
+            vec![0, 0, 0], //
+            vec![1, 0, 1],
+            vec![0, 1, 0],
+        ];
+        assert_eq!(
+            expected,
+            load_with_convention(&amat_cpdag, Convention::PcalgAmatCpdag, true).unwrap()
+        );
+
+        let amat_pag = vec![
+            vec![0, 2, 0], //
+            vec![3, 0, 3],
+            vec![0, 3, 0],
+        ];
+        assert_eq!(
+            expected,
+            load_with_convention(&amat_pag, Convention::PcalgAmatPag, true).unwrap()
+        );
+
+        // circle marks cannot be represented
+        let amat_pag = vec![
+            vec![0, 1], //
+            vec![2, 0],
+        ];
+        assert!(matches!(
+            load_with_convention(&amat_pag, Convention::PcalgAmatPag, true),
+            Err(ConventionError::NotRepresentable { .. })
+        ));
+
+        assert!(matches!(
+            load_with_convention(&amat_pag, Convention::Gadjid, true),
+            Err(ConventionError::Inconsistent(Convention::Gadjid))
+        ));
+    }
+
+    #[test]
+    fn proceeds_only_if_unambiguous() {
+        let dag = vec![
+            vec![0, 1, 1], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ];
+        // gadjid from row to column and pcalg amat.cpdag read the edges in opposite directions
+        assert!(matches!(
+            load_with_detected_convention(&dag, true),
+            Err(ConventionError::Ambiguous(_))
+        ));
+        // gadjid from column to row and pcalg amat.cpdag agree
+        let (pdag, report) = load_with_detected_convention(&dag, false).unwrap();
+        assert_eq!(pdag, PDAG::from_dense_col_to_row(dag));
+        assert_eq!(
+            report.consistent,
+            vec![Convention::Gadjid, Convention::PcalgAmatCpdag]
+        );
+
+        let cpdag = vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 0],
+        ];
+        let (pdag, report) = load_with_detected_convention(&cpdag, true).unwrap();
+        assert_eq!(pdag, PDAG::from_dense_row_to_col(cpdag));
+        assert_eq!(
+            report.warnings,
+            vec![ConventionWarning::OneSidedUndirected { row: 1, col: 2 }]
+        );
+
+        let cyclic = vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![1, 0, 0],
+        ];
+        assert!(matches!(
+            load_with_detected_convention(&cyclic, true),
+            Err(ConventionError::Load(_))
+        ));
+
+        assert!(matches!(
+            load_with_detected_convention(&[], true),
+            Err(ConventionError::NoConsistentConvention(_))
+        ));
+    }
+
+    #[test]
+    fn sanitized_matrices_load() {
+        // 0 -> 1 -- 2 in gadjid coding from row to column, with 1 -- 2 coded on one side and self-loops
+        let gadjid = vec![
+            vec![1, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 2],
+        ];
+        assert!(load_with_convention(&gadjid, Convention::Gadjid, true).is_err());
+        let sanitized = sanitize_adjacency(&gadjid, Convention::Gadjid).unwrap();
+        assert_eq!(sanitized.fixes.len(), 3);
+        let pdag = load_with_convention(&sanitized.matrix, Convention::Gadjid, true).unwrap();
+        assert_eq!(pdag.n_directed_edges, 1);
+        assert_eq!(pdag.n_undirected_edges, 1);
+
+        // 0 -- 1 in symmetric bool coding, coded on one side
+        let skeleton = vec![
+            vec![0, 0], //
+            vec![1, 0],
+        ];
+        let sanitized = sanitize_adjacency(&skeleton, Convention::SymmetricBool).unwrap();
+        assert_eq!(
+            sanitized.fixes,
+            vec![AdjacencyFix::Symmetrized {
+                row: 1,
+                col: 0,
+                value: 1
+            }]
+        );
+        assert!(load_with_convention(&sanitized.matrix, Convention::SymmetricBool, true).is_ok());
+
+        // a pcalg amat.cpdag coded matrix is asymmetric by design, so only the diagonal is fixed
+        let sanitized = sanitize_adjacency(&skeleton, Convention::PcalgAmatCpdag).unwrap();
+        assert_eq!(sanitized.matrix, skeleton);
+        assert!(sanitized.fixes.is_empty());
+
+        assert!(matches!(
+            sanitize_adjacency(&[vec![0, 1]], Convention::Gadjid),
+            Err(ConventionError::NoConsistentConvention(_))
+        ));
+    }
+}
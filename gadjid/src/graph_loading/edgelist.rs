@@ -1,12 +1,15 @@
 // SPDX-License-Identifier: MPL-2.0
 //! This module contains the Edgelist struct, which is an iterator over the edges of a graph.
 
-/// An iterator over the edges of a graph, yielding `(from, to, edgetype)` tuples.
+use crate::LoadError;
 
+/// An iterator over the edges of a graph, yielding `(from, to, edgetype)` tuples.
+///
 /// Example yield: `(4, 7, 1)`, which is to be interpreted as `4 -> 7`.
-
+///
 /// Will skip over all 0's in the inner iterator, yielding only nonzero entries.
-/// Will panic during load if the inner iterator yields edges in a non-row-by-row or non-column-by-column order.
+/// Will panic during iteration, and makes loading return [`LoadError::NotOrdered`],
+/// if the inner iterator yields edges in a non-row-by-row or non-column-by-column order.
 pub struct Edgelist<Order: IterationLayoutTag, I>
 where
     I: Iterator<Item = (usize, usize, i8)>,
@@ -26,33 +29,29 @@ where
     Order: IterationLayoutTag,
     I: Iterator<Item = (usize, usize, i8)>,
 {
-    /// panic if receiving `next_index` having an earlier outer idx than `prev_index`
+    /// Returns [`LoadError::NotOrdered`] if receiving `next_index` having an earlier outer idx than `prev_index`
     /// OR if receiving `next_index` with an earlier-or-same inner idx given the same outer idx as
     /// `prev_index`
-    fn order_check(prev_index: Option<(usize, usize)>, next_index: (usize, usize)) {
+    fn order_check(
+        prev_index: Option<(usize, usize)>,
+        next_index: (usize, usize),
+    ) -> Result<(), LoadError> {
         if let Some((prev_outer, prev_inner)) = prev_index {
             let (next_outer, next_inner) = next_index;
 
             if next_outer < prev_outer || (next_outer == prev_outer && next_inner <= prev_inner) {
-                panic!(
-                    "Iterator yielded entries in wrong order. {}, prev (outer, inner) index:{:?}, next (outer, inner) index:{:?}",
-                    std::any::type_name::<Self>(),
-                    (prev_outer, prev_inner),
-                    (next_outer, next_inner)
-                );
+                return Err(LoadError::NotOrdered {
+                    previous: (prev_outer, prev_inner),
+                    next: (next_outer, next_inner),
+                });
             }
         }
+        Ok(())
     }
-}
 
-// Iterator so we can iterate over the [`Edgelist`] skipping zero entries, and panicking on order violation
-impl<Order, I> Iterator for Edgelist<Order, I>
-where
-    Order: IterationLayoutTag,
-    I: Iterator<Item = (usize, usize, i8)>,
-{
-    type Item = (usize, usize, i8);
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Yields the next non-zero entry like [`Iterator::next`],
+    /// but returns [`LoadError::NotOrdered`] on an order violation instead of panicking.
+    pub(crate) fn try_next(&mut self) -> Result<Option<(usize, usize, i8)>, LoadError> {
         for val in self.iterator.by_ref() {
             match val {
                 // skip 0 entries
@@ -61,15 +60,29 @@ where
                 }
                 // yield non-zero entries
                 (_, _, _) => {
-                    // panic if order is violated
-                    (Self::order_check)(self.previous_index, (val.0, val.1));
+                    Self::order_check(self.previous_index, (val.0, val.1))?;
                     // record previous yield index
                     self.previous_index = Some((val.0, val.1));
-                    return Some(val);
+                    return Ok(Some(val));
                 }
             }
         }
-        None
+        Ok(None)
+    }
+}
+
+// Iterator so we can iterate over the [`Edgelist`] skipping zero entries, and panicking on order violation
+impl<Order, I> Iterator for Edgelist<Order, I>
+where
+    Order: IterationLayoutTag,
+    I: Iterator<Item = (usize, usize, i8)>,
+{
+    type Item = (usize, usize, i8);
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.try_next() {
+            Ok(val) => val,
+            Err(err) => panic!("{}: {err}", std::any::type_name::<Self>()),
+        }
     }
 }
 
@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Resolves the missing entries of a dense adjacency matrix, whose edge status is unknown,
+//! as exported by some tools as NaN or a designated missing code, before the matrix is loaded.
+
+use std::{error::Error, fmt};
+
+use crate::{LoadError, PDAG};
+
+/// How the missing entries of an adjacency matrix are resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingPolicy {
+    /// a missing entry is an error
+    Error,
+    /// a missing entry codes no edge
+    Absent,
+    /// a pair of nodes with a missing entry is joined by an undirected edge,
+    /// as an edge of unknown status that may point either way
+    Undirected,
+}
+
+/// Errors that can occur when resolving or loading a matrix with missing entries.
+#[derive(Debug, PartialEq)]
+pub enum MissingError {
+    /// The matrix is not square, row `row` has length `len` instead of the number of rows.
+    NotSquare {
+        /// the offending row
+        row: usize,
+        /// its length
+        len: usize,
+    },
+    /// The entry at `[row, col]` is missing, and the policy is [`MissingPolicy::Error`].
+    Missing {
+        /// row of the first missing entry
+        row: usize,
+        /// column of the first missing entry
+        col: usize,
+    },
+    /// The resolved graph could not be loaded, for example, because it is not acyclic.
+    Load(LoadError),
+}
+
+impl fmt::Display for MissingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MissingError::NotSquare { row, len } => {
+                write!(f, "matrix is not square, row {row} has length {len}")
+            }
+            MissingError::Missing { row, col } => {
+                write!(
+                    f,
+                    "entry at ({row}, {col}) is missing, the edge status is unknown"
+                )
+            }
+            MissingError::Load(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl Error for MissingError {}
+
+/// An adjacency matrix whose missing entries are resolved by [`resolve_missing`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedAdjacency {
+    /// the matrix in gadjid coding, without missing entries
+    pub matrix: Vec<Vec<i8>>,
+    /// the number of missing entries, including any on the diagonal
+    pub n_missing: usize,
+    /// the number of unordered pairs of distinct nodes with at least one missing entry
+    pub n_affected_pairs: usize,
+}
+
+/// Resolves the missing entries (`None`) of a dense adjacency matrix in gadjid coding by the `policy`.
+/// Missing entries on the diagonal become `0`. With [`MissingPolicy::Absent`], every other missing entry
+/// becomes `0`, so a pair of nodes coded `1` on the other side keeps its directed edge.
+/// With [`MissingPolicy::Undirected`], both entries of a pair with a missing entry become `2`,
+/// also if the other side codes a directed edge, since the edge status of the pair is unknown.
+///
+/// ```
+/// use gadjid::{resolve_missing, MissingError, MissingPolicy};
+///
+/// // 0 -> 1, unknown status between 1 and 2
+/// let matrix = vec![
+///     vec![None, Some(1), Some(0)],
+///     vec![Some(0), Some(0), None],
+///     vec![Some(0), Some(0), Some(0)],
+/// ];
+///
+/// let absent = resolve_missing(&matrix, MissingPolicy::Absent).unwrap();
+/// assert_eq!(absent.matrix, vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 0, 0]]);
+/// assert_eq!((absent.n_missing, absent.n_affected_pairs), (2, 1));
+///
+/// let undirected = resolve_missing(&matrix, MissingPolicy::Undirected).unwrap();
+/// assert_eq!(undirected.matrix, vec![vec![0, 1, 0], vec![0, 0, 2], vec![0, 2, 0]]);
+///
+/// assert_eq!(
+///     resolve_missing(&matrix, MissingPolicy::Error),
+///     Err(MissingError::Missing { row: 0, col: 0 })
+/// );
+/// ```
+pub fn resolve_missing(
+    matrix: &[Vec<Option<i8>>],
+    policy: MissingPolicy,
+) -> Result<ResolvedAdjacency, MissingError> {
+    let n = matrix.len();
+    if let Some((row, values)) = matrix.iter().enumerate().find(|(_, v)| v.len() != n) {
+        return Err(MissingError::NotSquare {
+            row,
+            len: values.len(),
+        });
+    }
+
+    if policy == MissingPolicy::Error {
+        let first_missing = (0..n)
+            .flat_map(|row| (0..n).map(move |col| (row, col)))
+            .find(|(row, col)| matrix[*row][*col].is_none());
+        if let Some((row, col)) = first_missing {
+            return Err(MissingError::Missing { row, col });
+        }
+    }
+
+    let mut resolved = Vec::from_iter(
+        matrix
+            .iter()
+            .map(|values| Vec::from_iter(values.iter().map(|value| value.unwrap_or(0)))),
+    );
+    let (mut n_missing, mut n_affected_pairs) = (0, 0);
+    for row in 0..n {
+        n_missing += matrix[row][row].is_none() as usize;
+        for col in (row + 1)..n {
+            let n_missing_in_pair =
+                matrix[row][col].is_none() as usize + matrix[col][row].is_none() as usize;
+            if n_missing_in_pair > 0 {
+                n_missing += n_missing_in_pair;
+                n_affected_pairs += 1;
+                if policy == MissingPolicy::Undirected {
+                    (resolved[row][col], resolved[col][row]) = (2, 2);
+                }
+            }
+        }
+    }
+    Ok(ResolvedAdjacency {
+        matrix: resolved,
+        n_missing,
+        n_affected_pairs,
+    })
+}
+
+/// Loads a dense adjacency matrix in gadjid coding with missing entries (`None`) into a PDAG,
+/// after resolving the missing entries by the `policy` as in [`resolve_missing`].
+/// Returns the graph alongside the resolved matrix, whose counts of missing entries can be reported.
+pub fn load_with_missing(
+    matrix: &[Vec<Option<i8>>],
+    policy: MissingPolicy,
+    row_to_col: bool,
+) -> Result<(PDAG, ResolvedAdjacency), MissingError> {
+    let resolved = resolve_missing(matrix, policy)?;
+    let dense = resolved.matrix.clone();
+    let graph = match row_to_col {
+        true => PDAG::try_from_dense_row_to_col(dense),
+        false => PDAG::try_from_dense_col_to_row(dense),
+    };
+    Ok((graph.map_err(MissingError::Load)?, resolved))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{LoadError, PDAG};
+
+    use super::{load_with_missing, resolve_missing, MissingError, MissingPolicy};
+
+    #[test]
+    fn resolves_missing_entries_by_policy() {
+        // 0 -> 1 with an unknown reverse entry, 1 -- 2 coded on one side, 2 and 0 unknown
+        let matrix = vec![
+            vec![Some(0), Some(1), None],
+            vec![None, Some(0), Some(2)],
+            vec![None, Some(0), Some(0)],
+        ];
+
+        let absent = resolve_missing(&matrix, MissingPolicy::Absent).unwrap();
+        assert_eq!(
+            absent.matrix,
+            vec![vec![0, 1, 0], vec![0, 0, 2], vec![0, 0, 0]]
+        );
+        assert_eq!((absent.n_missing, absent.n_affected_pairs), (3, 2));
+
+        let undirected = resolve_missing(&matrix, MissingPolicy::Undirected).unwrap();
+        assert_eq!(
+            undirected.matrix,
+            vec![vec![0, 2, 2], vec![2, 0, 2], vec![2, 0, 0]]
+        );
+
+        let (graph, _) = load_with_missing(&matrix, MissingPolicy::Absent, false).unwrap();
+        assert_eq!(
+            graph,
+            PDAG::from_dense_col_to_row(vec![vec![0, 1, 0], vec![0, 0, 2], vec![0, 0, 0]])
+        );
+        assert_eq!(
+            resolve_missing(&matrix, MissingPolicy::Error),
+            Err(MissingError::Missing { row: 0, col: 2 })
+        );
+    }
+
+    #[test]
+    fn reports_matrices_that_do_not_load() {
+        let not_square = vec![vec![None, Some(1)], vec![Some(0)]];
+        assert_eq!(
+            resolve_missing(&not_square, MissingPolicy::Absent),
+            Err(MissingError::NotSquare { row: 1, len: 1 })
+        );
+
+        // 0 -> 1 -> 2 -> 0 with an unknown diagonal entry
+        let cycle = vec![
+            vec![None, Some(1), Some(0)],
+            vec![Some(0), Some(0), Some(1)],
+            vec![Some(1), Some(0), Some(0)],
+        ];
+        assert_eq!(
+            load_with_missing(&cycle, MissingPolicy::Absent, true),
+            Err(MissingError::Load(LoadError::NotAcyclic))
+        );
+    }
+}
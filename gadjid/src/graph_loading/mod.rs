@@ -2,4 +2,7 @@
 //! This module defines the graph edgelist iterator adaptor for strong typing for the EdgeList struct.
 
 pub mod constructor;
+pub mod convention;
 pub mod edgelist;
+pub mod missing;
+pub mod pool;
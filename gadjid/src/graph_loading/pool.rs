@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Loads many adjacency matrices of the same size at once from one contiguous memory pool,
+//! such as the graphs learned on bootstrap samples, stacked into a (k, n, n) array.
+
+use std::{error::Error, fmt};
+
+use rayon::prelude::*;
+
+use crate::{EdgelistIterator, LoadError, PDAG};
+
+#[derive(Debug, PartialEq)]
+/// Error that can occur when loading many adjacency matrices from one memory pool.
+pub enum LoadManyError {
+    /// The pool does not hold `n_graphs` square matrices of `n_nodes` rows.
+    PoolSize {
+        /// the number of entries of the pool
+        len: usize,
+        /// the number of graphs
+        n_graphs: usize,
+        /// the number of nodes of each graph
+        n_nodes: usize,
+    },
+    /// A graph could not be loaded.
+    Load {
+        /// the index of the offending graph in the pool
+        index: usize,
+        /// why it could not be loaded
+        error: LoadError,
+    },
+}
+
+impl Error for LoadManyError {}
+
+impl fmt::Display for LoadManyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadManyError::PoolSize {
+                len,
+                n_graphs,
+                n_nodes,
+            } => write!(
+                f,
+                "pool of {len} entries does not hold {n_graphs} adjacency matrices of {n_nodes} nodes"
+            ),
+            LoadManyError::Load { index, error } => write!(f, "graph {index}: {error}"),
+        }
+    }
+}
+
+/// Loads `n_graphs` adjacency matrices of `n_nodes` nodes each from the `pool`,
+/// which holds the entries of one matrix after the other, each in row-major order,
+/// as a C-contiguous (n_graphs, n_nodes, n_nodes) array does.
+/// A matrix codes edges from row to column if `row_to_col`, and from column to row otherwise.
+/// The graphs are loaded in parallel, without copying the matrices out of the pool.
+///
+/// ```
+/// use gadjid::load_many;
+///
+/// // the graphs 0 -> 1 and 1 -> 0, coded from row to column
+/// let pool = [
+///     0, 1, //
+///     0, 0, //
+///     0, 0, //
+///     1, 0,
+/// ];
+/// let graphs = load_many(&pool, 2, 2, true).unwrap();
+/// assert_eq!(graphs[0].children_of(0), &[1]);
+/// assert_eq!(graphs[1].children_of(1), &[0]);
+/// ```
+pub fn load_many(
+    pool: &[i8],
+    n_graphs: usize,
+    n_nodes: usize,
+    row_to_col: bool,
+) -> Result<Vec<PDAG>, LoadManyError> {
+    let matrix_len = n_nodes.checked_mul(n_nodes);
+    if matrix_len.and_then(|len| len.checked_mul(n_graphs)) != Some(pool.len()) {
+        return Err(LoadManyError::PoolSize {
+            len: pool.len(),
+            n_graphs,
+            n_nodes,
+        });
+    }
+    let matrix_len = n_nodes * n_nodes;
+
+    crate::rayon::build_global();
+
+    (0..n_graphs)
+        .into_par_iter()
+        .map(|index| {
+            let matrix = &pool[index * matrix_len..(index + 1) * matrix_len];
+            let entries = matrix
+                .iter()
+                .enumerate()
+                .map(|(i, val)| (i / n_nodes, i % n_nodes, *val));
+            // a row-major matrix read column by column is its transpose
+            match row_to_col {
+                true => PDAG::try_from_row_major(entries.into_row_major_edgelist(n_nodes)),
+                false => PDAG::try_from_col_major(entries.into_column_major_edgelist(n_nodes)),
+            }
+            .map_err(|error| LoadManyError::Load { index, error })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{LoadError, PDAG};
+
+    use super::{load_many, LoadManyError};
+
+    #[test]
+    fn loads_like_single_graphs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let graphs = Vec::from_iter((0..5).map(|_| PDAG::random_pdag(0.5, 8, &mut rng)));
+        let pool = Vec::from_iter(
+            graphs
+                .iter()
+                .flat_map(|graph| graph.to_row_to_column_vecvec().into_iter().flatten()),
+        );
+        assert_eq!(load_many(&pool, 5, 8, true).unwrap(), graphs);
+
+        // column to row matrices are the transposed row to column matrices
+        let transposed_pool = Vec::from_iter(graphs.iter().flat_map(|graph| {
+            let dense = graph.to_row_to_column_vecvec();
+            (0..8).flat_map(move |w| Vec::from_iter((0..8).map(|v| dense[v][w])))
+        }));
+        assert_eq!(load_many(&transposed_pool, 5, 8, false).unwrap(), graphs);
+    }
+
+    #[test]
+    fn reports_which_graph_failed() {
+        let pool = [0, 1, 0, 0, 0, 1, 1, 0];
+        assert_eq!(
+            load_many(&pool, 2, 2, true).unwrap_err(),
+            LoadManyError::Load {
+                index: 1,
+                error: LoadError::NotSimple { node: 0, other: 1 }
+            }
+        );
+        assert!(matches!(
+            load_many(&pool, 3, 2, true).unwrap_err(),
+            LoadManyError::PoolSize { len: 8, .. }
+        ));
+        assert_eq!(load_many(&[], 0, 3, true).unwrap(), vec![]);
+    }
+}
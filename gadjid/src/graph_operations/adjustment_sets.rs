@@ -0,0 +1,392 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Compares adjustment sets for the same treatments and effect by their validity
+//! and by the asymptotic variance of the resulting estimators,
+//! and computes the parent, optimal, and a minimal adjustment set in one go,
+//! and lists the effects for which a given adjustment set is not valid
+
+use std::cmp::Ordering;
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{
+        get_d_pd_nam, get_nam_nva, get_parents, optimal_adjustment_set_given_descendants,
+        separation::{dag_in_class, is_d_separated},
+    },
+    PDAG,
+};
+
+/// The comparison of two adjustment sets, as returned by [`compare_adjustment_sets`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjustmentSetComparison {
+    /// whether the first set is a valid adjustment set in the graph
+    pub z1_is_valid: bool,
+    /// whether the second set is a valid adjustment set in the graph
+    pub z2_is_valid: bool,
+    /// the (sorted) nodes only in the first set
+    pub only_in_z1: Vec<usize>,
+    /// the (sorted) nodes only in the second set
+    pub only_in_z2: Vec<usize>,
+    /// how the asymptotic variance of the estimator adjusting for the first set compares to that of the
+    /// second set in all linear causal models compatible with the graph, if both sets are valid and
+    /// the variances are ordered by the graphical criterion; for example, `Some(Ordering::Less)`
+    /// if adjusting for the first set is at least as efficient as adjusting for the second set, but not vice versa
+    pub variance_ordering: Option<Ordering>,
+}
+
+/// The parent, optimal, and a minimal adjustment set for the same treatments and effect,
+/// as returned by [`adjustment_set_candidates`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AdjustmentSetCandidates {
+    /// the (sorted) parents of the treatments
+    pub parents: Vec<usize>,
+    /// whether the parents are a valid adjustment set
+    pub parents_is_valid: bool,
+    /// the (sorted) optimal adjustment set
+    pub optimal: Vec<usize>,
+    /// whether the optimal adjustment set is valid, which holds if and only if any adjustment set is valid
+    pub optimal_is_valid: bool,
+    /// a (sorted) valid adjustment set none of whose proper subsets is valid,
+    /// if any adjustment set is valid; it is a subset of the optimal adjustment set
+    pub minimal: Option<Vec<usize>>,
+    /// whether the parents are a subset of the optimal adjustment set
+    pub parents_subset_of_optimal: bool,
+    /// whether the optimal adjustment set is a subset of the parents
+    pub optimal_subset_of_parents: bool,
+    /// whether the minimal adjustment set is a subset of the parents
+    pub minimal_subset_of_parents: bool,
+}
+
+/// Returns whether `z` is a valid adjustment set for the effect of `t` on `y` in the graph,
+/// which requires the graph to be amenable relative to `t` and `y`.
+fn is_valid_adjustment_set(graph: &PDAG, t: &[usize], y: usize, z: &FxHashSet<usize>) -> bool {
+    let (_, nva) = get_nam_nva(graph, t, z, Some(&FxHashSet::from_iter([y])));
+    !nva.contains(&y)
+}
+
+fn sorted(set: &FxHashSet<usize>) -> Vec<usize> {
+    let mut vec = Vec::from_iter(set.iter().copied());
+    vec.sort_unstable();
+    vec
+}
+
+/// Computes the parents of the treatments `t`, the optimal adjustment set, and a minimal adjustment set
+/// for the effect of `t` on `y` in the `graph` DAG or CPDAG, alongside their validity and which of them
+/// are subsets of the others.
+///
+/// The minimal adjustment set is found by removing the nodes of the optimal adjustment set one by one,
+/// in increasing order, whenever the remaining set stays valid; since a valid adjustment set
+/// from which no single node can be removed is minimal, so is the result.
+///
+/// ```
+/// use gadjid::{graph_operations::adjustment_set_candidates, PDAG};
+///
+/// // 2 -> 0 -> 1 <- 2, 3 -> 1
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![1, 1, 0, 0],
+///     vec![0, 1, 0, 0],
+/// ]);
+///
+/// let candidates = adjustment_set_candidates(&graph, &[0], 1);
+/// assert_eq!(candidates.parents, vec![2]);
+/// assert_eq!(candidates.optimal, vec![2, 3]);
+/// assert_eq!(candidates.minimal, Some(vec![2]));
+/// assert!(candidates.parents_is_valid && candidates.optimal_is_valid);
+/// assert!(candidates.parents_subset_of_optimal && !candidates.optimal_subset_of_parents);
+/// ```
+pub fn adjustment_set_candidates(graph: &PDAG, t: &[usize], y: usize) -> AdjustmentSetCandidates {
+    assert!(!t.is_empty(), "there must be at least one treatment");
+    assert!(
+        t.iter().chain([&y]).all(|v| *v < graph.n_nodes),
+        "all nodes must be in bounds"
+    );
+    assert!(!t.contains(&y), "the effect must not be a treatment");
+
+    let (t_descendants, _, _) = get_d_pd_nam(graph, t);
+    let optimal = optimal_adjustment_set_given_descendants(graph, t, &[y], &t_descendants);
+    let parents = get_parents(graph, t.iter());
+    let optimal_is_valid = is_valid_adjustment_set(graph, t, y, &optimal);
+
+    let minimal = optimal_is_valid.then(|| {
+        let mut minimal = optimal.clone();
+        for v in sorted(&optimal) {
+            minimal.remove(&v);
+            if !is_valid_adjustment_set(graph, t, y, &minimal) {
+                minimal.insert(v);
+            }
+        }
+        minimal
+    });
+
+    AdjustmentSetCandidates {
+        parents_is_valid: is_valid_adjustment_set(graph, t, y, &parents),
+        optimal_is_valid,
+        parents_subset_of_optimal: parents.is_subset(&optimal),
+        optimal_subset_of_parents: optimal.is_subset(&parents),
+        minimal_subset_of_parents: minimal.as_ref().is_some_and(|m| m.is_subset(&parents)),
+        parents: sorted(&parents),
+        optimal: sorted(&optimal),
+        minimal: minimal.as_ref().map(sorted),
+    }
+}
+
+/// Returns the (sorted) nodes `y` not in `t` for which `z` is not a valid adjustment set
+/// for the effect of the treatments `t` on `y` in the `graph` DAG or CPDAG,
+/// including the nodes for which no adjustment set is valid since the graph is not amenable
+/// relative to `t` and `y`; for example, to ask for which outcomes a chosen set of covariates
+/// does not identify the effect according to a learned graph.
+///
+/// ```
+/// use gadjid::{graph_operations::not_validly_adjusted_for, PDAG};
+///
+/// // 2 -> 0 -> 1 <- 2, 3 -> 1
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![1, 1, 0, 0],
+///     vec![0, 1, 0, 0],
+/// ]);
+///
+/// // the paths 0 <- 2 -> 1 and 0 <- 2 are open unless adjusting for 2
+/// assert_eq!(not_validly_adjusted_for(&graph, &[0], &[]), vec![1, 2]);
+/// assert_eq!(not_validly_adjusted_for(&graph, &[0], &[2]), vec![2]);
+/// ```
+pub fn not_validly_adjusted_for(graph: &PDAG, t: &[usize], z: &[usize]) -> Vec<usize> {
+    assert!(!t.is_empty(), "there must be at least one treatment");
+    assert!(
+        t.iter().chain(z).all(|v| *v < graph.n_nodes),
+        "all nodes must be in bounds"
+    );
+
+    let z = FxHashSet::from_iter(z.iter().copied());
+    let (_, nva) = get_nam_nva(graph, t, &z, None);
+    sorted(&nva)
+}
+
+/// Compares the adjustment sets `z1` and `z2` for the effect of the treatments `t` on the effect `y`
+/// in the `truth` DAG or CPDAG, for example, to choose between covariates proposed by a learned graph.
+///
+/// Reports whether each set is valid, which nodes are only in one of the sets, and, if both are valid,
+/// whether adjusting for one of them gives an estimator with at most the asymptotic variance of the other.
+/// By the graphical criterion of Henckel, Perković, and Maathuis (2022, Theorem 3.4), this holds for `z2`
+/// in all linear causal models compatible with the graph if `y` is d-separated from the nodes only in `z1`
+/// given `t` and `z2`, and `t` is d-separated from the nodes only in `z2` given `z1`;
+/// that is, if `z2` only adds nodes that are associated with `y` but not with `t`, such as
+/// the parents of `y`, and only drops nodes that are associated with `t` but not with `y`, such as instruments.
+/// In particular, the optimal adjustment set is at least as efficient as any other valid adjustment set.
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use gadjid::{graph_operations::compare_adjustment_sets, PDAG};
+///
+/// // 2 -> 0 -> 1 <- 2, 3 -> 1
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![1, 1, 0, 0],
+///     vec![0, 1, 0, 0],
+/// ]);
+///
+/// // adding the parent 3 of the effect to the adjustment set reduces the variance
+/// let comparison = compare_adjustment_sets(&graph, &[0], 1, &[2], &[2, 3]);
+/// assert!(comparison.z1_is_valid && comparison.z2_is_valid);
+/// assert_eq!(comparison.only_in_z2, vec![3]);
+/// assert_eq!(comparison.variance_ordering, Some(Ordering::Greater));
+/// ```
+pub fn compare_adjustment_sets(
+    truth: &PDAG,
+    t: &[usize],
+    y: usize,
+    z1: &[usize],
+    z2: &[usize],
+) -> AdjustmentSetComparison {
+    assert!(!t.is_empty(), "there must be at least one treatment");
+    assert!(
+        t.iter()
+            .chain(z1)
+            .chain(z2)
+            .chain([&y])
+            .all(|v| *v < truth.n_nodes),
+        "all nodes must be in bounds"
+    );
+    assert!(!t.contains(&y), "the effect must not be a treatment");
+
+    let z1 = FxHashSet::from_iter(z1.iter().copied());
+    let z2 = FxHashSet::from_iter(z2.iter().copied());
+    let sorted_difference = |a: &FxHashSet<usize>, b: &FxHashSet<usize>| {
+        let mut difference = Vec::from_iter(a.difference(b).copied());
+        difference.sort_unstable();
+        difference
+    };
+    let z1_is_valid = is_valid_adjustment_set(truth, t, y, &z1);
+    let z2_is_valid = is_valid_adjustment_set(truth, t, y, &z2);
+    let (only_in_z1, only_in_z2) = (sorted_difference(&z1, &z2), sorted_difference(&z2, &z1));
+
+    let variance_ordering = (z1_is_valid && z2_is_valid).then(|| {
+        // d-separation is the same in all DAGs in the Markov equivalence class of a CPDAG
+        let dag = dag_in_class(truth);
+        let t_and = |z: &FxHashSet<usize>| FxHashSet::from_iter(t.iter().chain(z).copied());
+        let at_most_variance_of = |better: &FxHashSet<usize>,
+                                   worse: &FxHashSet<usize>,
+                                   only_in_better: &[usize],
+                                   only_in_worse: &[usize]| {
+            is_d_separated(&dag, &[y], only_in_worse, &t_and(better))
+                && is_d_separated(&dag, t, only_in_better, worse)
+        };
+        let z1_at_most = at_most_variance_of(&z1, &z2, &only_in_z1, &only_in_z2);
+        let z2_at_most = at_most_variance_of(&z2, &z1, &only_in_z2, &only_in_z1);
+        match (z1_at_most, z2_at_most) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    });
+
+    AdjustmentSetComparison {
+        z1_is_valid,
+        z2_is_valid,
+        only_in_z1,
+        only_in_z2,
+        variance_ordering: variance_ordering.flatten(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use rand::SeedableRng;
+
+    use crate::PDAG;
+
+    use super::{adjustment_set_candidates, compare_adjustment_sets, not_validly_adjusted_for};
+
+    #[test]
+    fn lists_effects_not_validly_adjusted_for() {
+        // 2 -> 0 -> 1, 2 -> 1, 3 -> 1, and 3 -- 4 in the CPDAG
+        let cpdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0], //
+            vec![0, 0, 0, 0, 0],
+            vec![1, 1, 0, 0, 0],
+            vec![0, 1, 0, 0, 2],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        // the effect on 1 is confounded by 2, and the effect on 2 is that on a parent
+        assert_eq!(not_validly_adjusted_for(&cpdag, &[0], &[]), vec![1, 2]);
+        assert_eq!(not_validly_adjusted_for(&cpdag, &[0], &[2]), vec![2]);
+        // the treatment 3 is not amenable relative to 4, irrespective of the adjustment set
+        assert_eq!(not_validly_adjusted_for(&cpdag, &[3], &[]), vec![4]);
+    }
+
+    #[test]
+    fn orders_precision_variables_and_instruments() {
+        // 0 -> 1 -> 2 (treatment 1, effect 2), 3 -> 1, 3 -> 2, 4 -> 2, 5 -> 0
+        let dag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0, 0], //
+            vec![0, 0, 1, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 0, 0, 0],
+            vec![0, 0, 1, 0, 0, 0],
+            vec![1, 0, 0, 0, 0, 0],
+        ]);
+
+        // adding the precision variable 4 to the confounder 3 can only help
+        let comparison = compare_adjustment_sets(&dag, &[1], 2, &[3], &[3, 4]);
+        assert!(comparison.z1_is_valid && comparison.z2_is_valid);
+        assert_eq!(
+            (comparison.only_in_z1, comparison.only_in_z2),
+            (vec![], vec![4])
+        );
+        assert_eq!(comparison.variance_ordering, Some(Ordering::Greater));
+
+        // adding the instrument 0 can only hurt
+        let comparison = compare_adjustment_sets(&dag, &[1], 2, &[0, 3], &[3]);
+        assert_eq!(comparison.variance_ordering, Some(Ordering::Greater));
+        let comparison = compare_adjustment_sets(&dag, &[1], 2, &[3], &[0, 3, 4]);
+        assert_eq!(comparison.variance_ordering, None);
+        let comparison = compare_adjustment_sets(&dag, &[1], 2, &[0, 3], &[0, 3]);
+        assert_eq!(comparison.variance_ordering, Some(Ordering::Equal));
+
+        // the empty set misses the confounder 3
+        let comparison = compare_adjustment_sets(&dag, &[1], 2, &[], &[3]);
+        assert!(!comparison.z1_is_valid && comparison.z2_is_valid);
+        assert_eq!(comparison.variance_ordering, None);
+    }
+
+    #[test]
+    fn compares_adjustment_sets_in_cpdag() {
+        // 0 -- 1, 0 -> 2 <- 3, 2 -> 4, 1 -> 4 (treatment 2, effect 4)
+        let cpdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2, 1, 0, 0], //
+            vec![0, 0, 0, 0, 1],
+            vec![0, 0, 0, 0, 1],
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        // the parents {0, 3} of the treatment are valid, and replacing the instrument 3
+        // by 1, which is only associated with the treatment via the confounder 0, can only help
+        let comparison = compare_adjustment_sets(&cpdag, &[2], 4, &[0, 3], &[0, 1]);
+        assert!(comparison.z1_is_valid && comparison.z2_is_valid);
+        assert_eq!(comparison.variance_ordering, Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn computes_parent_optimal_and_minimal_sets() {
+        // 0 -> 1 -> 2 (treatment 1, effect 2), 3 -> 1, 3 -> 2, 4 -> 2, 5 -> 0, 5 -> 3
+        let dag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0, 0], //
+            vec![0, 0, 1, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 1, 0, 0, 0],
+            vec![0, 0, 1, 0, 0, 0],
+            vec![1, 0, 0, 1, 0, 0],
+        ]);
+        let candidates = adjustment_set_candidates(&dag, &[1], 2);
+        assert_eq!(candidates.parents, vec![0, 3]);
+        assert_eq!(candidates.optimal, vec![3, 4]);
+        assert_eq!(candidates.minimal, Some(vec![3]));
+        assert!(candidates.parents_is_valid && candidates.optimal_is_valid);
+        assert!(!candidates.parents_subset_of_optimal && !candidates.optimal_subset_of_parents);
+        assert!(candidates.minimal_subset_of_parents);
+
+        // 0 -- 1 is not amenable relative to 0 and 1
+        let cpdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2], //
+            vec![0, 0],
+        ]);
+        let candidates = adjustment_set_candidates(&cpdag, &[0], 1);
+        assert!(!candidates.parents_is_valid && !candidates.optimal_is_valid);
+        assert_eq!(candidates.minimal, None);
+    }
+
+    #[test]
+    fn property_minimal_sets_are_minimal() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..12 {
+            let dag = PDAG::random_dag(0.5, n, &mut rng);
+            for (t, y) in [(0, n - 1), (n - 1, 0), (n / 2, 0)] {
+                if t == y {
+                    continue;
+                }
+                let candidates = adjustment_set_candidates(&dag, &[t], y);
+                // in DAGs, the parents of a single treatment are valid unless the effect is one of them
+                assert_eq!(
+                    candidates.parents_is_valid,
+                    !candidates.parents.contains(&y)
+                );
+                let Some(minimal) = candidates.minimal else {
+                    continue;
+                };
+                for v in minimal.iter() {
+                    let subset = Vec::from_iter(minimal.iter().copied().filter(|w| w != v));
+                    let comparison = compare_adjustment_sets(&dag, &[t], y, &subset, &minimal);
+                    assert!(!comparison.z1_is_valid);
+                }
+            }
+        }
+    }
+}
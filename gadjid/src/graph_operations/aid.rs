@@ -0,0 +1,295 @@
+// SPDX-License-Identifier: MPL-2.0
+//! One entry point per adjustment identification distance, generic over what it returns.
+//!
+//! [`ancestor_aid_with`](super::ancestor_aid_with), [`oset_aid_with`](super::oset_aid_with),
+//! and [`parent_aid_with`](super::parent_aid_with) search from the treatments of an [`Aid`]
+//! and record the verdict on each `(treatment, effect)` pair into the mistake sink of the requested
+//! [`AidOutput`], such as a count, a breakdown by cause, or a matrix of the mistake pairs,
+//! so that every output shares the same traversal.
+
+use std::sync::OnceLock;
+
+use rayon::prelude::*;
+
+use crate::{
+    graph_operations::{
+        ancestor_aid::record_ancestor_aid_verdicts,
+        oset_aid::{record_oset_aid_verdicts, Ancestors},
+        parent_aid::record_parent_aid_verdicts,
+        quick_reject::identical,
+        selected_pairs::{Effects, MistakeSink},
+        twins::{is_isolated, twin_classes},
+    },
+    PDAG,
+};
+
+/// The adjustment set that the guess graph claims for a treatment.
+enum Adjustment {
+    /// the ancestors of the treatment
+    Ancestor,
+    /// the optimal adjustment set, with the ancestors of every node of the guess graph if shared
+    Oset(OnceLock<Option<Ancestors>>),
+    /// the parents of the treatment
+    Parent,
+}
+
+/// An adjustment identification distance between an estimated `guess` DAG or CPDAG
+/// and the true `truth` DAG or CPDAG, whose verdicts an [`AidOutput`] records.
+pub struct Aid<'a> {
+    truth: &'a PDAG,
+    guess: &'a PDAG,
+    adjustment: Adjustment,
+}
+
+impl<'a> Aid<'a> {
+    pub(crate) fn ancestor(truth: &'a PDAG, guess: &'a PDAG) -> Self {
+        Aid {
+            truth,
+            guess,
+            adjustment: Adjustment::Ancestor,
+        }
+    }
+
+    pub(crate) fn oset(truth: &'a PDAG, guess: &'a PDAG) -> Self {
+        Aid {
+            truth,
+            guess,
+            adjustment: Adjustment::Oset(OnceLock::new()),
+        }
+    }
+
+    pub(crate) fn parent(truth: &'a PDAG, guess: &'a PDAG) -> Self {
+        Aid {
+            truth,
+            guess,
+            adjustment: Adjustment::Parent,
+        }
+    }
+
+    /// The true graph.
+    pub fn truth(&self) -> &'a PDAG {
+        self.truth
+    }
+
+    /// The guess graph.
+    pub fn guess(&self) -> &'a PDAG {
+        self.guess
+    }
+
+    /// Precomputes the ancestors of every node of the guess graph for the oset AID, which pays off
+    /// for outputs that search from every treatment, but not for a few sampled or selected treatments.
+    pub fn share_guess_ancestors(&self) {
+        if let Adjustment::Oset(guess_ancestors) = &self.adjustment {
+            guess_ancestors.get_or_init(|| Ancestors::of(self.guess));
+        }
+    }
+
+    /// Records the verdict on the adjustment for the single `treatment`
+    /// on each of the given `effects`, except the treatment itself, into the `sink`,
+    /// such as to compute an [`AidOutput`] with a custom [`MistakeSink`].
+    ///
+    /// ```
+    /// use gadjid::{
+    ///     graph_operations::{ancestor_aid, ancestor_aid_with, Aid, AidOutput, MistakeSink, Verdict},
+    ///     PDAG,
+    /// };
+    ///
+    /// /// Counts the mistakes for which the guess graph claims a wrong causal order.
+    /// struct WrongOrders;
+    ///
+    /// impl AidOutput for WrongOrders {
+    ///     type Output = usize;
+    ///
+    ///     fn compute(self, aid: &Aid) -> usize {
+    ///         struct Count(usize);
+    ///
+    ///         impl MistakeSink for Count {
+    ///             fn record(&mut self, _: &[usize], _: &[usize], verdict: Verdict) {
+    ///                 if verdict == Verdict::WrongOrder {
+    ///                     self.0 += 1;
+    ///                 }
+    ///             }
+    ///         }
+    ///
+    ///         let nodes = Vec::from_iter(0..aid.truth().n_nodes);
+    ///         let mut count = Count(0);
+    ///         for treatment in 0..aid.truth().n_nodes {
+    ///             aid.record(treatment, &nodes, &mut count);
+    ///         }
+    ///         count.0
+    ///     }
+    /// }
+    ///
+    /// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+    /// let truth = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 0, 0],
+    /// ]);
+    /// let guess = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ]);
+    ///
+    /// // the guess claims that 2 is not an effect of 0 or 1
+    /// assert_eq!(ancestor_aid_with(&truth, &guess, WrongOrders), 2);
+    /// assert_eq!(ancestor_aid(&truth, &guess).1, 3);
+    /// ```
+    pub fn record(&self, treatment: usize, effects: &[usize], sink: &mut impl MistakeSink) {
+        self.record_effects(treatment, Effects::All(effects), sink)
+    }
+
+    /// Records the verdict on the adjustment for the single `treatment`
+    /// on each of the given `effects` into the `sink`.
+    pub(crate) fn record_effects(
+        &self,
+        treatment: usize,
+        effects: Effects,
+        sink: &mut impl MistakeSink,
+    ) {
+        let (truth, guess) = (self.truth, self.guess);
+        match &self.adjustment {
+            Adjustment::Ancestor => {
+                record_ancestor_aid_verdicts(truth, guess, treatment, effects, sink)
+            }
+            Adjustment::Oset(guess_ancestors) => {
+                let ancestors = guess_ancestors.get().and_then(Option::as_ref);
+                record_oset_aid_verdicts(truth, guess, ancestors, &[treatment], effects, sink)
+            }
+            Adjustment::Parent => {
+                record_parent_aid_verdicts(truth, guess, treatment, effects, sink)
+            }
+        }
+    }
+
+    /// Counts the mistakes of the adjustment for the single `treatment` over the given `effects`.
+    pub(crate) fn mistakes(&self, treatment: usize, effects: Effects) -> usize {
+        let mut mistakes = 0;
+        self.record_effects(treatment, effects, &mut mistakes);
+        mistakes
+    }
+
+    /// The number n² − n of `(treatment, effect)` pairs of distinct nodes,
+    /// which the distances over all pairs are normalized by.
+    pub(crate) fn n_pairs(&self) -> usize {
+        let n = self.guess.n_nodes;
+        n * n - n
+    }
+
+    /// Checks the graphs and returns their nodes that are not isolated in both graphs, which are all
+    /// treatments and effects that can be part of a mistake, ready to be searched from in parallel.
+    fn connected(&self) -> Vec<usize> {
+        let (truth, guess) = (self.truth, self.guess);
+        assert!(
+            guess.n_nodes == truth.n_nodes,
+            "both graphs must contain the same number of nodes"
+        );
+        assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+        crate::rayon::build_global();
+        self.share_guess_ancestors();
+
+        Vec::from_iter((0..guess.n_nodes).filter(|v| !is_isolated(truth, guess, *v)))
+    }
+
+    /// Records the verdicts of one treatment per class of twins over all effects into a sink each
+    /// and sums the sinks, each counted once per twin of its class,
+    /// since twins have the same mistakes with different nodes.
+    pub(crate) fn fold_twins<S: TwinSink>(&self) -> S {
+        let connected = self.connected();
+        twin_classes(self.truth, self.guess)
+            .into_par_iter()
+            .map(|(treatment, n_twins)| {
+                let mut sink = S::default();
+                sink.search(treatment, |sink| {
+                    self.record_effects(treatment, Effects::All(&connected), sink)
+                });
+                sink.scaled(n_twins)
+            })
+            .reduce(S::default, S::add)
+    }
+
+    /// Records the verdicts of every treatment over all effects into its own sink,
+    /// made by `sink_of`, and returns the sinks indexed by treatment;
+    /// the sinks of isolated treatments record nothing.
+    pub(crate) fn each_treatment<S: MistakeSink + Send>(
+        &self,
+        sink_of: impl Fn(usize) -> S + Sync,
+    ) -> Vec<S> {
+        let connected = self.connected();
+        (0..self.guess.n_nodes)
+            .into_par_iter()
+            .map(|treatment| {
+                let mut sink = sink_of(treatment);
+                if connected.binary_search(&treatment).is_ok() {
+                    self.record_effects(treatment, Effects::All(&connected), &mut sink);
+                }
+                sink
+            })
+            .collect()
+    }
+}
+
+/// A [`MistakeSink`] whose verdicts of one treatment stand for those of all its twins,
+/// so that [`Aid::fold_twins`] only searches from one treatment per class of twins.
+pub(crate) trait TwinSink: MistakeSink + Default + Send {
+    /// The sink as if the verdicts it recorded for one treatment were recorded for each of `n_twins` twins.
+    fn scaled(self, n_twins: usize) -> Self;
+
+    /// The sink that recorded the verdicts of both sinks.
+    fn add(self, other: Self) -> Self;
+
+    /// Runs the `search` that records the verdicts of the `treatment` into the sink,
+    /// for sinks that also observe the search itself.
+    fn search(&mut self, _treatment: usize, search: impl FnOnce(&mut Self)) {
+        search(self)
+    }
+}
+
+impl TwinSink for usize {
+    fn scaled(self, n_twins: usize) -> usize {
+        n_twins * self
+    }
+
+    fn add(self, other: usize) -> usize {
+        self + other
+    }
+}
+
+/// What an adjustment identification distance returns, which decides the mistake sink
+/// that the verdicts of its traversal are recorded into.
+pub trait AidOutput {
+    /// The result of the distance.
+    type Output;
+
+    /// Computes the result from the verdicts of the `aid`.
+    fn compute(self, aid: &Aid) -> Self::Output;
+}
+
+/// The distance as a tuple of (normalized error (in \[0,1]), total number of errors),
+/// as returned by [`ancestor_aid`](super::ancestor_aid) and its siblings.
+#[derive(Clone, Copy, Debug)]
+pub struct Plain;
+
+impl AidOutput for Plain {
+    type Output = (f64, usize);
+
+    fn compute(self, aid: &Aid) -> (f64, usize) {
+        let (truth, guess) = (aid.truth, aid.guess);
+        assert!(
+            guess.n_nodes == truth.n_nodes,
+            "both graphs must contain the same number of nodes"
+        );
+        assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+        if identical(truth, guess) {
+            return (0.0, 0);
+        }
+
+        let verifier_mistakes_found: usize = aid.fold_twins();
+        (
+            verifier_mistakes_found as f64 / aid.n_pairs() as f64,
+            verifier_mistakes_found,
+        )
+    }
+}
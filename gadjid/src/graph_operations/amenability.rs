@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Reports which (treatment, effect) pairs of a single graph are amenable to
+//! identification by adjustment, without comparing it to a true graph,
+//! and how often two graphs disagree on it
+
+use rayon::prelude::*;
+
+use crate::{
+    graph_operations::{get_nam, get_pd_nam},
+    PDAG,
+};
+
+/// Computes the amenability matrix of a DAG or CPDAG, whose entry in row `t` and column `y`
+/// is `true` if the graph is amenable relative to the treatment `t` and the effect `y`,
+/// that is, if the causal effect of `t` on `y` can be identified by adjustment.
+/// Pairs where `y` is no possible descendant of `t` are amenable, since there is no effect to identify,
+/// and the diagonal is `false`, since no node is its own effect.
+/// Each treatment is searched from once, in parallel.
+///
+/// ```
+/// use gadjid::{graph_operations::amenability_matrix, PDAG};
+///
+/// // 0 -- 1, and 2 is not adjacent to any node
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 0],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// assert_eq!(
+///     amenability_matrix(&cpdag),
+///     vec![
+///         vec![false, false, true],
+///         vec![false, false, true],
+///         vec![true, true, false],
+///     ]
+/// );
+/// ```
+pub fn amenability_matrix(graph: &PDAG) -> Vec<Vec<bool>> {
+    crate::rayon::build_global();
+
+    (0..graph.n_nodes)
+        .into_par_iter()
+        .map(|t| {
+            let nam = get_nam(graph, &[t], None);
+            Vec::from_iter((0..graph.n_nodes).map(|y| y != t && !nam.contains(&y)))
+        })
+        .collect()
+}
+
+/// Computes the amenability disagreement between an estimated `guess` DAG or CPDAG and the true `truth`
+/// DAG or CPDAG, the number of (treatment, effect) pairs for which one graph is amenable and the other is not,
+/// that is, on which the [`amenability_matrix`] of both graphs differ.
+/// Returns a tuple of (normalized error (in \[0,1]), total number of errors)
+///
+/// Each of these pairs is a mistake of the ancestor, the oset, and the parent AID, whatever the adjustment,
+/// so the amenability disagreement is a lower bound on each of them, the part of the AID that stems from
+/// the ambiguity of the equivalence class rather than from a wrong adjustment set.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{amenability_disagreement, parent_aid},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// // the guess is not amenable relative to (0, 1), (0, 2), and (1, 0)
+/// assert_eq!(amenability_disagreement(&truth, &guess), (0.5, 3));
+/// assert!(parent_aid(&truth, &guess).1 >= 3);
+/// ```
+pub fn amenability_disagreement(truth: &PDAG, guess: &PDAG) -> (f64, usize) {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    let n = guess.n_nodes;
+    let mistakes: usize = (0..n)
+        .into_par_iter()
+        .map(|t| {
+            let (nam_in_true, nam_in_guess) =
+                (get_nam(truth, &[t], None), get_nam(guess, &[t], None));
+            nam_in_true.symmetric_difference(&nam_in_guess).count()
+        })
+        .sum();
+    (mistakes as f64 / (n * n - n) as f64, mistakes)
+}
+
+/// A diagnostic of which causal effects a single DAG or CPDAG can identify by adjustment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdentifiabilityReport {
+    /// The fraction of the (treatment, effect) pairs the graph is amenable relative to, in \[0,1].
+    pub fraction_amenable: f64,
+    /// For each node, the number of its proper possible descendants
+    /// onto which its effect can be identified by adjustment.
+    pub amenable_effects: Vec<usize>,
+    /// The connected components of the undirected edges of the graph, which are what makes
+    /// effects not amenable, largest first; nodes without undirected edges are omitted.
+    pub non_amenable_clusters: Vec<Vec<usize>>,
+}
+
+/// Summarizes which causal effects a DAG or CPDAG can identify by adjustment,
+/// as a diagnostic for the output of a structure learning algorithm that needs no true graph.
+/// Each treatment is searched from once, in parallel, as for [`amenability_matrix`].
+///
+/// ```
+/// use gadjid::{graph_operations::identifiability_report, PDAG};
+///
+/// // 0 -- 1, and 2 is not adjacent to any node
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 0],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let report = identifiability_report(&cpdag);
+/// assert_eq!(report.fraction_amenable, 4.0 / 6.0);
+/// // the effects of 0 and 1 on their only possible descendants 1 and 0 are not amenable
+/// assert_eq!(report.amenable_effects, vec![0, 0, 0]);
+/// assert_eq!(report.non_amenable_clusters, vec![vec![0, 1]]);
+/// ```
+pub fn identifiability_report(graph: &PDAG) -> IdentifiabilityReport {
+    let n = graph.n_nodes;
+    assert!(n >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    let (amenable_pairs, amenable_effects): (Vec<usize>, Vec<usize>) = (0..n)
+        .into_par_iter()
+        .map(|t| {
+            let (poss_desc, nam) = get_pd_nam(graph, &[t], None);
+            // the treatment itself is a possible descendant, but never in NAM
+            let amenable_effects = poss_desc.len() - 1 - nam.len();
+            (n - 1 - nam.len(), amenable_effects)
+        })
+        .unzip();
+
+    IdentifiabilityReport {
+        fraction_amenable: amenable_pairs.iter().sum::<usize>() as f64 / (n * n - n) as f64,
+        amenable_effects,
+        non_amenable_clusters: undirected_components(graph),
+    }
+}
+
+/// Returns the connected components of the undirected edges of the graph with at least two nodes,
+/// each sorted, largest first and otherwise by their smallest node.
+fn undirected_components(graph: &PDAG) -> Vec<Vec<usize>> {
+    let mut visited = vec![false; graph.n_nodes];
+    let mut components = Vec::new();
+
+    for start in 0..graph.n_nodes {
+        if visited[start] || graph.adjacent_undirected_of(start).is_empty() {
+            continue;
+        }
+        visited[start] = true;
+        let mut component = vec![start];
+        let mut to_visit_stack = vec![start];
+        while let Some(node) = to_visit_stack.pop() {
+            for w in graph.adjacent_undirected_of(node) {
+                if !visited[*w] {
+                    visited[*w] = true;
+                    component.push(*w);
+                    to_visit_stack.push(*w);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+
+    // sorting is stable, so components of equal size stay ordered by their smallest node
+    components.sort_by_key(|component| std::cmp::Reverse(component.len()));
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{ancestor_aid, oset_aid, parent_aid},
+        PDAG,
+    };
+
+    use super::{
+        amenability_disagreement, amenability_matrix, identifiability_report, IdentifiabilityReport,
+    };
+
+    #[test]
+    fn undirected_edges_are_not_amenable() {
+        // 0 -> 1 -- 2
+        // |
+        // 3
+        let cpdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 2], //
+            vec![0, 0, 2, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+        assert_eq!(
+            amenability_matrix(&cpdag),
+            vec![
+                vec![false, true, true, false],
+                vec![true, false, false, true],
+                vec![true, false, false, true],
+                vec![false, false, false, false],
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_identifiability() {
+        // 0 -> 1 -- 2    4 -- 5 -- 6 <- 7
+        // |
+        // 3
+        let mut adjacency = vec![vec![0; 8]; 8];
+        for (from, to, edge) in [
+            (0, 1, 1),
+            (1, 2, 2),
+            (0, 3, 2),
+            (4, 5, 2),
+            (5, 6, 2),
+            (7, 6, 1),
+        ] {
+            adjacency[from][to] = edge;
+        }
+        let cpdag = PDAG::from_dense_row_to_col(adjacency);
+        let matrix = amenability_matrix(&cpdag);
+        let amenable_pairs = matrix
+            .iter()
+            .flatten()
+            .filter(|amenable| **amenable)
+            .count();
+
+        assert_eq!(
+            identifiability_report(&cpdag),
+            IdentifiabilityReport {
+                fraction_amenable: amenable_pairs as f64 / 56.0,
+                amenable_effects: vec![2, 0, 0, 0, 0, 0, 0, 3],
+                non_amenable_clusters: vec![vec![4, 5, 6], vec![0, 3], vec![1, 2]],
+            }
+        );
+    }
+
+    #[test]
+    fn amenability_disagreement_bounds_the_aids() {
+        // anchors at parent directory of Cargo.toml
+        let testgraphs = std::path::PathBuf::from("..").join("testgraphs");
+        let load = |kind: &str, id: usize| {
+            crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-{kind}-{id}.mtx"))
+                    .to_str()
+                    .unwrap(),
+            )
+        };
+
+        for (truth_kind, guess_kind) in [("CPDAG", "CPDAG"), ("DAG", "CPDAG"), ("CPDAG", "DAG")] {
+            for graph_id in 10..=19 {
+                let truth = load(truth_kind, graph_id);
+                let guess = load(guess_kind, 10 + (graph_id + 1) % 10);
+
+                let (truth_matrix, guess_matrix) =
+                    (amenability_matrix(&truth), amenability_matrix(&guess));
+                let differing = truth_matrix
+                    .iter()
+                    .flatten()
+                    .zip(guess_matrix.iter().flatten())
+                    .filter(|(in_truth, in_guess)| in_truth != in_guess)
+                    .count();
+
+                let (normalized, mistakes) = amenability_disagreement(&truth, &guess);
+                assert_eq!(mistakes, differing);
+                assert_eq!(normalized, mistakes as f64 / 90.0);
+                for aid in [ancestor_aid, oset_aid, parent_aid] {
+                    assert!(mistakes <= aid(&truth, &guess).1);
+                }
+                assert_eq!(amenability_disagreement(&guess, &guess), (0.0, 0));
+            }
+        }
+    }
+}
@@ -1,12 +1,12 @@
 // SPDX-License-Identifier: MPL-2.0
 //! Implements the Ancestor Adjustment Intervention Distance (Ancestor-AID) algorithm
 
-use rayon::prelude::*;
-
 use crate::{
     graph_operations::{
+        aid::{Aid, AidOutput, Plain},
         gensearch,
         reachability::{get_pd_nam, get_pd_nam_nva},
+        selected_pairs::{Claims, Effects, MistakeSink},
     },
     PDAG,
 };
@@ -16,89 +16,124 @@ use crate::{
 /// (a PDAG is used for internal representation, but every PDAG is assumed either a DAG or a CPDAG
 ///  currently distances between general PDAGs are not implemented)
 /// Returns a tuple of (normalized error (in \[0,1]), total number of errors)
-// This function largely overlaps with parent_aid in parent_aid.rs; differences ---highlighted--- below
+///
+/// ```
+/// use gadjid::{graph_operations::ancestor_aid, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the guess misses that 2 is a descendant of 0 and 1, and its empty adjustment set
+/// // for the effect of 2 on 1 does not block the path 2 <- 1 of the true graph
+/// assert_eq!(ancestor_aid(&truth, &guess), (0.5, 3));
+/// ```
 pub fn ancestor_aid(truth: &PDAG, guess: &PDAG) -> (f64, usize) {
-    assert!(
-        guess.n_nodes == truth.n_nodes,
-        "both graphs must contain the same number of nodes"
+    ancestor_aid_with(truth, guess, Plain)
+}
+
+/// Computes the ancestor adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG
+/// as the given [`AidOutput`]: [`Plain`] for the distance of [`ancestor_aid`],
+/// or an output that grades or reports the verdicts of the same search from each treatment differently,
+/// such as [`WithBreakdown`](super::WithBreakdown), [`Detailed`](super::Detailed),
+/// [`PerTreatment`](super::PerTreatment), [`WithStats`](super::WithStats),
+/// [`WithTruthSets`](super::WithTruthSets), [`Normalization`](super::Normalization),
+/// [`PartialCredit`](super::PartialCredit), [`PairWeights`](super::PairWeights),
+/// [`KnownEdges`](super::KnownEdges), [`SelectedPairs`](super::SelectedPairs),
+/// [`SelectedEffectSets`](super::SelectedEffectSets), or [`Approximate`](super::Approximate).
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{ancestor_aid, ancestor_aid_with, Plain, SelectedPairs, WithBreakdown},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let distance = ancestor_aid_with(&truth, &guess, Plain);
+/// assert_eq!(distance, ancestor_aid(&truth, &guess));
+/// let breakdown = ancestor_aid_with(&truth, &guess, WithBreakdown);
+/// assert_eq!(breakdown.mistakes(), distance.1);
+/// let selected = ancestor_aid_with(&truth, &guess, SelectedPairs(&[(2, 1)])).unwrap();
+/// assert_eq!(selected.distance(), (1.0, 1));
+/// ```
+pub fn ancestor_aid_with<O: AidOutput>(truth: &PDAG, guess: &PDAG, output: O) -> O::Output {
+    output.compute(&Aid::ancestor(truth, guess))
+}
+
+/// Records the verdict on the ancestor adjustment for the single `treatment`
+/// on each of the given `effects` into the `sink`.
+// This function largely overlaps with record_parent_aid_verdicts in parent_aid.rs; differences ---highlighted--- below
+pub(crate) fn record_ancestor_aid_verdicts(
+    truth: &PDAG,
+    guess: &PDAG,
+    treatment: usize,
+    effects: Effects,
+    sink: &mut impl MistakeSink,
+) {
+    // --- this function differs from parent_aid.rs only in the imports and from here
+
+    // ancestor adjustment
+    let ruletable = crate::graph_operations::ruletables::Ancestors {};
+    let adjustment_set = gensearch(
+        // gensearch yield_starting_vertices 'false' because Ancestors(T)\T is the adjustment set
+        guess,
+        ruletable,
+        [treatment].iter(),
+        false,
     );
-    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
-
-    crate::rayon::build_global();
-
-    let verifier_mistakes_found = (0..guess.n_nodes)
-        .into_par_iter()
-        .map(|treatment| {
-            // --- this function differs from parent_aid.rs only in the imports and from here
-
-            // ancestor adjustment
-            let ruletable = crate::graph_operations::ruletables::Ancestors {};
-            let adjustment_set = gensearch(
-                // gensearch yield_starting_vertices 'false' because Ancestors(T)\T is the adjustment set
-                guess,
-                ruletable,
-                [treatment].iter(),
-                false,
-            );
 
-            // claim that all possible descendants could be affected by the treatment
-            let (claim_possible_effect, nam_in_guess) = get_pd_nam(guess, &[treatment]);
-            // --- to here
-
-            // now we take a look at the nodes in the true graph for which the adj.set. was not valid.
-            let (t_poss_desc_in_truth, nam_in_true, nva_in_true) =
-                get_pd_nam_nva(truth, &[treatment], &adjustment_set);
-
-            let mut mistakes = 0;
-            for y in 0..truth.n_nodes {
-                if y == treatment {
-                    continue; // this case is always correct
-                }
-                // if y is not claimed to be effect of t based on the guess graph
-                if !claim_possible_effect.contains(&y) {
-                    // but possibly a descendant of t in the truth graph.
-                    if t_poss_desc_in_truth.contains(&y) {
-                        // the ancestral order might be wrong, so
-                        // we count a mistake
-                        mistakes += 1;
-                    }
-                } else {
-                    let y_nam_in_guess = nam_in_guess.contains(&y);
-                    let y_nam_in_true = nam_in_true.contains(&y);
-
-                    #[allow(clippy::if_same_then_else)]
-                    // if they disagree on amenability:
-                    if y_nam_in_guess != y_nam_in_true {
-                        mistakes += 1;
-                    }
-                    // if we reach this point, (t,y) is either amenable or non-amenable in both graphs;
-                    // now, if it is amenable but the adjustment set is not valid in the true graph (only in the guess graph)
-                    else if !y_nam_in_true && nva_in_true.contains(&y) {
-                        // we count a mistake
-                        mistakes += 1;
-                    }
-                }
-            }
+    // claim that all possible descendants could be affected by the treatment
+    let (possible_descendants_in_guess, nam_in_guess) =
+        get_pd_nam(guess, &[treatment], effects.of_interest());
+    let claim_possible_effect = |y: usize| possible_descendants_in_guess.contains(&y);
+    // --- to here
 
-            mistakes
-        })
-        .sum();
+    // now we take a look at the nodes in the true graph for which the adj.set. was not valid.
+    let (t_poss_desc_in_truth, nam_in_true, nva_in_true) =
+        get_pd_nam_nva(truth, &[treatment], &adjustment_set, effects.of_interest());
 
-    let n = guess.n_nodes;
-    let comparisons = n * n - n;
-    (
-        verifier_mistakes_found as f64 / comparisons as f64,
-        verifier_mistakes_found,
-    )
+    sink.record_truth_sets(&[treatment], &nam_in_true, &nva_in_true);
+
+    let claims = Claims {
+        is_claimed_effect: claim_possible_effect,
+        poss_desc_in_truth: &t_poss_desc_in_truth,
+        nam_in_guess: &nam_in_guess,
+        nam_in_true: &nam_in_true,
+        // the adjustment set is valid in the guess graph, but maybe not in the true graph
+        is_wrongly_adjusted: |ys: &[usize]| ys.iter().any(|y| nva_in_true.contains(y)),
+    };
+
+    effects.record_verdicts(&[treatment], |ys| claims.verdict(ys), sink);
 }
 
 #[cfg(test)]
 mod test {
+    use crate::graph_operations::{SelectedEffectSets, SelectedPairs};
     use rand::SeedableRng;
 
     use crate::PDAG;
 
-    use super::ancestor_aid;
+    use super::{ancestor_aid, ancestor_aid_with};
 
     #[test]
     fn property_equal_dags_zero_distance() {
@@ -128,4 +163,49 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn property_selected_pairs_agree_with_all_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            assert_eq!(
+                ancestor_aid(&truth, &guess),
+                ancestor_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+            // searches restricted to a single effect must count the same mistakes
+            let mistakes_by_pair: usize = all_pairs
+                .iter()
+                .map(|pair| {
+                    ancestor_aid_with(&truth, &guess, SelectedPairs(&[*pair]))
+                        .unwrap()
+                        .mistakes
+                })
+                .sum();
+            assert_eq!(ancestor_aid(&truth, &guess).1, mistakes_by_pair);
+        }
+    }
+
+    #[test]
+    fn property_singleton_effect_sets_agree_with_selected_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            let singletons = Vec::from_iter(all_pairs.iter().map(|(t, y)| (*t, vec![*y])));
+            assert_eq!(
+                ancestor_aid_with(&truth, &guess, SelectedPairs(&all_pairs)),
+                ancestor_aid_with(&truth, &guess, SelectedEffectSets(&singletons))
+            );
+        }
+    }
 }
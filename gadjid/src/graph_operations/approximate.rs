@@ -0,0 +1,386 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Estimates the adjustment identification distances from a sample of treatments,
+//! for graphs too large to search from every treatment
+
+use rand::{RngCore, SeedableRng};
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{
+        aid::{Aid, AidOutput},
+        get_possible_descendants,
+        selected_pairs::Effects,
+        twins::is_isolated,
+    },
+    PDAG,
+};
+
+/// How the treatments are sampled to estimate a distance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TreatmentSampling {
+    /// Sample the treatments uniformly without replacement.
+    Uniform,
+    /// Sort the nodes by their total number of edges in both graphs, split them into `n_strata`
+    /// strata of (almost) equal size, and sample the treatments uniformly without replacement within
+    /// each stratum, proportionally to its size but at least two per stratum.
+    /// This reduces the variance if the number of mistakes of a treatment depends on its degree.
+    ByDegree {
+        /// the number of strata
+        n_strata: usize,
+    },
+    /// Sample the treatments uniformly without replacement, and for each, sample up to `n_effects`
+    /// effects uniformly without replacement from each of two strata, the possible descendants of the
+    /// treatment in the true graph and the other nodes, and weigh each sampled pair by the inverse of
+    /// its probability to be sampled (Horvitz–Thompson estimation).
+    /// Since mistakes are more frequent among the possible descendants, this reduces the variance
+    /// compared to sampling effects from all nodes, and searches may stop once the sampled effects are resolved.
+    StratifiedPairs {
+        /// the number of effects sampled from each stratum of each treatment
+        n_effects: usize,
+    },
+}
+
+/// An estimate of a normalized distance, as returned for the [`Approximate`] output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ApproximateDistance {
+    /// the estimated normalized distance (in \[0,1])
+    pub estimate: f64,
+    /// the standard error of the estimate
+    pub standard_error: f64,
+    /// the approximate 95% confidence interval of the normalized distance,
+    /// the estimate plus or minus 1.96 standard errors, clipped to \[0,1]
+    pub confidence_interval: (f64, f64),
+    /// the number of sampled treatments
+    pub n_treatments: usize,
+}
+
+/// Splits the nodes into strata of (almost) equal size by their total number of edges in both graphs.
+fn degree_strata(truth: &PDAG, guess: &PDAG, n_strata: usize) -> Vec<Vec<usize>> {
+    let degree = |v: usize| {
+        let edges = |graph: &PDAG| graph.node_edge_ranges[v + 1] - graph.node_edge_ranges[v];
+        edges(truth) + edges(guess)
+    };
+    let mut nodes = Vec::from_iter(0..guess.n_nodes);
+    nodes.sort_by_key(|v| (degree(*v), *v));
+    let n_strata = n_strata.clamp(1, guess.n_nodes);
+    Vec::from_iter(
+        (0..n_strata).map(|h| {
+            nodes[h * guess.n_nodes / n_strata..(h + 1) * guess.n_nodes / n_strata].to_vec()
+        }),
+    )
+}
+
+/// Estimates the distance from the mistakes of about `sample_size` treatments sampled with `rng`
+/// by the given [`TreatmentSampling`], which is faster than the exact distance on large graphs,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+/// The estimate comes alongside its standard error and confidence interval.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{ancestor_aid, ancestor_aid_with, Approximate, TreatmentSampling},
+///     PDAG,
+/// };
+/// use rand::SeedableRng;
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+/// let sampling = TreatmentSampling::Uniform;
+/// let approx = ancestor_aid_with(
+///     &truth,
+///     &guess,
+///     Approximate { sample_size: 3, sampling: &sampling, rng },
+/// );
+/// // sampling all treatments gives the exact distance
+/// assert_eq!(approx.estimate, ancestor_aid(&truth, &guess).0);
+/// assert_eq!(approx.standard_error, 0.0);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Approximate<'a, R> {
+    /// the number of treatments to sample
+    pub sample_size: usize,
+    /// how the treatments are sampled
+    pub sampling: &'a TreatmentSampling,
+    /// the random number generator the treatments are sampled with
+    pub rng: R,
+}
+
+impl<R: RngCore> AidOutput for Approximate<'_, R> {
+    type Output = ApproximateDistance;
+
+    fn compute(self, aid: &Aid) -> ApproximateDistance {
+        let (truth, guess) = (aid.truth(), aid.guess());
+        approximate_aid(
+            truth,
+            guess,
+            self.sample_size,
+            self.sampling,
+            self.rng,
+            |t, effects| aid.mistakes(t, effects),
+        )
+    }
+}
+
+/// Estimates the normalized distance, the mean over all treatments of the fraction of the other nodes
+/// for which the treatment has a mistake, from a sample of about `sample_size` treatments,
+/// given the number of `mistakes` of a treatment over the given effects.
+/// The standard error is that of the (stratified) mean of a sample without replacement.
+pub(crate) fn approximate_aid(
+    truth: &PDAG,
+    guess: &PDAG,
+    sample_size: usize,
+    sampling: &TreatmentSampling,
+    mut rng: impl RngCore,
+    mistakes: impl Fn(usize, Effects) -> usize + Sync,
+) -> ApproximateDistance {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+    assert!(sample_size >= 2, "at least 2 treatments must be sampled");
+
+    let n = guess.n_nodes;
+    let strata = match sampling {
+        TreatmentSampling::Uniform => vec![Vec::from_iter(0..n)],
+        TreatmentSampling::ByDegree { n_strata } => degree_strata(truth, guess, *n_strata),
+        TreatmentSampling::StratifiedPairs { n_effects } => {
+            return approximate_aid_by_pairs(truth, guess, sample_size, *n_effects, rng, mistakes)
+        }
+    };
+    let samples = Vec::from_iter(strata.iter().map(|stratum| {
+        let proportional = (sample_size as f64 * stratum.len() as f64 / n as f64).round() as usize;
+        let stratum_size = proportional.max(2).min(stratum.len());
+        Vec::from_iter(
+            rand::seq::index::sample(&mut rng, stratum.len(), stratum_size)
+                .into_iter()
+                .map(|i| stratum[i]),
+        )
+    }));
+
+    crate::rayon::build_global();
+
+    let connected = Vec::from_iter((0..n).filter(|v| !is_isolated(truth, guess, *v)));
+    let rates = Vec::from_iter(samples.iter().map(|sample| {
+        sample
+            .par_iter()
+            .map(|treatment| match is_isolated(truth, guess, *treatment) {
+                true => 0.0,
+                false => mistakes(*treatment, Effects::All(&connected)) as f64 / (n - 1) as f64,
+            })
+            .collect::<Vec<f64>>()
+    }));
+
+    let mut estimate = 0.0;
+    let mut variance = 0.0;
+    for (stratum, rates) in strata.iter().zip(rates.iter()) {
+        let (size, sampled) = (stratum.len() as f64, rates.len() as f64);
+        let weight = size / n as f64;
+        let mean = rates.iter().sum::<f64>() / sampled;
+        estimate += weight * mean;
+        if rates.len() > 1 {
+            let sample_variance =
+                rates.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (sampled - 1.0);
+            // with the finite population correction, as the treatments are sampled without replacement
+            variance += weight.powi(2) * (1.0 - sampled / size) * sample_variance / sampled;
+        }
+    }
+    let standard_error = variance.sqrt();
+
+    ApproximateDistance {
+        estimate,
+        standard_error,
+        confidence_interval: (
+            (estimate - 1.96 * standard_error).max(0.0),
+            (estimate + 1.96 * standard_error).min(1.0),
+        ),
+        n_treatments: rates.iter().map(|rates| rates.len()).sum(),
+    }
+}
+
+/// Estimates the normalized distance from a two-stage sample, first of treatments and then, for each,
+/// of up to `n_effects` effects from each of the possible descendants and the other nodes,
+/// by the Horvitz–Thompson estimator of the number of mistakes and its unbiased variance estimator.
+fn approximate_aid_by_pairs(
+    truth: &PDAG,
+    guess: &PDAG,
+    sample_size: usize,
+    n_effects: usize,
+    mut rng: impl RngCore,
+    mistakes: impl Fn(usize, Effects) -> usize + Sync,
+) -> ApproximateDistance {
+    assert!(
+        n_effects >= 1,
+        "at least 1 effect per stratum must be sampled"
+    );
+
+    let n = guess.n_nodes;
+    let n_sampled = sample_size.min(n);
+    let treatments = rand::seq::index::sample(&mut rng, n, n_sampled).into_vec();
+    // one generator per treatment, so that the effects can be sampled in parallel
+    let seeds = Vec::from_iter(treatments.iter().map(|_| rng.next_u64()));
+
+    crate::rayon::build_global();
+
+    let connected = Vec::from_iter((0..n).filter(|v| !is_isolated(truth, guess, *v)));
+    // the estimated number of mistakes of each treatment and the variance due to sampling its effects
+    let treatment_estimates: Vec<(f64, f64)> = treatments
+        .par_iter()
+        .zip(seeds.par_iter())
+        .map(|(treatment, seed)| {
+            if is_isolated(truth, guess, *treatment) {
+                return (0.0, 0.0);
+            }
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(*seed);
+            let descendants = get_possible_descendants(truth, [*treatment].iter());
+            let (in_descendants, in_others): (Vec<usize>, Vec<usize>) = connected
+                .iter()
+                .filter(|v| *v != treatment)
+                .partition(|v| descendants.contains(v));
+
+            let (mut estimate, mut variance) = (0.0, 0.0);
+            for stratum in [in_descendants, in_others] {
+                let n_sampled_effects = n_effects.min(stratum.len());
+                if n_sampled_effects == 0 {
+                    continue;
+                }
+                let effects = FxHashSet::from_iter(
+                    rand::seq::index::sample(&mut rng, stratum.len(), n_sampled_effects)
+                        .into_iter()
+                        .map(|i| stratum[i]),
+                );
+                let (size, sampled) = (stratum.len() as f64, n_sampled_effects as f64);
+                let rate = mistakes(*treatment, Effects::Selected(&effects)) as f64 / sampled;
+                estimate += size * rate;
+                if n_sampled_effects > 1 {
+                    let sample_variance = rate * (1.0 - rate) * sampled / (sampled - 1.0);
+                    variance += size.powi(2) * (1.0 - sampled / size) * sample_variance / sampled;
+                }
+            }
+            (estimate, variance)
+        })
+        .collect();
+
+    let (n, sampled) = (n as f64, n_sampled as f64);
+    let mean = treatment_estimates.iter().map(|(e, _)| e).sum::<f64>() / sampled;
+    let between_variance = treatment_estimates
+        .iter()
+        .map(|(e, _)| (e - mean).powi(2))
+        .sum::<f64>()
+        / (sampled - 1.0);
+    let within_variance = treatment_estimates.iter().map(|(_, v)| v).sum::<f64>();
+    let total_variance = n.powi(2) * (1.0 - sampled / n) * between_variance / sampled
+        + n / sampled * within_variance;
+
+    let comparisons = n * (n - 1.0);
+    let estimate = n * mean / comparisons;
+    let standard_error = total_variance.sqrt() / comparisons;
+    ApproximateDistance {
+        estimate,
+        standard_error,
+        confidence_interval: (
+            (estimate - 1.96 * standard_error).max(0.0),
+            (estimate + 1.96 * standard_error).min(1.0),
+        ),
+        n_treatments: n_sampled,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+        },
+        PDAG,
+    };
+
+    use super::{Approximate, ApproximateDistance, TreatmentSampling};
+
+    #[test]
+    fn full_sample_is_exact() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_dag(0.2, 30, &mut rng);
+        let guess = PDAG::random_dag(0.2, 30, &mut rng);
+        for sampling in [
+            TreatmentSampling::Uniform,
+            TreatmentSampling::ByDegree { n_strata: 4 },
+            TreatmentSampling::StratifiedPairs { n_effects: 30 },
+        ] {
+            let approx = oset_aid_with(
+                &truth,
+                &guess,
+                Approximate {
+                    sample_size: 30,
+                    sampling: &sampling,
+                    rng: &mut rng,
+                },
+            );
+            assert!((approx.estimate - oset_aid(&truth, &guess).0).abs() < 1e-12);
+            assert_eq!(approx.standard_error, 0.0);
+            assert_eq!(approx.n_treatments, 30);
+        }
+    }
+
+    #[test]
+    fn estimates_are_close_to_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_pdag(0.05, 150, &mut rng);
+        let guess = PDAG::random_pdag(0.05, 150, &mut rng);
+        // the 95% confidence intervals miss the distance in 1 of 20 cases,
+        // so this checks a wider margin of 4 standard errors
+        let is_close = |approx: ApproximateDistance, exact: (f64, usize)| {
+            approx.standard_error > 0.0
+                && (approx.estimate - exact.0).abs() <= 4.0 * approx.standard_error
+        };
+        for sampling in [
+            TreatmentSampling::Uniform,
+            TreatmentSampling::ByDegree { n_strata: 5 },
+            TreatmentSampling::StratifiedPairs { n_effects: 20 },
+        ] {
+            let approx = ancestor_aid_with(
+                &truth,
+                &guess,
+                Approximate {
+                    sample_size: 40,
+                    sampling: &sampling,
+                    rng: &mut rng,
+                },
+            );
+            assert!(is_close(approx, ancestor_aid(&truth, &guess)));
+            let approx = oset_aid_with(
+                &truth,
+                &guess,
+                Approximate {
+                    sample_size: 40,
+                    sampling: &sampling,
+                    rng: &mut rng,
+                },
+            );
+            assert!(is_close(approx, oset_aid(&truth, &guess)));
+            let approx = parent_aid_with(
+                &truth,
+                &guess,
+                Approximate {
+                    sample_size: 40,
+                    sampling: &sampling,
+                    rng: &mut rng,
+                },
+            );
+            assert!(is_close(approx, parent_aid(&truth, &guess)));
+        }
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Summarizes an ensemble of graphs by a single graph under the structural hamming distance,
+//! either the medoid graph of the ensemble or a barycenter graph found by local search
+
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    graph_loading::edgelist::Edgelist,
+    graph_operations::{
+        ensemble::{check_ensemble, count_edges},
+        shd, EnsembleError,
+    },
+    PDAG,
+};
+
+/// Returns the index of the medoid graph of an ensemble of DAGs or CPDAGs, the graph with the smallest
+/// total structural hamming distance to all graphs of the ensemble (the first such graph if tied).
+///
+/// Fails if the ensemble is empty or its graphs differ in size.
+///
+/// ```
+/// use gadjid::{graph_operations::shd_medoid, PDAG};
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// assert_eq!(shd_medoid(&graphs).unwrap(), 0);
+/// assert!(shd_medoid(&[]).is_err());
+/// ```
+pub fn shd_medoid(graphs: &[PDAG]) -> Result<usize, EnsembleError> {
+    check_ensemble(graphs)?;
+
+    crate::rayon::build_global();
+
+    let totals: Vec<usize> = graphs
+        .par_iter()
+        .map(|medoid| graphs.iter().map(|graph| shd(medoid, graph).1).sum())
+        .collect();
+    // the ensemble is not empty, so there is a minimum
+    let smallest = *totals.iter().min().expect("the ensemble is not empty");
+    Ok(totals
+        .iter()
+        .position(|total| *total == smallest)
+        .unwrap_or(0))
+}
+
+/// The possible states of the edge between two nodes v < w, in the order that breaks ties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edge {
+    Absent = 0,
+    Undirected = 1,
+    Forward = 2,
+    Backward = 3,
+}
+
+const EDGES: [Edge; 4] = [
+    Edge::Absent,
+    Edge::Undirected,
+    Edge::Forward,
+    Edge::Backward,
+];
+
+impl Edge {
+    /// Returns the directed edge `(from, to)` between v < w, if this state is directed.
+    fn directed(self, (v, w): (usize, usize)) -> Option<(usize, usize)> {
+        match self {
+            Edge::Forward => Some((v, w)),
+            Edge::Backward => Some((w, v)),
+            Edge::Absent | Edge::Undirected => None,
+        }
+    }
+}
+
+/// Returns the edges of a directed path from `from` to `to`, if there is one.
+fn directed_path(
+    children: &[FxHashSet<usize>],
+    from: usize,
+    to: usize,
+) -> Option<Vec<(usize, usize)>> {
+    let mut predecessor = FxHashMap::default();
+    let mut to_visit_stack = vec![from];
+    while let Some(current_node) = to_visit_stack.pop() {
+        if current_node == to {
+            let mut path = Vec::new();
+            let mut node = to;
+            while node != from {
+                let parent = predecessor[&node];
+                path.push((parent, node));
+                node = parent;
+            }
+            return Some(path);
+        }
+        for child in children[current_node].iter() {
+            if *child != from && !predecessor.contains_key(child) {
+                predecessor.insert(*child, current_node);
+                to_visit_stack.push(*child);
+            }
+        }
+    }
+    None
+}
+
+/// The edge states of a candidate barycenter, alongside how often each state occurs in the ensemble.
+struct Candidate {
+    pairs: Vec<(usize, usize)>,
+    counts: Vec<[usize; 4]>,
+    states: Vec<Edge>,
+    children: Vec<FxHashSet<usize>>,
+}
+
+impl Candidate {
+    fn set(&mut self, pair: usize, state: Edge) {
+        if let Some((from, to)) = self.states[pair].directed(self.pairs[pair]) {
+            self.children[from].remove(&to);
+        }
+        if let Some((from, to)) = state.directed(self.pairs[pair]) {
+            self.children[from].insert(to);
+        }
+        self.states[pair] = state;
+    }
+
+    fn count(&self, pair: usize, state: Edge) -> usize {
+        self.counts[pair][state as usize]
+    }
+
+    /// Returns whether setting the pair to the state keeps the directed edges acyclic,
+    /// assuming the pair is currently not directed.
+    fn is_acyclic_with(&self, pair: usize, state: Edge) -> bool {
+        match state.directed(self.pairs[pair]) {
+            Some((from, to)) => directed_path(&self.children, to, from).is_none(),
+            None => true,
+        }
+    }
+
+    /// Finds and applies a change of the pair to a more frequent state that decreases the total
+    /// distance, which may require to undirect or remove another edge to avoid a directed cycle.
+    fn improve(&mut self, pair: usize, pair_index: &FxHashMap<(usize, usize), usize>) -> bool {
+        let current = self.states[pair];
+        for state in EDGES {
+            let gain = match self
+                .count(pair, state)
+                .checked_sub(self.count(pair, current))
+            {
+                Some(gain) if gain > 0 => gain,
+                _ => continue,
+            };
+            self.set(pair, Edge::Absent);
+            let Some((from, to)) = state.directed(self.pairs[pair]) else {
+                self.set(pair, state);
+                return true;
+            };
+            let Some(cycle_path) = directed_path(&self.children, to, from) else {
+                self.set(pair, state);
+                return true;
+            };
+            for (x, y) in cycle_path {
+                let other = pair_index[&(x.min(y), x.max(y))];
+                let other_state = self.states[other];
+                let replacement =
+                    match self.count(other, Edge::Absent) >= self.count(other, Edge::Undirected) {
+                        true => Edge::Absent,
+                        false => Edge::Undirected,
+                    };
+                let loss = self.count(other, other_state) - self.count(other, replacement);
+                if loss >= gain {
+                    continue;
+                }
+                self.set(other, replacement);
+                if self.is_acyclic_with(pair, state) {
+                    self.set(pair, state);
+                    return true;
+                }
+                self.set(other, other_state);
+            }
+            self.set(pair, current);
+        }
+        false
+    }
+}
+
+/// Computes a barycenter graph of an ensemble of DAGs or CPDAGs, a PDAG with a small total
+/// structural hamming distance to all graphs of the ensemble, and returns it alongside that total.
+///
+/// Since the total distance adds up over the pairs of nodes, it is smallest if each pair takes
+/// its most frequent edge in the ensemble, unless these edges form a directed cycle. Starting with
+/// the pairs whose most frequent edge is most frequent, each pair takes its most frequent edge
+/// that does not close a directed cycle. Then, hill climbing changes single edges to more frequent ones,
+/// undirecting or removing another edge of a directed cycle this would close if that decreases the total.
+/// The result is a local optimum, and an exact barycenter if no directed cycle needed to be avoided.
+///
+/// Fails if the ensemble is empty or its graphs differ in size.
+///
+/// ```
+/// use gadjid::{graph_operations::shd_barycenter, PDAG};
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// let (barycenter, total) = shd_barycenter(&graphs).unwrap();
+/// assert_eq!(barycenter.to_row_to_column_vecvec(), graphs[0].to_row_to_column_vecvec());
+/// assert_eq!(total, 1);
+/// ```
+pub fn shd_barycenter(graphs: &[PDAG]) -> Result<(PDAG, usize), EnsembleError> {
+    let n_nodes = check_ensemble(graphs)?;
+
+    // pairs that are not adjacent in any graph are not adjacent in the barycenter either
+    let mut edge_counts = Vec::from_iter(count_edges(graphs, n_nodes));
+    edge_counts.sort_unstable_by_key(|(pair, _)| *pair);
+    let pairs = Vec::from_iter(edge_counts.iter().map(|(pair, _)| *pair));
+    let counts = Vec::from_iter(
+        edge_counts
+            .iter()
+            .map(|(_, [forward, backward, undirected])| {
+                let absent = graphs.len() - forward - backward - undirected;
+                [absent, *undirected, *forward, *backward]
+            }),
+    );
+    let pair_index = FxHashMap::from_iter(pairs.iter().enumerate().map(|(i, pair)| (*pair, i)));
+
+    let mut candidate = Candidate {
+        states: vec![Edge::Absent; pairs.len()],
+        children: vec![FxHashSet::default(); n_nodes],
+        pairs,
+        counts,
+    };
+
+    let mut order = Vec::from_iter(0..candidate.pairs.len());
+    order.sort_by_key(|pair| std::cmp::Reverse(candidate.counts[*pair].iter().max().copied()));
+    for pair in order {
+        let mut states = EDGES;
+        // stable, so that ties keep the order of EDGES
+        states.sort_by_key(|state| std::cmp::Reverse(candidate.count(pair, *state)));
+        // an absent edge never closes a directed cycle
+        let state = states
+            .into_iter()
+            .find(|state| candidate.is_acyclic_with(pair, *state))
+            .unwrap_or(Edge::Absent);
+        candidate.set(pair, state);
+    }
+
+    // every improvement decreases the total distance, so the hill climbing terminates
+    while (0..candidate.pairs.len()).any(|pair| candidate.improve(pair, &pair_index)) {}
+
+    let mut dense = vec![vec![0; n_nodes]; n_nodes];
+    let mut total = 0;
+    for (pair, (v, w)) in candidate.pairs.iter().enumerate() {
+        let state = candidate.states[pair];
+        total += graphs.len() - candidate.count(pair, state);
+        match state {
+            Edge::Absent => {}
+            Edge::Undirected => dense[*v][*w] = 2,
+            Edge::Forward => dense[*v][*w] = 1,
+            Edge::Backward => dense[*w][*v] = 1,
+        }
+    }
+
+    let barycenter =
+        PDAG::try_from_row_major(Edgelist::from_vecvec(dense)).map_err(EnsembleError::Load)?;
+    Ok((barycenter, total))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{shd, EnsembleError},
+        PDAG,
+    };
+
+    use super::{shd_barycenter, shd_medoid};
+
+    /// Builds a graph on three nodes from its directed and undirected edges.
+    fn graph(directed: &[(usize, usize)], undirected: &[(usize, usize)]) -> PDAG {
+        let mut dense = vec![vec![0; 3]; 3];
+        for (v, w) in directed {
+            dense[*v][*w] = 1;
+        }
+        for (v, w) in undirected {
+            dense[*v][*w] = 2;
+        }
+        PDAG::from_dense_row_to_col(dense)
+    }
+
+    #[test]
+    fn finds_medoid_and_barycenter() {
+        let graphs = [
+            graph(&[(0, 1), (1, 2)], &[]),
+            graph(&[(0, 1), (2, 1)], &[]),
+            graph(&[(1, 2), (0, 2)], &[(0, 1)]),
+        ];
+        assert_eq!(shd_medoid(&graphs), Ok(0));
+        let (barycenter, total) = shd_barycenter(&graphs).unwrap();
+        assert_eq!(barycenter, graphs[0]);
+        assert_eq!(total, 3);
+
+        assert_eq!(shd_medoid(&[]), Err(EnsembleError::Empty));
+    }
+
+    #[test]
+    fn avoids_directed_cycles() {
+        // the most frequent edges 0 -> 1 -> 2 -> 0 form a directed cycle
+        let graphs = [
+            graph(&[(0, 1), (1, 2)], &[]),
+            graph(&[(1, 2), (2, 0)], &[]),
+            graph(&[(2, 0), (0, 1)], &[]),
+        ];
+        let (barycenter, total) = shd_barycenter(&graphs).unwrap();
+        assert_eq!(barycenter, graphs[2]);
+        assert_eq!(total, 4);
+    }
+
+    #[test]
+    fn hill_climbing_improves_greedy_choice() {
+        // the most frequent edges 0 -> 1 (5 of 9 graphs), 1 -> 2 (5 of 9), and 2 -> 0 (4 of 9)
+        // form a directed cycle; 0 -> 1 and 1 -> 2 are taken first, but the total distance
+        // decreases from 15 to 14 if either is removed in favor of 2 -> 0, since the next most
+        // frequent edge between 0 and 2 is rare, while 1 and 2 are not adjacent in 4 of 9 graphs
+        let graphs = [
+            graph(&[(0, 1), (2, 0)], &[]),
+            graph(&[(0, 1), (2, 0)], &[]),
+            graph(&[(0, 1), (2, 0)], &[]),
+            graph(&[(0, 1), (2, 0)], &[]),
+            graph(&[(0, 1), (1, 2), (0, 2)], &[]),
+            graph(&[(1, 2), (0, 2)], &[]),
+            graph(&[(1, 2)], &[(0, 2)]),
+            graph(&[(1, 2)], &[(0, 2)]),
+            graph(&[(1, 2)], &[]),
+        ];
+        let (barycenter, total) = shd_barycenter(&graphs).unwrap();
+        assert_eq!(barycenter, graph(&[(0, 1), (2, 0)], &[]));
+        assert_eq!(total, 14);
+        assert_eq!(
+            graphs.iter().map(|g| shd(&barycenter, g).1).sum::<usize>(),
+            total
+        );
+    }
+}
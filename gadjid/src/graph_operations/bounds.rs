@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Bounds the Parent-AID from the neighbourhoods of each node, without any graph search,
+//! to discard candidate graphs in model selection before computing their exact distance.
+//!
+//! The lower bound counts the pairs `(t, y)` where the guess takes `y` as a parent of `t`
+//! while `y` is a child of `t` or adjacent to it by an undirected edge in the true graph:
+//! the guess then does not claim `y` as a possible effect of `t`, but `y` is a possible
+//! descendant of `t` in the true graph, which is always a mistake.
+//!
+//! The upper bound only counts mistakes for treatments whose neighbourhoods differ.
+//! If `t` has the same parents in both graphs and no undirected edges in either,
+//! every effect of `t` is amenable in both graphs, the parents of `t` are the same set in
+//! every DAG the true graph represents and thus a valid adjustment set for each non-parent,
+//! and no parent of `t` is a possible descendant of `t`, so `t` has no mistakes as treatment.
+//! Every other treatment has at most one mistake per node that has an edge in either graph.
+
+use crate::{graph_operations::twins::is_isolated, PDAG};
+
+/// Bounds on the number of mistakes of a distance, as returned by [`parent_aid_bounds`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DistanceBounds {
+    /// A lower bound on the number of mistakes.
+    pub lower: usize,
+    /// An upper bound on the number of mistakes.
+    pub upper: usize,
+    /// The number of (treatment, effect) pairs, which the distance is normalized by.
+    pub comparisons: usize,
+}
+
+impl DistanceBounds {
+    /// Returns the lower and the upper bound normalized by the number of comparisons, in \[0,1].
+    pub fn normalized(&self) -> (f64, f64) {
+        (
+            self.lower as f64 / self.comparisons as f64,
+            self.upper as f64 / self.comparisons as f64,
+        )
+    }
+}
+
+/// Bounds the parent adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG
+/// in time linear in the number of edges, see the [module documentation](self) for how.
+/// Both bounds are zero for identical graphs; otherwise, they can be far apart.
+///
+/// ```
+/// use gadjid::{graph_operations::{parent_aid, parent_aid_bounds}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let bounds = parent_aid_bounds(&truth, &guess);
+/// let (_, mistakes) = parent_aid(&truth, &guess);
+/// assert!(bounds.lower <= mistakes && mistakes <= bounds.upper);
+/// assert_eq!((bounds.lower, bounds.upper), (1, 4));
+/// ```
+pub fn parent_aid_bounds(truth: &PDAG, guess: &PDAG) -> DistanceBounds {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    let n = guess.n_nodes;
+    let n_connected = (0..n).filter(|v| !is_isolated(truth, guess, *v)).count();
+
+    let mut lower = 0;
+    let mut upper = 0;
+    for t in 0..n {
+        lower += guess
+            .parents_of(t)
+            .iter()
+            .filter(|y| {
+                truth.children_of(t).binary_search(y).is_ok()
+                    || truth.adjacent_undirected_of(t).binary_search(y).is_ok()
+            })
+            .count();
+        let no_mistakes_as_treatment = truth.parents_of(t) == guess.parents_of(t)
+            && truth.adjacent_undirected_of(t).is_empty()
+            && guess.adjacent_undirected_of(t).is_empty();
+        if !no_mistakes_as_treatment {
+            upper += n_connected - 1;
+        }
+    }
+
+    DistanceBounds {
+        lower,
+        upper,
+        comparisons: n * n - n,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::parent_aid, PDAG};
+
+    use super::parent_aid_bounds;
+
+    #[test]
+    fn property_bounds_enclose_parent_aid() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            for density in [0.1, 0.3, 0.7] {
+                let dag = PDAG::random_dag(density, n, &mut rng);
+                let pdag = PDAG::random_pdag(density, n, &mut rng);
+                let other_dag = PDAG::random_dag(density, n, &mut rng);
+                for (truth, guess) in [(&dag, &pdag), (&pdag, &dag), (&dag, &other_dag)] {
+                    let bounds = parent_aid_bounds(truth, guess);
+                    let (normalized, mistakes) = parent_aid(truth, guess);
+                    assert!(bounds.lower <= mistakes && mistakes <= bounds.upper);
+                    let (lower, upper) = bounds.normalized();
+                    assert!(lower <= normalized && normalized <= upper);
+                }
+                let bounds = parent_aid_bounds(&dag, &dag);
+                assert_eq!((bounds.lower, bounds.upper), (0, 0));
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Decomposes the mistakes of the adjustment identification distances by their cause
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput, TwinSink},
+    selected_pairs::{MistakeSink, Verdict},
+};
+
+/// The mistakes of an adjustment identification distance, split by their cause,
+/// as returned for the [`WithBreakdown`] output. Every mistake has exactly one cause, so the causes sum to the mistakes of the distance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DistanceBreakdown {
+    /// Mistakes for which the guess graph claims that the effect is not a possible effect of the treatment,
+    /// while it is a possible descendant in the true graph, so the causal order is wrong.
+    pub wrong_order: usize,
+    /// Mistakes for which the graphs disagree on whether the effect is amenable relative to the treatment.
+    pub amenability: usize,
+    /// Mistakes for which both graphs claim amenability, but the guess graph's adjustment set
+    /// is not valid in the true graph.
+    pub invalid_adjustment: usize,
+    /// The number of (treatment, effect) pairs, which the distance is normalized by.
+    pub comparisons: usize,
+}
+
+impl DistanceBreakdown {
+    /// The total number of mistakes, as counted by the distance.
+    pub fn mistakes(&self) -> usize {
+        self.wrong_order + self.amenability + self.invalid_adjustment
+    }
+
+    /// The normalized distance (in \[0,1]), as returned by the distance.
+    pub fn normalized(&self) -> f64 {
+        self.mistakes() as f64 / self.comparisons as f64
+    }
+}
+
+impl TwinSink for DistanceBreakdown {
+    fn scaled(self, n_twins: usize) -> DistanceBreakdown {
+        DistanceBreakdown {
+            wrong_order: n_twins * self.wrong_order,
+            amenability: n_twins * self.amenability,
+            invalid_adjustment: n_twins * self.invalid_adjustment,
+            comparisons: n_twins * self.comparisons,
+        }
+    }
+
+    fn add(self, other: DistanceBreakdown) -> DistanceBreakdown {
+        DistanceBreakdown {
+            wrong_order: self.wrong_order + other.wrong_order,
+            amenability: self.amenability + other.amenability,
+            invalid_adjustment: self.invalid_adjustment + other.invalid_adjustment,
+            comparisons: self.comparisons + other.comparisons,
+        }
+    }
+}
+
+impl MistakeSink for DistanceBreakdown {
+    fn record(&mut self, _: &[usize], _: &[usize], verdict: Verdict) {
+        match verdict {
+            Verdict::Correct => (),
+            Verdict::WrongOrder => self.wrong_order += 1,
+            Verdict::AmenableInGuess | Verdict::NotAmenableInGuess => self.amenability += 1,
+            Verdict::WronglyAdjusted => self.invalid_adjustment += 1,
+        }
+    }
+}
+
+/// Splits the mistakes by their cause when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings:
+/// a wrong causal order, a disagreement on amenability,
+/// or an adjustment set that is not valid in the true graph.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, WithBreakdown}, PDAG};
+///
+/// // truth: 0 -> 1, guess: 0 <- 1
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1], //
+///     vec![0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0], //
+///     vec![1, 0],
+/// ]);
+/// let breakdown = ancestor_aid_with(&truth, &guess, WithBreakdown);
+/// // the guess wrongly claims that 1 is not an effect of 0,
+/// // and that the effect of 1 on 0 needs no adjustment, while 0 -> 1 confounds it
+/// assert_eq!((breakdown.wrong_order, breakdown.invalid_adjustment), (1, 1));
+/// assert_eq!((breakdown.normalized(), breakdown.mistakes()), (1.0, 2));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WithBreakdown;
+
+impl AidOutput for WithBreakdown {
+    type Output = DistanceBreakdown;
+
+    fn compute(self, aid: &Aid) -> DistanceBreakdown {
+        DistanceBreakdown {
+            comparisons: aid.n_pairs(),
+            ..aid.fold_twins()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            WithBreakdown,
+        },
+        PDAG,
+    };
+
+    use super::DistanceBreakdown;
+
+    #[test]
+    fn reversed_edge_is_a_wrong_order() {
+        // truth: 0 -> 1, guess: 0 <- 1
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1], //
+            vec![0, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0], //
+            vec![1, 0],
+        ]);
+        // the guess claims that 1 is no effect of 0, and that 0 is an effect of 1
+        // with empty adjustment set, which is not valid in the truth
+        let expected = DistanceBreakdown {
+            wrong_order: 1,
+            amenability: 0,
+            invalid_adjustment: 1,
+            comparisons: 2,
+        };
+        assert_eq!(parent_aid_with(&truth, &guess, WithBreakdown), expected);
+        assert_eq!(ancestor_aid_with(&truth, &guess, WithBreakdown), expected);
+    }
+
+    #[test]
+    fn descendant_in_optimal_set_is_an_invalid_adjustment() {
+        // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+        let truth = PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]]);
+        let guess = PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 1, 0]]);
+        // the guess claims that 2 is no effect of 0 or 1, and its optimal adjustment sets {2}
+        // for the effect of 0 on 1 and {0} for the effect of 2 on 1 are not valid in the truth
+        let expected = DistanceBreakdown {
+            wrong_order: 2,
+            amenability: 0,
+            invalid_adjustment: 2,
+            comparisons: 6,
+        };
+        assert_eq!(oset_aid_with(&truth, &guess, WithBreakdown), expected);
+    }
+
+    #[test]
+    fn undirected_edge_is_an_amenability_disagreement() {
+        // truth: 0 -> 1, guess: 0 -- 1
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1], //
+            vec![0, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2], //
+            vec![0, 0],
+        ]);
+        let breakdown = parent_aid_with(&truth, &guess, WithBreakdown);
+        assert_eq!(breakdown.amenability, 2);
+        assert_eq!(breakdown.mistakes(), 2);
+        assert_eq!(breakdown.normalized(), 1.0);
+    }
+
+    #[test]
+    fn property_causes_sum_to_the_distances() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+
+        let load = |kind: &str, id: usize| {
+            crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-{kind}-{id}.mtx"))
+                    .to_str()
+                    .unwrap(),
+            )
+        };
+        for kind in ["DAG", "CPDAG"] {
+            for graph_id in 10..=19 {
+                let truth = load(kind, graph_id);
+                let guess = load(kind, 10 + (graph_id + 1) % 10);
+                for (breakdown, (normalized, mistakes)) in [
+                    (
+                        parent_aid_with(&truth, &guess, WithBreakdown),
+                        parent_aid(&truth, &guess),
+                    ),
+                    (
+                        ancestor_aid_with(&truth, &guess, WithBreakdown),
+                        ancestor_aid(&truth, &guess),
+                    ),
+                    (
+                        oset_aid_with(&truth, &guess, WithBreakdown),
+                        oset_aid(&truth, &guess),
+                    ),
+                ] {
+                    assert_eq!(breakdown.mistakes(), mistakes);
+                    assert_eq!(breakdown.normalized(), normalized);
+                }
+                if kind == "DAG" {
+                    // all pairs are amenable in DAGs
+                    assert_eq!(
+                        parent_aid_with(&truth, &guess, WithBreakdown).amenability,
+                        0
+                    );
+                }
+            }
+        }
+    }
+}
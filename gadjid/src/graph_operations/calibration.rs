@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Calibrates distances against random guessing, by scoring randomly generated graphs against the true graph
+
+use rand::{RngCore, SeedableRng};
+use rayon::prelude::*;
+
+use crate::{partially_directed_acyclic_graph::P_DIRECTED, PDAG};
+
+/// The random graphs that a null distribution is sampled from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RandomGraphs {
+    /// DAGs with independent edges of the given density, as generated by [`PDAG::random_dag`]
+    Dag {
+        /// the probability of an edge between any two nodes
+        edge_density: f64,
+    },
+    /// PDAGs with independent edges of the given density, of which some are undirected,
+    /// as generated by [`PDAG::random_pdag`]
+    Pdag {
+        /// the probability of an edge between any two nodes
+        edge_density: f64,
+    },
+}
+
+impl RandomGraphs {
+    /// Returns random graphs with the same edge density as the `guess` graph,
+    /// which are DAGs if the guess graph has no undirected edges, and PDAGs otherwise.
+    ///
+    /// ```
+    /// use gadjid::{graph_operations::RandomGraphs, PDAG};
+    ///
+    /// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+    /// let truth = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 0, 0],
+    /// ]);
+    /// let guess = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ]);
+    ///
+    /// // two of the three pairs of nodes are adjacent
+    /// assert_eq!(
+    ///     RandomGraphs::like(&guess),
+    ///     RandomGraphs::Dag { edge_density: 2.0 / 3.0 }
+    /// );
+    /// ```
+    pub fn like(guess: &PDAG) -> RandomGraphs {
+        let n_pairs = guess.n_nodes * guess.n_nodes.saturating_sub(1) / 2;
+        let edge_density = match n_pairs {
+            0 => 0.0,
+            _ => (guess.n_directed_edges + guess.n_undirected_edges) as f64 / n_pairs as f64,
+        };
+        match guess.n_undirected_edges {
+            0 => RandomGraphs::Dag { edge_density },
+            _ => RandomGraphs::Pdag { edge_density },
+        }
+    }
+
+    /// Samples a random graph with `n_nodes` nodes.
+    fn sample(&self, n_nodes: usize, rng: impl RngCore) -> PDAG {
+        match self {
+            RandomGraphs::Dag { edge_density } => PDAG::random_dag(*edge_density, n_nodes, rng),
+            RandomGraphs::Pdag { edge_density } => PDAG::random_pdag(*edge_density, n_nodes, rng),
+        }
+    }
+}
+
+/// The distances of random graphs to a true graph, as returned by [`null_distribution`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NullDistribution {
+    /// The normalized distances of the random graphs, in ascending order.
+    pub samples: Vec<f64>,
+}
+
+impl NullDistribution {
+    /// Returns the percentile of the normalized distance `score` of a guess graph in the null distribution,
+    /// the percentage of random graphs that are at most as far from the true graph,
+    /// so that a small percentile means that the guess graph is better than chance.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::NullDistribution;
+    ///
+    /// let null = NullDistribution {
+    ///     samples: vec![0.2, 0.4, 0.6, 0.8],
+    /// };
+    /// assert_eq!(null.percentile(0.4), 50.0);
+    /// assert_eq!(null.percentile(0.1), 0.0);
+    /// ```
+    pub fn percentile(&self, score: f64) -> f64 {
+        let at_most = self.samples.partition_point(|sample| *sample <= score);
+        100.0 * at_most as f64 / self.samples.len() as f64
+    }
+
+    /// Returns the mean normalized distance of the random graphs, a Monte Carlo estimate of the expected
+    /// normalized distance under random guessing, which is not available in closed form for the
+    /// adjustment identification distances (unlike for the SHD, see [`expected_random_shd`]).
+    ///
+    /// ```
+    /// use gadjid::graph_operations::NullDistribution;
+    ///
+    /// let null = NullDistribution {
+    ///     samples: vec![0.2, 0.4, 0.6, 0.8],
+    /// };
+    /// assert_eq!(null.mean(), 0.5);
+    /// ```
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+}
+
+/// Computes the expected structural hamming distance between the `truth` graph and a random graph,
+/// returning a tuple of (expected normalized distance (in \[0,1]), expected number of errors).
+///
+/// The SHD adds up over the pairs of nodes, and in the random graphs, the pairs are adjacent
+/// independently of each other with probability `edge_density`, and any given edge is equally likely
+/// to point either way. So, an edge is missed with probability `1 - edge_density` and a non-adjacent pair
+/// is joined with probability `edge_density`, while an edge is guessed right with probability
+/// `edge_density / 2` if directed in both graphs, and with probability `edge_density * (1 - 0.8)`
+/// if undirected in both graphs, as 80% of the edges of random PDAGs are directed.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{expected_random_shd, RandomGraphs},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // each of the two edges is missed or reversed with probability 3/4,
+/// // and the non-adjacent pair of nodes is joined with probability 1/2
+/// let random_graphs = RandomGraphs::Dag { edge_density: 0.5 };
+/// assert_eq!(expected_random_shd(&truth, &random_graphs), (2.0 / 3.0, 2.0));
+/// ```
+pub fn expected_random_shd(truth: &PDAG, random_graphs: &RandomGraphs) -> (f64, f64) {
+    let (edge_density, p_directed) = match random_graphs {
+        RandomGraphs::Dag { edge_density } => (*edge_density, 1.0),
+        RandomGraphs::Pdag { edge_density } => (*edge_density, P_DIRECTED),
+    };
+    assert!(
+        (0.0..=1.0).contains(&edge_density),
+        "edge probability must be in [0, 1]"
+    );
+    let n_pairs = truth.n_nodes * truth.n_nodes.saturating_sub(1) / 2;
+    if n_pairs == 0 {
+        return (0.0, 0.0);
+    }
+    let n_absent = n_pairs - truth.n_directed_edges - truth.n_undirected_edges;
+
+    let expected = n_absent as f64 * edge_density
+        + truth.n_directed_edges as f64 * (1.0 - edge_density * p_directed / 2.0)
+        + truth.n_undirected_edges as f64 * (1.0 - edge_density * (1.0 - p_directed));
+    (expected / n_pairs as f64, expected)
+}
+
+/// Computes the `distance` from the `truth` graph to `n_samples` random graphs, such as
+/// `null_distribution(&truth, 1000, &RandomGraphs::like(&guess), oset_aid, &mut rng)`,
+/// to tell how much closer a guess graph is to the truth than random guessing.
+///
+/// The graphs are sampled from random number generators seeded by `rng`, one per graph,
+/// so that the distribution only depends on `rng` and they can be scored in parallel.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{null_distribution, shd, RandomGraphs},
+///     PDAG,
+/// };
+/// use rand::SeedableRng;
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+/// let null = null_distribution(&truth, 100, &RandomGraphs::like(&guess), shd, rng);
+/// assert_eq!(null.samples.len(), 100);
+/// let percentile = null.percentile(shd(&truth, &guess).0);
+/// assert!((0.0..=100.0).contains(&percentile));
+/// ```
+pub fn null_distribution(
+    truth: &PDAG,
+    n_samples: usize,
+    random_graphs: &RandomGraphs,
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize) + Sync,
+    mut rng: impl RngCore,
+) -> NullDistribution {
+    assert!(n_samples > 0, "there must be at least one sample");
+    let seeds = Vec::from_iter((0..n_samples).map(|_| rng.next_u64()));
+
+    crate::rayon::build_global();
+
+    let mut samples: Vec<f64> = seeds
+        .into_par_iter()
+        .map(|seed| {
+            let rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+            distance(truth, &random_graphs.sample(truth.n_nodes, rng)).0
+        })
+        .collect();
+    samples.sort_unstable_by(f64::total_cmp);
+    NullDistribution { samples }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{oset_aid, shd},
+        PDAG,
+    };
+
+    use super::{expected_random_shd, null_distribution, NullDistribution, RandomGraphs};
+
+    #[test]
+    fn matches_density_of_guess() {
+        // 0 -> 1 -> 2, 0 -- 3
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 2], //
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+        assert_eq!(
+            RandomGraphs::like(&guess),
+            RandomGraphs::Pdag { edge_density: 0.5 }
+        );
+        let dag = PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]);
+        assert_eq!(
+            RandomGraphs::like(&dag),
+            RandomGraphs::Dag { edge_density: 1.0 }
+        );
+    }
+
+    #[test]
+    fn computes_null_distribution_and_percentile() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_dag(0.3, 12, &mut rng);
+        let random_graphs = RandomGraphs::like(&truth);
+
+        let null = null_distribution(&truth, 200, &random_graphs, oset_aid, &mut rng);
+        assert_eq!(null.samples.len(), 200);
+        assert!(null.samples.windows(2).all(|w| w[0] <= w[1]));
+        // the true graph itself is better than any random graph that is not equal to it
+        assert_eq!(null.percentile(oset_aid(&truth, &truth).0), 0.0);
+        assert_eq!(null.percentile(1.0), 100.0);
+
+        // the same seed gives the same distribution
+        let again = null_distribution(
+            &truth,
+            200,
+            &random_graphs,
+            oset_aid,
+            rand_chacha::ChaCha8Rng::seed_from_u64(1),
+        );
+        let same = null_distribution(
+            &truth,
+            200,
+            &random_graphs,
+            oset_aid,
+            rand_chacha::ChaCha8Rng::seed_from_u64(1),
+        );
+        assert_eq!(again, same);
+
+        let null = NullDistribution {
+            samples: vec![0.1, 0.2, 0.2, 0.4],
+        };
+        assert_eq!(null.percentile(0.2), 75.0);
+        assert_eq!(null.percentile(0.05), 0.0);
+        let empty_truth = PDAG::random_dag(0.0, 5, &mut rng);
+        let null = null_distribution(
+            &empty_truth,
+            10,
+            &RandomGraphs::Dag { edge_density: 0.0 },
+            shd,
+            &mut rng,
+        );
+        assert_eq!(null.samples, vec![0.0; 10]);
+    }
+
+    #[test]
+    fn expected_shd_matches_monte_carlo() {
+        // 0 -> 1, with the non-adjacent pairs 0, 2 and 1, 2
+        let dag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 0],
+            vec![0, 0, 0],
+        ]);
+        let random_dags = RandomGraphs::Dag { edge_density: 0.5 };
+        assert_eq!(
+            expected_random_shd(&dag, &random_dags),
+            (1.75 / 3.0, 0.75 + 0.5 + 0.5)
+        );
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for random_graphs in [
+            RandomGraphs::Dag { edge_density: 0.3 },
+            RandomGraphs::Pdag { edge_density: 0.6 },
+        ] {
+            let truth = PDAG::random_pdag(0.4, 10, &mut rng);
+            let null = null_distribution(&truth, 2000, &random_graphs, shd, &mut rng);
+            let (expected, _) = expected_random_shd(&truth, &random_graphs);
+            assert!(
+                (null.mean() - expected).abs() < 0.01,
+                "{} vs {expected}",
+                null.mean()
+            );
+        }
+    }
+}
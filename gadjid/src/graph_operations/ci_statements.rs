@@ -0,0 +1,395 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Compares the conditional independence statements implied by two DAGs or CPDAGs,
+//! and tests the statements implied by a graph against data
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{
+        components::shared_components,
+        separation::{dag_in_class, is_d_separated},
+        shd::edge_type,
+    },
+    PDAG,
+};
+
+/// The conditional independence statements on which two graphs disagree,
+/// as returned by [`ci_statement_distance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CiStatementDistance {
+    /// The number of statements "x and y are independent given Z" implied by the true graph only.
+    pub only_in_truth: usize,
+    /// The number of statements "x and y are independent given Z" implied by the guess graph only.
+    pub only_in_guess: usize,
+    /// The number of statements compared, which the distance is normalized by.
+    pub statements: usize,
+}
+
+impl CiStatementDistance {
+    /// The number of statements implied by one graph but not the other.
+    pub fn mistakes(&self) -> usize {
+        self.only_in_truth + self.only_in_guess
+    }
+
+    /// The number of mistakes normalized by the number of statements compared, in \[0,1].
+    pub fn normalized(&self) -> f64 {
+        self.mistakes() as f64 / self.statements as f64
+    }
+}
+
+/// Compares the conditional independence statements "x and y are independent given Z" that the
+/// true `truth` and the estimated `guess` DAG or CPDAG imply by d-separation, for all pairs of nodes
+/// x, y and all sets Z of at most `max_condition_size` other nodes, and counts the statements implied
+/// by one graph but not the other. Unlike the adjustment identification distances, which grade
+/// what a graph implies for causal effects, this grades what it implies for the observed distribution,
+/// so graphs in the same Markov equivalence class have a distance of 0.
+///
+/// The statements of a CPDAG are those of any DAG in its Markov equivalence class. Pairs that are
+/// adjacent in both graphs are dependent given any set in both, and pairs that are connected by no path
+/// in either graph are independent given any set in both, so their statements are not enumerated.
+/// There are n(n-1)/2 times the sum of (n-2 choose k) for k up to `max_condition_size` statements,
+/// so keep `max_condition_size` small for larger graphs.
+///
+/// ```
+/// use gadjid::{graph_operations::ci_statement_distance, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let distance = ci_statement_distance(&truth, &guess, 1);
+/// // the truth implies that 0 and 2 are independent given {1}, the guess that they are independent given {}
+/// assert_eq!((distance.only_in_truth, distance.only_in_guess), (1, 1));
+/// // three pairs, each with the empty set and one set of one other node
+/// assert_eq!(distance.statements, 6);
+/// ```
+pub fn ci_statement_distance(
+    truth: &PDAG,
+    guess: &PDAG,
+    max_condition_size: usize,
+) -> CiStatementDistance {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    let n = truth.n_nodes;
+    let (truth_dag, guess_dag) = (dag_in_class(truth), dag_in_class(guess));
+    // no path of either graph joins two of their shared components
+    let mut component = vec![0; n];
+    for (index, nodes) in shared_components(truth, guess).iter().enumerate() {
+        for v in nodes {
+            component[*v] = index;
+        }
+    }
+
+    let pairs = Vec::from_iter((0..n).flat_map(|x| (x + 1..n).map(move |y| (x, y))));
+    let (only_in_truth, only_in_guess) = pairs
+        .into_par_iter()
+        .filter(|(x, y)| !(edge_type(truth, *x, *y) != 0 && edge_type(guess, *x, *y) != 0))
+        .filter(|(x, y)| component[*x] == component[*y])
+        .map(|(x, y)| {
+            let others = Vec::from_iter((0..n).filter(|v| *v != x && *v != y));
+            let (mut only_in_truth, mut only_in_guess) = (0, 0);
+            for_each_subset(&others, max_condition_size, |z| {
+                let z = FxHashSet::from_iter(z.iter().copied());
+                match (
+                    is_d_separated(&truth_dag, &[x], &[y], &z),
+                    is_d_separated(&guess_dag, &[x], &[y], &z),
+                ) {
+                    (true, false) => only_in_truth += 1,
+                    (false, true) => only_in_guess += 1,
+                    _ => (),
+                }
+            });
+            (only_in_truth, only_in_guess)
+        })
+        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1));
+
+    let sets_per_pair: usize = (0..=max_condition_size.min(n - 2))
+        .map(|k| n_choose_k(n - 2, k))
+        .sum();
+    CiStatementDistance {
+        only_in_truth,
+        only_in_guess,
+        statements: n * (n - 1) / 2 * sets_per_pair,
+    }
+}
+
+/// A conditional independence statement "x and y are independent given the nodes in `given`".
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CiStatement {
+    /// the first node, the smaller of both
+    pub x: usize,
+    /// the second node
+    pub y: usize,
+    /// the conditioning set, sorted
+    pub given: Vec<usize>,
+}
+
+/// Returns the conditional independence statements that the DAG or CPDAG `graph` implies by d-separation,
+/// for pairs of non-adjacent nodes and conditioning sets of at most `max_condition_size` nodes,
+/// ordered by the size of the conditioning set and then by the pair, so that the statements which
+/// need the least data to test come first. Adjacent nodes are not d-separated given any set.
+///
+/// ```
+/// use gadjid::{graph_operations::{implied_independencies, CiStatement}, PDAG};
+///
+/// // 0 -> 1 -> 2
+/// let dag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// assert_eq!(
+///     implied_independencies(&dag, 2),
+///     vec![CiStatement { x: 0, y: 2, given: vec![1] }]
+/// );
+/// ```
+pub fn implied_independencies(graph: &PDAG, max_condition_size: usize) -> Vec<CiStatement> {
+    crate::rayon::build_global();
+
+    let n = graph.n_nodes;
+    let dag = dag_in_class(graph);
+    let pairs = Vec::from_iter((0..n).flat_map(|x| (x + 1..n).map(move |y| (x, y))));
+    let mut statements: Vec<CiStatement> = pairs
+        .into_par_iter()
+        .filter(|(x, y)| edge_type(graph, *x, *y) == 0)
+        .flat_map_iter(|(x, y)| {
+            let others = Vec::from_iter((0..n).filter(|v| *v != x && *v != y));
+            let mut statements = Vec::new();
+            for_each_subset(&others, max_condition_size, |z| {
+                if is_d_separated(&dag, &[x], &[y], &FxHashSet::from_iter(z.iter().copied())) {
+                    statements.push(CiStatement {
+                        x,
+                        y,
+                        given: z.to_vec(),
+                    });
+                }
+            });
+            statements
+        })
+        .collect();
+    statements.sort_by(|a, b| {
+        (a.given.len(), a.x, a.y, &a.given).cmp(&(b.given.len(), b.x, b.y, &b.given))
+    });
+    statements
+}
+
+/// The outcome of testing the statements implied by a graph against data, as returned by [`markov_property_test`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkovPropertyTest {
+    /// the number of statements tested
+    pub tested: usize,
+    /// the statements that the conditional independence test rejected, in the order they were tested
+    pub rejected: Vec<CiStatement>,
+}
+
+/// Tests the conditional independence statements that the DAG or CPDAG `graph` implies, as listed by
+/// [`implied_independencies`], against data: `is_independent` runs a conditional independence test
+/// on a statement, such as a partial correlation test on a dataset, and returns whether the test
+/// accepts the independence. Tests the first `max_tests` statements if given, and all otherwise.
+/// Many rejections suggest that the graph does not fit the data; mind that the tests are not
+/// corrected for multiple testing.
+///
+/// ```
+/// use gadjid::{graph_operations::markov_property_test, PDAG};
+///
+/// // 0 -> 1 -> 2
+/// let dag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// // an oracle for data from 0 -> 1 <- 2, where 0 and 2 are independent only marginally
+/// let result = markov_property_test(&dag, 1, None, |statement| statement.given.is_empty());
+/// assert_eq!(result.tested, 1);
+/// assert_eq!(result.rejected[0].given, vec![1]);
+/// ```
+pub fn markov_property_test(
+    graph: &PDAG,
+    max_condition_size: usize,
+    max_tests: Option<usize>,
+    mut is_independent: impl FnMut(&CiStatement) -> bool,
+) -> MarkovPropertyTest {
+    let statements = implied_independencies(graph, max_condition_size);
+    let statements = &statements[..max_tests.map_or(statements.len(), |m| m.min(statements.len()))];
+    MarkovPropertyTest {
+        tested: statements.len(),
+        rejected: Vec::from_iter(
+            statements
+                .iter()
+                .filter(|statement| !is_independent(statement))
+                .cloned(),
+        ),
+    }
+}
+
+/// Calls `f` on every subset of the `nodes` with at most `max_size` elements, in increasing size.
+fn for_each_subset(nodes: &[usize], max_size: usize, mut f: impl FnMut(&[usize])) {
+    let mut subset = Vec::new();
+    for size in 0..=max_size.min(nodes.len()) {
+        // the indices of the elements of the subset, in increasing order
+        let mut indices = Vec::from_iter(0..size);
+        loop {
+            subset.clear();
+            subset.extend(indices.iter().map(|i| nodes[*i]));
+            f(&subset);
+            // advances to the next combination in lexicographic order
+            let Some(i) = (0..size)
+                .rev()
+                .find(|i| indices[*i] < nodes.len() - size + i)
+            else {
+                break;
+            };
+            indices[i] += 1;
+            for j in i + 1..size {
+                indices[j] = indices[j - 1] + 1;
+            }
+        }
+    }
+}
+
+fn n_choose_k(n: usize, k: usize) -> usize {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rustc_hash::FxHashSet;
+
+    use crate::{
+        graph_operations::separation::{dag_in_class, is_d_separated},
+        PDAG,
+    };
+
+    use super::{
+        ci_statement_distance, for_each_subset, implied_independencies, markov_property_test,
+        n_choose_k,
+    };
+
+    #[test]
+    fn enumerates_subsets_up_to_size() {
+        let nodes = [3, 5, 7, 9];
+        for max_size in 0..=5 {
+            let mut subsets = Vec::new();
+            for_each_subset(&nodes, max_size, |z| subsets.push(z.to_vec()));
+            let expected: usize = (0..=max_size.min(4)).map(|k| n_choose_k(4, k)).sum();
+            assert_eq!(subsets.len(), expected);
+            let mut distinct = subsets.clone();
+            distinct.sort();
+            distinct.dedup();
+            assert_eq!(distinct.len(), subsets.len());
+        }
+    }
+
+    #[test]
+    fn markov_equivalent_graphs_agree() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+
+        for graph_id in 10..=19 {
+            let cpdag = crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-CPDAG-{}.mtx", graph_id))
+                    .to_str()
+                    .unwrap(),
+            );
+            let distance = ci_statement_distance(&cpdag, &dag_in_class(&cpdag), 2);
+            assert_eq!(distance.mistakes(), 0);
+            assert_eq!(distance.statements, 45 * (1 + 8 + 28));
+        }
+    }
+
+    #[test]
+    fn property_pruning_matches_enumeration() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..9 {
+            let truth = PDAG::random_dag(0.3, n, &mut rng);
+            let guess = PDAG::random_dag(0.3, n, &mut rng);
+            let distance = ci_statement_distance(&truth, &guess, n);
+            let reversed = ci_statement_distance(&guess, &truth, n);
+            assert_eq!(
+                (reversed.only_in_truth, reversed.only_in_guess),
+                (distance.only_in_guess, distance.only_in_truth)
+            );
+
+            // the empty graph implies every statement, so it implies all that the truth does
+            // and one more for every statement that the truth does not imply
+            let empty = PDAG::from_dense_row_to_col(vec![vec![0; n]; n]);
+            let to_empty = ci_statement_distance(&truth, &empty, n);
+            assert_eq!(to_empty.only_in_truth, 0);
+            let dag = dag_in_class(&truth);
+            let mut implied = 0;
+            for x in 0..n {
+                for y in x + 1..n {
+                    let others = Vec::from_iter((0..n).filter(|v| *v != x && *v != y));
+                    for_each_subset(&others, n, |z| {
+                        let z = FxHashSet::from_iter(z.iter().copied());
+                        if is_d_separated(&dag, &[x], &[y], &z) {
+                            implied += 1;
+                        }
+                    });
+                }
+            }
+            assert_eq!(to_empty.statements - to_empty.only_in_guess, implied);
+        }
+    }
+
+    #[test]
+    fn implied_independencies_are_consistent_with_the_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for n in 2..9 {
+            let graph = PDAG::random_dag(0.3, n, &mut rng);
+            let statements = implied_independencies(&graph, 2);
+            assert!(statements
+                .windows(2)
+                .all(|w| w[0].given.len() <= w[1].given.len()));
+            // the empty graph implies every statement
+            let empty = PDAG::from_dense_row_to_col(vec![vec![0; n]; n]);
+            let distance = ci_statement_distance(&graph, &empty, 2);
+            assert_eq!(
+                statements.len(),
+                distance.statements - distance.only_in_guess
+            );
+        }
+    }
+
+    #[test]
+    fn oracle_of_the_true_graph_rejects_nothing() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(2);
+        let truth = PDAG::random_dag(0.3, 8, &mut rng);
+        let guess = PDAG::random_dag(0.3, 8, &mut rng);
+        let truth_dag = dag_in_class(&truth);
+        let oracle = |statement: &super::CiStatement| {
+            let given = FxHashSet::from_iter(statement.given.iter().copied());
+            is_d_separated(&truth_dag, &[statement.x], &[statement.y], &given)
+        };
+        let result = markov_property_test(&truth, 2, None, oracle);
+        assert!(result.rejected.is_empty());
+        // the guess is rejected on exactly the statements it implies and the truth does not
+        let result = markov_property_test(&guess, 2, None, oracle);
+        assert_eq!(
+            result.rejected.len(),
+            ci_statement_distance(&truth, &guess, 2).only_in_guess
+        );
+        assert_eq!(
+            markov_property_test(&guess, 2, Some(3), oracle).tested,
+            3.min(result.tested)
+        );
+    }
+}
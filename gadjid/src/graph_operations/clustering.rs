@@ -0,0 +1,228 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Clusters an ensemble of graphs hierarchically by their pairwise distances,
+//! such as those computed by [`cross_consistency`](crate::graph_operations::cross_consistency)
+
+use crate::graph_operations::EnsembleError;
+
+/// The linkage that defines the distance between two clusters from the distances between their graphs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Linkage {
+    /// the smallest distance between a graph of the one and a graph of the other cluster
+    Single,
+    /// the largest distance between a graph of the one and a graph of the other cluster
+    Complete,
+    /// the mean distance between the graphs of the one and the graphs of the other cluster
+    Average,
+}
+
+impl Linkage {
+    /// Returns the distance of the union of the clusters `a` and `b`, with `n_a` and `n_b` graphs,
+    /// to a third cluster, given the distances `d_a` and `d_b` of `a` and `b` to it.
+    fn merged_distance(self, d_a: f64, d_b: f64, n_a: usize, n_b: usize) -> f64 {
+        match self {
+            Linkage::Single => d_a.min(d_b),
+            Linkage::Complete => d_a.max(d_b),
+            Linkage::Average => (n_a as f64 * d_a + n_b as f64 * d_b) / (n_a + n_b) as f64,
+        }
+    }
+}
+
+/// The clusters of an ensemble of graphs, as returned by [`cluster_graphs`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Clustering {
+    /// The cluster of each graph, numbered in the order of the first graph of each cluster.
+    pub assignments: Vec<usize>,
+    /// The index of the medoid graph of each cluster, the graph with the smallest
+    /// total distance to the other graphs of its cluster (the first such graph if tied).
+    pub medoids: Vec<usize>,
+}
+
+/// Clusters the graphs of an ensemble into `n_clusters` clusters by agglomerative hierarchical clustering
+/// with the given `linkage`, based on the square matrix of their pairwise `distances`,
+/// whose entry in row `i` and column `j` is the distance between graph `i` and graph `j`.
+///
+/// Since the adjustment identification distances are not symmetric, the distance
+/// between two graphs is taken to be the mean of the distances in both directions.
+/// Of equally close pairs of clusters, the pair with the first graphs is merged first.
+///
+/// Fails if the matrix is not square or `n_clusters` is not between one and the number of graphs.
+///
+/// ```
+/// use gadjid::graph_operations::{cluster_graphs, Linkage};
+///
+/// // graphs 0 and 1 are close to each other, and far from graph 2
+/// let distances = vec![
+///     vec![0.0, 0.1, 0.9],
+///     vec![0.2, 0.0, 0.8],
+///     vec![0.9, 0.7, 0.0],
+/// ];
+/// let clustering = cluster_graphs(&distances, 2, Linkage::Average).unwrap();
+/// assert_eq!(clustering.assignments, vec![0, 0, 1]);
+/// assert_eq!(clustering.medoids, vec![0, 2]);
+/// ```
+pub fn cluster_graphs(
+    distances: &[Vec<f64>],
+    n_clusters: usize,
+    linkage: Linkage,
+) -> Result<Clustering, EnsembleError> {
+    let k = distances.len();
+    if let Some(row) = distances.iter().position(|row| row.len() != k) {
+        return Err(EnsembleError::NotSquare {
+            row,
+            len: distances[row].len(),
+            expected: k,
+        });
+    }
+    if n_clusters == 0 || n_clusters > k {
+        return Err(EnsembleError::InvalidClusterCount {
+            n_clusters,
+            n_graphs: k,
+        });
+    }
+
+    let symmetric = |i: usize, j: usize| (distances[i][j] + distances[j][i]) / 2.0;
+
+    // the cluster in slot i contains graph i and no graph before it,
+    // since clusters are always merged into the slot of the earlier cluster
+    let mut clusters = Vec::from_iter((0..k).map(|i| Some(vec![i])));
+    let mut cluster_distances =
+        Vec::from_iter((0..k).map(|i| Vec::from_iter((0..k).map(|j| symmetric(i, j)))));
+
+    for _ in n_clusters..k {
+        let active = Vec::from_iter((0..k).filter(|i| clusters[*i].is_some()));
+        let mut closest: Option<(f64, usize, usize)> = None;
+        for (position, i) in active.iter().enumerate() {
+            for j in active[position + 1..].iter() {
+                let d = cluster_distances[*i][*j];
+                let closer = match closest {
+                    Some((smallest, _, _)) => d < smallest,
+                    None => true,
+                };
+                if closer {
+                    closest = Some((d, *i, *j));
+                }
+            }
+        }
+        // there are more active clusters than n_clusters >= 1, so at least one pair
+        let (_, a, b) = closest.expect("at least two clusters are left to merge");
+
+        let merged = clusters[b].take().expect("active clusters are not empty");
+        let n_a = clusters[a]
+            .as_ref()
+            .expect("active clusters are not empty")
+            .len();
+        for c in active.iter().filter(|c| **c != a && **c != b) {
+            let d = linkage.merged_distance(
+                cluster_distances[a][*c],
+                cluster_distances[b][*c],
+                n_a,
+                merged.len(),
+            );
+            cluster_distances[a][*c] = d;
+            cluster_distances[*c][a] = d;
+        }
+        if let Some(members) = clusters[a].as_mut() {
+            members.extend(merged);
+        }
+    }
+
+    let mut assignments = vec![0; k];
+    let mut medoids = Vec::with_capacity(n_clusters);
+    for mut members in clusters.into_iter().flatten() {
+        members.sort_unstable();
+        let total_distance = |g: usize| members.iter().map(|m| symmetric(g, *m)).sum::<f64>();
+        let mut medoid = (members[0], total_distance(members[0]));
+        for g in members.iter().skip(1) {
+            let total = total_distance(*g);
+            if total < medoid.1 {
+                medoid = (*g, total);
+            }
+        }
+        for g in members.iter() {
+            assignments[*g] = medoids.len();
+        }
+        medoids.push(medoid.0);
+    }
+
+    Ok(Clustering {
+        assignments,
+        medoids,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::graph_operations::EnsembleError;
+
+    use super::{cluster_graphs, Clustering, Linkage};
+
+    #[test]
+    fn clusters_asymmetric_distances() {
+        // the graphs 0, 1, 2 and the graphs 3, 4 are close, with graph 1 in the middle of the first group
+        let distances = vec![
+            vec![0.0, 0.5, 3.0, 9.0, 9.0],
+            vec![1.5, 0.0, 0.0, 9.0, 9.0],
+            vec![3.0, 2.0, 0.0, 9.0, 9.0],
+            vec![9.0, 9.0, 9.0, 0.0, 1.0],
+            vec![9.0, 9.0, 9.0, 1.0, 0.0],
+        ];
+        for linkage in [Linkage::Single, Linkage::Complete, Linkage::Average] {
+            assert_eq!(
+                cluster_graphs(&distances, 2, linkage),
+                Ok(Clustering {
+                    assignments: vec![0, 0, 0, 1, 1],
+                    medoids: vec![1, 3],
+                })
+            );
+        }
+        assert_eq!(
+            cluster_graphs(&distances, 5, Linkage::Single).unwrap(),
+            Clustering {
+                assignments: vec![0, 1, 2, 3, 4],
+                medoids: vec![0, 1, 2, 3, 4],
+            }
+        );
+        assert_eq!(
+            cluster_graphs(&distances, 1, Linkage::Single)
+                .unwrap()
+                .medoids,
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn linkages_differ_on_chains() {
+        // graphs at the positions 0, 3, 5, and 6.5 on a line
+        let positions = [0.0, 3.0, 5.0, 6.5f64];
+        let distances = Vec::from_iter(
+            positions
+                .iter()
+                .map(|x| Vec::from_iter(positions.iter().map(|y| (x - y).abs()))),
+        );
+        let assignments = |linkage| cluster_graphs(&distances, 2, linkage).unwrap().assignments;
+        assert_eq!(assignments(Linkage::Single), vec![0, 1, 1, 1]);
+        assert_eq!(assignments(Linkage::Average), vec![0, 1, 1, 1]);
+        assert_eq!(assignments(Linkage::Complete), vec![0, 0, 1, 1]);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let distances = vec![vec![0.0, 1.0], vec![1.0]];
+        assert_eq!(
+            cluster_graphs(&distances, 1, Linkage::Average),
+            Err(EnsembleError::NotSquare {
+                row: 1,
+                len: 1,
+                expected: 2
+            })
+        );
+        let distances = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        assert_eq!(
+            cluster_graphs(&distances, 3, Linkage::Average),
+            Err(EnsembleError::InvalidClusterCount {
+                n_clusters: 3,
+                n_graphs: 2
+            })
+        );
+    }
+}
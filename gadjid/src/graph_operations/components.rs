@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Decomposes the distances between two graphs over their shared connected components
+
+use rayon::prelude::*;
+
+use crate::PDAG;
+
+/// The distance restricted to one connected component, as returned by [`distance_by_components`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentDistance {
+    /// the nodes of the component, in ascending order
+    pub nodes: Vec<usize>,
+    /// the distance between the subgraphs induced by the nodes
+    pub distance: (f64, usize),
+}
+
+/// Returns the weakly connected components of the union of both graphs, which are the
+/// finest partition of the nodes such that no edge of either graph joins two of its parts.
+/// Components are sorted by their smallest node, and their nodes are in ascending order.
+pub(crate) fn shared_components(truth: &PDAG, guess: &PDAG) -> Vec<Vec<usize>> {
+    let n = truth.n_nodes;
+    let mut representative = Vec::from_iter(0..n);
+    fn find(representative: &mut [usize], mut v: usize) -> usize {
+        while representative[v] != v {
+            representative[v] = representative[representative[v]];
+            v = representative[v];
+        }
+        v
+    }
+    for graph in [truth, guess] {
+        for v in 0..n {
+            for w in graph
+                .children_of(v)
+                .iter()
+                .chain(graph.adjacent_undirected_of(v))
+            {
+                let (a, b) = (find(&mut representative, v), find(&mut representative, *w));
+                representative[a.max(b)] = a.min(b);
+            }
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut component_of_root = vec![usize::MAX; n];
+    for v in 0..n {
+        let root = find(&mut representative, v);
+        if component_of_root[root] == usize::MAX {
+            component_of_root[root] = components.len();
+            components.push(Vec::new());
+        }
+        components[component_of_root[root]].push(v);
+    }
+    components
+}
+
+/// Computes the `distance` between the `truth` and the `guess` graph separately on each connected
+/// component shared by both graphs, in parallel, such as for block-diagonal graphs learned from
+/// several datasets. Returns the distance between the whole graphs alongside the distance on each
+/// component with at least two nodes (single nodes are not adjacent to any other node in either graph).
+///
+/// No edge of either graph joins two components, so pairs of nodes in different components
+/// are never a mistake of the adjustment identification distances or the SHD. The distance between
+/// the whole graphs is the sum of the mistakes, normalized like the distance, which is assumed to be
+/// normalized by a multiple of the number of pairs of nodes, such as the AIDs and the SHD.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{distance_by_components, shd},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1, 2 -> 3, guess: 0 -> 1, 2 <- 3
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 1],
+///     vec![0, 0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 1, 0],
+/// ]);
+/// let (distance, components) = distance_by_components(&truth, &guess, shd);
+/// assert_eq!(distance, shd(&truth, &guess));
+/// assert_eq!(components[1].nodes, vec![2, 3]);
+/// assert_eq!(components[1].distance, (1.0, 1));
+/// ```
+pub fn distance_by_components(
+    truth: &PDAG,
+    guess: &PDAG,
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize) + Sync,
+) -> ((f64, usize), Vec<ComponentDistance>) {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    let components: Vec<ComponentDistance> = shared_components(truth, guess)
+        .into_par_iter()
+        .filter(|nodes| nodes.len() >= 2)
+        .map(|nodes| {
+            let distance = distance(
+                &truth.induced_subgraph(&nodes),
+                &guess.induced_subgraph(&nodes),
+            );
+            ComponentDistance { nodes, distance }
+        })
+        .collect();
+
+    let n_pairs = |n: usize| (n * (n - 1)) as f64;
+    let mistakes = components.iter().map(|c| c.distance.1).sum();
+    let normalized = components
+        .iter()
+        .map(|c| c.distance.0 * n_pairs(c.nodes.len()))
+        .sum::<f64>()
+        / n_pairs(truth.n_nodes);
+    ((normalized, mistakes), components)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{ancestor_aid, oset_aid, parent_aid, shd},
+        PDAG,
+    };
+
+    use super::{distance_by_components, shared_components};
+
+    /// Places the graphs on the diagonal of one larger graph, followed by `n_isolated` isolated nodes.
+    fn block_diagonal(blocks: &[&PDAG], n_isolated: usize) -> PDAG {
+        let n = blocks.iter().map(|b| b.n_nodes).sum::<usize>() + n_isolated;
+        let mut dense = vec![vec![0; n]; n];
+        let mut offset = 0;
+        for block in blocks {
+            for (i, row) in block.to_row_to_column_vecvec().into_iter().enumerate() {
+                dense[offset + i][offset..offset + row.len()].copy_from_slice(&row);
+            }
+            offset += block.n_nodes;
+        }
+        PDAG::from_dense_row_to_col(dense)
+    }
+
+    #[test]
+    fn finds_shared_components() {
+        // truth: 0 -> 1, 2 -> 3; guess: 1 -- 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0], //
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0, 0, 0], //
+            vec![0, 0, 2, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(
+            shared_components(&truth, &guess),
+            vec![vec![0, 1, 2, 3], vec![4]]
+        );
+        assert_eq!(
+            truth.induced_subgraph(&[1, 2, 3]),
+            PDAG::from_dense_row_to_col(vec![
+                vec![0, 0, 0], //
+                vec![0, 0, 1],
+                vec![0, 0, 0],
+            ])
+        );
+    }
+
+    #[test]
+    fn property_decomposition_agrees_with_whole_graphs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..12 {
+            let truth_blocks = [
+                PDAG::random_pdag(0.5, n, &mut rng),
+                PDAG::random_pdag(0.5, n + 3, &mut rng),
+            ];
+            let guess_blocks = [
+                PDAG::random_pdag(0.5, n, &mut rng),
+                PDAG::random_pdag(0.5, n + 3, &mut rng),
+            ];
+            let truth = block_diagonal(&[&truth_blocks[0], &truth_blocks[1]], 2);
+            let guess = block_diagonal(&[&guess_blocks[0], &guess_blocks[1]], 2);
+            for distance in [ancestor_aid, oset_aid, parent_aid, shd] {
+                let (whole, components) = distance_by_components(&truth, &guess, distance);
+                let expected = distance(&truth, &guess);
+                assert_eq!(whole.1, expected.1);
+                assert!((whole.0 - expected.0).abs() < 1e-12);
+                // no component crosses the blocks or contains the isolated nodes
+                for component in components {
+                    let block = |v: &usize| (*v >= n) as usize + (*v >= 2 * n + 3) as usize;
+                    assert!(component
+                        .nodes
+                        .iter()
+                        .all(|v| block(v) == block(&component.nodes[0])));
+                    assert!(component.nodes.iter().all(|v| *v < 2 * n + 3));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Implements the Conditional Adjustment Intervention Distance (Conditional-AID) algorithm,
+//! which grades conditional treatment effects of a treatment on an effect given a conditioning set
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{
+        get_invalidly_un_blocked, get_pd_nam,
+        selected_pairs::{
+            group_by_treatment_and_conditioning_set, Claims, Effects, PairsError,
+            SelectedPairsDistance,
+        },
+        twins::is_isolated,
+    },
+    PDAG,
+};
+
+/// Gets all the possible ancestors of a set of nodes.
+/// The input nodes are also included in the output.
+fn get_possible_ancestors<'a>(
+    pdag: &PDAG,
+    starting_vertices: impl Iterator<Item = &'a usize>,
+) -> FxHashSet<usize> {
+    let mut to_visit_stack = Vec::from_iter(starting_vertices.copied());
+    let mut result = FxHashSet::from_iter(to_visit_stack.iter().copied());
+
+    while let Some(current_node) = to_visit_stack.pop() {
+        for p in pdag.possible_parents_of(current_node) {
+            if result.insert(*p) {
+                to_visit_stack.push(*p);
+            }
+        }
+    }
+
+    result
+}
+
+/// Returns the (sorted) adjustment set for the conditional effect of the `treatment` on the `effects`
+/// given the `conditioning` set: all possible ancestors of the treatment, effects, and
+/// conditioning nodes that are neither effects nor possible descendants of the treatment.
+/// It contains the conditioning set if no conditioning node is a possible descendant
+/// of the treatment, and then it is valid if any valid adjustment set that contains
+/// the conditioning set and no possible descendant of the treatment is.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::conditional_adjustment_set_given_possible_descendants, PDAG,
+/// };
+///
+/// // 2 -> 0 -> 1 <- 2, 3 -> 1
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![1, 1, 0, 0],
+///     vec![0, 1, 0, 0],
+/// ]);
+///
+/// let z = conditional_adjustment_set_given_possible_descendants(&graph, 0, &[1], &[3], &[0, 1]);
+/// assert_eq!(z, vec![2, 3]);
+/// ```
+pub fn conditional_adjustment_set_given_possible_descendants(
+    graph: &PDAG,
+    treatment: usize,
+    effects: &[usize],
+    conditioning: &[usize],
+    t_poss_descendants: &[usize],
+) -> Vec<usize> {
+    let t_poss_descendants = FxHashSet::from_iter(t_poss_descendants.iter().copied());
+    let adjustment_set =
+        conditional_adjustment_set(graph, treatment, effects, conditioning, &t_poss_descendants);
+    let mut adjustment_set = Vec::from_iter(adjustment_set);
+    adjustment_set.sort_unstable();
+    adjustment_set
+}
+
+/// Returns the adjustment set of [`conditional_adjustment_set_given_possible_descendants`] as a hash set.
+fn conditional_adjustment_set(
+    graph: &PDAG,
+    treatment: usize,
+    effects: &[usize],
+    conditioning: &[usize],
+    t_poss_descendants: &FxHashSet<usize>,
+) -> FxHashSet<usize> {
+    let starting_vertices = [treatment].into_iter().chain(effects.iter().copied());
+    let starting_vertices = Vec::from_iter(starting_vertices.chain(conditioning.iter().copied()));
+    let mut adjustment_set = get_possible_ancestors(graph, starting_vertices.iter());
+    adjustment_set.retain(|v| !t_poss_descendants.contains(v) && !effects.contains(v));
+    adjustment_set
+}
+
+/// Computes the conditional adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG,
+/// only counting the mistakes for the selected `(treatment, effect, conditioning set)` triples.
+///
+/// Conditioning nodes are taken to be covariates that are not affected by the treatment,
+/// such as the effect modifiers of a conditional average treatment effect.
+/// A graph claims that the conditional effect can be identified by adjustment if the effect is amenable,
+/// no conditioning node is a possible descendant of the treatment, and its adjustment set
+/// (see [`conditional_adjustment_set_given_possible_descendants`]), which contains the
+/// conditioning set, is valid in the graph. A triple is a mistake if the guess graph misses
+/// a possible effect, disagrees with the true graph on amenability or on whether the conditional
+/// effect can be identified by adjustment, or if its adjustment set is not valid in the true graph.
+///
+/// Returns the number of mistakes alongside the number of distinct triples it is normalized by,
+/// counting repeated triples once (irrespective of the order of the conditioning nodes),
+/// or an error if no triple is selected, a node is out of bounds, a treatment equals its effect,
+/// or a conditioning set contains its treatment or effect.
+///
+/// ```
+/// use gadjid::{graph_operations::conditional_aid, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the guess misses the effect of 0 on 2, but agrees that 2 has no effect on 0
+/// let triples = [(0, 2, vec![]), (2, 0, vec![1])];
+/// let selected = conditional_aid(&truth, &guess, &triples).unwrap();
+/// assert_eq!(selected.distance(), (0.5, 1));
+/// ```
+pub fn conditional_aid(
+    truth: &PDAG,
+    guess: &PDAG,
+    triples: &[(usize, usize, Vec<usize>)],
+) -> Result<SelectedPairsDistance, PairsError> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    let (effects_of_treatments, n_triples) =
+        group_by_treatment_and_conditioning_set(guess.n_nodes, triples, |v| {
+            is_isolated(truth, guess, v)
+        })?;
+
+    crate::rayon::build_global();
+
+    let verifier_mistakes_found = effects_of_treatments
+        .par_iter()
+        .map(|((treatment, conditioning), effects)| {
+            conditional_aid_mistakes(
+                truth,
+                guess,
+                *treatment,
+                conditioning,
+                Effects::Selected(effects),
+            )
+        })
+        .sum();
+
+    Ok(SelectedPairsDistance::new(
+        verifier_mistakes_found,
+        n_triples,
+        triples.len(),
+        truth.n_nodes,
+    ))
+}
+
+/// Counts the mistakes of the conditional adjustment for the single `treatment`
+/// and the `conditioning` set over the given `effects`.
+fn conditional_aid_mistakes(
+    truth: &PDAG,
+    guess: &PDAG,
+    treatment: usize,
+    conditioning: &[usize],
+    effects: Effects,
+) -> usize {
+    // all possible descendants are needed to exclude them from the adjustment sets,
+    // so the searches cannot stop early at the effects of interest
+    let (t_poss_desc_in_guess, nam_in_guess) = get_pd_nam(guess, &[treatment], None);
+    let (t_poss_desc_in_truth, nam_in_true) = get_pd_nam(truth, &[treatment], None);
+
+    let is_invalid_in = |graph: &PDAG, adjustment_set: &FxHashSet<usize>, ys: &[usize]| {
+        let ys = FxHashSet::from_iter(ys.iter().copied());
+        !get_invalidly_un_blocked(graph, &[treatment], adjustment_set, Some(&ys)).is_disjoint(&ys)
+    };
+    // the adjustment set of a graph can only contain the conditioning set
+    // if no conditioning node is a possible descendant of the treatment
+    let is_pretreatment_in =
+        |t_poss_desc: &FxHashSet<usize>| conditioning.iter().all(|c| !t_poss_desc.contains(c));
+
+    // the effects are amenable in both graphs, so we need to find the adjustment set
+    let is_wrongly_adjusted = |ys: &[usize]| {
+        let adjustment_in_guess = is_pretreatment_in(&t_poss_desc_in_guess).then(|| {
+            conditional_adjustment_set(guess, treatment, ys, conditioning, &t_poss_desc_in_guess)
+        });
+
+        match adjustment_in_guess {
+            // if the adjustment set of the guess graph is not valid in the true graph
+            // (by blocking too much or too little) or the true graph claims that
+            // the conditioning set cannot be adjusted for, we count a mistake
+            Some(z) if !is_invalid_in(guess, &z, ys) => {
+                !is_pretreatment_in(&t_poss_desc_in_truth) || is_invalid_in(truth, &z, ys)
+            }
+            // if the guess graph claims that no valid adjustment set contains the conditioning set,
+            // we count a mistake if there is one in the true graph
+            _ => {
+                is_pretreatment_in(&t_poss_desc_in_truth) && {
+                    let z = conditional_adjustment_set(
+                        truth,
+                        treatment,
+                        ys,
+                        conditioning,
+                        &t_poss_desc_in_truth,
+                    );
+                    !is_invalid_in(truth, &z, ys)
+                }
+            }
+        }
+    };
+
+    let claims = Claims {
+        is_claimed_effect: |y: usize| t_poss_desc_in_guess.contains(&y),
+        poss_desc_in_truth: &t_poss_desc_in_truth,
+        nam_in_guess: &nam_in_guess,
+        nam_in_true: &nam_in_true,
+        is_wrongly_adjusted,
+    };
+
+    let mut mistakes = 0;
+    effects.record_verdicts(&[treatment], |ys| claims.verdict(ys), &mut mistakes);
+    mistakes
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::get_pd_nam, PDAG};
+
+    use super::{conditional_adjustment_set_given_possible_descendants, conditional_aid};
+
+    #[test]
+    fn conditioning_on_a_collider_is_graded() {
+        // truth: 0 -> 1, 0 <- 3 -> 4 <- 5 -> 1
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0, 0], //
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![1, 0, 0, 0, 1, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 1, 0, 0, 1, 0],
+        ]);
+        // guess: 0 -> 1
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0, 0], //
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ]);
+        let (t_poss_desc, _) = get_pd_nam(&truth, &[0], None);
+        let mut t_poss_desc = Vec::from_iter(t_poss_desc);
+        t_poss_desc.sort_unstable();
+        assert_eq!(
+            conditional_adjustment_set_given_possible_descendants(
+                &truth,
+                0,
+                &[1],
+                &[4],
+                &t_poss_desc
+            ),
+            vec![3, 4, 5]
+        );
+
+        // without conditioning, the empty adjustment set of the guess graph is valid,
+        // but conditioning only on the collider 4 opens the path 0 <- 3 -> 4 <- 5 -> 1
+        let distance = conditional_aid(&truth, &guess, &[(0, 1, vec![])]).unwrap();
+        assert_eq!(distance.mistakes, 0);
+        let distance = conditional_aid(&truth, &guess, &[(0, 1, vec![4])]).unwrap();
+        assert_eq!(distance.mistakes, 1);
+        let distance = conditional_aid(&truth, &truth, &[(0, 1, vec![4])]).unwrap();
+        assert_eq!(distance.mistakes, 0);
+
+        // in the true graph, 0 is a descendant of 3, so there is no valid adjustment set
+        // for the effect of 3 on 4 given 0 that is not affected by the treatment
+        let distance = conditional_aid(&truth, &truth, &[(3, 4, vec![0])]).unwrap();
+        assert_eq!(distance.mistakes, 0);
+        let distance = conditional_aid(&truth, &guess, &[(3, 4, vec![0])]).unwrap();
+        assert_eq!(distance.mistakes, 1);
+    }
+
+    #[test]
+    fn property_adjustment_sets_are_sorted() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for n in 3..25 {
+            let cpdag = PDAG::random_pdag(0.3, n, &mut rng);
+            let (t_poss_desc, _) = get_pd_nam(&cpdag, &[0], None);
+            let t_poss_desc = Vec::from_iter(t_poss_desc);
+            let z = conditional_adjustment_set_given_possible_descendants(
+                &cpdag,
+                0,
+                &[n - 1],
+                &[1],
+                &t_poss_desc,
+            );
+            assert!(z.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn property_equal_graphs_zero_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 3..25 {
+            let cpdag = PDAG::random_pdag(0.3, n, &mut rng);
+            let triples = Vec::from_iter((0..n).flat_map(|t| {
+                (0..n).filter(move |y| *y != t).map(move |y| {
+                    (
+                        t,
+                        y,
+                        Vec::from_iter((0..n).filter(|c| *c != t && *c != y && c % 3 == 0)),
+                    )
+                })
+            }));
+            let distance = conditional_aid(&cpdag, &cpdag, &triples).unwrap();
+            assert_eq!(distance.mistakes, 0, "graph: {cpdag}");
+        }
+    }
+}
@@ -0,0 +1,178 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Implements the adjacency and arrowhead confusion counts of a guess graph against a true graph,
+//! alongside the precision, recall, and F1 score that structure learning papers report next to the SHD
+
+use crate::{graph_operations::shd::transition_counts, PDAG};
+
+/// The true positives, false positives, and false negatives of the guess graph for one kind of mark.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConfusionCounts {
+    /// marks in both graphs
+    pub true_positives: usize,
+    /// marks in the guess graph only
+    pub false_positives: usize,
+    /// marks in the true graph only
+    pub false_negatives: usize,
+}
+
+impl ConfusionCounts {
+    /// The fraction of the marks of the guess graph that are in the true graph,
+    /// or 0 if the guess graph has none.
+    pub fn precision(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_positives,
+        )
+    }
+
+    /// The fraction of the marks of the true graph that are in the guess graph,
+    /// or 0 if the true graph has none.
+    pub fn recall(&self) -> f64 {
+        ratio(
+            self.true_positives,
+            self.true_positives + self.false_negatives,
+        )
+    }
+
+    /// The harmonic mean of precision and recall, or 0 if both are 0.
+    pub fn f1(&self) -> f64 {
+        ratio(
+            2 * self.true_positives,
+            2 * self.true_positives + self.false_positives + self.false_negatives,
+        )
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as f64 / denominator as f64
+}
+
+/// The confusion counts of a guess graph against a true graph, as returned by [`confusion`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Confusion {
+    /// the pairs of nodes that are adjacent, irrespective of the type of their edge
+    pub adjacency: ConfusionCounts,
+    /// the arrowheads, where a directed edge `v -> w` has an arrowhead at `w`
+    /// and an undirected edge `v -- w` has none
+    pub arrowhead: ConfusionCounts,
+}
+
+/// The arrowheads of each edge type between two nodes v < w, in the order of [`transition_counts`]:
+/// no edge, `v -> w`, `v <- w`, and `v -- w`, as a pair of whether there is one at v and at w.
+const ARROWHEADS: [(bool, bool); 4] =
+    [(false, false), (false, true), (true, false), (false, false)];
+
+/// Counts the true positives, false positives, and false negatives of the adjacencies
+/// and of the arrowheads of the `guess` graph against the `truth` graph,
+/// from which the precision, recall, and F1 score of each follow.
+///
+/// ```
+/// use gadjid::{graph_operations::confusion, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 <- 2, 0 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 1],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let confusion = confusion(&truth, &guess);
+/// // both edges of the truth are guessed, alongside the extra edge 0 -> 2
+/// assert_eq!(confusion.adjacency.recall(), 1.0);
+/// assert_eq!(confusion.adjacency.precision(), 2.0 / 3.0);
+/// // of the arrowheads at 1 and 2, neither is guessed,
+/// // and the guess has arrowheads at 1 and 2 of the wrong edges
+/// assert_eq!(confusion.arrowhead.true_positives, 0);
+/// assert_eq!(confusion.arrowhead.false_negatives, 2);
+/// assert_eq!(confusion.arrowhead.false_positives, 2);
+/// ```
+pub fn confusion(truth: &PDAG, guess: &PDAG) -> Confusion {
+    let counts = transition_counts(truth, guess);
+
+    let mut confusion = Confusion::default();
+    for (truth_type, row) in counts.iter().enumerate() {
+        for (guess_type, n_pairs) in row.iter().enumerate() {
+            let adjacency = &mut confusion.adjacency;
+            match (truth_type != 0, guess_type != 0) {
+                (true, true) => adjacency.true_positives += n_pairs,
+                (false, true) => adjacency.false_positives += n_pairs,
+                (true, false) => adjacency.false_negatives += n_pairs,
+                (false, false) => (),
+            }
+            let (truth_v, truth_w) = ARROWHEADS[truth_type];
+            let (guess_v, guess_w) = ARROWHEADS[guess_type];
+            for (in_truth, in_guess) in [(truth_v, guess_v), (truth_w, guess_w)] {
+                let arrowhead = &mut confusion.arrowhead;
+                match (in_truth, in_guess) {
+                    (true, true) => arrowhead.true_positives += n_pairs,
+                    (false, true) => arrowhead.false_positives += n_pairs,
+                    (true, false) => arrowhead.false_negatives += n_pairs,
+                    (false, false) => (),
+                }
+            }
+        }
+    }
+    confusion
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd_detailed, PDAG};
+
+    use super::{confusion, ConfusionCounts};
+
+    #[test]
+    fn equal_graphs_are_perfect() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let graph = PDAG::random_pdag(0.5, 10, &mut rng);
+        let confusion = confusion(&graph, &graph);
+        assert_eq!(confusion.adjacency.false_positives, 0);
+        assert_eq!(confusion.adjacency.false_negatives, 0);
+        assert_eq!(
+            confusion.adjacency.true_positives,
+            graph.n_directed_edges + graph.n_undirected_edges
+        );
+        assert_eq!(confusion.arrowhead.true_positives, graph.n_directed_edges);
+        assert_eq!(confusion.adjacency.f1(), 1.0);
+    }
+
+    #[test]
+    fn empty_counts_score_zero() {
+        let counts = ConfusionCounts::default();
+        assert_eq!(
+            (counts.precision(), counts.recall(), counts.f1()),
+            (0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn property_adjacency_errors_are_missing_and_superfluous_edges() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let confusion = confusion(&truth, &guess);
+            let detailed = shd_detailed(&truth, &guess);
+            assert_eq!(confusion.adjacency.false_negatives, detailed.missing);
+            assert_eq!(confusion.adjacency.false_positives, detailed.superfluous);
+            assert_eq!(
+                confusion.arrowhead.true_positives + confusion.arrowhead.false_negatives,
+                truth.n_directed_edges
+            );
+            assert_eq!(
+                confusion.arrowhead.true_positives + confusion.arrowhead.false_positives,
+                guess.n_directed_edges
+            );
+        }
+    }
+}
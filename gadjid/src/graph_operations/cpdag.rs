@@ -0,0 +1,144 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Computes the CPDAG of a DAG, which represents its Markov equivalence class
+
+use rustc_hash::FxHashSet;
+
+use crate::PDAG;
+
+/// Returns the CPDAG of the `dag`, which has the same skeleton and a directed edge for exactly
+/// those edges of the `dag` that have the same orientation in every Markov equivalent DAG,
+/// and an undirected edge otherwise. Panics if the `dag` has undirected edges.
+///
+/// Follows Meek (1995): the edges of the v-structures are directed, and the remaining edges are
+/// oriented by Meek's rules 1 to 3 until none applies; rule 4 is only needed with background knowledge.
+///
+/// ```
+/// use gadjid::{graph_operations::cpdag_of, PDAG};
+///
+/// // 0 -> 1 -> 2 <- 3
+/// let dag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 1, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 1, 0],
+/// ]);
+/// // 1 -> 2 <- 3 is a v-structure, and 0 -- 1 could be reversed
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0, 0],
+///     vec![0, 0, 1, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 1, 0],
+/// ]);
+/// assert_eq!(cpdag_of(&dag), cpdag);
+/// ```
+pub fn cpdag_of(dag: &PDAG) -> PDAG {
+    assert_eq!(dag.n_undirected_edges, 0, "graph must be a DAG");
+    let n = dag.n_nodes;
+    let is_adjacent = |v: usize, w: usize| {
+        dag.children_of(v).binary_search(&w).is_ok() || dag.parents_of(v).binary_search(&w).is_ok()
+    };
+
+    // the edges of v-structures are compelled, all others start out undirected
+    let mut parents = vec![FxHashSet::default(); n];
+    let mut undirected = vec![FxHashSet::default(); n];
+    for child in 0..n {
+        let dag_parents = dag.parents_of(child);
+        for parent in dag_parents {
+            if dag_parents
+                .iter()
+                .any(|other| other != parent && !is_adjacent(*parent, *other))
+            {
+                parents[child].insert(*parent);
+            } else {
+                undirected[child].insert(*parent);
+                undirected[*parent].insert(child);
+            }
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for v in 0..n {
+            for w in Vec::from_iter(dag.children_of(v).iter().copied()) {
+                if !undirected[v].contains(&w) {
+                    continue;
+                }
+                // rule 1: a -> v -- w with a, w not adjacent
+                let rule_1 = parents[v].iter().any(|a| !is_adjacent(*a, w));
+                // rule 2: v -> b -> w with v -- w
+                let rule_2 = parents[w].iter().any(|b| parents[*b].contains(&v));
+                // rule 3: v -- c -> w and v -- d -> w with c, d not adjacent
+                let rule_3 = {
+                    let both = Vec::from_iter(
+                        undirected[v]
+                            .iter()
+                            .filter(|c| parents[w].contains(*c))
+                            .copied(),
+                    );
+                    both.iter()
+                        .any(|c| both.iter().any(|d| c < d && !is_adjacent(*c, *d)))
+                };
+                if rule_1 || rule_2 || rule_3 {
+                    undirected[v].remove(&w);
+                    undirected[w].remove(&v);
+                    parents[w].insert(v);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    let directed = (0..n).flat_map(|w| parents[w].iter().map(move |v| (*v, w, 1)));
+    let undirected = (0..n).flat_map(|v| {
+        undirected[v]
+            .iter()
+            .filter(move |w| v < **w)
+            .map(move |w| (v, *w, 2))
+    });
+    PDAG::try_from_edges(n, directed.chain(undirected)).expect("the CPDAG of a DAG is a PDAG")
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::consistent_extension, PDAG};
+
+    use super::cpdag_of;
+
+    #[test]
+    fn recovers_cpdags_from_their_extensions() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+
+        for graph_id in 10..=19 {
+            let cpdag = crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-CPDAG-{}.mtx", graph_id))
+                    .to_str()
+                    .unwrap(),
+            );
+            let dag = consistent_extension(&cpdag).unwrap();
+            assert_eq!(cpdag_of(&dag), cpdag);
+        }
+    }
+
+    #[test]
+    fn property_cpdags_are_idempotent() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 1..30 {
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            let cpdag = cpdag_of(&dag);
+            assert_eq!(
+                cpdag.n_directed_edges + cpdag.n_undirected_edges,
+                dag.n_directed_edges
+            );
+            // every consistent extension of the CPDAG is Markov equivalent to the DAG
+            let extension = consistent_extension(&cpdag).unwrap();
+            assert_eq!(cpdag_of(&extension), cpdag);
+        }
+    }
+}
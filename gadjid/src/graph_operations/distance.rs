@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Selects a distance by value, such as from the name passed to a language binding,
+//! for the functions that compute, report, or compare any of several distances
+
+use crate::{
+    graph_operations::{
+        ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with, shd,
+        AidOutput,
+    },
+    PDAG,
+};
+
+/// A distance between a guess and a true graph, such as one reported in a bundle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distance {
+    /// the ancestor adjustment intervention distance
+    AncestorAid,
+    /// the oset adjustment intervention distance
+    OsetAid,
+    /// the parent adjustment intervention distance
+    ParentAid,
+    /// the structural hamming distance
+    Shd,
+}
+
+impl Distance {
+    /// All distances, in the order they are written to a bundle.
+    pub const ALL: [Distance; 4] = [
+        Distance::AncestorAid,
+        Distance::OsetAid,
+        Distance::ParentAid,
+        Distance::Shd,
+    ];
+
+    /// The name of the distance, which is also the name of its function.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distance::AncestorAid => "ancestor_aid",
+            Distance::OsetAid => "oset_aid",
+            Distance::ParentAid => "parent_aid",
+            Distance::Shd => "shd",
+        }
+    }
+
+    /// Returns the distance with the given name, if there is one.
+    pub fn from_name(name: &str) -> Option<Distance> {
+        Distance::ALL
+            .into_iter()
+            .find(|distance| distance.name() == name)
+    }
+
+    /// The adjustment identification distance, or `None` for the SHD.
+    pub fn aid(&self) -> Option<AidDistance> {
+        match self {
+            Distance::AncestorAid => Some(AidDistance::Ancestor),
+            Distance::OsetAid => Some(AidDistance::Oset),
+            Distance::ParentAid => Some(AidDistance::Parent),
+            Distance::Shd => None,
+        }
+    }
+
+    /// Computes the distance between the `truth` and the `guess` graph.
+    pub fn compute(&self, truth: &PDAG, guess: &PDAG) -> (f64, usize) {
+        match self {
+            Distance::AncestorAid => ancestor_aid(truth, guess),
+            Distance::OsetAid => oset_aid(truth, guess),
+            Distance::ParentAid => parent_aid(truth, guess),
+            Distance::Shd => shd(truth, guess),
+        }
+    }
+}
+
+/// An adjustment identification distance, for the functions that compute any [`AidOutput`]
+/// of one of them, which the SHD has none of.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{oset_aid_with, AidDistance, Distance, WithBreakdown},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1, guess: 0 <- 1
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1], //
+///     vec![0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0], //
+///     vec![1, 0],
+/// ]);
+///
+/// let aid = Distance::OsetAid.aid().unwrap();
+/// assert_eq!(
+///     aid.compute_with(&truth, &guess, WithBreakdown),
+///     oset_aid_with(&truth, &guess, WithBreakdown)
+/// );
+/// assert_eq!(Distance::Shd.aid(), None);
+/// assert_eq!(Distance::from(AidDistance::Parent), Distance::ParentAid);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AidDistance {
+    /// the ancestor adjustment intervention distance
+    Ancestor,
+    /// the oset adjustment intervention distance
+    Oset,
+    /// the parent adjustment intervention distance
+    Parent,
+}
+
+impl AidDistance {
+    /// All adjustment identification distances.
+    pub const ALL: [AidDistance; 3] = [
+        AidDistance::Ancestor,
+        AidDistance::Oset,
+        AidDistance::Parent,
+    ];
+
+    /// Computes the `output` of the distance between the `truth` and the `guess` graph,
+    /// as [`ancestor_aid_with`], [`oset_aid_with`], or [`parent_aid_with`].
+    pub fn compute_with<O: AidOutput>(&self, truth: &PDAG, guess: &PDAG, output: O) -> O::Output {
+        match self {
+            AidDistance::Ancestor => ancestor_aid_with(truth, guess, output),
+            AidDistance::Oset => oset_aid_with(truth, guess, output),
+            AidDistance::Parent => parent_aid_with(truth, guess, output),
+        }
+    }
+}
+
+impl From<AidDistance> for Distance {
+    fn from(aid: AidDistance) -> Distance {
+        match aid {
+            AidDistance::Ancestor => Distance::AncestorAid,
+            AidDistance::Oset => Distance::OsetAid,
+            AidDistance::Parent => Distance::ParentAid,
+        }
+    }
+}
@@ -0,0 +1,694 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Builds and compares ensembles of graphs, such as the graphs learned on bootstrap samples
+
+use std::{error::Error, fmt};
+
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    graph_loading::edgelist::Edgelist,
+    graph_operations::{selected_pairs::group_by_treatment, PairsError, SelectedPairsDistance},
+    LoadError, PDAG,
+};
+
+#[derive(Debug, PartialEq)]
+/// Error that can occur when building or comparing an ensemble of graphs.
+pub enum EnsembleError {
+    /// The ensemble contains no graphs.
+    Empty,
+    /// A graph of the ensemble has a different number of nodes than the first graph.
+    SizeMismatch {
+        /// the index of the offending graph in the ensemble
+        index: usize,
+        /// its number of nodes
+        n_nodes: usize,
+        /// the number of nodes of the first graph
+        expected: usize,
+    },
+    /// The threshold is not in (0, 1].
+    InvalidThreshold(f64),
+    /// The consensus graph could not be loaded, for example since it has a cycle.
+    Load(LoadError),
+    /// The ensemble has fewer graphs than needed to compare its graphs with each other.
+    TooFewGraphs {
+        /// the number of graphs in the ensemble
+        n_graphs: usize,
+        /// the number of graphs needed
+        required: usize,
+    },
+    /// The selected pairs are invalid.
+    Pairs(PairsError),
+    /// A row of a distance matrix has a different length than the number of rows.
+    NotSquare {
+        /// the index of the offending row
+        row: usize,
+        /// its length
+        len: usize,
+        /// the number of rows
+        expected: usize,
+    },
+    /// The number of clusters is not between one and the number of graphs.
+    InvalidClusterCount {
+        /// the requested number of clusters
+        n_clusters: usize,
+        /// the number of graphs
+        n_graphs: usize,
+    },
+}
+
+impl Error for EnsembleError {}
+
+impl fmt::Display for EnsembleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnsembleError::Empty => write!(f, "the ensemble must contain at least one graph"),
+            EnsembleError::SizeMismatch {
+                index,
+                n_nodes,
+                expected,
+            } => write!(
+                f,
+                "graph {index} of the ensemble has {n_nodes} nodes, but the first graph has {expected} nodes"
+            ),
+            EnsembleError::InvalidThreshold(threshold) => {
+                write!(f, "threshold {threshold} is not in (0, 1]")
+            }
+            EnsembleError::Load(err) => write!(f, "consensus graph: {err}"),
+            EnsembleError::TooFewGraphs { n_graphs, required } => write!(
+                f,
+                "the ensemble has {n_graphs} graphs, but at least {required} are needed"
+            ),
+            EnsembleError::Pairs(err) => write!(f, "{err}"),
+            EnsembleError::NotSquare { row, len, expected } => write!(
+                f,
+                "row {row} of the distance matrix has {len} entries, but the matrix has {expected} rows"
+            ),
+            EnsembleError::InvalidClusterCount {
+                n_clusters,
+                n_graphs,
+            } => write!(
+                f,
+                "cannot cluster {n_graphs} graphs into {n_clusters} clusters"
+            ),
+        }
+    }
+}
+
+/// Checks that the ensemble is not empty and all its graphs have the same number of nodes,
+/// which is returned.
+pub(crate) fn check_ensemble(graphs: &[PDAG]) -> Result<usize, EnsembleError> {
+    let expected = graphs.first().ok_or(EnsembleError::Empty)?.n_nodes;
+    match graphs.iter().position(|graph| graph.n_nodes != expected) {
+        Some(index) => Err(EnsembleError::SizeMismatch {
+            index,
+            n_nodes: graphs[index].n_nodes,
+            expected,
+        }),
+        None => Ok(expected),
+    }
+}
+
+/// Counts the edges `v -> w`, `v <- w`, and `v -- w` over the ensemble for each pair of nodes v < w
+/// that is adjacent in any of the graphs.
+pub(crate) fn count_edges(
+    graphs: &[PDAG],
+    n_nodes: usize,
+) -> FxHashMap<(usize, usize), [usize; 3]> {
+    let mut counts = FxHashMap::<(usize, usize), [usize; 3]>::default();
+    for graph in graphs {
+        for v in 0..n_nodes {
+            for w in graph.children_of(v) {
+                match v < *w {
+                    true => counts.entry((v, *w)).or_default()[0] += 1,
+                    false => counts.entry((*w, v)).or_default()[1] += 1,
+                }
+            }
+            for w in graph.adjacent_undirected_of(v).iter().filter(|w| v < **w) {
+                counts.entry((v, *w)).or_default()[2] += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// How often a pair of nodes `v < w` is adjacent, and by which edge, across an ensemble.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeFrequency {
+    /// the pair of nodes `(v, w)`, with `v < w`
+    pub nodes: (usize, usize),
+    /// the fraction of graphs with the edge `v -> w`
+    pub forward: f64,
+    /// the fraction of graphs with the edge `v <- w`
+    pub backward: f64,
+    /// the fraction of graphs with the edge `v -- w`
+    pub undirected: f64,
+}
+
+impl EdgeFrequency {
+    /// The fraction of graphs in which the nodes are adjacent, irrespective of the edge.
+    ///
+    /// ```
+    /// use gadjid::{graph_operations::edge_frequencies, PDAG};
+    ///
+    /// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+    /// let chain = || {
+    ///     PDAG::from_dense_row_to_col(vec![
+    ///         vec![0, 1, 0],
+    ///         vec![0, 0, 1],
+    ///         vec![0, 0, 0],
+    ///     ])
+    /// };
+    /// let collider = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ]);
+    /// let graphs = [chain(), chain(), collider];
+    ///
+    /// let frequencies = edge_frequencies(&graphs).unwrap();
+    /// assert_eq!(frequencies[1].adjacency(), 1.0);
+    /// ```
+    pub fn adjacency(&self) -> f64 {
+        self.forward + self.backward + self.undirected
+    }
+}
+
+/// Computes the selection frequencies of the edges of an ensemble of DAGs or CPDAGs,
+/// as in stability selection, for each pair of nodes that is adjacent in any of the graphs,
+/// ordered by the pair of nodes.
+///
+/// Fails if the ensemble is empty or its graphs differ in size.
+///
+/// ```
+/// use gadjid::{graph_operations::edge_frequencies, PDAG};
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// let frequencies = edge_frequencies(&graphs).unwrap();
+/// assert_eq!(frequencies.len(), 2);
+/// assert_eq!(frequencies[1].nodes, (1, 2));
+/// assert_eq!((frequencies[1].forward, frequencies[1].backward), (2.0 / 3.0, 1.0 / 3.0));
+/// ```
+pub fn edge_frequencies(graphs: &[PDAG]) -> Result<Vec<EdgeFrequency>, EnsembleError> {
+    let n_nodes = check_ensemble(graphs)?;
+    let n_graphs = graphs.len() as f64;
+    let mut frequencies = Vec::from_iter(count_edges(graphs, n_nodes).into_iter().map(
+        |(nodes, [forward, backward, undirected])| EdgeFrequency {
+            nodes,
+            forward: forward as f64 / n_graphs,
+            backward: backward as f64 / n_graphs,
+            undirected: undirected as f64 / n_graphs,
+        },
+    ));
+    frequencies.sort_unstable_by_key(|frequency| frequency.nodes);
+    Ok(frequencies)
+}
+
+/// Computes the identification stability of each selected `(treatment, effect)` pair
+/// across an ensemble of DAGs or CPDAGs, without a true graph: the fraction of ordered pairs of
+/// distinct graphs of the ensemble for which the effect identified in the one graph,
+/// such as by its adjustment set, is not a mistake relative to the other graph according to the
+/// selected-pairs `distance`, such as [`oset_aid_with`](super::oset_aid_with) for [`SelectedPairs`](super::SelectedPairs).
+///
+/// Fails if the ensemble has fewer than two graphs, its graphs differ in size, or the pairs are invalid.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{identification_stability, oset_aid_with, SelectedPairs},
+///     PDAG,
+/// };
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// // the effect of 0 on 2 is a mistake whenever a chain is the true graph and the collider the guess
+/// let oset_aid_selected_pairs = |truth: &PDAG, guess: &PDAG, pairs: &[(usize, usize)]| {
+///     oset_aid_with(truth, guess, SelectedPairs(pairs))
+/// };
+/// let stability = identification_stability(&graphs, &[(0, 2)], oset_aid_selected_pairs).unwrap();
+/// assert_eq!(stability, vec![4.0 / 6.0]);
+/// ```
+pub fn identification_stability(
+    graphs: &[PDAG],
+    pairs: &[(usize, usize)],
+    distance: impl Fn(&PDAG, &PDAG, &[(usize, usize)]) -> Result<SelectedPairsDistance, PairsError>
+        + Sync,
+) -> Result<Vec<f64>, EnsembleError> {
+    let n_nodes = check_ensemble(graphs)?;
+    if graphs.len() < 2 {
+        return Err(EnsembleError::TooFewGraphs {
+            n_graphs: graphs.len(),
+            required: 2,
+        });
+    }
+    group_by_treatment(n_nodes, pairs, |_| false).map_err(EnsembleError::Pairs)?;
+
+    let n_comparisons = graphs.len() * (graphs.len() - 1);
+    pairs
+        .par_iter()
+        .map(|pair| {
+            let mut agreeing = 0;
+            for (i, guess) in graphs.iter().enumerate() {
+                for (j, truth) in graphs.iter().enumerate() {
+                    if i != j {
+                        let distance = distance(truth, guess, &[*pair]);
+                        if distance.map_err(EnsembleError::Pairs)?.mistakes == 0 {
+                            agreeing += 1;
+                        }
+                    }
+                }
+            }
+            Ok(agreeing as f64 / n_comparisons as f64)
+        })
+        .collect()
+}
+
+/// Summary statistics of the off-diagonal entries of a cross-consistency matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DispersionSummary {
+    /// the mean normalized distance
+    pub mean: f64,
+    /// the (population) standard deviation of the normalized distances
+    pub standard_deviation: f64,
+    /// the smallest normalized distance
+    pub min: f64,
+    /// the largest normalized distance
+    pub max: f64,
+    /// the mean absolute difference between the normalized distances in both directions,
+    /// which is zero for symmetric distances such as the SHD
+    pub asymmetry: f64,
+}
+
+/// The distances among the graphs of an ensemble, as returned by [`cross_consistency`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CrossConsistency {
+    /// The distance matrix, whose entry in row `i` and column `j` is the distance
+    /// between graph `i` as true graph and graph `j` as guess graph; the diagonal is `(0.0, 0)`.
+    pub distances: Vec<Vec<(f64, usize)>>,
+    /// Summary statistics of the off-diagonal normalized distances.
+    pub summary: DispersionSummary,
+}
+
+/// Computes the full asymmetric `distance` matrix among the graphs of an ensemble of DAGs or CPDAGs,
+/// such as `cross_consistency(&graphs, oset_aid)`, alongside summary statistics of its dispersion,
+/// to assess how consistent the graphs are without a true graph.
+/// The ensemble is validated once, and the distances between all ordered pairs of distinct graphs
+/// are computed in parallel.
+///
+/// Fails if the ensemble has fewer than two graphs or its graphs differ in size.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{cross_consistency, shd},
+///     PDAG,
+/// };
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// let consistency = cross_consistency(&graphs, shd).unwrap();
+/// assert_eq!(consistency.distances[0][2], (1.0 / 3.0, 1));
+/// // the SHD is symmetric
+/// assert_eq!(consistency.summary.asymmetry, 0.0);
+/// ```
+pub fn cross_consistency(
+    graphs: &[PDAG],
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize) + Sync,
+) -> Result<CrossConsistency, EnsembleError> {
+    check_ensemble(graphs)?;
+    let k = graphs.len();
+    if k < 2 {
+        return Err(EnsembleError::TooFewGraphs {
+            n_graphs: k,
+            required: 2,
+        });
+    }
+
+    crate::rayon::build_global();
+
+    let distances: Vec<Vec<(f64, usize)>> = (0..k)
+        .into_par_iter()
+        .map(|i| {
+            Vec::from_iter((0..k).map(|j| match i == j {
+                true => (0.0, 0),
+                false => distance(&graphs[i], &graphs[j]),
+            }))
+        })
+        .collect();
+
+    let off_diagonal =
+        Vec::from_iter((0..k).flat_map(|i| (0..k).filter(move |j| i != *j).map(move |j| (i, j))));
+    let n = off_diagonal.len() as f64;
+    let mean = off_diagonal
+        .iter()
+        .map(|(i, j)| distances[*i][*j].0)
+        .sum::<f64>()
+        / n;
+    let variance = off_diagonal
+        .iter()
+        .map(|(i, j)| (distances[*i][*j].0 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    let normalized = || off_diagonal.iter().map(|(i, j)| distances[*i][*j].0);
+    let asymmetry = off_diagonal
+        .iter()
+        .map(|(i, j)| (distances[*i][*j].0 - distances[*j][*i].0).abs())
+        .sum::<f64>()
+        / n;
+
+    let summary = DispersionSummary {
+        mean,
+        standard_deviation: variance.sqrt(),
+        min: normalized().fold(f64::INFINITY, f64::min),
+        max: normalized().fold(f64::NEG_INFINITY, f64::max),
+        asymmetry,
+    };
+    Ok(CrossConsistency { distances, summary })
+}
+
+/// Builds the consensus PDAG of an ensemble of DAGs or CPDAGs.
+///
+/// Two nodes are adjacent in the consensus graph if they are adjacent in at least
+/// a `threshold` fraction of the graphs, and the edge between them is directed if it has
+/// the same direction in at least a `threshold` fraction of the graphs, and undirected otherwise.
+/// For thresholds of at most 0.5, both directions may reach the threshold; then the edge takes
+/// the more frequent direction, and is undirected if both are equally frequent.
+///
+/// Fails if the ensemble is empty, its graphs differ in size, the threshold is not in (0, 1],
+/// or the consensus graph has a directed cycle.
+///
+/// ```
+/// use gadjid::{graph_operations::consensus_graph, PDAG};
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// let consensus = consensus_graph(&graphs, 0.5).unwrap();
+/// assert_eq!(consensus.to_row_to_column_vecvec(), graphs[0].to_row_to_column_vecvec());
+/// ```
+pub fn consensus_graph(graphs: &[PDAG], threshold: f64) -> Result<PDAG, EnsembleError> {
+    let n_nodes = check_ensemble(graphs)?;
+    if !(threshold > 0.0 && threshold <= 1.0) {
+        return Err(EnsembleError::InvalidThreshold(threshold));
+    }
+
+    let counts = count_edges(graphs, n_nodes);
+
+    let reaches_threshold = |count: usize| count as f64 >= threshold * graphs.len() as f64;
+    let mut dense = vec![vec![0; n_nodes]; n_nodes];
+    for ((v, w), [forward, backward, undirected]) in counts {
+        if !reaches_threshold(forward + backward + undirected) {
+            continue;
+        }
+        match (reaches_threshold(forward), reaches_threshold(backward)) {
+            (true, _) if forward > backward => dense[v][w] = 1,
+            (_, true) if backward > forward => dense[w][v] = 1,
+            _ => dense[v][w] = 2,
+        }
+    }
+
+    PDAG::try_from_row_major(Edgelist::from_vecvec(dense)).map_err(EnsembleError::Load)
+}
+
+/// Computes the `distance` from the `consensus` graph, as true graph, to each graph of the ensemble,
+/// as guess graph, such as `distances_to_consensus(&graphs, &consensus, oset_aid)`.
+///
+/// Fails if the ensemble is empty or its graphs differ in size from each other or the consensus graph.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{consensus_graph, distances_to_consensus, shd},
+///     PDAG,
+/// };
+///
+/// // two chains 0 -> 1 -> 2 and a collider 0 -> 1 <- 2
+/// let chain = || {
+///     PDAG::from_dense_row_to_col(vec![
+///         vec![0, 1, 0],
+///         vec![0, 0, 1],
+///         vec![0, 0, 0],
+///     ])
+/// };
+/// let collider = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+/// let graphs = [chain(), chain(), collider];
+///
+/// let consensus = consensus_graph(&graphs, 0.5).unwrap();
+/// assert_eq!(
+///     distances_to_consensus(&graphs, &consensus, shd).unwrap(),
+///     vec![(0.0, 0), (0.0, 0), (1.0 / 3.0, 1)]
+/// );
+/// ```
+pub fn distances_to_consensus(
+    graphs: &[PDAG],
+    consensus: &PDAG,
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize),
+) -> Result<Vec<(f64, usize)>, EnsembleError> {
+    let n_nodes = check_ensemble(graphs)?;
+    if consensus.n_nodes != n_nodes {
+        return Err(EnsembleError::SizeMismatch {
+            index: 0,
+            n_nodes,
+            expected: consensus.n_nodes,
+        });
+    }
+    Ok(Vec::from_iter(
+        graphs.iter().map(|graph| distance(consensus, graph)),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{oset_aid, oset_aid_with, shd, PairsError, SelectedPairs},
+        LoadError, PDAG,
+    };
+
+    use super::{
+        consensus_graph, cross_consistency, distances_to_consensus, edge_frequencies,
+        identification_stability, DispersionSummary, EdgeFrequency, EnsembleError,
+    };
+
+    fn ensemble() -> Vec<PDAG> {
+        Vec::from_iter(
+            [
+                // 0 -> 1 -> 2
+                vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]],
+                // 0 -> 1 <- 2
+                vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 1, 0]],
+                // 0 -- 1 -> 2, 0 -> 2
+                vec![vec![0, 2, 1], vec![0, 0, 1], vec![0, 0, 0]],
+            ]
+            .into_iter()
+            .map(PDAG::from_dense_row_to_col),
+        )
+    }
+
+    #[test]
+    fn builds_consensus_by_frequency() {
+        let graphs = ensemble();
+        assert_eq!(
+            consensus_graph(&graphs, 1.0).unwrap(),
+            // 0 -- 1 -- 2
+            PDAG::from_dense_row_to_col(vec![vec![0, 2, 0], vec![0, 0, 2], vec![0, 0, 0]])
+        );
+        assert_eq!(
+            consensus_graph(&graphs, 0.6).unwrap(),
+            // 0 -> 1 -> 2
+            PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]])
+        );
+        assert_eq!(
+            consensus_graph(&graphs, 0.3).unwrap(),
+            // 0 -> 1 -> 2, 0 -> 2
+            PDAG::from_dense_row_to_col(vec![vec![0, 1, 1], vec![0, 0, 1], vec![0, 0, 0]])
+        );
+
+        let consensus = consensus_graph(&graphs, 0.6).unwrap();
+        assert_eq!(
+            distances_to_consensus(&graphs, &consensus, shd).unwrap(),
+            vec![(0.0, 0), (1.0 / 3.0, 1), (2.0 / 3.0, 2)]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_ensembles() {
+        assert_eq!(consensus_graph(&[], 0.5), Err(EnsembleError::Empty));
+        let mut graphs = ensemble();
+        assert_eq!(
+            consensus_graph(&graphs, 0.0),
+            Err(EnsembleError::InvalidThreshold(0.0))
+        );
+        graphs.push(PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]));
+        assert_eq!(
+            consensus_graph(&graphs, 0.5),
+            Err(EnsembleError::SizeMismatch {
+                index: 3,
+                n_nodes: 2,
+                expected: 3
+            })
+        );
+
+        // 0 -> 1 -> 2 -> 0 is in the consensus of graphs that each miss one edge of the cycle
+        let cycle = Vec::from_iter(
+            [
+                vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]],
+                vec![vec![0, 0, 0], vec![0, 0, 1], vec![1, 0, 0]],
+                vec![vec![0, 1, 0], vec![0, 0, 0], vec![1, 0, 0]],
+            ]
+            .into_iter()
+            .map(PDAG::from_dense_row_to_col),
+        );
+        assert_eq!(
+            consensus_graph(&cycle, 0.6),
+            Err(EnsembleError::Load(LoadError::NotAcyclic))
+        );
+    }
+
+    #[test]
+    fn scores_edge_and_identification_stability() {
+        let graphs = ensemble();
+        let frequencies = edge_frequencies(&graphs).unwrap();
+        assert_eq!(
+            frequencies,
+            vec![
+                EdgeFrequency {
+                    nodes: (0, 1),
+                    forward: 2.0 / 3.0,
+                    backward: 0.0,
+                    undirected: 1.0 / 3.0,
+                },
+                EdgeFrequency {
+                    nodes: (0, 2),
+                    forward: 1.0 / 3.0,
+                    backward: 0.0,
+                    undirected: 0.0,
+                },
+                EdgeFrequency {
+                    nodes: (1, 2),
+                    forward: 2.0 / 3.0,
+                    backward: 1.0 / 3.0,
+                    undirected: 0.0,
+                },
+            ]
+        );
+        assert_eq!(frequencies[0].adjacency(), 1.0);
+
+        // the effect of 0 on 1 is not amenable in the third graph, and the optimal adjustment set
+        // of the second graph, {2}, contains a descendant of 0 in the first graph, so only the
+        // empty optimal adjustment set of the first graph is valid in the second graph;
+        // 0 is no possible descendant of 2 in any of the graphs
+        let oset_aid_selected_pairs = |truth: &PDAG, guess: &PDAG, pairs: &[(usize, usize)]| {
+            oset_aid_with(truth, guess, SelectedPairs(pairs))
+        };
+        let stability =
+            identification_stability(&graphs, &[(0, 1), (2, 0)], oset_aid_selected_pairs);
+        assert_eq!(stability.unwrap(), vec![1.0 / 6.0, 1.0]);
+
+        assert_eq!(
+            identification_stability(&graphs[..1], &[(0, 1)], oset_aid_selected_pairs),
+            Err(EnsembleError::TooFewGraphs {
+                n_graphs: 1,
+                required: 2
+            })
+        );
+        assert_eq!(
+            identification_stability(&graphs, &[(1, 1)], oset_aid_selected_pairs),
+            Err(EnsembleError::Pairs(PairsError::TreatmentIsEffect {
+                node: 1
+            }))
+        );
+    }
+
+    #[test]
+    fn computes_cross_consistency() {
+        let graphs = ensemble();
+        let shds = cross_consistency(&graphs, shd).unwrap();
+        assert_eq!(
+            shds.distances,
+            vec![
+                vec![(0.0, 0), (1.0 / 3.0, 1), (2.0 / 3.0, 2)],
+                vec![(1.0 / 3.0, 1), (0.0, 0), (1.0, 3)],
+                vec![(2.0 / 3.0, 2), (1.0, 3), (0.0, 0)],
+            ]
+        );
+        assert_eq!(
+            shds.summary,
+            DispersionSummary {
+                mean: 2.0 / 3.0,
+                standard_deviation: (2.0f64 / 27.0).sqrt(),
+                min: 1.0 / 3.0,
+                max: 1.0,
+                asymmetry: 0.0,
+            }
+        );
+
+        let osets = cross_consistency(&graphs, oset_aid).unwrap();
+        for (i, row) in osets.distances.iter().enumerate() {
+            for (j, distance) in row.iter().enumerate() {
+                assert_eq!(*distance, oset_aid(&graphs[i], &graphs[j]));
+            }
+        }
+        assert!(osets.summary.asymmetry > 0.0);
+
+        assert_eq!(
+            cross_consistency(&graphs[..1], shd),
+            Err(EnsembleError::TooFewGraphs {
+                n_graphs: 1,
+                required: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,149 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Orients the undirected edges of a PDAG into a consistent DAG extension
+
+use rustc_hash::FxHashSet;
+
+use crate::PDAG;
+
+/// Returns a consistent DAG extension of the `pdag`, a DAG with the same skeleton, the same directed edges,
+/// and the same v-structures, or `None` if there is none. Every CPDAG has a consistent extension, which
+/// is one of the DAGs it represents.
+///
+/// Follows Dor and Tarsi (1992): a node that has no children and whose undirected neighbours are
+/// adjacent to all of its other neighbours can be a sink of the extension, so its undirected edges
+/// are oriented into it and it is removed from the graph, until no node is left.
+///
+/// ```
+/// use gadjid::{graph_operations::consistent_extension, PDAG};
+///
+/// // 0 -- 1 -- 2
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 2],
+///     vec![0, 0, 0],
+/// ]);
+/// let dag = consistent_extension(&cpdag).unwrap();
+/// assert_eq!(dag.n_undirected_edges, 0);
+/// assert_eq!(dag.n_directed_edges, 2);
+/// // orienting both edges into 1 would create the v-structure 0 -> 1 <- 2
+/// assert!(dag.parents_of(1).len() < 2);
+/// ```
+pub fn consistent_extension(pdag: &PDAG) -> Option<PDAG> {
+    let n = pdag.n_nodes;
+    let mut children =
+        Vec::from_iter((0..n).map(|v| FxHashSet::from_iter(pdag.children_of(v).to_vec())));
+    let mut parents =
+        Vec::from_iter((0..n).map(|v| FxHashSet::from_iter(pdag.parents_of(v).to_vec())));
+    let mut undirected = Vec::from_iter(
+        (0..n).map(|v| FxHashSet::from_iter(pdag.adjacent_undirected_of(v).to_vec())),
+    );
+
+    let mut edges =
+        Vec::from_iter((0..n).flat_map(|v| pdag.children_of(v).iter().map(move |w| (v, *w, 1))));
+    let mut remaining = FxHashSet::from_iter(0..n);
+    while !remaining.is_empty() {
+        let is_adjacent =
+            |v: usize, w: usize, parents: &[FxHashSet<usize>], undirected: &[FxHashSet<usize>]| {
+                parents[v].contains(&w) || parents[w].contains(&v) || undirected[v].contains(&w)
+            };
+        let sink = remaining.iter().copied().find(|x| {
+            children[*x].is_empty()
+                && undirected[*x].iter().all(|y| {
+                    parents[*x]
+                        .iter()
+                        .chain(&undirected[*x])
+                        .all(|z| z == y || is_adjacent(*y, *z, &parents, &undirected))
+                })
+        })?;
+
+        for y in std::mem::take(&mut undirected[sink]) {
+            undirected[y].remove(&sink);
+            edges.push((y, sink, 1));
+            children[y].insert(sink);
+            parents[sink].insert(y);
+        }
+        for p in std::mem::take(&mut parents[sink]) {
+            children[p].remove(&sink);
+        }
+        remaining.remove(&sink);
+    }
+    // orienting every undirected edge into a sink of the remaining graph cannot create a cycle
+    Some(PDAG::try_from_edges(n, edges).expect("consistent extensions are DAGs"))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rustc_hash::FxHashSet;
+
+    use crate::{graph_operations::shd::edge_type, PDAG};
+
+    use super::consistent_extension;
+
+    /// Checks that the `dag` is a consistent extension of the `pdag`.
+    fn assert_extends(dag: &PDAG, pdag: &PDAG) {
+        assert_eq!(dag.n_undirected_edges, 0);
+        for v in 0..pdag.n_nodes {
+            for w in 0..pdag.n_nodes {
+                let (in_pdag, in_dag) = (edge_type(pdag, v, w), edge_type(dag, v, w));
+                assert!(in_pdag == in_dag || (in_pdag == 3 && in_dag != 0));
+            }
+        }
+        let v_structures = |graph: &PDAG| {
+            FxHashSet::from_iter((0..graph.n_nodes).flat_map(|v| {
+                let parents = graph.parents_of(v);
+                parents.iter().flat_map(move |a| {
+                    parents
+                        .iter()
+                        .filter(move |b| a < *b && edge_type(graph, *a, **b) == 0)
+                        .map(move |b| (*a, v, *b))
+                })
+            }))
+        };
+        assert_eq!(v_structures(pdag), v_structures(dag));
+    }
+
+    #[test]
+    fn extends_cpdags() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+
+        for graph_id in 10..=19 {
+            let cpdag = crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-CPDAG-{}.mtx", graph_id))
+                    .to_str()
+                    .unwrap(),
+            );
+            assert_extends(&consistent_extension(&cpdag).unwrap(), &cpdag);
+        }
+    }
+
+    #[test]
+    fn property_extensions_of_random_pdags_are_consistent() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let pdag = PDAG::random_pdag(0.3, n, &mut rng);
+            if let Some(dag) = consistent_extension(&pdag) {
+                assert_extends(&dag, &pdag);
+            }
+            // a DAG is its own consistent extension
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            assert_eq!(consistent_extension(&dag).unwrap(), dag);
+        }
+    }
+
+    #[test]
+    fn rejects_pdags_without_extension() {
+        // 0 -> 1 -- 2 <- 3 with 0, 2 and 1, 3 not adjacent: either orientation of 1 -- 2 adds a v-structure
+        let pdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0],
+            vec![0, 0, 2, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 1, 0],
+        ]);
+        assert_eq!(consistent_extension(&pdag), None);
+    }
+}
@@ -4,7 +4,10 @@
 use rustc_hash::FxHashSet;
 
 use crate::{
-    graph_operations::ruletables::RuleTable, partially_directed_acyclic_graph::Edge, PDAG,
+    graph_operations::ruletables::RuleTable,
+    partially_directed_acyclic_graph::Edge,
+    sets::{use_bitset, BitSet, NodeSetImpl},
+    PDAG,
 };
 
 /// General reachability graph search algorithm, Algorithm 6 in https://doi.org/10.48550/arXiv.2211.16468
@@ -13,6 +16,25 @@ pub fn gensearch<'a>(
     ruletable: impl RuleTable,
     starting_vertices: impl Iterator<Item = &'a usize>,
     yield_starting_vertices: bool,
+) -> FxHashSet<usize> {
+    if use_bitset(dag.n_nodes) {
+        gensearch_with::<BitSet>(dag, ruletable, starting_vertices, yield_starting_vertices)
+    } else {
+        gensearch_with::<FxHashSet<usize>>(
+            dag,
+            ruletable,
+            starting_vertices,
+            yield_starting_vertices,
+        )
+    }
+}
+
+/// Like [`gensearch`], with the visited nodes marked in the set backend `S`.
+pub(crate) fn gensearch_with<'a, S: NodeSetImpl>(
+    dag: &PDAG,
+    ruletable: impl RuleTable,
+    starting_vertices: impl Iterator<Item = &'a usize>,
+    yield_starting_vertices: bool,
 ) -> FxHashSet<usize> {
     // Holds the edge traversed to get to some node and the node itself
     let mut to_visit_stack = Vec::<(Edge, usize)>::new();
@@ -27,8 +49,8 @@ pub fn gensearch<'a>(
     }
 
     // initialize all edges to visited=false for incoming and outgoing
-    let mut visited_in = FxHashSet::default();
-    let mut visited_out = FxHashSet::default();
+    let mut visited_in = S::for_keys(dag.n_nodes);
+    let mut visited_out = S::for_keys(dag.n_nodes);
 
     while let Some((current_edge, current_node)) = to_visit_stack.pop() {
         match current_edge {
@@ -52,8 +74,8 @@ pub fn gensearch<'a>(
                 let (continue_to_next, yield_next) =
                     ruletable.lookup(&current_edge, &current_node, &next_edge, &next_node);
                 if continue_to_next
-                    && (is_incoming && !visited_in.contains(&next_node)
-                        || !is_incoming && !visited_out.contains(&next_node))
+                    && (is_incoming && !visited_in.contains(next_node)
+                        || !is_incoming && !visited_out.contains(next_node))
                 {
                     to_visit_stack.push((next_edge, next_node));
                 }
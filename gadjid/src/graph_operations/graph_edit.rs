@@ -0,0 +1,301 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Implements a graph edit distance that, unlike the structural hamming distance,
+//! can explain many edge changes around the same node by a single node operation
+
+use crate::{graph_operations::shd::edge_type, PDAG};
+
+/// Up to this many nodes that are worth rewiring, the graph edit distance is computed exactly
+/// by branch and bound; for more nodes, it is approximated greedily.
+const MAX_NODES_EXACT: usize = 24;
+
+/// The graph edit distance and the edit script it is attained by, as returned by [`graph_edit_distance`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphEditDistance {
+    /// the total cost of the edits
+    pub cost: usize,
+    /// the (sorted) nodes that are rewired
+    pub rewired_nodes: Vec<usize>,
+    /// the number of single edge edits between nodes that are not rewired
+    pub edge_edits: usize,
+    /// whether the cost is the smallest possible one, or an upper bound found greedily
+    pub is_exact: bool,
+}
+
+/// The graph of the pairs of nodes whose edge types differ between two graphs,
+/// alongside which of its edges are covered by rewired nodes.
+struct Mismatches {
+    /// the nodes whose edge to each node differs between the graphs, with each pair in both lists
+    adjacent: Vec<Vec<usize>>,
+    /// whether each node is rewired
+    rewired: Vec<bool>,
+    /// the number of mismatches of each node with nodes that are not rewired
+    uncovered_degree: Vec<usize>,
+    /// the total number of uncovered mismatches
+    uncovered: usize,
+}
+
+impl Mismatches {
+    fn new(truth: &PDAG, guess: &PDAG) -> Mismatches {
+        let neighbours = |graph: &PDAG, v: usize| {
+            let edges = graph.children_of(v).iter().chain(graph.parents_of(v));
+            Vec::from_iter(edges.chain(graph.adjacent_undirected_of(v)).copied())
+        };
+        let mut adjacent = vec![Vec::new(); truth.n_nodes];
+        for v in 0..truth.n_nodes {
+            let mut candidates = neighbours(truth, v);
+            candidates.extend(neighbours(guess, v));
+            candidates.sort_unstable();
+            candidates.dedup();
+            for w in candidates.into_iter().filter(|w| v < *w) {
+                if edge_type(truth, v, w) != edge_type(guess, v, w) {
+                    adjacent[v].push(w);
+                    adjacent[w].push(v);
+                }
+            }
+        }
+        let uncovered_degree = Vec::from_iter(adjacent.iter().map(Vec::len));
+        Mismatches {
+            uncovered: uncovered_degree.iter().sum::<usize>() / 2,
+            rewired: vec![false; adjacent.len()],
+            adjacent,
+            uncovered_degree,
+        }
+    }
+
+    fn set_rewired(&mut self, v: usize, rewired: bool) {
+        self.rewired[v] = rewired;
+        for w in self.adjacent[v].iter() {
+            if rewired {
+                self.uncovered_degree[*w] -= 1;
+            } else {
+                self.uncovered_degree[*w] += 1;
+            }
+        }
+        if rewired {
+            self.uncovered -= self.uncovered_degree[v];
+        } else {
+            self.uncovered += self.uncovered_degree[v];
+        }
+    }
+
+    /// Returns the node that is neither rewired nor excluded whose rewiring
+    /// covers the most mismatches, if rewiring it is cheaper than editing those mismatches.
+    ///
+    /// If there is none, no set of further nodes is worth rewiring,
+    /// since together they cover at most `node_cost` mismatches per node.
+    fn best_node(&self, node_cost: usize, excluded: &[bool]) -> Option<usize> {
+        (0..self.adjacent.len())
+            .filter(|v| !self.rewired[*v] && !excluded[*v])
+            .filter(|v| self.uncovered_degree[*v] > node_cost)
+            .max_by_key(|v| (self.uncovered_degree[*v], std::cmp::Reverse(*v)))
+    }
+
+    /// Finds the cheapest set of nodes to rewire by branching on
+    /// whether the best node is rewired or excluded from rewiring.
+    fn branch_and_bound(
+        &mut self,
+        node_cost: usize,
+        excluded: &mut Vec<bool>,
+        cost: usize,
+        best: &mut (usize, Vec<bool>),
+    ) {
+        let Some(v) = self.best_node(node_cost, excluded) else {
+            if cost + self.uncovered < best.0 {
+                *best = (cost + self.uncovered, self.rewired.clone());
+            }
+            return;
+        };
+        // each rewiring covers at most as many mismatches as the best node,
+        // and each mismatch costs at least one otherwise
+        let max_degree = self.uncovered_degree[v];
+        let lower_bound = cost + (self.uncovered * node_cost).div_ceil(max_degree);
+        if lower_bound >= best.0 {
+            return;
+        }
+
+        self.set_rewired(v, true);
+        self.branch_and_bound(node_cost, excluded, cost + node_cost, best);
+        self.set_rewired(v, false);
+
+        excluded[v] = true;
+        self.branch_and_bound(node_cost, excluded, cost, best);
+        excluded[v] = false;
+    }
+
+    /// Greedily rewires the best node while that decreases the cost, and then
+    /// stops rewiring any node whose mismatches are mostly covered by other rewired nodes.
+    fn greedy(&mut self, node_cost: usize) {
+        let excluded = vec![false; self.adjacent.len()];
+        while let Some(v) = self.best_node(node_cost, &excluded) {
+            self.set_rewired(v, true);
+        }
+        for v in 0..self.adjacent.len() {
+            if self.rewired[v] {
+                // the mismatches only covered by v
+                if self.uncovered_degree[v] < node_cost {
+                    self.set_rewired(v, false);
+                }
+            }
+        }
+    }
+}
+
+/// Computes the graph edit distance between the `truth` and the `guess` DAG or CPDAG,
+/// that is, the smallest total cost of edits that turn the true graph into the guess graph.
+///
+/// An edge edit changes the edge between a single pair of nodes: it inserts, deletes, or reverses
+/// a directed edge, orients an undirected edge, or makes a directed edge undirected, at a cost of 1.
+/// Like for the structural hamming distance, a reversed edge is a single edit rather than a deletion
+/// followed by an insertion. A node edit rewires a node, removing all its edges and reinserting it
+/// with its edges in the guess graph, at a cost of `node_cost`, so that, for example,
+/// a misplaced node whose edges all differ can be explained by a single edit.
+/// With a `node_cost` of at least the number of nodes minus one,
+/// the distance equals the structural hamming distance.
+///
+/// Choosing the nodes to rewire is NP-hard in general, so the distance is computed exactly by
+/// branch and bound if at most 24 nodes have more differing edges than `node_cost`,
+/// and is otherwise approximated greedily (see [`GraphEditDistance::is_exact`]).
+///
+/// ```
+/// use gadjid::{graph_operations::graph_edit_distance, PDAG};
+///
+/// // truth: 1 <- 0 -> 2, 0 -> 3, guess: 1 -> 0 <- 2, 3 -> 0
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 1, 1],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_col_to_row(vec![
+///     vec![0, 1, 1, 1],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 0, 0, 0],
+/// ]);
+/// // rewiring node 0 is cheaper than reversing its three edges
+/// let distance = graph_edit_distance(&truth, &guess, 1);
+/// assert_eq!((distance.cost, distance.rewired_nodes, distance.edge_edits), (1, vec![0], 0));
+/// assert!(distance.is_exact);
+/// ```
+pub fn graph_edit_distance(truth: &PDAG, guess: &PDAG, node_cost: usize) -> GraphEditDistance {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    let mut mismatches = Mismatches::new(truth, guess);
+    let worth_rewiring = mismatches
+        .uncovered_degree
+        .iter()
+        .filter(|degree| **degree > node_cost)
+        .count();
+
+    let is_exact = worth_rewiring <= MAX_NODES_EXACT;
+    if is_exact {
+        let mut excluded = vec![false; truth.n_nodes];
+        let mut best = (mismatches.uncovered, vec![false; truth.n_nodes]);
+        mismatches.branch_and_bound(node_cost, &mut excluded, 0, &mut best);
+        for (v, rewired) in best.1.into_iter().enumerate() {
+            if rewired {
+                mismatches.set_rewired(v, true);
+            }
+        }
+    } else {
+        mismatches.greedy(node_cost);
+    }
+
+    let rewired_nodes = Vec::from_iter((0..truth.n_nodes).filter(|v| mismatches.rewired[*v]));
+    GraphEditDistance {
+        cost: rewired_nodes.len() * node_cost + mismatches.uncovered,
+        rewired_nodes,
+        edge_edits: mismatches.uncovered,
+        is_exact,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd, PDAG};
+
+    use super::{graph_edit_distance, Mismatches};
+
+    #[test]
+    fn rewires_misplaced_node() {
+        // truth: 0 -> 1, 0 -> 2, 0 -> 3, 0 -> 4, 1 -> 2, 3 -- 4
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 1, 1, 1], //
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 2],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        // guess: 1 -> 0, 1 -> 2, 3 -> 4
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0, 0, 0], //
+            vec![1, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 1],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(shd(&truth, &guess).1, 5);
+
+        let distance = graph_edit_distance(&truth, &guess, 2);
+        assert_eq!(distance.cost, 3);
+        assert_eq!(distance.rewired_nodes, vec![0]);
+        assert_eq!(distance.edge_edits, 1);
+        assert!(distance.is_exact);
+
+        // rewiring is not worth it if it costs as much as the edge edits it replaces
+        let distance = graph_edit_distance(&truth, &guess, 4);
+        assert_eq!((distance.cost, distance.edge_edits), (5, 5));
+        assert!(distance.rewired_nodes.is_empty());
+    }
+
+    #[test]
+    fn property_expensive_node_edits_give_shd() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..40 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let distance = graph_edit_distance(&truth, &guess, n);
+            assert_eq!(distance.cost, shd(&truth, &guess).1);
+            assert!(distance.is_exact);
+        }
+    }
+
+    #[test]
+    fn property_branch_and_bound_is_exact_and_greedy_is_bound() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..11 {
+            let truth = PDAG::random_pdag(0.5, n, &mut rng);
+            let guess = PDAG::random_pdag(0.5, n, &mut rng);
+            for node_cost in 0..4 {
+                let distance = graph_edit_distance(&truth, &guess, node_cost);
+
+                // the cheapest cost over all sets of nodes to rewire
+                let mut mismatches = Mismatches::new(&truth, &guess);
+                let brute_force = (0..1usize << n)
+                    .map(|set| {
+                        let rewired = Vec::from_iter((0..n).filter(|v| set & (1 << v) != 0));
+                        rewired
+                            .iter()
+                            .for_each(|v| mismatches.set_rewired(*v, true));
+                        let cost = rewired.len() * node_cost + mismatches.uncovered;
+                        rewired
+                            .iter()
+                            .for_each(|v| mismatches.set_rewired(*v, false));
+                        cost
+                    })
+                    .min()
+                    .unwrap();
+                assert_eq!(distance.cost, brute_force);
+
+                let mut mismatches = Mismatches::new(&truth, &guess);
+                mismatches.greedy(node_cost);
+                let rewired = mismatches.rewired.iter().filter(|r| **r).count();
+                assert!(rewired * node_cost + mismatches.uncovered >= distance.cost);
+            }
+        }
+    }
+}
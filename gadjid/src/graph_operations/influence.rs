@@ -0,0 +1,184 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Ranks the edges of a guess graph by how much removing or reversing each of them
+//! would change its distance to the true graph, to prioritize the manual review of a learned graph
+
+use rayon::prelude::*;
+
+use crate::PDAG;
+
+/// The influence of one edge of the guess on its distance, as returned by [`edge_influence`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EdgeInfluence {
+    /// the node the edge points out of, or the smaller node of an undirected edge
+    pub from: usize,
+    /// the node the edge points into, or the larger node of an undirected edge
+    pub to: usize,
+    /// whether the edge is directed
+    pub directed: bool,
+    /// the change in the number of mistakes if the edge were removed
+    pub removed: isize,
+    /// the change in the number of mistakes if the edge were reversed,
+    /// or `None` if the edge is undirected or reversing it would create a cycle
+    pub reversed: Option<isize>,
+}
+
+impl EdgeInfluence {
+    /// The largest decrease in the number of mistakes by removing or reversing the edge,
+    /// which is negative if every change of the edge increases the number of mistakes.
+    pub fn best_improvement(&self) -> isize {
+        -Ord::min(self.removed, self.reversed.unwrap_or(self.removed))
+    }
+}
+
+/// Computes, for each edge of the `guess`, the change in its `distance` to the `truth`
+/// if the edge were removed and, for directed edges whose reversal leaves the guess acyclic, reversed,
+/// by recomputing the distance for each changed guess in parallel.
+/// Returns the edges ranked by [`EdgeInfluence::best_improvement`], in descending order,
+/// so that the edges whose change corrects the most mistakes come first.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{edge_influence, shd},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1, guess: 0 <- 1, 0 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0, 1],
+///     vec![1, 0, 0],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let ranking = edge_influence(&truth, &guess, shd);
+/// // reversing 1 -> 0 corrects a mistake, so does removing 0 -> 2
+/// assert_eq!((ranking[0].from, ranking[0].to), (0, 2));
+/// assert_eq!((ranking[0].removed, ranking[0].reversed), (-1, Some(0)));
+/// assert_eq!((ranking[1].from, ranking[1].to), (1, 0));
+/// assert_eq!((ranking[1].removed, ranking[1].reversed), (0, Some(-1)));
+/// ```
+pub fn edge_influence(
+    truth: &PDAG,
+    guess: &PDAG,
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize) + Sync,
+) -> Vec<EdgeInfluence> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    // each undirected edge is listed once, from its smaller node
+    let mut edges = Vec::new();
+    for v in 0..guess.n_nodes {
+        edges.extend(guess.children_of(v).iter().map(|w| (v, *w, 1)));
+        edges.extend(
+            guess
+                .adjacent_undirected_of(v)
+                .iter()
+                .filter(|w| v < **w)
+                .map(|w| (v, *w, 2)),
+        );
+    }
+
+    let baseline = distance(truth, guess).1 as isize;
+    let changed = |index: usize, replacement: Option<(usize, usize, i8)>| {
+        let others = edges
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != index)
+            .map(|(_, edge)| *edge);
+        // removing an edge keeps the guess acyclic, reversing one may not
+        PDAG::try_from_edges(guess.n_nodes, others.chain(replacement))
+            .ok()
+            .map(|changed| distance(truth, &changed).1 as isize - baseline)
+    };
+
+    let mut influences: Vec<EdgeInfluence> = edges
+        .par_iter()
+        .enumerate()
+        .map(|(index, (from, to, edge))| {
+            let directed = *edge == 1;
+            EdgeInfluence {
+                from: *from,
+                to: *to,
+                directed,
+                removed: changed(index, None).expect("removing an edge keeps a PDAG a PDAG"),
+                reversed: if directed {
+                    changed(index, Some((*to, *from, 1)))
+                } else {
+                    None
+                },
+            }
+        })
+        .collect();
+    // the sort is stable, so edges of equal influence stay in the order of their nodes
+    influences.sort_by_key(|influence| -influence.best_improvement());
+    influences
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{oset_aid, shd},
+        PDAG,
+    };
+
+    use super::edge_influence;
+
+    #[test]
+    fn property_influences_match_recomputed_distances() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..12 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let ranking = edge_influence(&truth, &guess, oset_aid);
+            assert_eq!(
+                ranking.len(),
+                guess.n_directed_edges + guess.n_undirected_edges
+            );
+            assert!(ranking
+                .windows(2)
+                .all(|w| w[0].best_improvement() >= w[1].best_improvement()));
+
+            let baseline = oset_aid(&truth, &guess).1 as isize;
+            for influence in ranking {
+                let mut dense = guess.to_row_to_column_vecvec();
+                dense[influence.from][influence.to] = 0;
+                dense[influence.to][influence.from] = 0;
+                let removed = PDAG::from_dense_row_to_col(dense.clone());
+                assert_eq!(
+                    influence.removed,
+                    oset_aid(&truth, &removed).1 as isize - baseline
+                );
+                dense[influence.to][influence.from] = 1;
+                let reversed = PDAG::try_from_dense_row_to_col(dense).ok();
+                match (influence.directed, reversed) {
+                    (true, Some(reversed)) => assert_eq!(
+                        influence.reversed,
+                        Some(oset_aid(&truth, &reversed).1 as isize - baseline)
+                    ),
+                    _ => assert_eq!(influence.reversed, None),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn correct_guess_has_no_improving_edge() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let dag = PDAG::random_dag(0.5, 10, &mut rng);
+        for influence in edge_influence(&dag, &dag, shd) {
+            assert_eq!(influence.removed, 1);
+            assert_eq!(influence.best_improvement(), -1);
+        }
+    }
+}
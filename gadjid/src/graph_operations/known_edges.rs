@@ -0,0 +1,308 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Excludes node pairs whose edge is known a priori from the distances,
+//! so that only what a learner had to discover is graded
+
+use crate::{
+    graph_operations::{
+        aid::{Aid, AidOutput},
+        selected_pairs::{MistakeSink, PairsError, Verdict},
+        shd,
+        shd::edge_type,
+    },
+    PDAG,
+};
+
+/// Unordered node pairs whose edge (or absence of an edge) is known a priori,
+/// such as edges fixed by background knowledge, which are excluded from grading.
+/// Passed by reference to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings, the `(treatment, effect)` pairs
+/// (in either order) whose edge is known are not compared, and the distance is normalized
+/// by the number of pairs that are not excluded.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, KnownEdges}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the edge between 1 and 2 is known, so the pairs (1, 2) and (2, 1) are not compared
+/// let known = KnownEdges::new(3, &[(1, 2)]).unwrap();
+/// assert_eq!(ancestor_aid_with(&truth, &guess, &known), (0.25, 1));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnownEdges {
+    /// the (sorted) nodes known to each node
+    known_of: Vec<Vec<usize>>,
+    /// the number of distinct unordered pairs
+    n_pairs: usize,
+}
+
+impl KnownEdges {
+    /// Collects the unordered `pairs` of nodes of graphs with `n_nodes` nodes,
+    /// counting repeated pairs (in either order) once,
+    /// or returns an error if a node is out of bounds or a pair has the same node twice.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::KnownEdges;
+    ///
+    /// let known = KnownEdges::new(3, &[(0, 1), (1, 0), (1, 2)]).unwrap();
+    /// assert_eq!(known.n_pairs(), 2);
+    /// assert!(KnownEdges::new(3, &[(0, 3)]).is_err());
+    /// ```
+    pub fn new(n_nodes: usize, pairs: &[(usize, usize)]) -> Result<KnownEdges, PairsError> {
+        let mut known_of = vec![Vec::new(); n_nodes];
+        for (v, w) in pairs.iter().copied() {
+            if let Some(node) = [v, w].into_iter().find(|node| *node >= n_nodes) {
+                return Err(PairsError::OutOfBounds { node, n_nodes });
+            }
+            if v == w {
+                return Err(PairsError::TreatmentIsEffect { node: v });
+            }
+            known_of[v].push(w);
+            known_of[w].push(v);
+        }
+        for known in known_of.iter_mut() {
+            known.sort_unstable();
+            known.dedup();
+        }
+        let n_pairs = known_of.iter().map(Vec::len).sum::<usize>() / 2;
+        Ok(KnownEdges { known_of, n_pairs })
+    }
+
+    /// Returns whether the edge between the nodes v and w is known.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::KnownEdges;
+    ///
+    /// let known = KnownEdges::new(3, &[(1, 2)]).unwrap();
+    /// assert!(known.contains(1, 2) && known.contains(2, 1));
+    /// assert!(!known.contains(0, 1));
+    /// ```
+    pub fn contains(&self, v: usize, w: usize) -> bool {
+        self.known_of[v].binary_search(&w).is_ok()
+    }
+
+    /// The number of distinct unordered pairs whose edge is known.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::KnownEdges;
+    ///
+    /// assert_eq!(KnownEdges::new(4, &[(0, 1), (2, 3), (3, 2)]).unwrap().n_pairs(), 2);
+    /// ```
+    pub fn n_pairs(&self) -> usize {
+        self.n_pairs
+    }
+}
+
+/// Computes the structural hamming distance between two graphs,
+/// only counting the errors between pairs of nodes whose edge is not `known`.
+/// Returns a tuple of (normalized error (in \[0,1]), total number of errors),
+/// normalized by the number of unordered pairs whose edge is not known.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{shd_with_known_edges, KnownEdges},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the reversed edge between 1 and 2 is known, so it is not counted
+/// let known = KnownEdges::new(3, &[(1, 2)]).unwrap();
+/// assert_eq!(shd_with_known_edges(&truth, &guess, &known), (0.0, 0));
+/// ```
+pub fn shd_with_known_edges(g_truth: &PDAG, g_guess: &PDAG, known: &KnownEdges) -> (f64, usize) {
+    assert_eq!(g_truth.n_nodes, g_guess.n_nodes, "graph size mismatch");
+    assert_eq!(
+        known.known_of.len(),
+        g_truth.n_nodes,
+        "known edges must be of graphs of the same size"
+    );
+    let (_, errors) = shd(g_truth, g_guess);
+    let known_errors = (0..g_truth.n_nodes)
+        .flat_map(|v| {
+            known.known_of[v]
+                .iter()
+                .filter(move |w| v < **w)
+                .map(move |w| (v, *w))
+        })
+        .filter(|(v, w)| edge_type(g_truth, *v, *w) != edge_type(g_guess, *v, *w))
+        .count();
+
+    let comparisons = g_truth.n_nodes * (g_truth.n_nodes - 1) / 2 - known.n_pairs;
+    let errors = errors - known_errors;
+    if comparisons == 0 {
+        return (0.0, 0);
+    }
+    (errors as f64 / comparisons as f64, errors)
+}
+
+/// Counts the mistakes of the pairs whose edge is not known.
+struct UnknownMistakes<'a> {
+    known: &'a KnownEdges,
+    mistakes: usize,
+}
+
+impl MistakeSink for UnknownMistakes<'_> {
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict) {
+        if verdict != Verdict::Correct {
+            for t in treatments {
+                self.mistakes += effects
+                    .iter()
+                    .filter(|y| !self.known.contains(*t, **y))
+                    .count();
+            }
+        }
+    }
+}
+
+/// Excludes the pairs whose edge is known,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+impl AidOutput for &KnownEdges {
+    type Output = (f64, usize);
+
+    fn compute(self, aid: &Aid) -> (f64, usize) {
+        assert_eq!(
+            self.known_of.len(),
+            aid.truth().n_nodes,
+            "known edges must be of graphs of the same size"
+        );
+
+        // twins can differ in their known edges
+        let verifier_mistakes_found = aid
+            .each_treatment(|_| UnknownMistakes {
+                known: self,
+                mistakes: 0,
+            })
+            .iter()
+            .map(|sink| sink.mistakes)
+            .sum();
+
+        let comparisons = aid.n_pairs() - 2 * self.n_pairs;
+        if comparisons == 0 {
+            return (0.0, 0);
+        }
+        (
+            verifier_mistakes_found as f64 / comparisons as f64,
+            verifier_mistakes_found,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid_with, parent_aid_with,
+            selected_pairs::PairsError, shd, SelectedPairs,
+        },
+        PDAG,
+    };
+
+    use super::{shd_with_known_edges, KnownEdges};
+
+    #[test]
+    fn rejects_invalid_pairs() {
+        assert_eq!(
+            KnownEdges::new(3, &[(0, 3)]),
+            Err(PairsError::OutOfBounds {
+                node: 3,
+                n_nodes: 3
+            })
+        );
+        assert_eq!(
+            KnownEdges::new(3, &[(1, 1)]),
+            Err(PairsError::TreatmentIsEffect { node: 1 })
+        );
+        let known = KnownEdges::new(3, &[(0, 1), (1, 0), (2, 1)]).unwrap();
+        assert_eq!(known.n_pairs(), 2);
+        assert!(known.contains(1, 0) && known.contains(1, 2) && !known.contains(0, 2));
+    }
+
+    #[test]
+    fn known_edges_are_not_graded() {
+        // truth: 0 -> 1 -> 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        // guess: 0 <- 1 -> 2
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0], //
+            vec![1, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        let no_known = KnownEdges::new(3, &[]).unwrap();
+        assert_eq!(
+            shd_with_known_edges(&truth, &guess, &no_known),
+            shd(&truth, &guess)
+        );
+        assert_eq!(
+            ancestor_aid_with(&truth, &guess, &no_known),
+            ancestor_aid(&truth, &guess)
+        );
+
+        // the known edge 1 -> 2 is correct, and knowing the reversed edge 0 -> 1 excuses it
+        let known = KnownEdges::new(3, &[(1, 2)]).unwrap();
+        assert_eq!(shd_with_known_edges(&truth, &guess, &known), (0.5, 1));
+        let known = KnownEdges::new(3, &[(1, 0)]).unwrap();
+        assert_eq!(shd_with_known_edges(&truth, &guess, &known), (0.0, 0));
+    }
+
+    #[test]
+    fn property_aids_with_known_edges_equal_selected_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 4..20 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let known_pairs =
+                Vec::from_iter((0..n).map(|v| (v, (v * 7 + 1) % n)).filter(|(v, w)| v != w));
+            let known = KnownEdges::new(n, &known_pairs).unwrap();
+            let pairs = Vec::from_iter((0..n).flat_map(|t| {
+                let known = &known;
+                (0..n)
+                    .filter(move |y| *y != t && !known.contains(t, *y))
+                    .map(move |y| (t, y))
+            }));
+            assert_eq!(
+                ancestor_aid_with(&truth, &guess, &known),
+                ancestor_aid_with(&truth, &guess, SelectedPairs(&pairs))
+                    .unwrap()
+                    .distance()
+            );
+            assert_eq!(
+                oset_aid_with(&truth, &guess, &known),
+                oset_aid_with(&truth, &guess, SelectedPairs(&pairs))
+                    .unwrap()
+                    .distance()
+            );
+            assert_eq!(
+                parent_aid_with(&truth, &guess, &known),
+                parent_aid_with(&truth, &guess, SelectedPairs(&pairs))
+                    .unwrap()
+                    .distance()
+            );
+        }
+    }
+}
@@ -0,0 +1,812 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Grades a guess CPDAG against a true DAG by the members of the guess's Markov equivalence class,
+//! whose DAGs orient the undirected edges of each chain component independently
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::graph_operations::{
+    shd,
+    sid::{mistakes_with_parents, SIDError},
+};
+use crate::partially_directed_acyclic_graph::Structure::DAG;
+use crate::PDAG;
+
+/// The largest chain component whose orientation is optimized exactly, over all subsets of its nodes.
+const EXACT_COMPONENT_SIZE: usize = 20;
+
+/// The SHD between a true DAG and the closest DAG in the Markov equivalence class of a guess CPDAG,
+/// as returned by [`min_shd_over_mec`].
+#[derive(Debug, PartialEq)]
+pub struct MecShd {
+    /// The number of mistakes divided by the number of unordered pairs of nodes, in \[0,1].
+    pub normalized: f64,
+    /// The SHD between the true DAG and `dag`.
+    pub mistakes: usize,
+    /// A DAG in the Markov equivalence class of the guess CPDAG with the reported SHD.
+    pub dag: PDAG,
+    /// Whether the SHD is provably the minimum over the Markov equivalence class; it is unless a chain
+    /// component with more than 20 nodes is oriented heuristically with some edge against the true DAG.
+    pub exact: bool,
+}
+
+/// Returns the chain components of the `pdag` with at least two nodes, the connected components
+/// of its undirected edges, each sorted, in order of their smallest node.
+pub(crate) fn chain_components(pdag: &PDAG) -> Vec<Vec<usize>> {
+    let mut component_of = vec![None; pdag.n_nodes];
+    let mut components = Vec::new();
+    for start in 0..pdag.n_nodes {
+        if component_of[start].is_some() || pdag.adjacent_undirected_of(start).is_empty() {
+            continue;
+        }
+        let mut component = vec![start];
+        component_of[start] = Some(components.len());
+        let mut to_visit = vec![start];
+        while let Some(v) = to_visit.pop() {
+            for w in pdag.adjacent_undirected_of(v) {
+                if component_of[*w].is_none() {
+                    component_of[*w] = Some(components.len());
+                    component.push(*w);
+                    to_visit.push(*w);
+                }
+            }
+        }
+        component.sort_unstable();
+        components.push(component);
+    }
+    components
+}
+
+/// The undirected edges within a chain component, as the sorted local neighbours of each node,
+/// alongside the local neighbours that `truth` has as children of each node.
+fn component_lists(
+    component: &[usize],
+    guess: &PDAG,
+    truth: &PDAG,
+) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let local = |neighbours: &[usize]| {
+        Vec::from_iter(
+            neighbours
+                .iter()
+                .filter_map(|w| component.binary_search(w).ok()),
+        )
+    };
+    let adjacent = Vec::from_iter(
+        component
+            .iter()
+            .map(|v| local(guess.adjacent_undirected_of(*v))),
+    );
+    let truth_children = Vec::from_iter(component.iter().map(|v| local(truth.children_of(*v))));
+    (adjacent, truth_children)
+}
+
+fn to_masks(lists: &[Vec<usize>]) -> Vec<u64> {
+    Vec::from_iter(
+        lists
+            .iter()
+            .map(|list| list.iter().fold(0u64, |mask, w| mask | 1 << w)),
+    )
+}
+
+fn is_clique(nodes: u64, adjacent: &[u64]) -> bool {
+    let mut rest = nodes;
+    while rest != 0 {
+        let u = rest.trailing_zeros() as usize;
+        rest &= rest - 1;
+        if nodes & !(1 << u) & !adjacent[u] != 0 {
+            return false;
+        }
+    }
+    true
+}
+
+/// Orders the `k` nodes of a chain component so that each node's earlier neighbours form a clique,
+/// which makes orienting every edge from the earlier to the later node acyclic and free of v-structures,
+/// minimizing the number of edges `u -> v` whose reversal `v -> u` is in the true graph,
+/// by dynamic programming over the sets of already ordered nodes.
+/// Returns `None` if there is no such order, since the component is not chordal.
+fn exact_order(adjacent: &[u64], truth_children: &[u64]) -> Option<(Vec<usize>, usize)> {
+    let k = adjacent.len();
+    let full = (1usize << k) - 1;
+    let mut cost = vec![usize::MAX; full + 1];
+    let mut last = vec![0u8; full + 1];
+    cost[0] = 0;
+    for placed in 0..full {
+        if cost[placed] == usize::MAX {
+            continue;
+        }
+        for v in (0..k).filter(|v| placed & 1 << v == 0) {
+            let earlier = adjacent[v] & placed as u64;
+            if !is_clique(earlier, adjacent) {
+                continue;
+            }
+            let next = placed | 1 << v;
+            let next_cost = cost[placed] + (earlier & truth_children[v]).count_ones() as usize;
+            if next_cost < cost[next] {
+                cost[next] = next_cost;
+                last[next] = v as u8;
+            }
+        }
+    }
+    if cost[full] == usize::MAX {
+        return None;
+    }
+    let mut order = Vec::with_capacity(k);
+    let mut placed = full;
+    while placed != 0 {
+        let v = last[placed] as usize;
+        order.push(v);
+        placed &= !(1 << v);
+    }
+    order.reverse();
+    Some((order, cost[full]))
+}
+
+/// The most start nodes that [`greedy_order`] searches from.
+const GREEDY_STARTS: usize = 4;
+
+/// Orders the `k` nodes of a chordal chain component by maximum cardinality search from up to
+/// [`GREEDY_STARTS`] nodes without a parent in the true graph, breaking ties by the fewest edges against
+/// the true graph, like [`exact_order`] without the guarantee of minimality. Every maximum cardinality
+/// search order of a chordal graph has earlier neighbours that form a clique.
+fn greedy_order(adjacent: &[Vec<usize>], truth_children: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let k = adjacent.len();
+    let mut has_truth_parent = vec![false; k];
+    for w in truth_children.iter().flatten() {
+        has_truth_parent[*w] = true;
+    }
+    // the true graph is acyclic, so some node of the component has no parent in it
+    let starts = (0..k).filter(|v| !has_truth_parent[*v]).take(GREEDY_STARTS);
+
+    let mut best = (Vec::new(), usize::MAX);
+    for start in starts {
+        let mut placed = vec![false; k];
+        let mut n_placed_neighbours = vec![0; k];
+        // the number of placed neighbours that are children of each node in the true graph,
+        // which is the number of edges against the truth if the node comes next
+        let mut cost = vec![0; k];
+        // keyed by the most placed neighbours, then the lowest cost, then the lowest node,
+        // every update pushes a node again with more placed neighbours, so its latest entry is popped first
+        let mut candidates = BinaryHeap::from([Reverse((Reverse(0), 0, start))]);
+        let mut order = Vec::with_capacity(k);
+        let mut total = 0;
+        while let Some(Reverse((_, _, next))) = candidates.pop() {
+            if placed[next] {
+                continue;
+            }
+            placed[next] = true;
+            order.push(next);
+            total += cost[next];
+            for w in adjacent[next].iter().filter(|w| !placed[**w]) {
+                n_placed_neighbours[*w] += 1;
+                if truth_children[*w].binary_search(&next).is_ok() {
+                    cost[*w] += 1;
+                }
+                candidates.push(Reverse((Reverse(n_placed_neighbours[*w]), cost[*w], *w)));
+            }
+        }
+        if total < best.1 {
+            best = (order, total);
+        }
+    }
+    best
+}
+
+/// Computes the minimum structural hamming distance between the `truth` DAG and the DAGs in the
+/// Markov equivalence class of the `guess` CPDAG, so that a CPDAG is not punished for the orientations
+/// that observational data cannot decide. Returns the distance alongside a DAG in the class that attains it,
+/// or `None` if the chain components of the `guess` cannot be oriented without cycles or new v-structures,
+/// since it is not a CPDAG. Panics if the `truth` has undirected edges.
+///
+/// The undirected edges of each chain component are oriented independently: exactly over all subsets of the
+/// nodes for components of up to 20 nodes, and greedily by maximum cardinality search for larger ones,
+/// in which case [`MecShd::exact`] tells whether the distance is provably minimal.
+///
+/// ```
+/// use gadjid::{graph_operations::{min_shd_over_mec, shd}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 -- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 2],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// // the truth is in the Markov equivalence class of the guess
+/// assert_eq!(shd(&truth, &guess), (2.0 / 3.0, 2));
+/// let closest = min_shd_over_mec(&truth, &guess).unwrap();
+/// assert_eq!((closest.normalized, closest.mistakes), (0.0, 0));
+/// assert_eq!(closest.dag, truth);
+/// assert!(closest.exact);
+/// ```
+pub fn min_shd_over_mec(truth: &PDAG, guess: &PDAG) -> Option<MecShd> {
+    assert_eq!(
+        truth.n_nodes, guess.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert_eq!(truth.n_undirected_edges, 0, "truth graph must be a DAG");
+
+    let mut edges = Vec::from_iter(
+        (0..guess.n_nodes).flat_map(|v| guess.children_of(v).iter().map(move |w| (v, *w, 1))),
+    );
+    let mut exact = true;
+    for component in chain_components(guess) {
+        let (adjacent, truth_children) = component_lists(&component, guess, truth);
+        let order = if component.len() <= EXACT_COMPONENT_SIZE {
+            exact_order(&to_masks(&adjacent), &to_masks(&truth_children))?.0
+        } else {
+            let (order, cost) = greedy_order(&adjacent, &truth_children);
+            // no DAG in the class can orient fewer edges against the truth
+            exact &= cost == 0;
+            order
+        };
+        let mut position = vec![0; component.len()];
+        for (index, v) in order.iter().enumerate() {
+            position[*v] = index;
+        }
+        for (v, neighbours) in adjacent.iter().enumerate() {
+            let earlier = Vec::from_iter(neighbours.iter().filter(|w| position[**w] < position[v]));
+            // two non-adjacent earlier neighbours would be a new v-structure, as for non-chordal components
+            if earlier.iter().any(|u| {
+                earlier
+                    .iter()
+                    .any(|w| u != w && adjacent[**u].binary_search(w).is_err())
+            }) {
+                return None;
+            }
+            for w in neighbours.iter().filter(|w| position[v] < position[**w]) {
+                edges.push((component[v], component[*w], 1));
+            }
+        }
+    }
+    let dag = PDAG::try_from_edges(guess.n_nodes, edges).ok()?;
+
+    let (normalized, mistakes) = shd(truth, &dag);
+    Some(MecShd {
+        normalized,
+        mistakes,
+        dag,
+        exact,
+    })
+}
+
+/// The SID between a true DAG and the DAGs in the Markov equivalence class of a guess CPDAG,
+/// as returned by [`sid_over_mec`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MecSid {
+    /// A tuple of (normalized error (in \[0,1]), total number of errors) of the closest DAG in the class.
+    pub min: (f64, usize),
+    /// A tuple of (normalized error (in \[0,1]), number of errors) averaged over the DAGs in the class.
+    pub mean: (f64, f64),
+    /// A tuple of (normalized error (in \[0,1]), total number of errors) of the farthest DAG in the class.
+    pub max: (f64, usize),
+    /// The number of DAGs in the class.
+    pub n_dags: u128,
+}
+
+/// The most orientations of a chain component that [`sid_over_mec`] enumerates, a clique of 10 nodes has more.
+const MAX_COMPONENT_ORIENTATIONS: usize = 1_000_000;
+
+/// Calls `visit` with each orientation of the undirected edges of a chain component of the `guess`
+/// that neither creates a cycle nor a new v-structure, as the sorted local parents within the component
+/// of every node, without storing the orientations. Returns the number of orientations,
+/// or `None` as soon as there are more than `limit`.
+fn for_each_orientation(
+    component: &[usize],
+    guess: &PDAG,
+    limit: usize,
+    visit: &mut impl FnMut(&[Vec<usize>]),
+) -> Option<usize> {
+    let k = component.len();
+    let adjacent = Vec::from_iter(component.iter().map(|v| {
+        Vec::from_iter(
+            guess
+                .adjacent_undirected_of(*v)
+                .iter()
+                .filter_map(|w| component.binary_search(w).ok()),
+        )
+    }));
+    let is_adjacent = |v: usize, w: usize| {
+        guess.adjacent_undirected_of(v).binary_search(&w).is_ok()
+            || guess.parents_of(v).binary_search(&w).is_ok()
+            || guess.children_of(v).binary_search(&w).is_ok()
+    };
+
+    // edges in breadth-first order, so that cycles and v-structures are cut off early
+    let mut order = vec![0];
+    let mut visited = vec![false; k];
+    visited[0] = true;
+    let mut i = 0;
+    while let Some(v) = order.get(i).copied() {
+        for w in &adjacent[v] {
+            if !visited[*w] {
+                visited[*w] = true;
+                order.push(*w);
+            }
+        }
+        i += 1;
+    }
+    let mut done = vec![false; k];
+    let mut edges = Vec::new();
+    for v in order {
+        edges.extend(adjacent[v].iter().filter(|w| !done[**w]).map(|w| (v, *w)));
+        done[v] = true;
+    }
+
+    /// The state of the depth-first enumeration of the orientations.
+    struct Enumeration<'a, V> {
+        parents: Vec<Vec<usize>>,
+        sorted: Vec<Vec<usize>>,
+        count: usize,
+        limit: usize,
+        visit: &'a mut V,
+    }
+
+    /// Orients the `edges` in both directions that are allowed, returns false once over the limit.
+    fn orient<V: FnMut(&[Vec<usize>])>(
+        edges: &[(usize, usize)],
+        is_allowed: &impl Fn(&[Vec<usize>], usize, usize) -> bool,
+        state: &mut Enumeration<'_, V>,
+    ) -> bool {
+        let Some(((v, w), rest)) = edges.split_first() else {
+            state.count += 1;
+            if state.count > state.limit {
+                return false;
+            }
+            for (sorted, parents) in state.sorted.iter_mut().zip(&state.parents) {
+                sorted.clone_from(parents);
+                sorted.sort_unstable();
+            }
+            (state.visit)(&state.sorted);
+            return true;
+        };
+        for (from, to) in [(*v, *w), (*w, *v)] {
+            if is_allowed(&state.parents, from, to) {
+                state.parents[to].push(from);
+                let within_limit = orient(rest, is_allowed, state);
+                state.parents[to].pop();
+                if !within_limit {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+    let is_allowed = |parents: &[Vec<usize>], from: usize, to: usize| {
+        // a parent of `to` that is not adjacent to `from` would form a new v-structure
+        let v_structure = parents[to]
+            .iter()
+            .map(|p| component[*p])
+            .chain(guess.parents_of(component[to]).iter().copied())
+            .any(|p| !is_adjacent(p, component[from]));
+        // an oriented path from `to` to `from` would close a cycle
+        let mut ancestors = vec![from];
+        let mut seen = vec![false; k];
+        let mut cycle = false;
+        while let Some(a) = ancestors.pop() {
+            if a == to {
+                cycle = true;
+                break;
+            }
+            for p in &parents[a] {
+                if !seen[*p] {
+                    seen[*p] = true;
+                    ancestors.push(*p);
+                }
+            }
+        }
+        !v_structure && !cycle
+    };
+    let mut state = Enumeration {
+        parents: vec![Vec::new(); k],
+        sorted: vec![Vec::new(); k],
+        count: 0,
+        limit,
+        visit,
+    };
+    orient(&edges, &is_allowed, &mut state).then_some(state.count)
+}
+
+/// Enumerates the DAGs in the Markov equivalence class of the `cpdag`, which orient the undirected edges of
+/// each chain component without cycles or new v-structures, or returns `None` if a chain component has no
+/// such orientation. The number of DAGs grows exponentially with the size of the chain components,
+/// so [`sid_over_mec`] only enumerates the orientations of each chain component by itself.
+///
+/// ```
+/// use gadjid::{graph_operations::mec_dags, PDAG};
+///
+/// // 0 -- 1 -- 2 is represented by 0 -> 1 -> 2, 0 <- 1 <- 2, and 0 <- 1 -> 2
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 2],
+///     vec![0, 0, 0],
+/// ]);
+/// assert_eq!(mec_dags(&cpdag).unwrap().len(), 3);
+/// ```
+pub fn mec_dags(cpdag: &PDAG) -> Option<Vec<PDAG>> {
+    let mut edge_sets =
+        vec![Vec::from_iter((0..cpdag.n_nodes).flat_map(|v| {
+            cpdag.children_of(v).iter().map(move |w| (v, *w, 1))
+        }))];
+    for component in chain_components(cpdag) {
+        let mut orientations = Vec::new();
+        for_each_orientation(&component, cpdag, usize::MAX, &mut |parents| {
+            orientations.push(parents.to_vec())
+        });
+        if orientations.is_empty() {
+            return None;
+        }
+        edge_sets = Vec::from_iter(edge_sets.iter().flat_map(|edges| {
+            orientations.iter().map(|parents| {
+                let mut edges = edges.clone();
+                for (v, local_parents) in parents.iter().enumerate() {
+                    edges.extend(
+                        local_parents
+                            .iter()
+                            .map(|p| (component[*p], component[v], 1)),
+                    );
+                }
+                edges
+            })
+        }));
+    }
+    edge_sets
+        .into_iter()
+        .map(|edges| PDAG::try_from_edges(cpdag.n_nodes, edges).ok())
+        .collect()
+}
+
+/// Structural Intervention Distance between the `truth` DAG and the DAGs in the Markov equivalence class
+/// of the `guess` CPDAG, reporting the fewest, the average, and the most mistakes over the class
+/// alongside the number of DAGs in it. A DAG guess is its own class.
+/// Returns [`SIDError::GuessNotExtendable`] if a chain component of the `guess` cannot be oriented
+/// without cycles or new v-structures, and [`SIDError::GuessClassTooLarge`] if a chain component
+/// has more than 10^6 orientations, as a clique of 10 nodes has, or the class has more than 2^128 DAGs.
+///
+/// Since the SID mistakes of a treatment only depend on its parents in the guess DAG, which only depend
+/// on the orientation of its own chain component, each chain component is enumerated by itself
+/// and the mistakes of each treatment are computed once per distinct parent set;
+/// the orientations are folded into their extremes and their sum as they are enumerated, without storing them,
+/// and the extremes and the average over the class are the sums of those over the chain components.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{sid, sid_over_mec},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 -- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 2],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let over_mec = sid_over_mec(&truth, &guess).unwrap();
+/// assert_eq!(over_mec.n_dags, 3);
+/// // the truth is one of the DAGs in the class
+/// assert_eq!(over_mec.min, (0.0, 0));
+/// assert!(over_mec.mean.1 <= over_mec.max.1 as f64);
+///
+/// // a DAG guess is its own class
+/// let exact = sid_over_mec(&truth, &truth).unwrap();
+/// assert_eq!((exact.min, exact.max, exact.n_dags), ((0.0, 0), (0.0, 0), 1));
+/// ```
+pub fn sid_over_mec(truth: &PDAG, guess: &PDAG) -> Result<MecSid, SIDError> {
+    sid_over_mec_limited(truth, guess, MAX_COMPONENT_ORIENTATIONS)
+}
+
+/// [`sid_over_mec`] with at most `max_orientations` orientations per chain component.
+fn sid_over_mec_limited(
+    truth: &PDAG,
+    guess: &PDAG,
+    max_orientations: usize,
+) -> Result<MecSid, SIDError> {
+    if !matches!(truth.pdag_type, DAG) {
+        return Err(SIDError::TruthNotDAG);
+    }
+    if truth.n_nodes != guess.n_nodes {
+        return Err(SIDError::NotSameSize);
+    }
+    crate::rayon::build_global();
+
+    let all_nodes = Vec::from_iter(0..truth.n_nodes);
+    let components = chain_components(guess);
+
+    // treatments outside of chain components have the same parents in every DAG in the class
+    let mut in_component = vec![false; truth.n_nodes];
+    components
+        .iter()
+        .flatten()
+        .for_each(|v| in_component[*v] = true);
+    let fixed: usize = (0..truth.n_nodes)
+        .into_par_iter()
+        .filter(|v| !in_component[*v])
+        .map(|v| mistakes_with_parents(truth, v, guess.parents_of(v), &all_nodes))
+        .sum();
+
+    let (mut min, mut mean, mut max, mut n_dags) = (fixed, fixed as f64, fixed, 1u128);
+    for component in components {
+        // the parent sets each treatment has in some orientation, which are far fewer than the orientations
+        let mut parent_sets = vec![FxHashSet::<Vec<usize>>::default(); component.len()];
+        let n_orientations =
+            for_each_orientation(&component, guess, max_orientations, &mut |parents| {
+                for (sets, local_parents) in parent_sets.iter_mut().zip(parents) {
+                    if !sets.contains(local_parents) {
+                        sets.insert(local_parents.clone());
+                    }
+                }
+            })
+            .ok_or(SIDError::GuessClassTooLarge)?;
+        if n_orientations == 0 {
+            return Err(SIDError::GuessNotExtendable);
+        }
+
+        // the mistakes of each treatment, once per parent set it has in some orientation
+        let mistakes: Vec<FxHashMap<Vec<usize>, usize>> = parent_sets
+            .into_par_iter()
+            .enumerate()
+            .map(|(v, sets)| {
+                let mistakes_of = |local_parents: Vec<usize>| {
+                    let mut parents = guess.parents_of(component[v]).to_vec();
+                    parents.extend(local_parents.iter().map(|p| component[*p]));
+                    parents.sort_unstable();
+                    let mistakes = mistakes_with_parents(truth, component[v], &parents, &all_nodes);
+                    (local_parents, mistakes)
+                };
+                FxHashMap::from_iter(sets.into_iter().map(mistakes_of))
+            })
+            .collect();
+
+        // fold the mistakes of each orientation into the extremes and the sum over the component
+        let (mut component_min, mut component_max, mut component_sum) = (usize::MAX, 0, 0);
+        for_each_orientation(&component, guess, n_orientations, &mut |parents| {
+            let total: usize = parents
+                .iter()
+                .zip(&mistakes)
+                .map(|(local_parents, mistakes)| mistakes[local_parents])
+                .sum();
+            component_min = component_min.min(total);
+            component_max = component_max.max(total);
+            component_sum += total;
+        });
+        min += component_min;
+        max += component_max;
+        mean += component_sum as f64 / n_orientations as f64;
+        n_dags = n_dags
+            .checked_mul(n_orientations as u128)
+            .ok_or(SIDError::GuessClassTooLarge)?;
+    }
+
+    let comparisons = (truth.n_nodes * truth.n_nodes - truth.n_nodes) as f64;
+    Ok(MecSid {
+        min: (min as f64 / comparisons, min),
+        mean: (mean / comparisons, mean),
+        max: (max as f64 / comparisons, max),
+        n_dags,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            consistent_extension, cpdag_of, shd, sid, sid_with_undirected, SIDError,
+            UndirectedEdges,
+        },
+        PDAG,
+    };
+
+    use super::{chain_components, mec_dags, min_shd_over_mec, sid_over_mec, sid_over_mec_limited};
+
+    #[test]
+    fn finds_chain_components() {
+        // 0 -- 1 -- 2 -> 3, 4 -- 5
+        let pdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2, 0, 0, 0, 0],
+            vec![0, 0, 2, 0, 0, 0],
+            vec![0, 0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 2],
+            vec![0, 0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(chain_components(&pdag), vec![vec![0, 1, 2], vec![4, 5]]);
+    }
+
+    #[test]
+    fn rejects_non_chordal_components() {
+        // the undirected 4-cycle 0 -- 1 -- 2 -- 3 -- 0 has no orientation without a v-structure
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2, 0, 2],
+            vec![0, 0, 2, 0],
+            vec![0, 0, 0, 2],
+            vec![0, 0, 0, 0],
+        ]);
+        let truth = PDAG::from_dense_row_to_col(vec![vec![0; 4]; 4]);
+        assert_eq!(min_shd_over_mec(&truth, &guess), None);
+
+        // nor does the undirected 30-cycle, which is oriented greedily
+        let n = 30;
+        let guess = PDAG::try_from_edges(n, (0..n).map(|i| (i, (i + 1) % n, 2))).unwrap();
+        let truth = PDAG::try_from_edges(n, []).unwrap();
+        assert_eq!(min_shd_over_mec(&truth, &guess), None);
+    }
+
+    #[test]
+    fn property_closest_member_is_in_the_class() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_dag(0.3, n, &mut rng);
+            let guess = cpdag_of(&PDAG::random_dag(0.3, n, &mut rng));
+            let closest = min_shd_over_mec(&truth, &guess).unwrap();
+            assert!(closest.exact);
+            assert_eq!(cpdag_of(&closest.dag), guess);
+            assert_eq!(
+                (closest.normalized, closest.mistakes),
+                shd(&truth, &closest.dag)
+            );
+            // at least as close as any other member of the class
+            let member = consistent_extension(&guess).unwrap();
+            assert!(closest.mistakes <= shd(&truth, &member).1);
+
+            // a DAG is the closest member of its own class
+            let own = min_shd_over_mec(&truth, &cpdag_of(&truth)).unwrap();
+            assert_eq!(own.mistakes, 0);
+            assert_eq!(own.dag, truth);
+        }
+    }
+
+    #[test]
+    fn orients_large_components_greedily() {
+        // the complete DAG 0 -> 1 -> ... -> 21 with all edges i -> j for i < j,
+        // whose class is the complete undirected graph
+        let n = 22;
+        let truth =
+            PDAG::try_from_edges(n, (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j, 1))))
+                .unwrap();
+        let guess =
+            PDAG::try_from_edges(n, (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j, 2))))
+                .unwrap();
+        let closest = min_shd_over_mec(&truth, &guess).unwrap();
+        assert_eq!(closest.mistakes, 0);
+        assert!(closest.exact);
+        assert_eq!(closest.dag, truth);
+    }
+
+    #[test]
+    fn orients_very_large_components() {
+        // the undirected path 0 -- 1 -- ... -- 1999, whose class contains
+        // the truth 0 <- 1 <- ... <- 1000 -> ... -> 1999
+        let n = 2000;
+        let guess = PDAG::try_from_edges(n, (1..n).map(|i| (i - 1, i, 2))).unwrap();
+        let truth = PDAG::try_from_edges(
+            n,
+            (1..n).map(|i| {
+                if i <= n / 2 {
+                    (i, i - 1, 1)
+                } else {
+                    (i - 1, i, 1)
+                }
+            }),
+        )
+        .unwrap();
+        let closest = min_shd_over_mec(&truth, &guess).unwrap();
+        assert_eq!(closest.mistakes, 0);
+        assert!(closest.exact);
+        assert_eq!(closest.dag, truth);
+    }
+
+    #[test]
+    fn property_enumerates_the_class() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..12 {
+            let truth = PDAG::random_dag(0.3, n, &mut rng);
+            let dag = PDAG::random_dag(0.5, n, &mut rng);
+            let cpdag = cpdag_of(&dag);
+            let dags = mec_dags(&cpdag).unwrap();
+            assert!(dags.contains(&dag));
+            for (i, member) in dags.iter().enumerate() {
+                assert_eq!(cpdag_of(member), cpdag);
+                assert!(!dags[..i].contains(member));
+            }
+
+            let over_mec = sid_over_mec(&truth, &cpdag).unwrap();
+            let sids = Vec::from_iter(dags.iter().map(|dag| sid(&truth, dag).unwrap()));
+            assert_eq!(over_mec.n_dags, dags.len() as u128);
+            assert_eq!(over_mec.min, *sids.iter().min_by_key(|s| s.1).unwrap());
+            assert_eq!(over_mec.max, *sids.iter().max_by_key(|s| s.1).unwrap());
+            let mean = sids.iter().map(|s| s.1).sum::<usize>() as f64 / sids.len() as f64;
+            assert!((over_mec.mean.1 - mean).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn sid_over_mec_of_testgraphs_is_within_bounds() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+        let load =
+            |name: String| crate::test::load_pdag_from_mtx(testgraphs.join(name).to_str().unwrap());
+
+        for graph_id in 20..=29 {
+            let truth = load(format!("100-node-DAG-{graph_id}.mtx"));
+            let cpdag = load(format!("100-node-CPDAG-{}.mtx", 20 + (graph_id + 1) % 10));
+            let over_mec = sid_over_mec(&truth, &cpdag).unwrap();
+            let bounds = sid_with_undirected(&truth, &cpdag, UndirectedEdges::Bounds).unwrap();
+            let member = sid(&truth, &consistent_extension(&cpdag).unwrap()).unwrap();
+            assert!(bounds.lower.1 <= over_mec.min.1 && over_mec.max.1 <= bounds.upper.1);
+            assert!(over_mec.min.1 <= member.1 && member.1 <= over_mec.max.1);
+            assert!(over_mec.min.1 as f64 <= over_mec.mean.1);
+            assert!(over_mec.mean.1 <= over_mec.max.1 as f64);
+        }
+    }
+
+    #[test]
+    fn sid_over_mec_rejects_too_large_components() {
+        // the complete undirected graph on k nodes has k! orientations, one per order of its nodes
+        let clique = |k: usize| {
+            PDAG::try_from_edges(k, (0..k).flat_map(|i| (i + 1..k).map(move |j| (i, j, 2))))
+                .unwrap()
+        };
+        let truth = |k: usize| PDAG::try_from_edges(k, []).unwrap();
+        let over_mec = sid_over_mec(&truth(6), &clique(6)).unwrap();
+        assert_eq!(over_mec.n_dags, 720);
+        assert_eq!(
+            sid_over_mec_limited(&truth(6), &clique(6), 720).unwrap(),
+            over_mec
+        );
+        assert!(matches!(
+            sid_over_mec_limited(&truth(6), &clique(6), 719),
+            Err(SIDError::GuessClassTooLarge)
+        ));
+
+        // 50 disjoint triangles have 6^50 > 2^128 orientations in total
+        let triangles = PDAG::try_from_edges(
+            150,
+            (0..50).flat_map(|t| {
+                [
+                    (3 * t, 3 * t + 1, 2),
+                    (3 * t, 3 * t + 2, 2),
+                    (3 * t + 1, 3 * t + 2, 2),
+                ]
+            }),
+        )
+        .unwrap();
+        assert!(matches!(
+            sid_over_mec(&truth(150), &triangles),
+            Err(SIDError::GuessClassTooLarge)
+        ));
+    }
+
+    #[test]
+    fn sid_over_mec_rejects_non_chordal_components() {
+        // the undirected 4-cycle 0 -- 1 -- 2 -- 3 -- 0 has no orientation without a v-structure
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2, 0, 2],
+            vec![0, 0, 2, 0],
+            vec![0, 0, 0, 2],
+            vec![0, 0, 0, 0],
+        ]);
+        let truth = PDAG::from_dense_row_to_col(vec![vec![0; 4]; 4]);
+        assert_eq!(mec_dags(&guess), None);
+        assert!(matches!(
+            sid_over_mec(&truth, &guess),
+            Err(SIDError::GuessNotExtendable)
+        ));
+    }
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Reports which `(treatment, effect)` pairs the adjustment identification distances count as mistakes
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput},
+    selected_pairs::{MistakeSink, Verdict},
+};
+
+/// An adjustment identification distance alongside the matrix of its mistakes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetailedDistance {
+    /// The number of mistakes divided by the number of `(treatment, effect)` pairs, in \[0,1].
+    pub normalized: f64,
+    /// The number of mistakes.
+    pub mistakes: usize,
+    /// The n×n mistake matrix, whose entry in row `t` and column `y` is `true`
+    /// if the pair of the treatment `t` and the effect `y` is a mistake;
+    /// the diagonal is `false`.
+    pub matrix: Vec<Vec<bool>>,
+}
+
+impl DetailedDistance {
+    /// The tuple of (normalized error (in \[0,1]), total number of errors)
+    /// that the distance without the mistake matrix returns.
+    pub fn distance(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+
+    /// The `(treatment, effect)` pairs that are mistakes, sorted.
+    pub fn mistake_pairs(&self) -> Vec<(usize, usize)> {
+        Vec::from_iter(self.matrix.iter().enumerate().flat_map(|(t, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, mistake)| **mistake)
+                .map(move |(y, _)| (t, y))
+        }))
+    }
+}
+
+/// Marks the mistakes of a single treatment in its row of the mistake matrix.
+struct MistakeRow(Vec<bool>);
+
+impl MistakeSink for MistakeRow {
+    fn record(&mut self, _: &[usize], effects: &[usize], verdict: Verdict) {
+        if verdict != Verdict::Correct {
+            for y in effects {
+                self.0[*y] = true;
+            }
+        }
+    }
+}
+
+/// The distance alongside the n×n matrix that marks which `(treatment, effect)` pairs are mistakes,
+/// searching from every treatment separately, even if it is a twin of another,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid, ancestor_aid_with, Detailed}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let detailed = ancestor_aid_with(&truth, &guess, Detailed);
+/// assert_eq!(detailed.distance(), ancestor_aid(&truth, &guess));
+/// assert_eq!(detailed.mistake_pairs(), vec![(0, 2), (1, 2), (2, 1)]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Detailed;
+
+impl AidOutput for Detailed {
+    type Output = DetailedDistance;
+
+    fn compute(self, aid: &Aid) -> DetailedDistance {
+        // twins make the same mistakes with different nodes, so each needs its own row
+        let n = aid.guess().n_nodes;
+        let matrix = Vec::from_iter(
+            aid.each_treatment(|_| MistakeRow(vec![false; n]))
+                .into_iter()
+                .map(|row| row.0),
+        );
+        let mistakes = matrix.iter().flatten().filter(|mistake| **mistake).count();
+        DetailedDistance {
+            normalized: mistakes as f64 / aid.n_pairs() as f64,
+            mistakes,
+            matrix,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            Detailed, SelectedPairs,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn property_mistake_matrix_agrees_with_distances() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let detailed = [
+                ancestor_aid_with(&truth, &guess, Detailed),
+                oset_aid_with(&truth, &guess, Detailed),
+                parent_aid_with(&truth, &guess, Detailed),
+            ];
+            let distances = [
+                ancestor_aid(&truth, &guess),
+                oset_aid(&truth, &guess),
+                parent_aid(&truth, &guess),
+            ];
+            for (detailed, distance) in detailed.iter().zip(distances) {
+                assert_eq!(detailed.distance(), distance);
+                assert_eq!(detailed.mistake_pairs().len(), distance.1);
+                assert!((0..n).all(|v| !detailed.matrix[v][v]));
+            }
+
+            // each entry is the mistake of the pair on its own
+            let t = n - 1;
+            for y in 0..t {
+                let selected = [
+                    ancestor_aid_with(&truth, &guess, SelectedPairs(&[(t, y)])).unwrap(),
+                    oset_aid_with(&truth, &guess, SelectedPairs(&[(t, y)])).unwrap(),
+                    parent_aid_with(&truth, &guess, SelectedPairs(&[(t, y)])).unwrap(),
+                ];
+                for (detailed, selected) in detailed.iter().zip(selected) {
+                    assert_eq!(detailed.matrix[t][y], selected.mistakes == 1);
+                }
+            }
+        }
+    }
+}
@@ -1,42 +1,139 @@
 // SPDX-License-Identifier: MPL-2.0
 //! Implements functions that take graphs, such as SHD, generalized search, ...
+//!
+//! Sets of nodes are returned as `Vec<usize>` sorted in ascending order, so that results are deterministic;
+//! hash sets are only used internally.
 
+mod adjustment_sets;
+mod aid;
+mod amenability;
 mod ancestor_aid;
+mod approximate;
+mod barycenter;
+mod bounds;
+mod breakdown;
+mod calibration;
+mod ci_statements;
+mod clustering;
+mod components;
+mod conditional_aid;
+mod confusion;
+mod cpdag;
+mod distance;
+mod ensemble;
+mod extension;
 mod gensearch;
 mod gensearch_wrappers;
+mod graph_edit;
+mod influence;
+mod known_edges;
+mod mec;
+mod mistake_matrix;
+mod normalization;
 mod oset_aid;
+mod pair_weights;
 mod parent_aid;
+mod partial_credit;
+mod per_treatment;
+mod permuted_shd;
+mod possible_descendants;
+mod quick_reject;
 mod reachability;
+mod repair_path;
+mod robustness;
+mod selected_pairs;
+mod separation;
 mod shd;
 mod sid;
+mod specification;
+mod tiers;
+mod trajectory;
+mod truth_sets;
+mod twins;
+mod validated;
+mod visualization;
+mod vstructures;
+mod witnesses;
 
 pub(crate) mod ruletables;
+pub(crate) mod run_stats;
 
-pub use ancestor_aid::ancestor_aid;
-pub use oset_aid::oset_aid;
-pub use parent_aid::parent_aid;
-pub use shd::shd;
-pub use sid::sid;
+pub use adjustment_sets::{
+    adjustment_set_candidates, compare_adjustment_sets, not_validly_adjusted_for,
+    AdjustmentSetCandidates, AdjustmentSetComparison,
+};
+pub use aid::{Aid, AidOutput, Plain};
+pub use amenability::{
+    amenability_disagreement, amenability_matrix, identifiability_report, IdentifiabilityReport,
+};
+pub use ancestor_aid::{ancestor_aid, ancestor_aid_with};
+pub use approximate::{Approximate, ApproximateDistance, TreatmentSampling};
+pub use barycenter::{shd_barycenter, shd_medoid};
+pub use bounds::{parent_aid_bounds, DistanceBounds};
+pub use breakdown::{DistanceBreakdown, WithBreakdown};
+pub use calibration::{expected_random_shd, null_distribution, NullDistribution, RandomGraphs};
+pub use ci_statements::{
+    ci_statement_distance, implied_independencies, markov_property_test, CiStatement,
+    CiStatementDistance, MarkovPropertyTest,
+};
+pub use clustering::{cluster_graphs, Clustering, Linkage};
+pub use components::{distance_by_components, ComponentDistance};
+pub use conditional_aid::{conditional_adjustment_set_given_possible_descendants, conditional_aid};
+pub use confusion::{confusion, Confusion, ConfusionCounts};
+pub use cpdag::cpdag_of;
+pub use distance::{AidDistance, Distance};
+pub use ensemble::{
+    consensus_graph, cross_consistency, distances_to_consensus, edge_frequencies,
+    identification_stability, CrossConsistency, DispersionSummary, EdgeFrequency, EnsembleError,
+};
+pub use extension::consistent_extension;
+pub use graph_edit::{graph_edit_distance, GraphEditDistance};
+pub use influence::{edge_influence, EdgeInfluence};
+pub use known_edges::{shd_with_known_edges, KnownEdges};
+pub use mec::{mec_dags, min_shd_over_mec, sid_over_mec, MecShd, MecSid};
+pub use mistake_matrix::{Detailed, DetailedDistance};
+pub use normalization::{Normalization, NormalizedDistance};
+pub use oset_aid::{oset_aid, oset_aid_selected_treatment_sets, oset_aid_with};
+pub use pair_weights::PairWeights;
+pub use parent_aid::{parent_aid, parent_aid_with};
+pub use partial_credit::PartialCredit;
+pub use per_treatment::PerTreatment;
+pub use permuted_shd::{shd_min_over_permutations, PermutedShd};
+pub use repair_path::{repair_path, RepairPath, RepairPathError};
+pub use robustness::{node_dropout_curve, DropoutPoint};
+pub use run_stats::{RunStats, WithStats};
+pub use selected_pairs::{
+    pairs_from_masks, MistakeSink, PairsError, PairsNormalization, SelectedEffectSets,
+    SelectedPairs, SelectedPairsDistance, Verdict,
+};
+pub use shd::{shd, shd_detailed, shd_markov_equivalent, weighted_shd, DetailedShd, WeightedShd};
+pub use sid::{
+    sid, sid_selected_pairs, sid_with_undirected, SIDError, SidWithUndirected, UndirectedEdges,
+};
+pub use specification::{
+    specification_violations, EdgeSpecification, SpecificationError, SpecificationViolations,
+    Violation, ViolationWeights,
+};
+pub use tiers::{tiered_distance, TieredDistance, TieredTruth};
+pub use trajectory::{trajectory_aid, EdgeOperation, TrajectoryError};
+pub use truth_sets::{TruthSets, WithTruthSets};
+pub use validated::{ValidatedPair, ValidationError};
+pub use visualization::{to_cytoscape_json, Layer};
+pub use vstructures::{v_structures, vstructure_distance, VStructureDistance};
+pub use witnesses::{WithWitnesses, Witness, WitnessCollector, WitnessedDistance};
 
 pub(crate) use gensearch::gensearch;
 pub(crate) use gensearch_wrappers::get_parents;
 pub(crate) use gensearch_wrappers::get_proper_ancestors;
+pub(crate) use oset_aid::optimal_adjustment_set_given_descendants;
+pub(crate) use possible_descendants::get_possible_descendants;
 pub(crate) use reachability::{
-    get_d_pd_nam, get_invalidly_un_blocked, get_nam, get_pd_nam, get_pd_nam_nva,
+    get_d_pd_nam, get_invalidly_un_blocked, get_nam, get_nam_nva, get_pd_nam, get_pd_nam_nva,
 };
 
-#[cfg(test)]
-mod possible_descendants;
-
 #[cfg(test)]
 pub(crate) use gensearch_wrappers::get_ancestors;
 #[cfg(test)]
 pub(crate) use gensearch_wrappers::get_children;
 #[cfg(test)]
 pub(crate) use gensearch_wrappers::get_descendants;
-#[cfg(test)]
-pub(crate) use oset_aid::optimal_adjustment_set;
-#[cfg(test)]
-pub(crate) use possible_descendants::get_possible_descendants;
-#[cfg(test)]
-pub(crate) use reachability::get_nam_nva;
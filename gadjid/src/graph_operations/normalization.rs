@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Normalizes the adjustment identification distances by other sets of `(treatment, effect)` pairs
+//! than all pairs of distinct nodes, so that amenability failures can be told apart from adjustment failures
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput, Plain},
+    amenability_matrix,
+    mistake_matrix::Detailed,
+};
+
+/// Which `(treatment, effect)` pairs an adjustment identification distance is graded and normalized by
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings, for example only by the pairs that are
+/// identifiable in the truth graph, so that amenability failures of a guess CPDAG can be told apart
+/// from adjustment failures.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, Normalization}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let all = ancestor_aid_with(&truth, &guess, Normalization::AllPairs);
+/// assert_eq!((all.mistakes, all.comparisons), (3, 6));
+/// // the guess is not amenable relative to (0, 1), (0, 2), and (1, 0)
+/// let graded = ancestor_aid_with(&truth, &guess, Normalization::GradedPairs);
+/// assert_eq!((graded.mistakes, graded.comparisons), (0, 3));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Normalization {
+    /// all n(n-1) pairs of distinct nodes, like the distances without a normalization mode
+    AllPairs,
+    /// the pairs for which the true graph is amenable, that is, whose effect is identifiable by adjustment;
+    /// mistakes on the other pairs, where the guess claims an effect to be identifiable that is not, are not counted
+    AmenablePairsInTruth,
+    /// the pairs for which both graphs are amenable, so that the adjustment set claimed by the guess is graded;
+    /// only adjustment failures are counted, not amenability failures
+    GradedPairs,
+}
+
+/// An adjustment identification distance normalized by the pairs of a [`Normalization`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NormalizedDistance {
+    /// The number of mistakes divided by the number of comparisons, in \[0,1],
+    /// or 0 if no pair is compared.
+    pub normalized: f64,
+    /// The number of mistakes among the compared pairs.
+    pub mistakes: usize,
+    /// The number of compared pairs.
+    pub comparisons: usize,
+}
+
+impl NormalizedDistance {
+    /// The tuple of (normalized error (in \[0,1]), total number of errors).
+    pub fn distance(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+
+    fn new(mistakes: usize, comparisons: usize) -> Self {
+        let normalized = match comparisons {
+            0 => 0.0,
+            _ => mistakes as f64 / comparisons as f64,
+        };
+        NormalizedDistance {
+            normalized,
+            mistakes,
+            comparisons,
+        }
+    }
+}
+
+/// Grades and normalizes the distance by the pairs of the [`Normalization`],
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+impl AidOutput for Normalization {
+    type Output = NormalizedDistance;
+
+    fn compute(self, aid: &Aid) -> NormalizedDistance {
+        let graded = match self {
+            Normalization::AllPairs => {
+                return NormalizedDistance::new(Plain.compute(aid).1, aid.n_pairs())
+            }
+            Normalization::AmenablePairsInTruth => amenability_matrix(aid.truth()),
+            Normalization::GradedPairs => {
+                let mut graded = amenability_matrix(aid.truth());
+                for (graded_row, guess_row) in
+                    graded.iter_mut().zip(amenability_matrix(aid.guess()))
+                {
+                    for (graded, amenable_in_guess) in graded_row.iter_mut().zip(guess_row) {
+                        *graded &= amenable_in_guess;
+                    }
+                }
+                graded
+            }
+        };
+
+        let detailed = Detailed.compute(aid);
+        let (mut mistakes, mut comparisons) = (0, 0);
+        for (graded_row, mistake_row) in graded.iter().zip(&detailed.matrix) {
+            for (graded, mistake) in graded_row.iter().zip(mistake_row) {
+                if *graded {
+                    comparisons += 1;
+                    mistakes += *mistake as usize;
+                }
+            }
+        }
+        NormalizedDistance::new(mistakes, comparisons)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+        },
+        PDAG,
+    };
+
+    use super::Normalization;
+
+    #[test]
+    fn property_normalizations_grade_fewer_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            for (with_normalization, distance) in [
+                (
+                    ancestor_aid_with::<Normalization> as fn(_, _, _) -> _,
+                    ancestor_aid as fn(_, _) -> _,
+                ),
+                (oset_aid_with, oset_aid),
+                (parent_aid_with, parent_aid),
+            ] {
+                let all = with_normalization(&truth, &guess, Normalization::AllPairs);
+                assert_eq!(all.distance(), distance(&truth, &guess));
+                assert_eq!(all.comparisons, n * n - n);
+
+                let amenable =
+                    with_normalization(&truth, &guess, Normalization::AmenablePairsInTruth);
+                let graded = with_normalization(&truth, &guess, Normalization::GradedPairs);
+                assert!(graded.comparisons <= amenable.comparisons);
+                assert!(amenable.comparisons <= all.comparisons);
+                assert!(graded.mistakes <= amenable.mistakes);
+                assert!(amenable.mistakes <= all.mistakes);
+            }
+        }
+    }
+}
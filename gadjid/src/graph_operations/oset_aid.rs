@@ -2,18 +2,23 @@
 //! Implements the Optimal Adjustment Intervention Distance (Oset-AID) algorithm
 
 use rayon::prelude::*;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     graph_operations::{
+        aid::{Aid, AidOutput, Plain},
         get_d_pd_nam, get_invalidly_un_blocked, get_parents, get_pd_nam, get_proper_ancestors,
+        selected_pairs::{
+            group_by_treatment_set, Claims, Effects, MistakeSink, PairsError, SelectedPairsDistance,
+        },
+        twins::is_isolated,
     },
     PDAG,
 };
 
 /// This oset function takes in a precomputed t_descendants set.
 /// Returns the optimal adjustment set of the provided treatments.
-pub fn optimal_adjustment_set_given_descendants(
+pub(crate) fn optimal_adjustment_set_given_descendants(
     dag: &PDAG,
     treatments: &[usize],
     responses: &[usize],
@@ -26,86 +31,318 @@ pub fn optimal_adjustment_set_given_descendants(
     FxHashSet::from_iter(causal_nodes_parents.difference(t_descendants).copied())
 }
 
+/// Up to this many nodes, the ancestors of every node of the guess graph are searched for once,
+/// which takes n²/8 bytes, instead of once for each (treatment, effect) pair.
+const MAX_NODES_SHARED_ANCESTORS: usize = 8_192;
+
+/// The ancestors of each node of a graph, including the node itself, as the rows of a bit matrix.
+pub(crate) struct Ancestors {
+    words_per_row: usize,
+    bits: Vec<u64>,
+}
+
+impl Ancestors {
+    /// Searches for the ancestors of each node of the `dag`, once per node,
+    /// or returns None if the graph has more than [`MAX_NODES_SHARED_ANCESTORS`] nodes.
+    pub(crate) fn of(dag: &PDAG) -> Option<Ancestors> {
+        if dag.n_nodes > MAX_NODES_SHARED_ANCESTORS {
+            return None;
+        }
+        let words_per_row = dag.n_nodes.div_ceil(64);
+        let mut bits = vec![0; dag.n_nodes * words_per_row];
+        bits.par_chunks_mut(words_per_row.max(1))
+            .enumerate()
+            .for_each(|(y, row)| {
+                for v in get_proper_ancestors(dag, [].iter(), [y].iter()) {
+                    row[v / 64] |= 1 << (v % 64);
+                }
+            });
+        Some(Ancestors {
+            words_per_row,
+            bits,
+        })
+    }
+
+    fn contains(&self, y: usize, v: usize) -> bool {
+        self.bits[y * self.words_per_row + v / 64] & (1 << (v % 64)) != 0
+    }
+
+    /// Returns the optimal adjustment set of the single `treatment` for the effect `y`, like
+    /// [`optimal_adjustment_set_given_descendants`] but without a search: a descendant of
+    /// the treatment other than itself cannot reach `y` through it without a cycle,
+    /// so its causal nodes are its descendants among the ancestors of `y`.
+    fn optimal_adjustment_set(
+        &self,
+        dag: &PDAG,
+        treatment: usize,
+        y: usize,
+        t_descendants: &FxHashSet<usize>,
+    ) -> FxHashSet<usize> {
+        let causal_nodes = t_descendants
+            .iter()
+            .filter(|v| **v != treatment && self.contains(y, **v));
+        let causal_nodes_parents = get_parents(dag, causal_nodes);
+        FxHashSet::from_iter(causal_nodes_parents.difference(t_descendants).copied())
+    }
+}
+
 /// Computes the oset adjustment intervention distance
 /// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG
 /// (a PDAG is used for internal representation, but every PDAG is assumed either a DAG or a CPDAG
 ///  currently distances between general PDAGs are not implemented)
 /// Returns a tuple of (normalized error (in \[0,1]), total number of errors)
+///
+/// ```
+/// use gadjid::{graph_operations::oset_aid, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // like for the ancestor AID, and its optimal adjustment set {2} for the effect of 0 on 1
+/// // contains a descendant of 0 in the true graph
+/// assert_eq!(oset_aid(&truth, &guess), (2.0 / 3.0, 4));
+/// ```
 pub fn oset_aid(truth: &PDAG, guess: &PDAG) -> (f64, usize) {
+    oset_aid_with(truth, guess, Plain)
+}
+
+/// Computes the oset adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG
+/// as the given [`AidOutput`]: [`Plain`] for the distance of [`oset_aid`],
+/// or an output that grades or reports the verdicts of the same search from each treatment differently,
+/// such as [`WithBreakdown`](super::WithBreakdown), [`Detailed`](super::Detailed),
+/// [`PerTreatment`](super::PerTreatment), [`WithStats`](super::WithStats),
+/// [`WithTruthSets`](super::WithTruthSets), [`Normalization`](super::Normalization),
+/// [`PartialCredit`](super::PartialCredit), [`PairWeights`](super::PairWeights),
+/// [`KnownEdges`](super::KnownEdges), [`SelectedPairs`](super::SelectedPairs),
+/// [`SelectedEffectSets`](super::SelectedEffectSets), or [`Approximate`](super::Approximate).
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{oset_aid, oset_aid_with, Plain, SelectedPairs, WithBreakdown},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let distance = oset_aid_with(&truth, &guess, Plain);
+/// assert_eq!(distance, oset_aid(&truth, &guess));
+/// let breakdown = oset_aid_with(&truth, &guess, WithBreakdown);
+/// assert_eq!(breakdown.mistakes(), distance.1);
+/// let selected = oset_aid_with(&truth, &guess, SelectedPairs(&[(2, 1)])).unwrap();
+/// assert_eq!(selected.distance(), (1.0, 1));
+/// ```
+pub fn oset_aid_with<O: AidOutput>(truth: &PDAG, guess: &PDAG, output: O) -> O::Output {
+    output.compute(&Aid::oset(truth, guess))
+}
+
+/// Computes the oset adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG,
+/// only counting the mistakes for the selected `(treatments, effect)` pairs,
+/// each with a set of treatments that are intervened on jointly.
+///
+/// For a set of treatments, amenability no longer implies that a valid adjustment set exists.
+/// If one exists, so does a valid optimal adjustment set; so the guess graph claims that the
+/// effect can be identified by adjustment if its optimal adjustment set is valid in the guess graph,
+/// and the claim is checked against whether the optimal adjustment set of the true graph is valid
+/// in the true graph. Parent and ancestor adjustment sets may be invalid for sets of treatments
+/// even if a valid adjustment set exists, so there are no such variants of the parent and ancestor AID.
+///
+/// Returns the number of mistakes alongside the number of distinct pairs it is normalized by,
+/// counting repeated pairs once (irrespective of the order of the treatments),
+/// or an error if no pair is selected, a set of treatments is empty, a node is out of bounds,
+/// or a set of treatments contains its effect.
+///
+/// ```
+/// use gadjid::{graph_operations::oset_aid_selected_treatment_sets, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let selected = oset_aid_selected_treatment_sets(&truth, &guess, &[(vec![0, 1], 2)]).unwrap();
+/// assert_eq!(selected.distance(), (1.0, 1));
+/// ```
+pub fn oset_aid_selected_treatment_sets(
+    truth: &PDAG,
+    guess: &PDAG,
+    pairs: &[(Vec<usize>, usize)],
+) -> Result<SelectedPairsDistance, PairsError> {
     assert!(
         guess.n_nodes == truth.n_nodes,
         "both graphs must contain the same number of nodes"
     );
-    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+    let (effects_of_treatments, n_pairs) =
+        group_by_treatment_set(guess.n_nodes, pairs, |v| is_isolated(truth, guess, v))?;
 
     crate::rayon::build_global();
 
-    let verifier_mistakes_found = (0..guess.n_nodes)
-        .into_par_iter()
-        .map(|treatment| {
-            // precomputed once for each T because we use it for the optimal adjustment set.
-            let (t_desc_in_guess, claim_possible_effect, nam_in_guess) =
-                get_d_pd_nam(guess, &[treatment]);
+    let verifier_mistakes_found = effects_of_treatments
+        .par_iter()
+        .map(|(treatments, effects)| {
+            let mut mistakes = 0;
+            let effects = Effects::Selected(effects);
+            record_oset_aid_verdicts(truth, guess, None, treatments, effects, &mut mistakes);
+            mistakes
+        })
+        .sum();
+
+    Ok(SelectedPairsDistance::new(
+        verifier_mistakes_found,
+        n_pairs,
+        pairs.len(),
+        truth.n_nodes,
+    ))
+}
 
-            let (t_poss_desc_in_truth, nam_in_true) = get_pd_nam(truth, &[treatment]);
+/// Records the verdict on the optimal adjustment for the `treatments`
+/// on each of the given `effects` into the `sink`, looking up the optimal adjustment sets
+/// of a single treatment in the `guess_ancestors`, if given.
+pub(crate) fn record_oset_aid_verdicts(
+    truth: &PDAG,
+    guess: &PDAG,
+    guess_ancestors: Option<&Ancestors>,
+    treatments: &[usize],
+    effects: Effects,
+    sink: &mut impl MistakeSink,
+) {
+    // precomputed once for each T because we use it for the optimal adjustment set.
+    let (t_desc_in_guess, claim_possible_effect, nam_in_guess) = get_d_pd_nam(guess, treatments);
 
-            let mut mistakes = 0;
-            for y in 0..guess.n_nodes {
-                if y == treatment {
-                    continue; // this case is always correct
+    // for a single treatment, amenability implies that a valid adjustment set exists;
+    // for several, it exists if the optimal adjustment set is valid, which needs the descendants
+    let (t_desc_in_truth, t_poss_desc_in_truth, nam_in_true) = match treatments {
+        [_] => {
+            let (t_poss_desc_in_truth, nam_in_true) =
+                get_pd_nam(truth, treatments, effects.of_interest());
+            (None, t_poss_desc_in_truth, nam_in_true)
+        }
+        _ => {
+            let (t_desc_in_truth, t_poss_desc_in_truth, nam_in_true) =
+                get_d_pd_nam(truth, treatments);
+            (Some(t_desc_in_truth), t_poss_desc_in_truth, nam_in_true)
+        }
+    };
+
+    let is_invalid_in = |graph: &PDAG, adjustment_set: &FxHashSet<usize>, ys: &[usize]| {
+        let ys = FxHashSet::from_iter(ys.iter().copied());
+        !get_invalidly_un_blocked(graph, treatments, adjustment_set, Some(&ys)).is_disjoint(&ys)
+    };
+    // if the guess graph claims that no valid adjustment set exists for the effects,
+    // we count a mistake if there is one in the truth graph
+    let is_valid_in_truth_only = |t_desc_in_truth: &FxHashSet<usize>, ys: &[usize]| {
+        let o_set_in_truth =
+            optimal_adjustment_set_given_descendants(truth, treatments, ys, t_desc_in_truth);
+        !is_invalid_in(truth, &o_set_in_truth, ys)
+    };
+
+    // the single effects amenable in both graphs are the ones whose adjustment is graded;
+    // many of them share their optimal adjustment set in the guess graph,
+    // so each distinct set is verified once for all of its effects
+    let single_effects: Vec<usize> = match &effects {
+        Effects::All(nodes) => Vec::from_iter(nodes.iter().copied()),
+        Effects::Selected(selected) => Vec::from_iter(selected.iter().copied()),
+        Effects::Sets { .. } => Vec::new(),
+    };
+    let mut effects_by_o_set =
+        FxHashMap::<Vec<usize>, (FxHashSet<usize>, FxHashSet<usize>)>::default();
+    for y in single_effects.into_iter().filter(|y| {
+        !treatments.contains(y)
+            && claim_possible_effect.contains(y)
+            && !nam_in_guess.contains(y)
+            && !nam_in_true.contains(y)
+    }) {
+        let o_set = match (guess_ancestors, treatments) {
+            (Some(ancestors), [treatment]) => {
+                ancestors.optimal_adjustment_set(guess, *treatment, y, &t_desc_in_guess)
+            }
+            _ => {
+                optimal_adjustment_set_given_descendants(guess, treatments, &[y], &t_desc_in_guess)
+            }
+        };
+        let mut key = Vec::from_iter(o_set.iter().copied());
+        key.sort_unstable();
+        effects_by_o_set
+            .entry(key)
+            .or_insert_with(|| (o_set, FxHashSet::default()))
+            .1
+            .insert(y);
+    }
+    let mut wrongly_adjusted = FxHashSet::default();
+    for (o_set, mut ys) in effects_by_o_set.into_values() {
+        if let Some(t_desc_in_truth) = &t_desc_in_truth {
+            let invalid_in_guess = get_invalidly_un_blocked(guess, treatments, &o_set, Some(&ys));
+            ys.retain(|y| {
+                if !invalid_in_guess.contains(y) {
+                    return true;
                 }
-                // if y is not claimed to be effect of t based on the guess graph
-                if !claim_possible_effect.contains(&y) {
-                    // but possibly a descendant of t in the truth graph.
-                    if t_poss_desc_in_truth.contains(&y) {
-                        // the causal order might be wrong, so
-                        // we count a mistake
-                        mistakes += 1;
-                    }
-                } else {
-                    let y_nam_in_guess = nam_in_guess.contains(&y);
-                    let y_nam_in_true = nam_in_true.contains(&y);
-
-                    // if they disagree on amenability:
-                    if y_nam_in_guess != y_nam_in_true {
-                        mistakes += 1;
-                    }
-                    // if they agree on amenability and y is amenable, we need to find the adjustment set
-                    else if !y_nam_in_guess {
-                        // this oset function uses the precomputed t_desc_in_guess
-                        let o_set_adjustment = optimal_adjustment_set_given_descendants(
-                            guess,
-                            &[treatment],
-                            &[y],
-                            &t_desc_in_guess,
-                        );
-
-                        // if the o-set from the guess graph is not valid in the truth graph (by blocking too much or too little)
-                        if get_invalidly_un_blocked(
-                            truth,
-                            &[treatment],
-                            &o_set_adjustment,
-                            Some(&FxHashSet::from_iter([y])),
-                        )
-                        .contains(&y)
-                        {
-                            // we count a mistake
-                            mistakes += 1;
-                        }
-                    }
+                if is_valid_in_truth_only(t_desc_in_truth, &[*y]) {
+                    wrongly_adjusted.insert(*y);
                 }
+                false
+            });
+        }
+        // if the o-set from the guess graph is not valid in the truth graph (by blocking too much or too little)
+        // we count a mistake
+        let invalid_in_truth = get_invalidly_un_blocked(truth, treatments, &o_set, Some(&ys));
+        wrongly_adjusted.extend(ys.intersection(&invalid_in_truth));
+    }
+
+    // the effects are amenable in both graphs, so we need to find the adjustment set
+    let is_wrongly_adjusted = |ys: &[usize]| {
+        if !matches!(effects, Effects::Sets { .. }) {
+            return ys.iter().any(|y| wrongly_adjusted.contains(y));
+        }
+        // this oset function uses the precomputed t_desc_in_guess
+        let o_set_adjustment =
+            optimal_adjustment_set_given_descendants(guess, treatments, ys, &t_desc_in_guess);
+
+        if let Some(t_desc_in_truth) = &t_desc_in_truth {
+            if is_invalid_in(guess, &o_set_adjustment, ys) {
+                return is_valid_in_truth_only(t_desc_in_truth, ys);
             }
+        }
+        is_invalid_in(truth, &o_set_adjustment, ys)
+    };
 
-            mistakes
-        })
-        .sum();
+    sink.record_truth_sets(treatments, &nam_in_true, &wrongly_adjusted);
 
-    let n = guess.n_nodes;
-    let comparisons = n * n - n;
-    (
-        verifier_mistakes_found as f64 / comparisons as f64,
-        verifier_mistakes_found,
-    )
+    let claims = Claims {
+        is_claimed_effect: |y: usize| claim_possible_effect.contains(&y),
+        poss_desc_in_truth: &t_poss_desc_in_truth,
+        nam_in_guess: &nam_in_guess,
+        nam_in_true: &nam_in_true,
+        is_wrongly_adjusted,
+    };
+
+    effects.record_verdicts(treatments, |ys| claims.verdict(ys), sink);
 }
 
 #[cfg(test)]
@@ -120,12 +357,16 @@ pub fn optimal_adjustment_set(
 
 #[cfg(test)]
 mod test {
+    use crate::graph_operations::{SelectedEffectSets, SelectedPairs};
     use rand::SeedableRng;
     use rustc_hash::FxHashSet;
 
-    use crate::PDAG;
+    use crate::{graph_operations::get_d_pd_nam, PDAG};
 
-    use super::{optimal_adjustment_set, oset_aid};
+    use super::{
+        optimal_adjustment_set, optimal_adjustment_set_given_descendants, oset_aid,
+        oset_aid_selected_treatment_sets, oset_aid_with, Ancestors,
+    };
 
     #[test]
     fn property_equal_dags_zero_distance() {
@@ -174,7 +415,7 @@ mod test {
             vec![0, 0, 0, 1, 0, 0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         assert_eq!(
             FxHashSet::from_iter([7]),
@@ -226,7 +467,7 @@ mod test {
             vec![0, 0, 0, 1, 0, 0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         assert_eq!(
             FxHashSet::from_iter([5]),
@@ -237,4 +478,173 @@ mod test {
             FxHashSet::from_iter(optimal_adjustment_set(&dag, &[5], &[3]))
         );
     }
+
+    #[test]
+    fn property_selected_pairs_agree_with_all_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            assert_eq!(
+                oset_aid(&truth, &guess),
+                oset_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+            // searches restricted to a single effect must count the same mistakes
+            let mistakes_by_pair: usize = all_pairs
+                .iter()
+                .map(|pair| {
+                    oset_aid_with(&truth, &guess, SelectedPairs(&[*pair]))
+                        .unwrap()
+                        .mistakes
+                })
+                .sum();
+            assert_eq!(oset_aid(&truth, &guess).1, mistakes_by_pair);
+        }
+    }
+
+    #[test]
+    fn treatment_sets_check_whether_a_valid_adjustment_set_exists() {
+        // 0 -> 2 -> 1 -> 4 with 3 -> 2 and 3 -> 4, treatments {0, 1}, effect 4:
+        // {3} blocks 1 <- 2 <- 3 -> 4 without opening 0 -> 2 <- 3 -> 4
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 1],
+            vec![0, 1, 0, 0, 0],
+            vec![0, 0, 1, 0, 1],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        // the guess misses node 3's edges, so its optimal adjustment set is empty
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 0, 1],
+            vec![0, 1, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        let pairs = [(vec![0, 1], 4)];
+        let mistakes = |truth, guess| {
+            oset_aid_selected_treatment_sets(truth, guess, &pairs)
+                .unwrap()
+                .mistakes
+        };
+        assert_eq!(mistakes(&truth, &truth), 0);
+        assert_eq!(mistakes(&truth, &guess), 1);
+        assert_eq!(mistakes(&guess, &truth), 0);
+
+        // 0 -> 2 -> 1 -> 3 with 2 -> 3, treatments {0, 1}, effect 3:
+        // 2 needs to be adjusted for but mediates the effect of 0, so no valid adjustment set exists
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![0, 1, 0, 1],
+            vec![0, 0, 0, 0],
+        ]);
+        // without 2 -> 3, the empty set is valid
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 1],
+            vec![0, 1, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+        let pairs = [(vec![0, 1], 3)];
+        let mistakes = |truth, guess| {
+            oset_aid_selected_treatment_sets(truth, guess, &pairs)
+                .unwrap()
+                .mistakes
+        };
+        assert_eq!(mistakes(&truth, &truth), 0);
+        assert_eq!(mistakes(&truth, &guess), 1);
+        assert_eq!(mistakes(&guess, &truth), 1);
+    }
+
+    #[test]
+    fn property_singleton_treatment_sets_agree_with_selected_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            let singletons = Vec::from_iter(all_pairs.iter().map(|(t, y)| (vec![*t], *y)));
+            assert_eq!(
+                oset_aid_with(&truth, &guess, SelectedPairs(&all_pairs)),
+                oset_aid_selected_treatment_sets(&truth, &guess, &singletons)
+            );
+
+            // equal graphs agree on which effects of treatment sets can be identified by adjustment
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            let sets = Vec::from_iter((1..n).map(|y| (Vec::from_iter(0..y), y)));
+            assert_eq!(
+                oset_aid_selected_treatment_sets(&dag, &dag, &sets)
+                    .unwrap()
+                    .mistakes,
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn property_shared_ancestors_give_optimal_adjustment_sets() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+        for n in 2..30 {
+            let pdag = PDAG::random_pdag(0.4, n, &mut rng);
+            let ancestors = Ancestors::of(&pdag).unwrap();
+            for t in 0..n {
+                let (t_descendants, _, _) = get_d_pd_nam(&pdag, &[t]);
+                for y in (0..n).filter(|y| *y != t) {
+                    assert_eq!(
+                        ancestors.optimal_adjustment_set(&pdag, t, y, &t_descendants),
+                        optimal_adjustment_set_given_descendants(&pdag, &[t], &[y], &t_descendants)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn property_shared_o_sets_are_verified_like_single_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(2);
+        for n in 3..25 {
+            let truth = PDAG::random_pdag(0.4, n, &mut rng);
+            let guess = PDAG::random_pdag(0.4, n, &mut rng);
+            let pairs = Vec::from_iter((2..n).map(|y| (vec![0, 1], y)));
+            let mistakes_by_pair: usize = pairs
+                .iter()
+                .map(|pair| {
+                    oset_aid_selected_treatment_sets(&truth, &guess, std::slice::from_ref(pair))
+                        .unwrap()
+                        .mistakes
+                })
+                .sum();
+            assert_eq!(
+                oset_aid_selected_treatment_sets(&truth, &guess, &pairs)
+                    .unwrap()
+                    .mistakes,
+                mistakes_by_pair
+            );
+        }
+    }
+
+    #[test]
+    fn property_singleton_effect_sets_agree_with_selected_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            let singletons = Vec::from_iter(all_pairs.iter().map(|(t, y)| (*t, vec![*y])));
+            assert_eq!(
+                oset_aid_with(&truth, &guess, SelectedPairs(&all_pairs)),
+                oset_aid_with(&truth, &guess, SelectedEffectSets(&singletons))
+            );
+        }
+    }
 }
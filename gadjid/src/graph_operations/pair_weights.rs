@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Weighs the mistakes of the adjustment identification distances by integer weights
+//! of their `(treatment, effect)` pairs, such as to emphasize the effects of interest
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput},
+    selected_pairs::WeightedMistakes,
+};
+
+/// Weighs each mistake by the `weight` of its `(treatment, effect)` pair
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+/// The output is a tuple of (weighted error normalized by the total weight of all pairs (in \[0,1]),
+/// total weight of errors).
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, PairWeights}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the mistake (0, 2) counts twice, out of a total weight of 8
+/// let weight = |treatment: usize, _effect: usize| if treatment == 0 { 2 } else { 1 };
+/// assert_eq!(ancestor_aid_with(&truth, &guess, PairWeights(weight)), (0.5, 4));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PairWeights<W>(pub W);
+
+impl<W: Fn(usize, usize) -> usize + Sync> AidOutput for PairWeights<W> {
+    type Output = (f64, usize);
+
+    fn compute(self, aid: &Aid) -> (f64, usize) {
+        let weight = self.0;
+        let n = aid.guess().n_nodes;
+        let total_weight: usize = (0..n)
+            .flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y)))
+            .map(|(t, y)| weight(t, y))
+            .sum();
+        assert!(total_weight > 0, "the pairs must not all weigh zero");
+
+        // twins may weigh differently
+        let weighted_mistakes: usize = aid
+            .each_treatment(|_| WeightedMistakes {
+                weight: &weight,
+                sum: 0,
+            })
+            .iter()
+            .map(|mistakes| mistakes.sum)
+            .sum();
+        (
+            weighted_mistakes as f64 / total_weight as f64,
+            weighted_mistakes,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            PairWeights, SelectedPairs,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn weighs_mistakes_by_pair() {
+        // truth: 0 -> 1 -> 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        // guess: 0 <- 1 -> 2
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0], //
+            vec![1, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        let weight = |t: usize, y: usize| if (t, y) == (0, 2) { 10 } else { 1 };
+        let mistakes = oset_aid_with(&truth, &guess, SelectedPairs(&[(0, 2)])).unwrap();
+        let (_, unweighted) = oset_aid(&truth, &guess);
+        let (normalized, weighted) = oset_aid_with(&truth, &guess, PairWeights(weight));
+        assert_eq!(weighted, unweighted + 9 * mistakes.mistakes);
+        assert_eq!(normalized, weighted as f64 / 15.0);
+    }
+
+    #[test]
+    fn property_unit_weights_give_aids() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let unit = |_: usize, _: usize| 1;
+            assert_eq!(
+                ancestor_aid_with(&truth, &guess, PairWeights(unit)),
+                ancestor_aid(&truth, &guess)
+            );
+            assert_eq!(
+                oset_aid_with(&truth, &guess, PairWeights(unit)),
+                oset_aid(&truth, &guess)
+            );
+            assert_eq!(
+                parent_aid_with(&truth, &guess, PairWeights(unit)),
+                parent_aid(&truth, &guess)
+            );
+        }
+    }
+}
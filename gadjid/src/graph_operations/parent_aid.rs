@@ -1,11 +1,14 @@
 // SPDX-License-Identifier: MPL-2.0
 //! Implements the Parent Adjustment Intervention Distance (Parent-AID) algorithm
 
-use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 
 use crate::{
-    graph_operations::{get_nam, get_pd_nam_nva},
+    graph_operations::{
+        aid::{Aid, AidOutput, Plain},
+        get_nam, get_pd_nam_nva,
+        selected_pairs::{Claims, Effects, MistakeSink},
+    },
     PDAG,
 };
 
@@ -14,87 +17,122 @@ use crate::{
 /// (a PDAG is used for internal representation, but every PDAG is assumed either a DAG or a CPDAG
 ///  currently distances between general PDAGs are not implemented)
 /// Returns a tuple of (normalized error (in \[0,1]), total number of errors)
-// This function largely overlaps with ancestor_aid in ancestor_aid.rs; differences ---highlighted--- below
+///
+/// ```
+/// use gadjid::{graph_operations::parent_aid, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the guess takes 2 as a parent of 1 rather than as its child, so that adjusting for
+/// // the parents of 1 and of 2 in the guess is not valid in the true graph
+/// assert_eq!(parent_aid(&truth, &guess), (0.5, 3));
+/// ```
 pub fn parent_aid(truth: &PDAG, guess: &PDAG) -> (f64, usize) {
-    assert!(
-        guess.n_nodes == truth.n_nodes,
-        "both graphs must contain the same number of nodes"
-    );
-    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
-
-    crate::rayon::build_global();
-
-    let verifier_mistakes_found = (0..guess.n_nodes)
-        .into_par_iter()
-        .map(|treatment| {
-            // --- this function differs from ancestor_aid.rs only in the imports and from here
-
-            // parent adjustment
-            let adjustment_set = FxHashSet::from_iter(guess.parents_of(treatment).to_vec());
-
-            // in line with the original SID, claim all NonParents may be effects
-            // (this is a larger set than the NonDescendants in ancestor_aid and oset_aid;
-            //  that is, the validity of the adjustment set is also checked
-            //  for the additional non-effect nodes in NonParents\NonDescendants)
-            let claim_possible_effect =
-                FxHashSet::from_iter((0..truth.n_nodes).filter(|v| !adjustment_set.contains(v)));
-            let nam_in_guess = get_nam(guess, &[treatment]);
-            // --- to here
-
-            // now we take a look at the nodes in the true graph for which the adj.set. was not valid.
-            let (t_poss_desc_in_truth, nam_in_true, nva_in_true) =
-                get_pd_nam_nva(truth, &[treatment], &adjustment_set);
-
-            let mut mistakes = 0;
-            for y in 0..truth.n_nodes {
-                if y == treatment {
-                    continue; // this case is always correct
-                }
-                // if y is not claimed to be effect of t based on the guess graph
-                if !claim_possible_effect.contains(&y) {
-                    // but possibly a descendant of t in the truth graph.
-                    if t_poss_desc_in_truth.contains(&y) {
-                        // the ancestral order might be wrong, so
-                        // we count a mistake
-                        mistakes += 1;
-                    }
-                } else {
-                    let y_nam_in_guess = nam_in_guess.contains(&y);
-                    let y_nam_in_true = nam_in_true.contains(&y);
-
-                    #[allow(clippy::if_same_then_else)]
-                    // if they disagree on amenability:
-                    if y_nam_in_guess != y_nam_in_true {
-                        mistakes += 1;
-                    }
-                    // if we reach this point, (t,y) is either amenable or non-amenable in both graphs;
-                    // now, if it is amenable but the adjustment set is not valid in the true graph (only in the guess graph)
-                    else if !y_nam_in_true && nva_in_true.contains(&y) {
-                        // we count a mistake
-                        mistakes += 1;
-                    }
-                }
-            }
+    parent_aid_with(truth, guess, Plain)
+}
+
+/// Computes the parent adjustment intervention distance
+/// between an estimated `guess` DAG or CPDAG and the true `truth` DAG or CPDAG
+/// as the given [`AidOutput`]: [`Plain`] for the distance of [`parent_aid`],
+/// or an output that grades or reports the verdicts of the same search from each treatment differently,
+/// such as [`WithBreakdown`](super::WithBreakdown), [`Detailed`](super::Detailed),
+/// [`PerTreatment`](super::PerTreatment), [`WithStats`](super::WithStats),
+/// [`WithTruthSets`](super::WithTruthSets), [`Normalization`](super::Normalization),
+/// [`PartialCredit`](super::PartialCredit), [`PairWeights`](super::PairWeights),
+/// [`KnownEdges`](super::KnownEdges), [`SelectedPairs`](super::SelectedPairs),
+/// [`SelectedEffectSets`](super::SelectedEffectSets), or [`Approximate`](super::Approximate).
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{parent_aid, parent_aid_with, Plain, SelectedPairs, WithBreakdown},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let distance = parent_aid_with(&truth, &guess, Plain);
+/// assert_eq!(distance, parent_aid(&truth, &guess));
+/// let breakdown = parent_aid_with(&truth, &guess, WithBreakdown);
+/// assert_eq!(breakdown.mistakes(), distance.1);
+/// let selected = parent_aid_with(&truth, &guess, SelectedPairs(&[(2, 1)])).unwrap();
+/// assert_eq!(selected.distance(), (1.0, 1));
+/// ```
+pub fn parent_aid_with<O: AidOutput>(truth: &PDAG, guess: &PDAG, output: O) -> O::Output {
+    output.compute(&Aid::parent(truth, guess))
+}
+
+/// Records the verdict on the parent adjustment for the single `treatment`
+/// on each of the given `effects` into the `sink`.
+// This function largely overlaps with record_ancestor_aid_verdicts in ancestor_aid.rs; differences ---highlighted--- below
+pub(crate) fn record_parent_aid_verdicts(
+    truth: &PDAG,
+    guess: &PDAG,
+    treatment: usize,
+    effects: Effects,
+    sink: &mut impl MistakeSink,
+) {
+    // --- this function differs from ancestor_aid.rs only in the imports and from here
+
+    // parent adjustment
+    let adjustment_set = FxHashSet::from_iter(guess.parents_of(treatment).to_vec());
+
+    // in line with the original SID, claim all NonParents may be effects
+    // (this is a larger set than the NonDescendants in ancestor_aid and oset_aid;
+    //  that is, the validity of the adjustment set is also checked
+    //  for the additional non-effect nodes in NonParents\NonDescendants)
+    let claim_possible_effect = |y: usize| !adjustment_set.contains(&y);
+    let nam_in_guess = get_nam(guess, &[treatment], effects.of_interest());
+    // --- to here
+
+    // now we take a look at the nodes in the true graph for which the adj.set. was not valid.
+    let (t_poss_desc_in_truth, nam_in_true, nva_in_true) =
+        get_pd_nam_nva(truth, &[treatment], &adjustment_set, effects.of_interest());
+
+    sink.record_truth_sets(&[treatment], &nam_in_true, &nva_in_true);
 
-            mistakes
-        })
-        .sum();
+    let claims = Claims {
+        is_claimed_effect: claim_possible_effect,
+        poss_desc_in_truth: &t_poss_desc_in_truth,
+        nam_in_guess: &nam_in_guess,
+        nam_in_true: &nam_in_true,
+        // the adjustment set is valid in the guess graph, but maybe not in the true graph
+        is_wrongly_adjusted: |ys: &[usize]| ys.iter().any(|y| nva_in_true.contains(y)),
+    };
 
-    let n = guess.n_nodes;
-    let comparisons = n * n - n;
-    (
-        verifier_mistakes_found as f64 / comparisons as f64,
-        verifier_mistakes_found,
-    )
+    effects.record_verdicts(&[treatment], |ys| claims.verdict(ys), sink);
 }
 
 #[cfg(test)]
 mod test {
+    use crate::graph_operations::{SelectedEffectSets, SelectedPairs};
     use rand::SeedableRng;
 
-    use crate::PDAG;
+    use crate::{
+        testdata::reference::{Corpus, SHD, SID},
+        PDAG,
+    };
 
-    use super::parent_aid;
+    use super::{parent_aid, parent_aid_with};
 
     #[test]
     fn property_equal_dags_zero_distance() {
@@ -150,9 +188,9 @@ mod test {
             vec![0, 0, 0, 0, 0],
             vec![0, 0, 0, 0, 0],
         ];
-        let g_dag = PDAG::from_row_to_column_vecvec(g);
-        let h1_dag = PDAG::from_row_to_column_vecvec(h1);
-        let h2_dag = PDAG::from_row_to_column_vecvec(h2);
+        let g_dag = PDAG::from_dense_row_to_col(g);
+        let h1_dag = PDAG::from_dense_row_to_col(h1);
+        let h2_dag = PDAG::from_dense_row_to_col(h2);
 
         assert_eq!(parent_aid(&g_dag, &h1_dag), (0.0, 0));
         assert_eq!(parent_aid(&g_dag, &h2_dag), (0.4, 8));
@@ -162,37 +200,87 @@ mod test {
     #[ignore]
     // uses that for DAGs the Parent-AID reduces to the SID
     fn parent_aid_against_r_sid() {
-        // anchors at parent directory of Cargo.toml
-        let mut testgraphs = std::path::PathBuf::new();
-        testgraphs.push("..");
-        testgraphs.push("testgraphs");
-
-        let testcases_file =
-            std::fs::read_to_string(testgraphs.join("SID-100-node-DAGs.csv")).unwrap();
-        let mut testcases = testcases_file.lines();
-        testcases.next(); // skip header
-
-        // create iterator over testcases to later use in the loop
-        let tests = testcases.map(|line| {
-            let mut iter = line.split(',');
-            let g_true = iter.next().unwrap().parse::<usize>().unwrap();
-            let g_guess = iter.next().unwrap().parse::<usize>().unwrap();
-            iter.next();
-            let r_sid = iter.next().unwrap().parse::<usize>().unwrap();
-            (g_true, g_guess, r_sid)
-        });
-
-        // go through all testcases, load the DAGs from the mtx files and
-        // compare the computed Parent-AID=SID with the SID in the csv file (computed via R SID package)
-        for (gtrue, gguess, rsid) in tests {
-            let full_path_true = testgraphs.join(format!("100-node-DAG-{}.mtx", gtrue));
-            let full_path_guess = testgraphs.join(format!("100-node-DAG-{}.mtx", gguess));
-            let g_true = crate::test::load_pdag_from_mtx(full_path_true.to_str().unwrap());
-            let g_guess = crate::test::load_pdag_from_mtx(full_path_guess.to_str().unwrap());
-
-            let (_, mistakes) = parent_aid(&g_true, &g_guess);
-
-            assert_eq!(mistakes, rsid);
+        // compare the computed Parent-AID=SID and SHD with the values in the csv file
+        // (computed via the R packages SID and pcalg)
+        Corpus::testgraphs("SID-100-node-DAGs.csv", "100-node-DAG-{}.mtx")
+            .assert_agreement(&[SID, SHD]);
+    }
+
+    #[test]
+    fn property_selected_pairs_agree_with_all_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            assert_eq!(
+                parent_aid(&truth, &guess),
+                parent_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+            // searches restricted to a single effect must count the same mistakes
+            let mistakes_by_pair: usize = all_pairs
+                .iter()
+                .map(|pair| {
+                    parent_aid_with(&truth, &guess, SelectedPairs(&[*pair]))
+                        .unwrap()
+                        .mistakes
+                })
+                .sum();
+            assert_eq!(parent_aid(&truth, &guess).1, mistakes_by_pair);
+        }
+    }
+
+    #[test]
+    fn effect_sets_are_graded_jointly() {
+        // guess: 0 -- 1, 0 -> 2; truth: 0 -> 1, 0 -- 2
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2, 1], //
+            vec![0, 0, 0],
+            vec![0, 0, 0],
+        ]);
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 2], //
+            vec![0, 0, 0],
+            vec![0, 0, 0],
+        ]);
+        // each effect on its own is a mistake, since the graphs disagree on its amenability,
+        // but both graphs agree that the joint effect on 1 and 2 is not amenable
+        let distance = parent_aid_with(&truth, &guess, SelectedPairs(&[(0, 1), (0, 2)])).unwrap();
+        assert_eq!(distance.mistakes, 2);
+        let distance = parent_aid_with(
+            &truth,
+            &guess,
+            SelectedEffectSets(&[(0, vec![1, 2]), (0, vec![2, 1])]),
+        )
+        .unwrap();
+        assert_eq!((distance.mistakes, distance.comparisons), (0, 1));
+        let distance = parent_aid_with(
+            &truth,
+            &guess,
+            SelectedEffectSets(&[(0, vec![1]), (0, vec![1, 2])]),
+        )
+        .unwrap();
+        assert_eq!((distance.mistakes, distance.comparisons), (1, 2));
+    }
+
+    #[test]
+    fn property_singleton_effect_sets_agree_with_selected_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            let singletons = Vec::from_iter(all_pairs.iter().map(|(t, y)| (*t, vec![*y])));
+            assert_eq!(
+                parent_aid_with(&truth, &guess, SelectedPairs(&all_pairs)),
+                parent_aid_with(&truth, &guess, SelectedEffectSets(&singletons))
+            );
         }
     }
 }
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Grades the adjustment identification distances with partial credit for "don't know" answers,
+//! that is, for pairs that the guess graph wrongly claims not to be amenable
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput},
+    selected_pairs::Mistakes,
+};
+
+/// Counts each pair that the guess graph wrongly claims not to be amenable, such as due to
+/// an undirected edge that is directed in the true graph, as `1 - partial_credit` mistakes
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings, so that "don't know" answers are graded softer.
+/// The output is a tuple of (normalized error (in \[0,1]), fractional total of errors).
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, PartialCredit}, PDAG};
+///
+/// // truth: 0 -> 1, guess: 0 -- 1
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1], //
+///     vec![0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2], //
+///     vec![0, 0],
+/// ]);
+/// // the guess claims that neither effect is amenable, which earns half credit for both pairs
+/// assert_eq!(ancestor_aid_with(&truth, &guess, PartialCredit(0.5)), (0.5, 1.0));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PartialCredit(pub f64);
+
+impl AidOutput for PartialCredit {
+    type Output = (f64, f64);
+
+    fn compute(self, aid: &Aid) -> (f64, f64) {
+        let partial_credit = self.0;
+        assert!(
+            (0.0..=1.0).contains(&partial_credit),
+            "partial credit must be in [0, 1]"
+        );
+
+        let mistakes: Mistakes = aid.fold_twins();
+        let verifier_mistakes_found = mistakes.with_partial_credit(partial_credit);
+        (
+            verifier_mistakes_found / aid.n_pairs() as f64,
+            verifier_mistakes_found,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            PartialCredit,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn undirected_edges_earn_partial_credit() {
+        // truth: 0 -> 1
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1], //
+            vec![0, 0],
+        ]);
+        // guess: 0 -- 1
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 2], //
+            vec![0, 0],
+        ]);
+        // the guess graph claims that neither effect is amenable, which the true graph contradicts
+        assert_eq!(ancestor_aid(&truth, &guess), (1.0, 2));
+        assert_eq!(
+            ancestor_aid_with(&truth, &guess, PartialCredit(0.0)),
+            (1.0, 2.0)
+        );
+        assert_eq!(
+            ancestor_aid_with(&truth, &guess, PartialCredit(0.5)),
+            (0.5, 1.0)
+        );
+        assert_eq!(
+            ancestor_aid_with(&truth, &guess, PartialCredit(1.0)),
+            (0.0, 0.0)
+        );
+
+        // a wrong orientation is a full mistake, irrespective of the partial credit
+        let reversed = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0], //
+            vec![1, 0],
+        ]);
+        assert_eq!(
+            oset_aid_with(&truth, &reversed, PartialCredit(1.0)),
+            (1.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn property_no_partial_credit_gives_aids() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let as_fraction = |(normalized, mistakes): (f64, usize)| (normalized, mistakes as f64);
+            assert_eq!(
+                ancestor_aid_with(&truth, &guess, PartialCredit(0.0)),
+                as_fraction(ancestor_aid(&truth, &guess))
+            );
+            assert_eq!(
+                oset_aid_with(&truth, &guess, PartialCredit(0.0)),
+                as_fraction(oset_aid(&truth, &guess))
+            );
+            assert_eq!(
+                parent_aid_with(&truth, &guess, PartialCredit(0.0)),
+                as_fraction(parent_aid(&truth, &guess))
+            );
+            // partial credit can only decrease the distance
+            let (_, full_credit) = parent_aid_with(&truth, &guess, PartialCredit(1.0));
+            assert!(full_credit <= parent_aid(&truth, &guess).1 as f64);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Reports the mistakes of the adjustment identification distances per treatment,
+//! to locate the regions of a guess graph that cause them
+
+use crate::graph_operations::aid::{Aid, AidOutput};
+
+/// The number of mistakes of each treatment, when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings:
+/// entry `t` is the number of effects `y` for which the pair `(t, y)` is a mistake,
+/// to identify the regions of the guess graph that cause mistakes.
+/// The entries sum to the total number of errors of the distance.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, PerTreatment}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// assert_eq!(ancestor_aid_with(&truth, &guess, PerTreatment), vec![1, 1, 1]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct PerTreatment;
+
+impl AidOutput for PerTreatment {
+    type Output = Vec<usize>;
+
+    fn compute(self, aid: &Aid) -> Vec<usize> {
+        // every twin needs its own entry
+        aid.each_treatment(|_| 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            Detailed, PerTreatment,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn property_per_treatment_mistakes_sum_to_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            for (per_treatment, distance, detailed) in [
+                (
+                    ancestor_aid_with(&truth, &guess, PerTreatment),
+                    ancestor_aid(&truth, &guess),
+                    ancestor_aid_with(&truth, &guess, Detailed),
+                ),
+                (
+                    oset_aid_with(&truth, &guess, PerTreatment),
+                    oset_aid(&truth, &guess),
+                    oset_aid_with(&truth, &guess, Detailed),
+                ),
+                (
+                    parent_aid_with(&truth, &guess, PerTreatment),
+                    parent_aid(&truth, &guess),
+                    parent_aid_with(&truth, &guess, Detailed),
+                ),
+            ] {
+                assert_eq!(per_treatment.len(), n);
+                assert_eq!(per_treatment.iter().sum::<usize>(), distance.1);
+                for (mistakes, row) in per_treatment.iter().zip(&detailed.matrix) {
+                    assert_eq!(*mistakes, row.iter().filter(|mistake| **mistake).count());
+                }
+            }
+        }
+    }
+}
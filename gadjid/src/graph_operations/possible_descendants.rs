@@ -1,7 +1,6 @@
 // SPDX-License-Identifier: MPL-2.0
 //! Algorithm for getting all possible descendants of a set of nodes
 
-#[cfg(test)]
 /// Gets all the possible descendants of a set of nodes.
 /// The input nodes are also included in the output.
 pub(crate) fn get_possible_descendants<'a>(
@@ -47,7 +46,7 @@ mod test {
             vec![0, 0, 0, 0],
             vec![0, 0, 0, 0],
         ];
-        let cpdag = PDAG::from_row_to_column_vecvec(cpdag);
+        let cpdag = PDAG::from_dense_row_to_col(cpdag);
         let result = super::get_possible_descendants(&cpdag, [0].iter());
         assert_eq!(result, FxHashSet::from_iter(vec![0, 1, 2, 3]));
 
@@ -62,7 +61,7 @@ mod test {
             vec![0, 0, 0, 0, 0, 0],
             vec![0, 0, 0, 0, 1, 0],
         ];
-        let cpdag = PDAG::from_row_to_column_vecvec(cpdag);
+        let cpdag = PDAG::from_dense_row_to_col(cpdag);
         let result = super::get_possible_descendants(&cpdag, [4].iter());
         assert_eq!(result, FxHashSet::from_iter(vec![0, 1, 2, 3, 4]));
     }
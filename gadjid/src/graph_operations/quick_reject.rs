@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Answers distance queries that need no search from cheap statistics of the two graphs.
+//!
+//! Identical graphs are at distance zero under every metric, and which graphs are identical is
+//! mostly decided by their numbers of edges and the degrees of their nodes, without reading any edge.
+//! For the SHD, a graph without edges shares no adjacency with the other graph,
+//! so every edge of the other graph is a mistake.
+
+use crate::PDAG;
+
+/// Whether the graphs are identical. Compares the numbers of edges and the degrees of each node
+/// before the neighbourhoods, so graphs that differ are usually told apart without reading their edges.
+pub(crate) fn identical(truth: &PDAG, guess: &PDAG) -> bool {
+    truth.n_nodes == guess.n_nodes
+        && truth.n_directed_edges == guess.n_directed_edges
+        && truth.n_undirected_edges == guess.n_undirected_edges
+        && truth.node_in_out_degree == guess.node_in_out_degree
+        && truth.node_edge_ranges == guess.node_edge_ranges
+        && truth.neighbourhoods == guess.neighbourhoods
+}
+
+/// The number of SHD mistakes if either graph has no edges, which is the number of edges of the other.
+pub(crate) fn shd_if_either_empty(truth: &PDAG, guess: &PDAG) -> Option<usize> {
+    let truth_edges = truth.n_directed_edges + truth.n_undirected_edges;
+    let guess_edges = guess.n_directed_edges + guess.n_undirected_edges;
+    (truth_edges == 0 || guess_edges == 0).then_some(truth_edges + guess_edges)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{ancestor_aid, oset_aid, parent_aid, shd, weighted_shd},
+        PDAG,
+    };
+
+    use super::{identical, shd_if_either_empty};
+
+    #[test]
+    fn tells_graphs_apart() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..20 {
+            let truth = PDAG::random_pdag(0.3, 10, &mut rng);
+            let guess = PDAG::random_pdag(0.3, 10, &mut rng);
+            assert_eq!(identical(&truth, &guess), truth == guess);
+            assert!(identical(&truth, &truth));
+            for distance in [ancestor_aid, oset_aid, parent_aid, shd] {
+                assert_eq!(distance(&truth, &truth), (0.0, 0));
+            }
+        }
+    }
+
+    #[test]
+    fn shd_against_empty_graph_counts_edges() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let empty = PDAG::from_dense_row_to_col(vec![vec![0; 10]; 10]);
+        // the weighted SHD with unit costs off the diagonal is the SHD, without the shortcut
+        let mut unit_costs = [[1.0; 4]; 4];
+        (0..4).for_each(|i| unit_costs[i][i] = 0.0);
+        for _ in 0..20 {
+            let graph = PDAG::random_pdag(0.3, 10, &mut rng);
+            let edges = graph.n_directed_edges + graph.n_undirected_edges;
+            assert_eq!(shd_if_either_empty(&graph, &empty), Some(edges));
+            assert_eq!(shd_if_either_empty(&empty, &graph), Some(edges));
+            assert_eq!(shd(&graph, &empty), (edges as f64 / 45.0, edges));
+            assert_eq!(weighted_shd(&graph, &empty, &unit_costs).cost, edges as f64);
+        }
+    }
+}
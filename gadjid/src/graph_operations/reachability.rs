@@ -3,7 +3,11 @@
 
 use rustc_hash::FxHashSet;
 
-use crate::{partially_directed_acyclic_graph::Edge, PDAG};
+use crate::{
+    partially_directed_acyclic_graph::Edge,
+    sets::{use_bitset, with_set_backend, NodeSetImpl, VisitedStates},
+    PDAG,
+};
 
 /*
 Developer's guide to the functions in this file (see also Appendix D of https://doi.org/10.48550/arXiv.2402.08616)
@@ -80,23 +84,19 @@ The following reachability algorithms take a graph, a set of nodes t, and a set
 
 /// Returns possible children of the node `v` and the shared edge. `v (-> c)` or `v (-- c)`. See the [`Edge`] enum for a more detailed explanation of this notation.
 /// Will not return treatment nodes.
-fn get_next_steps(graph: &PDAG, t: &[usize], v: usize) -> Vec<(Edge, usize)> {
-    let mut next = Vec::<(Edge, usize)>::new();
-    graph
+fn get_next_steps<'a>(
+    graph: &'a PDAG,
+    t: &'a [usize],
+    v: usize,
+) -> impl Iterator<Item = (Edge, usize)> + 'a {
+    let undirected = graph
         .adjacent_undirected_of(v)
         .iter()
-        .filter(|u| !t.contains(*u))
-        .for_each(|u| {
-            next.push((Edge::Undirected, *u));
-        });
-    graph
-        .children_of(v)
-        .iter()
-        .filter(|c| !t.contains(*c))
-        .for_each(|c| {
-            next.push((Edge::Incoming, *c));
-        });
-    next
+        .map(|u| (Edge::Undirected, *u));
+    let children = graph.children_of(v).iter().map(|c| (Edge::Incoming, *c));
+    undirected
+        .chain(children)
+        .filter(move |(_, w)| !t.contains(w))
 }
 
 /// Checks amenability of a (CP)DAG relative to (T, Y) for a given set T of treatment
@@ -109,6 +109,14 @@ fn get_next_steps(graph: &PDAG, t: &[usize], v: usize) -> Vec<(Edge, usize)> {
 pub fn get_d_pd_nam(
     graph: &PDAG,
     t: &[usize],
+) -> (FxHashSet<usize>, FxHashSet<usize>, FxHashSet<usize>) {
+    with_set_backend!(use_bitset(graph.n_nodes), get_d_pd_nam_with(graph, t))
+}
+
+/// Like [`get_d_pd_nam`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_d_pd_nam_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
 ) -> (FxHashSet<usize>, FxHashSet<usize>, FxHashSet<usize>) {
     #[allow(non_camel_case_types)]
     #[allow(clippy::upper_case_acronyms)]
@@ -128,11 +136,12 @@ pub fn get_d_pd_nam(
     let mut poss_desc = desc.clone();
     let mut not_amenable = FxHashSet::<usize>::default();
 
-    let mut visited = FxHashSet::<(Edge, usize, WalkStatus)>::default();
+    // Init is the last walk status
+    let mut visited = VisitedStates::<S>::new(graph.n_nodes, WalkStatus::Init as usize + 1);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v, WalkStatus::Init)));
 
     while let Some((arrived_by, node, walkstatus)) = to_visit_stack.pop() {
-        visited.insert((arrived_by, node, walkstatus));
+        visited.insert(arrived_by, node, walkstatus as usize);
 
         match walkstatus {
             WalkStatus::PD_NAM => {
@@ -168,7 +177,7 @@ pub fn get_d_pd_nam(
             };
 
             if let Some(next) = next {
-                if !visited.contains(&next) {
+                if !visited.contains(next.0, next.1, next.2 as usize) {
                     to_visit_stack.push(next);
                 }
             }
@@ -179,12 +188,32 @@ pub fn get_d_pd_nam(
 }
 
 /// Checks amenability of a (CP)DAG relative to (T, Y) for a given set T of treatment
-/// nodes and all possible Y.
+/// nodes and all possible Y (or optionally only all y_of_interest).
 ///
 /// Returns tuple of:<br>
 /// - Set PD of possible descendants of T in G
 /// - Set NAM (Not AMenable) of nodes Y \notin T in G such that G is not amenable relative to (T, Y)
-pub fn get_pd_nam(graph: &PDAG, t: &[usize]) -> (FxHashSet<usize>, FxHashSet<usize>) {
+///
+/// If y_of_interest is given, the search stops as soon as all y_of_interest are in NAM
+/// (and thereby in PD); then, both sets are only guaranteed to be correct for the y_of_interest.
+/// If some y_of_interest are amenable, the search never stops early and visits as many states as without them.
+pub fn get_pd_nam(
+    graph: &PDAG,
+    t: &[usize],
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> (FxHashSet<usize>, FxHashSet<usize>) {
+    with_set_backend!(
+        use_bitset(graph.n_nodes),
+        get_pd_nam_with(graph, t, y_of_interest)
+    )
+}
+
+/// Like [`get_pd_nam`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_pd_nam_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> (FxHashSet<usize>, FxHashSet<usize>) {
     #[allow(non_camel_case_types)]
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
     enum WalkStatus {
@@ -196,19 +225,29 @@ pub fn get_pd_nam(graph: &PDAG, t: &[usize]) -> (FxHashSet<usize>, FxHashSet<usi
         Init,
     }
 
+    let mut y_of_interest = y_of_interest.cloned();
+
     let mut poss_de = FxHashSet::from_iter(t.iter().copied());
     let mut not_amenable = FxHashSet::<usize>::default();
 
-    let mut visited = FxHashSet::<(Edge, usize, WalkStatus)>::default();
+    // Init is the last walk status
+    let mut visited = VisitedStates::<S>::new(graph.n_nodes, WalkStatus::Init as usize + 1);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v, WalkStatus::Init)));
 
     while let Some((arrived_by, node, walkstatus)) = to_visit_stack.pop() {
-        visited.insert((arrived_by, node, walkstatus));
+        visited.insert(arrived_by, node, walkstatus as usize);
 
         match walkstatus {
             WalkStatus::PD_NAM => {
                 not_amenable.insert(node);
                 poss_de.insert(node);
+                // a node in NAM is in all returned sets, so no further walk can change its status
+                if let Some(ref mut still_to_be_determined_y) = y_of_interest {
+                    if still_to_be_determined_y.remove(&node) && still_to_be_determined_y.is_empty()
+                    {
+                        return (poss_de, not_amenable);
+                    }
+                }
             }
             // any other PD walk
             WalkStatus::PD_AM => {
@@ -231,7 +270,7 @@ pub fn get_pd_nam(graph: &PDAG, t: &[usize]) -> (FxHashSet<usize>, FxHashSet<usi
             };
 
             if let Some(next) = next {
-                if !visited.contains(&next) {
+                if !visited.contains(next.0, next.1, next.2 as usize) {
                     to_visit_stack.push(next);
                 }
             }
@@ -242,15 +281,37 @@ pub fn get_pd_nam(graph: &PDAG, t: &[usize]) -> (FxHashSet<usize>, FxHashSet<usi
 }
 
 /// Checks amenability of a CPDAG relative to (T, Y) for a given set T of treatment
-/// nodes and all possible Y.
+/// nodes and all possible Y (or optionally only all y_of_interest).
 ///
 /// Returns set NAM (Not AMenable) of nodes Y \notin T in G such that G is not amenable relative to (T, Y)
 ///
+/// If y_of_interest is given, the search stops as soon as all y_of_interest are in NAM;
+/// then, NAM is only guaranteed to be correct for the y_of_interest.
+/// If some y_of_interest are amenable, the search never stops early and visits as many states as without them.
+///
 /// Follows Algorithm 2 in https://doi.org/10.48550/arXiv.2402.08616
-pub fn get_nam(graph: &PDAG, t: &[usize]) -> FxHashSet<usize> {
+pub fn get_nam(
+    graph: &PDAG,
+    t: &[usize],
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> FxHashSet<usize> {
+    with_set_backend!(
+        use_bitset(graph.n_nodes),
+        get_nam_with(graph, t, y_of_interest)
+    )
+}
+
+/// Like [`get_nam`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_nam_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> FxHashSet<usize> {
+    let mut y_of_interest = y_of_interest.cloned();
+
     let mut not_amenable = FxHashSet::<usize>::default();
 
-    let mut visited = FxHashSet::<usize>::default();
+    let mut visited = S::for_keys(graph.n_nodes);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v)));
 
     while let Some((arrived_by, node)) = to_visit_stack.pop() {
@@ -260,7 +321,7 @@ pub fn get_nam(graph: &PDAG, t: &[usize]) -> FxHashSet<usize> {
                 graph
                     .adjacent_undirected_of(node)
                     .iter()
-                    .filter(|p| !visited.contains(p) && !t.contains(p))
+                    .filter(|p| !visited.contains(**p) && !t.contains(p))
                     .for_each(|p| {
                         to_visit_stack.push((Edge::Undirected, *p));
                     });
@@ -268,63 +329,51 @@ pub fn get_nam(graph: &PDAG, t: &[usize]) -> FxHashSet<usize> {
             // Edge::Incoming | Edge::Outgoing | Edge::Undirected
             _ => {
                 not_amenable.insert(node);
-                get_next_steps(graph, t, node)
-                    .into_iter()
-                    .for_each(|(move_on_by, w)| {
-                        if !visited.contains(&w) {
-                            to_visit_stack.push((move_on_by, w));
-                        }
-                    });
+                if let Some(ref mut still_to_be_determined_y) = y_of_interest {
+                    if still_to_be_determined_y.remove(&node) && still_to_be_determined_y.is_empty()
+                    {
+                        return not_amenable;
+                    }
+                }
+                get_next_steps(graph, t, node).for_each(|(move_on_by, w)| {
+                    if !visited.contains(w) {
+                        to_visit_stack.push((move_on_by, w));
+                    }
+                });
             }
         }
     }
     not_amenable
 }
 
-fn get_next_steps_conditioned(
-    graph: &PDAG,
-    t: &[usize],
+fn get_next_steps_conditioned<'a>(
+    graph: &'a PDAG,
+    t: &'a [usize],
     arrived_by: Edge,
     v: usize,
     node_is_adjustment: bool,
-) -> Vec<(Edge, usize, bool)> {
-    let mut next = Vec::<(Edge, usize, bool)>::new();
-    match arrived_by {
-        Edge::Incoming => {
-            graph
-                .parents_of(v)
-                .iter()
-                .filter(|p| !t.contains(*p))
-                .for_each(|p| {
-                    next.push((Edge::Outgoing, *p, !node_is_adjustment));
-                });
-        }
-        Edge::Init | Edge::Outgoing => {
-            graph
-                .parents_of(v)
-                .iter()
-                .filter(|p| !t.contains(*p))
-                .for_each(|p| {
-                    next.push((Edge::Outgoing, *p, node_is_adjustment));
-                });
-        }
-        _ => (),
-    }
-    graph
+) -> impl Iterator<Item = (Edge, usize, bool)> + 'a {
+    // parents are not walked to after arriving by an undirected edge
+    let (parents, parent_is_blocked): (&[usize], bool) = match arrived_by {
+        Edge::Incoming => (graph.parents_of(v), !node_is_adjustment),
+        Edge::Init | Edge::Outgoing => (graph.parents_of(v), node_is_adjustment),
+        _ => (&[], false),
+    };
+    let parents = parents
+        .iter()
+        .map(move |p| (Edge::Outgoing, *p, parent_is_blocked));
+    let undirected = graph
         .adjacent_undirected_of(v)
         .iter()
-        .filter(|u| !t.contains(*u))
-        .for_each(|u| {
-            next.push((Edge::Undirected, *u, node_is_adjustment));
-        });
-    graph
+        .map(move |u| (Edge::Undirected, *u, node_is_adjustment));
+    let children = graph
         .children_of(v)
         .iter()
-        .filter(|c| !t.contains(*c))
-        .for_each(|c| {
-            next.push((Edge::Incoming, *c, node_is_adjustment));
-        });
-    next
+        .map(move |c| (Edge::Incoming, *c, node_is_adjustment));
+    parents
+        .chain(undirected)
+        .chain(children)
+        .filter(move |(_, w, _)| !t.contains(w))
 }
 
 /// Validate Z as adjustment set relative to (T, Y) for a given set T of treatment
@@ -335,10 +384,28 @@ fn get_next_steps_conditioned(
 /// - Set NAM (Not AMenable) of nodes Y \notin T in G such that G is not amenable relative to (T, Y)
 /// - Set NVA (Not Validly Adjusted) of nodes Y \notin T in G such that Z is not a valid adjustment set for (T, Y) in G.
 ///   This includes all NAM, so NAM is a subset NVA.
+///
+/// If y_of_interest is given, the search stops as soon as all y_of_interest are in NAM
+/// (and thereby in PD and NVA); then, the sets are only guaranteed to be correct for the y_of_interest.
+/// If some y_of_interest are amenable, the search never stops early and visits as many states as without them.
 pub fn get_pd_nam_nva(
     graph: &PDAG,
     t: &[usize],
     z: &FxHashSet<usize>,
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> (FxHashSet<usize>, FxHashSet<usize>, FxHashSet<usize>) {
+    with_set_backend!(
+        use_bitset(graph.n_nodes),
+        get_pd_nam_nva_with(graph, t, z, y_of_interest)
+    )
+}
+
+/// Like [`get_pd_nam_nva`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_pd_nam_nva_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
+    z: &FxHashSet<usize>,
+    y_of_interest: Option<&FxHashSet<usize>>,
 ) -> (FxHashSet<usize>, FxHashSet<usize>, FxHashSet<usize>) {
     #[allow(non_camel_case_types)]
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -357,15 +424,18 @@ pub fn get_pd_nam_nva(
         Init,
     }
 
+    let mut y_of_interest = y_of_interest.cloned();
+
     let mut poss_de = FxHashSet::from_iter(t.iter().copied());
     let mut not_amenable = FxHashSet::<usize>::default();
     let mut not_vas = z.clone();
 
-    let mut visited = FxHashSet::<(Edge, usize, WalkStatus)>::default();
+    // Init is the last walk status
+    let mut visited = VisitedStates::<S>::new(graph.n_nodes, WalkStatus::Init as usize + 1);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v, WalkStatus::Init)));
 
     while let Some((arrived_by, node, walkstatus)) = to_visit_stack.pop() {
-        visited.insert((arrived_by, node, walkstatus));
+        visited.insert(arrived_by, node, walkstatus as usize);
 
         match walkstatus {
             WalkStatus::PD_OPEN_NAM | WalkStatus::PD_BLOCKED_NAM => {
@@ -374,6 +444,13 @@ pub fn get_pd_nam_nva(
                 // so, if we insert a node into not_amenable, we also insert it into not_vas
                 not_vas.insert(node);
                 poss_de.insert(node);
+                // a node in NAM is in all returned sets, so no further walk can change its status
+                if let Some(ref mut still_to_be_determined_y) = y_of_interest {
+                    if still_to_be_determined_y.remove(&node) && still_to_be_determined_y.is_empty()
+                    {
+                        return (poss_de, not_amenable, not_vas);
+                    }
+                }
             }
             WalkStatus::NON_CAUSAL_OPEN => {
                 not_vas.insert(node);
@@ -426,7 +503,7 @@ pub fn get_pd_nam_nva(
             };
 
             if let Some(next) = next {
-                if !visited.contains(&next) {
+                if !visited.contains(next.0, next.1, next.2 as usize) {
                     to_visit_stack.push(next);
                 }
             }
@@ -445,11 +522,28 @@ pub fn get_pd_nam_nva(
 /// - Set NAM (Not AMenable) of nodes Y \notin T in G such that G is not amenable relative to (T, Y)
 /// - Set NVA (Not Validly Adjusted) of nodes Y \notin T in G such that Z is not a valid adjustment set for (T, Y) in G.
 ///   This includes all NAM, so NAM is a subset NVA.
-#[cfg(test)]
+///
+/// If y_of_interest is given, the search stops as soon as all y_of_interest are in NAM
+/// (and thereby in NVA); then, both sets are only guaranteed to be correct for the y_of_interest.
+/// If some y_of_interest are amenable, the search never stops early and visits as many states as without them.
 pub fn get_nam_nva(
     graph: &PDAG,
     t: &[usize],
     z: &FxHashSet<usize>,
+    y_of_interest: Option<&FxHashSet<usize>>,
+) -> (FxHashSet<usize>, FxHashSet<usize>) {
+    with_set_backend!(
+        use_bitset(graph.n_nodes),
+        get_nam_nva_with(graph, t, z, y_of_interest)
+    )
+}
+
+/// Like [`get_nam_nva`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_nam_nva_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
+    z: &FxHashSet<usize>,
+    y_of_interest: Option<&FxHashSet<usize>>,
 ) -> (FxHashSet<usize>, FxHashSet<usize>) {
     #[allow(non_camel_case_types)]
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -468,14 +562,17 @@ pub fn get_nam_nva(
         Init,
     }
 
+    let mut y_of_interest = y_of_interest.cloned();
+
     let mut not_amenable = FxHashSet::<usize>::default();
     let mut not_vas = z.clone();
 
-    let mut visited = FxHashSet::<(Edge, usize, WalkStatus)>::default();
+    // Init is the last walk status
+    let mut visited = VisitedStates::<S>::new(graph.n_nodes, WalkStatus::Init as usize + 1);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v, WalkStatus::Init)));
 
     while let Some((arrived_by, node, walkstatus)) = to_visit_stack.pop() {
-        visited.insert((arrived_by, node, walkstatus));
+        visited.insert(arrived_by, node, walkstatus as usize);
 
         match walkstatus {
             WalkStatus::PD_OPEN_NAM | WalkStatus::PD_BLOCKED_NAM => {
@@ -483,6 +580,12 @@ pub fn get_nam_nva(
                 // we want the property that not_amenable is a subset of not_vas
                 // so, if we insert a node into not_amenable, we also insert it into not_vas
                 not_vas.insert(node);
+                if let Some(ref mut still_to_be_determined_y) = y_of_interest {
+                    if still_to_be_determined_y.remove(&node) && still_to_be_determined_y.is_empty()
+                    {
+                        return (not_amenable, not_vas);
+                    }
+                }
             }
             WalkStatus::NON_CAUSAL_OPEN | WalkStatus::PD_BLOCKED_AM => {
                 not_vas.insert(node);
@@ -528,7 +631,7 @@ pub fn get_nam_nva(
             };
 
             if let Some(next) = next {
-                if !visited.contains(&next) {
+                if !visited.contains(next.0, next.1, next.2 as usize) {
                     to_visit_stack.push(next);
                 }
             }
@@ -543,15 +646,28 @@ pub fn get_nam_nva(
 ///
 /// Returns tuple of:<br>
 /// - Set NVA (Not Validly Adjusted) of nodes Y \notin T in G such that Z is not a valid adjustment set for (T, Y) in G.
-/// Here, amenability (condition 1.) is not verified, that is, NVA is not a superset of NAM;
-/// instead, NVA contains Y for which condition 2. or 3.
-/// of the modified adjustment criterion for walk-based verification
-/// in https://doi.org/10.48550/arXiv.2402.08616 are violated
+///   Here, amenability (condition 1.) is not verified, that is, NVA is not a superset of NAM;
+///   instead, NVA contains Y for which condition 2. or 3.
+///   of the modified adjustment criterion for walk-based verification
+///   in https://doi.org/10.48550/arXiv.2402.08616 are violated
 pub fn get_invalidly_un_blocked(
     graph: &PDAG,
     t: &[usize],
     z: &FxHashSet<usize>,
     y_of_interest: Option<&FxHashSet<usize>>,
+) -> FxHashSet<usize> {
+    with_set_backend!(
+        use_bitset(graph.n_nodes),
+        get_invalidly_un_blocked_with(graph, t, z, y_of_interest)
+    )
+}
+
+/// Like [`get_invalidly_un_blocked`], with the visited states marked in the set backend `S`.
+pub(crate) fn get_invalidly_un_blocked_with<S: NodeSetImpl>(
+    graph: &PDAG,
+    t: &[usize],
+    z: &FxHashSet<usize>,
+    y_of_interest: Option<&FxHashSet<usize>>,
 ) -> FxHashSet<usize> {
     #[allow(non_camel_case_types)]
     #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -570,11 +686,12 @@ pub fn get_invalidly_un_blocked(
 
     let mut ivb = z.clone();
 
-    let mut visited = FxHashSet::<(Edge, usize, WalkStatus)>::default();
+    // Init is the last walk status
+    let mut visited = VisitedStates::<S>::new(graph.n_nodes, WalkStatus::Init as usize + 1);
     let mut to_visit_stack = Vec::from_iter(t.iter().map(|v| (Edge::Init, *v, WalkStatus::Init)));
 
     while let Some((arrived_by, node, walkstatus)) = to_visit_stack.pop() {
-        visited.insert((arrived_by, node, walkstatus));
+        visited.insert(arrived_by, node, walkstatus as usize);
 
         match walkstatus {
             // when the node is reached on a causal path but blocked, or an unblocked non-causal path
@@ -622,7 +739,7 @@ pub fn get_invalidly_un_blocked(
             };
 
             if let Some(next) = next {
-                if !visited.contains(&next) {
+                if !visited.contains(next.0, next.1, next.2 as usize) {
                     to_visit_stack.push(next);
                 }
             }
@@ -638,12 +755,19 @@ mod test {
     use rustc_hash::FxHashSet;
 
     use crate::graph_operations::{
-        ancestor_aid, gensearch, get_descendants, get_nam_nva, get_possible_descendants, oset_aid,
-        parent_aid, ruletables,
+        ancestor_aid, gensearch, gensearch::gensearch_with, get_descendants, get_nam_nva,
+        get_possible_descendants, oset_aid, parent_aid, ruletables,
+    };
+    use crate::{
+        graph_operations::run_stats::visited_triplets_on_this_thread,
+        sets::{BitSet, Counted},
+        PDAG,
     };
-    use crate::PDAG;
 
-    use super::get_nam;
+    use super::{
+        get_d_pd_nam_with, get_invalidly_un_blocked_with, get_nam, get_nam_nva_with, get_nam_with,
+        get_pd_nam_nva_with, get_pd_nam_with,
+    };
 
     #[test]
     pub fn nam_test() {
@@ -657,9 +781,32 @@ mod test {
             vec![0, 0, 0, 0],
             vec![0, 0, 0, 0],
         ];
-        let cpdag = PDAG::from_row_to_column_vecvec(cpdag);
+        let cpdag = PDAG::from_dense_row_to_col(cpdag);
 
-        assert!(get_nam(&cpdag, &[0]) == FxHashSet::from_iter([3]));
+        assert!(get_nam(&cpdag, &[0], None) == FxHashSet::from_iter([3]));
+    }
+
+    #[test]
+    pub fn early_exit_visits_fewer_states() {
+        // 0 -- 1 -> 2 -> ... -> 19, and 20 unconnected
+        let n = 21;
+        let mut cpdag = vec![vec![0; n]; n];
+        cpdag[0][1] = 2;
+        for i in 1..19 {
+            cpdag[i][i + 1] = 1;
+        }
+        let cpdag = PDAG::from_dense_row_to_col(cpdag);
+
+        let visited_states = |y_of_interest: Option<&FxHashSet<usize>>| {
+            let before = visited_triplets_on_this_thread();
+            get_pd_nam_with::<Counted<BitSet>>(&cpdag, &[0], y_of_interest);
+            visited_triplets_on_this_thread() - before
+        };
+        let all = visited_states(None);
+        // all y_of_interest are not amenable, so the search stops once it reaches 1
+        assert!(visited_states(Some(&FxHashSet::from_iter([1]))) < all);
+        // 20 is amenable, so the search does not stop early
+        assert_eq!(visited_states(Some(&FxHashSet::from_iter([1, 20]))), all);
     }
 
     #[test]
@@ -674,8 +821,8 @@ mod test {
             vec![0, 2], //
             vec![0, 0],
         ];
-        let dag = PDAG::from_row_to_column_vecvec(dag);
-        let cpdag = PDAG::from_row_to_column_vecvec(cpdag);
+        let dag = PDAG::from_dense_row_to_col(dag);
+        let cpdag = PDAG::from_dense_row_to_col(cpdag);
 
         assert_eq!((1.0, 2), parent_aid(&dag, &cpdag));
         assert_eq!((1.0, 2), parent_aid(&cpdag, &dag));
@@ -685,6 +832,49 @@ mod test {
         assert_eq!((1.0, 2), oset_aid(&cpdag, &dag));
     }
 
+    #[test]
+    fn set_backends_agree() {
+        type Hashed = FxHashSet<usize>;
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 3..40 {
+            let pdag = PDAG::random_pdag(0.3, n, &mut rng);
+            let mut nodes = rand::seq::index::sample(&mut rng, n, 3).into_vec();
+            let t = vec![nodes.pop().unwrap()];
+            let z = FxHashSet::from_iter(nodes.iter().copied().take(1));
+            let y = FxHashSet::from_iter(nodes.iter().copied().skip(1));
+
+            assert_eq!(
+                get_d_pd_nam_with::<BitSet>(&pdag, &t),
+                get_d_pd_nam_with::<Hashed>(&pdag, &t)
+            );
+            assert_eq!(
+                get_pd_nam_with::<BitSet>(&pdag, &t, None),
+                get_pd_nam_with::<Hashed>(&pdag, &t, None)
+            );
+            assert_eq!(
+                get_nam_with::<BitSet>(&pdag, &t, Some(&y)),
+                get_nam_with::<Hashed>(&pdag, &t, Some(&y))
+            );
+            assert_eq!(
+                get_pd_nam_nva_with::<BitSet>(&pdag, &t, &z, None),
+                get_pd_nam_nva_with::<Hashed>(&pdag, &t, &z, None)
+            );
+            assert_eq!(
+                get_nam_nva_with::<BitSet>(&pdag, &t, &z, None),
+                get_nam_nva_with::<Hashed>(&pdag, &t, &z, None)
+            );
+            assert_eq!(
+                get_invalidly_un_blocked_with::<BitSet>(&pdag, &t, &z, None),
+                get_invalidly_un_blocked_with::<Hashed>(&pdag, &t, &z, None)
+            );
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            assert_eq!(
+                gensearch_with::<BitSet>(&dag, ruletables::Ancestors {}, t.iter(), false),
+                gensearch_with::<Hashed>(&dag, ruletables::Ancestors {}, t.iter(), false)
+            );
+        }
+    }
+
     #[test]
     pub fn reachability_algos_agree_on_random_pdag() {
         let reps = 30;
@@ -723,7 +913,7 @@ mod test {
 
         let d_expected = get_descendants(pdag, t.iter());
         let pd_expected = get_possible_descendants(pdag, t.iter());
-        let (nam_expected, nva_expected) = get_nam_nva(pdag, &t, &adjust);
+        let (nam_expected, nva_expected) = get_nam_nva(pdag, &t, &adjust, None);
 
         #[cfg(test)]
         assert!(d_expected.is_subset(&pd_expected));
@@ -737,14 +927,14 @@ mod test {
         assert_eq!(pd_expected, pd);
         assert_eq!(nam_expected, nam);
 
-        let (pd, nam) = super::get_pd_nam(pdag, &t);
+        let (pd, nam) = super::get_pd_nam(pdag, &t, None);
         assert_eq!(nam_expected, nam);
         assert_eq!(pd_expected, pd);
 
-        let nam = super::get_nam(pdag, &t);
+        let nam = super::get_nam(pdag, &t, None);
         assert_eq!(nam_expected, nam);
 
-        let (pd, nam, nva) = super::get_pd_nam_nva(pdag, &t, &adjust);
+        let (pd, nam, nva) = super::get_pd_nam_nva(pdag, &t, &adjust, None);
         assert_eq!(pd_expected, pd);
         assert_eq!(nam_expected, nam);
         assert_eq!(nva_expected, nva);
@@ -752,5 +942,30 @@ mod test {
         let ivb = super::get_invalidly_un_blocked(pdag, &t, &adjust, None);
         assert!(ivb.is_subset(&nva_expected));
         assert_eq!(nva_expected, &ivb | &nam_expected);
+
+        // searches that stop early must agree on the y of interest,
+        // both for y of interest that are all NAM (where the search stops early) and arbitrary ones
+        let some_nam = FxHashSet::from_iter(nam_expected.iter().copied().take(2));
+        let some_y = FxHashSet::from_iter(rand::seq::index::sample(&mut rng, 100, 5));
+        for y_of_interest in [&some_nam, &some_y] {
+            let restrict = |set: &FxHashSet<usize>| {
+                &(set & y_of_interest) - &FxHashSet::from_iter(t.iter().copied())
+            };
+            let (pd, nam) = super::get_pd_nam(pdag, &t, Some(y_of_interest));
+            assert_eq!(restrict(&pd_expected), restrict(&pd));
+            assert_eq!(restrict(&nam_expected), restrict(&nam));
+
+            let nam = super::get_nam(pdag, &t, Some(y_of_interest));
+            assert_eq!(restrict(&nam_expected), restrict(&nam));
+
+            let (pd, nam, nva) = super::get_pd_nam_nva(pdag, &t, &adjust, Some(y_of_interest));
+            assert_eq!(restrict(&pd_expected), restrict(&pd));
+            assert_eq!(restrict(&nam_expected), restrict(&nam));
+            assert_eq!(restrict(&nva_expected), restrict(&nva));
+
+            let (nam, nva) = get_nam_nva(pdag, &t, &adjust, Some(y_of_interest));
+            assert_eq!(restrict(&nam_expected), restrict(&nam));
+            assert_eq!(restrict(&nva_expected), restrict(&nva));
+        }
     }
 }
@@ -0,0 +1,277 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Finds a sequence of single-edge operations that transforms a guess DAG into the true DAG,
+//! to study how the Parent-AID changes on the way from a guess to the truth.
+//!
+//! Every operation fixes one pair of nodes whose edges differ, by deleting a superfluous edge,
+//! inserting a missing edge, or reversing a reversed edge, so that the SHD decreases by one per step.
+//! Among the operations that keep the guess acyclic, each step greedily picks the one that leads to
+//! the lowest Parent-AID. As in [`trajectory_aid`](super::trajectory_aid), an operation on the edge
+//! `from -> to` only changes the parents of `to`, and a reversal also those of `from`, so only these
+//! treatments are rescored for each candidate operation.
+
+use std::{error::Error, fmt};
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{selected_pairs::Effects, shd, Aid, EdgeOperation},
+    PDAG,
+};
+
+/// The reason no repair path can be found.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RepairPathError {
+    /// The truth graph has undirected edges
+    TruthNotDAG,
+    /// The guess graph has undirected edges
+    GuessNotDAG,
+}
+
+impl Error for RepairPathError {}
+
+impl fmt::Display for RepairPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepairPathError::TruthNotDAG => write!(f, "Truth graph is not a DAG"),
+            RepairPathError::GuessNotDAG => write!(f, "Guess graph is not a DAG"),
+        }
+    }
+}
+
+/// A sequence of edge operations from a guess DAG to the true DAG, as returned by [`repair_path`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepairPath {
+    /// the `(operation, from, to)` tuples of an edge `from -> to`, in the order they are applied,
+    /// which can be passed to [`trajectory_aid`](super::trajectory_aid)
+    pub operations: Vec<(EdgeOperation, usize, usize)>,
+    /// the SHD between the truth and the initial guess and each snapshot after an operation
+    pub shd: Vec<usize>,
+    /// the Parent-AID, a tuple of (normalized error (in \[0,1]), total number of errors),
+    /// between the truth and the initial guess and each snapshot after an operation
+    pub parent_aid: Vec<(f64, usize)>,
+}
+
+/// Finds a sequence of single-edge operations that transforms the `guess` DAG into the `truth` DAG,
+/// along which the SHD decreases by one per step and each step picks the operation with the lowest
+/// Parent-AID, see the [module documentation](self). If reversing an edge would create a cycle and
+/// no other operation applies, the edge is deleted first and inserted in its true direction later,
+/// which keeps the SHD for one step.
+/// Each step builds the snapshot of every candidate operation, so this takes time quadratic in the SHD.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{repair_path, EdgeOperation},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 1 -> 0, 0 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0, 1],
+///     vec![1, 0, 0],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let path = repair_path(&truth, &guess).unwrap();
+/// assert_eq!(
+///     path.operations,
+///     vec![
+///         (EdgeOperation::Reverse, 1, 0),
+///         (EdgeOperation::Insert, 1, 2),
+///         (EdgeOperation::Delete, 0, 2),
+///     ]
+/// );
+/// assert_eq!(path.shd, vec![3, 2, 1, 0]);
+/// // the superfluous edge 0 -> 2 is deleted last, once its deletion no longer changes the Parent-AID
+/// assert_eq!(
+///     Vec::from_iter(path.parent_aid.iter().map(|(_, mistakes)| *mistakes)),
+///     vec![4, 1, 0, 0]
+/// );
+/// ```
+pub fn repair_path(truth: &PDAG, guess: &PDAG) -> Result<RepairPath, RepairPathError> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(truth.n_nodes >= 2, "graph must contain at least 2 nodes");
+    if truth.n_undirected_edges > 0 {
+        return Err(RepairPathError::TruthNotDAG);
+    }
+    if guess.n_undirected_edges > 0 {
+        return Err(RepairPathError::GuessNotDAG);
+    }
+
+    crate::rayon::build_global();
+
+    let n = truth.n_nodes;
+    let all_nodes = Vec::from_iter(0..n);
+    let mistakes_of = |guess: &PDAG, treatment: usize| {
+        Aid::parent(truth, guess).mistakes(treatment, Effects::All(&all_nodes))
+    };
+    let edges_of = |graph: &PDAG| {
+        FxHashSet::from_iter((0..n).flat_map(|v| graph.children_of(v).iter().map(move |w| (v, *w))))
+    };
+    let truth_edges = edges_of(truth);
+    let mut directed = edges_of(guess);
+
+    let mut mistakes: Vec<usize> = all_nodes
+        .par_iter()
+        .map(|t| mistakes_of(guess, *t))
+        .collect();
+    let comparisons = n * n - n;
+    let aid = |total: usize| (total as f64 / comparisons as f64, total);
+    let mut shd = shd(truth, guess).1;
+    let mut path = RepairPath {
+        operations: Vec::with_capacity(shd),
+        shd: vec![shd],
+        parent_aid: vec![aid(mistakes.iter().sum())],
+    };
+
+    while directed != truth_edges {
+        let mut candidates = Vec::from_iter(directed.iter().filter_map(|&(from, to)| {
+            match truth_edges.contains(&(to, from)) {
+                true => Some((EdgeOperation::Reverse, from, to)),
+                false if !truth_edges.contains(&(from, to)) => {
+                    Some((EdgeOperation::Delete, from, to))
+                }
+                false => None,
+            }
+        }));
+        candidates.extend(
+            truth_edges
+                .iter()
+                .filter(|(from, to)| {
+                    !directed.contains(&(*from, *to)) && !directed.contains(&(*to, *from))
+                })
+                .map(|&(from, to)| (EdgeOperation::Insert, from, to)),
+        );
+        candidates.sort_unstable_by_key(|(_, from, to)| (*from, *to));
+
+        // the candidate with the fewest mistakes, and its rescored treatments, ties broken by the order above
+        let best = candidates
+            .par_iter()
+            .filter_map(|&(operation, from, to)| {
+                let snapshot = apply(n, &directed, operation, from, to)?;
+                let changed = match operation {
+                    EdgeOperation::Reverse => vec![from, to],
+                    _ => vec![to],
+                };
+                let rescored =
+                    Vec::from_iter(changed.iter().map(|t| (*t, mistakes_of(&snapshot, *t))));
+                let total: usize = mistakes.iter().sum::<usize>()
+                    - changed.iter().map(|t| mistakes[*t]).sum::<usize>()
+                    + rescored.iter().map(|(_, m)| m).sum::<usize>();
+                Some((total, (operation, from, to), rescored))
+            })
+            .min_by_key(|(total, (_, from, to), _)| (*total, *from, *to));
+
+        let ((operation, from, to), rescored) = match best {
+            Some((_, operation, rescored)) => {
+                shd -= 1;
+                (operation, rescored)
+            }
+            None => {
+                // every reversal creates a cycle, so delete a reversed edge without fixing its pair yet
+                let (_, from, to) = *candidates
+                    .iter()
+                    .find(|(operation, _, _)| *operation == EdgeOperation::Reverse)
+                    .expect(
+                        "inserting an edge of the truth into a subgraph of it keeps it acyclic",
+                    );
+                let snapshot = apply(n, &directed, EdgeOperation::Delete, from, to)
+                    .expect("deleting an edge keeps the graph acyclic");
+                (
+                    (EdgeOperation::Delete, from, to),
+                    vec![(to, mistakes_of(&snapshot, to))],
+                )
+            }
+        };
+        match operation {
+            EdgeOperation::Insert => {
+                directed.insert((from, to));
+            }
+            EdgeOperation::Delete => {
+                directed.remove(&(from, to));
+            }
+            EdgeOperation::Reverse => {
+                directed.remove(&(from, to));
+                directed.insert((to, from));
+            }
+        }
+        for (treatment, treatment_mistakes) in rescored {
+            mistakes[treatment] = treatment_mistakes;
+        }
+        path.operations.push((operation, from, to));
+        path.shd.push(shd);
+        path.parent_aid.push(aid(mistakes.iter().sum()));
+    }
+    Ok(path)
+}
+
+/// The snapshot after the `operation` on the edge `from -> to` of the `directed` edges,
+/// or `None` if it has a cycle.
+fn apply(
+    n: usize,
+    directed: &FxHashSet<(usize, usize)>,
+    operation: EdgeOperation,
+    from: usize,
+    to: usize,
+) -> Option<PDAG> {
+    let kept = directed.iter().filter(|edge| **edge != (from, to));
+    let added = match operation {
+        EdgeOperation::Insert => Some((from, to)),
+        EdgeOperation::Delete => None,
+        EdgeOperation::Reverse => Some((to, from)),
+    };
+    let edges = kept.chain(added.iter()).map(|(v, w)| (*v, *w, 1));
+    PDAG::try_from_edges(n, edges).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{parent_aid, shd, trajectory_aid},
+        PDAG,
+    };
+
+    use super::{repair_path, RepairPathError};
+
+    #[test]
+    fn property_repair_path_reaches_truth() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..15 {
+            let truth = PDAG::random_dag(0.3, n, &mut rng);
+            let guess = PDAG::random_dag(0.3, n, &mut rng);
+            let path = repair_path(&truth, &guess).unwrap();
+
+            assert_eq!(path.shd.len(), path.operations.len() + 1);
+            assert_eq!(path.shd[0], shd(&truth, &guess).1);
+            assert_eq!(*path.shd.last().unwrap(), 0);
+            assert!(path.shd.windows(2).all(|w| w[1] <= w[0]));
+
+            // the incrementally updated distances agree with rescoring every snapshot
+            assert_eq!(
+                path.parent_aid,
+                trajectory_aid(&truth, &guess, &path.operations).unwrap()
+            );
+            assert_eq!(path.parent_aid[0], parent_aid(&truth, &guess));
+            assert_eq!(*path.parent_aid.last().unwrap(), (0.0, 0));
+        }
+    }
+
+    #[test]
+    fn rejects_cpdags() {
+        let dag = PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]);
+        let cpdag = PDAG::from_dense_row_to_col(vec![vec![0, 2], vec![0, 0]]);
+        assert_eq!(repair_path(&cpdag, &dag), Err(RepairPathError::TruthNotDAG));
+        assert_eq!(repair_path(&dag, &cpdag), Err(RepairPathError::GuessNotDAG));
+        assert_eq!(repair_path(&dag, &dag).unwrap().operations, vec![]);
+    }
+}
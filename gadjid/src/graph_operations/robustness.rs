@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Studies how stable a distance is when nodes are missing, by recomputing it between
+//! the subgraphs that remain after dropping random subsets of nodes from both graphs
+
+use rand::{RngCore, SeedableRng};
+use rayon::prelude::*;
+
+use crate::PDAG;
+
+/// The distances after dropping a fraction of the nodes, one point of the curve
+/// returned by [`node_dropout_curve`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropoutPoint {
+    /// the fraction of the nodes that is dropped
+    pub fraction: f64,
+    /// the number of nodes that is dropped, the fraction of the nodes rounded to the nearest integer
+    pub n_dropped: usize,
+    /// the mean normalized distance between the remaining subgraphs
+    pub mean: f64,
+    /// the (population) standard deviation of the normalized distances between the remaining subgraphs
+    pub standard_deviation: f64,
+}
+
+/// Computes the `distance` between the subgraphs of the `truth` and the `guess` graph that remain
+/// after dropping a random subset of the nodes from both, `n_repetitions` times for each of the
+/// `fractions` of dropped nodes, and returns the mean and the standard deviation of the normalized distances
+/// for each fraction, a robustness curve of how much the distance depends on which nodes are measured.
+///
+/// The subsets are sampled from random number generators seeded by `rng`, one per repetition,
+/// so that the curve only depends on `rng` and the repetitions can be scored in parallel.
+/// Panics unless each fraction is in \[0,1] and leaves at least 2 nodes.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{node_dropout_curve, shd},
+///     PDAG,
+/// };
+/// use rand::SeedableRng;
+///
+/// // truth: 0 -> 1 -> 2 -> 3, guess: 0 -> 1 <- 2 -> 3
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 1, 0],
+///     vec![0, 0, 0, 1],
+///     vec![0, 0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 1, 0, 1],
+///     vec![0, 0, 0, 0],
+/// ]);
+///
+/// let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+/// let curve = node_dropout_curve(&truth, &guess, &[0.0, 0.5], 20, shd, rng);
+/// // dropping no nodes gives the distance between the whole graphs every time
+/// assert!((curve[0].mean - shd(&truth, &guess).0).abs() < 1e-12);
+/// assert!(curve[0].standard_deviation < 1e-12);
+/// assert_eq!(curve[1].n_dropped, 2);
+/// ```
+pub fn node_dropout_curve(
+    truth: &PDAG,
+    guess: &PDAG,
+    fractions: &[f64],
+    n_repetitions: usize,
+    distance: impl Fn(&PDAG, &PDAG) -> (f64, usize) + Sync,
+    mut rng: impl RngCore,
+) -> Vec<DropoutPoint> {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(n_repetitions > 0, "there must be at least one repetition");
+    let n = truth.n_nodes;
+    let n_dropped = Vec::from_iter(fractions.iter().map(|fraction| {
+        assert!(
+            (0.0..=1.0).contains(fraction),
+            "dropout fraction must be in [0, 1]"
+        );
+        let n_dropped = (fraction * n as f64).round() as usize;
+        assert!(n - n_dropped >= 2, "dropout must leave at least 2 nodes");
+        n_dropped
+    }));
+    let repetitions = Vec::from_iter(
+        n_dropped
+            .iter()
+            .flat_map(|n_dropped| (0..n_repetitions).map(move |_| *n_dropped))
+            .map(|n_dropped| (n_dropped, rng.next_u64())),
+    );
+
+    crate::rayon::build_global();
+
+    let samples: Vec<f64> = repetitions
+        .into_par_iter()
+        .map(|(n_dropped, seed)| {
+            let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+            let mut kept = rand::seq::index::sample(&mut rng, n, n - n_dropped).into_vec();
+            kept.sort_unstable();
+            distance(
+                &truth.induced_subgraph(&kept),
+                &guess.induced_subgraph(&kept),
+            )
+            .0
+        })
+        .collect();
+
+    Vec::from_iter(
+        fractions
+            .iter()
+            .zip(n_dropped)
+            .zip(samples.chunks(n_repetitions))
+            .map(|((fraction, n_dropped), samples)| {
+                let mean = samples.iter().sum::<f64>() / n_repetitions as f64;
+                let variance =
+                    samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n_repetitions as f64;
+                DropoutPoint {
+                    fraction: *fraction,
+                    n_dropped,
+                    mean,
+                    standard_deviation: variance.sqrt(),
+                }
+            }),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{oset_aid, shd},
+        PDAG,
+    };
+
+    use super::node_dropout_curve;
+
+    #[test]
+    fn computes_one_point_per_fraction() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_dag(0.3, 15, &mut rng);
+        let guess = PDAG::random_dag(0.3, 15, &mut rng);
+        let fractions = [0.0, 0.2, 0.5, 0.8];
+
+        let curve = node_dropout_curve(&truth, &guess, &fractions, 30, oset_aid, &mut rng);
+        assert_eq!(
+            Vec::from_iter(curve.iter().map(|point| point.n_dropped)),
+            vec![0, 3, 8, 12]
+        );
+        assert!((curve[0].mean - oset_aid(&truth, &guess).0).abs() < 1e-12);
+        assert!(curve[0].standard_deviation < 1e-12);
+        for point in &curve {
+            assert!((0.0..=1.0).contains(&point.mean));
+            assert!(point.standard_deviation >= 0.0);
+        }
+
+        // the same seed gives the same curve
+        let seeded = || rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        assert_eq!(
+            node_dropout_curve(&truth, &guess, &fractions, 30, shd, seeded()),
+            node_dropout_curve(&truth, &guess, &fractions, 30, shd, seeded())
+        );
+        // identical graphs stay identical after dropping nodes
+        for point in node_dropout_curve(&truth, &truth, &fractions, 10, shd, seeded()) {
+            assert_eq!((point.mean, point.standard_deviation), (0.0, 0.0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "dropout must leave at least 2 nodes")]
+    fn panics_if_too_few_nodes_remain() {
+        let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let dag = PDAG::random_dag(0.3, 4, rng.clone());
+        node_dropout_curve(&dag, &dag, &[0.9], 10, shd, rng);
+    }
+}
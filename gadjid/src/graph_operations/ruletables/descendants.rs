@@ -48,7 +48,7 @@ mod test {
             vec![0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let expected = HashSet::from([1, 2]);
         let result = get_descendants(&dag, [2, 1].iter());
@@ -78,7 +78,7 @@ mod test {
             vec![0, 0, 1, 1, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let expected = HashSet::from([2, 3]);
         let result = get_descendants(&dag, [2].iter());
@@ -6,7 +6,9 @@ pub mod ruletable;
 
 // implementations of the ruletable trait
 pub mod ancestors;
+#[cfg(test)]
 pub mod children;
+#[cfg(test)]
 pub mod descendants;
 pub mod parents;
 pub mod proper_ancestors;
@@ -37,7 +37,7 @@ mod test {
             vec![0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let result = get_parents(&dag, [0].iter());
         let expected = HashSet::from([]);
@@ -67,7 +67,7 @@ mod test {
             vec![0, 0, 1, 1, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let result = get_parents(&dag, [4].iter());
         let expected = HashSet::from([]);
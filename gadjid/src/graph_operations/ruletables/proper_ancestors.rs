@@ -60,7 +60,7 @@ mod test {
             vec![0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let result = get_proper_ancestors(&dag, [].iter(), [2].iter());
         let expected = HashSet::from([0, 1, 2]);
@@ -82,7 +82,7 @@ mod test {
             vec![0, 0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let result = get_proper_ancestors(&dag, [].iter(), [3].iter());
         let expected = HashSet::from([0, 1, 2, 3]);
@@ -100,7 +100,7 @@ mod test {
             vec![0, 0, 0, 0, 1],
             vec![0, 0, 0, 0, 0],
         ];
-        let dag = PDAG::from_row_to_column_vecvec(v_dag);
+        let dag = PDAG::from_dense_row_to_col(v_dag);
 
         let result = get_proper_ancestors(&dag, [].iter(), [4].iter());
         let expected = HashSet::from([0, 1, 2, 3, 4]);
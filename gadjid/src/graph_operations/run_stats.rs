@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Collects timing and work counters of the adjustment identification distances,
+//! to understand how they scale on given graphs
+
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput, TwinSink},
+    selected_pairs::{MistakeSink, Verdict},
+};
+
+thread_local! {
+    /// The number of (edge, node, walk status) triplets visited on this thread so far,
+    /// which the searches of a single treatment only ever increase from a single thread.
+    static VISITED_TRIPLETS: Cell<usize> = const { Cell::new(0) };
+    /// Whether the searches on this thread count their visited triplets, which [`StatsSink`]
+    /// turns on around the searches of each treatment, so that the other distances do not count.
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the searches on this thread count their visited triplets, which they check once per search
+/// to select a counting set backend.
+pub(crate) fn recording() -> bool {
+    RECORDING.with(Cell::get)
+}
+
+/// Counts a newly visited (edge, node, walk status) triplet of a reachability algorithm.
+pub(crate) fn count_visited_triplet() {
+    VISITED_TRIPLETS.with(|visited| visited.set(visited.get() + 1));
+}
+
+/// The number of visited triplets counted on this thread so far.
+pub(crate) fn visited_triplets_on_this_thread() -> usize {
+    VISITED_TRIPLETS.with(Cell::get)
+}
+
+/// The timing and work counters of a single call of a distance,
+/// as returned alongside the distance for the [`WithStats`] output.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunStats {
+    /// the wall time of the whole call
+    pub wall_time: Duration,
+    /// the (sorted) treatments searched from, alongside the time spent on the mistakes of each;
+    /// isolated nodes and all but one node per class of twins are skipped,
+    /// since their mistakes are known without searching
+    pub treatment_times: Vec<(usize, Duration)>,
+    /// the number of (edge, node, walk status) triplets visited by the reachability algorithms
+    /// (counting nodes for the search of non-amenable nodes only),
+    /// which does not depend on the number of threads
+    pub visited_triplets: usize,
+}
+
+/// The distance alongside its [`RunStats`], the wall time, the time spent on each treatment,
+/// and the number of visited (edge, node, walk status) triplets of the call,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid, ancestor_aid_with, WithStats}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let (distance, stats) = ancestor_aid_with(&truth, &guess, WithStats);
+/// assert_eq!(distance, ancestor_aid(&truth, &guess));
+/// assert_eq!(stats.treatment_times.len(), 3);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WithStats;
+
+impl AidOutput for WithStats {
+    type Output = ((f64, usize), RunStats);
+
+    fn compute(self, aid: &Aid) -> ((f64, usize), RunStats) {
+        let start = Instant::now();
+        let mut stats: StatsSink = aid.fold_twins();
+        stats.treatment_times.sort_unstable();
+        (
+            (stats.mistakes as f64 / aid.n_pairs() as f64, stats.mistakes),
+            RunStats {
+                wall_time: start.elapsed(),
+                treatment_times: stats.treatment_times,
+                visited_triplets: stats.visited_triplets,
+            },
+        )
+    }
+}
+
+/// Counts the mistakes and times the searches of the treatments it records the verdicts of.
+#[derive(Default)]
+struct StatsSink {
+    mistakes: usize,
+    treatment_times: Vec<(usize, Duration)>,
+    visited_triplets: usize,
+}
+
+impl MistakeSink for StatsSink {
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict) {
+        self.mistakes.record(treatments, effects, verdict);
+    }
+}
+
+impl TwinSink for StatsSink {
+    fn scaled(self, n_twins: usize) -> StatsSink {
+        StatsSink {
+            mistakes: n_twins * self.mistakes,
+            ..self
+        }
+    }
+
+    fn add(mut self, other: StatsSink) -> StatsSink {
+        self.mistakes += other.mistakes;
+        self.treatment_times.extend(other.treatment_times);
+        self.visited_triplets += other.visited_triplets;
+        self
+    }
+
+    fn search(&mut self, treatment: usize, search: impl FnOnce(&mut Self)) {
+        let (treatment_start, visited_before) = (Instant::now(), visited_triplets_on_this_thread());
+        let recorded_before = RECORDING.with(|recording| recording.replace(true));
+        search(self);
+        RECORDING.with(|recording| recording.set(recorded_before));
+        self.visited_triplets += visited_triplets_on_this_thread() - visited_before;
+        self.treatment_times
+            .push((treatment, treatment_start.elapsed()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            WithStats,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn stats_count_searched_treatments() {
+        // truth: 0 -> 1 -> 2, 3 isolated
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0], //
+            vec![0, 0, 1, 0],
+            vec![0, 0, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+        let (distance, stats) = parent_aid_with(&truth, &truth, WithStats);
+        assert_eq!(distance, (0.0, 0));
+        let treatments = Vec::from_iter(stats.treatment_times.iter().map(|(t, _)| *t));
+        assert_eq!(treatments, vec![0, 1, 2]);
+        assert!(stats.visited_triplets > 0);
+        assert!(stats
+            .treatment_times
+            .iter()
+            .all(|(_, time)| *time <= stats.wall_time));
+    }
+
+    #[test]
+    fn property_stats_do_not_change_distances() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let (distance, stats) = ancestor_aid_with(&truth, &guess, WithStats);
+            assert_eq!(distance, ancestor_aid(&truth, &guess));
+            // the work does not depend on the scheduling of the treatments
+            assert_eq!(
+                stats.visited_triplets,
+                ancestor_aid_with(&truth, &guess, WithStats)
+                    .1
+                    .visited_triplets
+            );
+            assert_eq!(
+                oset_aid_with(&truth, &guess, WithStats).0,
+                oset_aid(&truth, &guess)
+            );
+            assert_eq!(
+                parent_aid_with(&truth, &guess, WithStats).0,
+                parent_aid(&truth, &guess)
+            );
+        }
+    }
+}
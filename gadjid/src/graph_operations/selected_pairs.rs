@@ -0,0 +1,907 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Helpers for counting the mistakes of the adjustment identification distances,
+//! over all or only selected (treatment, effect) pairs.
+
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hash},
+};
+
+use rayon::prelude::*;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput, TwinSink},
+    twins::is_isolated,
+};
+
+/// A distance restricted to selected `(treatment, effect)` pairs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelectedPairsDistance {
+    /// The number of mistakes divided by the number of comparisons, in \[0,1].
+    pub normalized: f64,
+    /// The number of pairs that are mistakes.
+    pub mistakes: usize,
+    /// The number of distinct selected pairs, which the distance is normalized by.
+    pub comparisons: usize,
+    /// The number of selected pairs that repeat an earlier pair and are only counted once.
+    pub duplicates: usize,
+    /// The number of (treatment, effect) pairs of distinct nodes in the graphs, n² − n,
+    /// which the distances over all pairs are normalized by.
+    pub population: usize,
+}
+
+/// What the mistakes on selected pairs are normalized by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairsNormalization {
+    /// the number of distinct selected pairs, so that the distance is the fraction of selected pairs
+    /// that are mistakes
+    Selected,
+    /// the number of (treatment, effect) pairs in the graphs, n² − n, so that the distance is on
+    /// the same scale as the distances over all pairs and the distances of disjoint selections add up
+    Population,
+}
+
+impl SelectedPairsDistance {
+    pub(crate) fn new(
+        mistakes: usize,
+        comparisons: usize,
+        n_selected: usize,
+        n_nodes: usize,
+    ) -> Self {
+        SelectedPairsDistance {
+            normalized: mistakes as f64 / comparisons as f64,
+            mistakes,
+            comparisons,
+            duplicates: n_selected - comparisons,
+            population: n_nodes * n_nodes - n_nodes,
+        }
+    }
+
+    /// Returns the tuple of (normalized error, total number of errors), where the errors are
+    /// normalized as given by `normalization`.
+    ///
+    /// ```
+    /// use gadjid::{
+    ///     graph_operations::{ancestor_aid, ancestor_aid_with, PairsNormalization, SelectedPairs},
+    ///     PDAG,
+    /// };
+    ///
+    /// // truth: 0 -> 1, guess: 0 <- 1
+    /// let truth = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1], //
+    ///     vec![0, 0],
+    /// ]);
+    /// let guess = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0], //
+    ///     vec![1, 0],
+    /// ]);
+    /// let selected = ancestor_aid_with(&truth, &guess, SelectedPairs(&[(0, 1)])).unwrap();
+    /// assert_eq!(selected.normalized_by(PairsNormalization::Selected), (1.0, 1));
+    /// // 1 of the 2 pairs of the graphs
+    /// assert_eq!(selected.normalized_by(PairsNormalization::Population), (0.5, 1));
+    /// assert_eq!(ancestor_aid(&truth, &guess).0, 1.0);
+    /// ```
+    pub fn normalized_by(&self, normalization: PairsNormalization) -> (f64, usize) {
+        match normalization {
+            PairsNormalization::Selected => (self.normalized, self.mistakes),
+            PairsNormalization::Population => {
+                (self.mistakes as f64 / self.population as f64, self.mistakes)
+            }
+        }
+    }
+
+    /// Returns the tuple of (normalized error (in \[0,1]), total number of errors),
+    /// as returned by the distances over all pairs.
+    ///
+    /// ```
+    /// use gadjid::{graph_operations::{ancestor_aid_with, SelectedPairs}, PDAG};
+    ///
+    /// // truth: 0 -> 1, guess: 0 <- 1
+    /// let truth = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1], //
+    ///     vec![0, 0],
+    /// ]);
+    /// let guess = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0], //
+    ///     vec![1, 0],
+    /// ]);
+    /// let selected = ancestor_aid_with(&truth, &guess, SelectedPairs(&[(0, 1)])).unwrap();
+    /// assert_eq!(selected.distance(), (1.0, 1));
+    /// ```
+    pub fn distance(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+}
+
+/// Returns the `(treatment, effect)` pairs of each node selected by the `treatments` mask
+/// and each other node selected by the `effects` mask, ordered by treatment and then by effect,
+/// to select pairs on large graphs by per-node masks instead of listing them.
+/// Both masks have one entry per node; a longer mask selects nodes out of bounds.
+///
+/// ```
+/// use gadjid::graph_operations::pairs_from_masks;
+///
+/// let treatments = [true, true, false];
+/// let effects = [false, true, true];
+/// assert_eq!(
+///     pairs_from_masks(&treatments, &effects),
+///     vec![(0, 1), (0, 2), (1, 2)]
+/// );
+/// ```
+pub fn pairs_from_masks(treatments: &[bool], effects: &[bool]) -> Vec<(usize, usize)> {
+    let effects = Vec::from_iter((0..effects.len()).filter(|y| effects[*y]));
+    let treatments = (0..treatments.len()).filter(|t| treatments[*t]);
+    Vec::from_iter(treatments.flat_map(|t| {
+        effects
+            .iter()
+            .filter(move |y| **y != t)
+            .map(move |y| (t, *y))
+    }))
+}
+
+#[derive(Debug, PartialEq)]
+/// Error that can occur when selecting `(treatment, effect)` pairs.
+pub enum PairsError {
+    /// No pair is selected, so there is nothing to normalize by.
+    NoPairs,
+    /// A pair refers to a node that is not in the graphs.
+    OutOfBounds {
+        /// the offending node
+        node: usize,
+        /// the number of nodes of the graphs
+        n_nodes: usize,
+    },
+    /// A pair has the same node as treatment and effect.
+    TreatmentIsEffect {
+        /// the node that is both treatment and effect
+        node: usize,
+    },
+    /// A pair has an empty set of treatments.
+    EmptyTreatmentSet,
+    /// A pair has an empty set of effects.
+    EmptyEffectSet,
+    /// A pair is conditioned on its own treatment or effect.
+    ConditionsOnPair {
+        /// the node that is both in the pair and in its conditioning set
+        node: usize,
+    },
+}
+
+impl Error for PairsError {}
+
+impl fmt::Display for PairsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PairsError::NoPairs => write!(f, "at least one pair must be selected"),
+            PairsError::OutOfBounds { node, n_nodes } => write!(
+                f,
+                "node {node} is out of bounds for graphs with {n_nodes} nodes"
+            ),
+            PairsError::TreatmentIsEffect { node } => {
+                write!(f, "node {node} is both treatment and effect of a pair")
+            }
+            PairsError::EmptyTreatmentSet => write!(f, "a pair has no treatments"),
+            PairsError::EmptyEffectSet => write!(f, "a pair has no effects"),
+            PairsError::ConditionsOnPair { node } => {
+                write!(
+                    f,
+                    "node {node} is both in a pair and in its conditioning set"
+                )
+            }
+        }
+    }
+}
+
+/// Only counts the mistakes for the selected `(treatment, effect)` pairs
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+/// The output is the number of mistakes alongside the number of distinct pairs it is normalized by,
+/// counting repeated pairs once, or an error if no pair is selected, a node is out of bounds,
+/// or a treatment equals its effect.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, SelectedPairs}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let pairs = [(0, 2), (2, 0), (0, 2)];
+/// let selected = ancestor_aid_with(&truth, &guess, SelectedPairs(&pairs)).unwrap();
+/// assert_eq!(selected.distance(), (0.5, 1));
+/// assert_eq!((selected.comparisons, selected.duplicates), (2, 1));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SelectedPairs<'a>(pub &'a [(usize, usize)]);
+
+impl AidOutput for SelectedPairs<'_> {
+    type Output = Result<SelectedPairsDistance, PairsError>;
+
+    fn compute(self, aid: &Aid) -> Result<SelectedPairsDistance, PairsError> {
+        let (truth, guess, pairs) = (aid.truth(), aid.guess(), self.0);
+        assert!(
+            guess.n_nodes == truth.n_nodes,
+            "both graphs must contain the same number of nodes"
+        );
+        let (effects_of_treatments, n_pairs) =
+            group_by_treatment(guess.n_nodes, pairs, |v| is_isolated(truth, guess, v))?;
+
+        crate::rayon::build_global();
+
+        let verifier_mistakes_found = effects_of_treatments
+            .par_iter()
+            .map(|(treatment, effects)| aid.mistakes(*treatment, Effects::Selected(effects)))
+            .sum();
+
+        Ok(SelectedPairsDistance::new(
+            verifier_mistakes_found,
+            n_pairs,
+            pairs.len(),
+            truth.n_nodes,
+        ))
+    }
+}
+
+/// Only counts the mistakes for the selected `(treatment, effects)` pairs, each of which grades
+/// the joint effect of the treatment on a set of effects, when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+/// Such a pair is a mistake if the guess graph misses a possible effect in the set,
+/// disagrees with the true graph on whether the possible effects are jointly amenable,
+/// or if its adjustment set is not valid in the true graph relative to every one of them.
+/// For sets of a single effect, this agrees with [`SelectedPairs`].
+/// The output is the number of mistakes alongside the number of distinct pairs it is normalized by,
+/// counting repeated pairs once (irrespective of the order of the effects),
+/// or an error if no pair is selected, a set of effects is empty, a node is out of bounds,
+/// or a set of effects contains its treatment.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid_with, SelectedEffectSets}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the guess misses that 2 is a descendant of 0
+/// let pairs = [(0, vec![1, 2]), (1, vec![0])];
+/// let selected = ancestor_aid_with(&truth, &guess, SelectedEffectSets(&pairs)).unwrap();
+/// assert_eq!(selected.distance(), (0.5, 1));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SelectedEffectSets<'a>(pub &'a [(usize, Vec<usize>)]);
+
+impl AidOutput for SelectedEffectSets<'_> {
+    type Output = Result<SelectedPairsDistance, PairsError>;
+
+    fn compute(self, aid: &Aid) -> Result<SelectedPairsDistance, PairsError> {
+        let (truth, guess, pairs) = (aid.truth(), aid.guess(), self.0);
+        assert!(
+            guess.n_nodes == truth.n_nodes,
+            "both graphs must contain the same number of nodes"
+        );
+        let (effect_sets_of_treatments, n_pairs) =
+            group_by_treatment_with_effect_sets(guess.n_nodes, pairs, |v| {
+                is_isolated(truth, guess, v)
+            })?;
+
+        crate::rayon::build_global();
+
+        let verifier_mistakes_found = effect_sets_of_treatments
+            .par_iter()
+            .map(|(treatment, effect_sets)| {
+                let sets = Vec::from_iter(effect_sets.iter().cloned());
+                let union = FxHashSet::from_iter(sets.iter().flatten().copied());
+                let effects = Effects::Sets {
+                    sets: &sets,
+                    union: &union,
+                };
+                aid.mistakes(*treatment, effects)
+            })
+            .sum();
+
+        Ok(SelectedPairsDistance::new(
+            verifier_mistakes_found,
+            n_pairs,
+            pairs.len(),
+            truth.n_nodes,
+        ))
+    }
+}
+
+/// The effects over which the mistakes of a single treatment are counted.
+pub(crate) enum Effects<'a> {
+    /// All of the given nodes, except the treatments themselves.
+    All(&'a [usize]),
+    /// Only the selected effects; searches may stop early once these are resolved.
+    Selected(&'a FxHashSet<usize>),
+    /// Only the selected sets of effects, each graded as one joint effect;
+    /// searches may stop early once all effects in the sets are resolved.
+    Sets {
+        /// the sets of effects
+        sets: &'a [Vec<usize>],
+        /// all effects in any of the sets
+        union: &'a FxHashSet<usize>,
+    },
+}
+
+impl Effects<'_> {
+    /// The effects of interest at which searches may stop early, if any.
+    pub(crate) fn of_interest(&self) -> Option<&FxHashSet<usize>> {
+        match self {
+            Effects::All(_) => None,
+            Effects::Selected(effects) => Some(effects),
+            Effects::Sets { union, .. } => Some(union),
+        }
+    }
+
+    /// Records the verdict on each effect y (or set of effects) of the treatments into the `sink`.
+    pub(crate) fn record_verdicts(
+        &self,
+        treatments: &[usize],
+        verdict: impl Fn(&[usize]) -> Verdict,
+        sink: &mut impl MistakeSink,
+    ) {
+        let mut record = |ys: &[usize]| sink.record(treatments, ys, verdict(ys));
+        match self {
+            // the case y in treatments is always correct
+            Effects::All(nodes) => nodes
+                .iter()
+                .filter(|y| !treatments.contains(y))
+                .for_each(|y| record(std::slice::from_ref(y))),
+            Effects::Selected(effects) => {
+                effects.iter().for_each(|y| record(std::slice::from_ref(y)))
+            }
+            Effects::Sets { sets, .. } => sets.iter().for_each(|ys| record(ys)),
+        }
+    }
+}
+
+/// Accumulates the verdicts of a traversal over the effects of some treatments,
+/// so that each output of the distances, such as a count or a weighted sum of the mistakes,
+/// shares the same traversal.
+///
+/// Implement it to compute an output of the distances that this crate does not provide,
+/// by recording the verdicts of [`Aid::record`](super::Aid::record) from an [`AidOutput`](super::AidOutput),
+/// such as collecting the mistakes as the [`WitnessCollector`](super::WitnessCollector) does.
+pub trait MistakeSink {
+    /// Records the `verdict` on the claim about the effect of the `treatments` on the `effects`.
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict);
+
+    /// Records the nodes whose effect of the `treatments` is not amenable in the true graph (`nam`),
+    /// and those for which the adjustment set of the guess graph is not valid in the true graph (`nva`),
+    /// as the traversal materializes them; sinks that only need the verdicts ignore them.
+    fn record_truth_sets<S: BuildHasher>(
+        &mut self,
+        _treatments: &[usize],
+        _nam: &HashSet<usize, S>,
+        _nva: &HashSet<usize, S>,
+    ) {
+    }
+}
+
+/// Counts the mistakes.
+impl MistakeSink for usize {
+    fn record(&mut self, _: &[usize], _: &[usize], verdict: Verdict) {
+        if verdict != Verdict::Correct {
+            *self += 1;
+        }
+    }
+}
+
+/// The verdict on the claim of the guess graph about the effect of the treatments on some effects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Verdict {
+    /// The claim is correct.
+    Correct,
+    /// The guess graph claims that an effect is not a possible effect, which the true graph contradicts,
+    /// so the causal order is wrong.
+    WrongOrder,
+    /// The guess graph claims that the effect is amenable, which the true graph contradicts.
+    AmenableInGuess,
+    /// The guess graph claims that the effect is not amenable, which the true graph contradicts;
+    /// a "don't know" answer that partial credit may be given for.
+    NotAmenableInGuess,
+    /// Both graphs claim that the effect is amenable, but the guess graph's adjustment set
+    /// is not valid in the true graph.
+    WronglyAdjusted,
+}
+
+/// The number of mistakes of a treatment, split by whether the guess graph answered "don't know".
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Mistakes {
+    /// mistakes for which the guess graph claims a wrong possible effect or adjustment set
+    pub(crate) definite: usize,
+    /// mistakes for which the guess graph wrongly claims that the effect is not amenable
+    pub(crate) not_amenable_in_guess: usize,
+}
+
+impl Mistakes {
+    /// The number of mistakes if each "don't know" answer earns a `partial_credit` in \[0,1].
+    pub(crate) fn with_partial_credit(&self, partial_credit: f64) -> f64 {
+        self.definite as f64 + (1.0 - partial_credit) * self.not_amenable_in_guess as f64
+    }
+}
+
+impl TwinSink for Mistakes {
+    fn scaled(self, n_twins: usize) -> Mistakes {
+        Mistakes {
+            definite: n_twins * self.definite,
+            not_amenable_in_guess: n_twins * self.not_amenable_in_guess,
+        }
+    }
+
+    fn add(self, other: Mistakes) -> Mistakes {
+        Mistakes {
+            definite: self.definite + other.definite,
+            not_amenable_in_guess: self.not_amenable_in_guess + other.not_amenable_in_guess,
+        }
+    }
+}
+
+impl MistakeSink for Mistakes {
+    fn record(&mut self, _: &[usize], _: &[usize], verdict: Verdict) {
+        match verdict {
+            Verdict::Correct => (),
+            Verdict::NotAmenableInGuess => self.not_amenable_in_guess += 1,
+            Verdict::WrongOrder | Verdict::AmenableInGuess | Verdict::WronglyAdjusted => {
+                self.definite += 1
+            }
+        }
+    }
+}
+
+/// Sums the weights of the mistakes, where the weight of a mistake is the sum of the `weight`
+/// of each of its `(treatment, effect)` pairs.
+pub(crate) struct WeightedMistakes<W> {
+    /// the weight of a `(treatment, effect)` pair
+    pub(crate) weight: W,
+    /// the sum of the weights of the mistakes recorded so far
+    pub(crate) sum: usize,
+}
+
+impl<W: Fn(usize, usize) -> usize> MistakeSink for WeightedMistakes<W> {
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict) {
+        if verdict != Verdict::Correct {
+            for t in treatments {
+                self.sum += effects.iter().map(|y| (self.weight)(*t, *y)).sum::<usize>();
+            }
+        }
+    }
+}
+
+/// What the guess and the true graph say about the effects of the treatments,
+/// which decides whether the claims of the guess graph are mistakes.
+pub(crate) struct Claims<'a, C, W> {
+    /// Whether the guess graph claims that y may be an effect of the treatments.
+    pub(crate) is_claimed_effect: C,
+    /// Possible descendants of the treatments in the true graph.
+    pub(crate) poss_desc_in_truth: &'a FxHashSet<usize>,
+    /// Effects y such that the guess graph is not amenable relative to (treatments, y).
+    pub(crate) nam_in_guess: &'a FxHashSet<usize>,
+    /// Effects y such that the true graph is not amenable relative to (treatments, y).
+    pub(crate) nam_in_true: &'a FxHashSet<usize>,
+    /// Whether the guess graph's adjustment for the given claimed effects, which are
+    /// amenable in both graphs, is a mistake.
+    pub(crate) is_wrongly_adjusted: W,
+}
+
+impl<C, W> Claims<'_, C, W>
+where
+    C: Fn(usize) -> bool,
+    W: Fn(&[usize]) -> bool,
+{
+    /// The verdict on the guess graph's claim about the joint effect of the treatments on `effects`,
+    /// which tells apart the causes of mistakes. For several effects, the guess graph claims that
+    /// the effects it considers possible effects are amenable if each of them is, and adjusts for
+    /// all of them at once.
+    pub(crate) fn verdict(&self, effects: &[usize]) -> Verdict {
+        // if y is not claimed to be effect of t based on the guess graph
+        // but possibly a descendant of t in the truth graph,
+        // the causal order might be wrong, so we count a mistake
+        let is_claimed = |y: &&usize| (self.is_claimed_effect)(**y);
+        if effects
+            .iter()
+            .filter(|y| !is_claimed(y))
+            .any(|y| self.poss_desc_in_truth.contains(y))
+        {
+            return Verdict::WrongOrder;
+        }
+        if !effects.iter().any(|y| is_claimed(&y)) {
+            return Verdict::Correct;
+        }
+
+        // if they disagree on amenability, we count a mistake;
+        // otherwise, (t,y) is either amenable or non-amenable in both graphs;
+        // now, if it is amenable but the adjustment is not valid in the true graph,
+        // we count a mistake
+        let nam_in_guess = effects
+            .iter()
+            .filter(is_claimed)
+            .any(|y| self.nam_in_guess.contains(y));
+        let nam_in_true = effects
+            .iter()
+            .filter(is_claimed)
+            .any(|y| self.nam_in_true.contains(y));
+        if nam_in_guess != nam_in_true {
+            return match nam_in_guess {
+                true => Verdict::NotAmenableInGuess,
+                false => Verdict::AmenableInGuess,
+            };
+        }
+        if nam_in_true {
+            return Verdict::Correct;
+        }
+        let is_wrongly_adjusted = if effects.iter().all(|y| is_claimed(&y)) {
+            (self.is_wrongly_adjusted)(effects)
+        } else {
+            (self.is_wrongly_adjusted)(&Vec::from_iter(
+                effects
+                    .iter()
+                    .copied()
+                    .filter(|y| (self.is_claimed_effect)(*y)),
+            ))
+        };
+        match is_wrongly_adjusted {
+            true => Verdict::WronglyAdjusted,
+            false => Verdict::Correct,
+        }
+    }
+}
+
+/// Treatments alongside the set of their selected effects.
+pub(crate) type EffectsByTreatment<T = usize, E = usize> = Vec<(T, FxHashSet<E>)>;
+
+/// A treatment alongside a sorted conditioning set.
+pub(crate) type ConditionedTreatment = (usize, Vec<usize>);
+
+/// Groups the selected `(treatment, effect)` pairs by treatment, so that each treatment only
+/// needs to be searched from once.
+///
+/// Returns the treatments (in ascending order), each alongside the set of its selected effects,
+/// and the number of distinct pairs. Repeated pairs are counted once.
+/// Pairs for which `is_trivial` holds for the treatment or the effect are counted,
+/// but not returned, since they cannot be mistakes.
+/// Fails if no pair is selected, a node is out of bounds, or a treatment equals its effect.
+pub(crate) fn group_by_treatment(
+    n_nodes: usize,
+    pairs: &[(usize, usize)],
+    is_trivial: impl Fn(usize) -> bool,
+) -> Result<(EffectsByTreatment, usize), PairsError> {
+    group(pairs.iter().map(|&(treatment, effect)| {
+        check_bounds(n_nodes, treatment)?;
+        check_bounds(n_nodes, effect)?;
+        if treatment == effect {
+            return Err(PairsError::TreatmentIsEffect { node: effect });
+        }
+        Ok((
+            treatment,
+            effect,
+            is_trivial(treatment) || is_trivial(effect),
+        ))
+    }))
+}
+
+/// Groups the selected `(treatments, effect)` pairs by their set of treatments,
+/// like [`group_by_treatment`] does for single treatments.
+///
+/// Treatment sets are returned sorted and without repeated nodes, so that sets listed in a
+/// different order are grouped together. A pair is trivial if `is_trivial` holds for the effect
+/// or for all of its treatments.
+/// Fails if no pair is selected, a treatment set is empty, a node is out of bounds,
+/// or a treatment set contains its effect.
+pub(crate) fn group_by_treatment_set(
+    n_nodes: usize,
+    pairs: &[(Vec<usize>, usize)],
+    is_trivial: impl Fn(usize) -> bool,
+) -> Result<(EffectsByTreatment<Vec<usize>>, usize), PairsError> {
+    group(pairs.iter().map(|(treatments, effect)| {
+        let mut treatments = treatments.clone();
+        treatments.sort_unstable();
+        treatments.dedup();
+        if treatments.is_empty() {
+            return Err(PairsError::EmptyTreatmentSet);
+        }
+        for node in treatments.iter().chain([effect]) {
+            check_bounds(n_nodes, *node)?;
+        }
+        if treatments.binary_search(effect).is_ok() {
+            return Err(PairsError::TreatmentIsEffect { node: *effect });
+        }
+        let trivial = is_trivial(*effect) || treatments.iter().all(|t| is_trivial(*t));
+        Ok((treatments, *effect, trivial))
+    }))
+}
+
+/// Groups the selected `(treatment, effects)` pairs by treatment,
+/// like [`group_by_treatment`] does for single effects.
+///
+/// Effect sets are returned sorted and without repeated nodes. A pair is trivial if `is_trivial`
+/// holds for the treatment or for all of its effects.
+/// Fails if no pair is selected, a set of effects is empty, a node is out of bounds,
+/// or a set of effects contains its treatment.
+pub(crate) fn group_by_treatment_with_effect_sets(
+    n_nodes: usize,
+    pairs: &[(usize, Vec<usize>)],
+    is_trivial: impl Fn(usize) -> bool,
+) -> Result<(EffectsByTreatment<usize, Vec<usize>>, usize), PairsError> {
+    group(pairs.iter().map(|(treatment, effects)| {
+        let mut effects = effects.clone();
+        effects.sort_unstable();
+        effects.dedup();
+        if effects.is_empty() {
+            return Err(PairsError::EmptyEffectSet);
+        }
+        for node in effects.iter().chain([treatment]) {
+            check_bounds(n_nodes, *node)?;
+        }
+        if effects.binary_search(treatment).is_ok() {
+            return Err(PairsError::TreatmentIsEffect { node: *treatment });
+        }
+        let trivial = is_trivial(*treatment) || effects.iter().all(|y| is_trivial(*y));
+        Ok((*treatment, effects, trivial))
+    }))
+}
+
+/// Groups the selected `(treatment, effect, conditioning set)` triples by treatment and
+/// conditioning set, like [`group_by_treatment`] does for unconditional pairs.
+///
+/// Conditioning sets are returned sorted and without repeated nodes, so that sets listed in a
+/// different order are grouped together. A triple is trivial if `is_trivial` holds for the
+/// treatment or the effect; an empty conditioning set is allowed.
+/// Fails if no triple is selected, a node is out of bounds, a treatment equals its effect,
+/// or a conditioning set contains its treatment or effect.
+pub(crate) fn group_by_treatment_and_conditioning_set(
+    n_nodes: usize,
+    triples: &[(usize, usize, Vec<usize>)],
+    is_trivial: impl Fn(usize) -> bool,
+) -> Result<(EffectsByTreatment<ConditionedTreatment>, usize), PairsError> {
+    group(triples.iter().map(|(treatment, effect, conditioning)| {
+        let mut conditioning = conditioning.clone();
+        conditioning.sort_unstable();
+        conditioning.dedup();
+        for node in conditioning.iter().chain([treatment, effect]) {
+            check_bounds(n_nodes, *node)?;
+        }
+        if treatment == effect {
+            return Err(PairsError::TreatmentIsEffect { node: *effect });
+        }
+        if let Some(node) = [treatment, effect]
+            .into_iter()
+            .find(|v| conditioning.binary_search(v).is_ok())
+        {
+            return Err(PairsError::ConditionsOnPair { node: *node });
+        }
+        let trivial = is_trivial(*treatment) || is_trivial(*effect);
+        Ok(((*treatment, conditioning), *effect, trivial))
+    }))
+}
+
+fn check_bounds(n_nodes: usize, node: usize) -> Result<(), PairsError> {
+    match node < n_nodes {
+        true => Ok(()),
+        false => Err(PairsError::OutOfBounds { node, n_nodes }),
+    }
+}
+
+/// Groups validated `(treatment, effect, is_trivial)` triples by treatment.
+fn group<T: Hash + Ord, E: Hash + Eq>(
+    pairs: impl Iterator<Item = Result<(T, E, bool), PairsError>>,
+) -> Result<(EffectsByTreatment<T, E>, usize), PairsError> {
+    let mut effects_of = FxHashMap::<T, FxHashSet<E>>::default();
+    let mut trivial_pairs = FxHashSet::<(T, E)>::default();
+    let mut any_pair = false;
+    for pair in pairs {
+        let (treatment, effect, trivial) = pair?;
+        any_pair = true;
+        if trivial {
+            trivial_pairs.insert((treatment, effect));
+        } else {
+            effects_of.entry(treatment).or_default().insert(effect);
+        }
+    }
+    if !any_pair {
+        return Err(PairsError::NoPairs);
+    }
+
+    let n_pairs = trivial_pairs.len()
+        + effects_of
+            .values()
+            .map(|effects| effects.len())
+            .sum::<usize>();
+    let mut grouped = Vec::from_iter(effects_of);
+    grouped.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+    Ok((grouped, n_pairs))
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+    use rustc_hash::FxHashSet;
+
+    use crate::{
+        graph_operations::{oset_aid, oset_aid_with, SelectedPairs},
+        PDAG,
+    };
+
+    use super::{
+        group_by_treatment, group_by_treatment_and_conditioning_set, group_by_treatment_set,
+        group_by_treatment_with_effect_sets, pairs_from_masks, PairsError, PairsNormalization,
+        SelectedPairsDistance,
+    };
+
+    #[test]
+    fn groups_and_deduplicates() {
+        let (grouped, n_pairs) =
+            group_by_treatment(5, &[(3, 0), (1, 2), (3, 4), (1, 2)], |_| false).unwrap();
+        assert_eq!(n_pairs, 3);
+        assert_eq!(
+            grouped,
+            vec![
+                (1, FxHashSet::from_iter([2])),
+                (3, FxHashSet::from_iter([0, 4]))
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_but_omits_trivial_pairs() {
+        let (grouped, n_pairs) =
+            group_by_treatment(5, &[(3, 0), (1, 2), (3, 4), (4, 3), (4, 3)], |v| v == 4).unwrap();
+        assert_eq!(n_pairs, 4);
+        assert_eq!(
+            grouped,
+            vec![
+                (1, FxHashSet::from_iter([2])),
+                (3, FxHashSet::from_iter([0]))
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_pairs() {
+        assert_eq!(
+            group_by_treatment(3, &[], |_| false),
+            Err(PairsError::NoPairs)
+        );
+        assert_eq!(
+            group_by_treatment(3, &[(0, 1), (0, 3)], |_| false),
+            Err(PairsError::OutOfBounds {
+                node: 3,
+                n_nodes: 3
+            })
+        );
+        assert_eq!(
+            group_by_treatment(3, &[(1, 1)], |_| false),
+            Err(PairsError::TreatmentIsEffect { node: 1 })
+        );
+    }
+
+    #[test]
+    fn groups_treatment_sets_regardless_of_order() {
+        let pairs = [
+            (vec![3, 1], 0),
+            (vec![1, 3, 3], 0),
+            (vec![1, 3], 2),
+            (vec![4], 2),
+            (vec![2, 4], 0),
+        ];
+        let (grouped, n_pairs) = group_by_treatment_set(5, &pairs, |v| v == 4).unwrap();
+        assert_eq!(n_pairs, 4);
+        assert_eq!(
+            grouped,
+            vec![
+                (vec![1, 3], FxHashSet::from_iter([0, 2])),
+                (vec![2, 4], FxHashSet::from_iter([0]))
+            ]
+        );
+
+        assert_eq!(
+            group_by_treatment_set(5, &[(vec![], 0)], |_| false),
+            Err(PairsError::EmptyTreatmentSet)
+        );
+        assert_eq!(
+            group_by_treatment_set(5, &[(vec![1, 0], 0)], |_| false),
+            Err(PairsError::TreatmentIsEffect { node: 0 })
+        );
+        assert_eq!(
+            group_by_treatment_set(5, &[(vec![1, 5], 0)], |_| false),
+            Err(PairsError::OutOfBounds {
+                node: 5,
+                n_nodes: 5
+            })
+        );
+    }
+
+    #[test]
+    fn reports_comparisons_and_duplicates() {
+        let distance = SelectedPairsDistance::new(1, 4, 6, 5);
+        assert_eq!(distance.distance(), (0.25, 1));
+        assert_eq!((distance.comparisons, distance.duplicates), (4, 2));
+        assert_eq!(
+            distance.normalized_by(PairsNormalization::Population),
+            (0.05, 1)
+        );
+    }
+
+    #[test]
+    fn groups_effect_sets_regardless_of_order() {
+        let pairs = [(0, vec![3, 1]), (0, vec![1, 3]), (2, vec![4]), (0, vec![1])];
+        let (grouped, n_pairs) =
+            group_by_treatment_with_effect_sets(5, &pairs, |v| v == 4).unwrap();
+        assert_eq!(n_pairs, 3);
+        assert_eq!(
+            grouped,
+            vec![(0, FxHashSet::from_iter([vec![1], vec![1, 3]]))]
+        );
+        assert_eq!(
+            group_by_treatment_with_effect_sets(5, &[(0, vec![])], |_| false),
+            Err(PairsError::EmptyEffectSet)
+        );
+        assert_eq!(
+            group_by_treatment_with_effect_sets(5, &[(0, vec![1, 0])], |_| false),
+            Err(PairsError::TreatmentIsEffect { node: 0 })
+        );
+    }
+
+    #[test]
+    fn groups_triples_by_treatment_and_conditioning_set() {
+        let triples = [
+            (0, 1, vec![3, 2]),
+            (0, 1, vec![2, 3, 2]),
+            (0, 4, vec![]),
+            (0, 1, vec![]),
+        ];
+        let (grouped, n_triples) =
+            group_by_treatment_and_conditioning_set(5, &triples, |v| v == 4).unwrap();
+        assert_eq!(n_triples, 3);
+        assert_eq!(
+            grouped,
+            vec![
+                ((0, vec![]), FxHashSet::from_iter([1])),
+                ((0, vec![2, 3]), FxHashSet::from_iter([1])),
+            ]
+        );
+        assert_eq!(
+            group_by_treatment_and_conditioning_set(5, &[(0, 1, vec![2, 1])], |_| false),
+            Err(PairsError::ConditionsOnPair { node: 1 })
+        );
+        assert_eq!(
+            group_by_treatment_and_conditioning_set(5, &[(0, 1, vec![5])], |_| false),
+            Err(PairsError::OutOfBounds {
+                node: 5,
+                n_nodes: 5
+            })
+        );
+    }
+
+    #[test]
+    fn masks_selecting_all_nodes_select_all_pairs() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_pdag(0.3, 12, &mut rng);
+        let guess = PDAG::random_pdag(0.3, 12, &mut rng);
+        let pairs = pairs_from_masks(&[true; 12], &[true; 12]);
+        assert_eq!(pairs.len(), 12 * 11);
+        assert_eq!(
+            oset_aid_with(&truth, &guess, SelectedPairs(&pairs))
+                .unwrap()
+                .distance(),
+            oset_aid(&truth, &guess)
+        );
+    }
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Tests d-separation in DAGs and CPDAGs, via a DAG in the Markov equivalence class of a CPDAG
+
+use std::collections::BinaryHeap;
+
+use rustc_hash::FxHashSet;
+
+use crate::{EdgelistIterator, PDAG};
+
+/// Returns a DAG in the Markov equivalence class of the `cpdag`, which is a copy of the graph for DAGs.
+///
+/// Orients the undirected edges of each connected component of undirected edges
+/// in the order of a maximum cardinality search, which visits the nodes of the chordal components
+/// of a CPDAG in an order that creates neither cycles nor v-structures.
+pub(crate) fn dag_in_class(cpdag: &PDAG) -> PDAG {
+    // the position of each node in the search, where each search starts at an unvisited node,
+    // and then repeatedly visits the node with the most visited undirected neighbours
+    let mut position = vec![usize::MAX; cpdag.n_nodes];
+    let mut visited_neighbours = vec![0; cpdag.n_nodes];
+    let mut n_visited = 0;
+    for start in 0..cpdag.n_nodes {
+        if position[start] != usize::MAX {
+            continue;
+        }
+        let mut to_visit = BinaryHeap::from([(0, start)]);
+        while let Some((count, v)) = to_visit.pop() {
+            if position[v] != usize::MAX || count < visited_neighbours[v] {
+                continue;
+            }
+            position[v] = n_visited;
+            n_visited += 1;
+            for w in cpdag.adjacent_undirected_of(v) {
+                if position[*w] == usize::MAX {
+                    visited_neighbours[*w] += 1;
+                    to_visit.push((visited_neighbours[*w], *w));
+                }
+            }
+        }
+    }
+
+    let mut entries = Vec::new();
+    for v in 0..cpdag.n_nodes {
+        let oriented = cpdag
+            .adjacent_undirected_of(v)
+            .iter()
+            .filter(|w| position[v] < position[**w]);
+        let mut children = Vec::from_iter(cpdag.children_of(v).iter().chain(oriented).copied());
+        children.sort_unstable();
+        entries.extend(children.into_iter().map(|w| (v, w, 1)));
+    }
+    PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(cpdag.n_nodes))
+        .expect("orienting a CPDAG by a maximum cardinality search gives a DAG")
+}
+
+/// Returns whether the node sets `x` and `y` are d-separated given the set `z` in the `dag`,
+/// following the reachable algorithm of Koller and Friedman (2009, Algorithm 3.1).
+pub(crate) fn is_d_separated(dag: &PDAG, x: &[usize], y: &[usize], z: &FxHashSet<usize>) -> bool {
+    // colliders are only open if they are ancestors of z
+    let mut ancestors_of_z = z.clone();
+    let mut to_visit = Vec::from_iter(z.iter().copied());
+    while let Some(v) = to_visit.pop() {
+        for p in dag.parents_of(v) {
+            if ancestors_of_z.insert(*p) {
+                to_visit.push(*p);
+            }
+        }
+    }
+
+    let y = FxHashSet::from_iter(y.iter().copied());
+    // whether a node is reached from one of its children (upwards) or from one of its parents
+    let mut visited = FxHashSet::<(usize, bool)>::default();
+    let mut to_visit = Vec::from_iter(x.iter().map(|v| (*v, true)));
+    while let Some((v, upwards)) = to_visit.pop() {
+        if !visited.insert((v, upwards)) {
+            continue;
+        }
+        let is_conditioned = z.contains(&v);
+        if !is_conditioned && y.contains(&v) {
+            return false;
+        }
+        if upwards && !is_conditioned {
+            to_visit.extend(dag.parents_of(v).iter().map(|p| (*p, true)));
+            to_visit.extend(dag.children_of(v).iter().map(|c| (*c, false)));
+        } else if !upwards {
+            if !is_conditioned {
+                to_visit.extend(dag.children_of(v).iter().map(|c| (*c, false)));
+            }
+            if ancestors_of_z.contains(&v) {
+                to_visit.extend(dag.parents_of(v).iter().map(|p| (*p, true)));
+            }
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use rustc_hash::FxHashSet;
+
+    use crate::{graph_operations::shd::edge_type, PDAG};
+
+    use super::{dag_in_class, is_d_separated};
+
+    #[test]
+    fn d_separation_of_chain_fork_and_collider() {
+        // 0 -> 1 -> 2, 1 -> 3 <- 4
+        let dag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0], //
+            vec![0, 0, 1, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0],
+        ]);
+        let given = |z: &[usize]| FxHashSet::from_iter(z.iter().copied());
+        assert!(!is_d_separated(&dag, &[0], &[2], &given(&[])));
+        assert!(is_d_separated(&dag, &[0], &[2], &given(&[1])));
+        assert!(is_d_separated(&dag, &[2], &[3], &given(&[1])));
+        assert!(is_d_separated(&dag, &[0], &[4], &given(&[])));
+        assert!(!is_d_separated(&dag, &[0], &[4], &given(&[3])));
+        assert!(is_d_separated(&dag, &[0], &[4], &given(&[1, 3])));
+    }
+
+    #[test]
+    fn dags_in_class_keep_skeleton_and_v_structures() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+
+        for graph_id in 10..=19 {
+            let cpdag = crate::test::load_pdag_from_mtx(
+                testgraphs
+                    .join(format!("10-node-CPDAG-{}.mtx", graph_id))
+                    .to_str()
+                    .unwrap(),
+            );
+            let dag = dag_in_class(&cpdag);
+            assert_eq!(dag.n_undirected_edges, 0);
+            let v_structures = |graph: &PDAG| {
+                FxHashSet::from_iter((0..graph.n_nodes).flat_map(|v| {
+                    let parents = graph.parents_of(v);
+                    parents.iter().flat_map(move |a| {
+                        parents
+                            .iter()
+                            .filter(move |b| a < *b && edge_type(graph, *a, **b) == 0)
+                            .map(move |b| (*a, v, *b))
+                    })
+                }))
+            };
+            assert_eq!(v_structures(&cpdag), v_structures(&dag));
+            for v in 0..cpdag.n_nodes {
+                for w in 0..cpdag.n_nodes {
+                    let (in_cpdag, in_dag) = (edge_type(&cpdag, v, w), edge_type(&dag, v, w));
+                    assert!(in_cpdag == in_dag || (in_cpdag == 3 && in_dag != 0));
+                }
+            }
+        }
+    }
+}
@@ -5,11 +5,34 @@ use rayon::prelude::*;
 
 use crate::{
     ascending_list_utils::{ascending_lists_set_symmetric_difference, ascending_lists_set_union},
+    graph_operations::{
+        cpdag_of,
+        quick_reject::{identical, shd_if_either_empty},
+    },
     PDAG,
 };
 
 /// Generalized Structural hamming distance between two simple graphs. Returns a tuple of
 /// (normalized error (in \[0,1]), total number of errors)
+///
+/// ```
+/// use gadjid::{graph_operations::shd, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // the edge between 1 and 2 is reversed
+/// assert_eq!(shd(&truth, &guess), (1.0 / 3.0, 1));
+/// ```
 // this can be generalised to different graphs with different types of edges
 // using generics, as we don't care about incoming/outgoing/parent/child semantics here
 pub fn shd(g_truth: &PDAG, g_guess: &PDAG) -> (f64, usize) {
@@ -17,72 +40,324 @@ pub fn shd(g_truth: &PDAG, g_guess: &PDAG) -> (f64, usize) {
     if g_truth.n_nodes == 1 {
         return (0f64, 0);
     }
+    // there are |V|*(|V|-1)/2  unordered pairs of nodes
+    let comparisons = g_truth.n_nodes * (g_truth.n_nodes - 1) / 2;
+    if identical(g_truth, g_guess) {
+        return (0f64, 0);
+    }
+    if let Some(dist) = shd_if_either_empty(g_truth, g_guess) {
+        return (dist as f64 / comparisons as f64, dist);
+    }
 
     crate::rayon::build_global();
 
     let dist = (0..g_truth.n_nodes)
+        .into_par_iter()
+        .map(|node| differing_below(g_truth, g_guess, node).len())
+        .sum();
+    (dist as f64 / comparisons as f64, dist)
+}
+
+/// The nodes w < `node` whose edge with `node` differs between the two graphs, ascending.
+fn differing_below(g_truth: &PDAG, g_guess: &PDAG, node: usize) -> Vec<usize> {
+    let truth_children = g_truth
+        .children_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+    let truth_parents = g_truth
+        .parents_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+    let truth_undirected = g_truth
+        .adjacent_undirected_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+    let guess_children = g_guess
+        .children_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+    let guess_parents = g_guess
+        .parents_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+    let guess_undirected = g_guess
+        .adjacent_undirected_of(node)
+        .iter()
+        .copied()
+        .filter(|e| e < &node);
+
+    let children_symdif = ascending_lists_set_symmetric_difference(truth_children, guess_children);
+    let parents_symdif = ascending_lists_set_symmetric_difference(truth_parents, guess_parents);
+    let undirected_symdif =
+        ascending_lists_set_symmetric_difference(truth_undirected, guess_undirected);
+
+    let distinct_children_and_parents =
+        ascending_lists_set_union(children_symdif.into_iter(), parents_symdif.into_iter());
+    ascending_lists_set_union(
+        distinct_children_and_parents.into_iter(),
+        undirected_symdif.into_iter(),
+    )
+}
+
+/// Structural hamming distance between the CPDAGs of two DAGs, so that Markov equivalent DAGs,
+/// which no observational data can tell apart, have distance 0.
+/// Returns a tuple of (normalized error (in \[0,1]), total number of errors).
+/// Panics if either graph has undirected edges.
+///
+/// ```
+/// use gadjid::{graph_operations::{shd, shd_markov_equivalent}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 <- 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0, 0],
+///     vec![1, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // both edges are reversed, but both DAGs have the CPDAG 0 -- 1 -- 2
+/// assert_eq!(shd(&truth, &guess), (2.0 / 3.0, 2));
+/// assert_eq!(shd_markov_equivalent(&truth, &guess), (0.0, 0));
+/// ```
+pub fn shd_markov_equivalent(truth_dag: &PDAG, guess_dag: &PDAG) -> (f64, usize) {
+    shd(&cpdag_of(truth_dag), &cpdag_of(guess_dag))
+}
+
+/// The structural hamming distance split by the kind of mistake, as returned by [`shd_detailed`].
+/// Every differing pair of nodes is counted once, so the kinds sum to the SHD.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DetailedShd {
+    /// pairs adjacent in the true graph but not in the guess graph
+    pub missing: usize,
+    /// pairs adjacent in the guess graph but not in the true graph
+    pub superfluous: usize,
+    /// pairs with a directed edge in both graphs, pointing in opposite directions
+    pub reversed: usize,
+    /// pairs with a directed edge in one graph and an undirected edge in the other
+    pub mistyped: usize,
+    /// the number of unordered pairs of nodes, which the SHD is normalized by
+    pub comparisons: usize,
+}
+
+impl DetailedShd {
+    /// The total number of mistakes, as counted by [`shd`].
+    pub fn mistakes(&self) -> usize {
+        self.missing + self.superfluous + self.reversed + self.mistyped
+    }
+
+    /// The normalized distance (in \[0,1]), as returned by [`shd`].
+    pub fn normalized(&self) -> f64 {
+        if self.comparisons == 0 {
+            return 0f64;
+        }
+        self.mistakes() as f64 / self.comparisons as f64
+    }
+
+    fn add(self, other: DetailedShd) -> DetailedShd {
+        DetailedShd {
+            missing: self.missing + other.missing,
+            superfluous: self.superfluous + other.superfluous,
+            reversed: self.reversed + other.reversed,
+            mistyped: self.mistyped + other.mistyped,
+            comparisons: self.comparisons + other.comparisons,
+        }
+    }
+}
+
+/// Structural hamming distance between two simple graphs, with its mistakes split into missing edges,
+/// superfluous edges, reversed directed edges, and directed-vs-undirected mismatches.
+///
+/// ```
+/// use gadjid::{graph_operations::shd_detailed, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 <- 2, 0 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 1],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let detailed = shd_detailed(&truth, &guess);
+/// assert_eq!(
+///     (detailed.missing, detailed.superfluous, detailed.reversed, detailed.mistyped),
+///     (0, 1, 1, 1)
+/// );
+/// assert_eq!((detailed.normalized(), detailed.mistakes()), (1.0, 3));
+/// ```
+pub fn shd_detailed(g_truth: &PDAG, g_guess: &PDAG) -> DetailedShd {
+    assert_eq!(g_truth.n_nodes, g_guess.n_nodes, "graph size mismatch");
+    // there are |V|*(|V|-1)/2  unordered pairs of nodes
+    let comparisons = g_truth.n_nodes * g_truth.n_nodes.saturating_sub(1) / 2;
+    if identical(g_truth, g_guess) {
+        return DetailedShd {
+            comparisons,
+            ..DetailedShd::default()
+        };
+    }
+
+    crate::rayon::build_global();
+
+    let detailed = (0..g_truth.n_nodes)
         .into_par_iter()
         .map(|node| {
-            let truth_children = g_truth
-                .children_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-            let truth_parents = g_truth
-                .parents_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-            let truth_undirected = g_truth
-                .adjacent_undirected_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-
-            let guess_children = g_guess
-                .children_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-            let guess_parents = g_guess
-                .parents_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-            let guess_undirected = g_guess
-                .adjacent_undirected_of(node)
-                .iter()
-                .copied()
-                .filter(|e| e < &node);
-
-            let children_symdif =
-                ascending_lists_set_symmetric_difference(truth_children, guess_children);
-            let parents_symdif =
-                ascending_lists_set_symmetric_difference(truth_parents, guess_parents);
-            let undirected_symdif =
-                ascending_lists_set_symmetric_difference(truth_undirected, guess_undirected);
-
-            let distinct_children_and_parents =
-                ascending_lists_set_union(children_symdif.into_iter(), parents_symdif.into_iter());
-            let union = ascending_lists_set_union(
-                distinct_children_and_parents.into_iter(),
-                undirected_symdif.into_iter(),
-            );
-            union.len()
+            let mut detailed = DetailedShd::default();
+            for w in differing_below(g_truth, g_guess, node) {
+                match (edge_type(g_truth, w, node), edge_type(g_guess, w, node)) {
+                    (_, 0) => detailed.missing += 1,
+                    (0, _) => detailed.superfluous += 1,
+                    (1, 2) | (2, 1) => detailed.reversed += 1,
+                    _ => detailed.mistyped += 1,
+                }
+            }
+            detailed
         })
+        .reduce(DetailedShd::default, DetailedShd::add);
+    DetailedShd {
+        comparisons,
+        ..detailed
+    }
+}
+
+/// The edge types between two nodes v < w, which index the rows and columns of the cost matrix
+/// of [`weighted_shd`] in this order: no edge, `v -> w`, `v <- w`, and `v -- w`.
+pub(crate) fn edge_type(graph: &PDAG, v: usize, w: usize) -> usize {
+    if graph.children_of(v).binary_search(&w).is_ok() {
+        1
+    } else if graph.parents_of(v).binary_search(&w).is_ok() {
+        2
+    } else if graph.adjacent_undirected_of(v).binary_search(&w).is_ok() {
+        3
+    } else {
+        0
+    }
+}
+
+/// The weighted structural hamming distance, as returned by [`weighted_shd`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WeightedShd {
+    /// the total cost of the edge type transitions from the true to the guess graph
+    pub cost: f64,
+    /// the number of pairs of nodes v < w for each transition from the edge type in the true graph (row)
+    /// to the edge type in the guess graph (column), in the order no edge, `v -> w`, `v <- w`, and `v -- w`
+    pub counts: [[usize; 4]; 4],
+}
+
+/// Structural hamming distance between two simple graphs, weighted by the cost of each transition
+/// from the edge type between two nodes v < w in the true graph (row of `costs`) to the edge type
+/// in the guess graph (column of `costs`), in the order no edge, `v -> w`, `v <- w`, and `v -- w`.
+/// For example, `costs[0][1]` is the cost of an extra edge `v -> w`, `costs[1][2]` is the cost of
+/// a reversed edge, and `costs[1][3]` is the cost of guessing `v -- w` instead of `v -> w`.
+/// The SHD is the weighted SHD with zero costs on the diagonal and unit costs elsewhere.
+/// Returns the total cost alongside the number of pairs of nodes for each transition.
+///
+/// ```
+/// use gadjid::{graph_operations::weighted_shd, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// // reversed edges cost half as much as other mistakes
+/// let costs = [
+///     [0.0, 1.0, 1.0, 1.0],
+///     [1.0, 0.0, 0.5, 1.0],
+///     [1.0, 0.5, 0.0, 1.0],
+///     [1.0, 1.0, 1.0, 0.0],
+/// ];
+/// let weighted = weighted_shd(&truth, &guess, &costs);
+/// assert_eq!(weighted.cost, 0.5);
+/// // the pairs (0, 1), (0, 2), and (1, 2)
+/// assert_eq!((weighted.counts[1][1], weighted.counts[0][0], weighted.counts[1][2]), (1, 1, 1));
+/// ```
+pub fn weighted_shd(g_truth: &PDAG, g_guess: &PDAG, costs: &[[f64; 4]; 4]) -> WeightedShd {
+    let counts = transition_counts(g_truth, g_guess);
+
+    let cost = (0..4)
+        .flat_map(|i| (0..4).map(move |j| (i, j)))
+        .map(|(i, j)| counts[i][j] as f64 * costs[i][j])
         .sum();
-    // there are |V|*(|V|-1)/2  unordered pairs of nodes
-    let comparisons = g_truth.n_nodes * (g_truth.n_nodes - 1) / 2;
-    (dist as f64 / comparisons as f64, dist)
+    WeightedShd { cost, counts }
+}
+
+/// The number of pairs of nodes v < w for each transition from the edge type in the true graph (row)
+/// to the edge type in the guess graph (column), as in [`WeightedShd::counts`].
+pub(crate) fn transition_counts(g_truth: &PDAG, g_guess: &PDAG) -> [[usize; 4]; 4] {
+    assert_eq!(g_truth.n_nodes, g_guess.n_nodes, "graph size mismatch");
+
+    crate::rayon::build_global();
+
+    let mut counts = (0..g_truth.n_nodes)
+        .into_par_iter()
+        .map(|v| {
+            let mut counts = [[0; 4]; 4];
+            // all pairs v < w that are adjacent in either graph
+            let adjacent = |graph: &PDAG| {
+                let children_and_parents = ascending_lists_set_union(
+                    graph.children_of(v).iter().copied(),
+                    graph.parents_of(v).iter().copied(),
+                );
+                ascending_lists_set_union(
+                    children_and_parents.into_iter(),
+                    graph.adjacent_undirected_of(v).iter().copied(),
+                )
+            };
+            let pairs = ascending_lists_set_union(
+                adjacent(g_truth).into_iter(),
+                adjacent(g_guess).into_iter(),
+            );
+            for w in pairs.into_iter().filter(|w| v < *w) {
+                counts[edge_type(g_truth, v, w)][edge_type(g_guess, v, w)] += 1;
+            }
+            counts
+        })
+        .reduce(
+            || [[0; 4]; 4],
+            |mut a, b| {
+                for (row_a, row_b) in a.iter_mut().zip(b.iter()) {
+                    for (count_a, count_b) in row_a.iter_mut().zip(row_b.iter()) {
+                        *count_a += count_b;
+                    }
+                }
+                a
+            },
+        );
+    let n_pairs = g_truth.n_nodes * g_truth.n_nodes.saturating_sub(1) / 2;
+    counts[0][0] = n_pairs - counts.iter().flatten().sum::<usize>();
+    counts
 }
 
 #[cfg(test)]
 mod test {
     use rand::SeedableRng;
 
-    use crate::PDAG;
+    use crate::{
+        graph_operations::{consistent_extension, cpdag_of},
+        PDAG,
+    };
 
-    use super::shd;
+    use super::{shd, shd_detailed, shd_markov_equivalent, weighted_shd, WeightedShd};
 
     /// Structural hamming distance between two adjacency matrices, ignores diagonal. Only used for the tests.
     /// This function works directly on the adjacency matrix representation.
@@ -152,8 +427,8 @@ mod test {
 
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (0f64, 0));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (0f64, 0));
@@ -168,8 +443,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
         assert_eq!(shd(&d_truth, &d_guess), (1f64, 1));
 
@@ -186,8 +461,8 @@ mod test {
 
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (1f64, 1));
@@ -204,8 +479,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (0f64, 0));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (0f64, 0));
@@ -224,8 +499,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64 / 6f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (1f64 / 6f64, 1));
@@ -238,8 +513,8 @@ mod test {
 
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (0f64, 0));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (0f64, 0));
@@ -254,8 +529,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
         assert_eq!(shd(&d_truth, &d_guess), (1f64, 1));
 
@@ -272,8 +547,8 @@ mod test {
 
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (1f64, 1));
@@ -290,8 +565,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (0f64, 0));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
         assert_eq!(shd(&d_truth, &d_guess), (0f64, 0));
 
@@ -307,8 +582,8 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64, 3));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
         assert_eq!(shd(&d_truth, &d_guess), (1f64, 3));
 
@@ -326,10 +601,100 @@ mod test {
         ];
         assert_eq!(shd_from_adjacency(&g_truth, &g_guess), (1f64 / 6f64, 1));
         let (d_truth, d_guess) = (
-            PDAG::from_row_to_column_vecvec(g_truth),
-            PDAG::from_row_to_column_vecvec(g_guess),
+            PDAG::from_dense_row_to_col(g_truth),
+            PDAG::from_dense_row_to_col(g_guess),
         );
 
         assert_eq!(shd(&d_truth, &d_guess), (1f64 / 6f64, 1));
     }
+
+    #[test]
+    fn weighs_edge_type_transitions() {
+        // 0 -> 1 -> 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        // 0 <- 1 -- 2, 0 -> 2
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 1], //
+            vec![1, 0, 2],
+            vec![0, 0, 0],
+        ]);
+        let mut costs = [[0.0; 4]; 4];
+        for (i, row) in costs.iter_mut().enumerate() {
+            for (j, cost) in row.iter_mut().enumerate() {
+                *cost = (10 * i + j) as f64;
+            }
+        }
+        let weighted = weighted_shd(&truth, &guess, &costs);
+        let mut counts = [[0; 4]; 4];
+        // a reversed edge, an undirected edge instead of a directed one, and an extra edge
+        counts[1][2] = 1;
+        counts[1][3] = 1;
+        counts[0][1] = 1;
+        assert_eq!(
+            weighted,
+            WeightedShd {
+                cost: 12.0 + 13.0 + 1.0,
+                counts
+            }
+        );
+    }
+
+    #[test]
+    fn property_unit_costs_give_shd() {
+        let mut costs = [[1.0; 4]; 4];
+        for (i, row) in costs.iter_mut().enumerate() {
+            row[i] = 0.0;
+        }
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 1..30 {
+            let truth = PDAG::random_pdag(0.4, n, &mut rng);
+            let guess = PDAG::random_pdag(0.4, n, &mut rng);
+            let weighted = weighted_shd(&truth, &guess, &costs);
+            assert_eq!(weighted.cost, shd(&truth, &guess).1 as f64);
+            assert_eq!(
+                weighted.counts.iter().flatten().sum::<usize>(),
+                n * (n - 1) / 2
+            );
+        }
+    }
+
+    #[test]
+    fn property_detailed_kinds_sum_to_shd() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 1..30 {
+            let truth = PDAG::random_pdag(0.4, n, &mut rng);
+            let guess = PDAG::random_pdag(0.4, n, &mut rng);
+            let detailed = shd_detailed(&truth, &guess);
+            assert_eq!(
+                (detailed.normalized(), detailed.mistakes()),
+                shd(&truth, &guess)
+            );
+
+            let counts = weighted_shd(&truth, &guess, &[[0.0; 4]; 4]).counts;
+            assert_eq!(detailed.missing, counts[1][0] + counts[2][0] + counts[3][0]);
+            assert_eq!(
+                detailed.superfluous,
+                counts[0][1] + counts[0][2] + counts[0][3]
+            );
+            assert_eq!(detailed.reversed, counts[1][2] + counts[2][1]);
+        }
+    }
+
+    #[test]
+    fn property_markov_equivalent_dags_have_zero_distance() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            let equivalent = consistent_extension(&cpdag_of(&dag)).unwrap();
+            assert_eq!(shd_markov_equivalent(&dag, &equivalent), (0.0, 0));
+
+            let other = PDAG::random_dag(0.3, n, &mut rng);
+            let (_, mistakes) = shd_markov_equivalent(&dag, &other);
+            assert_eq!(mistakes == 0, cpdag_of(&dag) == cpdag_of(&other));
+        }
+    }
 }
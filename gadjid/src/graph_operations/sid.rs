@@ -3,7 +3,12 @@
 
 use std::{error::Error, fmt};
 
-use crate::graph_operations::parent_aid;
+use rayon::prelude::*;
+
+use crate::graph_operations::{
+    consistent_extension, parent_aid, parent_aid_with, selected_pairs::Effects, Aid, PairsError,
+    SelectedPairs, SelectedPairsDistance,
+};
 use crate::partially_directed_acyclic_graph::Structure::DAG;
 use crate::PDAG;
 
@@ -16,6 +21,14 @@ pub enum SIDError {
     GuessNotDAG,
     /// The two input graphs are not the same size
     NotSameSize,
+    /// The truth graph has undirected edges that cannot be oriented into a consistent DAG extension
+    TruthNotExtendable,
+    /// The guess graph has undirected edges that cannot be oriented into a consistent DAG extension
+    GuessNotExtendable,
+    /// The selected `(treatment, effect)` pairs are invalid
+    InvalidPairs(PairsError),
+    /// The Markov equivalence class of the guess graph has too many DAGs to be enumerated
+    GuessClassTooLarge,
 }
 
 impl fmt::Display for SIDError {
@@ -30,6 +43,19 @@ impl fmt::Display for SIDError {
                 "Guess graph is not a DAG. Use `parent_aid` if you want to pass a CPDAG"
             ),
             SIDError::NotSameSize => write!(f, "The two input graphs are not the same size"),
+            SIDError::TruthNotExtendable => write!(
+                f,
+                "Truth graph has no consistent DAG extension, so its undirected edges cannot be oriented"
+            ),
+            SIDError::GuessNotExtendable => write!(
+                f,
+                "Guess graph has no consistent DAG extension, so its undirected edges cannot be oriented"
+            ),
+            SIDError::InvalidPairs(err) => write!(f, "Invalid selected pairs: {err}"),
+            SIDError::GuessClassTooLarge => write!(
+                f,
+                "Markov equivalence class of the guess graph has too many DAGs to be enumerated"
+            ),
         }
     }
 }
@@ -38,6 +64,33 @@ impl Error for SIDError {}
 
 /// Structural Intervention Distance between DAGs.
 /// Will return error if either graph is not a DAG.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{parent_aid, sid, SIDError},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// assert_eq!(sid(&truth, &guess).unwrap(), parent_aid(&truth, &guess));
+///
+/// let cpdag = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2], //
+///     vec![0, 0],
+/// ]);
+/// assert!(matches!(sid(&cpdag, &cpdag), Err(SIDError::TruthNotDAG)));
+/// ```
 pub fn sid(truth: &PDAG, guess: &PDAG) -> Result<(f64, usize), SIDError> {
     if !matches!(truth.pdag_type, DAG) {
         return Err(SIDError::TruthNotDAG);
@@ -51,3 +104,310 @@ pub fn sid(truth: &PDAG, guess: &PDAG) -> Result<(f64, usize), SIDError> {
 
     Ok(parent_aid(truth, guess))
 }
+
+/// Structural Intervention Distance between DAGs,
+/// only counting the mistakes for the selected `(treatment, effect)` pairs,
+/// see [`SelectedPairs`].
+/// Will return error if either graph is not a DAG or if the pairs are invalid.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{sid_selected_pairs, SIDError},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let selected = sid_selected_pairs(&truth, &guess, &[(0, 2), (2, 0)]).unwrap();
+/// assert_eq!(selected.distance(), (0.5, 1));
+///
+/// assert!(matches!(
+///     sid_selected_pairs(&truth, &guess, &[]),
+///     Err(SIDError::InvalidPairs(_))
+/// ));
+/// ```
+pub fn sid_selected_pairs(
+    truth: &PDAG,
+    guess: &PDAG,
+    pairs: &[(usize, usize)],
+) -> Result<SelectedPairsDistance, SIDError> {
+    if !matches!(truth.pdag_type, DAG) {
+        return Err(SIDError::TruthNotDAG);
+    }
+    if !matches!(guess.pdag_type, DAG) {
+        return Err(SIDError::GuessNotDAG);
+    }
+    if truth.n_nodes != guess.n_nodes {
+        return Err(SIDError::NotSameSize);
+    }
+
+    parent_aid_with(truth, guess, SelectedPairs(pairs)).map_err(SIDError::InvalidPairs)
+}
+
+/// How [`sid_with_undirected`] handles graphs with undirected edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UndirectedEdges {
+    /// fails on undirected edges, like [`sid`]
+    Reject,
+    /// orients the undirected edges of either graph into a consistent DAG extension
+    Orient,
+    /// bounds the SID over the DAGs that the guess CPDAG represents, like the original SID
+    Bounds,
+}
+
+/// The SID as returned by [`sid_with_undirected`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SidWithUndirected {
+    /// A tuple of (normalized error (in \[0,1]), total number of errors), or its lower bound
+    /// over the DAGs represented by the guess if undirected edges are bounded.
+    pub lower: (f64, usize),
+    /// The upper bound over the DAGs represented by the guess if undirected edges are bounded,
+    /// and the same as `lower` otherwise.
+    pub upper: (f64, usize),
+    /// Whether the undirected edges of the truth graph were oriented.
+    pub oriented_truth: bool,
+    /// Whether the undirected edges of the guess graph were oriented.
+    pub oriented_guess: bool,
+}
+
+/// Structural Intervention Distance between DAGs, which handles `undirected` edges in the input,
+/// such as a stray `2` in a script ported from elsewhere, instead of failing like [`sid`] does.
+///
+/// With [`UndirectedEdges::Orient`], graphs with undirected edges are replaced by a
+/// [consistent DAG extension](consistent_extension), which fails if there is none,
+/// and the result records which graphs were oriented so that callers can warn about it.
+/// With [`UndirectedEdges::Bounds`], the truth graph must be a DAG and the guess a DAG or CPDAG,
+/// and the SID is bounded like by the original SID: the parents of each treatment in a DAG
+/// represented by the guess are its parents in the guess and a clique of its undirected neighbours
+/// (Maathuis et al. 2009), so the mistakes of each treatment are bounded by the fewest and the most
+/// mistakes over these parent sets, which are summed over the treatments.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{sid_with_undirected, UndirectedEdges},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -- 1 -- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 2, 0],
+///     vec![0, 0, 2],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let bounds = sid_with_undirected(&truth, &guess, UndirectedEdges::Bounds).unwrap();
+/// // the truth is one of the DAGs the guess represents
+/// assert_eq!(bounds.lower, (0.0, 0));
+/// assert!(bounds.upper.1 > 0);
+///
+/// let oriented = sid_with_undirected(&truth, &guess, UndirectedEdges::Orient).unwrap();
+/// assert!(oriented.oriented_guess && !oriented.oriented_truth);
+/// assert!(bounds.lower.1 <= oriented.lower.1 && oriented.lower.1 <= bounds.upper.1);
+/// ```
+pub fn sid_with_undirected(
+    truth: &PDAG,
+    guess: &PDAG,
+    undirected: UndirectedEdges,
+) -> Result<SidWithUndirected, SIDError> {
+    let exact = |distance, oriented_truth, oriented_guess| SidWithUndirected {
+        lower: distance,
+        upper: distance,
+        oriented_truth,
+        oriented_guess,
+    };
+    match undirected {
+        UndirectedEdges::Reject => Ok(exact(sid(truth, guess)?, false, false)),
+        UndirectedEdges::Orient => {
+            let oriented_truth = truth.n_undirected_edges > 0;
+            let oriented_guess = guess.n_undirected_edges > 0;
+            let truth = match oriented_truth {
+                true => &consistent_extension(truth).ok_or(SIDError::TruthNotExtendable)?,
+                false => truth,
+            };
+            let guess = match oriented_guess {
+                true => &consistent_extension(guess).ok_or(SIDError::GuessNotExtendable)?,
+                false => guess,
+            };
+            Ok(exact(sid(truth, guess)?, oriented_truth, oriented_guess))
+        }
+        UndirectedEdges::Bounds => {
+            if !matches!(truth.pdag_type, DAG) {
+                return Err(SIDError::TruthNotDAG);
+            }
+            if truth.n_nodes != guess.n_nodes {
+                return Err(SIDError::NotSameSize);
+            }
+            let (lower, upper) = sid_bounds(truth, guess);
+            let n = truth.n_nodes;
+            let comparisons = (n * n - n) as f64;
+            Ok(SidWithUndirected {
+                lower: (lower as f64 / comparisons, lower),
+                upper: (upper as f64 / comparisons, upper),
+                oriented_truth: false,
+                oriented_guess: false,
+            })
+        }
+    }
+}
+
+/// Sums the fewest and the most mistakes of each treatment over its possible parent sets in the DAGs
+/// represented by the `guess` CPDAG, its parents and any clique of its undirected neighbours.
+fn sid_bounds(truth: &PDAG, guess: &PDAG) -> (usize, usize) {
+    crate::rayon::build_global();
+
+    let all_nodes = Vec::from_iter(0..truth.n_nodes);
+    (0..truth.n_nodes)
+        .into_par_iter()
+        .map(|treatment| {
+            let mut parent_sets = vec![guess.parents_of(treatment).to_vec()];
+            // extends each clique found so far by each larger neighbour adjacent to all of its members
+            for w in guess.adjacent_undirected_of(treatment) {
+                for i in 0..parent_sets.len() {
+                    let added = &parent_sets[i][guess.parents_of(treatment).len()..];
+                    let adjacent = |v: &usize| {
+                        guess.adjacent_undirected_of(*w).contains(v)
+                            || guess.parents_of(*w).contains(v)
+                            || guess.children_of(*w).contains(v)
+                    };
+                    if added.iter().all(adjacent) {
+                        let mut extended = parent_sets[i].clone();
+                        extended.push(*w);
+                        parent_sets.push(extended);
+                    }
+                }
+            }
+            let mistakes = parent_sets
+                .iter()
+                .map(|parents| mistakes_with_parents(truth, treatment, parents, &all_nodes));
+            mistakes.fold((usize::MAX, 0), |(lower, upper), m| {
+                (lower.min(m), upper.max(m))
+            })
+        })
+        .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1))
+}
+
+/// The SID mistakes of the `treatment` in any guess DAG in which it has the given `parents`,
+/// since in a DAG, the mistakes of a treatment only depend on its parents.
+pub(crate) fn mistakes_with_parents(
+    truth: &PDAG,
+    treatment: usize,
+    parents: &[usize],
+    all_nodes: &[usize],
+) -> usize {
+    let star = PDAG::try_from_edges(truth.n_nodes, parents.iter().map(|p| (*p, treatment, 1)))
+        .expect("edges into a single node form a DAG");
+    Aid::parent(truth, &star).mistakes(treatment, Effects::All(all_nodes))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{
+            consistent_extension, parent_aid, parent_aid_with, separation::dag_in_class,
+            PairsError, SelectedPairs,
+        },
+        PDAG,
+    };
+
+    use super::{sid, sid_selected_pairs, sid_with_undirected, SIDError, UndirectedEdges};
+
+    #[test]
+    fn bounds_enclose_sid_of_dags_in_class() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+        let load =
+            |name: String| crate::test::load_pdag_from_mtx(testgraphs.join(name).to_str().unwrap());
+
+        for graph_id in 10..=19 {
+            let truth = load(format!("10-node-DAG-{graph_id}.mtx"));
+            let cpdag = load(format!("10-node-CPDAG-{}.mtx", 10 + (graph_id + 1) % 10));
+            let bounds = sid_with_undirected(&truth, &cpdag, UndirectedEdges::Bounds).unwrap();
+            for dag in [dag_in_class(&cpdag), consistent_extension(&cpdag).unwrap()] {
+                let (_, mistakes) = sid(&truth, &dag).unwrap();
+                assert!(bounds.lower.1 <= mistakes && mistakes <= bounds.upper.1);
+            }
+
+            // a DAG guess is bounded by its SID
+            let exact = sid_with_undirected(&truth, &truth, UndirectedEdges::Bounds).unwrap();
+            assert_eq!((exact.lower, exact.upper), ((0.0, 0), (0.0, 0)));
+            let guess = load(format!("10-node-DAG-{}.mtx", 10 + (graph_id + 1) % 10));
+            let exact = sid_with_undirected(&truth, &guess, UndirectedEdges::Bounds).unwrap();
+            assert_eq!(exact.lower, parent_aid(&truth, &guess));
+            assert_eq!(exact.upper, exact.lower);
+        }
+    }
+
+    #[test]
+    fn orients_stray_undirected_edges() {
+        // truth: 0 -> 1 -> 2 with a stray 1 -- 2
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 0],
+        ]);
+        assert!(matches!(
+            sid_with_undirected(&truth, &truth, UndirectedEdges::Reject),
+            Err(SIDError::TruthNotDAG)
+        ));
+        let oriented = sid_with_undirected(&truth, &truth, UndirectedEdges::Orient).unwrap();
+        assert!(oriented.oriented_truth && oriented.oriented_guess);
+        assert_eq!(oriented.lower, (0.0, 0));
+        assert!(matches!(
+            sid_with_undirected(&truth, &truth, UndirectedEdges::Bounds),
+            Err(SIDError::TruthNotDAG)
+        ));
+    }
+
+    #[test]
+    fn selected_pairs_match_parent_aid_on_dags() {
+        // anchors at parent directory of Cargo.toml
+        let mut testgraphs = std::path::PathBuf::new();
+        testgraphs.push("..");
+        testgraphs.push("testgraphs");
+        let load =
+            |name: String| crate::test::load_pdag_from_mtx(testgraphs.join(name).to_str().unwrap());
+
+        let pairs = [(0, 9), (3, 1), (5, 7), (9, 0), (2, 8)];
+        for graph_id in 10..=19 {
+            let truth = load(format!("10-node-DAG-{graph_id}.mtx"));
+            let guess = load(format!("10-node-DAG-{}.mtx", 10 + (graph_id + 1) % 10));
+            assert_eq!(
+                sid_selected_pairs(&truth, &guess, &pairs).unwrap(),
+                parent_aid_with(&truth, &guess, SelectedPairs(&pairs)).unwrap()
+            );
+
+            let cpdag = load(format!("10-node-CPDAG-{graph_id}.mtx"));
+            if cpdag.n_undirected_edges > 0 {
+                assert!(matches!(
+                    sid_selected_pairs(&truth, &cpdag, &pairs),
+                    Err(SIDError::GuessNotDAG)
+                ));
+            }
+        }
+
+        let truth = load("10-node-DAG-10.mtx".to_string());
+        assert!(matches!(
+            sid_selected_pairs(&truth, &truth, &[(4, 4)]),
+            Err(SIDError::InvalidPairs(PairsError::TreatmentIsEffect {
+                node: 4
+            }))
+        ));
+    }
+}
@@ -0,0 +1,390 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Evaluates a guess graph against partial ground truth, given as required and forbidden edges,
+//! such as edges elicited from domain experts, when no complete true graph exists
+
+use std::{error::Error, fmt};
+
+use crate::{graph_operations::shd::edge_type, PDAG};
+
+/// The reason an edge specification is invalid.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SpecificationError {
+    /// An edge refers to a node that is not in the graphs.
+    OutOfBounds {
+        /// the offending node
+        node: usize,
+        /// the number of nodes of the graphs
+        n_nodes: usize,
+    },
+    /// An edge points from a node into itself.
+    SelfLoop {
+        /// the offending node
+        node: usize,
+    },
+    /// The edge `from -> to` is required and also forbidden, or required in both directions.
+    Contradicts {
+        /// the tail of the offending edge
+        from: usize,
+        /// the head of the offending edge
+        to: usize,
+    },
+}
+
+impl Error for SpecificationError {}
+
+impl fmt::Display for SpecificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecificationError::OutOfBounds { node, n_nodes } => {
+                write!(
+                    f,
+                    "node {node} is out of bounds for graphs with {n_nodes} nodes"
+                )
+            }
+            SpecificationError::SelfLoop { node } => {
+                write!(f, "edge from node {node} into itself")
+            }
+            SpecificationError::Contradicts { from, to } => {
+                write!(
+                    f,
+                    "edge {from} -> {to} contradicts another edge of the specification"
+                )
+            }
+        }
+    }
+}
+
+/// Directed edges that a guess graph must have and must not have.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdgeSpecification {
+    n_nodes: usize,
+    /// the sorted required `(from, to)` edges
+    required: Vec<(usize, usize)>,
+    /// the sorted forbidden `(from, to)` edges
+    forbidden: Vec<(usize, usize)>,
+}
+
+impl EdgeSpecification {
+    /// Collects the `required` and `forbidden` edges `from -> to` of graphs with `n_nodes` nodes,
+    /// counting repeated edges once, or returns an error if a node is out of bounds, an edge is a self-loop,
+    /// or an edge is both required and forbidden or required in both directions.
+    /// To forbid any edge between two nodes, forbid both directions.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::{EdgeSpecification, SpecificationError};
+    ///
+    /// let specification = EdgeSpecification::new(3, &[(0, 1), (0, 1)], &[(1, 2), (2, 1)]).unwrap();
+    /// assert_eq!(specification.n_constraints(), 3);
+    /// assert_eq!(
+    ///     EdgeSpecification::new(3, &[(0, 1)], &[(0, 1)]),
+    ///     Err(SpecificationError::Contradicts { from: 0, to: 1 })
+    /// );
+    /// ```
+    pub fn new(
+        n_nodes: usize,
+        required: &[(usize, usize)],
+        forbidden: &[(usize, usize)],
+    ) -> Result<EdgeSpecification, SpecificationError> {
+        let collect = |edges: &[(usize, usize)]| {
+            for (from, to) in edges.iter().copied() {
+                if let Some(node) = [from, to].into_iter().find(|node| *node >= n_nodes) {
+                    return Err(SpecificationError::OutOfBounds { node, n_nodes });
+                }
+                if from == to {
+                    return Err(SpecificationError::SelfLoop { node: from });
+                }
+            }
+            let mut edges = edges.to_vec();
+            edges.sort_unstable();
+            edges.dedup();
+            Ok(edges)
+        };
+        let (required, forbidden) = (collect(required)?, collect(forbidden)?);
+        let contradiction = required.iter().find(|(from, to)| {
+            forbidden.binary_search(&(*from, *to)).is_ok()
+                || required.binary_search(&(*to, *from)).is_ok()
+        });
+        if let Some(&(from, to)) = contradiction {
+            return Err(SpecificationError::Contradicts { from, to });
+        }
+        Ok(EdgeSpecification {
+            n_nodes,
+            required,
+            forbidden,
+        })
+    }
+
+    /// The number of distinct required and forbidden edges.
+    pub fn n_constraints(&self) -> usize {
+        self.required.len() + self.forbidden.len()
+    }
+}
+
+/// The ways a guess graph can violate an [`EdgeSpecification`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Violation {
+    /// a required edge `from -> to` is missing, the nodes are not adjacent
+    MissingRequired,
+    /// a required edge `from -> to` is reversed to `from <- to`
+    ReversedRequired,
+    /// a required edge `from -> to` is undirected, `from -- to`
+    UndirectedRequired,
+    /// a forbidden edge `from -> to` is present
+    ForbiddenPresent,
+    /// a forbidden edge `from -> to` is undirected, `from -- to`, which does not rule it out
+    ForbiddenUndirected,
+}
+
+/// The weight of each type of [`Violation`], one by default.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ViolationWeights {
+    /// the weight of a [`Violation::MissingRequired`]
+    pub missing_required: f64,
+    /// the weight of a [`Violation::ReversedRequired`]
+    pub reversed_required: f64,
+    /// the weight of a [`Violation::UndirectedRequired`]
+    pub undirected_required: f64,
+    /// the weight of a [`Violation::ForbiddenPresent`]
+    pub forbidden_present: f64,
+    /// the weight of a [`Violation::ForbiddenUndirected`]
+    pub forbidden_undirected: f64,
+}
+
+impl Default for ViolationWeights {
+    fn default() -> Self {
+        ViolationWeights {
+            missing_required: 1.0,
+            reversed_required: 1.0,
+            undirected_required: 1.0,
+            forbidden_present: 1.0,
+            forbidden_undirected: 1.0,
+        }
+    }
+}
+
+impl ViolationWeights {
+    /// The weight of the `violation`.
+    pub fn of(&self, violation: Violation) -> f64 {
+        match violation {
+            Violation::MissingRequired => self.missing_required,
+            Violation::ReversedRequired => self.reversed_required,
+            Violation::UndirectedRequired => self.undirected_required,
+            Violation::ForbiddenPresent => self.forbidden_present,
+            Violation::ForbiddenUndirected => self.forbidden_undirected,
+        }
+    }
+}
+
+/// The violations of an [`EdgeSpecification`] by a guess graph, as returned by [`specification_violations`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpecificationViolations {
+    /// the `(violation, from, to)` tuples of the violated edges `from -> to`,
+    /// the required edges before the forbidden edges, each in ascending order
+    pub violations: Vec<(Violation, usize, usize)>,
+    /// the number of distinct required and forbidden edges
+    pub n_constraints: usize,
+}
+
+impl SpecificationViolations {
+    /// The number of violations of the given type.
+    pub fn count(&self, violation: Violation) -> usize {
+        self.violations
+            .iter()
+            .filter(|(v, _, _)| *v == violation)
+            .count()
+    }
+
+    /// The number of violated constraints divided by the number of constraints (in \[0,1]),
+    /// or 0 if there are no constraints, and the number of violated constraints.
+    pub fn distance(&self) -> (f64, usize) {
+        let violated = self.violations.len();
+        if self.n_constraints == 0 {
+            return (0.0, 0);
+        }
+        (violated as f64 / self.n_constraints as f64, violated)
+    }
+
+    /// The sum of the weights of the violations.
+    pub fn weighted(&self, weights: &ViolationWeights) -> f64 {
+        self.violations
+            .iter()
+            .map(|(violation, _, _)| weights.of(*violation))
+            .sum()
+    }
+}
+
+/// Counts the violations of the required and forbidden edges of the `specification` by the `guess` graph,
+/// to evaluate a guess against partial ground truth when no complete true graph exists.
+/// An undirected edge of the guess violates a required edge between its nodes, since it does not orient it,
+/// and a forbidden edge between its nodes, since it does not rule it out.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{
+///         specification_violations, EdgeSpecification, Violation, ViolationWeights,
+///     },
+///     PDAG,
+/// };
+///
+/// // guess: 0 -> 1, 2 -> 1, 2 -- 3
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0, 0],
+///     vec![0, 0, 0, 0],
+///     vec![0, 1, 0, 2],
+///     vec![0, 0, 0, 0],
+/// ]);
+///
+/// // 0 -> 1 and 1 -> 2 are required, 3 -> 2 is forbidden
+/// let specification = EdgeSpecification::new(4, &[(0, 1), (1, 2)], &[(3, 2)]).unwrap();
+/// let violations = specification_violations(&guess, &specification);
+/// assert_eq!(
+///     violations.violations,
+///     vec![
+///         (Violation::ReversedRequired, 1, 2),
+///         (Violation::ForbiddenUndirected, 3, 2),
+///     ]
+/// );
+/// assert_eq!(violations.distance(), (2.0 / 3.0, 2));
+///
+/// // an undirected edge that may be forbidden counts half
+/// let weights = ViolationWeights {
+///     forbidden_undirected: 0.5,
+///     ..Default::default()
+/// };
+/// assert_eq!(violations.weighted(&weights), 1.5);
+/// ```
+pub fn specification_violations(
+    guess: &PDAG,
+    specification: &EdgeSpecification,
+) -> SpecificationViolations {
+    assert_eq!(
+        specification.n_nodes, guess.n_nodes,
+        "specification must be of graphs of the same size"
+    );
+
+    let required = specification.required.iter().filter_map(|&(from, to)| {
+        let violation = match edge_type(guess, from, to) {
+            0 => Violation::MissingRequired,
+            1 => return None,
+            2 => Violation::ReversedRequired,
+            _ => Violation::UndirectedRequired,
+        };
+        Some((violation, from, to))
+    });
+    let forbidden = specification.forbidden.iter().filter_map(|&(from, to)| {
+        let violation = match edge_type(guess, from, to) {
+            1 => Violation::ForbiddenPresent,
+            3 => Violation::ForbiddenUndirected,
+            _ => return None,
+        };
+        Some((violation, from, to))
+    });
+    SpecificationViolations {
+        violations: required.chain(forbidden).collect(),
+        n_constraints: specification.n_constraints(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd, PDAG};
+
+    use super::{
+        specification_violations, EdgeSpecification, SpecificationError, Violation,
+        ViolationWeights,
+    };
+
+    #[test]
+    fn rejects_invalid_specifications() {
+        assert_eq!(
+            EdgeSpecification::new(3, &[(0, 3)], &[]),
+            Err(SpecificationError::OutOfBounds {
+                node: 3,
+                n_nodes: 3
+            })
+        );
+        assert_eq!(
+            EdgeSpecification::new(3, &[], &[(1, 1)]),
+            Err(SpecificationError::SelfLoop { node: 1 })
+        );
+        assert_eq!(
+            EdgeSpecification::new(3, &[(2, 1), (1, 2)], &[]),
+            Err(SpecificationError::Contradicts { from: 1, to: 2 })
+        );
+        // forbidding the reverse of a required edge is consistent
+        assert!(EdgeSpecification::new(3, &[(1, 2)], &[(2, 1)]).is_ok());
+    }
+
+    #[test]
+    fn counts_each_type_of_violation() {
+        // guess: 0 -> 1, 2 -- 3, 3 -> 4
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 2, 0],
+            vec![0, 0, 0, 0, 1],
+            vec![0, 0, 0, 0, 0],
+        ]);
+        let required = [(1, 0), (0, 2), (2, 3), (3, 4)];
+        let forbidden = [(0, 1), (3, 2), (4, 3)];
+        let specification = EdgeSpecification::new(5, &required, &forbidden).unwrap();
+        let violations = specification_violations(&guess, &specification);
+        assert_eq!(
+            violations.violations,
+            vec![
+                (Violation::MissingRequired, 0, 2),
+                (Violation::ReversedRequired, 1, 0),
+                (Violation::UndirectedRequired, 2, 3),
+                (Violation::ForbiddenPresent, 0, 1),
+                (Violation::ForbiddenUndirected, 3, 2),
+            ]
+        );
+        assert_eq!(violations.distance(), (5.0 / 7.0, 5));
+        assert_eq!(violations.count(Violation::ReversedRequired), 1);
+
+        let weights = ViolationWeights {
+            missing_required: 2.0,
+            reversed_required: 3.0,
+            undirected_required: 0.5,
+            forbidden_present: 4.0,
+            forbidden_undirected: 0.25,
+        };
+        assert_eq!(violations.weighted(&weights), 9.75);
+    }
+
+    #[test]
+    fn property_full_specification_of_a_dag_counts_its_shd() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..20 {
+            let truth = PDAG::random_dag(0.3, n, &mut rng);
+            let guess = PDAG::random_dag(0.3, n, &mut rng);
+            // require every edge of the truth, and forbid every other edge
+            let pairs = Vec::from_iter((0..n).flat_map(|v| (0..n).map(move |w| (v, w))));
+            let required = Vec::from_iter(
+                pairs
+                    .iter()
+                    .copied()
+                    .filter(|(v, w)| truth.children_of(*v).contains(w)),
+            );
+            let forbidden = Vec::from_iter(
+                pairs
+                    .iter()
+                    .copied()
+                    .filter(|(v, w)| v != w && !required.contains(&(*v, *w))),
+            );
+            let specification = EdgeSpecification::new(n, &required, &forbidden).unwrap();
+            let violations = specification_violations(&guess, &specification);
+            assert_eq!(violations.n_constraints, n * n - n);
+
+            // a reversed edge violates the required edge and the forbidden reverse edge
+            let reversed = violations.count(Violation::ReversedRequired);
+            assert_eq!(violations.distance().1 - reversed, shd(&truth, &guess).1);
+            assert_eq!(
+                specification_violations(&truth, &specification).violations,
+                vec![]
+            );
+        }
+    }
+}
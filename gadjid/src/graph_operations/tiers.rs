@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Grades a guess graph against a ground truth that is only known as a partial order of tiers,
+//! and optionally a skeleton, for practitioners without a full true DAG
+
+use crate::{
+    graph_operations::{selected_pairs::PairsError, KnownEdges},
+    PDAG,
+};
+
+/// A ground truth known only partially: a tier for each node, such that no node is caused by a node
+/// of a later tier while the causal order within a tier is unknown, and optionally the skeleton,
+/// that is, which pairs of nodes are adjacent.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TieredTruth {
+    /// the tier of each node
+    tiers: Vec<usize>,
+    /// the adjacent pairs of nodes, if known
+    skeleton: Option<KnownEdges>,
+}
+
+impl TieredTruth {
+    /// Collects the `tiers` of the nodes and the optional `skeleton`, a list of unordered adjacent pairs,
+    /// or returns an error if a pair of the skeleton is out of bounds or has the same node twice.
+    ///
+    /// ```
+    /// use gadjid::graph_operations::TieredTruth;
+    ///
+    /// // 0 and 1 precede 2, which is adjacent to both
+    /// let truth = TieredTruth::new(vec![0, 0, 1], Some(&[(0, 2), (1, 2)])).unwrap();
+    /// assert_eq!(truth.n_nodes(), 3);
+    /// assert!(TieredTruth::new(vec![0, 1], Some(&[(0, 2)])).is_err());
+    /// ```
+    pub fn new(
+        tiers: Vec<usize>,
+        skeleton: Option<&[(usize, usize)]>,
+    ) -> Result<TieredTruth, PairsError> {
+        let skeleton = skeleton
+            .map(|pairs| KnownEdges::new(tiers.len(), pairs))
+            .transpose()?;
+        Ok(TieredTruth { tiers, skeleton })
+    }
+
+    /// The number of nodes of the ground truth.
+    pub fn n_nodes(&self) -> usize {
+        self.tiers.len()
+    }
+}
+
+/// The distance of a guess graph to a [`TieredTruth`], as returned by [`tiered_distance`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TieredDistance {
+    /// The number of mistakes divided by the number of unordered pairs of nodes, in \[0,1].
+    pub normalized: f64,
+    /// The number of pairs of nodes whose edge in the guess contradicts the ground truth.
+    pub mistakes: usize,
+    /// The number of directed edges of the guess from a later to an earlier tier.
+    pub order_violations: usize,
+    /// The number of adjacent pairs in the guess that are not adjacent in the skeleton.
+    pub extra_adjacencies: usize,
+    /// The number of adjacent pairs in the skeleton that are not adjacent in the guess.
+    pub missing_adjacencies: usize,
+}
+
+/// Counts the pairs of nodes whose edge in the `guess` DAG or CPDAG contradicts the `truth`:
+/// directed edges from a node of a later tier to a node of an earlier tier, and,
+/// if the skeleton is known, adjacencies the skeleton lacks and adjacencies the guess lacks.
+/// Edges within a tier and undirected edges are not graded, since the ground truth does not
+/// constrain their orientation. Each pair of nodes is at most one mistake, so an edge
+/// that the skeleton lacks is not graded on its orientation; the distance is normalized like the SHD.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{tiered_distance, TieredTruth},
+///     PDAG,
+/// };
+///
+/// // 0 and 1 precede 2, which is adjacent to both
+/// let truth = TieredTruth::new(vec![0, 0, 1], Some(&[(0, 2), (1, 2)])).unwrap();
+/// // guess: 0 -> 1, 2 -> 0
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![1, 0, 0],
+/// ]);
+///
+/// let distance = tiered_distance(&truth, &guess);
+/// assert_eq!(distance.order_violations, 1);
+/// assert_eq!(distance.extra_adjacencies, 1);
+/// assert_eq!(distance.missing_adjacencies, 1);
+/// assert_eq!((distance.normalized, distance.mistakes), (1.0, 3));
+/// ```
+pub fn tiered_distance(truth: &TieredTruth, guess: &PDAG) -> TieredDistance {
+    assert!(
+        guess.n_nodes == truth.n_nodes(),
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(guess.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    let tiers = &truth.tiers;
+    let mut order_violations = 0;
+    let mut extra_adjacencies = 0;
+    let mut shared_adjacencies = 0;
+    for v in 0..guess.n_nodes {
+        let undirected = guess.adjacent_undirected_of(v).iter().map(|w| (*w, false));
+        let children = guess.children_of(v).iter().map(|w| (*w, true));
+        for (w, directed) in children.chain(undirected) {
+            // each undirected edge is met from both of its nodes
+            if !directed && w < v {
+                continue;
+            }
+            match &truth.skeleton {
+                Some(skeleton) if !skeleton.contains(v, w) => extra_adjacencies += 1,
+                _ => {
+                    shared_adjacencies += 1;
+                    if directed && tiers[v] > tiers[w] {
+                        order_violations += 1;
+                    }
+                }
+            }
+        }
+    }
+    let missing_adjacencies = match &truth.skeleton {
+        Some(skeleton) => skeleton.n_pairs() - shared_adjacencies,
+        None => 0,
+    };
+
+    let mistakes = order_violations + extra_adjacencies + missing_adjacencies;
+    let n = guess.n_nodes;
+    TieredDistance {
+        normalized: mistakes as f64 / (n * (n - 1) / 2) as f64,
+        mistakes,
+        order_violations,
+        extra_adjacencies,
+        missing_adjacencies,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd, PDAG};
+
+    use super::{tiered_distance, TieredTruth};
+
+    /// Returns the unordered adjacent pairs of the graph.
+    fn skeleton_of(graph: &PDAG) -> Vec<(usize, usize)> {
+        Vec::from_iter((0..graph.n_nodes).flat_map(|v| {
+            let adjacent = [
+                graph.parents_of(v),
+                graph.children_of(v),
+                graph.adjacent_undirected_of(v),
+            ]
+            .concat();
+            adjacent
+                .into_iter()
+                .filter(move |w| v < *w)
+                .map(move |w| (v, w))
+        }))
+    }
+
+    /// Returns the length of the longest directed path into each node of a DAG,
+    /// so that each node is in a later tier than its parents.
+    fn depths_of(dag: &PDAG) -> Vec<usize> {
+        let mut depths = vec![0; dag.n_nodes];
+        // a longest path has fewer than n edges, so n rounds of relaxation suffice
+        for _ in 0..dag.n_nodes {
+            for v in 0..dag.n_nodes {
+                depths[v] =
+                    Iterator::max(dag.parents_of(v).iter().map(|p| depths[*p] + 1)).unwrap_or(0);
+            }
+        }
+        depths
+    }
+
+    #[test]
+    fn property_depths_and_skeleton_of_a_dag_grade_like_shd() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let dag = PDAG::random_dag(0.3, n, &mut rng);
+            let guess = PDAG::random_dag(0.3, n, &mut rng);
+            // adjacent nodes of the DAG are in different tiers, so every reversed edge is graded
+            let truth = TieredTruth::new(depths_of(&dag), Some(&skeleton_of(&dag))).unwrap();
+            assert_eq!(
+                tiered_distance(&truth, &guess).mistakes,
+                shd(&dag, &guess).1
+            );
+            assert_eq!(tiered_distance(&truth, &dag).mistakes, 0);
+
+            // a single tier without skeleton constrains nothing
+            let unconstrained = TieredTruth::new(vec![0; n], None).unwrap();
+            assert_eq!(tiered_distance(&unconstrained, &guess).mistakes, 0);
+        }
+    }
+
+    #[test]
+    fn ignores_unconstrained_edges() {
+        // tiers {0, 1} < {2}, skeleton unknown; guess: 0 -> 1, 1 -- 2, 2 -> 0
+        let truth = TieredTruth::new(vec![0, 0, 1], None).unwrap();
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![1, 0, 0],
+        ]);
+        let distance = tiered_distance(&truth, &guess);
+        assert_eq!((distance.order_violations, distance.mistakes), (1, 1));
+    }
+}
@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Tracks the Parent-AID of a guess graph along the edge operations of a structure-learning run,
+//! such as the insertions, deletions, and reversals of greedy equivalence search or hill climbing,
+//! updating the distance incrementally instead of rescoring each snapshot.
+//!
+//! The mistakes of the Parent-AID add up over the treatments, and as long as the guess is a DAG,
+//! every effect is amenable in it and the mistakes of a treatment only depend on the guess through
+//! the parents of the treatment. An operation on the edge `from -> to` only changes the parents of `to`,
+//! and a reversal also those of `from`, so only these treatments are rescored. Snapshots with undirected
+//! edges rescore every treatment, since amenability in a CPDAG depends on the whole graph.
+
+use std::{error::Error, fmt};
+
+use rayon::prelude::*;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{selected_pairs::Effects, Aid},
+    LoadError, PDAG,
+};
+
+/// An operation on a directed edge of the guess graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeOperation {
+    /// adds the edge `from -> to` between two non-adjacent nodes
+    Insert,
+    /// removes the edge `from -> to`
+    Delete,
+    /// replaces the edge `from -> to` by `to -> from`
+    Reverse,
+}
+
+/// The reason an operation of a trajectory could not be applied.
+#[derive(Debug, PartialEq)]
+pub enum TrajectoryError {
+    /// The operation does not apply to the snapshot, such as deleting an edge that is not there.
+    Inapplicable {
+        /// the index of the operation
+        step: usize,
+        /// the operation
+        operation: EdgeOperation,
+        /// the node the edge points out of
+        from: usize,
+        /// the node the edge points into
+        to: usize,
+    },
+    /// The snapshot after the operation is not a PDAG, for example, because it has a cycle.
+    Load {
+        /// the index of the operation
+        step: usize,
+        /// the reason the snapshot could not be loaded
+        err: LoadError,
+    },
+}
+
+impl Error for TrajectoryError {}
+
+impl fmt::Display for TrajectoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrajectoryError::Inapplicable {
+                step,
+                operation,
+                from,
+                to,
+            } => {
+                let reason = match operation {
+                    EdgeOperation::Insert => "the nodes are already adjacent",
+                    EdgeOperation::Delete | EdgeOperation::Reverse => {
+                        "the edge is not in the graph"
+                    }
+                };
+                write!(
+                    f,
+                    "operation {step} ({operation:?} {from} -> {to}) does not apply: {reason}"
+                )
+            }
+            TrajectoryError::Load { step, err } => {
+                write!(f, "graph after operation {step} is not a PDAG: {err}")
+            }
+        }
+    }
+}
+
+/// Computes the parent adjustment intervention distance between the `truth` and each snapshot
+/// of a guess graph that starts out as `initial_guess` and changes by each of the `operations`,
+/// given as `(operation, from, to)` tuples of an edge `from -> to`, updating the distance incrementally,
+/// see the [module documentation](self) for how. Returns a tuple of (normalized error (in \[0,1]),
+/// total number of errors) for the initial guess and for the snapshot after each operation,
+/// or an error if an operation does not apply or leads to a snapshot that is not a PDAG.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{parent_aid, trajectory_aid, EdgeOperation},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, initial guess: 1 -> 0, 1 -> 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0, 0],
+///     vec![1, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+///
+/// let operations = [(EdgeOperation::Reverse, 1, 0), (EdgeOperation::Insert, 0, 2)];
+/// let trajectory = trajectory_aid(&truth, &guess, &operations).unwrap();
+/// assert_eq!(trajectory.len(), 3);
+/// assert_eq!(trajectory[0], parent_aid(&truth, &guess));
+/// // reversing 1 -> 0 recovers the true graph
+/// assert_eq!(trajectory[1], (0.0, 0));
+/// ```
+pub fn trajectory_aid(
+    truth: &PDAG,
+    initial_guess: &PDAG,
+    operations: &[(EdgeOperation, usize, usize)],
+) -> Result<Vec<(f64, usize)>, TrajectoryError> {
+    assert!(
+        initial_guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+    assert!(truth.n_nodes >= 2, "graph must contain at least 2 nodes");
+
+    crate::rayon::build_global();
+
+    let n = truth.n_nodes;
+    let all_nodes = Vec::from_iter(0..n);
+    let mistakes_of = |guess: &PDAG, treatments: &[usize]| {
+        let aid = Aid::parent(truth, guess);
+        treatments
+            .par_iter()
+            .map(|treatment| aid.mistakes(*treatment, Effects::All(&all_nodes)))
+            .collect::<Vec<usize>>()
+    };
+
+    let mut directed = FxHashSet::default();
+    let mut undirected = Vec::new();
+    for v in 0..n {
+        directed.extend(initial_guess.children_of(v).iter().map(|w| (v, *w)));
+        undirected.extend(
+            initial_guess
+                .adjacent_undirected_of(v)
+                .iter()
+                .filter(|w| v < **w)
+                .map(|w| (v, *w, 2)),
+        );
+    }
+
+    let mut mistakes = mistakes_of(initial_guess, &all_nodes);
+    let mut trajectory = Vec::with_capacity(operations.len() + 1);
+    let comparisons = n * n - n;
+    let mut record = |mistakes: &[usize]| {
+        let total: usize = mistakes.iter().sum();
+        trajectory.push((total as f64 / comparisons as f64, total));
+    };
+    record(&mistakes);
+
+    for (step, (operation, from, to)) in operations.iter().copied().enumerate() {
+        let applies = match operation {
+            EdgeOperation::Insert => {
+                !directed.contains(&(from, to))
+                    && !directed.contains(&(to, from))
+                    && !undirected.contains(&(from.min(to), from.max(to), 2))
+            }
+            EdgeOperation::Delete | EdgeOperation::Reverse => directed.contains(&(from, to)),
+        };
+        if !applies {
+            return Err(TrajectoryError::Inapplicable {
+                step,
+                operation,
+                from,
+                to,
+            });
+        }
+        match operation {
+            EdgeOperation::Insert => {
+                directed.insert((from, to));
+            }
+            EdgeOperation::Delete => {
+                directed.remove(&(from, to));
+            }
+            EdgeOperation::Reverse => {
+                directed.remove(&(from, to));
+                directed.insert((to, from));
+            }
+        }
+
+        let edges = directed.iter().map(|(v, w)| (*v, *w, 1));
+        let guess = PDAG::try_from_edges(n, edges.chain(undirected.iter().copied()))
+            .map_err(|err| TrajectoryError::Load { step, err })?;
+        let changed = if guess.n_undirected_edges > 0 {
+            all_nodes.clone()
+        } else if operation == EdgeOperation::Reverse {
+            vec![from, to]
+        } else {
+            vec![to]
+        };
+        for (treatment, treatment_mistakes) in changed.iter().zip(mistakes_of(&guess, &changed)) {
+            mistakes[*treatment] = treatment_mistakes;
+        }
+        record(&mistakes);
+    }
+    Ok(trajectory)
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng};
+
+    use crate::{graph_operations::parent_aid, LoadError, PDAG};
+
+    use super::{trajectory_aid, EdgeOperation, TrajectoryError};
+
+    /// Applies random operations that keep the graph acyclic to it,
+    /// and returns the operations alongside the snapshot after each of them.
+    fn random_run(
+        initial: &PDAG,
+        n_operations: usize,
+        rng: &mut impl Rng,
+    ) -> (Vec<(EdgeOperation, usize, usize)>, Vec<PDAG>) {
+        let n = initial.n_nodes;
+        let mut dense = initial.to_row_to_column_vecvec();
+        let (mut operations, mut snapshots) = (Vec::new(), Vec::new());
+        while operations.len() < n_operations {
+            let (from, to) = (rng.gen_range(0..n), rng.gen_range(0..n));
+            let mut changed = dense.clone();
+            let operation = match (dense[from][to], dense[to][from]) {
+                _ if from == to => continue,
+                (0, 0) => {
+                    changed[from][to] = 1;
+                    EdgeOperation::Insert
+                }
+                (1, 0) if rng.gen_bool(0.5) => {
+                    changed[from][to] = 0;
+                    EdgeOperation::Delete
+                }
+                (1, 0) => {
+                    (changed[from][to], changed[to][from]) = (0, 1);
+                    EdgeOperation::Reverse
+                }
+                _ => continue,
+            };
+            if let Ok(snapshot) = PDAG::try_from_dense_row_to_col(changed.clone()) {
+                operations.push((operation, from, to));
+                snapshots.push(snapshot);
+                dense = changed;
+            }
+        }
+        (operations, snapshots)
+    }
+
+    #[test]
+    fn property_trajectory_matches_rescored_snapshots() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..15 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            // a DAG guess is updated incrementally, a PDAG guess is rescored
+            for initial in [
+                PDAG::random_dag(0.3, n, &mut rng),
+                PDAG::random_pdag(0.3, n, &mut rng),
+            ] {
+                let (operations, snapshots) = random_run(&initial, 20, &mut rng);
+                let trajectory = trajectory_aid(&truth, &initial, &operations).unwrap();
+                assert_eq!(trajectory[0], parent_aid(&truth, &initial));
+                for (distance, snapshot) in trajectory[1..].iter().zip(&snapshots) {
+                    assert_eq!(*distance, parent_aid(&truth, snapshot));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reports_operations_that_do_not_apply() {
+        // 0 -> 1 -> 2
+        let chain = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        let operations = [(EdgeOperation::Delete, 0, 1), (EdgeOperation::Delete, 0, 1)];
+        assert_eq!(
+            trajectory_aid(&chain, &chain, &operations),
+            Err(TrajectoryError::Inapplicable {
+                step: 1,
+                operation: EdgeOperation::Delete,
+                from: 0,
+                to: 1
+            })
+        );
+        assert!(trajectory_aid(&chain, &chain, &[(EdgeOperation::Insert, 2, 1)]).is_err());
+        assert_eq!(
+            trajectory_aid(&chain, &chain, &[(EdgeOperation::Insert, 2, 0)]),
+            Err(TrajectoryError::Load {
+                step: 0,
+                err: LoadError::NotAcyclic
+            })
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Collects the per-treatment sets of the true graph that the adjustment identification distances
+//! materialize while counting mistakes, to report them alongside the distances in one pass
+
+use std::{collections::HashSet, hash::BuildHasher};
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput},
+    selected_pairs::{MistakeSink, Verdict},
+};
+
+/// The sets of a single treatment in the true graph, as returned alongside the distance
+/// for the [`WithTruthSets`] output.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TruthSets {
+    /// the nodes whose effect of the treatment is not amenable in the true graph (NAM), sorted
+    pub nam: Vec<usize>,
+    /// the nodes for which the adjustment set of the guess graph is not a valid adjustment set
+    /// in the true graph (NVA), as by [`not_validly_adjusted_for`](crate::graph_operations::not_validly_adjusted_for),
+    /// sorted; for the oset distance, whose adjustment set depends on the effect,
+    /// only the effects amenable in both graphs and claimed as possible effects by the guess graph are checked
+    pub nva: Vec<usize>,
+}
+
+/// Counts the mistakes of a single treatment and keeps the sets of the true graph it is told of.
+#[derive(Default)]
+pub(crate) struct TruthSetsSink {
+    mistakes: usize,
+    sets: TruthSets,
+}
+
+impl MistakeSink for TruthSetsSink {
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict) {
+        self.mistakes.record(treatments, effects, verdict);
+    }
+
+    fn record_truth_sets<S: BuildHasher>(
+        &mut self,
+        _: &[usize],
+        nam: &HashSet<usize, S>,
+        nva: &HashSet<usize, S>,
+    ) {
+        let sorted = |set: &HashSet<usize, S>| {
+            let mut sorted = Vec::from_iter(set.iter().copied());
+            sorted.sort_unstable();
+            sorted
+        };
+        self.sets = TruthSets {
+            nam: sorted(nam),
+            nva: sorted(nva),
+        };
+    }
+}
+
+/// The distance alongside the [`TruthSets`] of every treatment, indexed by treatment,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings, which the distance computes anyway.
+///
+/// ```
+/// use gadjid::{graph_operations::{ancestor_aid, ancestor_aid_with, WithTruthSets}, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let (distance, sets) = ancestor_aid_with(&truth, &guess, WithTruthSets);
+/// assert_eq!(distance, ancestor_aid(&truth, &guess));
+/// // all effects are amenable in a DAG
+/// assert!(sets.iter().all(|sets| sets.nam.is_empty()));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WithTruthSets;
+
+impl AidOutput for WithTruthSets {
+    type Output = ((f64, usize), Vec<TruthSets>);
+
+    fn compute(self, aid: &Aid) -> ((f64, usize), Vec<TruthSets>) {
+        let per_treatment = aid.each_treatment(|_| TruthSetsSink::default());
+        let verifier_mistakes_found = per_treatment.iter().map(|sink| sink.mistakes).sum();
+        (
+            (
+                verifier_mistakes_found as f64 / aid.n_pairs() as f64,
+                verifier_mistakes_found,
+            ),
+            Vec::from_iter(per_treatment.into_iter().map(|sink| sink.sets)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, get_nam, not_validly_adjusted_for, oset_aid,
+            oset_aid_with, parent_aid, parent_aid_with, WithTruthSets,
+        },
+        PDAG,
+    };
+
+    #[test]
+    fn parent_sets_of_a_reversed_edge() {
+        // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+        let truth = PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]]);
+        let guess = PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 1, 0]]);
+        let (distance, sets) = parent_aid_with(&truth, &guess, WithTruthSets);
+        assert_eq!(distance, parent_aid(&truth, &guess));
+        // all effects are amenable in a DAG, and the NVA sets are those of the guessed parents
+        // as given by `not_validly_adjusted_for` in the truth
+        assert!(sets.iter().all(|sets| sets.nam.is_empty()));
+        assert!(sets[0].nva.is_empty());
+        assert_eq!(sets[1].nva, vec![0, 2]);
+        assert_eq!(sets[2].nva, vec![0, 1]);
+    }
+
+    #[test]
+    fn property_sets_agree_with_distances_and_truth() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..20 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let (distance, parent_sets) = parent_aid_with(&truth, &guess, WithTruthSets);
+            assert_eq!(distance, parent_aid(&truth, &guess));
+            let (distance, ancestor_sets) = ancestor_aid_with(&truth, &guess, WithTruthSets);
+            assert_eq!(distance, ancestor_aid(&truth, &guess));
+            let (distance, oset_sets) = oset_aid_with(&truth, &guess, WithTruthSets);
+            assert_eq!(distance, oset_aid(&truth, &guess));
+
+            for treatment in 0..n {
+                let mut nam = Vec::from_iter(get_nam(&truth, &[treatment], None));
+                nam.sort_unstable();
+                if parent_sets[treatment] != Default::default() {
+                    assert_eq!(parent_sets[treatment].nam, nam);
+                    let parents = guess.parents_of(treatment);
+                    assert_eq!(
+                        parent_sets[treatment].nva,
+                        not_validly_adjusted_for(&truth, &[treatment], parents)
+                    );
+                }
+                if ancestor_sets[treatment] != Default::default() {
+                    assert_eq!(ancestor_sets[treatment].nam, nam);
+                }
+                if oset_sets[treatment] != Default::default() {
+                    assert_eq!(oset_sets[treatment].nam, nam);
+                }
+            }
+        }
+    }
+}
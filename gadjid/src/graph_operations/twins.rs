@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Groups treatments whose per-treatment mistakes are necessarily identical.
+//!
+//! Two nodes are twins if they have the same parents, children, and undirected neighbours,
+//! in the guess graph as well as in the true graph. Twins are not adjacent to each other,
+//! so swapping them maps both graphs onto themselves; this swap maps every walk from one twin
+//! to a walk from the other, and the adjustment set of one twin onto that of the other,
+//! so each twin, as treatment, has the same number of mistakes over all effects.
+//! Sparse graphs often have many twins, for example leaves with the same parent.
+//!
+//! Nodes without any edge in either graph are twins, too, but need no search at all:
+//! as treatment, no walk leaves them and their adjustment sets are empty,
+//! and as effect, no walk reaches them and they are in no adjustment set,
+//! so (T, Y) is amenable and validly adjusted for in both graphs whenever T or Y is isolated.
+
+use rustc_hash::FxHashMap;
+
+use crate::PDAG;
+
+/// Whether the node has no edges in either graph.
+pub(crate) fn is_isolated(truth: &PDAG, guess: &PDAG, node: usize) -> bool {
+    let edges = |graph: &PDAG| graph.node_edge_ranges[node + 1] - graph.node_edge_ranges[node];
+    edges(truth) == 0 && edges(guess) == 0
+}
+
+/// Returns one representative treatment per class of twins, alongside the class size,
+/// skipping isolated nodes, which cannot have any mistakes.
+/// Representatives are the smallest node of their class and are returned in ascending order.
+pub(crate) fn twin_classes(truth: &PDAG, guess: &PDAG) -> Vec<(usize, usize)> {
+    let neighbourhoods = |node: usize| {
+        [
+            truth.parents_of(node),
+            truth.children_of(node),
+            truth.adjacent_undirected_of(node),
+            guess.parents_of(node),
+            guess.children_of(node),
+            guess.adjacent_undirected_of(node),
+        ]
+    };
+
+    let mut class_of = FxHashMap::default();
+    let mut classes = Vec::<(usize, usize)>::new();
+    for node in (0..guess.n_nodes).filter(|v| !is_isolated(truth, guess, *v)) {
+        let class = *class_of
+            .entry(neighbourhoods(node))
+            .or_insert_with(|| classes.len());
+        match classes.get_mut(class) {
+            Some((_, size)) => *size += 1,
+            None => classes.push((node, 1)),
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid, ancestor_aid_with, oset_aid, oset_aid_with, parent_aid, parent_aid_with,
+            SelectedPairs,
+        },
+        PDAG,
+    };
+
+    use super::{is_isolated, twin_classes};
+
+    #[test]
+    fn groups_nodes_with_identical_neighbourhoods() {
+        // truth: 0 -> 1, 0 -> 2, 0 -> 3, 4 and 5 isolated
+        // guess: 0 -> 1, 0 -> 2, 3 -- 0, 4 and 5 isolated
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 1, 1, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 1, 2, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+            vec![0, 0, 0, 0, 0, 0],
+        ]);
+        assert_eq!(twin_classes(&truth, &guess), vec![(0, 1), (1, 2), (3, 1)]);
+        assert_eq!(twin_classes(&truth, &truth), vec![(0, 1), (1, 3)]);
+
+        let empty = PDAG::from_dense_row_to_col(vec![vec![0; 6]; 6]);
+        assert!(twin_classes(&empty, &empty).is_empty());
+        assert!(is_isolated(&truth, &empty, 4) && !is_isolated(&truth, &empty, 3));
+    }
+
+    #[test]
+    fn property_twins_do_not_change_distances() {
+        // the selected-pairs distances search from every treatment, so they serve as reference
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.05, n, &mut rng);
+            let guess = PDAG::random_pdag(0.05, n, &mut rng);
+            let all_pairs = Vec::from_iter(
+                (0..n).flat_map(|t| (0..n).filter(move |y| *y != t).map(move |y| (t, y))),
+            );
+            assert_eq!(
+                ancestor_aid(&truth, &guess),
+                ancestor_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+            assert_eq!(
+                oset_aid(&truth, &guess),
+                oset_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+            assert_eq!(
+                parent_aid(&truth, &guess),
+                parent_aid_with(&truth, &guess, SelectedPairs(&all_pairs))
+                    .unwrap()
+                    .distance()
+            );
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MPL-2.0
+//! A fast path for scoring many small graphs in a tight loop.
+//!
+//! The distances check their inputs and fan the treatments out to the thread pool on every call,
+//! which dominates their cost on graphs of a few dozen nodes. A [`ValidatedPair`] checks its graphs
+//! once, and its distances search from each treatment in turn on the calling thread.
+
+use std::{error::Error, fmt};
+
+use crate::{
+    graph_operations::{
+        quick_reject::identical,
+        selected_pairs::Effects,
+        shd,
+        twins::{is_isolated, twin_classes},
+        Aid,
+    },
+    PDAG,
+};
+
+#[derive(Debug, PartialEq)]
+/// Error that can occur when validating a pair of graphs.
+pub enum ValidationError {
+    /// The two graphs have different numbers of nodes.
+    NotSameSize {
+        /// the number of nodes of the true graph
+        truth: usize,
+        /// the number of nodes of the guess graph
+        guess: usize,
+    },
+    /// The graphs have fewer than 2 nodes, so there are no pairs of distinct nodes.
+    TooFewNodes {
+        /// the number of nodes of both graphs
+        n_nodes: usize,
+    },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NotSameSize { truth, guess } => write!(
+                f,
+                "the true graph has {truth} nodes, but the guess graph has {guess} nodes"
+            ),
+            ValidationError::TooFewNodes { n_nodes } => {
+                write!(f, "graph must contain at least 2 nodes, but has {n_nodes}")
+            }
+        }
+    }
+}
+
+impl Error for ValidationError {}
+
+/// A true and a guess DAG or CPDAG that are checked once to have the same number of at least 2 nodes,
+/// so that the distances between them skip these checks.
+/// The adjustment identification distances search from each treatment on the calling thread,
+/// without the overhead of the thread pool, and return the same as [`ancestor_aid`](super::ancestor_aid),
+/// [`oset_aid`](super::oset_aid), and [`parent_aid`](super::parent_aid).
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{parent_aid, ValidatedPair, ValidationError},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let pair = ValidatedPair::new(&truth, &guess).unwrap();
+/// assert_eq!(pair.parent_aid(), parent_aid(&truth, &guess));
+///
+/// let single = PDAG::from_dense_row_to_col(vec![vec![0]]);
+/// assert_eq!(
+///     ValidatedPair::new(&truth, &single).unwrap_err(),
+///     ValidationError::NotSameSize { truth: 3, guess: 1 }
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct ValidatedPair<'a> {
+    truth: &'a PDAG,
+    guess: &'a PDAG,
+}
+
+impl<'a> ValidatedPair<'a> {
+    /// Checks that the graphs have the same number of at least 2 nodes.
+    pub fn new(truth: &'a PDAG, guess: &'a PDAG) -> Result<Self, ValidationError> {
+        if truth.n_nodes != guess.n_nodes {
+            return Err(ValidationError::NotSameSize {
+                truth: truth.n_nodes,
+                guess: guess.n_nodes,
+            });
+        }
+        if truth.n_nodes < 2 {
+            return Err(ValidationError::TooFewNodes {
+                n_nodes: truth.n_nodes,
+            });
+        }
+        Ok(ValidatedPair { truth, guess })
+    }
+
+    /// The true graph.
+    pub fn truth(&self) -> &'a PDAG {
+        self.truth
+    }
+
+    /// The guess graph.
+    pub fn guess(&self) -> &'a PDAG {
+        self.guess
+    }
+
+    /// The ancestor adjustment intervention distance,
+    /// a tuple of (normalized error (in \[0,1]), total number of errors).
+    pub fn ancestor_aid(&self) -> (f64, usize) {
+        self.aid(Aid::ancestor(self.truth, self.guess))
+    }
+
+    /// The oset adjustment intervention distance,
+    /// a tuple of (normalized error (in \[0,1]), total number of errors).
+    pub fn oset_aid(&self) -> (f64, usize) {
+        let aid = Aid::oset(self.truth, self.guess);
+        aid.share_guess_ancestors();
+        self.aid(aid)
+    }
+
+    /// The parent adjustment intervention distance,
+    /// a tuple of (normalized error (in \[0,1]), total number of errors).
+    pub fn parent_aid(&self) -> (f64, usize) {
+        self.aid(Aid::parent(self.truth, self.guess))
+    }
+
+    /// The structural Hamming distance, see [`shd`].
+    pub fn shd(&self) -> (f64, usize) {
+        shd(self.truth, self.guess)
+    }
+
+    /// Sums the mistakes of the `aid` of one treatment per class of twins over all effects,
+    /// on the calling thread.
+    fn aid(&self, aid: Aid) -> (f64, usize) {
+        let (truth, guess) = (self.truth, self.guess);
+        if identical(truth, guess) {
+            return (0.0, 0);
+        }
+
+        let connected =
+            Vec::from_iter((0..guess.n_nodes).filter(|v| !is_isolated(truth, guess, *v)));
+        let verifier_mistakes_found: usize = twin_classes(truth, guess)
+            .into_iter()
+            .map(|(treatment, n_twins)| n_twins * aid.mistakes(treatment, Effects::All(&connected)))
+            .sum();
+
+        let n = guess.n_nodes;
+        let comparisons = n * n - n;
+        (
+            verifier_mistakes_found as f64 / comparisons as f64,
+            verifier_mistakes_found,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{ancestor_aid, oset_aid, parent_aid, shd},
+        PDAG,
+    };
+
+    use super::{ValidatedPair, ValidationError};
+
+    #[test]
+    fn property_validated_pair_agrees_with_distances() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            for (truth, guess) in [(&truth, &guess), (&truth, &truth)] {
+                let pair = ValidatedPair::new(truth, guess).unwrap();
+                assert_eq!(pair.ancestor_aid(), ancestor_aid(truth, guess));
+                assert_eq!(pair.oset_aid(), oset_aid(truth, guess));
+                assert_eq!(pair.parent_aid(), parent_aid(truth, guess));
+                assert_eq!(pair.shd(), shd(truth, guess));
+            }
+        }
+
+        let single = PDAG::from_dense_row_to_col(vec![vec![0]]);
+        assert_eq!(
+            ValidatedPair::new(&single, &single).unwrap_err(),
+            ValidationError::TooFewNodes { n_nodes: 1 }
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Exports a graph alongside per-node annotations as Cytoscape.js JSON,
+//! so that visualization frontends can render evaluation results without recomputing them
+
+use std::fmt::Write;
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::{get_d_pd_nam, oset_aid::optimal_adjustment_set_given_descendants},
+    PDAG,
+};
+
+/// An annotation layer that adds one data field to every node of the exported graph.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Layer {
+    /// The number of mistakes of each node as treatment, such as for a heat map,
+    /// stored in the `mistakes` field.
+    Mistakes(Vec<usize>),
+    /// Whether the graph is not amenable relative to the `treatment` and each node,
+    /// stored in the `not_amenable` field.
+    NotAmenable {
+        /// the treatment node
+        treatment: usize,
+    },
+    /// Whether each node is in the optimal adjustment set of the graph relative to
+    /// the `treatment` and the `effect`, stored in the `in_optimal_adjustment_set` field.
+    OptimalAdjustmentSet {
+        /// the treatment node
+        treatment: usize,
+        /// the effect node
+        effect: usize,
+    },
+}
+
+impl Layer {
+    /// Computes the value of this layer for each node of the graph.
+    fn values(&self, graph: &PDAG) -> (&'static str, Vec<String>) {
+        let membership = |set: FxHashSet<usize>| {
+            Vec::from_iter((0..graph.n_nodes).map(|v| set.contains(&v).to_string()))
+        };
+        match self {
+            Layer::Mistakes(mistakes) => {
+                assert!(
+                    mistakes.len() == graph.n_nodes,
+                    "there must be one number of mistakes per node"
+                );
+                (
+                    "mistakes",
+                    Vec::from_iter(mistakes.iter().map(|m| m.to_string())),
+                )
+            }
+            Layer::NotAmenable { treatment } => {
+                assert!(*treatment < graph.n_nodes, "treatment is out of bounds");
+                let (_, _, nam) = get_d_pd_nam(graph, &[*treatment]);
+                ("not_amenable", membership(nam))
+            }
+            Layer::OptimalAdjustmentSet { treatment, effect } => {
+                assert!(
+                    *treatment < graph.n_nodes && *effect < graph.n_nodes,
+                    "treatment and effect must be in bounds"
+                );
+                let (t_descendants, _, _) = get_d_pd_nam(graph, &[*treatment]);
+                let o_set = optimal_adjustment_set_given_descendants(
+                    graph,
+                    &[*treatment],
+                    &[*effect],
+                    &t_descendants,
+                );
+                ("in_optimal_adjustment_set", membership(o_set))
+            }
+        }
+    }
+}
+
+/// Exports the graph as Cytoscape.js JSON (`{"elements": {"nodes": [...], "edges": [...]}}`),
+/// where each node has the data fields `id` (its index as string) and one field per annotation layer,
+/// and each edge has the data fields `id`, `source`, `target`, and `directed`,
+/// which is `false` for undirected edges (whose source is the smaller node).
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{to_cytoscape_json, Layer},
+///     PDAG,
+/// };
+///
+/// // 0 -> 1
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1], //
+///     vec![0, 0],
+/// ]);
+/// let json = to_cytoscape_json(&graph, &[Layer::Mistakes(vec![2, 0])]);
+/// assert!(json.starts_with(r#"{"elements": {"nodes": [{"data": {"id": "0", "mistakes": 2}}"#));
+/// assert!(json.contains(r#"{"data": {"id": "0-1", "source": "0", "target": "1", "directed": true}}"#));
+/// ```
+pub fn to_cytoscape_json(graph: &PDAG, layers: &[Layer]) -> String {
+    let layers = Vec::from_iter(layers.iter().map(|layer| layer.values(graph)));
+
+    let mut nodes = Vec::with_capacity(graph.n_nodes);
+    for v in 0..graph.n_nodes {
+        let mut data = format!(r#""id": "{v}""#);
+        for (field, values) in layers.iter() {
+            // infallible, since writing to a String cannot fail
+            let _ = write!(data, r#", "{field}": {}"#, values[v]);
+        }
+        nodes.push(format!(r#"{{"data": {{{data}}}}}"#));
+    }
+
+    let mut edges = Vec::new();
+    for v in 0..graph.n_nodes {
+        let directed = graph.children_of(v).iter().map(|w| (*w, true));
+        let undirected = graph.adjacent_undirected_of(v).iter().filter(|w| v < **w);
+        for (w, is_directed) in directed.chain(undirected.map(|w| (*w, false))) {
+            edges.push(format!(
+                r#"{{"data": {{"id": "{v}-{w}", "source": "{v}", "target": "{w}", "directed": {is_directed}}}}}"#
+            ));
+        }
+    }
+
+    format!(
+        r#"{{"elements": {{"nodes": [{}], "edges": [{}]}}}}"#,
+        nodes.join(", "),
+        edges.join(", ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::PDAG;
+
+    use super::{to_cytoscape_json, Layer};
+
+    #[test]
+    fn exports_nodes_edges_and_layers() {
+        // 2 -> 0 -> 1, 2 -> 1, 0 -- 3
+        let pdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 2], //
+            vec![0, 0, 0, 0],
+            vec![1, 1, 0, 0],
+            vec![0, 0, 0, 0],
+        ]);
+        assert_eq!(
+            to_cytoscape_json(&pdag, &[]),
+            r#"{"elements": {"nodes": [{"data": {"id": "0"}}, {"data": {"id": "1"}}, {"data": {"id": "2"}}, {"data": {"id": "3"}}], "edges": [{"data": {"id": "0-1", "source": "0", "target": "1", "directed": true}}, {"data": {"id": "0-3", "source": "0", "target": "3", "directed": false}}, {"data": {"id": "2-0", "source": "2", "target": "0", "directed": true}}, {"data": {"id": "2-1", "source": "2", "target": "1", "directed": true}}]}}"#
+        );
+
+        let layers = [
+            Layer::Mistakes(vec![3, 0, 1, 2]),
+            Layer::NotAmenable { treatment: 0 },
+            Layer::OptimalAdjustmentSet {
+                treatment: 0,
+                effect: 1,
+            },
+        ];
+        let json = to_cytoscape_json(&pdag, &layers);
+        assert!(json.contains(
+            r#"{"data": {"id": "2", "mistakes": 1, "not_amenable": false, "in_optimal_adjustment_set": true}}"#
+        ));
+        assert!(json.contains(
+            r#"{"data": {"id": "3", "mistakes": 2, "not_amenable": true, "in_optimal_adjustment_set": false}}"#
+        ));
+    }
+}
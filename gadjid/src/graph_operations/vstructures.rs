@@ -0,0 +1,190 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Compares the v-structures of two graphs, which together with the skeleton
+//! determine the Markov equivalence class of a DAG
+
+use rayon::prelude::*;
+
+use crate::{graph_operations::shd::edge_type, PDAG};
+
+/// The v-structures present in one graph but not the other, and in both, as returned by [`vstructure_distance`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VStructureDistance {
+    /// v-structures of the true graph that are not v-structures of the guess graph
+    pub only_in_truth: usize,
+    /// v-structures of the guess graph that are not v-structures of the true graph
+    pub only_in_guess: usize,
+    /// v-structures of both graphs
+    pub shared: usize,
+}
+
+impl VStructureDistance {
+    /// The total number of mistakes, the v-structures present in only one of the graphs.
+    pub fn mistakes(&self) -> usize {
+        self.only_in_truth + self.only_in_guess
+    }
+
+    /// The mistakes divided by the number of v-structures present in either graph (in \[0,1]),
+    /// or 0 if neither graph has a v-structure.
+    pub fn normalized(&self) -> f64 {
+        let either = self.mistakes() + self.shared;
+        if either == 0 {
+            return 0f64;
+        }
+        self.mistakes() as f64 / either as f64
+    }
+}
+
+/// The v-structures `a -> c <- b` of a graph, the pairs of non-adjacent nodes `a < b` with directed edges
+/// into a common child `c`, as ascending `(a, c, b)` tuples. Undirected edges are not part of any v-structure.
+///
+/// ```
+/// use gadjid::{graph_operations::v_structures, PDAG};
+///
+/// // 0 -> 2 <- 1, 1 -> 3 <- 2
+/// let graph = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 0, 1, 0],
+///     vec![0, 0, 1, 1],
+///     vec![0, 0, 0, 1],
+///     vec![0, 0, 0, 0],
+/// ]);
+///
+/// // 1 and 2 are adjacent, so 1 -> 3 <- 2 is shielded
+/// assert_eq!(v_structures(&graph), vec![(0, 2, 1)]);
+/// ```
+pub fn v_structures(graph: &PDAG) -> Vec<(usize, usize, usize)> {
+    crate::rayon::build_global();
+
+    let mut v_structures: Vec<_> = (0..graph.n_nodes)
+        .into_par_iter()
+        .flat_map_iter(|c| {
+            let parents = graph.parents_of(c);
+            parents.iter().enumerate().flat_map(move |(i, &a)| {
+                parents[i + 1..]
+                    .iter()
+                    .filter(move |&&b| edge_type(graph, a, b) == 0)
+                    .map(move |&b| (a, c, b))
+            })
+        })
+        .collect();
+    v_structures.sort_unstable();
+    v_structures
+}
+
+/// Compares the v-structures (immoralities) of two graphs, counting those present in only one of the graphs
+/// and in both. Together with the missing and superfluous edges of [`shd_detailed`](super::shd_detailed),
+/// which compare the skeletons, this evaluates how well the Markov equivalence class of a DAG is recovered.
+///
+/// ```
+/// use gadjid::{graph_operations::vstructure_distance, PDAG};
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let distance = vstructure_distance(&truth, &guess);
+/// assert_eq!((distance.only_in_truth, distance.only_in_guess, distance.shared), (0, 1, 0));
+/// assert_eq!((distance.normalized(), distance.mistakes()), (1.0, 1));
+/// ```
+pub fn vstructure_distance(truth: &PDAG, guess: &PDAG) -> VStructureDistance {
+    assert!(
+        guess.n_nodes == truth.n_nodes,
+        "both graphs must contain the same number of nodes"
+    );
+
+    let (truth_v_structures, guess_v_structures) = (v_structures(truth), v_structures(guess));
+    let shared = truth_v_structures
+        .iter()
+        .filter(|v_structure| guess_v_structures.binary_search(v_structure).is_ok())
+        .count();
+    VStructureDistance {
+        only_in_truth: truth_v_structures.len() - shared,
+        only_in_guess: guess_v_structures.len() - shared,
+        shared,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd::edge_type, PDAG};
+
+    use super::{v_structures, vstructure_distance, VStructureDistance};
+
+    /// The v-structures found by checking every triple of nodes.
+    fn v_structures_by_triples(graph: &PDAG) -> Vec<(usize, usize, usize)> {
+        let n = graph.n_nodes;
+        let mut v_structures = Vec::new();
+        for a in 0..n {
+            for c in 0..n {
+                for b in a + 1..n {
+                    let into_c = |v: usize| graph.parents_of(c).contains(&v);
+                    if into_c(a) && into_c(b) && edge_type(graph, a, b) == 0 {
+                        v_structures.push((a, c, b));
+                    }
+                }
+            }
+        }
+        v_structures
+    }
+
+    #[test]
+    fn property_v_structures_agree_with_triples() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..30 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            assert_eq!(v_structures(&truth), v_structures_by_triples(&truth));
+
+            let distance = vstructure_distance(&truth, &guess);
+            assert_eq!(
+                distance.only_in_truth + distance.shared,
+                v_structures(&truth).len()
+            );
+            assert_eq!(
+                distance.only_in_guess + distance.shared,
+                v_structures(&guess).len()
+            );
+            assert_eq!(
+                vstructure_distance(&guess, &truth).mistakes(),
+                distance.mistakes()
+            );
+            assert_eq!(vstructure_distance(&truth, &truth).mistakes(), 0);
+        }
+    }
+
+    #[test]
+    fn markov_equivalent_dags_share_v_structures() {
+        // 0 -> 1 -> 2 -> 3 <- 4 and 0 <- 1 <- 2 -> 3 <- 4
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0, 0, 0],
+            vec![0, 0, 1, 0, 0],
+            vec![0, 0, 0, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0, 0, 0],
+            vec![1, 0, 0, 0, 0],
+            vec![0, 1, 0, 1, 0],
+            vec![0, 0, 0, 0, 0],
+            vec![0, 0, 0, 1, 0],
+        ]);
+        assert_eq!(
+            vstructure_distance(&truth, &guess),
+            VStructureDistance {
+                only_in_truth: 0,
+                only_in_guess: 0,
+                shared: 1
+            }
+        );
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Collects the `(treatment, effect)` pairs that the adjustment identification distances count as mistakes,
+//! alongside the verdict that makes each of them a mistake
+
+use crate::graph_operations::{
+    aid::{Aid, AidOutput},
+    selected_pairs::{MistakeSink, Verdict},
+};
+
+/// A `(treatment, effect)` pair that a distance counts as a mistake, which witnesses the distance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Witness {
+    /// the treatment
+    pub treatment: usize,
+    /// the effect
+    pub effect: usize,
+    /// why the claim of the guess graph about the effect of the treatment is a mistake
+    pub verdict: Verdict,
+}
+
+/// Collects a [`Witness`] for every `(treatment, effect)` pair of a mistake, in the order they are recorded;
+/// a mistake on a set of treatments or effects is collected once for each of its pairs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WitnessCollector(pub Vec<Witness>);
+
+impl MistakeSink for WitnessCollector {
+    fn record(&mut self, treatments: &[usize], effects: &[usize], verdict: Verdict) {
+        if verdict != Verdict::Correct {
+            for treatment in treatments {
+                self.0.extend(effects.iter().map(|effect| Witness {
+                    treatment: *treatment,
+                    effect: *effect,
+                    verdict,
+                }));
+            }
+        }
+    }
+}
+
+/// An adjustment identification distance alongside the witnesses of its mistakes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WitnessedDistance {
+    /// The number of mistakes divided by the number of `(treatment, effect)` pairs, in \[0,1].
+    pub normalized: f64,
+    /// The number of mistakes.
+    pub mistakes: usize,
+    /// The witness of every mistake, sorted by `(treatment, effect)`.
+    pub witnesses: Vec<Witness>,
+}
+
+impl WitnessedDistance {
+    /// The tuple of (normalized error (in \[0,1]), total number of errors)
+    /// that the distance without the witnesses returns.
+    pub fn distance(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+}
+
+/// The distance alongside a [`Witness`] for each of its mistakes,
+/// searching from every treatment separately, even if it is a twin of another,
+/// when passed to [`ancestor_aid_with`](super::ancestor_aid_with) and its siblings.
+///
+/// ```
+/// use gadjid::{
+///     graph_operations::{ancestor_aid, ancestor_aid_with, Verdict, Witness, WithWitnesses},
+///     PDAG,
+/// };
+///
+/// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+/// let truth = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 1],
+///     vec![0, 0, 0],
+/// ]);
+/// let guess = PDAG::from_dense_row_to_col(vec![
+///     vec![0, 1, 0],
+///     vec![0, 0, 0],
+///     vec![0, 1, 0],
+/// ]);
+///
+/// let witnessed = ancestor_aid_with(&truth, &guess, WithWitnesses);
+/// assert_eq!(witnessed.distance(), ancestor_aid(&truth, &guess));
+/// // the guess claims that 2 is not an effect of 0
+/// assert_eq!(
+///     witnessed.witnesses[0],
+///     Witness { treatment: 0, effect: 2, verdict: Verdict::WrongOrder }
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct WithWitnesses;
+
+impl AidOutput for WithWitnesses {
+    type Output = WitnessedDistance;
+
+    fn compute(self, aid: &Aid) -> WitnessedDistance {
+        // twins make the same mistakes with different nodes, so each is searched from
+        let mut witnesses = Vec::from_iter(
+            aid.each_treatment(|_| WitnessCollector::default())
+                .into_iter()
+                .flat_map(|collector| collector.0),
+        );
+        witnesses.sort_unstable_by_key(|witness| (witness.treatment, witness.effect));
+
+        let mistakes = witnesses.len();
+        WitnessedDistance {
+            normalized: mistakes as f64 / aid.n_pairs() as f64,
+            mistakes,
+            witnesses,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{
+            ancestor_aid_with, oset_aid_with, parent_aid_with, Detailed, WithBreakdown,
+            WithWitnesses,
+        },
+        PDAG,
+    };
+
+    use super::Verdict;
+
+    #[test]
+    fn property_witnesses_agree_with_mistake_matrix_and_breakdown() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..25 {
+            let truth = PDAG::random_pdag(0.3, n, &mut rng);
+            let guess = PDAG::random_pdag(0.3, n, &mut rng);
+            let witnessed = [
+                ancestor_aid_with(&truth, &guess, WithWitnesses),
+                oset_aid_with(&truth, &guess, WithWitnesses),
+                parent_aid_with(&truth, &guess, WithWitnesses),
+            ];
+            let detailed = [
+                ancestor_aid_with(&truth, &guess, Detailed),
+                oset_aid_with(&truth, &guess, Detailed),
+                parent_aid_with(&truth, &guess, Detailed),
+            ];
+            let breakdowns = [
+                ancestor_aid_with(&truth, &guess, WithBreakdown),
+                oset_aid_with(&truth, &guess, WithBreakdown),
+                parent_aid_with(&truth, &guess, WithBreakdown),
+            ];
+            for ((witnessed, detailed), breakdown) in witnessed.iter().zip(detailed).zip(breakdowns)
+            {
+                assert_eq!(witnessed.distance(), detailed.distance());
+                let pairs =
+                    Vec::from_iter(witnessed.witnesses.iter().map(|w| (w.treatment, w.effect)));
+                assert_eq!(pairs, detailed.mistake_pairs());
+
+                let count = |verdicts: &[Verdict]| {
+                    witnessed
+                        .witnesses
+                        .iter()
+                        .filter(|w| verdicts.contains(&w.verdict))
+                        .count()
+                };
+                assert_eq!(count(&[Verdict::WrongOrder]), breakdown.wrong_order);
+                assert_eq!(
+                    count(&[Verdict::AmenableInGuess, Verdict::NotAmenableInGuess]),
+                    breakdown.amenability
+                );
+                assert_eq!(
+                    count(&[Verdict::WronglyAdjusted]),
+                    breakdown.invalid_adjustment
+                );
+            }
+        }
+    }
+}
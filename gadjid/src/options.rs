@@ -0,0 +1,438 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Parses the string options of the language bindings, such as the names of distances
+//! and edge directions, so that all bindings accept the same values and report the valid ones alike
+
+use std::{error::Error, fmt};
+
+use crate::{
+    graph_operations::{
+        AidDistance, Distance, EdgeOperation, Linkage, Normalization, PairsNormalization,
+        RandomGraphs, UndirectedEdges, Violation,
+    },
+    scm::ParentModel,
+    Convention, MissingPolicy, TieBreak,
+};
+
+/// Error for a string that names none of the values of an option.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptionError {
+    /// the name of the option, such as `edge_direction`
+    pub option: &'static str,
+    /// the string that names none of the valid values
+    pub value: String,
+    /// the names of the valid values
+    pub valid: Vec<&'static str>,
+}
+
+impl Error for OptionError {}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quoted = Vec::from_iter(self.valid.iter().map(|name| format!(r#""{name}""#)));
+        write!(f, "{} string argument must be ", self.option)?;
+        match quoted.as_slice() {
+            [] => write!(f, "valid")?,
+            [only] => write!(f, "{only}")?,
+            [first, second] => write!(f, "either {first} or {second}")?,
+            [init @ .., last] => write!(f, "one of {}, or {last}", init.join(", "))?,
+        }
+        write!(f, r#", not "{}""#, self.value)
+    }
+}
+
+/// An option whose values are named by strings.
+pub trait NamedOption: Copy + Sized + 'static {
+    /// The name of the option, such as `edge_direction`.
+    const OPTION: &'static str;
+    /// All values of the option, in the order they are listed in error messages.
+    const ALL: &'static [Self];
+
+    /// The name of the value.
+    fn name(&self) -> &'static str;
+
+    /// Returns the value with the given name,
+    /// or an [`OptionError`] that lists the names of all values.
+    ///
+    /// ```
+    /// use gadjid::options::{EdgeDirection, NamedOption};
+    ///
+    /// assert_eq!(
+    ///     EdgeDirection::parse("from row to column"),
+    ///     Ok(EdgeDirection::RowToColumn)
+    /// );
+    /// assert_eq!(
+    ///     EdgeDirection::parse("row to column").unwrap_err().to_string(),
+    ///     r#"edge_direction string argument must be either "from row to column" or "from column to row", not "row to column""#
+    /// );
+    /// ```
+    fn parse(name: &str) -> Result<Self, OptionError> {
+        Self::parse_among(name, Self::ALL)
+    }
+
+    /// Returns the value among the `allowed` values with the given name,
+    /// or an [`OptionError`] that lists the names of the `allowed` values,
+    /// for functions that only support some values of an option.
+    ///
+    /// ```
+    /// use gadjid::{graph_operations::Distance, options::NamedOption};
+    ///
+    /// let aids = [Distance::AncestorAid, Distance::OsetAid, Distance::ParentAid];
+    /// assert_eq!(Distance::parse_among("oset_aid", &aids), Ok(Distance::OsetAid));
+    /// assert!(Distance::parse_among("shd", &aids).is_err());
+    /// ```
+    fn parse_among(name: &str, allowed: &[Self]) -> Result<Self, OptionError> {
+        match allowed.iter().find(|value| value.name() == name) {
+            Some(value) => Ok(*value),
+            None => Err(OptionError {
+                option: Self::OPTION,
+                value: name.to_string(),
+                valid: Vec::from_iter(allowed.iter().map(|value| value.name())),
+            }),
+        }
+    }
+}
+
+/// How an adjacency matrix codes the direction of its edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdgeDirection {
+    /// a `1` in row `r` and column `c` codes a directed edge `r -> c`
+    RowToColumn,
+    /// a `1` in row `r` and column `c` codes a directed edge `c -> r`
+    ColumnToRow,
+}
+
+impl EdgeDirection {
+    /// Whether the edges are coded from row to column.
+    pub fn is_row_to_col(&self) -> bool {
+        *self == EdgeDirection::RowToColumn
+    }
+}
+
+impl NamedOption for EdgeDirection {
+    const OPTION: &'static str = "edge_direction";
+    const ALL: &'static [Self] = &[EdgeDirection::RowToColumn, EdgeDirection::ColumnToRow];
+
+    fn name(&self) -> &'static str {
+        match self {
+            EdgeDirection::RowToColumn => "from row to column",
+            EdgeDirection::ColumnToRow => "from column to row",
+        }
+    }
+}
+
+impl NamedOption for Distance {
+    const OPTION: &'static str = "distance";
+    const ALL: &'static [Self] = &Distance::ALL;
+
+    fn name(&self) -> &'static str {
+        Distance::name(self)
+    }
+}
+
+impl NamedOption for AidDistance {
+    const OPTION: &'static str = "distance";
+    const ALL: &'static [Self] = &AidDistance::ALL;
+
+    fn name(&self) -> &'static str {
+        Distance::from(*self).name()
+    }
+}
+
+impl NamedOption for Linkage {
+    const OPTION: &'static str = "linkage";
+    const ALL: &'static [Self] = &[Linkage::Single, Linkage::Complete, Linkage::Average];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Linkage::Single => "single",
+            Linkage::Complete => "complete",
+            Linkage::Average => "average",
+        }
+    }
+}
+
+impl NamedOption for EdgeOperation {
+    const OPTION: &'static str = "operation";
+    const ALL: &'static [Self] = &[
+        EdgeOperation::Insert,
+        EdgeOperation::Delete,
+        EdgeOperation::Reverse,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            EdgeOperation::Insert => "insert",
+            EdgeOperation::Delete => "delete",
+            EdgeOperation::Reverse => "reverse",
+        }
+    }
+}
+
+impl NamedOption for UndirectedEdges {
+    const OPTION: &'static str = "undirected";
+    const ALL: &'static [Self] = &[
+        UndirectedEdges::Reject,
+        UndirectedEdges::Orient,
+        UndirectedEdges::Bounds,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            UndirectedEdges::Reject => "error",
+            UndirectedEdges::Orient => "orient",
+            UndirectedEdges::Bounds => "bounds",
+        }
+    }
+}
+
+impl NamedOption for Normalization {
+    const OPTION: &'static str = "normalization";
+    const ALL: &'static [Self] = &[
+        Normalization::AllPairs,
+        Normalization::AmenablePairsInTruth,
+        Normalization::GradedPairs,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Normalization::AllPairs => "all_pairs",
+            Normalization::AmenablePairsInTruth => "amenable_pairs_in_truth",
+            Normalization::GradedPairs => "graded_pairs",
+        }
+    }
+}
+
+impl NamedOption for Convention {
+    const OPTION: &'static str = "convention";
+    const ALL: &'static [Self] = &[
+        Convention::Gadjid,
+        Convention::PcalgAmatCpdag,
+        Convention::PcalgAmatPag,
+        Convention::SymmetricBool,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Convention::Gadjid => "gadjid",
+            Convention::PcalgAmatCpdag => "pcalg amat.cpdag",
+            Convention::PcalgAmatPag => "pcalg amat.pag",
+            Convention::SymmetricBool => "symmetric bool",
+        }
+    }
+}
+
+impl NamedOption for MissingPolicy {
+    const OPTION: &'static str = "missing";
+    const ALL: &'static [Self] = &[
+        MissingPolicy::Error,
+        MissingPolicy::Absent,
+        MissingPolicy::Undirected,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            MissingPolicy::Error => "error",
+            MissingPolicy::Absent => "absent",
+            MissingPolicy::Undirected => "undirected",
+        }
+    }
+}
+
+impl NamedOption for Violation {
+    const OPTION: &'static str = "violation";
+    const ALL: &'static [Self] = &[
+        Violation::MissingRequired,
+        Violation::ReversedRequired,
+        Violation::UndirectedRequired,
+        Violation::ForbiddenPresent,
+        Violation::ForbiddenUndirected,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Violation::MissingRequired => "missing required",
+            Violation::ReversedRequired => "reversed required",
+            Violation::UndirectedRequired => "undirected required",
+            Violation::ForbiddenPresent => "forbidden present",
+            Violation::ForbiddenUndirected => "forbidden undirected",
+        }
+    }
+}
+
+impl NamedOption for PairsNormalization {
+    const OPTION: &'static str = "normalization";
+    const ALL: &'static [Self] = &[PairsNormalization::Selected, PairsNormalization::Population];
+
+    fn name(&self) -> &'static str {
+        match self {
+            PairsNormalization::Selected => "selected",
+            PairsNormalization::Population => "population",
+        }
+    }
+}
+
+/// The kind of random graphs that a null distribution is sampled from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomGraphKind {
+    /// DAGs, see [`RandomGraphs::Dag`]
+    Dag,
+    /// PDAGs, see [`RandomGraphs::Pdag`]
+    Pdag,
+}
+
+impl RandomGraphKind {
+    /// The random graphs of this kind with independent edges of the given density.
+    pub fn with_edge_density(&self, edge_density: f64) -> RandomGraphs {
+        match self {
+            RandomGraphKind::Dag => RandomGraphs::Dag { edge_density },
+            RandomGraphKind::Pdag => RandomGraphs::Pdag { edge_density },
+        }
+    }
+}
+
+impl NamedOption for RandomGraphKind {
+    const OPTION: &'static str = "random_graphs";
+    const ALL: &'static [Self] = &[RandomGraphKind::Dag, RandomGraphKind::Pdag];
+
+    fn name(&self) -> &'static str {
+        match self {
+            RandomGraphKind::Dag => "dag",
+            RandomGraphKind::Pdag => "pdag",
+        }
+    }
+}
+
+/// How ties are broken in a topological order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TieBreakKind {
+    /// see [`TieBreak::Index`]
+    Index,
+    /// see [`TieBreak::Degree`]
+    Degree,
+    /// see [`TieBreak::Random`]
+    Random,
+}
+
+impl TieBreakKind {
+    /// The tie break of this kind, which draws the order from the `seed` if it is [`TieBreakKind::Random`].
+    pub fn with_seed(&self, seed: u64) -> TieBreak {
+        match self {
+            TieBreakKind::Index => TieBreak::Index,
+            TieBreakKind::Degree => TieBreak::Degree,
+            TieBreakKind::Random => TieBreak::Random { seed },
+        }
+    }
+}
+
+impl NamedOption for TieBreakKind {
+    const OPTION: &'static str = "tie_break";
+    const ALL: &'static [Self] = &[
+        TieBreakKind::Index,
+        TieBreakKind::Degree,
+        TieBreakKind::Random,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            TieBreakKind::Index => "index",
+            TieBreakKind::Degree => "degree",
+            TieBreakKind::Random => "random",
+        }
+    }
+}
+
+/// The random graph model that the parents of the variables of an SCM are sampled from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParentModelKind {
+    /// see [`ParentModel::ErdosRenyi`]
+    ErdosRenyi,
+    /// see [`ParentModel::ScaleFree`]
+    ScaleFree,
+}
+
+impl ParentModelKind {
+    /// The model of this kind with the given `edge_density`, used by [`ParentModel::ErdosRenyi`],
+    /// or `parents_per_node`, used by [`ParentModel::ScaleFree`].
+    pub fn with_parameters(&self, edge_density: f64, parents_per_node: usize) -> ParentModel {
+        match self {
+            ParentModelKind::ErdosRenyi => ParentModel::ErdosRenyi { edge_density },
+            ParentModelKind::ScaleFree => ParentModel::ScaleFree { parents_per_node },
+        }
+    }
+}
+
+impl NamedOption for ParentModelKind {
+    const OPTION: &'static str = "model";
+    const ALL: &'static [Self] = &[ParentModelKind::ErdosRenyi, ParentModelKind::ScaleFree];
+
+    fn name(&self) -> &'static str {
+        match self {
+            ParentModelKind::ErdosRenyi => "erdos_renyi",
+            ParentModelKind::ScaleFree => "scale_free",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        graph_operations::{
+            AidDistance, Distance, EdgeOperation, Linkage, Normalization, PairsNormalization,
+            UndirectedEdges, Violation,
+        },
+        Convention, MissingPolicy,
+    };
+
+    use super::{
+        EdgeDirection, NamedOption, OptionError, ParentModelKind, RandomGraphKind, TieBreakKind,
+    };
+
+    /// Checks that every value is parsed from its name, and that unknown names list all values.
+    fn round_trips<T: NamedOption + PartialEq + std::fmt::Debug>() {
+        for value in T::ALL {
+            assert_eq!(T::parse(value.name()), Ok(*value));
+        }
+        let error = T::parse("unknown").unwrap_err();
+        assert_eq!(error.valid.len(), T::ALL.len());
+    }
+
+    #[test]
+    fn parses_all_names() {
+        round_trips::<EdgeDirection>();
+        round_trips::<Distance>();
+        round_trips::<AidDistance>();
+        round_trips::<Linkage>();
+        round_trips::<EdgeOperation>();
+        round_trips::<UndirectedEdges>();
+        round_trips::<Normalization>();
+        round_trips::<Convention>();
+        round_trips::<RandomGraphKind>();
+        round_trips::<ParentModelKind>();
+        round_trips::<PairsNormalization>();
+        round_trips::<MissingPolicy>();
+        round_trips::<Violation>();
+        round_trips::<TieBreakKind>();
+    }
+
+    #[test]
+    fn lists_valid_values() {
+        assert_eq!(
+            Distance::parse("sid").unwrap_err(),
+            OptionError {
+                option: "distance",
+                value: "sid".to_string(),
+                valid: vec!["ancestor_aid", "oset_aid", "parent_aid", "shd"],
+            }
+        );
+        assert_eq!(
+            Distance::parse("sid").unwrap_err().to_string(),
+            r#"distance string argument must be one of "ancestor_aid", "oset_aid", "parent_aid", or "shd", not "sid""#
+        );
+        assert_eq!(
+            Distance::parse_among("shd", &[Distance::OsetAid])
+                .unwrap_err()
+                .to_string(),
+            r#"distance string argument must be "oset_aid", not "shd""#
+        );
+    }
+}
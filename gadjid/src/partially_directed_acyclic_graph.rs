@@ -1,14 +1,14 @@
 // SPDX-License-Identifier: MPL-2.0
 //! Defines the PDAG struct that is a supertype of DAGs and CPDAGs.
 
-use core::panic;
-use rand::distributions::Distribution;
+use rand::{distributions::Distribution, seq::SliceRandom, SeedableRng};
 use rustc_hash::FxHashMap;
-use std::{error::Error, fmt};
+use std::{cmp::Reverse, collections::BinaryHeap, error::Error, fmt};
 
 use crate::{
     ascending_list_utils::ascending_lists_first_shared_element,
     graph_loading::edgelist::{ColumnMajorOrder, Edgelist, RowMajorOrder},
+    EdgelistIterator,
 };
 
 /// PDAG edge enum defined from a graph traversal perspective.
@@ -86,19 +86,7 @@ pub enum Structure {
 /// Will display the adjacency matrix of the PDAG, encoded as row-to-column adjacency matrix.
 impl fmt::Display for PDAG {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut adjacency = vec![vec![0; self.n_nodes]; self.n_nodes];
-
-        #[allow(clippy::needless_range_loop)]
-        for node in 0..self.n_nodes {
-            for child in self.children_of(node).iter().copied() {
-                adjacency[node][child] = 1;
-            }
-            for undirected in self.adjacent_undirected_of(node).iter().copied() {
-                adjacency[node][undirected] = 2;
-            }
-        }
-
-        for row in adjacency {
+        for row in self.to_row_to_column_vecvec() {
             writeln!(f)?;
             for val in row {
                 write!(f, "{} ", val)?;
@@ -108,9 +96,27 @@ impl fmt::Display for PDAG {
     }
 }
 
+/// P(edge between X and Y is directed) given that there is an edge between X and Y,
+/// in the random PDAGs generated by [`PDAG::random_pdag`]
+pub(crate) const P_DIRECTED: f64 = 0.8;
+
 impl PDAG {
     /// Given a node, return all nodes reachable by an incoming edge. Nodes will be returned in sorted
     /// ascending order
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(pdag.parents_of(2), &[0, 1]);
+    /// assert!(pdag.parents_of(3).is_empty());
+    /// ```
     pub fn parents_of(&self, node: usize) -> &[usize] {
         let start = self.node_edge_ranges[node];
         let end = self.node_edge_ranges[node + 1];
@@ -122,6 +128,20 @@ impl PDAG {
     }
     /// Given a node, return all nodes reachable by an outgoing edge. Nodes will be returned in sorted
     /// ascending order
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(pdag.children_of(0), &[2]);
+    /// assert!(pdag.children_of(2).is_empty());
+    /// ```
     pub fn children_of(&self, node: usize) -> &[usize] {
         let start = self.node_edge_ranges[node];
         let end = self.node_edge_ranges[node + 1];
@@ -135,6 +155,20 @@ impl PDAG {
 
     /// Given a node, return all nodes reachable via an undirected edge. Nodes will be returned in sorted
     /// ascending order
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(pdag.adjacent_undirected_of(2), &[3]);
+    /// assert_eq!(pdag.adjacent_undirected_of(3), &[2]);
+    /// ```
     pub fn adjacent_undirected_of(&self, node: usize) -> &[usize] {
         let start = self.node_edge_ranges[node];
         let end = self.node_edge_ranges[node + 1];
@@ -149,6 +183,21 @@ impl PDAG {
 
     /// Given a node, return all nodes reachable in one step along a possibly incoming edge (undirected or incoming).
     /// Not yielded in any particular order.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// let mut possible_parents = pdag.possible_parents_of(2).to_vec();
+    /// possible_parents.sort();
+    /// assert_eq!(possible_parents, vec![0, 1, 3]);
+    /// ```
     pub fn possible_parents_of(&self, node: usize) -> &[usize] {
         let start = self.node_edge_ranges[node];
         let end = self.node_edge_ranges[node + 1];
@@ -162,6 +211,20 @@ impl PDAG {
 
     /// Given a node, return all nodes reachable in one step along a possibly outgoing edge (undirected or outgoing).
     /// Not yielded in any particular order.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(pdag.possible_children_of(0), &[2]);
+    /// assert_eq!(pdag.possible_children_of(2), &[3]);
+    /// ```
     pub fn possible_children_of(&self, node: usize) -> &[usize] {
         let start = self.node_edge_ranges[node];
         let end = self.node_edge_ranges[node + 1];
@@ -174,11 +237,72 @@ impl PDAG {
     }
 }
 
-#[derive(Debug)]
+/// The largest number of nodes a PDAG may have.
+///
+/// The distances are normalized by the number of ordered pairs of distinct nodes, `n * (n - 1)`,
+/// which is also the largest possible number of mistakes; with at most `MAX_NODES` nodes,
+/// this count fits into a `usize` (`2^32` nodes on 64-bit and `2^16` nodes on 32-bit targets).
+pub const MAX_NODES: usize = 1 << (usize::BITS / 2);
+
+#[derive(Debug, PartialEq)]
 /// Error that can occur when loading a PDAG from an adjacency matrix.
+///
+/// Positions are `(outer, inner)` indices as yielded by the edgelist, that is,
+/// `(row, column)` for a row-major and `(column, row)` for a column-major edgelist.
 pub enum LoadError {
     /// The adjacency matrix does not represent a PDAG because it contains a cycle.
     NotAcyclic,
+    /// The adjacency matrix codes more than one edge between the same two nodes,
+    /// for example, both `node -> other` and `node -- other`.
+    NotSimple {
+        /// one endpoint of the edges
+        node: usize,
+        /// the other endpoint of the edges
+        other: usize,
+    },
+    /// The adjacency matrix codes an edge from a node to itself.
+    SelfLoop {
+        /// the node with a self-loop
+        node: usize,
+        /// the value on the diagonal
+        value: i8,
+    },
+    /// The adjacency matrix contains a value other than 0, 1, or 2.
+    InvalidEdgeValue {
+        /// the `(outer, inner)` position of the entry
+        position: (usize, usize),
+        /// the offending value
+        value: i8,
+    },
+    /// The edgelist yielded an entry outside of the adjacency matrix.
+    IndexOutOfBounds {
+        /// the `(outer, inner)` position of the entry
+        position: (usize, usize),
+        /// the number of nodes of the graph
+        n_nodes: usize,
+    },
+    /// The graph has more than [`MAX_NODES`] nodes.
+    TooManyNodes {
+        /// the number of nodes of the graph
+        n_nodes: usize,
+    },
+    /// A row of a dense adjacency matrix does not have one entry per row.
+    NotSquare {
+        /// the row
+        row: usize,
+        /// the number of entries of the row
+        len: usize,
+        /// the number of rows
+        n_nodes: usize,
+    },
+    /// The edgelist yielded an entry at or before the position of the previous entry,
+    /// so its entries are not in row-by-row (column-by-column) order or repeat a position.
+    NotOrdered {
+        /// the `(outer, inner)` position of the previous entry
+        previous: (usize, usize),
+        /// the `(outer, inner)` position of the entry out of order
+        next: (usize, usize),
+    },
 }
 
 impl Error for LoadError {}
@@ -187,10 +311,51 @@ impl fmt::Display for LoadError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LoadError::NotAcyclic => write!(f, "Graph is not acyclic"),
+            LoadError::NotSimple { node, other } => write!(
+                f,
+                "Graph not simple: found more than one edge between nodes {node} and {other} in adjacency matrix"
+            ),
+            LoadError::SelfLoop { node, value } => write!(
+                f,
+                "found unexpected self-looping edge '{value}' at position ({node}, {node})"
+            ),
+            LoadError::InvalidEdgeValue { position, value } => write!(
+                f,
+                "Found value '{value}' in adjacency matrix at position {position:?}, expected to see only 0's, 1's or 2's for PDAG."
+            ),
+            LoadError::IndexOutOfBounds { position, n_nodes } => write!(
+                f,
+                "Found entry at position {position:?}, which is out of bounds for a graph with {n_nodes} nodes"
+            ),
+            LoadError::TooManyNodes { n_nodes } => write!(
+                f,
+                "Graph has {n_nodes} nodes, but at most {MAX_NODES} nodes are supported"
+            ),
+            LoadError::NotSquare { row, len, n_nodes } => write!(
+                f,
+                "Adjacency matrix is not square: row {row} has {len} entries, but there are {n_nodes} rows"
+            ),
+            LoadError::NotOrdered { previous, next } => write!(
+                f,
+                "Iterator yielded entries in wrong order: entry at position {next:?} after entry at position {previous:?}"
+            ),
         }
     }
 }
 
+/// Returns [`LoadError::NotSquare`] for the first row of the dense adjacency matrix
+/// that does not have one entry per row.
+fn check_square(dense: &[Vec<i8>]) -> Result<(), LoadError> {
+    match dense.iter().position(|row| row.len() != dense.len()) {
+        Some(row) => Err(LoadError::NotSquare {
+            row,
+            len: dense[row].len(),
+            n_nodes: dense.len(),
+        }),
+        None => Ok(()),
+    }
+}
+
 impl PDAG {
     // TODO: from_row_major and from_col_major are very similar, unify as much as possible for clarity
 
@@ -198,12 +363,29 @@ impl PDAG {
     ///
     /// If there is an undirected edge between node i and j, the edgelist may yield
     /// (i, j, 2) and (j, i, 2). Yielding only one is also fine, but yielding
-    /// (i, j, 1) and (j, i, 1), or (i, j, 1) and (j, i, 2) will cause a panic.
-    pub fn try_from_row_major<I>(edgelist: Edgelist<RowMajorOrder, I>) -> Result<PDAG, LoadError>
+    /// (i, j, 1) and (j, i, 1), or (i, j, 1) and (j, i, 2) will return [`LoadError::NotSimple`].
+    ///
+    /// ```
+    /// use gadjid::{EdgelistIterator, PDAG};
+    ///
+    /// // 0 -> 1, 0 -- 2, as (row, column, entry) of a row-to-column adjacency matrix in row-major order
+    /// let entries = [(0, 1, 1), (0, 2, 2), (2, 0, 2)];
+    /// let pdag = PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(3)).unwrap();
+    /// assert_eq!(pdag.children_of(0), &[1]);
+    /// assert_eq!(pdag.adjacent_undirected_of(0), &[2]);
+    /// ```
+    pub fn try_from_row_major<I>(
+        mut edgelist: Edgelist<RowMajorOrder, I>,
+    ) -> Result<PDAG, LoadError>
     where
         I: Iterator<Item = (usize, usize, i8)>,
     {
         let matrix_size = edgelist.size;
+        if matrix_size > MAX_NODES {
+            return Err(LoadError::TooManyNodes {
+                n_nodes: matrix_size,
+            });
+        }
         // incoming edges will be encountered in order lexicographically sorted by (inner_idx, outer_idx),
         let mut incomings: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
         // outgoing edges will be encountered in order (outer_idx, inner_idx),
@@ -222,10 +404,19 @@ impl PDAG {
         let mut node_edge_ranges = vec![0; matrix_size + 1];
         let node_edge_ranges_slice = node_edge_ranges.as_mut_slice();
 
-        for (outer_idx, inner_idx, val) in edgelist {
+        while let Some((outer_idx, inner_idx, val)) = edgelist.try_next()? {
+            if outer_idx >= matrix_size || inner_idx >= matrix_size {
+                return Err(LoadError::IndexOutOfBounds {
+                    position: (outer_idx, inner_idx),
+                    n_nodes: matrix_size,
+                });
+            }
             // verify that no edges are self-looping
             if outer_idx == inner_idx {
-                panic!("found unexpected self-looping edge '{val}' at position ({outer_idx}, {inner_idx})")
+                return Err(LoadError::SelfLoop {
+                    node: outer_idx,
+                    value: val,
+                });
             }
 
             match val {
@@ -245,7 +436,12 @@ impl PDAG {
                     node_undirected_degree_slice[inner_idx] += 1;
                     node_undirected_degree_slice[outer_idx] += 1;
                 }
-                _ => panic!("Found value '{val}' in adjacency matrix at position ({}, {}), expected to see only 0's, 1's or 2's for PDAG.", outer_idx, inner_idx)
+                _ => {
+                    return Err(LoadError::InvalidEdgeValue {
+                        position: (outer_idx, inner_idx),
+                        value: val,
+                    })
+                }
             }
         }
 
@@ -271,7 +467,7 @@ impl PDAG {
             if let Some(mut vec) = undirected.remove(&i) {
                 vec.sort_unstable();
                 vec.dedup();
-                nb.extend(vec.into_iter());
+                nb.extend(vec);
             }
             let n_undirected = nb.len() - n_in;
 
@@ -293,19 +489,22 @@ impl PDAG {
                 let outgoings = &nb[n_in + n_undirected..];
 
                 if let Some(val) = ascending_lists_first_shared_element(incomings, undirected) {
-                    panic!(
-                        "Graph not simple: found both edge {val}->{i} and edge {val}--{i} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
                 if let Some(val) = ascending_lists_first_shared_element(outgoings, undirected) {
-                    panic!(
-                        "Graph not simple: found both edge {i}->{val} and edge {i}--{val} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
                 if let Some(val) = ascending_lists_first_shared_element(incomings, outgoings) {
-                    panic!(
-                        "Graph not simple: found both edge {val}->{i} and edge {i}->{val} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
             }
 
@@ -314,7 +513,7 @@ impl PDAG {
             n_edges += nb.len();
 
             // finally, we add the constructed neighbourhood to the neighbourhoods list and continue
-            neighbourhoods.extend(nb.into_iter());
+            neighbourhoods.extend(nb);
         }
 
         n_directed_edges /= 2;
@@ -348,12 +547,29 @@ impl PDAG {
     ///
     /// If there is an undirected edge between node i and j, the edgelist may yield
     /// (i, j, 2) and (j, i, 2). Yielding only one is also fine, but yielding
-    /// (i, j, 1) and (j, i, 1), or (i, j, 1) and (j, i, 2) will cause a panic.
-    pub fn try_from_col_major<I>(edgelist: Edgelist<ColumnMajorOrder, I>) -> Result<PDAG, LoadError>
+    /// (i, j, 1) and (j, i, 1), or (i, j, 1) and (j, i, 2) will return [`LoadError::NotSimple`].
+    ///
+    /// ```
+    /// use gadjid::{EdgelistIterator, PDAG};
+    ///
+    /// // 0 -> 1, 0 -- 2, as (column, row, entry) of a row-to-column adjacency matrix in column-major order
+    /// let entries = [(0, 2, 2), (1, 0, 1), (2, 0, 2)];
+    /// let pdag = PDAG::try_from_col_major(entries.into_iter().into_column_major_edgelist(3)).unwrap();
+    /// assert_eq!(pdag.children_of(0), &[1]);
+    /// assert_eq!(pdag.adjacent_undirected_of(0), &[2]);
+    /// ```
+    pub fn try_from_col_major<I>(
+        mut edgelist: Edgelist<ColumnMajorOrder, I>,
+    ) -> Result<PDAG, LoadError>
     where
         I: Iterator<Item = (usize, usize, i8)>,
     {
         let matrix_size = edgelist.size;
+        if matrix_size > MAX_NODES {
+            return Err(LoadError::TooManyNodes {
+                n_nodes: matrix_size,
+            });
+        }
         // outgoing_ edges will be encountered in order lexicographically sorted by (inner_idx, outer_idx),
         let mut outgoings_: FxHashMap<usize, Vec<usize>> = FxHashMap::default();
         // outgoing edges will be encountered in order (outer_idx, inner_idx),
@@ -372,10 +588,19 @@ impl PDAG {
         let mut node_edge_ranges = vec![0; matrix_size + 1];
         let node_edge_ranges_slice = node_edge_ranges.as_mut_slice();
 
-        for (outer_idx, inner_idx, val) in edgelist {
+        while let Some((outer_idx, inner_idx, val)) = edgelist.try_next()? {
+            if outer_idx >= matrix_size || inner_idx >= matrix_size {
+                return Err(LoadError::IndexOutOfBounds {
+                    position: (outer_idx, inner_idx),
+                    n_nodes: matrix_size,
+                });
+            }
             // verify that no edges are self-looping
             if outer_idx == inner_idx {
-                panic!("found unexpected self-looping edge '{val}' at position ({outer_idx}, {inner_idx})")
+                return Err(LoadError::SelfLoop {
+                    node: outer_idx,
+                    value: val,
+                });
             }
 
             match val {
@@ -395,7 +620,12 @@ impl PDAG {
                     node_undirected_degree_slice[inner_idx] += 1;
                     node_undirected_degree_slice[outer_idx] += 1;
                 }
-                _ => panic!("Found value '{val}' in adjacency matrix at position ({}, {}), expected to see only 0's, 1's or 2's for PDAG.", outer_idx, inner_idx),
+                _ => {
+                    return Err(LoadError::InvalidEdgeValue {
+                        position: (outer_idx, inner_idx),
+                        value: val,
+                    })
+                }
             }
         }
 
@@ -421,7 +651,7 @@ impl PDAG {
             if let Some(mut vec) = undirected.remove(&i) {
                 vec.sort_unstable();
                 vec.dedup();
-                nb.extend(vec.into_iter());
+                nb.extend(vec);
             }
 
             let n_undirected = nb.len() - n_in;
@@ -448,19 +678,22 @@ impl PDAG {
                 let outgoings = &nb[n_in + n_undirected..];
 
                 if let Some(val) = ascending_lists_first_shared_element(incomings, undirected) {
-                    panic!(
-                        "Graph not simple: found both edge {val}->{i} and edge {val}--{i} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
                 if let Some(val) = ascending_lists_first_shared_element(outgoings, undirected) {
-                    panic!(
-                        "Graph not simple: found both edge {i}->{val} and edge {i}--{val} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
                 if let Some(val) = ascending_lists_first_shared_element(incomings, outgoings) {
-                    panic!(
-                        "Graph not simple: found both edge {val}->{i} and edge {i}->{val} in adjacency matrix",
-                    );
+                    return Err(LoadError::NotSimple {
+                        node: i,
+                        other: val,
+                    });
                 }
             }
 
@@ -469,7 +702,7 @@ impl PDAG {
             n_edges += nb.len();
 
             // finally, we add the constructed neighbourhood to the neighbourhoods list and continue
-            neighbourhoods.extend(nb.into_iter());
+            neighbourhoods.extend(nb);
         }
 
         n_directed_edges /= 2;
@@ -499,41 +732,275 @@ impl PDAG {
         Ok(pdag)
     }
 
-    /// Creates a PDAG from a row-major encoded adjacency matrix.
-    /// An entry of 1 at position `[i,j]` indicates a directed edge `i -> j`,
-    /// the opposite of how [`from_col_to_row_vecvec`] does it.
-    /// An entry of 2 at position `[i,j]` and/or `[j,i]` indicates an undirected edge between `i` and `j`.
+    /// Creates a PDAG from a dense adjacency matrix in the row-to-column convention:
+    /// an entry of 1 at position `[i][j]` codes a directed edge `i -> j`,
+    /// and an entry of 2 at position `[i][j]` and/or `[j][i]` codes an undirected edge between `i` and `j`.
+    /// Panics if the matrix does not code a PDAG, see [`PDAG::try_from_dense_row_to_col`].
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -> 3
+    /// let dag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 1],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(dag.parents_of(2), &[0, 1]);
+    /// assert_eq!(dag.children_of(2), &[3]);
+    /// ```
+    pub fn from_dense_row_to_col(dense: Vec<Vec<i8>>) -> Self {
+        PDAG::try_from_dense_row_to_col(dense).unwrap()
+    }
+
+    /// Creates a PDAG from a dense adjacency matrix in the row-to-column convention,
+    /// like [`PDAG::from_dense_row_to_col`], but returns an error instead of panicking
+    /// if the matrix is not square or does not code a PDAG.
+    ///
+    /// ```
+    /// use gadjid::{LoadError, PDAG};
+    ///
+    /// // 1 -- 0 -- 2
+    /// let cpdag = PDAG::try_from_dense_row_to_col(vec![
+    ///     vec![0, 2, 2],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 0, 0],
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(cpdag.adjacent_undirected_of(0), &[1, 2]);
+    ///
+    /// // 0 -> 1 -> 2 -> 0
+    /// let cyclic = PDAG::try_from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![1, 0, 0],
+    /// ]);
+    /// assert_eq!(cyclic.unwrap_err(), LoadError::NotAcyclic);
+    /// ```
+    pub fn try_from_dense_row_to_col(dense: Vec<Vec<i8>>) -> Result<Self, LoadError> {
+        check_square(&dense)?;
+        PDAG::try_from_row_major(Edgelist::from_vecvec(dense))
+    }
+
+    /// Creates a PDAG from a row-to-column adjacency matrix.
+    #[deprecated(note = "renamed to `PDAG::from_dense_row_to_col`")]
     pub fn from_row_to_column_vecvec(dense: Vec<Vec<i8>>) -> Self {
-        let edgelist = Edgelist::from_vecvec(dense);
-        let mut pdag = PDAG::try_from_row_major(edgelist).unwrap();
+        PDAG::from_dense_row_to_col(dense)
+    }
 
-        // TODO: CPDAGness check
-        if pdag.n_undirected_edges > 0 {
-            pdag.pdag_type = Structure::CPDAG
-        } else {
-            pdag.pdag_type = Structure::DAG
+    /// Returns the row-to-column adjacency matrix of the PDAG, the inverse of [`PDAG::from_dense_row_to_col`].
+    /// An entry of 1 at position `[i,j]` codes a directed edge `i -> j`, and
+    /// an undirected edge between `i` and `j` is coded by a 2 at both `[i,j]` and `[j,i]`.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // an undirected edge coded once is coded in both directions
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 2],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 0, 0],
+    /// ]);
+    /// assert_eq!(
+    ///     pdag.to_row_to_column_vecvec(),
+    ///     vec![vec![0, 1, 2], vec![0, 0, 0], vec![2, 0, 0]]
+    /// );
+    /// ```
+    pub fn to_row_to_column_vecvec(&self) -> Vec<Vec<i8>> {
+        let mut adjacency = vec![vec![0; self.n_nodes]; self.n_nodes];
+
+        #[allow(clippy::needless_range_loop)]
+        for node in 0..self.n_nodes {
+            for child in self.children_of(node).iter().copied() {
+                adjacency[node][child] = 1;
+            }
+            for undirected in self.adjacent_undirected_of(node).iter().copied() {
+                adjacency[node][undirected] = 2;
+            }
         }
-        pdag
+
+        adjacency
     }
 
-    /// Creates a PDAG from a row_major adjacency matrix.
-    /// An entry of 1 at position `[i,j]` indicates a directed edge `j -> i`,
-    /// the opposite of how [`from_row_to_col_vecvec`] does it.
-    /// An entry of 2 at position `[i,j]` and/or `[j,i]` indicates an undirected edge between `i` and `j`.
-    pub fn from_col_to_row_vecvec(vecvec: Vec<Vec<i8>>) -> Self {
-        let edgelist = Edgelist::from_vecvec(vecvec);
-        let mut pdag = PDAG::try_from_col_major(edgelist).unwrap();
+    /// Returns the subgraph induced by the ascending and distinct `nodes`,
+    /// in which node `nodes[i]` becomes node `i`.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -- 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 1, 0],
+    ///     vec![0, 0, 0, 2],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// // nodes 1, 2, and 3 become nodes 0, 1, and 2
+    /// let subgraph = pdag.induced_subgraph(&[1, 2, 3]);
+    /// assert_eq!(subgraph.children_of(0), &[1]);
+    /// assert_eq!(subgraph.adjacent_undirected_of(1), &[2]);
+    /// ```
+    pub fn induced_subgraph(&self, nodes: &[usize]) -> PDAG {
+        assert!(
+            nodes.windows(2).all(|w| w[0] < w[1]),
+            "nodes must be ascending and distinct"
+        );
+        let index = FxHashMap::from_iter(nodes.iter().enumerate().map(|(i, v)| (*v, i)));
+        let mut entries = Vec::new();
+        for (row, node) in nodes.iter().enumerate() {
+            let children = self.children_of(*node).iter().map(|w| (*w, 1));
+            let undirected = self.adjacent_undirected_of(*node).iter().map(|w| (*w, 2));
+            let mut row_entries = Vec::from_iter(
+                children
+                    .chain(undirected)
+                    .filter_map(|(w, edge)| index.get(&w).map(|column| (row, *column, edge))),
+            );
+            row_entries.sort_unstable();
+            entries.extend(row_entries);
+        }
+        // an induced subgraph of an acyclic graph is acyclic
+        PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(nodes.len()))
+            .expect("induced subgraphs of a PDAG are PDAGs")
+    }
 
-        // TODO: CPDAGness check
-        if pdag.n_undirected_edges > 0 {
-            pdag.pdag_type = Structure::CPDAG
-        } else {
-            pdag.pdag_type = Structure::DAG
+    /// Returns the graph in which node `v` takes the place of node `permutation[v]` of this graph,
+    /// so that an edge `permutation[v] -> permutation[w]` becomes `v -> w`.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 1 -> 2
+    /// let chain = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 0, 0],
+    /// ]);
+    /// // node 0 takes the place of node 2, so that the chain becomes 2 -> 1 -> 0
+    /// let reversed = chain.relabeled(&[2, 1, 0]);
+    /// assert_eq!(reversed.children_of(2), &[1]);
+    /// assert_eq!(reversed.children_of(1), &[0]);
+    /// ```
+    pub fn relabeled(&self, permutation: &[usize]) -> PDAG {
+        assert!(
+            permutation.len() == self.n_nodes,
+            "there must be one label per node"
+        );
+        let mut new_label = vec![usize::MAX; self.n_nodes];
+        for (v, old) in permutation.iter().enumerate() {
+            assert!(
+                *old < self.n_nodes && new_label[*old] == usize::MAX,
+                "labels must be a permutation of the nodes"
+            );
+            new_label[*old] = v;
         }
-        pdag
+        let mut entries = Vec::new();
+        for (row, old) in permutation.iter().enumerate() {
+            let children = self.children_of(*old).iter().map(|w| (new_label[*w], 1));
+            let undirected = self
+                .adjacent_undirected_of(*old)
+                .iter()
+                .map(|w| (new_label[*w], 2));
+            let mut row_entries = Vec::from_iter(
+                children
+                    .chain(undirected)
+                    .map(|(column, edge)| (row, column, edge)),
+            );
+            row_entries.sort_unstable();
+            entries.extend(row_entries);
+        }
+        // relabeling keeps the graph acyclic
+        PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(self.n_nodes))
+            .expect("relabeled PDAGs are PDAGs")
+    }
+
+    /// Creates a PDAG from a dense adjacency matrix in the column-to-row convention:
+    /// an entry of 1 at position `[i][j]` codes a directed edge `j -> i`,
+    /// the opposite of [`PDAG::from_dense_row_to_col`],
+    /// and an entry of 2 at position `[i][j]` and/or `[j][i]` codes an undirected edge between `i` and `j`.
+    /// Panics if the matrix does not code a PDAG, see [`PDAG::try_from_dense_col_to_row`].
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 2 -> 3
+    /// let dag = PDAG::from_dense_col_to_row(vec![
+    ///     vec![0, 0, 0, 0],
+    ///     vec![0, 0, 0, 0],
+    ///     vec![1, 1, 0, 0],
+    ///     vec![0, 0, 1, 0],
+    /// ]);
+    /// assert_eq!(dag.parents_of(2), &[0, 1]);
+    /// assert_eq!(dag.children_of(2), &[3]);
+    /// ```
+    pub fn from_dense_col_to_row(dense: Vec<Vec<i8>>) -> Self {
+        PDAG::try_from_dense_col_to_row(dense).unwrap()
+    }
+
+    /// Creates a PDAG from a dense adjacency matrix in the column-to-row convention,
+    /// like [`PDAG::from_dense_col_to_row`], but returns an error instead of panicking
+    /// if the matrix is not square or does not code a PDAG.
+    ///
+    /// ```
+    /// use gadjid::{LoadError, PDAG};
+    ///
+    /// let not_square = PDAG::try_from_dense_col_to_row(vec![vec![0, 1], vec![0]]);
+    /// assert!(matches!(not_square, Err(LoadError::NotSquare { row: 1, .. })));
+    /// ```
+    pub fn try_from_dense_col_to_row(dense: Vec<Vec<i8>>) -> Result<Self, LoadError> {
+        check_square(&dense)?;
+        PDAG::try_from_col_major(Edgelist::from_vecvec(dense))
+    }
+
+    /// Creates a PDAG from a column-to-row adjacency matrix.
+    #[deprecated(note = "renamed to `PDAG::from_dense_col_to_row`")]
+    pub fn from_col_to_row_vecvec(dense: Vec<Vec<i8>>) -> Self {
+        PDAG::from_dense_col_to_row(dense)
+    }
+
+    /// Creates a PDAG with `n_nodes` nodes from `(from, to, edge type)` entries in any order,
+    /// where an edge type of 1 codes a directed edge `from -> to` and 2 an undirected edge,
+    /// without building an adjacency matrix. Entries with an edge type of 0 are skipped.
+    /// Returns [`LoadError::NotSimple`] if an ordered pair of nodes is listed twice,
+    /// and otherwise the same errors as [`PDAG::try_from_row_major`].
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    ///
+    /// // 0 -> 2 <- 1, 3 -- 4
+    /// let cpdag = PDAG::try_from_edges(5, [(3, 4, 2), (1, 2, 1), (0, 2, 1)]).unwrap();
+    /// assert_eq!(cpdag.parents_of(2), &[0, 1]);
+    /// assert_eq!(cpdag.adjacent_undirected_of(4), &[3]);
+    /// ```
+    pub fn try_from_edges(
+        n_nodes: usize,
+        edges: impl IntoIterator<Item = (usize, usize, i8)>,
+    ) -> Result<Self, LoadError> {
+        let mut entries = Vec::from_iter(edges.into_iter().filter(|(_, _, edge)| *edge != 0));
+        entries.sort_unstable();
+        if let Some(pair) = entries
+            .windows(2)
+            .find(|pair| pair[0].0 == pair[1].0 && pair[0].1 == pair[1].1)
+        {
+            return Err(LoadError::NotSimple {
+                node: pair[0].0,
+                other: pair[0].1,
+            });
+        }
+        PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(n_nodes))
     }
 
     /// Creates a random DAG with the given edge density and size.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    /// use rand::SeedableRng;
+    ///
+    /// let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    /// let dag = PDAG::random_dag(0.5, 10, rng);
+    /// assert_eq!(dag.n_nodes, 10);
+    /// assert_eq!(dag.n_undirected_edges, 0);
+    /// ```
     pub fn random_dag(edge_density: f64, graph_size: usize, mut rng: impl rand::RngCore) -> PDAG {
         assert!(graph_size > 0, "Graph size must be larger than 0");
         assert!(
@@ -551,7 +1018,7 @@ impl PDAG {
             }
         }
 
-        PDAG::from_row_to_column_vecvec(adjacency)
+        PDAG::from_dense_row_to_col(adjacency)
     }
 
     /// Creates a random vecvec of a PDAG with random edges with the given edge density and size.
@@ -566,9 +1033,7 @@ impl PDAG {
             "edge probability must be in [0, 1]"
         );
         let edge_dist = rand::distributions::Bernoulli::new(edge_density).unwrap();
-        // P(edge between X and Y is directed) given that there is an edge between X and Y
-        let p_directedness = 0.8;
-        let directionality_dist = rand::distributions::Bernoulli::new(p_directedness).unwrap();
+        let directionality_dist = rand::distributions::Bernoulli::new(P_DIRECTED).unwrap();
         let mut adjacency = vec![vec![0; graph_size]; graph_size];
         let permutation = rand::seq::index::sample(&mut rng, graph_size, graph_size);
         for y in 0..graph_size {
@@ -589,8 +1054,17 @@ impl PDAG {
     }
 
     /// Creates a random PDAG with random edges with the given edge density and size.
+    ///
+    /// ```
+    /// use gadjid::PDAG;
+    /// use rand::SeedableRng;
+    ///
+    /// let rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    /// let pdag = PDAG::random_pdag(0.5, 10, rng);
+    /// assert_eq!(pdag.n_nodes, 10);
+    /// ```
     pub fn random_pdag(edge_density: f64, graph_size: usize, mut rng: impl rand::RngCore) -> PDAG {
-        PDAG::from_row_to_column_vecvec(PDAG::_random_pdag_vecvec(
+        PDAG::from_dense_row_to_col(PDAG::_random_pdag_vecvec(
             edge_density,
             graph_size,
             &mut rng,
@@ -598,6 +1072,60 @@ impl PDAG {
     }
 }
 
+/// How [`PDAG::topological_order_with_tie_breaking`] breaks ties between nodes that can come next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    /// the node with the lowest index comes first
+    Index,
+    /// the node with the most adjacent nodes comes first, ties broken by the lowest index
+    Degree,
+    /// the nodes come in an order drawn from the `seed`, which is the same on every platform
+    Random {
+        /// the seed of the [`rand_chacha::ChaCha8Rng`] that draws the order
+        seed: u64,
+    },
+}
+
+impl PDAG {
+    /// Returns a topological order of the nodes along the directed edges, so that every parent
+    /// comes before its children, and breaks ties between nodes that can come next by the `tie_break`,
+    /// so that the order is deterministic, for example, to serialize a graph reproducibly.
+    /// Undirected edges do not constrain the order.
+    ///
+    /// ```
+    /// use gadjid::{TieBreak, PDAG};
+    ///
+    /// // 2 -> 0, 1 -> 3, 2 -> 3
+    /// let pdag = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 0, 0, 0],
+    ///     vec![0, 0, 0, 1],
+    ///     vec![1, 0, 0, 1],
+    ///     vec![0, 0, 0, 0],
+    /// ]);
+    /// assert_eq!(pdag.topological_order_with_tie_breaking(TieBreak::Index), vec![1, 2, 0, 3]);
+    /// assert_eq!(pdag.topological_order_with_tie_breaking(TieBreak::Degree), vec![2, 0, 1, 3]);
+    /// ```
+    pub fn topological_order_with_tie_breaking(&self, tie_break: TieBreak) -> Vec<usize> {
+        let mut rank = Vec::from_iter(0..self.n_nodes);
+        match tie_break {
+            TieBreak::Index => {}
+            TieBreak::Degree => {
+                let degree = |v: usize| self.node_edge_ranges[v + 1] - self.node_edge_ranges[v];
+                let mut by_degree = Vec::from_iter(0..self.n_nodes);
+                by_degree.sort_by_key(|v| (Reverse(degree(*v)), *v));
+                for (position, v) in by_degree.into_iter().enumerate() {
+                    rank[v] = position;
+                }
+            }
+            TieBreak::Random { seed } => {
+                let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+                rank.shuffle(&mut rng);
+            }
+        }
+        kahn_order(self, &rank)
+    }
+}
+
 /// Returns true if the graph has a cycle, false otherwise.
 /// An implementation of Kahn's algorithm for topological sorting.
 pub fn has_cycle(graph: &PDAG) -> bool {
@@ -647,12 +1175,49 @@ pub fn has_cycle(graph: &PDAG) -> bool {
     visited < graph.n_nodes
 }
 
+/// An implementation of Kahn's algorithm for topological sorting, which returns the nodes in
+/// topological order, of which there are fewer than the nodes of the graph if it has a cycle.
+/// Among the nodes whose parents all come before them, the node with the lowest `rank` comes next.
+fn kahn_order(graph: &PDAG, rank: &[usize]) -> Vec<usize> {
+    let mut in_degree: Vec<usize> = graph.node_in_out_degree.iter().map(|x| x.0).collect();
+
+    // Fill the heap with all roots, keyed by their rank so that the lowest rank is popped first.
+    let mut roots = BinaryHeap::from_iter(
+        (0..graph.n_nodes)
+            .filter(|u| in_degree[*u] == 0)
+            .map(|u| Reverse((rank[u], u))),
+    );
+
+    let mut order = Vec::with_capacity(graph.n_nodes);
+
+    // One by one pop roots and push children
+    // if the in-degree of the child becomes 0
+    while let Some(Reverse((_, current))) = roots.pop() {
+        order.push(current);
+        // Iterate through all child nodes v
+        // of popped node and decrease their in-degree
+        // by 1 (effectively removing edges from the graph)
+        for v in graph.children_of(current).iter().copied() {
+            in_degree[v] -= 1;
+
+            // If in-degree becomes zero, add it to the heap because it is now a root.
+            if in_degree[v] == 0 {
+                roots.push(Reverse((rank[v], v)));
+            }
+        }
+    }
+    // Every node is visited once, unless it is on or downstream of a cycle.
+    order
+}
+
 #[cfg(test)]
 mod test {
     use rand::SeedableRng;
     use std::collections::HashSet;
 
-    use crate::PDAG;
+    use crate::{
+        graph_loading::edgelist::Edgelist, EdgelistIterator, LoadError, TieBreak, MAX_NODES, PDAG,
+    };
 
     #[test]
     #[should_panic]
@@ -662,7 +1227,7 @@ mod test {
             vec![1, 0],
         ];
 
-        PDAG::from_row_to_column_vecvec(dense);
+        PDAG::from_dense_row_to_col(dense);
     }
 
     #[test]
@@ -673,7 +1238,140 @@ mod test {
             vec![2, 0],
         ];
 
-        PDAG::from_row_to_column_vecvec(dense);
+        PDAG::from_dense_row_to_col(dense);
+    }
+
+    #[test]
+    pub fn reports_why_loading_failed() {
+        let load = |dense: Vec<Vec<i8>>| PDAG::try_from_row_major(Edgelist::from_vecvec(dense));
+        assert_eq!(
+            load(vec![vec![0, 1], vec![2, 0]]).unwrap_err(),
+            LoadError::NotSimple { node: 0, other: 1 }
+        );
+        assert_eq!(
+            load(vec![vec![0, 0], vec![0, 1]]).unwrap_err(),
+            LoadError::SelfLoop { node: 1, value: 1 }
+        );
+        assert_eq!(
+            load(vec![vec![0, 3], vec![0, 0]]).unwrap_err(),
+            LoadError::InvalidEdgeValue {
+                position: (0, 1),
+                value: 3
+            }
+        );
+        assert_eq!(
+            load(vec![vec![0, 1, 0], vec![0, 0, 1], vec![1, 0, 0]]).unwrap_err(),
+            LoadError::NotAcyclic
+        );
+    }
+
+    #[test]
+    pub fn dense_constructors_agree_with_deprecated_aliases() {
+        let row_to_col = vec![vec![0, 1, 2], vec![0, 0, 0], vec![0, 0, 0]];
+        let col_to_row = vec![vec![0, 0, 2], vec![1, 0, 0], vec![0, 0, 0]];
+        #[allow(deprecated)]
+        let (old_row_to_col, old_col_to_row) = (
+            PDAG::from_row_to_column_vecvec(row_to_col.clone()),
+            PDAG::from_col_to_row_vecvec(col_to_row.clone()),
+        );
+        let pdag = PDAG::from_dense_row_to_col(row_to_col);
+        assert_eq!(pdag, old_row_to_col);
+        assert_eq!(PDAG::from_dense_col_to_row(col_to_row), old_col_to_row);
+        assert_eq!(pdag, old_col_to_row);
+
+        assert_eq!(
+            PDAG::try_from_dense_row_to_col(vec![vec![0, 0, 0], vec![0, 0]]).unwrap_err(),
+            LoadError::NotSquare {
+                row: 0,
+                len: 3,
+                n_nodes: 2
+            }
+        );
+        assert_eq!(
+            PDAG::try_from_dense_col_to_row(vec![vec![0, 0, 1], vec![1, 0, 0], vec![0, 1, 0]])
+                .unwrap_err(),
+            LoadError::NotAcyclic
+        );
+    }
+
+    #[test]
+    pub fn loads_edges_in_any_order() {
+        let dense = vec![
+            vec![0, 1, 1, 0],
+            vec![0, 0, 0, 2],
+            vec![0, 0, 0, 1],
+            vec![0, 0, 0, 0],
+        ];
+        let edges = [(2, 3, 1), (1, 3, 2), (0, 2, 1), (3, 0, 0), (0, 1, 1)];
+        assert_eq!(
+            PDAG::try_from_edges(4, edges).unwrap(),
+            PDAG::from_dense_row_to_col(dense)
+        );
+        assert_eq!(
+            PDAG::try_from_edges(2, [(0, 1, 1), (0, 1, 2)]).unwrap_err(),
+            LoadError::NotSimple { node: 0, other: 1 }
+        );
+        assert!(matches!(
+            PDAG::try_from_edges(2, [(0, 2, 1)]),
+            Err(LoadError::IndexOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    pub fn enforces_node_limit() {
+        // the number of ordered pairs of distinct nodes fits into a usize up to MAX_NODES nodes
+        assert!(MAX_NODES.checked_mul(MAX_NODES - 1).is_some());
+        assert!((MAX_NODES + 1).checked_mul(MAX_NODES).is_none());
+
+        let too_many = std::iter::empty().into_row_major_edgelist(MAX_NODES + 1);
+        assert_eq!(
+            PDAG::try_from_row_major(too_many).unwrap_err(),
+            LoadError::TooManyNodes {
+                n_nodes: MAX_NODES + 1
+            }
+        );
+        let too_many = std::iter::empty().into_column_major_edgelist(MAX_NODES + 1);
+        assert_eq!(
+            PDAG::try_from_col_major(too_many).unwrap_err(),
+            LoadError::TooManyNodes {
+                n_nodes: MAX_NODES + 1
+            }
+        );
+
+        let out_of_bounds = [(0, 1, 1), (1, 2, 1)]
+            .into_iter()
+            .into_row_major_edgelist(2);
+        assert_eq!(
+            PDAG::try_from_row_major(out_of_bounds).unwrap_err(),
+            LoadError::IndexOutOfBounds {
+                position: (1, 2),
+                n_nodes: 2
+            }
+        );
+    }
+
+    #[test]
+    pub fn reports_entries_out_of_order() {
+        let unsorted = [(0, 2, 1), (0, 1, 1)]
+            .into_iter()
+            .into_row_major_edgelist(3);
+        assert_eq!(
+            PDAG::try_from_row_major(unsorted).unwrap_err(),
+            LoadError::NotOrdered {
+                previous: (0, 2),
+                next: (0, 1)
+            }
+        );
+        let repeated = [(1, 0, 1), (1, 0, 1)]
+            .into_iter()
+            .into_column_major_edgelist(3);
+        assert_eq!(
+            PDAG::try_from_col_major(repeated).unwrap_err(),
+            LoadError::NotOrdered {
+                previous: (1, 0),
+                next: (1, 0)
+            }
+        );
     }
 
     #[test]
@@ -684,7 +1382,7 @@ mod test {
             vec![0, 0, 0],
         ];
 
-        PDAG::from_row_to_column_vecvec(dense);
+        PDAG::from_dense_row_to_col(dense);
     }
 
     #[test]
@@ -695,7 +1393,7 @@ mod test {
             vec![0, 0],
         ];
 
-        let cpdag = PDAG::from_row_to_column_vecvec(dense);
+        let cpdag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(cpdag.n_nodes, 2);
 
@@ -719,7 +1417,7 @@ mod test {
             vec![0, 0, 0, 0],
         ];
 
-        let cpdag = PDAG::from_row_to_column_vecvec(dense);
+        let cpdag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(cpdag.n_nodes, 4);
         assert_eq!(
@@ -758,7 +1456,7 @@ mod test {
             vec![2, 0, 0],
         ];
 
-        let cpdag = PDAG::from_row_to_column_vecvec(dense);
+        let cpdag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(cpdag.n_nodes, 3);
 
@@ -784,7 +1482,7 @@ mod test {
             vec![0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(dense);
+        let dag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(dag.n_nodes, 2);
 
@@ -808,7 +1506,7 @@ mod test {
             vec![0, 0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(dense);
+        let dag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(dag.n_nodes, 4);
         assert_eq!(
@@ -846,7 +1544,7 @@ mod test {
             vec![0, 0, 0],
         ];
 
-        let dag = PDAG::from_row_to_column_vecvec(dense);
+        let dag = PDAG::from_dense_row_to_col(dense);
 
         assert_eq!(dag.n_nodes, 3);
 
@@ -877,8 +1575,8 @@ mod test {
             }
 
             // construct the DAG from the original and transposed adjacency matrix
-            let row_major_dag = PDAG::from_row_to_column_vecvec(adjacency);
-            let col_major_dag = PDAG::from_col_to_row_vecvec(transpose_adjacency);
+            let row_major_dag = PDAG::from_dense_row_to_col(adjacency);
+            let col_major_dag = PDAG::from_dense_col_to_row(transpose_adjacency);
 
             // the final representations of the DAG should be 100% equal
             assert_eq!(row_major_dag, col_major_dag);
@@ -901,6 +1599,43 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn property_topological_orders_respect_edges() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 1..40 {
+            let pdag = PDAG::random_pdag(0.3, n, &mut rng);
+            for tie_break in [
+                TieBreak::Index,
+                TieBreak::Degree,
+                TieBreak::Random { seed: 0 },
+                TieBreak::Random { seed: 1 },
+            ] {
+                let order = pdag.topological_order_with_tie_breaking(tie_break);
+                assert_eq!(order.len(), n);
+                let mut position = vec![0; n];
+                for (i, v) in order.iter().enumerate() {
+                    position[*v] = i;
+                }
+                for v in 0..n {
+                    for w in pdag.children_of(v) {
+                        assert!(position[v] < position[*w]);
+                    }
+                }
+                assert_eq!(pdag.topological_order_with_tie_breaking(tie_break), order);
+            }
+        }
+        // without edges, the order is decided by the tie break alone
+        let empty = PDAG::from_dense_row_to_col(vec![vec![0; 4]; 4]);
+        assert_eq!(
+            empty.topological_order_with_tie_breaking(TieBreak::Index),
+            vec![0, 1, 2, 3]
+        );
+        assert_ne!(
+            empty.topological_order_with_tie_breaking(TieBreak::Random { seed: 0 }),
+            empty.topological_order_with_tie_breaking(TieBreak::Random { seed: 2 })
+        );
+    }
+
     #[test]
     pub fn sorted_return_values() {
         let dense_matrices: Vec<Vec<Vec<i8>>> = vec![
@@ -929,7 +1664,7 @@ mod test {
         ];
 
         for (i, dense) in dense_matrices.iter().enumerate() {
-            let cpdag = PDAG::from_row_to_column_vecvec(dense.clone());
+            let cpdag = PDAG::from_dense_row_to_col(dense.clone());
 
             for n in 0..cpdag.n_nodes {
                 let mut children = cpdag.children_of(n).to_vec();
@@ -976,7 +1711,7 @@ mod test {
             vec![0, 0, 1],
             vec![0, 1, 0],
         ];
-        let _ = PDAG::from_row_to_column_vecvec(g_truth);
+        let _ = PDAG::from_dense_row_to_col(g_truth);
     }
 
     #[test]
@@ -987,7 +1722,7 @@ mod test {
             vec![0, 0, 1],
             vec![1, 0, 0],
         ];
-        let _ = PDAG::from_row_to_column_vecvec(g_truth);
+        let _ = PDAG::from_dense_row_to_col(g_truth);
     }
 
     #[test]
@@ -998,6 +1733,6 @@ mod test {
             vec![0, 0, 1],
             vec![1, 1, 0],
         ];
-        let _ = PDAG::from_row_to_column_vecvec(g_truth);
+        let _ = PDAG::from_dense_row_to_col(g_truth);
     }
 }
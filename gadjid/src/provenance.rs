@@ -0,0 +1,233 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Records where a distance comes from, the gadjid version, the options it was computed with,
+//! and fingerprints of the graphs, so that numbers in experiment logs can be traced back to the
+//! exact version of the algorithms and the graphs they were computed from.
+
+use crate::{
+    bundle::{fingerprint, to_mtx},
+    graph_operations::Distance,
+    PDAG,
+};
+
+/// The fingerprint of a graph, the 64-bit FNV-1a hash of its edges as a Matrix Market file
+/// (see [`crate::bundle`]), as 16 hexadecimal digits. It is stable across versions and platforms
+/// and only depends on the edges of the graph, not on how the graph was loaded.
+///
+/// ```
+/// use gadjid::{provenance::graph_fingerprint, PDAG};
+///
+/// let dag = PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]);
+/// let same = PDAG::from_dense_col_to_row(vec![vec![0, 0], vec![1, 0]]);
+/// assert_eq!(graph_fingerprint(&dag), graph_fingerprint(&same));
+/// assert_eq!(graph_fingerprint(&dag).len(), 16);
+/// ```
+pub fn graph_fingerprint(graph: &PDAG) -> String {
+    fingerprint(&to_mtx(graph))
+}
+
+/// Where a result comes from: the gadjid version, the options, and the fingerprints of the graphs.
+///
+/// With the `serde` feature, this implements `serde::Serialize`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize))]
+pub struct Provenance {
+    /// the version of gadjid that computed the result
+    pub gadjid_version: &'static str,
+    /// the names and values of the options the result was computed with, such as the edge direction
+    pub options: Vec<(String, String)>,
+    /// the [`graph_fingerprint`] of the truth graph
+    pub truth_fingerprint: String,
+    /// the [`graph_fingerprint`] of the guess graph
+    pub guess_fingerprint: String,
+}
+
+impl Provenance {
+    /// The provenance of a result computed by this version of gadjid
+    /// from the `truth` and the `guess` graph with the given `options`.
+    pub fn new(truth: &PDAG, guess: &PDAG, options: &[(&str, &str)]) -> Self {
+        Provenance {
+            gadjid_version: env!("CARGO_PKG_VERSION"),
+            options: Vec::from_iter(
+                options
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string())),
+            ),
+            truth_fingerprint: graph_fingerprint(truth),
+            guess_fingerprint: graph_fingerprint(guess),
+        }
+    }
+
+    /// Writes the provenance as a JSON object.
+    pub fn to_json(&self) -> String {
+        let options = Vec::from_iter(
+            self.options
+                .iter()
+                .map(|(name, value)| format!("{}: {}", json_string(name), json_string(value))),
+        );
+        format!(
+            r#"{{"gadjid_version": "{}", "options": {{{}}}, "truth_fingerprint": "{}", "guess_fingerprint": "{}"}}"#,
+            self.gadjid_version,
+            options.join(", "),
+            self.truth_fingerprint,
+            self.guess_fingerprint,
+        )
+    }
+}
+
+/// A distance between two graphs alongside its [`Provenance`].
+///
+/// With the `serde` feature, this implements `serde::Serialize`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(any(test, feature = "serde"), derive(serde::Serialize))]
+pub struct DistanceResult {
+    /// the name of the distance, such as `parent_aid`
+    pub distance: &'static str,
+    /// the normalized error, in \[0,1]
+    pub normalized: f64,
+    /// the total number of errors
+    pub mistakes: usize,
+    /// where the result comes from
+    pub provenance: Provenance,
+}
+
+impl DistanceResult {
+    /// Computes the `distance` between the `truth` and the `guess` graph
+    /// and records its provenance with the given `options`,
+    /// such as the edge direction the graphs were loaded with.
+    ///
+    /// ```
+    /// use gadjid::{graph_operations::{parent_aid, Distance}, provenance::DistanceResult, PDAG};
+    ///
+    /// // truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+    /// let truth = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 1],
+    ///     vec![0, 0, 0],
+    /// ]);
+    /// let guess = PDAG::from_dense_row_to_col(vec![
+    ///     vec![0, 1, 0],
+    ///     vec![0, 0, 0],
+    ///     vec![0, 1, 0],
+    /// ]);
+    ///
+    /// let options = [("edge_direction", "from row to column")];
+    /// let result = DistanceResult::compute(Distance::ParentAid, &truth, &guess, &options);
+    /// assert_eq!((result.normalized, result.mistakes), parent_aid(&truth, &guess));
+    /// assert_eq!(result.provenance.gadjid_version, env!("CARGO_PKG_VERSION"));
+    /// assert!(result.to_json().contains(r#""edge_direction": "from row to column""#));
+    /// ```
+    pub fn compute(
+        distance: Distance,
+        truth: &PDAG,
+        guess: &PDAG,
+        options: &[(&str, &str)],
+    ) -> Self {
+        let (normalized, mistakes) = distance.compute(truth, guess);
+        DistanceResult {
+            distance: distance.name(),
+            normalized,
+            mistakes,
+            provenance: Provenance::new(truth, guess, options),
+        }
+    }
+
+    /// The tuple of (normalized error (in \[0,1]), total number of errors).
+    pub fn value(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+
+    /// Writes the result and its provenance as a JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"distance": "{}", "normalized": {}, "mistakes": {}, "provenance": {}}}"#,
+            self.distance,
+            self.normalized,
+            self.mistakes,
+            self.provenance.to_json()
+        )
+    }
+}
+
+/// Quotes a string for JSON, escaping quotes, backslashes, and control characters.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str(r#"\""#),
+            '\\' => quoted.push_str(r"\\"),
+            c if c.is_control() => quoted.push_str(&format!(r"\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Reads the JSON string at the start of `json`, as written by [`json_string`],
+/// returns it unquoted alongside the rest of `json`.
+pub(crate) fn parse_json_string(json: &str) -> Option<(String, &str)> {
+    let mut chars = json.strip_prefix('"')?.char_indices();
+    let mut unquoted = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Some((unquoted, &json[i + 2..])),
+            '\\' => match chars.next()?.1 {
+                'u' => {
+                    let hex =
+                        String::from_iter((0..4).filter_map(|_| chars.next().map(|(_, c)| c)));
+                    unquoted.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+                }
+                escaped @ ('"' | '\\' | '/') => unquoted.push(escaped),
+                _ => return None,
+            },
+            c => unquoted.push(c),
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{graph_operations::Distance, PDAG};
+
+    use super::{graph_fingerprint, DistanceResult};
+
+    #[test]
+    fn results_record_their_provenance() {
+        let truth = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 0],
+        ]);
+        let guess = PDAG::from_dense_row_to_col(vec![
+            vec![0, 0, 0], //
+            vec![1, 0, 1],
+            vec![0, 0, 0],
+        ]);
+        assert_ne!(graph_fingerprint(&truth), graph_fingerprint(&guess));
+
+        let options = [
+            ("edge_direction", "from row to column"),
+            ("note", "a \"b\"\n"),
+        ];
+        for distance in Distance::ALL {
+            let result = DistanceResult::compute(distance, &truth, &guess, &options);
+            assert_eq!(result.value(), distance.compute(&truth, &guess));
+            assert_eq!(
+                result.provenance.truth_fingerprint,
+                graph_fingerprint(&truth)
+            );
+
+            let json = result.to_json();
+            assert!(json.starts_with(&format!(r#"{{"distance": "{}", "#, distance.name())));
+            assert!(json.contains(&format!(
+                r#""gadjid_version": "{}""#,
+                env!("CARGO_PKG_VERSION")
+            )));
+            assert!(json.contains(
+                r#""options": {"edge_direction": "from row to column", "note": "a \"b\"\u000a"}"#
+            ));
+        }
+    }
+}
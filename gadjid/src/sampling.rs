@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Samples random (treatments, effect, adjustment set) configurations the way the snapshot tests do,
+//! so that the methodology can be reproduced for other graphs, for example, to validate a pipeline
+//! with [`analyze_pair`](crate::analysis::analyze_pair) or to run power analyses.
+//!
+//! The samplers take any random number generator; for results that are reproducible across platforms,
+//! use a seeded `rand_chacha` generator, as the snapshot tests do with the seed from [`seed_from_names`].
+
+use std::hash::{Hash, Hasher};
+
+use rand::Rng;
+use rustc_hash::FxHasher;
+
+/// The size of a sampled set of nodes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetSize {
+    /// exactly this many nodes
+    Exactly(usize),
+    /// a number of nodes drawn uniformly from 1 to as many as may be used,
+    /// as in the snapshot tests
+    Uniform,
+}
+
+/// Disjoint treatment nodes, an effect node, and an adjustment set, as returned by [`sample_configuration`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Configuration {
+    /// the treatment nodes, sorted
+    pub treatments: Vec<usize>,
+    /// the effect node
+    pub effect: usize,
+    /// the adjustment set, sorted
+    pub adjustment_set: Vec<usize>,
+}
+
+/// Returns the seed that the snapshot tests derive from the names of the true and the guess graph,
+/// by hashing both with the fx algorithm (the std hasher may change between Rust releases).
+pub fn seed_from_names(truth_name: &str, guess_name: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    truth_name.hash(&mut hasher);
+    guess_name.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Samples a configuration of disjoint treatments, effect, and adjustment set among `n_nodes` nodes
+/// like the snapshot tests: after shuffling the nodes, the first is the effect, the next `treatments`
+/// nodes are the treatments, and the next `adjustment_set` nodes are the adjustment set.
+/// With [`SetSize::Uniform`], at least one node is left for the adjustment set.
+/// Panics if the sets do not fit into the `n_nodes` nodes.
+///
+/// ```
+/// use gadjid::sampling::{sample_configuration, SetSize};
+/// use rand::SeedableRng;
+///
+/// let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+/// let configuration = sample_configuration(10, SetSize::Exactly(3), SetSize::Uniform, &mut rng);
+/// assert_eq!(configuration.treatments.len(), 3);
+/// assert!((1..=6).contains(&configuration.adjustment_set.len()));
+/// assert!(!configuration.treatments.contains(&configuration.effect));
+/// ```
+pub fn sample_configuration(
+    n_nodes: usize,
+    treatments: SetSize,
+    adjustment_set: SetSize,
+    rng: &mut impl Rng,
+) -> Configuration {
+    let mut indices = Vec::from_iter(0..n_nodes);
+    rand::seq::SliceRandom::shuffle(&mut *indices, rng);
+
+    // sizes are drawn as u32, like in the snapshot tests, to consume the same random numbers
+    let t_size = match treatments {
+        SetSize::Exactly(size) => size,
+        SetSize::Uniform => {
+            // leaves room for the effect and the adjustment set, which has at least one node if of random size
+            let reserved = match adjustment_set {
+                SetSize::Exactly(size) => 1 + size,
+                SetSize::Uniform => 2,
+            };
+            assert!(
+                n_nodes > reserved,
+                "treatments, effect, and adjustment set must fit into the nodes"
+            );
+            rng.gen_range(1..=(n_nodes - reserved) as u32) as usize
+        }
+    };
+    assert!(
+        t_size < n_nodes,
+        "treatments and effect must fit into the nodes"
+    );
+    let z_size = match adjustment_set {
+        SetSize::Exactly(size) => size,
+        SetSize::Uniform => {
+            assert!(
+                t_size + 1 < n_nodes,
+                "no node is left for the adjustment set"
+            );
+            rng.gen_range(1..=(n_nodes - t_size - 1) as u32) as usize
+        }
+    };
+    assert!(
+        t_size + z_size < n_nodes,
+        "treatments, effect, and adjustment set must fit into the nodes"
+    );
+
+    let mut t = indices[1..1 + t_size].to_vec();
+    t.sort_unstable();
+    let mut z = indices[1 + t_size..1 + t_size + z_size].to_vec();
+    z.sort_unstable();
+    Configuration {
+        treatments: t,
+        effect: indices[0],
+        adjustment_set: z,
+    }
+}
+
+/// Samples `size` distinct treatment nodes among `n_nodes` nodes, uniformly. Returns them sorted.
+pub fn sample_treatments(n_nodes: usize, size: usize, rng: &mut impl Rng) -> Vec<usize> {
+    sample_excluding(n_nodes, size, &[], rng)
+}
+
+/// Samples an effect node among the `n_nodes` nodes that are not `treatments`, uniformly.
+pub fn sample_effect(n_nodes: usize, treatments: &[usize], rng: &mut impl Rng) -> usize {
+    sample_excluding(n_nodes, 1, treatments, rng)[0]
+}
+
+/// Samples `size` distinct nodes for an adjustment set among the `n_nodes` nodes that are not `excluded`,
+/// such as the treatments and the effect, uniformly. Returns them sorted.
+pub fn sample_adjustment_set(
+    n_nodes: usize,
+    size: usize,
+    excluded: &[usize],
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    sample_excluding(n_nodes, size, excluded, rng)
+}
+
+fn sample_excluding(
+    n_nodes: usize,
+    size: usize,
+    excluded: &[usize],
+    rng: &mut impl Rng,
+) -> Vec<usize> {
+    let candidates = Vec::from_iter((0..n_nodes).filter(|v| !excluded.contains(v)));
+    assert!(
+        size <= candidates.len(),
+        "cannot sample more nodes than there are candidates"
+    );
+    let mut sample = Vec::from_iter(
+        rand::seq::index::sample(rng, candidates.len(), size)
+            .into_iter()
+            .map(|i| candidates[i]),
+    );
+    sample.sort_unstable();
+    sample
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use super::{
+        sample_adjustment_set, sample_configuration, sample_effect, sample_treatments, SetSize,
+    };
+
+    #[test]
+    fn configurations_are_disjoint() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 3..20 {
+            for (treatments, adjustment_set) in [
+                (SetSize::Uniform, SetSize::Uniform),
+                (SetSize::Exactly(1), SetSize::Exactly(0)),
+                (SetSize::Exactly(n - 2), SetSize::Uniform),
+                (SetSize::Uniform, SetSize::Exactly(1)),
+                (SetSize::Uniform, SetSize::Exactly(n - 2)),
+            ] {
+                let configuration = sample_configuration(n, treatments, adjustment_set, &mut rng);
+                let mut nodes = configuration.treatments.clone();
+                nodes.push(configuration.effect);
+                nodes.extend(&configuration.adjustment_set);
+                let n_sampled = nodes.len();
+                nodes.sort_unstable();
+                nodes.dedup();
+                assert_eq!(nodes.len(), n_sampled);
+                assert!(nodes.iter().all(|v| *v < n));
+                assert!(configuration.treatments.windows(2).all(|w| w[0] < w[1]));
+                if let SetSize::Exactly(size) = treatments {
+                    assert_eq!(configuration.treatments.len(), size);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn samplers_respect_exclusions() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let treatments = sample_treatments(8, 3, &mut rng);
+        assert_eq!(treatments.len(), 3);
+        let effect = sample_effect(8, &treatments, &mut rng);
+        assert!(!treatments.contains(&effect));
+        let mut excluded = treatments.clone();
+        excluded.push(effect);
+        let z = sample_adjustment_set(8, 4, &excluded, &mut rng);
+        assert!(z.iter().all(|v| !excluded.contains(v)));
+        assert_eq!(z.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "treatments, effect, and adjustment set must fit into the nodes")]
+    fn panics_if_sets_do_not_fit() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        sample_configuration(5, SetSize::Exactly(3), SetSize::Exactly(2), &mut rng);
+    }
+}
@@ -0,0 +1,192 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Set backends for the visited states of the graph searches.
+//!
+//! The searches in gensearch and reachability mark the (edge, node, walk status) states they have visited,
+//! which is their hottest loop. Hashing the states into an [`FxHashSet`] needs memory in proportion to
+//! the number of visited states only, while a [`BitSet`] indexed by the states is allocated for all
+//! states of the graph upfront, but inserts and looks up without hashing or probing.
+//! Up to [`MAX_NODES_BITSET`] nodes, the bitset is small enough to be cleared for every search,
+//! so [`use_bitset`] selects it by graph size, unless the `hash-sets` feature is enabled.
+//! The `set_backends` benchmark compares both, run it with and without the feature.
+//! A search with effects of interest, as for selected pairs, stops early only once all of them are
+//! not amenable, and otherwise visits as many states as without them, so it selects the backend alike.
+//! While the [`RunStats`](crate::graph_operations::RunStats) of a distance are recorded,
+//! [`with_set_backend`] wraps the backend in [`Counted`], so that only these searches count their visited states.
+
+use rustc_hash::FxHashSet;
+
+use crate::{
+    graph_operations::run_stats::count_visited_triplet, partially_directed_acyclic_graph::Edge,
+};
+
+/// Up to this many nodes, the searches mark visited states in a [`BitSet`],
+/// which takes (4 × number of walk statuses × n) / 8 bytes per search.
+pub(crate) const MAX_NODES_BITSET: usize = 10_000;
+
+/// Whether the searches on a graph of `n_nodes` nodes use a [`BitSet`] rather than an [`FxHashSet`].
+pub(crate) fn use_bitset(n_nodes: usize) -> bool {
+    !cfg!(feature = "hash-sets") && n_nodes <= MAX_NODES_BITSET
+}
+
+/// Calls the search `$search::<S>(args..)` with the set backend `S` a [`BitSet`] if `$use_bitset`,
+/// else an [`FxHashSet`], which is wrapped in [`Counted`] while run stats are recorded on this thread.
+macro_rules! with_set_backend {
+    ($use_bitset:expr, $search:ident($($arg:expr),* $(,)?)) => {{
+        use $crate::sets::{BitSet, Counted};
+        type Hashed = ::rustc_hash::FxHashSet<usize>;
+        match (
+            $use_bitset,
+            $crate::graph_operations::run_stats::recording(),
+        ) {
+            (true, false) => $search::<BitSet>($($arg),*),
+            (false, false) => $search::<Hashed>($($arg),*),
+            (true, true) => $search::<Counted<BitSet>>($($arg),*),
+            (false, true) => $search::<Counted<Hashed>>($($arg),*),
+        }
+    }};
+}
+pub(crate) use with_set_backend;
+
+/// A set of keys in `0..n_keys`, the indices of search states.
+pub(crate) trait NodeSetImpl {
+    /// Creates an empty set for keys in `0..n_keys`.
+    fn for_keys(n_keys: usize) -> Self;
+    /// Adds the `key`, returns whether it was not in the set before.
+    fn insert(&mut self, key: usize) -> bool;
+    /// Whether the `key` is in the set.
+    fn contains(&self, key: usize) -> bool;
+}
+
+impl NodeSetImpl for FxHashSet<usize> {
+    fn for_keys(_n_keys: usize) -> Self {
+        FxHashSet::default()
+    }
+
+    fn insert(&mut self, key: usize) -> bool {
+        FxHashSet::insert(self, key)
+    }
+
+    fn contains(&self, key: usize) -> bool {
+        FxHashSet::contains(self, &key)
+    }
+}
+
+/// A set of keys in a fixed range, one bit per key.
+pub(crate) struct BitSet {
+    words: Vec<u64>,
+}
+
+impl NodeSetImpl for BitSet {
+    fn for_keys(n_keys: usize) -> Self {
+        BitSet {
+            words: vec![0; n_keys.div_ceil(64)],
+        }
+    }
+
+    fn insert(&mut self, key: usize) -> bool {
+        let (word, bit) = (&mut self.words[key / 64], 1 << (key % 64));
+        let inserted = *word & bit == 0;
+        *word |= bit;
+        inserted
+    }
+
+    fn contains(&self, key: usize) -> bool {
+        self.words[key / 64] & (1 << (key % 64)) != 0
+    }
+}
+
+/// A [`NodeSetImpl`] that counts every newly inserted key as a visited triplet of the run stats,
+/// so that the searches of the distances without stats do not pay for counting.
+pub(crate) struct Counted<S>(S);
+
+impl<S: NodeSetImpl> NodeSetImpl for Counted<S> {
+    fn for_keys(n_keys: usize) -> Self {
+        Counted(S::for_keys(n_keys))
+    }
+
+    fn insert(&mut self, key: usize) -> bool {
+        let inserted = self.0.insert(key);
+        if inserted {
+            count_visited_triplet();
+        }
+        inserted
+    }
+
+    fn contains(&self, key: usize) -> bool {
+        self.0.contains(key)
+    }
+}
+
+/// The visited (edge, node, walk status) states of a search, indexed into a [`NodeSetImpl`].
+/// Walk statuses are given by their index among the `n_statuses` statuses of the search.
+pub(crate) struct VisitedStates<S> {
+    set: S,
+    n_statuses: usize,
+}
+
+impl<S: NodeSetImpl> VisitedStates<S> {
+    /// Creates an empty set of states of a graph of `n_nodes` nodes and searches with `n_statuses` walk statuses.
+    pub(crate) fn new(n_nodes: usize, n_statuses: usize) -> Self {
+        VisitedStates {
+            set: S::for_keys(n_nodes * 4 * n_statuses),
+            n_statuses,
+        }
+    }
+
+    fn key(&self, edge: Edge, node: usize, status: usize) -> usize {
+        debug_assert!(status < self.n_statuses);
+        (node * 4 + edge as usize) * self.n_statuses + status
+    }
+
+    /// Adds the state, returns whether it was not visited before.
+    pub(crate) fn insert(&mut self, edge: Edge, node: usize, status: usize) -> bool {
+        let key = self.key(edge, node, status);
+        self.set.insert(key)
+    }
+
+    /// Whether the state has been visited.
+    pub(crate) fn contains(&self, edge: Edge, node: usize, status: usize) -> bool {
+        self.set.contains(self.key(edge, node, status))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng};
+    use rustc_hash::FxHashSet;
+
+    use crate::partially_directed_acyclic_graph::Edge;
+
+    use super::{BitSet, NodeSetImpl, VisitedStates};
+
+    #[test]
+    fn bitset_agrees_with_hashset() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n_keys in [1, 63, 64, 65, 1000] {
+            let mut bits = BitSet::for_keys(n_keys);
+            let mut hashed = <FxHashSet<usize> as NodeSetImpl>::for_keys(n_keys);
+            for _ in 0..2 * n_keys {
+                let key = rng.gen_range(0..n_keys);
+                assert_eq!(bits.insert(key), NodeSetImpl::insert(&mut hashed, key));
+                let probe = rng.gen_range(0..n_keys);
+                assert_eq!(bits.contains(probe), NodeSetImpl::contains(&hashed, probe));
+            }
+        }
+    }
+
+    #[test]
+    fn states_have_distinct_keys() {
+        let (n_nodes, n_statuses) = (5, 3);
+        let mut visited = VisitedStates::<BitSet>::new(n_nodes, n_statuses);
+        let edges = [Edge::Init, Edge::Incoming, Edge::Outgoing, Edge::Undirected];
+        for node in 0..n_nodes {
+            for edge in edges {
+                for status in 0..n_statuses {
+                    assert!(!visited.contains(edge, node, status));
+                    assert!(visited.insert(edge, node, status));
+                    assert!(!visited.insert(edge, node, status));
+                }
+            }
+        }
+    }
+}
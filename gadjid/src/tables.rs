@@ -0,0 +1,195 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Lays out the results of trajectories, curves, and metric comparisons as tidy long-format tables,
+//! with one named column per variable and one row per observation, so that plotting libraries such as
+//! ggplot2, seaborn, or matplotlib can use them directly. What the results were computed for, such as the
+//! name of the distance, is a column of its own, so that tables of several runs can simply be concatenated.
+
+use crate::graph_operations::{Distance, DropoutPoint};
+
+/// The values of one column of a [`LongTable`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Column {
+    /// text, such as names and identifiers
+    Text(Vec<String>),
+    /// counts and indices
+    Integer(Vec<usize>),
+    /// measurements, such as normalized distances
+    Float(Vec<f64>),
+}
+
+impl Column {
+    /// The number of values of the column.
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Text(values) => values.len(),
+            Column::Integer(values) => values.len(),
+            Column::Float(values) => values.len(),
+        }
+    }
+
+    /// Whether the column has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the value in `row` as it appears in a CSV file.
+    fn format(&self, row: usize) -> String {
+        match self {
+            Column::Text(values) => values[row].clone(),
+            Column::Integer(values) => values[row].to_string(),
+            Column::Float(values) => values[row].to_string(),
+        }
+    }
+}
+
+/// A table in long format, whose named columns all have one value per row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LongTable {
+    /// the names and values of the columns, in order
+    pub columns: Vec<(&'static str, Column)>,
+}
+
+impl LongTable {
+    /// Collects the named `columns`, which must all have the same number of values.
+    pub fn new(columns: Vec<(&'static str, Column)>) -> LongTable {
+        assert!(
+            columns.windows(2).all(|w| w[0].1.len() == w[1].1.len()),
+            "columns must all have the same number of values"
+        );
+        LongTable { columns }
+    }
+
+    /// The number of rows of the table.
+    pub fn n_rows(&self) -> usize {
+        self.columns.first().map_or(0, |(_, values)| values.len())
+    }
+
+    /// Writes the table as a CSV file with a header of the column names.
+    ///
+    /// ```
+    /// use gadjid::tables::{Column, LongTable};
+    ///
+    /// let table = LongTable::new(vec![
+    ///     ("step", Column::Integer(vec![0, 1])),
+    ///     ("normalized", Column::Float(vec![0.5, 0.25])),
+    /// ]);
+    /// assert_eq!(table.to_csv(), "step,normalized\n0,0.5\n1,0.25\n");
+    /// ```
+    pub fn to_csv(&self) -> String {
+        let header = Vec::from_iter(self.columns.iter().map(|(name, _)| *name));
+        let mut csv = header.join(",") + "\n";
+        for row in 0..self.n_rows() {
+            let values = Vec::from_iter(self.columns.iter().map(|(_, values)| values.format(row)));
+            csv.push_str(&(values.join(",") + "\n"));
+        }
+        csv
+    }
+}
+
+/// Lays out a `trajectory` of the `distance`, such as returned by
+/// [`trajectory_aid`](crate::graph_operations::trajectory_aid), with the columns `distance`, `step`
+/// (0 for the initial guess and `i` for the snapshot after the `i`-th operation), `normalized`, and `mistakes`.
+///
+/// ```
+/// use gadjid::{graph_operations::Distance, tables::trajectory_table};
+///
+/// let table = trajectory_table(Distance::ParentAid, &[(0.5, 3), (0.0, 0)]);
+/// assert_eq!(
+///     table.to_csv(),
+///     "distance,step,normalized,mistakes\nparent_aid,0,0.5,3\nparent_aid,1,0,0\n"
+/// );
+/// ```
+pub fn trajectory_table(distance: Distance, trajectory: &[(f64, usize)]) -> LongTable {
+    LongTable::new(vec![
+        (
+            "distance",
+            Column::Text(vec![distance.name().to_string(); trajectory.len()]),
+        ),
+        ("step", Column::Integer(Vec::from_iter(0..trajectory.len()))),
+        (
+            "normalized",
+            Column::Float(Vec::from_iter(
+                trajectory.iter().map(|(normalized, _)| *normalized),
+            )),
+        ),
+        (
+            "mistakes",
+            Column::Integer(Vec::from_iter(
+                trajectory.iter().map(|(_, mistakes)| *mistakes),
+            )),
+        ),
+    ])
+}
+
+/// Lays out a robustness `curve` of the `distance`, as returned by
+/// [`node_dropout_curve`](crate::graph_operations::node_dropout_curve), with the columns `distance`,
+/// `fraction`, `n_dropped`, `mean`, and `standard_deviation`, one row per dropout fraction.
+pub fn dropout_curve_table(distance: Distance, curve: &[DropoutPoint]) -> LongTable {
+    LongTable::new(vec![
+        (
+            "distance",
+            Column::Text(vec![distance.name().to_string(); curve.len()]),
+        ),
+        (
+            "fraction",
+            Column::Float(Vec::from_iter(curve.iter().map(|point| point.fraction))),
+        ),
+        (
+            "n_dropped",
+            Column::Integer(Vec::from_iter(curve.iter().map(|point| point.n_dropped))),
+        ),
+        (
+            "mean",
+            Column::Float(Vec::from_iter(curve.iter().map(|point| point.mean))),
+        ),
+        (
+            "standard_deviation",
+            Column::Float(Vec::from_iter(
+                curve.iter().map(|point| point.standard_deviation),
+            )),
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{
+        graph_operations::{node_dropout_curve, shd, Distance},
+        PDAG,
+    };
+
+    use super::{dropout_curve_table, Column, LongTable};
+
+    #[test]
+    fn lays_out_one_row_per_point() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_dag(0.3, 10, &mut rng);
+        let guess = PDAG::random_dag(0.3, 10, &mut rng);
+        let curve = node_dropout_curve(&truth, &guess, &[0.0, 0.3, 0.6], 5, shd, &mut rng);
+
+        let table = dropout_curve_table(Distance::Shd, &curve);
+        assert_eq!(table.n_rows(), 3);
+        assert_eq!(
+            table.columns[2],
+            ("n_dropped", Column::Integer(vec![0, 3, 6]))
+        );
+        let csv = table.to_csv();
+        let lines = Vec::from_iter(csv.lines());
+        assert_eq!(
+            lines[0],
+            "distance,fraction,n_dropped,mean,standard_deviation"
+        );
+        assert!(lines[2].starts_with("shd,0.3,3,"));
+    }
+
+    #[test]
+    #[should_panic(expected = "columns must all have the same number of values")]
+    fn rejects_ragged_columns() {
+        LongTable::new(vec![
+            ("a", Column::Integer(vec![1, 2])),
+            ("b", Column::Float(vec![0.5])),
+        ]);
+    }
+}
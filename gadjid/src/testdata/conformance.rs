@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Conformance fixtures for the language bindings.
+//!
+//! The bindings wrap the same Rust functions, but each converts graphs and results on its own,
+//! so they can drift apart. [`conformance_table`] computes every [`Distance`] on a fixed set of
+//! graph pairs and lays out the expected results as a long-format table, one row per graph pair
+//! and distance, with the columns `case`, `g_true`, `g_guess`, `distance`, `normalized`, and `mistakes`.
+//! The graphs are written inline as row-to-column adjacency matrices, one digit per entry
+//! (0 for no edge, 1 for a directed, 2 for an undirected edge) and rows separated by `;`,
+//! so that `0-->1` on two nodes reads `01;00`. Normalized distances are written in the shortest
+//! form that parses back to the same `f64`, so that the bindings can compare them exactly.
+//!
+//! The table is kept at `testgraphs/conformance.csv`, from where the test suites of the bindings
+//! read it. A test checks that the file is up to date; after a change of the graph pairs or the
+//! distances, regenerate it with `cargo test -- --ignored write_conformance_corpus`.
+
+use std::{io, path::Path};
+
+use rand::SeedableRng;
+
+use crate::{
+    graph_operations::Distance,
+    tables::{Column, LongTable},
+    PDAG,
+};
+
+use super::read_mtx;
+
+/// Name of the file in `testgraphs/` that holds the conformance table.
+pub const CORPUS: &str = "conformance.csv";
+
+/// Writes the row-to-column adjacency matrix of the `graph` as in the conformance table.
+pub fn encode_graph(graph: &PDAG) -> String {
+    let rows =
+        Vec::from_iter(graph.to_row_to_column_vecvec().into_iter().map(|row| {
+            String::from_iter(row.into_iter().map(|entry| char::from(b'0' + entry as u8)))
+        }));
+    rows.join(";")
+}
+
+/// Returns the named graph pairs of the conformance table: small edge cases, random DAGs and
+/// PDAGs with 2 to 12 nodes drawn from a fixed seed, and consecutive 10-node DAGs and CPDAGs
+/// from the `testgraphs` directory.
+pub fn cases(testgraphs: &Path) -> io::Result<Vec<(String, PDAG, PDAG)>> {
+    let mut cases = vec![
+        (
+            "2-node-empty-vs-edge".to_string(),
+            PDAG::from_dense_row_to_col(vec![vec![0, 0], vec![0, 0]]),
+            PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]),
+        ),
+        (
+            "2-node-reversed".to_string(),
+            PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]),
+            PDAG::from_dense_row_to_col(vec![vec![0, 0], vec![1, 0]]),
+        ),
+        (
+            "2-node-undirected".to_string(),
+            PDAG::from_dense_row_to_col(vec![vec![0, 1], vec![0, 0]]),
+            PDAG::from_dense_row_to_col(vec![vec![0, 2], vec![2, 0]]),
+        ),
+        (
+            "3-node-chain-vs-collider".to_string(),
+            PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 1], vec![0, 0, 0]]),
+            PDAG::from_dense_row_to_col(vec![vec![0, 1, 0], vec![0, 0, 0], vec![0, 1, 0]]),
+        ),
+    ];
+
+    let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+    for n in 2..=12 {
+        let (truth, guess) = (
+            PDAG::random_dag(0.3, n, &mut rng),
+            PDAG::random_dag(0.3, n, &mut rng),
+        );
+        cases.push((format!("{n}-node-random-DAGs"), truth, guess));
+        let (truth, guess) = (
+            PDAG::random_pdag(0.3, n, &mut rng),
+            PDAG::random_pdag(0.3, n, &mut rng),
+        );
+        cases.push((format!("{n}-node-random-PDAGs"), truth, guess));
+    }
+
+    for kind in ["DAG", "CPDAG"] {
+        for graph_id in 10..=19 {
+            let next_id = 10 + (graph_id + 1) % 10;
+            let load = |id: usize| read_mtx(&testgraphs.join(format!("10-node-{kind}-{id}.mtx")));
+            cases.push((
+                format!("10-node-{kind}{graph_id}-vs-{kind}{next_id}"),
+                load(graph_id)?,
+                load(next_id)?,
+            ));
+        }
+    }
+    Ok(cases)
+}
+
+/// Computes every [`Distance`] on each of the [`cases`] and lays out the results
+/// as described in the [module documentation](self).
+pub fn conformance_table(testgraphs: &Path) -> io::Result<LongTable> {
+    let (mut case, mut g_true, mut g_guess) = (Vec::new(), Vec::new(), Vec::new());
+    let (mut distance, mut normalized, mut mistakes) = (Vec::new(), Vec::new(), Vec::new());
+    for (name, truth, guess) in cases(testgraphs)? {
+        for d in Distance::ALL {
+            let (norm, count) = d.compute(&truth, &guess);
+            case.push(name.clone());
+            g_true.push(encode_graph(&truth));
+            g_guess.push(encode_graph(&guess));
+            distance.push(d.name().to_string());
+            normalized.push(norm);
+            mistakes.push(count);
+        }
+    }
+    Ok(LongTable::new(vec![
+        ("case", Column::Text(case)),
+        ("g_true", Column::Text(g_true)),
+        ("g_guess", Column::Text(g_guess)),
+        ("distance", Column::Text(distance)),
+        ("normalized", Column::Float(normalized)),
+        ("mistakes", Column::Integer(mistakes)),
+    ]))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::PDAG;
+
+    use super::{conformance_table, encode_graph, CORPUS};
+
+    fn testgraphs() -> PathBuf {
+        // anchors at parent directory of Cargo.toml
+        PathBuf::from("..").join("testgraphs")
+    }
+
+    #[test]
+    fn encodes_graphs_row_by_row() {
+        // 0 -> 1, 1 -- 2
+        let pdag = PDAG::from_dense_row_to_col(vec![
+            vec![0, 1, 0], //
+            vec![0, 0, 2],
+            vec![0, 0, 0],
+        ]);
+        assert_eq!(encode_graph(&pdag), "010;002;020");
+    }
+
+    #[test]
+    fn conformance_corpus_is_up_to_date() {
+        let expected = conformance_table(&testgraphs()).unwrap().to_csv();
+        let stored = std::fs::read_to_string(testgraphs().join(CORPUS)).unwrap();
+        assert!(
+            stored == expected,
+            "testgraphs/{CORPUS} is out of date, regenerate it with \
+            `cargo test -- --ignored write_conformance_corpus`"
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn write_conformance_corpus() {
+        let csv = conformance_table(&testgraphs()).unwrap().to_csv();
+        std::fs::write(testgraphs().join(CORPUS), csv).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Large benchmark graphs for regression testing at scale.
+//!
+//! The snapshot tests in `lib.rs` load fixed 10- and 100-node graphs from `testgraphs/`.
+//! Graphs with 10⁴–10⁵ nodes are too large to keep in the repository, so the fixtures
+//! listed in [`FIXTURES`] are generated deterministically from a seed instead
+//! (using rand_chacha, which is reproducible across platforms).
+//!
+//! If the environment variable `GADJID_TESTDATA_DIR` is set, [`Fixture::load`] first looks for
+//! `{name}-true.mtx` and `{name}-guess.mtx` in that directory, so pre-generated or downloaded
+//! fixtures are used as-is; missing files are generated and written there for the next run.
+//!
+//! The golden distance values are stored as insta snapshots and checked by an ignored test:
+//! `cargo test --profile test-opt --features testdata -- --ignored large_scale`
+
+pub mod conformance;
+pub mod reference;
+pub mod shrink;
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use rand::{Rng, SeedableRng};
+use rustc_hash::FxHashSet;
+
+use crate::{EdgelistIterator, PDAG};
+
+/// Edges `(parent, child)` of a DAG.
+pub type Edges = Vec<(usize, usize)>;
+
+/// Describes a pair of large random DAGs, a true graph and a perturbed guess.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixture {
+    /// Name used for cache files and snapshots.
+    pub name: &'static str,
+    /// Number of nodes of both graphs.
+    pub n_nodes: usize,
+    /// Expected number of adjacent nodes per node in the true graph.
+    pub expected_degree: f64,
+    /// Fraction of edges of the true graph that are dropped in the guess,
+    /// the guess gets the same number of new edges in their place.
+    pub perturbation: f64,
+    /// Seed for the rand_chacha generator.
+    pub seed: u64,
+}
+
+/// The large fixtures checked by the `large_scale` snapshot test.
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "10000-node-DAG-deg2",
+        n_nodes: 10_000,
+        expected_degree: 2.0,
+        perturbation: 0.1,
+        seed: 10_000,
+    },
+    Fixture {
+        name: "10000-node-DAG-deg4",
+        n_nodes: 10_000,
+        expected_degree: 4.0,
+        perturbation: 0.05,
+        seed: 10_001,
+    },
+    Fixture {
+        name: "100000-node-DAG-deg2",
+        n_nodes: 100_000,
+        expected_degree: 2.0,
+        perturbation: 0.01,
+        seed: 100_000,
+    },
+];
+
+impl Fixture {
+    /// Returns the edges `(parent, child)` of the true and the guess graph, each sorted row-by-row.
+    pub fn generate(&self) -> (Edges, Edges) {
+        let n = self.n_nodes;
+        assert!(n >= 2, "fixtures need at least 2 nodes");
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(self.seed);
+
+        // edges are first drawn between positions in a causal order, which keeps both graphs
+        // acyclic, and then mapped to nodes by a random permutation
+        let n_pairs = n * (n - 1) / 2;
+        let n_edges = ((self.expected_degree * n as f64 / 2.0).round() as usize).min(n_pairs);
+        let mut truth = FxHashSet::default();
+        while truth.len() < n_edges {
+            truth.insert(random_ordered_pair(n, &mut rng));
+        }
+        let mut truth = Vec::from_iter(truth);
+        // sorting before drawing the perturbation, hash set iteration order is not specified
+        truth.sort();
+
+        let drop = rand::distributions::Bernoulli::new(self.perturbation).unwrap();
+        let mut guess = FxHashSet::default();
+        let mut n_dropped = 0;
+        for &edge in truth.iter() {
+            if rng.sample(drop) {
+                n_dropped += 1;
+            } else {
+                guess.insert(edge);
+            }
+        }
+        let target = (guess.len() + n_dropped).min(n_pairs);
+        while guess.len() < target {
+            guess.insert(random_ordered_pair(n, &mut rng));
+        }
+        let mut guess = Vec::from_iter(guess);
+        guess.sort();
+
+        let permutation = rand::seq::index::sample(&mut rng, n, n);
+        let relabel = |edges: Edges| {
+            let mut edges = Vec::from_iter(
+                edges
+                    .into_iter()
+                    .map(|(x, y)| (permutation.index(x), permutation.index(y))),
+            );
+            edges.sort();
+            edges
+        };
+        (relabel(truth), relabel(guess))
+    }
+
+    /// Loads the true and the guess graph, from `GADJID_TESTDATA_DIR` if the files exist there,
+    /// generating (and caching, if the directory is set) them otherwise.
+    pub fn load(&self) -> io::Result<(PDAG, PDAG)> {
+        let Some(dir) = std::env::var_os("GADJID_TESTDATA_DIR").map(PathBuf::from) else {
+            let (truth, guess) = self.generate();
+            return Ok((
+                dag_from_sorted_edges(self.n_nodes, truth),
+                dag_from_sorted_edges(self.n_nodes, guess),
+            ));
+        };
+        let true_path = dir.join(format!("{}-true.mtx", self.name));
+        let guess_path = dir.join(format!("{}-guess.mtx", self.name));
+        if !true_path.exists() || !guess_path.exists() {
+            std::fs::create_dir_all(&dir)?;
+            let (truth, guess) = self.generate();
+            write_mtx(&true_path, self.n_nodes, &truth)?;
+            write_mtx(&guess_path, self.n_nodes, &guess)?;
+        }
+        Ok((read_mtx(&true_path)?, read_mtx(&guess_path)?))
+    }
+}
+
+/// Draws a pair `(x, y)` with `x < y` uniformly at random.
+fn random_ordered_pair(n: usize, rng: &mut impl Rng) -> (usize, usize) {
+    loop {
+        let x = rng.gen_range(0..n);
+        let y = rng.gen_range(0..n);
+        if x != y {
+            return (x.min(y), x.max(y));
+        }
+    }
+}
+
+/// Builds a DAG from edges `(parent, child)` sorted row-by-row, without a dense intermediate.
+fn dag_from_sorted_edges(n_nodes: usize, edges: Edges) -> PDAG {
+    PDAG::try_from_row_major(
+        edges
+            .into_iter()
+            .map(|(x, y)| (x, y, 1))
+            .into_row_major_edgelist(n_nodes),
+    )
+    .expect("fixture graphs are acyclic")
+}
+
+/// Writes the edges as a 1-indexed MatrixMarket pattern file, like the DAGs in `testgraphs/`.
+pub fn write_mtx(path: &Path, n_nodes: usize, edges: &[(usize, usize)]) -> io::Result<()> {
+    let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+    writeln!(file, "%%MatrixMarket matrix coordinate pattern general")?;
+    writeln!(file, "{} {} {}", n_nodes, n_nodes, edges.len())?;
+    for (x, y) in edges {
+        writeln!(file, "{} {}", x + 1, y + 1)?;
+    }
+    file.flush()
+}
+
+/// Reads a PDAG from a 1-indexed MatrixMarket file, either a pattern file as written by
+/// [`write_mtx`] or, like the CPDAGs in `testgraphs/`, an integer file with edge codes
+/// (1 for a directed, 2 for an undirected edge).
+pub fn read_mtx(path: &Path) -> io::Result<PDAG> {
+    let mtx = std::fs::read_to_string(path)?;
+    crate::corpus::parse_mtx(&mtx)
+        .map_err(|msg| io::Error::new(io::ErrorKind::InvalidData, format!("{path:?}: {msg}")))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_mtx, write_mtx, Fixture};
+
+    const SMALL: Fixture = Fixture {
+        name: "small",
+        n_nodes: 50,
+        expected_degree: 3.0,
+        perturbation: 0.2,
+        seed: 0,
+    };
+
+    #[test]
+    fn generation_is_deterministic() {
+        let (truth, guess) = SMALL.generate();
+        assert_eq!((truth.clone(), guess.clone()), SMALL.generate());
+        assert_eq!(truth.len(), 75);
+        assert_eq!(guess.len(), 75);
+        assert_ne!(truth, guess);
+        let different_seed = Fixture { seed: 1, ..SMALL };
+        assert_ne!(truth, different_seed.generate().0);
+    }
+
+    #[test]
+    fn mtx_roundtrip() {
+        let (truth, _) = SMALL.generate();
+        let path = std::env::temp_dir().join(format!("gadjid-testdata-{}.mtx", std::process::id()));
+        write_mtx(&path, SMALL.n_nodes, &truth).unwrap();
+        let loaded = read_mtx(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.n_nodes, SMALL.n_nodes);
+        for node in 0..SMALL.n_nodes {
+            let children =
+                Vec::from_iter(truth.iter().filter(|(x, _)| *x == node).map(|(_, y)| *y));
+            assert_eq!(loaded.children_of(node), children);
+        }
+    }
+}
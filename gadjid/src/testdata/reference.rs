@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Differential testing against reference implementations.
+//!
+//! Reference results are exported from R (for example with `SID::structIntervDist` or
+//! `pcalg::shd`) as a CSV file whose first two columns identify the true and the guess graph
+//! and whose remaining columns each hold the reference value of one metric, like
+//! `testgraphs/SID-100-node-DAGs.csv`:
+//!
+//! ```text
+//! G_true,G_guess,SHD,SID
+//! 21,20,250,1540
+//! ```
+//!
+//! A [`Corpus`] pairs such a file with the location of the graphs, and each [`Check`] names a
+//! column and the computation in gadjid that is expected to agree with it. Columns without a
+//! check are ignored, so a single export can serve several checks.
+//!
+//! If the reference implementation can also be run on other graphs, such as through a script,
+//! [`Corpus::shrink_disagreements`] shrinks the graphs of each disagreement to a small reproducer.
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+    path::PathBuf,
+};
+
+use crate::{
+    graph_operations::{parent_aid, shd},
+    PDAG,
+};
+
+use super::{read_mtx, shrink::Counterexample};
+
+/// A reference column and the gadjid computation that is expected to reproduce it.
+#[derive(Clone, Copy)]
+pub struct Check {
+    /// Name of the column in the header of the reference file.
+    pub column: &'static str,
+    /// Computes the number of mistakes of the guess graph with respect to the true graph.
+    pub compute: fn(&PDAG, &PDAG) -> usize,
+}
+
+/// The Structural Intervention Distance as computed by the R package SID,
+/// which equals the number of mistakes of the Parent-AID between DAGs.
+pub const SID: Check = Check {
+    column: "SID",
+    compute: |g_true, g_guess| parent_aid(g_true, g_guess).1,
+};
+
+/// The Structural Hamming Distance as computed by `pcalg::shd`.
+pub const SHD: Check = Check {
+    column: "SHD",
+    compute: |g_true, g_guess| shd(g_true, g_guess).1,
+};
+
+/// A reference file together with the graphs it refers to.
+pub struct Corpus {
+    /// CSV file with reference values.
+    pub references: PathBuf,
+    /// Directory that holds the graphs as MatrixMarket files.
+    pub graphs: PathBuf,
+    /// File name of a graph, with `{}` standing in for the identifier used in the reference file.
+    pub graph_file: String,
+}
+
+/// A reference value that gadjid does not reproduce.
+#[derive(Debug, PartialEq)]
+pub struct Disagreement {
+    /// Identifier of the true graph.
+    pub g_true: String,
+    /// Identifier of the guess graph.
+    pub g_guess: String,
+    /// Column of the reference value.
+    pub column: &'static str,
+    /// The reference value.
+    pub reference: usize,
+    /// The value computed by gadjid.
+    pub computed: usize,
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of guess {} against truth {}: reference {}, computed {}",
+            self.column, self.g_guess, self.g_true, self.reference, self.computed
+        )
+    }
+}
+
+impl Corpus {
+    /// Creates a corpus of graphs and references in the `testgraphs` directory of the repository.
+    pub fn testgraphs(references: &str, graph_file: &str) -> Corpus {
+        let testgraphs = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("testgraphs");
+        Corpus {
+            references: testgraphs.join(references),
+            graphs: testgraphs,
+            graph_file: graph_file.to_string(),
+        }
+    }
+
+    fn load(&self, id: &str) -> io::Result<PDAG> {
+        read_mtx(&self.graphs.join(self.graph_file.replace("{}", id)))
+    }
+
+    /// Compares every row of the reference file against the given checks. Returns the number of
+    /// compared values and the disagreements, or an error if the reference file or a graph
+    /// cannot be read or a checked column is missing.
+    pub fn compare(&self, checks: &[Check]) -> io::Result<(usize, Vec<Disagreement>)> {
+        let invalid = |msg: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{:?}: {}", self.references, msg),
+            )
+        };
+        let file = io::BufReader::new(std::fs::File::open(&self.references)?);
+        let mut lines = file.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid("missing header".to_string()))??;
+        let header = Vec::from_iter(header.split(',').map(|name| name.trim().trim_matches('"')));
+
+        let mut columns = Vec::with_capacity(checks.len());
+        for check in checks {
+            match header.iter().skip(2).position(|name| *name == check.column) {
+                Some(position) => columns.push((check, position + 2)),
+                None => return Err(invalid(format!("missing column {}", check.column))),
+            }
+        }
+
+        let mut n_compared = 0;
+        let mut disagreements = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = Vec::from_iter(line.split(',').map(|entry| entry.trim().trim_matches('"')));
+            if row.len() != header.len() {
+                return Err(invalid(format!("row {line:?} does not match the header")));
+            }
+            let (g_true, g_guess) = (self.load(row[0])?, self.load(row[1])?);
+            for &(check, column) in columns.iter() {
+                let reference = parse_count(row[column])
+                    .ok_or_else(|| invalid(format!("{:?} is not a count", row[column])))?;
+                let computed = (check.compute)(&g_true, &g_guess);
+                n_compared += 1;
+                if computed != reference {
+                    disagreements.push(Disagreement {
+                        g_true: row[0].to_string(),
+                        g_guess: row[1].to_string(),
+                        column: check.column,
+                        reference,
+                        computed,
+                    });
+                }
+            }
+        }
+        Ok((n_compared, disagreements))
+    }
+
+    /// Panics, listing all disagreements, unless gadjid reproduces every checked reference value.
+    pub fn assert_agreement(&self, checks: &[Check]) {
+        let (n_compared, disagreements) = self.compare(checks).unwrap();
+        assert!(n_compared > 0, "{:?} has no rows", self.references);
+        assert!(
+            disagreements.is_empty(),
+            "{} of {} reference values differ:\n{}",
+            disagreements.len(),
+            n_compared,
+            Vec::from_iter(disagreements.iter().map(|d| d.to_string())).join("\n")
+        );
+    }
+}
+
+impl Corpus {
+    /// Compares every row of the reference file against the given checks like [`Corpus::compare`],
+    /// and shrinks the graphs of each disagreement while the check still disagrees with the
+    /// `reference` implementation, which computes the value of a column for a true and a guess graph.
+    pub fn shrink_disagreements(
+        &self,
+        checks: &[Check],
+        reference: impl Fn(&'static str, &PDAG, &PDAG) -> usize,
+    ) -> io::Result<Vec<(Disagreement, Counterexample)>> {
+        let (_, disagreements) = self.compare(checks)?;
+        let mut shrunk = Vec::with_capacity(disagreements.len());
+        for disagreement in disagreements {
+            let check = checks
+                .iter()
+                .find(|check| check.column == disagreement.column)
+                .expect("disagreements are found by one of the checks");
+            let (g_true, g_guess) = (
+                self.load(&disagreement.g_true)?,
+                self.load(&disagreement.g_guess)?,
+            );
+            let counterexample = Counterexample::shrink(&g_true, &g_guess, |g_true, g_guess| {
+                (check.compute)(g_true, g_guess) != reference(check.column, g_true, g_guess)
+            });
+            shrunk.push((disagreement, counterexample));
+        }
+        Ok(shrunk)
+    }
+}
+
+/// R writes counts either as integers or as doubles like `12.0` or `1e+05`.
+fn parse_count(entry: &str) -> Option<usize> {
+    entry.parse::<usize>().ok().or_else(|| {
+        let value = entry.parse::<f64>().ok()?;
+        (value >= 0.0 && value.fract() == 0.0).then_some(value as usize)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_count, Check, Corpus, Disagreement, SHD, SID};
+
+    #[test]
+    fn agrees_with_r_on_small_dags() {
+        Corpus::testgraphs("SID-10-node-DAGs.csv", "10-node-DAG-{}.mtx")
+            .assert_agreement(&[SID, SHD]);
+    }
+
+    #[test]
+    fn reports_disagreements() {
+        let off_by_one = Check {
+            column: "SID",
+            compute: |g_true, g_guess| (SID.compute)(g_true, g_guess) + 1,
+        };
+        let corpus = Corpus::testgraphs("SID-10-node-DAGs.csv", "10-node-DAG-{}.mtx");
+        let (n_compared, disagreements) = corpus.compare(&[SHD, off_by_one]).unwrap();
+        assert_eq!(n_compared, 2 * disagreements.len());
+        assert_eq!(
+            disagreements[0],
+            Disagreement {
+                g_true: "11".to_string(),
+                g_guess: "10".to_string(),
+                column: "SID",
+                reference: 35,
+                computed: 36,
+            }
+        );
+
+        let missing = Check {
+            column: "AID",
+            compute: SID.compute,
+        };
+        assert!(corpus.compare(&[missing]).is_err());
+    }
+
+    #[test]
+    fn shrinks_disagreements() {
+        // a bug that only shows for guess graphs with a node with at least two parents
+        let with_bug = Check {
+            column: "SHD",
+            compute: |g_true, g_guess| {
+                let has_collider = (0..g_guess.n_nodes).any(|v| g_guess.parents_of(v).len() > 1);
+                (SHD.compute)(g_true, g_guess) + usize::from(has_collider)
+            },
+        };
+        let corpus = Corpus::testgraphs("SID-10-node-DAGs.csv", "10-node-DAG-{}.mtx");
+        let shrunk = corpus
+            .shrink_disagreements(&[with_bug], |_, g_true, g_guess| {
+                (SHD.compute)(g_true, g_guess)
+            })
+            .unwrap();
+        assert!(!shrunk.is_empty());
+        for (_, counterexample) in shrunk {
+            assert_eq!(counterexample.truth.n_nodes, 3);
+            assert_eq!(counterexample.guess.n_directed_edges, 2);
+        }
+    }
+
+    #[test]
+    fn parses_r_counts() {
+        assert_eq!(parse_count("12"), Some(12));
+        assert_eq!(parse_count("12.0"), Some(12));
+        assert_eq!(parse_count("1e+05"), Some(100_000));
+        assert_eq!(parse_count("1.5"), None);
+        assert_eq!(parse_count("NA"), None);
+    }
+}
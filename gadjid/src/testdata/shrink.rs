@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: MPL-2.0
+//! Shrinking of counterexamples.
+//!
+//! When gadjid disagrees with a reference implementation on a pair of graphs, the graphs are
+//! usually too large to see why. [`Counterexample::shrink`] repeatedly removes nodes and edges
+//! from both graphs as long as the disagreement persists, and the shrunk counterexample
+//! displays as a reproducer in the `PDAG::from_dense_row_to_col` literal form of the tests.
+
+use std::fmt;
+
+use crate::{EdgelistIterator, PDAG};
+
+/// A true and a guess graph on which gadjid disagrees with a reference implementation.
+pub struct Counterexample {
+    /// The true graph.
+    pub truth: PDAG,
+    /// The guess graph.
+    pub guess: PDAG,
+}
+
+/// The edges of a graph as row-major `(from, to, edge type)` entries,
+/// listing each undirected edge once, from the smaller node.
+fn entries(graph: &PDAG) -> Vec<(usize, usize, i8)> {
+    let mut entries = Vec::new();
+    for v in 0..graph.n_nodes {
+        let undirected = graph.adjacent_undirected_of(v).iter().filter(|w| v < **w);
+        entries.extend(graph.children_of(v).iter().map(|w| (v, *w, 1)));
+        entries.extend(undirected.map(|w| (v, *w, 2)));
+    }
+    entries.sort_unstable();
+    entries
+}
+
+fn from_entries(n_nodes: usize, entries: Vec<(usize, usize, i8)>) -> PDAG {
+    PDAG::try_from_row_major(entries.into_iter().into_row_major_edgelist(n_nodes))
+        .expect("removing nodes or edges keeps a graph acyclic")
+}
+
+impl Counterexample {
+    /// Shrinks the `truth` and the `guess` graph, for which `disagrees` must hold,
+    /// to graphs for which it still holds but no single node (of both graphs)
+    /// or edge (of either graph) can be removed without it failing.
+    pub fn shrink(
+        truth: &PDAG,
+        guess: &PDAG,
+        mut disagrees: impl FnMut(&PDAG, &PDAG) -> bool,
+    ) -> Counterexample {
+        assert!(
+            disagrees(truth, guess),
+            "the graphs must be a counterexample to begin with"
+        );
+        let (mut truth, mut guess) = (
+            from_entries(truth.n_nodes, entries(truth)),
+            from_entries(guess.n_nodes, entries(guess)),
+        );
+
+        let mut shrunk = true;
+        while shrunk {
+            shrunk = false;
+
+            let mut v = 0;
+            while v < truth.n_nodes {
+                let kept = Vec::from_iter((0..truth.n_nodes).filter(|w| *w != v));
+                let (smaller_truth, smaller_guess) =
+                    (truth.induced_subgraph(&kept), guess.induced_subgraph(&kept));
+                if disagrees(&smaller_truth, &smaller_guess) {
+                    (truth, guess) = (smaller_truth, smaller_guess);
+                    shrunk = true;
+                } else {
+                    v += 1;
+                }
+            }
+
+            for is_truth in [true, false] {
+                let graph = if is_truth { &truth } else { &guess };
+                let mut edges = entries(graph);
+                let mut i = 0;
+                while i < edges.len() {
+                    let mut fewer = edges.clone();
+                    fewer.remove(i);
+                    let smaller = from_entries(truth.n_nodes, fewer.clone());
+                    let still_disagrees = match is_truth {
+                        true => disagrees(&smaller, &guess),
+                        false => disagrees(&truth, &smaller),
+                    };
+                    if still_disagrees {
+                        edges = fewer;
+                        shrunk = true;
+                    } else {
+                        i += 1;
+                    }
+                }
+                match is_truth {
+                    true => truth = from_entries(truth.n_nodes, edges),
+                    false => guess = from_entries(guess.n_nodes, edges),
+                }
+            }
+        }
+        Counterexample { truth, guess }
+    }
+}
+
+/// Writes the adjacency matrix of the graph as a `PDAG::from_dense_row_to_col` literal.
+fn write_literal(f: &mut fmt::Formatter<'_>, name: &str, graph: &PDAG) -> fmt::Result {
+    writeln!(f, "let {name} = PDAG::from_dense_row_to_col(vec![")?;
+    for (i, row) in graph.to_row_to_column_vecvec().iter().enumerate() {
+        let row = Vec::from_iter(row.iter().map(|entry| entry.to_string()));
+        // the comment keeps rustfmt from joining the rows into a single line
+        let comment = if i == 0 { " //" } else { "" };
+        writeln!(f, "    vec![{}],{comment}", row.join(", "))?;
+    }
+    writeln!(f, "]);")
+}
+
+impl fmt::Display for Counterexample {
+    /// Formats the counterexample as a reproducer that can be pasted into a test.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_literal(f, "truth", &self.truth)?;
+        write_literal(f, "guess", &self.guess)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::SeedableRng;
+
+    use crate::{graph_operations::shd, PDAG};
+
+    use super::Counterexample;
+
+    /// Counts the nodes with at least two parents, a stand-in for a bug triggered by colliders.
+    fn n_colliders(graph: &PDAG) -> usize {
+        (0..graph.n_nodes)
+            .filter(|v| graph.parents_of(*v).len() >= 2)
+            .count()
+    }
+
+    #[test]
+    fn shrinks_to_minimal_collider() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        let truth = PDAG::random_dag(0.5, 15, &mut rng);
+        let guess = PDAG::random_pdag(0.5, 15, &mut rng);
+        let counterexample =
+            Counterexample::shrink(&truth, &guess, |_, guess| n_colliders(guess) > 0);
+        assert_eq!(
+            counterexample.to_string(),
+            "let truth = PDAG::from_dense_row_to_col(vec![
+    vec![0, 0, 0], //
+    vec![0, 0, 0],
+    vec![0, 0, 0],
+]);
+let guess = PDAG::from_dense_row_to_col(vec![
+    vec![0, 0, 0], //
+    vec![1, 0, 0],
+    vec![1, 0, 0],
+]);
+"
+        );
+    }
+
+    #[test]
+    fn property_shrunk_counterexamples_still_disagree() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(0);
+        for n in 2..20 {
+            let truth = PDAG::random_pdag(0.4, n, &mut rng);
+            let guess = PDAG::random_pdag(0.4, n, &mut rng);
+            let disagrees = |truth: &PDAG, guess: &PDAG| shd(truth, guess).1 >= 3;
+            if !disagrees(&truth, &guess) {
+                continue;
+            }
+            let counterexample = Counterexample::shrink(&truth, &guess, disagrees);
+            // three differing pairs of nodes remain, and each needs an edge in either graph
+            assert_eq!(shd(&counterexample.truth, &counterexample.guess).1, 3);
+            assert!(counterexample.truth.n_nodes <= 6);
+        }
+    }
+}
@@ -0,0 +1,85 @@
+# SPDX-License-Identifier: MPL-2.0
+"""Awaitable versions of the distances for use in asyncio event loops.
+
+The distances release the GIL while computing, so awaiting them runs the
+computation in a background thread of the event loop's default executor
+without blocking the event loop, for example, in a web service that scores
+user-submitted graphs.
+
+Example:
+
+```python
+import asyncio
+
+from gadjid import aio
+
+async def grade(Gtrue, Gguess):
+    return await aio.ancestor_aid(
+        Gtrue, Gguess, edge_direction="from row to column"
+    )
+```
+"""
+
+import asyncio
+import functools
+
+from . import gadjid as _gadjid
+
+
+__all__ = [
+    "aid_approx",
+    "aid_detailed",
+    "aid_per_treatment",
+    "aid_selected_effect_sets",
+    "aid_selected_pairs",
+    "aid_with_normalization",
+    "amenability_disagreement",
+    "amenability_matrix",
+    "ancestor_aid",
+    "conditional_aid",
+    "distance_with_provenance",
+    "identifiability_report",
+    "oset_aid",
+    "oset_aid_selected_treatment_sets",
+    "parent_aid",
+    "shd",
+    "sid",
+    "sid_selected_pairs",
+    "vstructure_distance",
+]
+
+
+def _awaitable(distance):
+    """Wrap a distance so that it runs in the default executor."""
+
+    @functools.wraps(distance)
+    async def wrapper(*args, **kwargs):
+        loop = asyncio.get_running_loop()
+        return await loop.run_in_executor(
+            None, functools.partial(distance, *args, **kwargs)
+        )
+
+    return wrapper
+
+
+aid_approx = _awaitable(_gadjid.aid_approx)
+aid_detailed = _awaitable(_gadjid.aid_detailed)
+aid_per_treatment = _awaitable(_gadjid.aid_per_treatment)
+aid_selected_effect_sets = _awaitable(_gadjid.aid_selected_effect_sets)
+aid_selected_pairs = _awaitable(_gadjid.aid_selected_pairs)
+aid_with_normalization = _awaitable(_gadjid.aid_with_normalization)
+amenability_disagreement = _awaitable(_gadjid.amenability_disagreement)
+amenability_matrix = _awaitable(_gadjid.amenability_matrix)
+ancestor_aid = _awaitable(_gadjid.ancestor_aid)
+conditional_aid = _awaitable(_gadjid.conditional_aid)
+distance_with_provenance = _awaitable(_gadjid.distance_with_provenance)
+identifiability_report = _awaitable(_gadjid.identifiability_report)
+oset_aid = _awaitable(_gadjid.oset_aid)
+oset_aid_selected_treatment_sets = _awaitable(
+    _gadjid.oset_aid_selected_treatment_sets
+)
+parent_aid = _awaitable(_gadjid.parent_aid)
+shd = _awaitable(_gadjid.shd)
+sid = _awaitable(_gadjid.sid)
+sid_selected_pairs = _awaitable(_gadjid.sid_selected_pairs)
+vstructure_distance = _awaitable(_gadjid.vstructure_distance)
@@ -0,0 +1,63 @@
+# SPDX-License-Identifier: MPL-2.0
+"""Scorers for hyperparameter tuning and benchmarking frameworks.
+
+`make_scorer` turns a distance into a callable `scorer(G_true, G_pred)` that
+returns a single float, as expected by the tuning loops of causal discovery
+libraries and by benchmarking frameworks. Like the scorers of scikit-learn,
+the scorers are to be maximized by default, so they return the negated distance.
+
+Example:
+
+```python
+from gadjid.sklearn import make_scorer
+
+scorer = make_scorer("oset_aid", edge_direction="from row to column")
+best = max(candidates, key=lambda G_pred: scorer(Gtrue, G_pred))
+```
+"""
+
+import functools
+
+from . import gadjid as _gadjid
+
+
+__all__ = ["make_scorer"]
+
+
+_DISTANCES = {
+    "ancestor_aid": _gadjid.ancestor_aid,
+    "oset_aid": _gadjid.oset_aid,
+    "parent_aid": _gadjid.parent_aid,
+    "shd": _gadjid.shd,
+    "sid": _gadjid.sid,
+}
+
+
+def make_scorer(metric, greater_is_better=True, normalized=True, **options):
+    """Make a scorer `scorer(G_true, G_pred) -> float` from a distance.
+
+    `metric` is one of "ancestor_aid", "oset_aid", "parent_aid", "shd", or
+    "sid", and the `options`, such as `edge_direction` and `known_edges`, are
+    passed on to the distance; `edge_direction` is dropped for the SHD, which
+    does not depend on it. The scorer returns the normalized distance, or the
+    number of mistakes if not `normalized`, negated if `greater_is_better`,
+    so that the best graph has the highest score.
+    """
+    if metric not in _DISTANCES:
+        valid = ", ".join(f'"{name}"' for name in _DISTANCES)
+        raise ValueError(
+            f'metric string argument must be one of {valid}, not "{metric}"'
+        )
+    distance = _DISTANCES[metric]
+    if metric == "shd":
+        options.pop("edge_direction", None)
+    sign = -1 if greater_is_better else 1
+    entry = 0 if normalized else 1
+
+    @functools.wraps(distance)
+    def scorer(G_true, G_pred):
+        return sign * float(distance(G_true, G_pred, **options)[entry])
+
+    scorer.__name__ = f"neg_{metric}" if greater_is_better else metric
+    scorer.greater_is_better = greater_is_better
+    return scorer
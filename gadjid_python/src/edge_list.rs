@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use gadjid::PDAG;
+use numpy::{Element, PyReadonlyArray2, PyUntypedArrayMethods};
+use pyo3::prelude::*;
+
+use crate::{raise_load_error, GadjidError, InvalidEdgeValueError, SizeMismatchError};
+
+/// A graph given by an integer ndarray of its edges instead of an adjacency matrix,
+/// which can be passed to all functions in place of an adjacency matrix.
+/// Each row `[from, to]` codes a directed edge `from → to`, and each row `[from, to, type]`
+/// codes a directed edge `from → to` for type `1` and an undirected edge `from – to` for type `2`,
+/// irrespective of `edge_direction`. The graph has `n_nodes` nodes,
+/// or one more than the largest node in `edges` if `n_nodes` is not given.
+#[pyclass(module = "gadjid", frozen)]
+pub struct EdgeList {
+    n_nodes: usize,
+    edges: Vec<(usize, usize, i8)>,
+}
+
+#[pymethods]
+impl EdgeList {
+    #[new]
+    #[pyo3(signature = (edges, n_nodes=None))]
+    fn new(edges: &Bound<'_, PyAny>, n_nodes: Option<usize>) -> PyResult<Self> {
+        let edges = match edges.extract::<PyReadonlyArray2<i64>>() {
+            Ok(edges) => edges_from(edges)?,
+            Err(_) => edges_from(edges.extract::<PyReadonlyArray2<i32>>()?)?,
+        };
+        let n_nodes = match n_nodes {
+            Some(n_nodes) => n_nodes,
+            None => match edges.iter().map(|(from, to, _)| from.max(to) + 1).max() {
+                Some(n_nodes) => n_nodes,
+                None => {
+                    return Err(GadjidError::new_err(
+                        "n_nodes must be given for an edge list without edges",
+                    ))
+                }
+            },
+        };
+        let edge_list = EdgeList { n_nodes, edges };
+        // invalid edge lists raise here rather than when passed to a distance
+        edge_list.to_pdag()?;
+        Ok(edge_list)
+    }
+
+    /// The number of nodes of the graph
+    #[getter]
+    fn n_nodes(&self) -> usize {
+        self.n_nodes
+    }
+
+    fn __len__(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EdgeList(n_nodes={}, {} edges)",
+            self.n_nodes,
+            self.edges.len()
+        )
+    }
+}
+
+impl EdgeList {
+    /// Load the edges into a PDAG, which is validated like an adjacency matrix.
+    pub fn to_pdag(&self) -> PyResult<PDAG> {
+        PDAG::try_from_edges(self.n_nodes, self.edges.iter().copied()).map_err(raise_load_error)
+    }
+}
+
+/// Read the `(from, to, type)` entries of an (m, 2) or (m, 3) integer ndarray.
+fn edges_from<T: Element + Copy + Into<i64>>(
+    ndarray: PyReadonlyArray2<T>,
+) -> PyResult<Vec<(usize, usize, i8)>> {
+    let n_columns = ndarray.shape()[1];
+    if n_columns != 2 && n_columns != 3 {
+        return Err(SizeMismatchError::new_err(format!(
+            "Edge list must have 2 or 3 columns, but has {n_columns}"
+        )));
+    }
+    ndarray
+        .as_array()
+        .outer_iter()
+        .map(|row| {
+            let (from, to): (i64, i64) = (row[0].into(), row[1].into());
+            let edge_type: i64 = if n_columns == 3 { row[2].into() } else { 1 };
+            let (Ok(from), Ok(to)) = (usize::try_from(from), usize::try_from(to)) else {
+                return Err(SizeMismatchError::new_err(format!(
+                    "Found edge ({from}, {to}), but nodes must be non-negative"
+                )));
+            };
+            let edge_type = i8::try_from(edge_type).map_err(|_| {
+                InvalidEdgeValueError::new_err(format!(
+                    "Found edge type '{edge_type}' for edge ({from}, {to}), expected only 0's, 1's or 2's"
+                ))
+            })?;
+            Ok((from, to, edge_type))
+        })
+        .collect()
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use gadjid::{graph_operations::ValidatedPair, PDAG};
+use pyo3::prelude::*;
+
+use crate::{edge_direction_is_row_to_col, graph_from_pyobject, SizeMismatchError};
+
+/// A DAG or CPDAG loaded and validated once from an adjacency matrix (sparse or dense) or an `EdgeList`,
+/// which stays resident, so that its distance methods to other `Graph`s skip loading and checking
+/// the graphs and search from each treatment on the calling thread,
+/// to minimize the overhead of scoring many small graphs in a tight loop.
+/// The methods are called on the true graph with the guess graph as argument.
+#[pyclass(module = "gadjid", frozen)]
+pub struct Graph {
+    graph: PDAG,
+}
+
+#[pymethods]
+impl Graph {
+    #[new]
+    fn new(adjacency: &Bound<'_, PyAny>, edge_direction: &str) -> PyResult<Self> {
+        let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+        let graph = graph_from_pyobject(adjacency, row_to_col)?;
+        Ok(Graph { graph })
+    }
+
+    /// The number of nodes of the graph
+    #[getter]
+    fn n_nodes(&self) -> usize {
+        self.graph.n_nodes
+    }
+
+    /// Ancestor Adjustment Identification Distance to the `guess` graph, with this graph as the truth.
+    /// Returns a tuple of (normalized error (in [0,1]), total number of errors)
+    fn ancestor_aid(&self, guess: &Bound<'_, Graph>) -> PyResult<(f64, usize)> {
+        let pair = self.pair_with(guess.get())?;
+        Ok(guess.py().allow_threads(|| pair.ancestor_aid()))
+    }
+
+    /// Oset Adjustment Identification Distance to the `guess` graph, with this graph as the truth.
+    /// Returns a tuple of (normalized error (in [0,1]), total number of errors)
+    fn oset_aid(&self, guess: &Bound<'_, Graph>) -> PyResult<(f64, usize)> {
+        let pair = self.pair_with(guess.get())?;
+        Ok(guess.py().allow_threads(|| pair.oset_aid()))
+    }
+
+    /// Parent Adjustment Identification Distance to the `guess` graph, with this graph as the truth.
+    /// Returns a tuple of (normalized error (in [0,1]), total number of errors)
+    fn parent_aid(&self, guess: &Bound<'_, Graph>) -> PyResult<(f64, usize)> {
+        let pair = self.pair_with(guess.get())?;
+        Ok(guess.py().allow_threads(|| pair.parent_aid()))
+    }
+
+    /// Structural Hamming Distance to the `guess` graph.
+    /// Returns a tuple of (normalized error (in [0,1]), total number of errors)
+    fn shd(&self, guess: &Bound<'_, Graph>) -> PyResult<(f64, usize)> {
+        let pair = self.pair_with(guess.get())?;
+        Ok(guess.py().allow_threads(|| pair.shd()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Graph(n_nodes={}, {} directed edges, {} undirected edges)",
+            self.graph.n_nodes, self.graph.n_directed_edges, self.graph.n_undirected_edges
+        )
+    }
+}
+
+impl Graph {
+    /// Validate the pair of this graph as the truth and the `guess`, which must have the same size.
+    fn pair_with<'a>(&'a self, guess: &'a Graph) -> PyResult<ValidatedPair<'a>> {
+        ValidatedPair::new(&self.graph, &guess.graph)
+            .map_err(|err| SizeMismatchError::new_err(err.to_string()))
+    }
+}
@@ -0,0 +1,137 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use std::sync::Arc;
+
+use gadjid::{load_many, LoadManyError, PDAG};
+use numpy::{PyReadonlyArray2, PyReadonlyArray3, PyUntypedArrayMethods};
+use pyo3::prelude::*;
+
+use crate::{graph_from_pyobject, load_error_to_pyerr, GadjidError, SizeMismatchError};
+
+/// A collection of graphs loaded once by `load_many`, which can be passed to the ensemble functions
+/// in place of a list of adjacency matrices, so that repeated calls do not load the graphs again.
+#[pyclass(module = "gadjid", frozen)]
+pub struct GraphCollection {
+    graphs: Arc<[PDAG]>,
+}
+
+#[pymethods]
+impl GraphCollection {
+    /// The number of nodes of each graph
+    #[getter]
+    fn n_nodes(&self) -> usize {
+        self.graphs[0].n_nodes
+    }
+
+    fn __len__(&self) -> usize {
+        self.graphs.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GraphCollection({} graphs, n_nodes={})",
+            self.graphs.len(),
+            self.n_nodes()
+        )
+    }
+}
+
+impl GraphCollection {
+    /// Load a (k, n, n) int8 ndarray or a list of adjacency matrices (sparse or dense) or edge lists,
+    /// which must all have the same number of nodes.
+    pub fn load(graphs: &Bound<'_, PyAny>, is_row_to_col: bool) -> PyResult<Self> {
+        let graphs = ensemble_from_pyobject(graphs, is_row_to_col)?;
+        let Some(first) = graphs.first() else {
+            return Err(GadjidError::new_err("Cannot load an empty list of graphs"));
+        };
+        if let Some(index) = graphs.iter().position(|g| g.n_nodes != first.n_nodes) {
+            return Err(SizeMismatchError::new_err(format!(
+                "Graph {index} has {} nodes, but the first graph has {} nodes",
+                graphs[index].n_nodes, first.n_nodes
+            )));
+        }
+        Ok(GraphCollection { graphs })
+    }
+}
+
+/// Load an ensemble of graphs from a `GraphCollection`, a (k, n, n) int8 ndarray, or a list of
+/// adjacency matrices (sparse or dense) or edge lists; their sizes are checked by the ensemble functions.
+/// Dense matrices of the same size in a list are copied into one memory pool and loaded in parallel.
+pub fn ensemble_from_pyobject(
+    graphs: &Bound<'_, PyAny>,
+    is_row_to_col: bool,
+) -> PyResult<Arc<[PDAG]>> {
+    if let Ok(collection) = graphs.downcast::<GraphCollection>() {
+        return Ok(collection.get().graphs.clone());
+    }
+    // a (k, n, n) array is a pool of k adjacency matrices already
+    if let Ok(stacked) = graphs.extract::<PyReadonlyArray3<'_, i8>>() {
+        return stacked_from_ndarray(graphs.py(), stacked, is_row_to_col);
+    }
+    let graphs = graphs.extract::<Vec<Bound<'_, PyAny>>>()?;
+    let dense: Option<Vec<PyReadonlyArray2<'_, i8>>> =
+        graphs.iter().map(|graph| graph.extract().ok()).collect();
+    if let Some(dense) = dense.filter(|dense| !dense.is_empty()) {
+        let n_nodes = dense[0].shape()[0];
+        if dense
+            .iter()
+            .all(|matrix| matrix.shape() == [n_nodes, n_nodes])
+        {
+            let mut pool = Vec::with_capacity(dense.len() * n_nodes * n_nodes);
+            for matrix in &dense {
+                // in the logical, row-major order irrespective of the memory layout
+                pool.extend(matrix.as_array().iter().copied());
+            }
+            return load_pool(graphs[0].py(), &pool, dense.len(), n_nodes, is_row_to_col);
+        }
+    }
+    graphs
+        .iter()
+        .map(|graph| graph_from_pyobject(graph, is_row_to_col))
+        .collect()
+}
+
+/// Load the graphs of a (k, n, n) int8 ndarray, without copying it if it is C-contiguous.
+fn stacked_from_ndarray(
+    py: Python<'_>,
+    stacked: PyReadonlyArray3<'_, i8>,
+    is_row_to_col: bool,
+) -> PyResult<Arc<[PDAG]>> {
+    let &[n_graphs, n_rows, n_columns] = stacked.shape() else {
+        unreachable!("a three-dimensional array has three dimensions")
+    };
+    if n_rows != n_columns {
+        return Err(SizeMismatchError::new_err("Matrices must be square"));
+    }
+    match stacked.is_c_contiguous() {
+        true => load_pool(py, stacked.as_slice()?, n_graphs, n_rows, is_row_to_col),
+        false => {
+            let pool = Vec::from_iter(stacked.as_array().iter().copied());
+            load_pool(py, &pool, n_graphs, n_rows, is_row_to_col)
+        }
+    }
+}
+
+/// Load the graphs of a memory pool of row-major adjacency matrices,
+/// which must have at least 2 nodes, like graphs loaded by `graph_from_pyobject`.
+fn load_pool(
+    py: Python<'_>,
+    pool: &[i8],
+    n_graphs: usize,
+    n_nodes: usize,
+    is_row_to_col: bool,
+) -> PyResult<Arc<[PDAG]>> {
+    if n_graphs > 0 && n_nodes < 2 {
+        return Err(SizeMismatchError::new_err(format!(
+            "Graph must contain at least 2 nodes, but has {n_nodes}"
+        )));
+    }
+    match py.allow_threads(|| load_many(pool, n_graphs, n_nodes, is_row_to_col)) {
+        Ok(graphs) => Ok(graphs.into()),
+        Err(LoadManyError::Load { index, error }) => {
+            let message = format!("Graph {index}: {error}");
+            Err(load_error_to_pyerr(error, message))
+        }
+        Err(err) => Err(SizeMismatchError::new_err(err.to_string())),
+    }
+}
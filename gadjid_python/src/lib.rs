@@ -2,21 +2,155 @@
 #![warn(missing_docs)]
 //! Python-wrappers for the rust gadjid (Graph Adjustment Identification Distance) library.
 
+mod edge_list;
+mod graph;
+mod graph_collection;
+mod mec;
 mod numpy_ndarray_handler;
+mod provenance;
 mod scipy_sparse_handler;
 
-use anyhow::bail;
+use std::collections::HashMap;
+
+use numpy::PyArray1;
+use numpy::PyArray2;
+use numpy::PyReadonlyArray1;
+use numpy::PyReadonlyArray2;
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::exceptions::PyUserWarning;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
+use ::gadjid::analysis::parent_set_report as rust_parent_set_report;
+use ::gadjid::analysis::validate_pair as rust_validate_pair;
+use ::gadjid::benchmarks::metrics_table_csv as rust_metrics_table_csv;
+use ::gadjid::benchmarks::read_benchpress_csv as rust_read_benchpress_csv;
+use ::gadjid::benchmarks::read_score_matrix_csv as rust_read_score_matrix_csv;
+use ::gadjid::benchmarks::BenchmarkError;
+use ::gadjid::bundle::export_bundle as rust_export_bundle;
+use ::gadjid::bundle::verify_bundle as rust_verify_bundle;
+use ::gadjid::bundle::BundleError;
+use ::gadjid::corpus;
+use ::gadjid::graph_operations::adjustment_set_candidates as rust_adjustment_set_candidates;
+use ::gadjid::graph_operations::amenability_disagreement as rust_amenability_disagreement;
+use ::gadjid::graph_operations::amenability_matrix as rust_amenability_matrix;
 use ::gadjid::graph_operations::ancestor_aid as rust_ancestor_aid;
+use ::gadjid::graph_operations::ancestor_aid_with as rust_ancestor_aid_with;
+use ::gadjid::graph_operations::ci_statement_distance as rust_ci_statement_distance;
+use ::gadjid::graph_operations::cluster_graphs as rust_cluster_graphs;
+use ::gadjid::graph_operations::compare_adjustment_sets as rust_compare_adjustment_sets;
+use ::gadjid::graph_operations::conditional_aid as rust_conditional_aid;
+use ::gadjid::graph_operations::confusion as rust_confusion;
+use ::gadjid::graph_operations::consensus_graph as rust_consensus_graph;
+use ::gadjid::graph_operations::cpdag_of as rust_cpdag_of;
+use ::gadjid::graph_operations::cross_consistency as rust_cross_consistency;
+use ::gadjid::graph_operations::distance_by_components as rust_distance_by_components;
+use ::gadjid::graph_operations::edge_frequencies as rust_edge_frequencies;
+use ::gadjid::graph_operations::edge_influence as rust_edge_influence;
+use ::gadjid::graph_operations::expected_random_shd as rust_expected_random_shd;
+use ::gadjid::graph_operations::graph_edit_distance as rust_graph_edit_distance;
+use ::gadjid::graph_operations::identifiability_report as rust_identifiability_report;
+use ::gadjid::graph_operations::identification_stability as rust_identification_stability;
+use ::gadjid::graph_operations::implied_independencies as rust_implied_independencies;
+use ::gadjid::graph_operations::min_shd_over_mec as rust_min_shd_over_mec;
+use ::gadjid::graph_operations::node_dropout_curve as rust_node_dropout_curve;
+use ::gadjid::graph_operations::not_validly_adjusted_for as rust_not_validly_adjusted_for;
+use ::gadjid::graph_operations::null_distribution as rust_null_distribution;
 use ::gadjid::graph_operations::oset_aid as rust_oset_aid;
+use ::gadjid::graph_operations::oset_aid_selected_treatment_sets as rust_oset_aid_selected_treatment_sets;
+use ::gadjid::graph_operations::oset_aid_with as rust_oset_aid_with;
+use ::gadjid::graph_operations::pairs_from_masks as rust_pairs_from_masks;
 use ::gadjid::graph_operations::parent_aid as rust_parent_aid;
+use ::gadjid::graph_operations::parent_aid_bounds as rust_parent_aid_bounds;
+use ::gadjid::graph_operations::parent_aid_with as rust_parent_aid_with;
+use ::gadjid::graph_operations::repair_path as rust_repair_path;
 use ::gadjid::graph_operations::shd as rust_shd;
-use ::gadjid::graph_operations::sid as rust_sid;
+use ::gadjid::graph_operations::shd_barycenter as rust_shd_barycenter;
+use ::gadjid::graph_operations::shd_detailed as rust_shd_detailed;
+use ::gadjid::graph_operations::shd_markov_equivalent as rust_shd_markov_equivalent;
+use ::gadjid::graph_operations::shd_medoid as rust_shd_medoid;
+use ::gadjid::graph_operations::shd_min_over_permutations as rust_shd_min_over_permutations;
+use ::gadjid::graph_operations::shd_with_known_edges as rust_shd_with_known_edges;
+use ::gadjid::graph_operations::sid_over_mec as rust_sid_over_mec;
+use ::gadjid::graph_operations::sid_selected_pairs as rust_sid_selected_pairs;
+use ::gadjid::graph_operations::sid_with_undirected as rust_sid_with_undirected;
+use ::gadjid::graph_operations::specification_violations as rust_specification_violations;
+use ::gadjid::graph_operations::tiered_distance as rust_tiered_distance;
+use ::gadjid::graph_operations::to_cytoscape_json as rust_to_cytoscape_json;
+use ::gadjid::graph_operations::trajectory_aid as rust_trajectory_aid;
+use ::gadjid::graph_operations::vstructure_distance as rust_vstructure_distance;
+use ::gadjid::graph_operations::weighted_shd as rust_weighted_shd;
+use ::gadjid::graph_operations::AidDistance;
+use ::gadjid::graph_operations::AidOutput;
+use ::gadjid::graph_operations::Approximate;
+use ::gadjid::graph_operations::ConfusionCounts;
+use ::gadjid::graph_operations::Detailed;
+use ::gadjid::graph_operations::Distance;
+use ::gadjid::graph_operations::DistanceBreakdown;
+use ::gadjid::graph_operations::EdgeOperation;
+use ::gadjid::graph_operations::EdgeSpecification;
+use ::gadjid::graph_operations::EnsembleError;
+use ::gadjid::graph_operations::KnownEdges;
+use ::gadjid::graph_operations::Layer;
+use ::gadjid::graph_operations::Linkage;
+use ::gadjid::graph_operations::Normalization;
+use ::gadjid::graph_operations::PairWeights;
+use ::gadjid::graph_operations::PairsNormalization;
+use ::gadjid::graph_operations::PartialCredit;
+use ::gadjid::graph_operations::PerTreatment;
+use ::gadjid::graph_operations::RandomGraphs;
+use ::gadjid::graph_operations::RunStats;
+use ::gadjid::graph_operations::SIDError;
+use ::gadjid::graph_operations::SelectedEffectSets;
+use ::gadjid::graph_operations::SelectedPairs;
+use ::gadjid::graph_operations::SelectedPairsDistance;
+use ::gadjid::graph_operations::TieredTruth;
+use ::gadjid::graph_operations::TrajectoryError;
+use ::gadjid::graph_operations::TreatmentSampling;
+use ::gadjid::graph_operations::TruthSets;
+use ::gadjid::graph_operations::UndirectedEdges;
+use ::gadjid::graph_operations::Violation;
+use ::gadjid::graph_operations::ViolationWeights;
+use ::gadjid::graph_operations::WithBreakdown;
+use ::gadjid::graph_operations::WithStats;
+use ::gadjid::graph_operations::WithTruthSets;
+use ::gadjid::load_with_convention as rust_load_with_convention;
+use ::gadjid::load_with_detected_convention as rust_load_with_detected_convention;
+use ::gadjid::options::EdgeDirection;
+use ::gadjid::options::NamedOption;
+use ::gadjid::options::OptionError;
+use ::gadjid::options::ParentModelKind;
+use ::gadjid::options::RandomGraphKind;
+use ::gadjid::options::TieBreakKind;
+use ::gadjid::provenance::DistanceResult as RustDistanceResult;
+use ::gadjid::resolve_missing as rust_resolve_missing;
+use ::gadjid::sampling::sample_configuration as rust_sample_configuration;
+use ::gadjid::sampling::SetSize;
+use ::gadjid::scm::Scm;
+use ::gadjid::tables::dropout_curve_table;
+use ::gadjid::tables::trajectory_table;
+use ::gadjid::tables::Column;
+use ::gadjid::tables::LongTable;
+use ::gadjid::Convention;
+use ::gadjid::ConventionError;
 use ::gadjid::EdgelistIterator;
+use ::gadjid::LoadError;
+use ::gadjid::MissingError;
+use ::gadjid::MissingPolicy;
 use ::gadjid::PDAG;
 
+use edge_list::EdgeList;
+use graph::Graph;
+use graph_collection::ensemble_from_pyobject;
+use graph_collection::GraphCollection;
+use mec::MecShd;
+use mec::MecSid;
 use numpy_ndarray_handler::try_from as try_from_dense;
+use numpy_ndarray_handler::vecvec_from as vecvec_from_dense;
+use provenance::DistanceResult;
 use scipy_sparse_handler::try_from as try_from_sparse;
 
 /**
@@ -25,8 +159,13 @@ Adjustment Identification Distance: A 𝚐𝚊𝚍𝚓𝚒𝚍 for Causal Struct
 For details, see the arXiv preprint at https://doi.org/10.48550/arXiv.2402.08616
 The source code is available at https://github.com/CausalDisco/gadjid
 
+The distances release the GIL while computing, so they can run in other threads;
+the `gadjid.aio` submodule offers awaitable versions for use in asyncio event loops.
+
 Adjacency matrices are accepted as either int8 numpy ndarrays
 or int8 scipy sparse matrices in CSR or CSC format.
+Large sparse graphs can instead be passed as `gadjid.EdgeList(edges, n_nodes=None)`
+of an (m, 2) or (m, 3) integer ndarray with one edge `[from, to]` or `[from, to, type]` per row.
 If `edge_direction="from row to column"`, then
 a `1` in row `r` and column `c` codes a directed edge `r → c`;
 if `edge_direction="from column to row"`, then
@@ -39,6 +178,14 @@ one of the two entries is sufficient to code an undirected edge).
 An adjacency matrix for a DAG may only contain 0s and 1s.
 An adjacency matrix for a CPDAG may only contain 0s, 1s and 2s.
 DAG and CPDAG inputs are validated for acyclicity.
+Invalid inputs raise a subclass of `gadjid.GadjidError`:
+`NotAcyclicError` for cyclic graphs,
+`NotSimpleGraphError` for self-loops or several edges between two nodes,
+`InvalidEdgeValueError` for entries other than 0, 1, and 2, and
+`SizeMismatchError` for non-square matrices, graphs of different sizes,
+or graphs with fewer than 2 or more than 2^32 nodes (2^16 on 32-bit platforms), and
+`InvalidPairsError` for empty, out-of-bounds, or (T, T) selected pairs
+(or empty sets of treatments).
 However, for CPDAG inputs, __the user needs to ensure the adjacency
 matrix indeed codes a valid CPDAG (instead of just a PDAG)__.
 
@@ -73,144 +220,2602 @@ print(shd(Gtrue, Gguess))
 ```
 */
 #[pymodule]
-fn gadjid(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+fn gadjid(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<EdgeList>()?;
+    m.add_class::<DistanceResult>()?;
+    m.add_class::<Graph>()?;
+    m.add_class::<GraphCollection>()?;
+    m.add_class::<MecShd>()?;
+    m.add_class::<MecSid>()?;
+    m.add("GadjidError", py.get_type_bound::<GadjidError>())?;
+    m.add("NotAcyclicError", py.get_type_bound::<NotAcyclicError>())?;
+    m.add(
+        "NotSimpleGraphError",
+        py.get_type_bound::<NotSimpleGraphError>(),
+    )?;
+    m.add(
+        "InvalidEdgeValueError",
+        py.get_type_bound::<InvalidEdgeValueError>(),
+    )?;
+    m.add(
+        "SizeMismatchError",
+        py.get_type_bound::<SizeMismatchError>(),
+    )?;
+    m.add(
+        "InvalidPairsError",
+        py.get_type_bound::<InvalidPairsError>(),
+    )?;
+    m.add("GraphPairWarning", py.get_type_bound::<GraphPairWarning>())?;
     m.add_function(wrap_pyfunction!(crate::ancestor_aid, m)?)?;
     m.add_function(wrap_pyfunction!(crate::oset_aid, m)?)?;
     m.add_function(wrap_pyfunction!(crate::parent_aid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_approx, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_partial_credit, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_with_stats, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_with_breakdown, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_with_truth_sets, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_with_pair_weights, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_detailed, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_per_treatment, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_with_normalization, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_selected_pairs, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::aid_selected_effect_sets, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::parent_aid_bounds, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::tiered_distance, m)?)?;
     m.add_function(wrap_pyfunction!(crate::shd, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::shd_detailed, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::vstructure_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::specification_violations, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::confusion, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::shd_markov_equivalent, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::cpdag_of, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::topological_order, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::min_shd_over_mec, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::sid_over_mec, m)?)?;
     m.add_function(wrap_pyfunction!(crate::sid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::weighted_shd, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::shd_min_over_permutations, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::graph_edit_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::sid_selected_pairs, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        crate::oset_aid_selected_treatment_sets,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(crate::conditional_aid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::amenability_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::amenability_disagreement, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::ci_statement_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::implied_independencies, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::markov_property_test, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::identifiability_report, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::parent_set_report, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::validate_pair, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::compare_adjustment_sets, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::adjustment_set_candidates, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::not_validly_adjusted_for, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::to_cytoscape_json, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::load_many, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::corpus_graphs, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::load_corpus_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::consensus_graph, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::edge_frequencies, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::identification_stability, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::cross_consistency, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::cluster_graphs, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::shd_medoid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::shd_barycenter, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::null_distribution, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::expected_random_shd, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::node_dropout_curve, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::sample_configuration, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::sample_scm_dag, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::distance_by_components, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::edge_influence, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::trajectory_aid, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::repair_path, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::detect_convention, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::load_with_convention, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::sanitize_adjacency, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::resolve_missing, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::export_bundle, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::verify_bundle, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::distance_with_provenance, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::read_benchpress_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::read_score_matrix_csv, m)?)?;
+    m.add_function(wrap_pyfunction!(crate::metrics_table_csv, m)?)?;
     Ok(())
 }
 
-const ROW_TO_COL: &str = "from row to column";
-const COL_TO_ROW: &str = "from column to row";
+create_exception!(
+    gadjid,
+    GadjidError,
+    PyException,
+    "Base class of the errors raised for inputs gadjid cannot compute a distance for."
+);
+create_exception!(
+    gadjid,
+    NotAcyclicError,
+    GadjidError,
+    "The adjacency matrix codes a graph with a directed cycle."
+);
+create_exception!(
+    gadjid,
+    NotSimpleGraphError,
+    GadjidError,
+    "The adjacency matrix codes a self-loop or more than one edge between two nodes."
+);
+create_exception!(
+    gadjid,
+    InvalidEdgeValueError,
+    GadjidError,
+    "The adjacency matrix contains an entry other than 0, 1, or 2."
+);
+create_exception!(
+    gadjid,
+    SizeMismatchError,
+    GadjidError,
+    "An adjacency matrix is not square, too small, or too large, or the two graphs differ in their number of nodes."
+);
+create_exception!(
+    gadjid,
+    InvalidPairsError,
+    GadjidError,
+    "No pair is selected, or a selected pair is out of bounds, has no treatment, or has a treatment as effect."
+);
+create_exception!(
+    gadjid,
+    GraphPairWarning,
+    PyUserWarning,
+    "Warning issued by `validate_pair` for a pair of graphs whose distance is likely misleading."
+);
 
 fn edge_direction_is_row_to_col(edge_direction: &str) -> PyResult<bool> {
-    match edge_direction {
-        ROW_TO_COL => Ok(true),
-        COL_TO_ROW => Ok(false),
-        _ => Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(format!(
-            r#"edge_direction string argument must be either "{}" or "{}""#,
-            ROW_TO_COL, COL_TO_ROW
-        ))),
+    match EdgeDirection::parse(edge_direction) {
+        Ok(edge_direction) => Ok(edge_direction.is_row_to_col()),
+        Err(err) => Err(PyErr::new::<pyo3::exceptions::PyTypeError, _>(
+            err.to_string(),
+        )),
     }
 }
 
-/// Ancestor Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// Raises a ValueError listing the valid values of a string option.
+fn raise_option_error(err: OptionError) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string())
+}
+
+/// Collects the optional known edges of graphs with `n_nodes` nodes,
+/// raising an InvalidPairsError for pairs out of bounds or of the same node twice.
+fn known_edges_from(
+    n_nodes: usize,
+    known_edges: Option<Vec<(usize, usize)>>,
+) -> PyResult<Option<KnownEdges>> {
+    known_edges
+        .map(|pairs| KnownEdges::new(n_nodes, &pairs))
+        .transpose()
+        .map_err(|err| InvalidPairsError::new_err(err.to_string()))
+}
+
+/// Parses the `distance` among the adjustment identification distances,
+/// "ancestor_aid", "oset_aid", or "parent_aid", raising a ValueError for any other name.
+fn aid_from(distance: &str) -> PyResult<AidDistance> {
+    AidDistance::parse(distance).map_err(raise_option_error)
+}
+
+/// Computes the adjustment identification distance `aid` between the graphs as the given `output`,
+/// releasing the GIL while computing.
+fn aid_with<O>(py: Python<'_>, aid: AidDistance, truth: &PDAG, guess: &PDAG, output: O) -> O::Output
+where
+    O: AidOutput + Send,
+    O::Output: Send,
+{
+    py.allow_threads(|| aid.compute_with(truth, guess, output))
+}
+
+/// Ancestor Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// excluding the `(treatment, effect)` pairs whose edge is among the optional `known_edges` (a list of node pairs)
 #[pyfunction]
+#[pyo3(signature = (g_true, g_guess, edge_direction, known_edges=None))]
 pub fn ancestor_aid<'py>(
     g_true: &Bound<'py, PyAny>,
     g_guess: &Bound<'py, PyAny>,
     edge_direction: &str,
+    known_edges: Option<Vec<(usize, usize)>>,
 ) -> PyResult<(f64, usize)> {
     let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
-    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
-    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
-    let (normalized_distance, n_errors) = rust_ancestor_aid(&graph_truth, &graph_guess);
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let known = known_edges_from(graph_truth.n_nodes, known_edges)?;
+    let (normalized_distance, n_errors) = g_true.py().allow_threads(|| match &known {
+        Some(known) => rust_ancestor_aid_with(&graph_truth, &graph_guess, known),
+        None => rust_ancestor_aid(&graph_truth, &graph_guess),
+    });
     Ok((normalized_distance, n_errors))
 }
 
-/// Optimal Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// Optimal Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// excluding the `(treatment, effect)` pairs whose edge is among the optional `known_edges` (a list of node pairs)
 #[pyfunction]
+#[pyo3(signature = (g_true, g_guess, edge_direction, known_edges=None))]
 pub fn oset_aid<'py>(
     g_true: &Bound<'py, PyAny>,
     g_guess: &Bound<'py, PyAny>,
     edge_direction: &str,
+    known_edges: Option<Vec<(usize, usize)>>,
 ) -> PyResult<(f64, usize)> {
     let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
-    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
-    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
-    let (normalized_distance, n_errors) = rust_oset_aid(&graph_truth, &graph_guess);
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let known = known_edges_from(graph_truth.n_nodes, known_edges)?;
+    let (normalized_distance, n_errors) = g_true.py().allow_threads(|| match &known {
+        Some(known) => rust_oset_aid_with(&graph_truth, &graph_guess, known),
+        None => rust_oset_aid(&graph_truth, &graph_guess),
+    });
     Ok((normalized_distance, n_errors))
 }
 
-/// Parent Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// Parent Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// excluding the `(treatment, effect)` pairs whose edge is among the optional `known_edges` (a list of node pairs)
 #[pyfunction]
+#[pyo3(signature = (g_true, g_guess, edge_direction, known_edges=None))]
 pub fn parent_aid<'py>(
     g_true: &Bound<'py, PyAny>,
     g_guess: &Bound<'py, PyAny>,
     edge_direction: &str,
+    known_edges: Option<Vec<(usize, usize)>>,
 ) -> PyResult<(f64, usize)> {
     let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
-    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
-    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
-    let (normalized_distance, n_errors) = rust_parent_aid(&graph_truth, &graph_guess);
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let known = known_edges_from(graph_truth.n_nodes, known_edges)?;
+    let (normalized_distance, n_errors) = g_true.py().allow_threads(|| match &known {
+        Some(known) => rust_parent_aid_with(&graph_truth, &graph_guess, known),
+        None => rust_parent_aid(&graph_truth, &graph_guess),
+    });
     Ok((normalized_distance, n_errors))
 }
 
-/// Structural Hamming Distance between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// Raises a ValueError unless the `partial_credit` is between 0 and 1.
+fn check_partial_credit(partial_credit: f64) -> PyResult<()> {
+    if !(0.0..=1.0).contains(&partial_credit) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "partial_credit must be between 0 and 1",
+        ));
+    }
+    Ok(())
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// where each pair that `g_guess` wrongly claims not to be amenable, such as due to an undirected edge,
+/// only counts as `1 - partial_credit` mistakes for a `partial_credit` between 0 and 1.
+/// Returns a tuple `(normalized_distance, fractional_mistake_count)`
+#[pyfunction]
+pub fn aid_partial_credit<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+    partial_credit: f64,
+) -> PyResult<(f64, f64)> {
+    let aid = aid_from(distance)?;
+    check_partial_credit(partial_credit)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    Ok(aid_with(
+        g_true.py(),
+        aid,
+        &graph_truth,
+        &graph_guess,
+        PartialCredit(partial_credit),
+    ))
+}
+
+/// The wall time in seconds, the time in seconds spent on each searched treatment,
+/// and the number of visited (edge, node, walk status) triplets of a call.
+type Stats = (f64, Vec<(usize, f64)>, usize);
+
+fn stats_from(stats: RunStats) -> Stats {
+    (
+        stats.wall_time.as_secs_f64(),
+        Vec::from_iter(
+            stats
+                .treatment_times
+                .into_iter()
+                .map(|(treatment, time)| (treatment, time.as_secs_f64())),
+        ),
+        stats.visited_triplets,
+    )
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// alongside run statistics to understand how the distance scales on the graphs.
+/// Returns a tuple `((normalized_distance, mistake_count), (wall_time, treatment_times, visited_triplets))`,
+/// where the times are in seconds and `treatment_times` lists `(treatment, time)` for each treatment searched from
+#[pyfunction]
+pub fn aid_with_stats<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<((f64, usize), Stats)> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let (distance, stats) = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, WithStats);
+    Ok((distance, stats_from(stats)))
+}
+
+type Breakdown = ((f64, usize), (usize, usize, usize));
+
+fn breakdown_from(breakdown: DistanceBreakdown) -> Breakdown {
+    (
+        (breakdown.normalized(), breakdown.mistakes()),
+        (
+            breakdown.wrong_order,
+            breakdown.amenability,
+            breakdown.invalid_adjustment,
+        ),
+    )
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense), with its mistakes split by their cause.
+/// Returns a tuple `((normalized_distance, mistake_count), (wrong_order, amenability, invalid_adjustment))`,
+/// which count the mistakes due to a wrong causal order, to a disagreement on amenability,
+/// and to an adjustment set that is not valid in `g_true`, and sum to the mistake count
+#[pyfunction]
+pub fn aid_with_breakdown<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<Breakdown> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let breakdown = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, WithBreakdown);
+    Ok(breakdown_from(breakdown))
+}
+
+type TruthSetsTuple = ((f64, usize), Vec<(Vec<usize>, Vec<usize>)>);
+
+fn truth_sets_from(distance: (f64, usize), sets: Vec<TruthSets>) -> TruthSetsTuple {
+    (
+        distance,
+        Vec::from_iter(sets.into_iter().map(|sets| (sets.nam, sets.nva))),
+    )
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// with the sets of the true graph that it computes for each treatment.
+/// Returns a tuple `((normalized_distance, mistake_count), sets)`, where `sets[t]` is a tuple `(nam, nva)`
+/// of the sorted lists of the nodes not amenable relative to treatment `t` in `g_true`, and of the nodes
+/// for which the adjustment set of `g_guess` is not valid in `g_true`
+#[pyfunction]
+pub fn aid_with_truth_sets<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<TruthSetsTuple> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let (distance, sets) = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, WithTruthSets);
+    Ok(truth_sets_from(distance, sets))
+}
+
+/// Collects the n×n matrix of the non-negative integer `weights` of the `(treatment, effect)` pairs,
+/// raising a ValueError if it has another shape, a negative entry, or only zeros off the diagonal.
+fn pair_weights_from(
+    weights: PyReadonlyArray2<'_, i64>,
+    n_nodes: usize,
+) -> PyResult<Vec<Vec<usize>>> {
+    let weights = weights.as_array();
+    if weights.shape() != [n_nodes, n_nodes] {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "weights must be a {n_nodes}×{n_nodes} matrix"
+        )));
+    }
+    if weights.iter().any(|weight| *weight < 0) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "weights must not be negative",
+        ));
+    }
+    let off_diagonal = weights.indexed_iter().filter(|((t, y), _)| t != y);
+    if off_diagonal.map(|(_, weight)| *weight).sum::<i64>() == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "the pairs must not all weigh zero",
+        ));
+    }
+    Ok(Vec::from_iter(weights.outer_iter().map(|row| {
+        Vec::from_iter(row.iter().map(|weight| *weight as usize))
+    })))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// where each mistake counts as the entry `[treatment, effect]` of the n×n int64 `weights` matrix.
+/// Returns a tuple `(normalized_distance, weighted_mistakes)`, normalized by the total weight of all pairs
+#[pyfunction]
+pub fn aid_with_pair_weights<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    weights: PyReadonlyArray2<'py, i64>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<(f64, usize)> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let weights = pair_weights_from(weights, graph_truth.n_nodes)?;
+    let weight = |t: usize, y: usize| weights[t][y];
+    Ok(aid_with(
+        g_true.py(),
+        aid,
+        &graph_truth,
+        &graph_guess,
+        PairWeights(weight),
+    ))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// alongside the n×n boolean mistake matrix, whose entry in row `t` and column `y` is `True`
+/// if the pair of treatment `t` and effect `y` is a mistake.
+/// Rows always correspond to treatments, irrespective of the `edge_direction`.
+/// Returns a tuple `(normalized_distance, mistakes, mistake_matrix)`
+#[pyfunction]
+pub fn aid_detailed<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<(f64, usize, Bound<'py, PyArray2<bool>>)> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let detailed = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, Detailed);
+    let matrix = PyArray2::from_vec2_bound(g_true.py(), &detailed.matrix)?;
+    Ok((detailed.normalized, detailed.mistakes, matrix))
+}
+
+/// Number of mistakes of the Adjustment Identification Distance `distance`
+/// ("ancestor_aid", "oset_aid", or "parent_aid") between two DAG / CPDAG adjacency matrices
+/// (sparse or dense) for each treatment, a uint64 ndarray of length n whose entry `t`
+/// is the number of effects `y` for which the pair of treatment `t` and effect `y` is a mistake
+#[pyfunction]
+pub fn aid_per_treatment<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray1<u64>>> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let mistakes = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, PerTreatment);
+    let mistakes = Vec::from_iter(mistakes.into_iter().map(|m| m as u64));
+    Ok(PyArray1::from_vec_bound(g_true.py(), mistakes))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// graded and normalized by the (treatment, effect) pairs of the `normalization`, one of `"all_pairs"`,
+/// `"amenable_pairs_in_truth"`, or `"graded_pairs"` (the pairs for which both graphs are amenable).
+/// Returns a tuple of (normalized error, number of errors, number of compared pairs)
+#[pyfunction]
+pub fn aid_with_normalization<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    normalization: &str,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let normalization = Normalization::parse(normalization).map_err(raise_option_error)?;
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = aid_with(g_true.py(), aid, &graph_truth, &graph_guess, normalization);
+    Ok((distance.normalized, distance.mistakes, distance.comparisons))
+}
+
+/// Structural Hamming Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// excluding the pairs of nodes whose edge is among the optional `known_edges` (a list of node pairs)
 /// Does not take `edge_direction` argument, because SHD only considers the adjacency matrix,
 /// irrespective of the edge direction interpretation.
 #[pyfunction]
-pub fn shd<'py>(g_true: &Bound<'py, PyAny>, g_guess: &Bound<'py, PyAny>) -> PyResult<(f64, usize)> {
+#[pyo3(signature = (g_true, g_guess, known_edges=None))]
+pub fn shd<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    known_edges: Option<Vec<(usize, usize)>>,
+) -> PyResult<(f64, usize)> {
     // set row_to_col variable to 'true', but it doesn't matter
     let row_to_col = true;
-    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
-    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
-    let (normalized_distance, n_errors) = rust_shd(&graph_truth, &graph_guess);
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let known = known_edges_from(graph_truth.n_nodes, known_edges)?;
+    let (normalized_distance, n_errors) = g_true.py().allow_threads(|| match &known {
+        Some(known) => rust_shd_with_known_edges(&graph_truth, &graph_guess, known),
+        None => rust_shd(&graph_truth, &graph_guess),
+    });
     Ok((normalized_distance, n_errors))
 }
 
-/// Structural Identification Distance between two DAG adjacency matrices (sparse or dense)
+type DetailedShdTuple = ((f64, usize), (usize, usize, usize, usize));
+
+/// Structural Hamming Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// with its mistakes split by their kind.
+/// Returns a tuple `((normalized_distance, mistake_count), (missing, superfluous, reversed, mistyped))`,
+/// which count the edges of `g_true` missing in `g_guess`, the edges of `g_guess` not in `g_true`,
+/// the directed edges reversed in `g_guess`, and the pairs with a directed edge in one graph
+/// and an undirected edge in the other, and sum to the mistake count.
+/// Does not take `edge_direction` argument, because the kinds of mistakes do not depend on it.
+#[pyfunction]
+pub fn shd_detailed<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+) -> PyResult<DetailedShdTuple> {
+    // set row_to_col variable to 'true', but it doesn't matter
+    let row_to_col = true;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let detailed = g_true
+        .py()
+        .allow_threads(|| rust_shd_detailed(&graph_truth, &graph_guess));
+    Ok((
+        (detailed.normalized(), detailed.mistakes()),
+        (
+            detailed.missing,
+            detailed.superfluous,
+            detailed.reversed,
+            detailed.mistyped,
+        ),
+    ))
+}
+
+type VStructureTuple = ((f64, usize), (usize, usize, usize));
+
+/// V-structure distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// which compares their v-structures `a -> c <- b` of non-adjacent nodes `a` and `b`.
+/// Returns a tuple `((normalized_distance, mistake_count), (only_in_truth, only_in_guess, shared))`,
+/// which count the v-structures only of `g_true`, only of `g_guess`, and of both graphs;
+/// the mistakes are the v-structures of only one graph, normalized by those of either graph
+#[pyfunction]
+pub fn vstructure_distance<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<VStructureTuple> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = g_true
+        .py()
+        .allow_threads(|| rust_vstructure_distance(&graph_truth, &graph_guess));
+    Ok((
+        (distance.normalized(), distance.mistakes()),
+        (
+            distance.only_in_truth,
+            distance.only_in_guess,
+            distance.shared,
+        ),
+    ))
+}
+
+/// Directed `(from, to)` edges `from -> to`.
+type Edges = Vec<(usize, usize)>;
+
+type ViolationsTuple = ((f64, usize), f64, Vec<(&'static str, usize, usize)>);
+
+/// Violations of an edge specification by a DAG / CPDAG adjacency matrix (sparse or dense),
+/// as partial ground truth when no complete true graph exists: the `required` and `forbidden` edges
+/// are lists of `(from, to)` node pairs of directed edges `from -> to`. An undirected edge of `g_guess`
+/// violates a required and a forbidden edge between its nodes. The optional `weights` map the names of the
+/// violations, "missing required", "reversed required", "undirected required", "forbidden present", and
+/// "forbidden undirected", to their weight, which is 1 for violations not in the map.
+/// Returns a tuple `((normalized_distance, violation_count), weighted, violations)`, where the violations
+/// are normalized by the number of constraints, `weighted` is the sum of their weights,
+/// and `violations` is a list of `(violation, from, to)` tuples
+#[pyfunction]
+#[pyo3(signature = (g_guess, required, forbidden, edge_direction, weights=None))]
+pub fn specification_violations<'py>(
+    g_guess: &Bound<'py, PyAny>,
+    required: Edges,
+    forbidden: Edges,
+    edge_direction: &str,
+    weights: Option<HashMap<String, f64>>,
+) -> PyResult<ViolationsTuple> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
+    let specification = EdgeSpecification::new(graph_guess.n_nodes, &required, &forbidden)
+        .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    let mut violation_weights = ViolationWeights::default();
+    for (name, weight) in weights.unwrap_or_default() {
+        let field = match Violation::parse(&name).map_err(raise_option_error)? {
+            Violation::MissingRequired => &mut violation_weights.missing_required,
+            Violation::ReversedRequired => &mut violation_weights.reversed_required,
+            Violation::UndirectedRequired => &mut violation_weights.undirected_required,
+            Violation::ForbiddenPresent => &mut violation_weights.forbidden_present,
+            Violation::ForbiddenUndirected => &mut violation_weights.forbidden_undirected,
+        };
+        *field = weight;
+    }
+    let violations = rust_specification_violations(&graph_guess, &specification);
+    Ok((
+        violations.distance(),
+        violations.weighted(&violation_weights),
+        Vec::from_iter(
+            violations
+                .violations
+                .iter()
+                .map(|(violation, from, to)| (violation.name(), *from, *to)),
+        ),
+    ))
+}
+
+/// Raises a GadjidError if the `graph` has undirected edges.
+fn require_dag(graph: &PDAG, name: &str) -> PyResult<()> {
+    if graph.n_undirected_edges > 0 {
+        return Err(GadjidError::new_err(format!(
+            "{name} graph is not a DAG, but has {} undirected edges",
+            graph.n_undirected_edges
+        )));
+    }
+    Ok(())
+}
+
+/// Structural Hamming Distance between the CPDAGs of two DAG adjacency matrices (sparse or dense),
+/// so that Markov equivalent DAGs have distance 0; raises a GadjidError if either graph has undirected edges.
+/// Returns a tuple of (normalized error, number of errors)
+#[pyfunction]
+pub fn shd_markov_equivalent<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(f64, usize)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    require_dag(&graph_truth, "True")?;
+    require_dag(&graph_guess, "Guess")?;
+    Ok(g_true
+        .py()
+        .allow_threads(|| rust_shd_markov_equivalent(&graph_truth, &graph_guess)))
+}
+
+/// CPDAG of a DAG adjacency matrix (sparse or dense), which represents its Markov equivalence class;
+/// raises a GadjidError if the graph has undirected edges.
+/// Returns the int8 adjacency matrix of the CPDAG, coded in the given `edge_direction`,
+/// with undirected edges coded by a `2` on both sides
+#[pyfunction]
+pub fn cpdag_of<'py>(
+    graph: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let dag = graph_from_pyobject(graph, row_to_col)?;
+    require_dag(&dag, "Input")?;
+    let py = graph.py();
+    let cpdag = py.allow_threads(|| rust_cpdag_of(&dag));
+    adjacency_to_pyarray(py, &cpdag, row_to_col)
+}
+
+/// Topological order of the nodes of a DAG / CPDAG adjacency matrix (sparse or dense) along its directed edges,
+/// in which every parent comes before its children; undirected edges do not constrain the order.
+/// Ties between nodes that can come next are broken by `tie_break`: "index" picks the lowest index,
+/// "degree" the node with the most adjacent nodes, and "random" draws the order from the `seed`,
+/// so that the order is the same across platforms and runs.
+/// Returns the list of nodes in topological order
+#[pyfunction]
+#[pyo3(signature = (graph, edge_direction, tie_break="index", seed=0))]
+pub fn topological_order(
+    graph: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    tie_break: &str,
+    seed: u64,
+) -> PyResult<Vec<usize>> {
+    let tie_break = TieBreakKind::parse(tie_break)
+        .map_err(raise_option_error)?
+        .with_seed(seed);
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let pdag = graph_from_pyobject(graph, row_to_col)?;
+    Ok(pdag.topological_order_with_tie_breaking(tie_break))
+}
+
+/// Minimum Structural Hamming Distance between a true DAG and the DAGs in the Markov equivalence class
+/// of a guess CPDAG, given as adjacency matrices (sparse or dense); raises a GadjidError if the true graph
+/// has undirected edges or if the undirected edges of the guess cannot be oriented into a DAG without new v-structures.
+/// Returns a `MecShd` with the `normalized` error and number of `mistakes`, whether the distance is
+/// provably minimal (`exact`), and the int8 adjacency matrix of a closest DAG in the class (`dag`),
+/// coded in the given `edge_direction`
+#[pyfunction]
+pub fn min_shd_over_mec(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<MecShd> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    require_dag(&graph_truth, "True")?;
+    let py = g_true.py();
+    let closest = py
+        .allow_threads(|| rust_min_shd_over_mec(&graph_truth, &graph_guess))
+        .ok_or_else(|| {
+            GadjidError::new_err(
+                "Guess graph is not a CPDAG, its undirected edges cannot be oriented into a DAG without new v-structures",
+            )
+        })?;
+    Ok(MecShd::new(
+        closest.normalized,
+        closest.mistakes,
+        closest.exact,
+        adjacency_to_pyarray(py, &closest.dag, row_to_col)?,
+    ))
+}
+
+type Counts = (usize, usize, usize, f64, f64, f64);
+
+fn counts_from(counts: ConfusionCounts) -> Counts {
+    (
+        counts.true_positives,
+        counts.false_positives,
+        counts.false_negatives,
+        counts.precision(),
+        counts.recall(),
+        counts.f1(),
+    )
+}
+
+/// Adjacency and arrowhead confusion counts of `g_guess` against `g_true`, two DAG / CPDAG adjacency
+/// matrices (sparse or dense), where a directed edge has an arrowhead at its head and an undirected edge has none.
+/// Returns a tuple `(adjacency, arrowhead)` of two tuples
+/// `(true_positives, false_positives, false_negatives, precision, recall, f1)`,
+/// where precision, recall, and F1 are 0 if their denominator is 0
+#[pyfunction]
+pub fn confusion<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(Counts, Counts)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let confusion = g_true
+        .py()
+        .allow_threads(|| rust_confusion(&graph_truth, &graph_guess));
+    Ok((
+        counts_from(confusion.adjacency),
+        counts_from(confusion.arrowhead),
+    ))
+}
+
+/// Weighted Structural Hamming Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// where entry `[i, j]` of the 4×4 float64 `costs` matrix is the cost of a pair of nodes `v < w`
+/// with edge type `i` in the true graph and edge type `j` in the guess graph, in the order
+/// no edge, `v → w`, `v ← w`, and `v – w` (so `edge_direction` matters, unlike for the SHD).
+/// Returns a tuple `(cost, counts)` of the total cost and the 4×4 int64 matrix of the number of pairs of
+/// nodes for each transition
+#[pyfunction]
+pub fn weighted_shd<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    costs: PyReadonlyArray2<'py, f64>,
+    edge_direction: &str,
+) -> PyResult<(f64, Bound<'py, PyArray2<i64>>)> {
+    let costs = costs.as_array();
+    if costs.shape() != [4, 4] {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "costs must be a 4×4 matrix",
+        ));
+    }
+    let costs: [[f64; 4]; 4] = std::array::from_fn(|i| std::array::from_fn(|j| costs[[i, j]]));
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let weighted = g_true
+        .py()
+        .allow_threads(|| rust_weighted_shd(&graph_truth, &graph_guess, &costs));
+    let counts = Vec::from_iter(
+        weighted
+            .counts
+            .iter()
+            .map(|row| Vec::from_iter(row.iter().map(|count| *count as i64))),
+    );
+    Ok((
+        weighted.cost,
+        PyArray2::from_vec2_bound(g_true.py(), &counts)?,
+    ))
+}
+
+/// Approximately smallest Structural Hamming Distance between two DAG / CPDAG adjacency matrices
+/// (sparse or dense) over all relabelings of the nodes of `g2`, for graphs whose nodes do not share identities.
+/// Evaluates at most `budget` swaps of two labels to refine the initial matching of nodes by their degrees.
+/// Returns a tuple `(normalized_distance, mistake_count, permutation)`,
+/// where node `v` of `g1` is matched to node `permutation[v]` of `g2`
+#[pyfunction]
+#[pyo3(signature = (g1, g2, budget=10000))]
+pub fn shd_min_over_permutations<'py>(
+    g1: &Bound<'py, PyAny>,
+    g2: &Bound<'py, PyAny>,
+    budget: usize,
+) -> PyResult<(f64, usize, Vec<usize>)> {
+    // as for the SHD, the direction of the edges does not matter
+    let row_to_col = true;
+    let (graph_1, graph_2) = graphs_from_pyobjects(g1, g2, row_to_col)?;
+    let permuted = g1
+        .py()
+        .allow_threads(|| rust_shd_min_over_permutations(&graph_1, &graph_2, budget));
+    Ok((permuted.shd.0, permuted.shd.1, permuted.permutation))
+}
+
+/// Graph Edit Distance between two DAG / CPDAG adjacency matrices (sparse or dense), where each edit
+/// of the edge between a pair of nodes costs 1 and rewiring a node with all its edges costs `node_cost`.
+/// Returns a tuple `(cost, rewired_nodes, is_exact)`, where `is_exact` is false if there are too many
+/// nodes worth rewiring to find the cheapest edits and the cost is an upper bound found greedily
+#[pyfunction]
+pub fn graph_edit_distance<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    node_cost: usize,
+) -> PyResult<(usize, Vec<usize>, bool)> {
+    // as for the SHD, the direction of the edges does not matter
+    let row_to_col = true;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = g_true
+        .py()
+        .allow_threads(|| rust_graph_edit_distance(&graph_truth, &graph_guess, node_cost));
+    Ok((distance.cost, distance.rewired_nodes, distance.is_exact))
+}
+
+fn raise_sid_error(err: SIDError) -> PyErr {
+    match err {
+        SIDError::NotSameSize => SizeMismatchError::new_err(err.to_string()),
+        SIDError::TruthNotDAG
+        | SIDError::GuessNotDAG
+        | SIDError::TruthNotExtendable
+        | SIDError::GuessNotExtendable
+        | SIDError::GuessClassTooLarge => GadjidError::new_err(err.to_string()),
+        SIDError::InvalidPairs(_) => InvalidPairsError::new_err(err.to_string()),
+    }
+}
+
+/// Structural Identification Distance between two DAG adjacency matrices (sparse or dense).
+/// Graphs with undirected edges raise an error if `undirected` is "error"; if it is "orient",
+/// they are oriented into a consistent DAG extension with a warning; and if it is "bounds",
+/// the guess may be a CPDAG and a tuple `((lower_normalized, lower_mistakes), (upper_normalized, upper_mistakes))`
+/// bounding the SID over the DAGs it represents is returned
 #[pyfunction]
+#[pyo3(signature = (g_true, g_guess, edge_direction, undirected="error"))]
 pub fn sid<'py>(
     g_true: &Bound<'py, PyAny>,
     g_guess: &Bound<'py, PyAny>,
     edge_direction: &str,
-) -> anyhow::Result<(f64, usize)> {
+    undirected: &str,
+) -> PyResult<PyObject> {
+    let undirected = UndirectedEdges::parse(undirected).map_err(raise_option_error)?;
     let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
-    let dag_truth = graph_from_pyobject(g_true, row_to_col)?;
-    let dag_guess = graph_from_pyobject(g_guess, row_to_col)?;
-    let (normalized_distance, n_errors) = rust_sid(&dag_truth, &dag_guess)?;
-    Ok((normalized_distance, n_errors))
+    let (dag_truth, dag_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let py = g_true.py();
+    let result = py
+        .allow_threads(|| rust_sid_with_undirected(&dag_truth, &dag_guess, undirected))
+        .map_err(raise_sid_error)?;
+    for (oriented, graph) in [
+        (result.oriented_truth, "true"),
+        (result.oriented_guess, "guess"),
+    ] {
+        if oriented {
+            let warning = format!(
+                "The undirected edges of the {graph} graph were oriented into a consistent DAG extension"
+            );
+            PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), &warning, 1)?;
+        }
+    }
+    Ok(match undirected {
+        UndirectedEdges::Bounds => (result.lower, result.upper).into_py(py),
+        UndirectedEdges::Reject | UndirectedEdges::Orient => result.lower.into_py(py),
+    })
 }
 
-/// Load a graph from a 2D numpy or scipy sparse matrix.
-/// Will load a matrix into a PDAG, automatically loading into a DAG and checking
-/// acyclicity. If undirected edges present, assumes that it encodes as valid CPDAG
-fn graph_from_pyobject(ob: &Bound<'_, PyAny>, is_row_to_col: bool) -> anyhow::Result<PDAG> {
-    // first try to load as np dense matrix
-    match try_from_dense(ob, is_row_to_col) {
-        Ok(load_result) => Ok(load_result),
-        Err(e1) => match try_from_sparse(ob, is_row_to_col) {
-            Ok(graph) => Ok(graph),
-            Err(e2) => {
-                let msg = format!(
-                    "Errors occured when loading adjacency matrix. Did not succeed trying to load data
-as np ndarray or scipy sparse matrix.
-\nAttempt to load from numpy ndarray:\n\"{}\"
-\nAttempt to load from scipy sparse :\n\"{}\"", e1, e2);
-                anyhow::bail!(msg)
-            }
-        },
+/// Structural Identification Distance between a true DAG and the DAGs in the Markov equivalence class
+/// of a guess CPDAG, given as adjacency matrices (sparse or dense); raises a GadjidError if the true graph
+/// is not a DAG, if the undirected edges of the guess cannot be oriented into a DAG without new v-structures,
+/// or if a chain component of the guess has more than 10^6 orientations.
+/// Returns a `MecSid` with the `min`, `mean`, and `max` tuples of (normalized error, number of errors)
+/// over the `n_dags` DAGs in the class
+#[pyfunction]
+pub fn sid_over_mec(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<MecSid> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (dag_truth, cpdag_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let over_mec = g_true
+        .py()
+        .allow_threads(|| rust_sid_over_mec(&dag_truth, &cpdag_guess))
+        .map_err(raise_sid_error)?;
+    Ok(MecSid::from(over_mec))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// estimated from the mistakes of about `sample_size` treatments sampled with the given `seed`,
+/// uniformly or, if `n_strata` is given, stratified by the number of edges of the nodes in both graphs;
+/// if `n_effects` is given, only up to `n_effects` effects of each treatment are sampled from each of
+/// its possible descendants in the true graph and the other nodes, and weighted accordingly.
+/// Returns a tuple of (estimated normalized error, standard error, (lower, upper) 95% confidence bound)
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, distance, edge_direction, sample_size, n_strata=None, n_effects=None, seed=0))]
+#[allow(clippy::too_many_arguments)]
+pub fn aid_approx(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+    sample_size: usize,
+    n_strata: Option<usize>,
+    n_effects: Option<usize>,
+    seed: u64,
+) -> PyResult<(f64, f64, (f64, f64))> {
+    let aid = aid_from(distance)?;
+    approximate_distance(
+        g_true,
+        g_guess,
+        edge_direction,
+        sample_size,
+        (n_strata, n_effects),
+        seed,
+        aid,
+    )
+}
+
+/// Lower and upper bound on the Parent Adjustment Identification Distance between two DAG / CPDAG
+/// adjacency matrices (sparse or dense), computed from the neighbourhoods of each node without any graph search.
+/// Returns a tuple `((lower_normalized, lower_mistakes), (upper_normalized, upper_mistakes))`
+#[pyfunction]
+pub fn parent_aid_bounds(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<((f64, usize), (f64, usize))> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let bounds = g_true
+        .py()
+        .allow_threads(|| rust_parent_aid_bounds(&graph_truth, &graph_guess));
+    let (lower, upper) = bounds.normalized();
+    Ok(((lower, bounds.lower), (upper, bounds.upper)))
+}
+
+/// Distance of a DAG / CPDAG adjacency matrix (sparse or dense) to a ground truth that is only known
+/// as the `tiers` of its nodes, a list of integers such that no node is caused by a node of a later tier,
+/// and optionally its `skeleton`, a list of the unordered pairs of adjacent nodes.
+/// Returns a tuple of (normalized error, number of errors,
+/// (order violations, extra adjacencies, missing adjacencies))
+#[pyfunction]
+#[pyo3(signature = (tiers, g_guess, edge_direction, skeleton=None))]
+pub fn tiered_distance(
+    tiers: Vec<usize>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    skeleton: Option<Vec<(usize, usize)>>,
+) -> PyResult<(f64, usize, (usize, usize, usize))> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
+    if tiers.len() != graph_guess.n_nodes {
+        return Err(SizeMismatchError::new_err(format!(
+            "Tiers must have one entry per node, but have {} entries for {} nodes",
+            tiers.len(),
+            graph_guess.n_nodes
+        )));
     }
+    let truth = TieredTruth::new(tiers, skeleton.as_deref())
+        .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    let distance = g_guess
+        .py()
+        .allow_threads(|| rust_tiered_distance(&truth, &graph_guess));
+    Ok((
+        distance.normalized,
+        distance.mistakes,
+        (
+            distance.order_violations,
+            distance.extra_adjacencies,
+            distance.missing_adjacencies,
+        ),
+    ))
 }
 
-/// Helper to avoid repetition, used by the numpy and scipy sparse loading files.
-/// Will load an edgelist iterator into a PDAG, automatically loading into a DAG and checking
-/// acyclicity. If undirected edges present, assumes that it encodes as valid CPDAG
-pub(crate) fn graph_from_iterator(
-    iterator: impl Iterator<Item = (usize, usize, i8)>,
-    row_to_col: bool,
-    graph_size: usize,
-) -> anyhow::Result<PDAG> {
-    match row_to_col {
-        true => match PDAG::try_from_row_major(EdgelistIterator::into_row_major_edgelist(
-            iterator, graph_size,
-        )) {
-            Ok(pdag) => Ok(pdag),
-            Err(err) => match err {
-                ::gadjid::LoadError::NotAcyclic => bail!(err),
-            },
-        },
-        // we have a col-to-row matrix
-        false => match PDAG::try_from_col_major(EdgelistIterator::into_column_major_edgelist(
-            iterator, graph_size,
-        )) {
-            Ok(pdag) => Ok(pdag),
-            Err(err) => match err {
-                ::gadjid::LoadError::NotAcyclic => bail!(err),
-            },
-        },
+/// Returns the tuple of (normalized error, number of errors, number of distinct pairs)
+/// of a distance on selected pairs, normalized as given by `normalization`.
+fn selected_result(
+    distance: SelectedPairsDistance,
+    normalization: PairsNormalization,
+) -> (f64, usize, usize) {
+    let (normalized, mistakes) = distance.normalized_by(normalization);
+    (normalized, mistakes, distance.comparisons)
+}
+
+/// Collects the selected (treatment, effect) `pairs`, given either as a list of tuples of node indices
+/// or as a tuple of two boolean ndarrays of length n that mark the treatments and the effects,
+/// which select all pairs of a marked treatment and another marked effect.
+fn selected_pairs_from(pairs: &Bound<'_, PyAny>, n_nodes: usize) -> PyResult<Vec<(usize, usize)>> {
+    let Ok((treatments, effects)) =
+        pairs.extract::<(PyReadonlyArray1<'_, bool>, PyReadonlyArray1<'_, bool>)>()
+    else {
+        return pairs.extract();
+    };
+    let (treatments, effects) = (treatments.as_array(), effects.as_array());
+    if treatments.len() != n_nodes || effects.len() != n_nodes {
+        return Err(SizeMismatchError::new_err(format!(
+            "Treatment and effect masks must have length {n_nodes}, but have lengths {} and {}",
+            treatments.len(),
+            effects.len()
+        )));
+    }
+    let treatments = Vec::from_iter(treatments.iter().copied());
+    let effects = Vec::from_iter(effects.iter().copied());
+    Ok(rust_pairs_from_masks(&treatments, &effects))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// only counting mistakes for the selected (treatment, effect) `pairs`, a list of tuples of node indices
+/// or a tuple of two boolean ndarrays of length n that mark the treatments and the effects.
+/// Returns a tuple of (normalized error, number of errors, number of distinct pairs),
+/// where repeated pairs are counted once and the errors are normalized by the number of distinct pairs
+/// or, if `normalization="population"`, by the number n² − n of pairs in the graphs
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, pairs, distance, edge_direction, normalization="selected"))]
+pub fn aid_selected_pairs<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    pairs: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+    normalization: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let normalization = PairsNormalization::parse(normalization).map_err(raise_option_error)?;
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let pairs = selected_pairs_from(pairs, graph_truth.n_nodes)?;
+    let distance = aid_with(
+        g_true.py(),
+        aid,
+        &graph_truth,
+        &graph_guess,
+        SelectedPairs(&pairs),
+    )
+    .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    Ok(selected_result(distance, normalization))
+}
+
+/// Adjustment Identification Distance `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// only counting mistakes for the selected (treatment, effects) `pairs`, a list of tuples of
+/// a treatment node and a list of effect nodes whose joint effect is graded.
+/// Returns a tuple of (normalized error, number of errors, number of distinct pairs),
+/// where repeated pairs are counted once and the errors are normalized by the number of distinct pairs
+/// or, if `normalization="population"`, by the number n² − n of pairs in the graphs
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, pairs, distance, edge_direction, normalization="selected"))]
+pub fn aid_selected_effect_sets<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    pairs: Vec<(usize, Vec<usize>)>,
+    distance: &str,
+    edge_direction: &str,
+    normalization: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let normalization = PairsNormalization::parse(normalization).map_err(raise_option_error)?;
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = aid_with(
+        g_true.py(),
+        aid,
+        &graph_truth,
+        &graph_guess,
+        SelectedEffectSets(&pairs),
+    )
+    .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    Ok(selected_result(distance, normalization))
+}
+
+/// Optimal Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// only counting mistakes for the selected (treatments, effect) `pairs`, a list of tuples of
+/// a list of jointly intervened-on treatment nodes and an effect node.
+/// Returns a tuple of (normalized error, number of errors, number of distinct pairs),
+/// where repeated pairs are counted once and the errors are normalized by the number of distinct pairs
+/// or, if `normalization="population"`, by the number n² − n of pairs in the graphs
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, pairs, edge_direction, normalization="selected"))]
+pub fn oset_aid_selected_treatment_sets<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    pairs: Vec<(Vec<usize>, usize)>,
+    edge_direction: &str,
+    normalization: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let normalization = PairsNormalization::parse(normalization).map_err(raise_option_error)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = g_true
+        .py()
+        .allow_threads(|| rust_oset_aid_selected_treatment_sets(&graph_truth, &graph_guess, &pairs))
+        .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    Ok(selected_result(distance, normalization))
+}
+
+/// Conditional Adjustment Identification Distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// only counting mistakes for the selected (treatment, effect, conditioning set) `triples`,
+/// each a tuple of a treatment node, an effect node, and a list of conditioning nodes
+/// that are not affected by the treatment.
+/// Returns a tuple of (normalized error, number of errors, number of distinct triples),
+/// where repeated triples are counted once and the errors are normalized by the number of distinct triples
+/// or, if `normalization="population"`, by the number n² − n of pairs in the graphs
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, triples, edge_direction, normalization="selected"))]
+pub fn conditional_aid<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    triples: Vec<(usize, usize, Vec<usize>)>,
+    edge_direction: &str,
+    normalization: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let normalization = PairsNormalization::parse(normalization).map_err(raise_option_error)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = g_true
+        .py()
+        .allow_threads(|| rust_conditional_aid(&graph_truth, &graph_guess, &triples))
+        .map_err(|err| InvalidPairsError::new_err(err.to_string()))?;
+    Ok(selected_result(distance, normalization))
+}
+
+/// Structural Intervention Distance between two DAG adjacency matrices (sparse or dense),
+/// only counting mistakes for the selected (treatment, effect) `pairs`, a list of tuples of node indices
+/// or a tuple of two boolean ndarrays of length n that mark the treatments and the effects.
+/// Returns a tuple of (normalized error, number of errors, number of distinct pairs),
+/// where repeated pairs are counted once
+#[pyfunction]
+pub fn sid_selected_pairs<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    pairs: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(f64, usize, usize)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (dag_truth, dag_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let pairs = selected_pairs_from(pairs, dag_truth.n_nodes)?;
+    let distance = g_true
+        .py()
+        .allow_threads(|| rust_sid_selected_pairs(&dag_truth, &dag_guess, &pairs))
+        .map_err(|err| match err {
+            SIDError::NotSameSize => SizeMismatchError::new_err(err.to_string()),
+            SIDError::InvalidPairs(_) => InvalidPairsError::new_err(err.to_string()),
+            SIDError::TruthNotDAG
+            | SIDError::GuessNotDAG
+            | SIDError::TruthNotExtendable
+            | SIDError::GuessNotExtendable
+            | SIDError::GuessClassTooLarge => GadjidError::new_err(err.to_string()),
+        })?;
+    Ok((distance.normalized, distance.mistakes, distance.comparisons))
+}
+
+/// Amenability matrix of a DAG / CPDAG adjacency matrix (sparse or dense),
+/// a boolean numpy ndarray whose entry in row `t` and column `y` is `True`
+/// if the effect of treatment `t` on effect `y` can be identified by adjustment.
+/// Rows always correspond to treatments, irrespective of the `edge_direction`.
+#[pyfunction]
+pub fn amenability_matrix<'py>(
+    graph: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray2<bool>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let matrix = graph
+        .py()
+        .allow_threads(|| rust_amenability_matrix(&graph_pdag));
+    Ok(PyArray2::from_vec2_bound(graph.py(), &matrix)?)
+}
+
+/// Amenability disagreement between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// the number of (treatment, effect) pairs for which one graph is amenable and the other is not,
+/// a lower bound on the number of mistakes of each AID.
+/// Returns a tuple of (normalized error (in [0,1]), total number of errors)
+#[pyfunction]
+pub fn amenability_disagreement(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(f64, usize)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    Ok(g_true
+        .py()
+        .allow_threads(|| rust_amenability_disagreement(&graph_truth, &graph_guess)))
+}
+
+/// Conditional independence statement distance between two DAG / CPDAG adjacency matrices (sparse or dense),
+/// the number of statements "x and y are independent given Z" with at most `max_condition_size` nodes in Z
+/// that one graph implies by d-separation and the other does not.
+/// Returns a tuple `((normalized_distance, mistake_count), (only_in_truth, only_in_guess))`,
+/// where the mistakes split into the statements implied only by `g_true` and only by `g_guess`
+#[pyfunction]
+pub fn ci_statement_distance(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    max_condition_size: usize,
+) -> PyResult<((f64, usize), (usize, usize))> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let distance = g_true.py().allow_threads(|| {
+        rust_ci_statement_distance(&graph_truth, &graph_guess, max_condition_size)
+    });
+    Ok((
+        (distance.normalized(), distance.mistakes()),
+        (distance.only_in_truth, distance.only_in_guess),
+    ))
+}
+
+type Statement = (usize, usize, Vec<usize>);
+
+/// Conditional independence statements that a DAG / CPDAG adjacency matrix (sparse or dense) implies
+/// by d-separation, for non-adjacent nodes and conditioning sets of at most `max_condition_size` nodes,
+/// ordered by the size of the conditioning set. Returns a list of tuples `(x, y, given)`
+/// for the statements "x and y are independent given the nodes in the sorted list `given`"
+#[pyfunction]
+pub fn implied_independencies(
+    graph: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    max_condition_size: usize,
+) -> PyResult<Vec<Statement>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let statements = graph
+        .py()
+        .allow_threads(|| rust_implied_independencies(&graph_pdag, max_condition_size));
+    Ok(Vec::from_iter(
+        statements.into_iter().map(|s| (s.x, s.y, s.given)),
+    ))
+}
+
+/// Tests the conditional independence statements implied by a DAG / CPDAG adjacency matrix
+/// (sparse or dense), as listed by `implied_independencies`, against data: `ci_test(x, y, given)`
+/// runs a conditional independence test and returns whether it accepts that x and y are independent
+/// given the nodes in the list `given`. Tests the first `max_tests` statements if given, and all otherwise.
+/// Returns a tuple `(n_tested, rejected)` with the list of `(x, y, given)` statements the test rejected
+#[pyfunction]
+#[pyo3(signature = (graph, edge_direction, ci_test, max_condition_size=1, max_tests=None))]
+pub fn markov_property_test(
+    graph: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    ci_test: &Bound<'_, PyAny>,
+    max_condition_size: usize,
+    max_tests: Option<usize>,
+) -> PyResult<(usize, Vec<Statement>)> {
+    let statements = implied_independencies(graph, edge_direction, max_condition_size)?;
+    let n_tested = max_tests.map_or(statements.len(), |m| m.min(statements.len()));
+    let mut rejected = Vec::new();
+    // the callable needs the GIL, so the tests run one after the other
+    for (x, y, given) in statements.into_iter().take(n_tested) {
+        if !ci_test.call1((x, y, given.clone()))?.is_truthy()? {
+            rejected.push((x, y, given));
+        }
+    }
+    Ok((n_tested, rejected))
+}
+
+/// Identifiability report of a DAG / CPDAG adjacency matrix (sparse or dense).
+/// Returns a tuple of (fraction of amenable (treatment, effect) pairs,
+/// list of the number of amenable proper possible descendants of each node,
+/// list of the connected components of undirected edges, largest first)
+#[pyfunction]
+pub fn identifiability_report(
+    graph: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(f64, Vec<usize>, Vec<Vec<usize>>)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let report = graph
+        .py()
+        .allow_threads(|| rust_identifiability_report(&graph_pdag));
+    Ok((
+        report.fraction_amenable,
+        report.amenable_effects,
+        report.non_amenable_clusters,
+    ))
+}
+
+/// Parent set report comparing two DAG / CPDAG adjacency matrices (sparse or dense).
+/// Returns a list with one tuple per node of (true parents, guessed parents, Jaccard similarity of the two,
+/// whether the guessed parents are a valid adjustment set in `g_true` for the effects on the true children)
+#[pyfunction]
+pub fn parent_set_report(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<Vec<ParentSets>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let report = g_true
+        .py()
+        .allow_threads(|| rust_parent_set_report(&graph_truth, &graph_guess));
+    Ok(Vec::from_iter(report.into_iter().map(|comparison| {
+        (
+            comparison.true_parents,
+            comparison.guessed_parents,
+            comparison.jaccard,
+            comparison.valid_for_true_children,
+        )
+    })))
+}
+
+/// The true and guessed parents of a node, their Jaccard similarity, and whether the guessed parents
+/// are a valid adjustment set for the effects on the true children.
+type ParentSets = (Vec<usize>, Vec<usize>, f64, bool);
+
+/// Check two DAG / CPDAG adjacency matrices (sparse or dense) for signs of a bug in the pipeline
+/// that produced them before reporting a distance between them: different numbers of nodes,
+/// a graph without edges, identical graphs, a guess with more than ten times as many or as few edges,
+/// or a guess that is the skeleton of the truth.
+/// Issues a `GraphPairWarning` for each finding and returns the list of their messages
+#[pyfunction]
+pub fn validate_pair(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<Vec<String>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    // loaded one by one, so that graphs of different sizes are reported rather than raised
+    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
+    let graph_guess = graph_from_pyobject(g_guess, row_to_col)?;
+    let warnings = Vec::from_iter(
+        rust_validate_pair(&graph_truth, &graph_guess)
+            .iter()
+            .map(|warning| warning.to_string()),
+    );
+    let py = g_true.py();
+    let category = py.get_type_bound::<GraphPairWarning>();
+    for warning in &warnings {
+        PyErr::warn_bound(py, &category, warning, 1)?;
+    }
+    Ok(warnings)
+}
+
+/// Compare the adjustment sets `z1` and `z2` for the effect of the treatments `t` on the effect `y`
+/// in a DAG / CPDAG adjacency matrix (sparse or dense).
+/// Returns a tuple of (whether `z1` is valid, whether `z2` is valid, nodes only in `z1`, nodes only in `z2`,
+/// variance ordering), where the variance ordering is `-1` if adjusting for `z1` is at least as efficient
+/// as adjusting for `z2` in all linear causal models compatible with the graph (but not vice versa),
+/// `1` if it is the other way around, `0` if both hold, and `None` if neither holds or a set is not valid
+#[pyfunction]
+pub fn compare_adjustment_sets(
+    graph: &Bound<'_, PyAny>,
+    t: Vec<usize>,
+    y: usize,
+    z1: Vec<usize>,
+    z2: Vec<usize>,
+    edge_direction: &str,
+) -> PyResult<AdjustmentSets> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let n_nodes = graph_pdag.n_nodes;
+    if t.is_empty() || t.contains(&y) {
+        return Err(InvalidPairsError::new_err(
+            "there must be at least one treatment, and the effect must not be a treatment",
+        ));
+    }
+    if let Some(node) = t
+        .iter()
+        .chain(&z1)
+        .chain(&z2)
+        .chain([&y])
+        .find(|v| **v >= n_nodes)
+    {
+        return Err(InvalidPairsError::new_err(format!(
+            "node {node} is out of bounds for graphs with {n_nodes} nodes"
+        )));
+    }
+    let comparison = graph
+        .py()
+        .allow_threads(|| rust_compare_adjustment_sets(&graph_pdag, &t, y, &z1, &z2));
+    Ok((
+        comparison.z1_is_valid,
+        comparison.z2_is_valid,
+        comparison.only_in_z1,
+        comparison.only_in_z2,
+        comparison.variance_ordering.map(|ordering| ordering as i8),
+    ))
+}
+
+/// The validity of both adjustment sets, the nodes only in either of them, and the ordering of their variances.
+type AdjustmentSets = (bool, bool, Vec<usize>, Vec<usize>, Option<i8>);
+
+/// Parent, optimal, and minimal adjustment set for the effect of the treatments `t` on the effect `y`
+/// in a DAG / CPDAG adjacency matrix (sparse or dense).
+/// Returns a tuple of (parents of `t`, optimal adjustment set, a minimal adjustment set or `None` if no
+/// adjustment set is valid, whether the parents are valid, whether the optimal adjustment set is valid,
+/// a tuple of whether the parents are a subset of the optimal adjustment set, the optimal adjustment set
+/// is a subset of the parents, and the minimal adjustment set is a subset of the parents)
+#[pyfunction]
+pub fn adjustment_set_candidates(
+    graph: &Bound<'_, PyAny>,
+    t: Vec<usize>,
+    y: usize,
+    edge_direction: &str,
+) -> PyResult<Candidates> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let n_nodes = graph_pdag.n_nodes;
+    if t.is_empty() || t.contains(&y) {
+        return Err(InvalidPairsError::new_err(
+            "there must be at least one treatment, and the effect must not be a treatment",
+        ));
+    }
+    if let Some(node) = t.iter().chain([&y]).find(|v| **v >= n_nodes) {
+        return Err(InvalidPairsError::new_err(format!(
+            "node {node} is out of bounds for graphs with {n_nodes} nodes"
+        )));
+    }
+    let candidates = graph
+        .py()
+        .allow_threads(|| rust_adjustment_set_candidates(&graph_pdag, &t, y));
+    Ok((
+        candidates.parents,
+        candidates.optimal,
+        candidates.minimal,
+        candidates.parents_is_valid,
+        candidates.optimal_is_valid,
+        (
+            candidates.parents_subset_of_optimal,
+            candidates.optimal_subset_of_parents,
+            candidates.minimal_subset_of_parents,
+        ),
+    ))
+}
+
+/// Nodes `y` not in `t` for which `z` is not a valid adjustment set for the effect of the treatments `t` on `y`
+/// in a DAG / CPDAG adjacency matrix (sparse or dense), including the nodes for which no adjustment set is valid.
+/// Returns a sorted list of nodes
+#[pyfunction]
+pub fn not_validly_adjusted_for(
+    graph: &Bound<'_, PyAny>,
+    t: Vec<usize>,
+    z: Vec<usize>,
+    edge_direction: &str,
+) -> PyResult<Vec<usize>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let n_nodes = graph_pdag.n_nodes;
+    if t.is_empty() {
+        return Err(InvalidPairsError::new_err(
+            "there must be at least one treatment",
+        ));
+    }
+    if let Some(node) = t.iter().chain(&z).find(|v| **v >= n_nodes) {
+        return Err(InvalidPairsError::new_err(format!(
+            "node {node} is out of bounds for graphs with {n_nodes} nodes"
+        )));
+    }
+    Ok(graph
+        .py()
+        .allow_threads(|| rust_not_validly_adjusted_for(&graph_pdag, &t, &z)))
+}
+
+/// The parent, optimal, and minimal adjustment set, the validity of the first two, and their containment.
+type Candidates = (
+    Vec<usize>,
+    Vec<usize>,
+    Option<Vec<usize>>,
+    bool,
+    bool,
+    (bool, bool, bool),
+);
+
+/// Export a DAG / CPDAG adjacency matrix (sparse or dense) as Cytoscape.js JSON string,
+/// optionally annotating each node with its number of `mistakes` (a list with one entry per node),
+/// whether it is `not_amenable_from` the given treatment node,
+/// and whether it is in the optimal adjustment set for the given `(treatment, effect)` tuple
+/// `optimal_adjustment_set_for`
+#[pyfunction]
+#[pyo3(signature = (graph, edge_direction, mistakes=None, not_amenable_from=None, optimal_adjustment_set_for=None))]
+pub fn to_cytoscape_json(
+    graph: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    mistakes: Option<Vec<usize>>,
+    not_amenable_from: Option<usize>,
+    optimal_adjustment_set_for: Option<(usize, usize)>,
+) -> PyResult<String> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_pdag = graph_from_pyobject(graph, row_to_col)?;
+    let n_nodes = graph_pdag.n_nodes;
+
+    let mut layers = Vec::new();
+    if let Some(mistakes) = mistakes {
+        if mistakes.len() != n_nodes {
+            return Err(SizeMismatchError::new_err(format!(
+                "There are {} numbers of mistakes for a graph with {n_nodes} nodes",
+                mistakes.len()
+            )));
+        }
+        layers.push(Layer::Mistakes(mistakes));
+    }
+    if let Some(treatment) = not_amenable_from {
+        layers.push(Layer::NotAmenable { treatment });
+    }
+    if let Some((treatment, effect)) = optimal_adjustment_set_for {
+        layers.push(Layer::OptimalAdjustmentSet { treatment, effect });
+    }
+    let nodes = not_amenable_from.into_iter().chain(
+        optimal_adjustment_set_for
+            .into_iter()
+            .flat_map(|(t, y)| [t, y]),
+    );
+    for node in nodes {
+        if node >= n_nodes {
+            return Err(InvalidPairsError::new_err(format!(
+                "node {node} is out of bounds for graphs with {n_nodes} nodes"
+            )));
+        }
+    }
+
+    Ok(rust_to_cytoscape_json(&graph_pdag, &layers))
+}
+
+/// Load an ensemble (a list) of DAG / CPDAG adjacency matrices (sparse or dense) or edge lists,
+/// or a (k, n, n) int8 ndarray of k adjacency matrices, into a `GraphCollection`,
+/// which can be passed to the ensemble functions in place of the list
+/// so that scoring the same ensemble repeatedly does not load its graphs again.
+/// Dense adjacency matrices are copied into one memory pool and loaded in parallel.
+#[pyfunction]
+pub fn load_many(graphs: &Bound<'_, PyAny>, edge_direction: &str) -> PyResult<GraphCollection> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    GraphCollection::load(graphs, row_to_col)
+}
+
+/// Names of the test graphs bundled with gadjid, like "10-node-DAG-10" and "10-node-CPDAG-10",
+/// ten random DAGs with 10 nodes (ids 10 to 19) and ten with 100 nodes (ids 20 to 29) alongside their CPDAGs
+#[pyfunction]
+pub fn corpus_graphs() -> Vec<&'static str> {
+    Vec::from_iter(corpus::GRAPHS.iter().map(|graph| graph.name))
+}
+
+/// Loads the bundled test graph of the given name, as listed by `corpus_graphs`,
+/// raising a ValueError if there is none.
+/// Returns its int8 adjacency matrix, coded in the given `edge_direction`,
+/// with undirected edges coded by a `2` on both sides
+#[pyfunction]
+pub fn load_corpus_graph<'py>(
+    py: Python<'py>,
+    name: &str,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph = corpus::load(name).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "there is no graph named {name:?} in the corpus"
+        ))
+    })?;
+    adjacency_to_pyarray(py, &graph, row_to_col)
+}
+
+/// Consensus graph of an ensemble (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense),
+/// in which two nodes are adjacent if they are adjacent in at least a `threshold` fraction of the graphs,
+/// and the edge is directed if it has the same direction in at least a `threshold` fraction of the graphs.
+/// Returns the int8 adjacency matrix of the consensus graph, coded in the same `edge_direction`,
+/// with undirected edges coded by a `2` on both sides
+#[pyfunction]
+pub fn consensus_graph<'py>(
+    py: Python<'py>,
+    graphs: &Bound<'py, PyAny>,
+    threshold: f64,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    let consensus = py
+        .allow_threads(|| rust_consensus_graph(&graphs, threshold))
+        .map_err(raise_ensemble_error)?;
+    adjacency_to_pyarray(py, &consensus, row_to_col)
+}
+
+/// SHD medoid of an ensemble (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense):
+/// the index of the graph with the smallest total structural hamming distance to all graphs
+#[pyfunction]
+pub fn shd_medoid(
+    py: Python<'_>,
+    graphs: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<usize> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    py.allow_threads(|| rust_shd_medoid(&graphs))
+        .map_err(raise_ensemble_error)
+}
+
+/// SHD barycenter of an ensemble (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense):
+/// a graph with a small total structural hamming distance to all graphs, found by taking the most
+/// frequent edge between each pair of nodes that does not close a directed cycle and hill climbing.
+/// Returns a tuple `(adjacency, total_shd)` of the int8 adjacency matrix of the barycenter,
+/// coded in the same `edge_direction`, and its total structural hamming distance to the graphs
+#[pyfunction]
+pub fn shd_barycenter<'py>(
+    py: Python<'py>,
+    graphs: &Bound<'py, PyAny>,
+    edge_direction: &str,
+) -> PyResult<(Bound<'py, PyArray2<i8>>, usize)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    let (barycenter, total) = py
+        .allow_threads(|| rust_shd_barycenter(&graphs))
+        .map_err(raise_ensemble_error)?;
+    Ok((adjacency_to_pyarray(py, &barycenter, row_to_col)?, total))
+}
+
+type Frequency = (usize, usize, f64, f64, f64);
+
+/// Edge selection frequencies of an ensemble (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense).
+/// Returns a list of tuples `(v, w, forward, backward, undirected)`, one for each pair of nodes `v < w`
+/// adjacent in any of the graphs, of the fractions of graphs with the edge `v → w`, `v ← w`, and `v – w`
+#[pyfunction]
+pub fn edge_frequencies(
+    py: Python<'_>,
+    graphs: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<Vec<Frequency>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    let frequencies = py
+        .allow_threads(|| rust_edge_frequencies(&graphs))
+        .map_err(raise_ensemble_error)?;
+    Ok(Vec::from_iter(frequencies.iter().map(|frequency| {
+        let (v, w) = frequency.nodes;
+        (
+            v,
+            w,
+            frequency.forward,
+            frequency.backward,
+            frequency.undirected,
+        )
+    })))
+}
+
+/// Identification stability of the selected (treatment, effect) `pairs` across an ensemble
+/// (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense): for each pair, the fraction of ordered pairs of
+/// distinct graphs for which the `distance` ("ancestor_aid", "oset_aid", or "parent_aid")
+/// counts no mistake when taking the one graph as guess and the other as true graph
+#[pyfunction]
+pub fn identification_stability(
+    py: Python<'_>,
+    graphs: &Bound<'_, PyAny>,
+    pairs: Vec<(usize, usize)>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<Vec<f64>> {
+    let aid = aid_from(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    py.allow_threads(|| {
+        rust_identification_stability(&graphs, &pairs, |truth, guess, pairs| {
+            aid.compute_with(truth, guess, SelectedPairs(pairs))
+        })
+    })
+    .map_err(raise_ensemble_error)
+}
+
+type DispersionTuple = (f64, f64, f64, f64, f64);
+
+/// Cross-consistency of an ensemble (a list, a (k, n, n) int8 ndarray, or a `GraphCollection`)
+/// of DAG / CPDAG adjacency matrices (sparse or dense)
+/// under the `distance` ("ancestor_aid", "oset_aid", "parent_aid", or "shd"), without a true graph.
+/// Returns a tuple `(distances, (mean, standard_deviation, min, max, asymmetry))` of the float64 matrix
+/// whose entry in row `i` and column `j` is the normalized distance between graph `i` as true graph
+/// and graph `j` as guess graph, and summary statistics of its off-diagonal entries
+#[pyfunction]
+pub fn cross_consistency<'py>(
+    py: Python<'py>,
+    graphs: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<(Bound<'py, PyArray2<f64>>, DispersionTuple)> {
+    let distance = distance_from_name(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graphs = ensemble_from_pyobject(graphs, row_to_col)?;
+    let consistency = py
+        .allow_threads(|| rust_cross_consistency(&graphs, distance))
+        .map_err(raise_ensemble_error)?;
+    let normalized = Vec::from_iter(
+        consistency
+            .distances
+            .iter()
+            .map(|row| Vec::from_iter(row.iter().map(|(normalized, _)| *normalized))),
+    );
+    let summary = consistency.summary;
+    Ok((
+        PyArray2::from_vec2_bound(py, &normalized)?,
+        (
+            summary.mean,
+            summary.standard_deviation,
+            summary.min,
+            summary.max,
+            summary.asymmetry,
+        ),
+    ))
+}
+
+/// Hierarchical clustering of graphs into `n_clusters` clusters based on the float64 matrix of their
+/// pairwise `distances`, such as returned by `cross_consistency`, taking the mean of the distances in
+/// both directions and the `linkage` ("single", "complete", or "average") between clusters.
+/// Returns a tuple `(assignments, medoids)` of the cluster of each graph, numbered in the order of
+/// the first graph of each cluster, and the index of the medoid graph of each cluster
+#[pyfunction]
+#[pyo3(signature = (distances, n_clusters, linkage="average"))]
+pub fn cluster_graphs(
+    distances: PyReadonlyArray2<'_, f64>,
+    n_clusters: usize,
+    linkage: &str,
+) -> PyResult<(Vec<usize>, Vec<usize>)> {
+    let linkage = Linkage::parse(linkage).map_err(raise_option_error)?;
+    let distances = Vec::from_iter(distances.as_array().outer_iter().map(|row| row.to_vec()));
+    let clustering =
+        rust_cluster_graphs(&distances, n_clusters, linkage).map_err(raise_ensemble_error)?;
+    Ok((clustering.assignments, clustering.medoids))
+}
+
+/// Null distribution of the `distance` ("ancestor_aid", "oset_aid", "parent_aid", or "shd") from the true
+/// DAG / CPDAG to `n_samples` random graphs with the same edge density as the guess DAG / CPDAG,
+/// which are DAGs if the guess has no undirected edges, and PDAGs otherwise, sampled with the given `seed`.
+/// Returns a tuple `(samples, percentile)` of the float64 array of the normalized distances of the random
+/// graphs in ascending order and the percentile of the normalized distance of the guess in it,
+/// the percentage of random graphs that are at most as far from the true graph
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, distance, edge_direction, n_samples=1000, seed=0))]
+pub fn null_distribution<'py>(
+    py: Python<'py>,
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+    n_samples: usize,
+    seed: u64,
+) -> PyResult<(Bound<'py, PyArray1<f64>>, f64)> {
+    let distance = distance_from_name(distance)?;
+    if n_samples == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "n_samples must be at least 1",
+        ));
+    }
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let (null, percentile) = py.allow_threads(|| {
+        let null = rust_null_distribution(
+            &graph_truth,
+            n_samples,
+            &RandomGraphs::like(&graph_guess),
+            distance,
+            ChaCha8Rng::seed_from_u64(seed),
+        );
+        let percentile = null.percentile(distance(&graph_truth, &graph_guess).0);
+        (null, percentile)
+    });
+    Ok((PyArray1::from_vec_bound(py, null.samples), percentile))
+}
+
+/// Robustness curve of the `distance` ("ancestor_aid", "oset_aid", "parent_aid", or "shd") between two
+/// DAG / CPDAG adjacency matrices (sparse or dense) to dropping nodes: for each of the `fractions` of nodes,
+/// drops `n_repetitions` random subsets of that size from both graphs, sampled with the given `seed`,
+/// and recomputes the distance between the remaining subgraphs. Returns a list of tuples
+/// `(fraction, n_dropped, mean, standard_deviation)` of the normalized distances, one per fraction,
+/// or, if `tidy`, a dict of the columns "distance", "fraction", "n_dropped", "mean", and "standard_deviation"
+/// of a long-format table
+#[pyfunction]
+#[pyo3(signature = (g_true, g_guess, distance, edge_direction, fractions, n_repetitions=100, seed=0, tidy=false))]
+#[allow(clippy::too_many_arguments)]
+pub fn node_dropout_curve(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+    fractions: Vec<f64>,
+    n_repetitions: usize,
+    seed: u64,
+    tidy: bool,
+) -> PyResult<PyObject> {
+    let name = Distance::parse(distance).map_err(raise_option_error)?;
+    let distance = distance_from_name(distance)?;
+    if n_repetitions == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "n_repetitions must be at least 1",
+        ));
+    }
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let n = graph_truth.n_nodes;
+    if let Some(fraction) = fractions
+        .iter()
+        .find(|f| !(0.0..=1.0).contains(*f) || (**f * n as f64).round() as usize + 2 > n)
+    {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "Dropout fractions must be in [0, 1] and leave at least 2 of the {n} nodes, but one is {fraction}"
+        )));
+    }
+    let curve = g_true.py().allow_threads(|| {
+        rust_node_dropout_curve(
+            &graph_truth,
+            &graph_guess,
+            &fractions,
+            n_repetitions,
+            distance,
+            ChaCha8Rng::seed_from_u64(seed),
+        )
+    });
+    let py = g_true.py();
+    if tidy {
+        return table_to_pydict(py, dropout_curve_table(name, &curve));
+    }
+    let points = Vec::from_iter(curve.into_iter().map(|point| {
+        (
+            point.fraction,
+            point.n_dropped,
+            point.mean,
+            point.standard_deviation,
+        )
+    }));
+    Ok(points.into_py(py))
+}
+
+/// Random configuration of disjoint treatment nodes, effect node, and adjustment set among `n_nodes` nodes,
+/// sampled with the given `seed` the way the snapshot tests of gadjid do. The sets have `n_treatments` and
+/// `adjustment_set_size` nodes or, if these are None, a number of nodes drawn uniformly from 1 to as many as
+/// may be used. Returns a tuple of (sorted list of treatments, effect, sorted list of adjustment set nodes)
+#[pyfunction]
+#[pyo3(signature = (n_nodes, n_treatments=None, adjustment_set_size=None, seed=0))]
+pub fn sample_configuration(
+    n_nodes: usize,
+    n_treatments: Option<usize>,
+    adjustment_set_size: Option<usize>,
+    seed: u64,
+) -> PyResult<(Vec<usize>, usize, Vec<usize>)> {
+    // sets of random size take at least one node each
+    let (t_size, z_size) = (n_treatments.unwrap_or(1), adjustment_set_size.unwrap_or(1));
+    if t_size + z_size >= n_nodes {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "{n_nodes} nodes are too few for the treatments, the effect, and the adjustment set"
+        )));
+    }
+    let size = |size: Option<usize>| size.map_or(SetSize::Uniform, SetSize::Exactly);
+    let configuration = rust_sample_configuration(
+        n_nodes,
+        size(n_treatments),
+        size(adjustment_set_size),
+        &mut ChaCha8Rng::seed_from_u64(seed),
+    );
+    Ok((
+        configuration.treatments,
+        configuration.effect,
+        configuration.adjustment_set,
+    ))
+}
+
+/// Random ground-truth DAG of a structural causal model with `n_variables` variables, whose parents are
+/// sampled with the given `seed` from the `model`: for `model="erdos_renyi"`, every variable is a parent of
+/// every later variable in a random causal order with probability `edge_density`; for `model="scale_free"`,
+/// every variable draws `parents_per_node` parents among the earlier variables by preferential attachment.
+/// Returns the int8 adjacency matrix of the DAG, coded in the given `edge_direction`
+#[pyfunction]
+#[pyo3(signature = (n_variables, model, edge_direction, edge_density=0.3, parents_per_node=2, seed=0))]
+pub fn sample_scm_dag<'py>(
+    py: Python<'py>,
+    n_variables: usize,
+    model: &str,
+    edge_direction: &str,
+    edge_density: f64,
+    parents_per_node: usize,
+    seed: u64,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let model = ParentModelKind::parse(model)
+        .map_err(raise_option_error)?
+        .with_parameters(edge_density, parents_per_node);
+    if n_variables == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "n_variables must be at least 1",
+        ));
+    }
+    if !(0.0..=1.0).contains(&edge_density) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "edge_density must be in [0, 1]",
+        ));
+    }
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let scm = Scm::sample(n_variables, model, &mut ChaCha8Rng::seed_from_u64(seed));
+    adjacency_to_pyarray(py, &scm.to_dag(), row_to_col)
+}
+
+/// Expected structural hamming distance between the true DAG / CPDAG and a random graph with independent
+/// edges of the given `edge_density`, which is a DAG for `random_graphs="dag"` and a PDAG for
+/// `random_graphs="pdag"`, of which 80% of the edges are directed.
+/// Returns a tuple of (expected normalized distance (in [0,1]), expected number of errors)
+#[pyfunction]
+#[pyo3(signature = (g_true, edge_density, edge_direction, random_graphs="dag"))]
+pub fn expected_random_shd(
+    g_true: &Bound<'_, PyAny>,
+    edge_density: f64,
+    edge_direction: &str,
+    random_graphs: &str,
+) -> PyResult<(f64, f64)> {
+    let random_graphs = RandomGraphKind::parse(random_graphs)
+        .map_err(raise_option_error)?
+        .with_edge_density(edge_density);
+    if !(0.0..=1.0).contains(&edge_density) {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "edge_density must be in [0, 1]",
+        ));
+    }
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
+    Ok(rust_expected_random_shd(&graph_truth, &random_graphs))
+}
+
+/// The `distance` ("ancestor_aid", "oset_aid", "parent_aid", or "shd") between two DAG / CPDAG adjacency
+/// matrices (sparse or dense), computed separately on each connected component shared by both graphs.
+/// Returns a tuple `((normalized error, number of errors), components)` of the distance between the whole
+/// graphs and a list of tuples `(nodes, normalized error, number of errors)` for each component
+/// with at least two nodes
+#[pyfunction]
+pub fn distance_by_components(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<ComponentsBreakdown> {
+    let distance = distance_from_name(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let (whole, components) = g_true
+        .py()
+        .allow_threads(|| rust_distance_by_components(&graph_truth, &graph_guess, distance));
+    Ok((
+        whole,
+        Vec::from_iter(components.into_iter().map(|component| {
+            let (normalized, mistakes) = component.distance;
+            (component.nodes, normalized, mistakes)
+        })),
+    ))
+}
+
+/// The distance between the whole graphs and the nodes and distance of each component.
+type ComponentsBreakdown = ((f64, usize), Vec<(Vec<usize>, f64, usize)>);
+
+/// The edge `from -> to`, whether it is directed, and the change in mistakes if it were removed or reversed.
+type Influence = (usize, usize, bool, isize, Option<isize>);
+
+/// For each edge of the guess, the change in the number of mistakes of the `distance` ("ancestor_aid",
+/// "oset_aid", "parent_aid", or "shd") between two DAG / CPDAG adjacency matrices (sparse or dense)
+/// if the edge were removed or reversed. Returns a list of tuples `(from, to, directed, removed, reversed)`,
+/// where `reversed` is None for undirected edges and edges whose reversal creates a cycle,
+/// ranked such that the edges whose change corrects the most mistakes come first
+#[pyfunction]
+pub fn edge_influence(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<Vec<Influence>> {
+    let distance = distance_from_name(distance)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let influences = g_true
+        .py()
+        .allow_threads(|| rust_edge_influence(&graph_truth, &graph_guess, distance));
+    Ok(Vec::from_iter(influences.into_iter().map(|influence| {
+        (
+            influence.from,
+            influence.to,
+            influence.directed,
+            influence.removed,
+            influence.reversed,
+        )
+    })))
+}
+
+/// Parent Adjustment Identification Distance between a true DAG / CPDAG adjacency matrix (sparse or dense)
+/// and each snapshot of a guess that starts out as `g_initial` and changes by each of the `operations`,
+/// a list of `(operation, from, to)` tuples with operation "insert", "delete", or "reverse" of the edge
+/// `from -> to`, computed incrementally. Returns a list of tuples (normalized error, number of errors)
+/// for the initial guess and the snapshot after each operation, or, if `tidy`, a dict of the columns
+/// "distance", "step", "normalized", and "mistakes" of a long-format table
+#[pyfunction]
+#[pyo3(signature = (g_true, g_initial, operations, edge_direction, tidy=false))]
+pub fn trajectory_aid(
+    g_true: &Bound<'_, PyAny>,
+    g_initial: &Bound<'_, PyAny>,
+    operations: Vec<(String, usize, usize)>,
+    edge_direction: &str,
+    tidy: bool,
+) -> PyResult<PyObject> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let operations = operations
+        .iter()
+        .map(|(operation, from, to)| Ok((EdgeOperation::parse(operation)?, *from, *to)))
+        .collect::<Result<Vec<_>, OptionError>>()
+        .map_err(raise_option_error)?;
+    let (graph_truth, graph_initial) = graphs_from_pyobjects(g_true, g_initial, row_to_col)?;
+    let py = g_true.py();
+    let trajectory = py
+        .allow_threads(|| rust_trajectory_aid(&graph_truth, &graph_initial, &operations))
+        .map_err(raise_trajectory_error)?;
+    if tidy {
+        return table_to_pydict(py, trajectory_table(Distance::ParentAid, &trajectory));
+    }
+    Ok(trajectory.into_py(py))
+}
+
+/// Sequence of single-edge operations that transforms the guess DAG adjacency matrix `g_guess`
+/// (sparse or dense) into the true DAG `g_true`, fixing one differing pair of nodes per step,
+/// so that the SHD decreases by one per step, and greedily picking the operation with the lowest Parent-AID.
+/// Returns a tuple `(operations, shd, parent_aid)` of the list of `(operation, from, to)` tuples with
+/// operation "insert", "delete", or "reverse" of the edge `from -> to`, and the lists of the SHD and the
+/// tuples (normalized error, number of errors) of the Parent-AID for `g_guess` and after each operation
+#[pyfunction]
+pub fn repair_path(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+) -> PyResult<RepairPathTuple> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let path = g_true
+        .py()
+        .allow_threads(|| rust_repair_path(&graph_truth, &graph_guess))
+        .map_err(|err| GadjidError::new_err(err.to_string()))?;
+    let operations = path
+        .operations
+        .into_iter()
+        .map(|(operation, from, to)| (operation.name(), from, to));
+    Ok((Vec::from_iter(operations), path.shd, path.parent_aid))
+}
+
+/// The operations of a repair path alongside the SHD and the Parent-AID before and after each of them.
+type RepairPathTuple = (
+    Vec<(&'static str, usize, usize)>,
+    Vec<usize>,
+    Vec<(f64, usize)>,
+);
+
+/// Raises a ValueError for operations that do not apply,
+/// and the exception class of the loading error for snapshots that are not PDAGs.
+fn raise_trajectory_error(err: TrajectoryError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        TrajectoryError::Inapplicable { .. } => {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(message)
+        }
+        TrajectoryError::Load { err, .. } => load_error_to_pyerr(err, message),
+    }
+}
+
+/// Report which coding conventions a dense int8 adjacency matrix is consistent with,
+/// any of "gadjid", "pcalg amat.cpdag", "pcalg amat.pag", and "symmetric bool",
+/// alongside warnings about irregularities such as undirected edges coded on one side only.
+/// Returns a tuple `(consistent_conventions, warnings)` of two lists of strings.
+#[pyfunction]
+pub fn detect_convention(matrix: &Bound<'_, PyAny>) -> PyResult<(Vec<String>, Vec<String>)> {
+    let matrix = vecvec_from_dense(matrix)?;
+    let report = ::gadjid::detect_convention(&matrix);
+    Ok((
+        report.consistent.iter().map(|c| c.to_string()).collect(),
+        report.warnings.iter().map(|w| w.to_string()).collect(),
+    ))
+}
+
+/// Load a dense int8 adjacency matrix coded in the given `convention`, any of "gadjid",
+/// "pcalg amat.cpdag", "pcalg amat.pag", and "symmetric bool", or, with `convention="auto"`,
+/// detect the convention as `detect_convention` does and proceed if all conventions the matrix is
+/// consistent with code the same graph, warning about the irregularities noticed.
+/// The `edge_direction` is used to read matrices in gadjid coding and to code the returned matrix.
+/// Returns the int8 adjacency matrix of the loaded graph in gadjid coding, in the given `edge_direction`,
+/// with undirected edges coded by a `2` on both sides, which can be passed to the distances
+#[pyfunction]
+#[pyo3(signature = (matrix, edge_direction, convention="auto"))]
+pub fn load_with_convention<'py>(
+    matrix: &Bound<'py, PyAny>,
+    edge_direction: &str,
+    convention: &str,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let convention = match convention {
+        "auto" => None,
+        other => Some(Convention::parse(other).map_err(raise_option_error)?),
+    };
+    let py = matrix.py();
+    let matrix = vecvec_from_dense(matrix)?;
+    let graph = match convention {
+        Some(convention) => rust_load_with_convention(&matrix, convention, row_to_col)
+            .map_err(raise_convention_error)?,
+        None => {
+            let (graph, report) = rust_load_with_detected_convention(&matrix, row_to_col)
+                .map_err(raise_convention_error)?;
+            for warning in &report.warnings {
+                PyErr::warn_bound(
+                    py,
+                    &py.get_type_bound::<PyUserWarning>(),
+                    &warning.to_string(),
+                    1,
+                )?;
+            }
+            graph
+        }
+    };
+    adjacency_to_pyarray(py, &graph, row_to_col)
+}
+
+/// Raises the exception class of the loading error for coded graphs that are not PDAGs,
+/// and a ValueError for matrices that are not consistent with a (unique) convention.
+fn raise_convention_error(err: ConventionError) -> PyErr {
+    let message = err.to_string();
+    match err {
+        ConventionError::Load(err) => load_error_to_pyerr(err, message),
+        _ => PyErr::new::<pyo3::exceptions::PyValueError, _>(message),
+    }
+}
+
+/// Fix the irregularities of a dense adjacency matrix coded in the given `convention`, any of
+/// "gadjid", "pcalg amat.cpdag", "pcalg amat.pag", and "symmetric bool", that have an unambiguous fix:
+/// casts a boolean, integer, or float matrix with integer values in the range of int8 to int8,
+/// sets nonzero entries on the diagonal to 0, and, in "gadjid" and "symmetric bool" coding,
+/// completes undirected edges that are coded on one side only. Warns about every change made.
+/// Returns a tuple `(matrix, changes)` of the fixed int8 matrix and a list of strings describing the changes.
+#[pyfunction]
+pub fn sanitize_adjacency<'py>(
+    matrix: &Bound<'py, PyAny>,
+    convention: &str,
+) -> PyResult<(Bound<'py, PyArray2<i8>>, Vec<String>)> {
+    let convention = Convention::parse(convention).map_err(raise_option_error)?;
+    let py = matrix.py();
+    let mut changes = Vec::new();
+    let (matrix, cast_from) = cast_to_int8(matrix)?;
+    if let Some(dtype) = cast_from {
+        changes.push(format!("cast matrix of dtype {dtype} to int8"));
+    }
+    let sanitized = ::gadjid::sanitize_adjacency(&vecvec_from_dense(&matrix)?, convention)
+        .map_err(|err| SizeMismatchError::new_err(err.to_string()))?;
+    changes.extend(sanitized.fixes.iter().map(|fix| fix.to_string()));
+    for change in &changes {
+        PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), change, 1)?;
+    }
+    Ok((PyArray2::from_vec2_bound(py, &sanitized.matrix)?, changes))
+}
+
+/// Resolve the missing entries of a dense adjacency matrix in gadjid coding, whose edge status is unknown:
+/// the NaN entries of a float matrix and, if given, the entries equal to `missing_code`.
+/// With `missing="error"`, a missing entry raises a ValueError, with "absent", it codes no edge,
+/// and with "undirected", a pair of nodes with a missing entry is joined by an undirected edge;
+/// missing entries on the diagonal become 0. Warns about the number of resolved entries.
+/// Returns a tuple `(matrix, n_missing, n_affected_pairs)` of the resolved int8 matrix, the number of
+/// missing entries, and the number of unordered pairs of distinct nodes with at least one missing entry
+#[pyfunction]
+#[pyo3(signature = (matrix, missing, missing_code=None))]
+pub fn resolve_missing<'py>(
+    matrix: &Bound<'py, PyAny>,
+    missing: &str,
+    missing_code: Option<f64>,
+) -> PyResult<(Bound<'py, PyArray2<i8>>, usize, usize)> {
+    let policy = MissingPolicy::parse(missing).map_err(raise_option_error)?;
+    let py = matrix.py();
+    let numpy = PyModule::import_bound(py, "numpy")?;
+    let mut is_missing = numpy.call_method1("isnan", (matrix,))?;
+    if let Some(code) = missing_code {
+        let is_code = numpy.call_method1("equal", (matrix, code))?;
+        is_missing = numpy.call_method1("logical_or", (is_missing, is_code))?;
+    }
+    let filled = numpy.call_method1("where", (&is_missing, 0, matrix))?;
+    let (filled, _) = cast_to_int8(&filled)?;
+    let is_missing = is_missing.extract::<PyReadonlyArray2<bool>>()?;
+    let entries = vecvec_from_dense(&filled)?
+        .into_iter()
+        .zip(is_missing.as_array().outer_iter())
+        .map(|(values, is_missing)| {
+            Vec::from_iter(
+                values
+                    .into_iter()
+                    .zip(is_missing)
+                    .map(|(value, is_missing)| (!is_missing).then_some(value)),
+            )
+        })
+        .collect::<Vec<_>>();
+    let resolved = rust_resolve_missing(&entries, policy).map_err(|err| match err {
+        MissingError::NotSquare { .. } => SizeMismatchError::new_err(err.to_string()),
+        _ => PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+    })?;
+    if resolved.n_missing > 0 {
+        let message = format!(
+            "resolved {} missing entries of {} pairs of nodes as {missing}",
+            resolved.n_missing, resolved.n_affected_pairs
+        );
+        PyErr::warn_bound(py, &py.get_type_bound::<PyUserWarning>(), &message, 1)?;
+    }
+    Ok((
+        PyArray2::from_vec2_bound(py, &resolved.matrix)?,
+        resolved.n_missing,
+        resolved.n_affected_pairs,
+    ))
+}
+
+/// Cast a boolean, integer, or float matrix with integer values in the range of int8 to int8.
+/// Returns the int8 matrix alongside the dtype it was cast from, if it was not int8,
+/// or raises a ValueError if the cast would change a value.
+fn cast_to_int8<'py>(matrix: &Bound<'py, PyAny>) -> PyResult<(Bound<'py, PyAny>, Option<String>)> {
+    let dtype = matrix.getattr("dtype")?.str()?.to_string();
+    if dtype == "int8" {
+        return Ok((matrix.clone(), None));
+    }
+    let cast = matrix.call_method1("astype", ("int8",))?;
+    let lossless = cast
+        .call_method1("astype", (dtype.as_str(),))?
+        .rich_compare(matrix, pyo3::basic::CompareOp::Eq)?
+        .call_method0("all")?
+        .is_truthy()?;
+    if !lossless {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "matrix of dtype {dtype} has values that are not integers in the range of int8"
+        )));
+    }
+    Ok((cast, Some(dtype)))
+}
+
+/// Raises an OSError if a bundle file could not be accessed, and a ValueError otherwise.
+fn raise_bundle_error(err: BundleError) -> PyErr {
+    match err {
+        BundleError::Io(_) => PyErr::new::<pyo3::exceptions::PyOSError, _>(err.to_string()),
+        _ => PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+    }
+}
+
+/// Write a reproducible evaluation bundle of two DAG / CPDAG adjacency matrices (sparse or dense)
+/// and the reported `results` (a list of `(name, (normalized_distance, mistake_count))` tuples for the
+/// distances "ancestor_aid", "oset_aid", "parent_aid", and "shd") as a tar archive to the file `path`,
+/// which holds the graphs as Matrix Market files, the `edge_direction`, the results,
+/// and the fingerprints of the graphs as JSON
+#[pyfunction]
+pub fn export_bundle<'py>(
+    path: &str,
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    edge_direction: &str,
+    results: Vec<(String, (f64, usize))>,
+) -> PyResult<()> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let mut reported = Vec::with_capacity(results.len());
+    for (name, result) in results {
+        let distance = Distance::parse(&name).map_err(raise_option_error)?;
+        reported.push((distance, result));
+    }
+    let options = [(EdgeDirection::OPTION, edge_direction)];
+    rust_export_bundle(path, &graph_truth, &graph_guess, &options, &reported)
+        .map_err(raise_bundle_error)
+}
+
+/// Verify the evaluation bundle in the file `path`, as written by `export_bundle`,
+/// by checking its format version and the fingerprints of its graphs and recomputing its results.
+/// Returns a tuple `(exported_with, options, mismatches)` of the gadjid version that exported the bundle,
+/// a dict of the options of the evaluation, such as the `edge_direction`,
+/// and a list of `(name, reported, recomputed)` tuples for the results that are not reproduced
+#[pyfunction]
+pub fn verify_bundle(py: Python<'_>, path: &str) -> PyResult<BundleVerification> {
+    let verification = py
+        .allow_threads(|| rust_verify_bundle(path))
+        .map_err(raise_bundle_error)?;
+    Ok((
+        verification.exported_with,
+        HashMap::from_iter(verification.options),
+        Vec::from_iter(verification.mismatches.into_iter().map(|mismatch| {
+            (
+                mismatch.distance.name().to_string(),
+                mismatch.reported,
+                mismatch.recomputed,
+            )
+        })),
+    ))
+}
+
+/// The name of a distance alongside its reported and recomputed `(normalized_distance, mistake_count)`.
+type BundleMismatch = (String, (f64, usize), (f64, usize));
+
+/// The exporting gadjid version, the options, and the mismatches of a verified bundle.
+type BundleVerification = (String, HashMap<String, String>, Vec<BundleMismatch>);
+
+/// Compute the `distance` (one of "ancestor_aid", "oset_aid", "parent_aid", and "shd")
+/// between two DAG / CPDAG adjacency matrices (sparse or dense) and return it as a `DistanceResult`
+/// that records the gadjid version, the options, and the fingerprints of the graphs,
+/// so that the number can be traced back to how it was computed
+#[pyfunction]
+pub fn distance_with_provenance<'py>(
+    g_true: &Bound<'py, PyAny>,
+    g_guess: &Bound<'py, PyAny>,
+    distance: &str,
+    edge_direction: &str,
+) -> PyResult<DistanceResult> {
+    let distance = Distance::parse(distance).map_err(raise_option_error)?;
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let options = [("edge_direction", edge_direction)];
+    let result = g_true.py().allow_threads(|| {
+        RustDistanceResult::compute(distance, &graph_truth, &graph_guess, &options)
+    });
+    Ok(result.into())
+}
+
+/// Raises a ValueError if a benchmark file is malformed, and the load error if its graph cannot be loaded.
+fn raise_benchmark_error(err: BenchmarkError) -> PyErr {
+    match err {
+        BenchmarkError::Load(err) => raise_load_error(err),
+        _ => PyErr::new::<pyo3::exceptions::PyValueError, _>(err.to_string()),
+    }
+}
+
+/// Reads the file at `path`, raising an OSError if it cannot be read.
+fn read_file(path: &str) -> PyResult<String> {
+    std::fs::read_to_string(path)
+        .map_err(|err| PyErr::new::<pyo3::exceptions::PyOSError, _>(format!("{path}: {err}")))
+}
+
+/// Read a Benchpress adjacency matrix CSV file at `path`, with a header of node names and a `1` in row `i`
+/// and column `j` for an edge `i → j`, where an edge coded in both directions is undirected.
+/// Returns a tuple `(names, adjacency)` of the node names and the int8 adjacency matrix,
+/// coded in the given `edge_direction`
+#[pyfunction]
+pub fn read_benchpress_csv<'py>(
+    py: Python<'py>,
+    path: &str,
+    edge_direction: &str,
+) -> PyResult<(Vec<String>, Bound<'py, PyArray2<i8>>)> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (names, graph) =
+        rust_read_benchpress_csv(&read_file(path)?).map_err(raise_benchmark_error)?;
+    Ok((names, adjacency_to_pyarray(py, &graph, row_to_col)?))
+}
+
+/// Read a CauseMe score matrix CSV file at `path`, without header, whose entry in row `i` and column `j`
+/// is the confidence in a link `i → j`, keeping the links whose score is at least `threshold`,
+/// where a pair of nodes linked in both directions is adjacent by an undirected edge; the diagonal is ignored.
+/// Returns the int8 adjacency matrix, coded in the given `edge_direction`
+#[pyfunction]
+pub fn read_score_matrix_csv<'py>(
+    py: Python<'py>,
+    path: &str,
+    threshold: f64,
+    edge_direction: &str,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph =
+        rust_read_score_matrix_csv(&read_file(path)?, threshold).map_err(raise_benchmark_error)?;
+    adjacency_to_pyarray(py, &graph, row_to_col)
+}
+
+/// Compute the `distances` (a list of names, any of "ancestor_aid", "oset_aid", "parent_aid", and "shd")
+/// between a true DAG / CPDAG adjacency matrix (sparse or dense) and each of the named `guesses`
+/// (a list of `(id, adjacency matrix)` tuples).
+/// Returns a CSV table with the columns `id`, `distance`, `normalized`, and `mistakes`
+/// and one row per guess and distance, which merges into the results of benchmark suites
+#[pyfunction]
+pub fn metrics_table_csv<'py>(
+    g_true: &Bound<'py, PyAny>,
+    guesses: Vec<(String, Bound<'py, PyAny>)>,
+    distances: Vec<String>,
+    edge_direction: &str,
+) -> PyResult<String> {
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let graph_truth = graph_from_pyobject(g_true, row_to_col)?;
+    let mut graphs = Vec::with_capacity(guesses.len());
+    for (id, guess) in &guesses {
+        let graph_guess = graph_from_pyobject(guess, row_to_col)?;
+        if graph_guess.n_nodes != graph_truth.n_nodes {
+            return Err(SizeMismatchError::new_err(format!(
+                "The true graph has {} nodes, but the guess graph {id} has {} nodes",
+                graph_truth.n_nodes, graph_guess.n_nodes
+            )));
+        }
+        graphs.push((id.as_str(), graph_guess));
+    }
+    let distances = distances
+        .iter()
+        .map(|name| Distance::parse(name).map_err(raise_option_error))
+        .collect::<PyResult<Vec<_>>>()?;
+    let guesses = Vec::from_iter(graphs.iter().map(|(id, graph)| (*id, graph)));
+    Ok(g_true
+        .py()
+        .allow_threads(|| rust_metrics_table_csv(&graph_truth, &guesses, &distances)))
+}
+
+/// Load the true and the guess graph, which must have the same number of nodes.
+fn graphs_from_pyobjects(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    is_row_to_col: bool,
+) -> PyResult<(PDAG, PDAG)> {
+    let graph_truth = graph_from_pyobject(g_true, is_row_to_col)?;
+    let graph_guess = graph_from_pyobject(g_guess, is_row_to_col)?;
+    if graph_truth.n_nodes != graph_guess.n_nodes {
+        return Err(SizeMismatchError::new_err(format!(
+            "The true graph has {} nodes, but the guess graph has {} nodes",
+            graph_truth.n_nodes, graph_guess.n_nodes
+        )));
+    }
+    Ok((graph_truth, graph_guess))
+}
+
+/// Estimate a distance from a sample of treatments, as computed by `aid_approx`.
+fn approximate_distance(
+    g_true: &Bound<'_, PyAny>,
+    g_guess: &Bound<'_, PyAny>,
+    edge_direction: &str,
+    sample_size: usize,
+    (n_strata, n_effects): (Option<usize>, Option<usize>),
+    seed: u64,
+    aid: AidDistance,
+) -> PyResult<(f64, f64, (f64, f64))> {
+    let sampling = match (n_strata, n_effects) {
+        (_, _) if sample_size < 2 || n_strata == Some(0) || n_effects == Some(0) => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "sample_size must be at least 2, and n_strata and n_effects at least 1",
+            ))
+        }
+        (Some(_), Some(_)) => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "at most one of n_strata and n_effects can be given",
+            ))
+        }
+        (Some(n_strata), None) => TreatmentSampling::ByDegree { n_strata },
+        (None, Some(n_effects)) => TreatmentSampling::StratifiedPairs { n_effects },
+        (None, None) => TreatmentSampling::Uniform,
+    };
+    let row_to_col = edge_direction_is_row_to_col(edge_direction)?;
+    let (graph_truth, graph_guess) = graphs_from_pyobjects(g_true, g_guess, row_to_col)?;
+    let approx = aid_with(
+        g_true.py(),
+        aid,
+        &graph_truth,
+        &graph_guess,
+        Approximate {
+            sample_size,
+            sampling: &sampling,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        },
+    );
+    Ok((
+        approx.estimate,
+        approx.standard_error,
+        approx.confidence_interval,
+    ))
+}
+
+/// A distance between a true and a guess graph, returning (normalized error, total number of errors).
+type DistanceFn = fn(&PDAG, &PDAG) -> (f64, usize);
+
+/// Select the distance by its name, one of "ancestor_aid", "oset_aid", "parent_aid", or "shd".
+fn distance_from_name(distance: &str) -> PyResult<DistanceFn> {
+    match Distance::parse(distance).map_err(raise_option_error)? {
+        Distance::AncestorAid => Ok(rust_ancestor_aid),
+        Distance::OsetAid => Ok(rust_oset_aid),
+        Distance::ParentAid => Ok(rust_parent_aid),
+        Distance::Shd => Ok(rust_shd),
+    }
+}
+
+/// Return a long-format table as a dict from the column names to the columns, in order,
+/// with numbers as ndarrays and text as lists of strings, which pandas and polars turn into data frames.
+fn table_to_pydict(py: Python<'_>, table: LongTable) -> PyResult<PyObject> {
+    let dict = PyDict::new_bound(py);
+    for (name, values) in table.columns {
+        match values {
+            Column::Text(values) => dict.set_item(name, values)?,
+            Column::Integer(values) => dict.set_item(
+                name,
+                PyArray1::from_vec_bound(py, Vec::from_iter(values.into_iter().map(|v| v as u64))),
+            )?,
+            Column::Float(values) => dict.set_item(name, PyArray1::from_vec_bound(py, values))?,
+        }
+    }
+    Ok(dict.into_any().unbind())
+}
+
+/// Return the int8 adjacency matrix of a graph, coded in the given edge direction,
+/// with undirected edges coded by a `2` on both sides.
+fn adjacency_to_pyarray<'py>(
+    py: Python<'py>,
+    graph: &PDAG,
+    is_row_to_col: bool,
+) -> PyResult<Bound<'py, PyArray2<i8>>> {
+    let mut adjacency = graph.to_row_to_column_vecvec();
+    if !is_row_to_col {
+        adjacency = Vec::from_iter(
+            (0..adjacency.len()).map(|col| Vec::from_iter(adjacency.iter().map(|row| row[col]))),
+        );
+    }
+    Ok(PyArray2::from_vec2_bound(py, &adjacency)?)
+}
+
+/// Raises the exception class that corresponds to the reason an ensemble could not be used.
+fn raise_ensemble_error(err: EnsembleError) -> PyErr {
+    match err {
+        EnsembleError::Load(err) => raise_load_error(err),
+        EnsembleError::SizeMismatch { .. } | EnsembleError::NotSquare { .. } => {
+            SizeMismatchError::new_err(err.to_string())
+        }
+        EnsembleError::Pairs(_) => InvalidPairsError::new_err(err.to_string()),
+        EnsembleError::Empty
+        | EnsembleError::InvalidThreshold(_)
+        | EnsembleError::TooFewGraphs { .. }
+        | EnsembleError::InvalidClusterCount { .. } => GadjidError::new_err(err.to_string()),
+    }
+}
+
+/// Load a graph from a 2D numpy or scipy sparse matrix, or from an `EdgeList`.
+/// Will load a matrix into a PDAG, automatically loading into a DAG and checking
+/// acyclicity. If undirected edges present, assumes that it encodes as valid CPDAG
+fn graph_from_pyobject(ob: &Bound<'_, PyAny>, is_row_to_col: bool) -> PyResult<PDAG> {
+    let graph = if let Ok(edge_list) = ob.downcast::<EdgeList>() {
+        edge_list.get().to_pdag()?
+    }
+    // scipy sparse matrices have a format, numpy ndarrays do not
+    else if ob.hasattr("format")? {
+        try_from_sparse(ob, is_row_to_col)?
+    } else {
+        try_from_dense(ob, is_row_to_col)?
+    };
+    // the distances are normalized by the number of pairs of distinct nodes
+    if graph.n_nodes < 2 {
+        return Err(SizeMismatchError::new_err(format!(
+            "Graph must contain at least 2 nodes, but has {}",
+            graph.n_nodes
+        )));
+    }
+    Ok(graph)
+}
+
+/// Raises the exception class that corresponds to the reason loading failed.
+fn raise_load_error(err: LoadError) -> PyErr {
+    let message = err.to_string();
+    load_error_to_pyerr(err, message)
+}
+
+/// Raises the exception class that corresponds to the reason loading failed with the given message.
+pub(crate) fn load_error_to_pyerr(err: LoadError, message: String) -> PyErr {
+    match err {
+        LoadError::NotAcyclic => NotAcyclicError::new_err(message),
+        LoadError::NotSimple { .. } | LoadError::SelfLoop { .. } => {
+            NotSimpleGraphError::new_err(message)
+        }
+        LoadError::InvalidEdgeValue { .. } => InvalidEdgeValueError::new_err(message),
+        LoadError::IndexOutOfBounds { .. }
+        | LoadError::TooManyNodes { .. }
+        | LoadError::NotSquare { .. } => SizeMismatchError::new_err(message),
+        LoadError::NotOrdered { .. } => GadjidError::new_err(format!(
+            "{message}; sparse matrices must have sorted indices without duplicates, as after scipy's sum_duplicates()"
+        )),
+    }
+}
+
+/// Helper to avoid repetition, used by the numpy and scipy sparse loading files.
+/// Will load an edgelist iterator into a PDAG, automatically loading into a DAG and checking
+/// acyclicity. If undirected edges present, assumes that it encodes as valid CPDAG
+pub(crate) fn graph_from_iterator(
+    iterator: impl Iterator<Item = (usize, usize, i8)>,
+    row_to_col: bool,
+    graph_size: usize,
+) -> PyResult<PDAG> {
+    match row_to_col {
+        true => PDAG::try_from_row_major(EdgelistIterator::into_row_major_edgelist(
+            iterator, graph_size,
+        )),
+        // we have a col-to-row matrix
+        false => PDAG::try_from_col_major(EdgelistIterator::into_column_major_edgelist(
+            iterator, graph_size,
+        )),
     }
+    .map_err(raise_load_error)
 }
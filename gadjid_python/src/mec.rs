@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use gadjid::graph_operations::MecSid as RustMecSid;
+use numpy::PyArray2;
+use pyo3::prelude::*;
+
+/// The minimum Structural Hamming Distance between a true DAG and the DAGs in the Markov equivalence class
+/// of a guess CPDAG, returned by `min_shd_over_mec`, together with a closest DAG in the class.
+#[pyclass(module = "gadjid", frozen)]
+pub struct MecShd {
+    normalized: f64,
+    mistakes: usize,
+    exact: bool,
+    dag: Py<PyArray2<i8>>,
+}
+
+impl MecShd {
+    pub fn new(
+        normalized: f64,
+        mistakes: usize,
+        exact: bool,
+        dag: Bound<'_, PyArray2<i8>>,
+    ) -> Self {
+        MecShd {
+            normalized,
+            mistakes,
+            exact,
+            dag: dag.unbind(),
+        }
+    }
+}
+
+#[pymethods]
+impl MecShd {
+    /// The normalized error, in [0,1]
+    #[getter]
+    fn normalized(&self) -> f64 {
+        self.normalized
+    }
+
+    /// The total number of errors, the SHD between the true DAG and `dag`
+    #[getter]
+    fn mistakes(&self) -> usize {
+        self.mistakes
+    }
+
+    /// Whether the distance is provably the minimum over the Markov equivalence class;
+    /// it is unless a chain component of more than 20 nodes was oriented greedily
+    #[getter]
+    fn exact(&self) -> bool {
+        self.exact
+    }
+
+    /// The int8 adjacency matrix of a closest DAG in the class, coded in the given `edge_direction`
+    #[getter]
+    fn dag<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray2<i8>> {
+        self.dag.bind(py).clone()
+    }
+
+    /// The tuple of (normalized error (in [0,1]), total number of errors)
+    fn value(&self) -> (f64, usize) {
+        (self.normalized, self.mistakes)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MecShd(normalized={}, mistakes={}, exact={})",
+            self.normalized,
+            self.mistakes,
+            if self.exact { "True" } else { "False" }
+        )
+    }
+}
+
+/// The Structural Identification Distance between a true DAG and the DAGs in the Markov equivalence class
+/// of a guess CPDAG, returned by `sid_over_mec`: its minimum, mean, and maximum over the `n_dags` DAGs.
+#[pyclass(module = "gadjid", frozen)]
+pub struct MecSid {
+    over_mec: RustMecSid,
+}
+
+#[pymethods]
+impl MecSid {
+    /// The tuple of (normalized error (in [0,1]), total number of errors) of the closest DAG in the class
+    #[getter]
+    fn min(&self) -> (f64, usize) {
+        self.over_mec.min
+    }
+
+    /// The tuple of (normalized error (in [0,1]), number of errors) averaged over the DAGs in the class
+    #[getter]
+    fn mean(&self) -> (f64, f64) {
+        self.over_mec.mean
+    }
+
+    /// The tuple of (normalized error (in [0,1]), total number of errors) of the farthest DAG in the class
+    #[getter]
+    fn max(&self) -> (f64, usize) {
+        self.over_mec.max
+    }
+
+    /// The number of DAGs in the class
+    #[getter]
+    fn n_dags(&self) -> u128 {
+        self.over_mec.n_dags
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MecSid(min={:?}, mean={:?}, max={:?}, n_dags={})",
+            self.over_mec.min, self.over_mec.mean, self.over_mec.max, self.over_mec.n_dags
+        )
+    }
+}
+
+impl From<RustMecSid> for MecSid {
+    fn from(over_mec: RustMecSid) -> Self {
+        MecSid { over_mec }
+    }
+}
@@ -3,17 +3,21 @@
 use gadjid::PDAG;
 use numpy::ndarray::ArrayView2;
 use numpy::{PyReadonlyArray2, PyUntypedArrayMethods};
-use pyo3::{prelude::PyAnyMethods, Bound, PyAny};
+use pyo3::{prelude::PyAnyMethods, Bound, PyAny, PyResult};
 
-use crate::graph_from_iterator;
+use crate::{graph_from_iterator, GadjidError, SizeMismatchError};
 
 /// Load a PDAG from a numpy ndarray
-pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> anyhow::Result<PDAG> {
+pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> PyResult<PDAG> {
     let ndarray = ob.extract::<PyReadonlyArray2<i8>>()?;
     let shape = ndarray.shape();
     let graph_size = shape[0];
-    anyhow::ensure!(shape[0] == shape[1], "Matrix must be square");
-    anyhow::ensure!(graph_size > 0, "Matrix must be non-empty");
+    if shape[0] != shape[1] {
+        return Err(SizeMismatchError::new_err("Matrix must be square"));
+    }
+    if graph_size == 0 {
+        return Err(GadjidError::new_err("Matrix must be non-empty"));
+    }
 
     // determine iteration order
     let row_major_iteration = ndarray.is_c_contiguous();
@@ -34,12 +38,26 @@ pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> anyhow::Result<PDAG>
     }
 }
 
+/// Copy a square numpy ndarray into a vector of its rows
+pub fn vecvec_from(ob: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<i8>>> {
+    let ndarray = ob.extract::<PyReadonlyArray2<i8>>()?;
+    let shape = ndarray.shape();
+    if shape[0] != shape[1] {
+        return Err(SizeMismatchError::new_err("Matrix must be square"));
+    }
+    Ok(ndarray
+        .as_array()
+        .outer_iter()
+        .map(|row| row.to_vec())
+        .collect())
+}
+
 /// Load a PDAG from an slice of i8s
 fn graph_from_slice(
     slice: &[i8],
     interpret_as_row_major: bool,
     graph_size: usize,
-) -> anyhow::Result<PDAG> {
+) -> PyResult<PDAG> {
     let iterator = slice.iter().enumerate().map(move |(ind, val)| {
         (
             ind / graph_size,
@@ -52,11 +70,7 @@ fn graph_from_slice(
 }
 
 /// Load a PDAG from a numpy ndarray view
-fn graph_from_view(
-    view: ArrayView2<i8>,
-    row_to_col: bool,
-    graph_size: usize,
-) -> anyhow::Result<PDAG> {
+fn graph_from_view(view: ArrayView2<i8>, row_to_col: bool, graph_size: usize) -> PyResult<PDAG> {
     let iterator = view
         .indexed_iter()
         .map(move |((row, col), val)| (row, col, *val));
@@ -0,0 +1,87 @@
+// SPDX-License-Identifier: MPL-2.0
+
+use gadjid::provenance::DistanceResult as RustDistanceResult;
+use pyo3::{prelude::*, types::PyDict};
+
+/// A distance between two graphs returned by `distance_with_provenance`, together with
+/// the gadjid version, the options, and the fingerprints of the graphs it was computed from,
+/// so that the numbers recorded in experiment logs can be traced back to how they were computed.
+#[pyclass(module = "gadjid", frozen)]
+pub struct DistanceResult {
+    result: RustDistanceResult,
+}
+
+#[pymethods]
+impl DistanceResult {
+    /// The name of the distance, such as "parent_aid"
+    #[getter]
+    fn distance(&self) -> &'static str {
+        self.result.distance
+    }
+
+    /// The normalized error, in [0,1]
+    #[getter]
+    fn normalized(&self) -> f64 {
+        self.result.normalized
+    }
+
+    /// The total number of errors
+    #[getter]
+    fn mistakes(&self) -> usize {
+        self.result.mistakes
+    }
+
+    /// The version of gadjid that computed the distance
+    #[getter]
+    fn gadjid_version(&self) -> &'static str {
+        self.result.provenance.gadjid_version
+    }
+
+    /// A dict of the options the distance was computed with, such as the edge direction
+    #[getter]
+    fn options<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let options = PyDict::new_bound(py);
+        for (name, value) in &self.result.provenance.options {
+            options.set_item(name, value)?;
+        }
+        Ok(options)
+    }
+
+    /// The fingerprint of the true graph, 16 hexadecimal digits that only depend on its edges
+    #[getter]
+    fn truth_fingerprint(&self) -> &str {
+        &self.result.provenance.truth_fingerprint
+    }
+
+    /// The fingerprint of the guess graph, 16 hexadecimal digits that only depend on its edges
+    #[getter]
+    fn guess_fingerprint(&self) -> &str {
+        &self.result.provenance.guess_fingerprint
+    }
+
+    /// The tuple of (normalized error (in [0,1]), total number of errors)
+    fn value(&self) -> (f64, usize) {
+        self.result.value()
+    }
+
+    /// The distance and its provenance as a JSON object
+    fn to_json(&self) -> String {
+        self.result.to_json()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DistanceResult({}={}, mistakes={}, gadjid_version={})",
+            self.result.distance,
+            self.result.normalized,
+            self.result.mistakes,
+            self.result.provenance.gadjid_version
+        )
+    }
+}
+
+impl From<RustDistanceResult> for DistanceResult {
+    fn from(result: RustDistanceResult) -> Self {
+        DistanceResult { result }
+    }
+}
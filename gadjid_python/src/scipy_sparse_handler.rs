@@ -1,12 +1,11 @@
 // SPDX-License-Identifier: MPL-2.0
 
-use anyhow::bail;
 use gadjid::PDAG;
 use numpy::PyReadonlyArray1;
-use pyo3::{prelude::PyAnyMethods, Bound, PyAny};
+use pyo3::{prelude::PyAnyMethods, Bound, PyAny, PyResult};
 use std::slice::Iter;
 
-use crate::graph_from_iterator;
+use crate::{graph_from_iterator, GadjidError, SizeMismatchError};
 
 /// Encodes sparse matrix in CSR/CSC format.
 struct CSMatrix<'a> {
@@ -39,7 +38,7 @@ impl Iterator for CSMatrix<'_> {
 }
 
 /// Load a PDAG from a scipy sparse matrix in csr or csc format.
-pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> anyhow::Result<PDAG> {
+pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> PyResult<PDAG> {
     // get the encoding format
     let format = ob.getattr("format")?;
     let format = format.extract::<String>()?;
@@ -62,12 +61,14 @@ pub fn try_from(ob: &Bound<'_, PyAny>, row_to_col: bool) -> anyhow::Result<PDAG>
     // get the shape to make sure it is square and for later CSR / CSC iteration
     let shape = ob.getattr("shape")?;
     let shape = shape.extract::<(usize, usize)>()?;
-    anyhow::ensure!(shape.0 == shape.1, "Matrix must be square");
+    if shape.0 != shape.1 {
+        return Err(SizeMismatchError::new_err("Matrix must be square"));
+    }
 
     if format == "csr" || format == "csc" {
         graph_from_csc_or_csr(ob, interpret_as_row_major, shape.0)
     } else {
-        bail!("Unsupported sparse matrix format received: '{:?}'. The package currently only supports 'csr' and 'csc'.", format);
+        Err(GadjidError::new_err(format!("Unsupported sparse matrix format received: '{:?}'. The package currently only supports 'csr' and 'csc'.", format)))
     }
 }
 
@@ -75,7 +76,7 @@ fn graph_from_csc_or_csr(
     ob: &Bound<'_, PyAny>,
     interpret_as_row_major: bool,
     shape: usize,
-) -> anyhow::Result<PDAG> {
+) -> PyResult<PDAG> {
     // these explanations assume a csr matrix
     // element at index `r` and `r+1` hold the indices of the first (inclusive) and last
     // (exclusive) nonzero entries in row `r`
@@ -93,6 +94,23 @@ fn graph_from_csc_or_csr(
     let data = data.extract::<PyReadonlyArray1<i8>>()?;
     let data = data.as_slice()?;
 
+    // malformed arrays would make the iteration below index out of bounds
+    if indptr.len() != shape + 1 || indices.len() != data.len() {
+        return Err(SizeMismatchError::new_err(format!(
+            "Sparse matrix of shape ({shape}, {shape}) must have {} indptr entries and as many indices as data entries, but has {} indptr entries, {} indices and {} data entries",
+            shape + 1,
+            indptr.len(),
+            indices.len(),
+            data.len()
+        )));
+    }
+    let nondecreasing = indptr.windows(2).all(|pair| pair[0] <= pair[1]);
+    if indptr[0] != 0 || !nondecreasing || indptr[shape] as usize != indices.len() {
+        return Err(GadjidError::new_err(
+            "Sparse matrix indptr must be nondecreasing from 0 to the number of stored entries",
+        ));
+    }
+
     // So, relating this all to the source matrix M, we have M[r,c]=v
     let iterator = CSMatrix {
         shape,
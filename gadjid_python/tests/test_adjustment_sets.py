@@ -0,0 +1,71 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    InvalidPairsError,
+    adjustment_set_candidates,
+    compare_adjustment_sets,
+    not_validly_adjusted_for,
+)
+
+# 0 -> 1 -> 2, 3 -> 1, 3 -> 2, 4 -> 2
+DAG = np.array(
+    [
+        [0, 1, 0, 0, 0],
+        [0, 0, 1, 0, 0],
+        [0, 0, 0, 0, 0],
+        [0, 1, 1, 0, 0],
+        [0, 0, 1, 0, 0],
+    ],
+    dtype=np.int8,
+)
+
+
+def test_compare_adjustment_sets():
+    # the precision variable 4 can only help, the instrument 0 can only hurt
+    assert compare_adjustment_sets(DAG, [1], 2, [3], [3, 4], "from row to column") == (
+        True,
+        True,
+        [],
+        [4],
+        1,
+    )
+    assert compare_adjustment_sets(DAG, [1], 2, [3], [0, 3], "from row to column")[4] == -1
+    # the empty set misses the confounder 3
+    assert compare_adjustment_sets(DAG, [1], 2, [], [3], "from row to column") == (
+        False,
+        True,
+        [],
+        [3],
+        None,
+    )
+
+    with pytest.raises(InvalidPairsError):
+        compare_adjustment_sets(DAG, [1], 5, [], [3], "from row to column")
+    with pytest.raises(InvalidPairsError):
+        compare_adjustment_sets(DAG, [2], 2, [], [3], "from row to column")
+
+
+def test_adjustment_set_candidates():
+    parents, optimal, minimal, parents_valid, optimal_valid, containment = (
+        adjustment_set_candidates(DAG, [1], 2, "from row to column")
+    )
+    assert (parents, optimal, minimal) == ([0, 3], [3, 4], [3])
+    assert parents_valid and optimal_valid
+    assert containment == (False, False, True)
+
+    # 0 -- 1 is not amenable relative to 0 and 1
+    cpdag = np.array([[0, 2], [0, 0]], dtype=np.int8)
+    assert adjustment_set_candidates(cpdag, [0], 1, "from row to column")[2] is None
+
+
+def test_not_validly_adjusted_for():
+    # the empty set misses the confounder 3 of the effect on 2,
+    # and the effects on the parents 0 and 3 of 1 are never validly adjusted for
+    assert not_validly_adjusted_for(DAG, [1], [], "from row to column") == [0, 2, 3]
+    assert 2 not in not_validly_adjusted_for(DAG, [1], [3], "from row to column")
+    with pytest.raises(InvalidPairsError):
+        not_validly_adjusted_for(DAG, [], [3], "from row to column")
+    with pytest.raises(InvalidPairsError):
+        not_validly_adjusted_for(DAG, [1], [5], "from row to column")
@@ -0,0 +1,45 @@
+# SPDX-License-Identifier: MPL-2.0
+import asyncio
+
+import numpy as np
+
+import gadjid
+from gadjid import aio
+
+
+ROW_TO_COL = "from row to column"
+
+
+def random_dag(size, rng):
+    adj = np.triu(rng.random((size, size)) < 0.3, k=1).astype(np.int8)
+    perm = rng.permutation(size)
+    return adj[perm][:, perm]
+
+
+def test_awaitable_distances_agree():
+    rng = np.random.default_rng(0)
+    Gtrue = random_dag(30, rng)
+    Gguess = random_dag(30, rng)
+
+    async def run_concurrently():
+        return await asyncio.gather(
+            aio.ancestor_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+            aio.oset_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+            aio.parent_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+            aio.aid_selected_pairs(
+                Gtrue, Gguess, [(0, 1)], "parent_aid", edge_direction=ROW_TO_COL
+            ),
+            aio.shd(Gtrue, Gguess),
+            aio.sid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+        )
+
+    assert asyncio.run(run_concurrently()) == [
+        gadjid.ancestor_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+        gadjid.oset_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+        gadjid.parent_aid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+        gadjid.aid_selected_pairs(
+            Gtrue, Gguess, [(0, 1)], "parent_aid", edge_direction=ROW_TO_COL
+        ),
+        gadjid.shd(Gtrue, Gguess),
+        gadjid.sid(Gtrue, Gguess, edge_direction=ROW_TO_COL),
+    ]
@@ -0,0 +1,54 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import (
+    amenability_disagreement,
+    amenability_matrix,
+    identifiability_report,
+    parent_aid,
+)
+
+# 0 -> 1 -- 2
+# |
+# 3
+CPDAG = np.array(
+    [[0, 1, 0, 2], [0, 0, 2, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_amenability_matrix():
+    expected = np.array(
+        [
+            [False, True, True, False],
+            [True, False, False, True],
+            [True, False, False, True],
+            [False, False, False, False],
+        ]
+    )
+    matrix = amenability_matrix(CPDAG, edge_direction="from row to column")
+    assert matrix.dtype == np.bool_
+    assert (matrix == expected).all()
+    # rows are treatments irrespective of the edge direction
+    transposed = amenability_matrix(CPDAG.T, edge_direction="from column to row")
+    assert (transposed == expected).all()
+
+
+def test_identifiability_report():
+    fraction, amenable_effects, clusters = identifiability_report(
+        CPDAG, edge_direction="from row to column"
+    )
+    assert fraction == 6 / 12
+    assert amenable_effects == [2, 0, 0, 0]
+    assert clusters == [[0, 3], [1, 2]]
+
+
+def test_amenability_disagreement():
+    # 0 -> 1 -> 2, 0 -> 3 is amenable relative to every pair
+    dag = np.array(
+        [[0, 1, 0, 1], [0, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+    )
+    normalized, mistakes = amenability_disagreement(
+        dag, CPDAG, edge_direction="from row to column"
+    )
+    assert (normalized, mistakes) == (6 / 12, 6)
+    assert parent_aid(dag, CPDAG, edge_direction="from row to column")[1] >= 6
@@ -0,0 +1,65 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    aid_approx,
+    ancestor_aid,
+    oset_aid,
+    parent_aid,
+    parent_aid_bounds,
+)
+
+
+def random_dag(n, density, rng):
+    return np.triu(rng.random((n, n)) < density, k=1).astype(np.int8)
+
+
+@pytest.mark.parametrize(
+    "name, exact",
+    [
+        ("ancestor_aid", ancestor_aid),
+        ("oset_aid", oset_aid),
+        ("parent_aid", parent_aid),
+    ],
+)
+def test_full_sample_is_exact(name, exact):
+    rng = np.random.default_rng(0)
+    truth, guess = random_dag(20, 0.2, rng), random_dag(20, 0.2, rng)
+    for kwargs in [{}, {"n_strata": 3}, {"n_effects": 20}]:
+        estimate, standard_error, (low, high) = aid_approx(
+            truth, guess, name, "from row to column", 20, **kwargs
+        )
+        assert np.isclose(
+            estimate, exact(truth, guess, edge_direction="from row to column")[0]
+        )
+        assert standard_error == 0.0
+        assert low <= estimate <= high
+
+
+def test_approx_validates_arguments():
+    truth = random_dag(5, 0.5, np.random.default_rng(0))
+    with pytest.raises(ValueError):
+        aid_approx(truth, truth, "oset_aid", "from row to column", 1)
+    with pytest.raises(ValueError):
+        aid_approx(truth, truth, "oset_aid", "from row to column", 3, n_strata=0)
+    with pytest.raises(ValueError):
+        aid_approx(
+            truth, truth, "oset_aid", "from row to column", 3, n_strata=2, n_effects=2
+        )
+    with pytest.raises(ValueError):
+        aid_approx(truth, truth, "shd", "from row to column", 3)
+
+
+def test_bounds_enclose_parent_aid():
+    rng = np.random.default_rng(3)
+    for _ in range(10):
+        truth, guess = random_dag(20, 0.2, rng), random_dag(20, 0.2, rng)
+        (lower_normalized, lower), (upper_normalized, upper) = parent_aid_bounds(
+            truth, guess, edge_direction="from row to column"
+        )
+        normalized, mistakes = parent_aid(
+            truth, guess, edge_direction="from row to column"
+        )
+        assert lower <= mistakes <= upper
+        assert lower_normalized <= normalized <= upper_normalized
@@ -0,0 +1,49 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    metrics_table_csv,
+    read_benchpress_csv,
+    read_score_matrix_csv,
+    shd,
+)
+
+ROW_TO_COL = "from row to column"
+
+
+def test_read_benchpress_csv(tmp_path):
+    path = tmp_path / "adjmat.csv"
+    path.write_text('"a","b","c"\n0,1,0\n0,0,1\n0,1,0\n')
+    names, adjacency = read_benchpress_csv(str(path), edge_direction=ROW_TO_COL)
+    assert names == ["a", "b", "c"]
+    # a -> b -- c, with the undirected edge coded on one side
+    assert adjacency.tolist() == [[0, 1, 0], [0, 0, 2], [0, 0, 0]]
+
+    path.write_text("a,b\n0,2\n0,0\n")
+    with pytest.raises(ValueError, match="line 2"):
+        read_benchpress_csv(str(path), edge_direction=ROW_TO_COL)
+    with pytest.raises(OSError):
+        read_benchpress_csv(str(tmp_path / "missing.csv"), edge_direction=ROW_TO_COL)
+
+
+def test_read_score_matrix_csv(tmp_path):
+    path = tmp_path / "scores.csv"
+    path.write_text("0.9,0.8,0.1\n0.0,0.7,0.2\n0.2,0.9,0.5\n")
+    adjacency = read_score_matrix_csv(str(path), 0.5, edge_direction=ROW_TO_COL)
+    # 0 -> 1 <- 2
+    assert adjacency.tolist() == [[0, 1, 0], [0, 0, 0], [0, 1, 0]]
+
+
+def test_metrics_table_csv():
+    truth = np.array([[0, 1], [0, 0]], dtype=np.int8)
+    guess = truth.T.copy()
+    table = metrics_table_csv(
+        truth, [("pc", guess), ("oracle", truth)], ["shd"], edge_direction=ROW_TO_COL
+    )
+    normalized, mistakes = shd(truth, guess)
+    assert table.splitlines() == [
+        "id,distance,normalized,mistakes",
+        f"pc,shd,{normalized:g},{mistakes}",
+        "oracle,shd,0,0",
+    ]
@@ -0,0 +1,55 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import (
+    aid_with_breakdown,
+    aid_with_truth_sets,
+    ancestor_aid,
+    oset_aid,
+    parent_aid,
+)
+
+AIDS = [
+    ("ancestor_aid", ancestor_aid),
+    ("oset_aid", oset_aid),
+    ("parent_aid", parent_aid),
+]
+
+
+def test_causes_sum_to_mistakes():
+    rng = np.random.default_rng(0)
+    for _ in range(10):
+        truth = np.triu(rng.random((8, 8)) < 0.3, k=1).astype(np.int8)
+        guess = np.triu(rng.random((8, 8)) < 0.3, k=1).astype(np.int8)
+        for name, distance in AIDS:
+            distance_and_mistakes, causes = aid_with_breakdown(
+                truth, guess, name, "from row to column"
+            )
+            assert distance_and_mistakes == distance(truth, guess, "from row to column")
+            assert sum(causes) == distance_and_mistakes[1]
+
+
+def test_undirected_edge_is_an_amenability_disagreement():
+    # truth: 0 -> 1, guess: 0 -- 1
+    truth = np.array([[0, 1], [0, 0]], dtype=np.int8)
+    guess = np.array([[0, 1], [1, 0]], dtype=np.int8)
+    assert aid_with_breakdown(truth, guess, "parent_aid", "from row to column") == (
+        (1.0, 2),
+        (0, 2, 0),
+    )
+
+
+def test_truth_sets():
+    # truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+    truth = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    guess = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+    for name, distance in AIDS:
+        distance_and_mistakes, sets = aid_with_truth_sets(
+            truth, guess, name, "from row to column"
+        )
+        assert distance_and_mistakes == distance(truth, guess, "from row to column")
+        # one (nam, nva) tuple per treatment, and all effects are amenable in a DAG
+        assert len(sets) == 3
+        assert all(nam == [] for nam, _ in sets)
+    _, sets = aid_with_truth_sets(truth, guess, "parent_aid", "from row to column")
+    assert sets[1] == ([], [0, 2])
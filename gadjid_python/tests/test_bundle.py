@@ -0,0 +1,40 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import ancestor_aid, export_bundle, shd, verify_bundle
+
+# 0 -> 1 -> 2, 0 -- 3
+TRUTH = np.array(
+    [[0, 1, 0, 2], [0, 0, 1, 0], [0, 0, 0, 0], [2, 0, 0, 0]], dtype=np.int8
+)
+# 0 <- 1 -> 2
+GUESS = np.array(
+    [[0, 0, 0, 0], [1, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_bundle(tmp_path):
+    path = str(tmp_path / "bundle.tar")
+    results = {
+        "ancestor_aid": ancestor_aid(TRUTH, GUESS, "from row to column"),
+        "shd": shd(TRUTH, GUESS),
+    }
+    export_bundle(path, TRUTH, GUESS, "from row to column", list(results.items()))
+    _, options, mismatches = verify_bundle(path)
+    assert options == {"edge_direction": "from row to column"}
+    assert mismatches == []
+
+    normalized, mistakes = results["shd"]
+    export_bundle(path, TRUTH, GUESS, "from row to column", [("shd", (normalized, 0))])
+    _, _, mismatches = verify_bundle(path)
+    assert mismatches == [("shd", (normalized, 0), (normalized, mistakes))]
+
+    # a bundle without results verifies
+    export_bundle(path, TRUTH, GUESS, "from column to row", [])
+    assert verify_bundle(path)[1:] == ({"edge_direction": "from column to row"}, [])
+
+    with pytest.raises(ValueError):
+        export_bundle(path, TRUTH, GUESS, "from row to column", [("sid", (0.0, 0))])
+    with pytest.raises(OSError):
+        verify_bundle(str(tmp_path / "missing.tar"))
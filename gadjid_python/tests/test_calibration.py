@@ -0,0 +1,92 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import expected_random_shd, node_dropout_curve, null_distribution
+
+# 0 -> 1 -> 2 -> 3, 0 -> 2
+TRUTH = np.array(
+    [[0, 1, 1, 0], [0, 0, 1, 0], [0, 0, 0, 1], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_null_distribution():
+    samples, percentile = null_distribution(
+        TRUTH, TRUTH, "oset_aid", edge_direction="from row to column", n_samples=50
+    )
+    assert samples.shape == (50,)
+    assert (np.diff(samples) >= 0).all()
+    assert 0 <= percentile <= 100
+    assert percentile == 100 * np.mean(samples <= 0.0)
+
+    again, _ = null_distribution(
+        TRUTH, TRUTH, "oset_aid", edge_direction="from row to column", n_samples=50
+    )
+    assert (samples == again).all()
+
+    with pytest.raises(ValueError):
+        null_distribution(TRUTH, TRUTH, "sid", edge_direction="from row to column")
+    with pytest.raises(ValueError):
+        null_distribution(
+            TRUTH, TRUTH, "shd", edge_direction="from row to column", n_samples=0
+        )
+
+
+def test_expected_random_shd():
+    normalised, expected = expected_random_shd(
+        TRUTH, 0.5, edge_direction="from row to column"
+    )
+    # each of the 4 edges is guessed right with probability 1/4,
+    # and each of the 2 non-adjacent pairs is joined with probability 1/2
+    assert expected == 4 * 0.75 + 2 * 0.5
+    assert normalised == expected / 6
+    with pytest.raises(ValueError):
+        expected_random_shd(
+            TRUTH, 0.5, edge_direction="from row to column", random_graphs="cpdag"
+        )
+
+
+def test_node_dropout_curve():
+    guess = TRUTH.T.copy()
+    curve = node_dropout_curve(
+        TRUTH,
+        guess,
+        "shd",
+        edge_direction="from row to column",
+        fractions=[0.0, 0.5],
+        n_repetitions=20,
+    )
+    assert [(fraction, n_dropped) for fraction, n_dropped, _, _ in curve] == [
+        (0.0, 0),
+        (0.5, 2),
+    ]
+    # reversing every edge is wrong on every adjacent pair, whichever nodes remain
+    assert np.isclose(curve[0][2], 4 / 6)
+    assert np.isclose(curve[0][3], 0.0)
+
+    with pytest.raises(ValueError):
+        node_dropout_curve(
+            TRUTH, guess, "shd", edge_direction="from row to column", fractions=[0.75]
+        )
+
+
+def test_tidy_node_dropout_curve():
+    table = node_dropout_curve(
+        TRUTH,
+        TRUTH,
+        "oset_aid",
+        edge_direction="from row to column",
+        fractions=[0.0, 0.25, 0.5],
+        n_repetitions=5,
+        tidy=True,
+    )
+    assert list(table) == [
+        "distance",
+        "fraction",
+        "n_dropped",
+        "mean",
+        "standard_deviation",
+    ]
+    assert table["distance"] == ["oset_aid"] * 3
+    assert (table["n_dropped"] == [0, 1, 2]).all()
+    assert (table["mean"] == 0).all()
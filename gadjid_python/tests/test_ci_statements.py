@@ -0,0 +1,47 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import (
+    ci_statement_distance,
+    implied_independencies,
+    markov_property_test,
+)
+
+
+def test_ci_statement_distance():
+    # truth: 0 -> 1 -> 2, guess: 0 -> 1 <- 2
+    truth = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    guess = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+    # 0 and 2 are independent given {1} in the truth, and given {} in the guess
+    assert ci_statement_distance(truth, guess, "from row to column", 1) == (
+        (2 / 6, 2),
+        (1, 1),
+    )
+    # 0 <- 1 <- 2 is Markov equivalent to the truth
+    equivalent = np.array([[0, 0, 0], [1, 0, 0], [0, 1, 0]], dtype=np.int8)
+    assert ci_statement_distance(truth, equivalent, "from row to column", 1)[0] == (
+        0.0,
+        0,
+    )
+
+
+def test_markov_property_test():
+    # 0 -> 1 -> 2
+    chain = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    assert implied_independencies(chain, "from row to column", 2) == [(0, 2, [1])]
+
+    calls = []
+
+    def collider_oracle(x, y, given):
+        # data from 0 -> 1 <- 2, where 0 and 2 are independent only marginally
+        calls.append((x, y, given))
+        return len(given) == 0
+
+    assert markov_property_test(chain, "from row to column", collider_oracle) == (
+        1,
+        [(0, 2, [1])],
+    )
+    assert calls == [(0, 2, [1])]
+    assert markov_property_test(
+        chain, "from row to column", collider_oracle, max_tests=0
+    ) == (0, [])
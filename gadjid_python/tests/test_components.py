@@ -0,0 +1,30 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+from scipy.linalg import block_diag
+
+from gadjid import distance_by_components, oset_aid, shd
+
+# 0 -> 1 -> 2
+CHAIN = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 -> 1 <- 2
+COLLIDER = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+
+
+def test_distance_by_components():
+    truth = block_diag(CHAIN, CHAIN, [[0]]).astype(np.int8)
+    guess = block_diag(CHAIN, COLLIDER, [[0]]).astype(np.int8)
+    expected_distances = {
+        "oset_aid": oset_aid(truth, guess, edge_direction="from row to column"),
+        "shd": shd(truth, guess),
+    }
+    for name, expected in expected_distances.items():
+        whole, components = distance_by_components(
+            truth, guess, name, edge_direction="from row to column"
+        )
+        assert whole[1] == expected[1]
+        assert np.isclose(whole[0], expected[0])
+        assert [nodes for nodes, _, _ in components] == [[0, 1, 2], [3, 4, 5]]
+        assert components[0][1:] == (0.0, 0)
+    with pytest.raises(ValueError):
+        distance_by_components(truth, guess, "sid", edge_direction="from row to column")
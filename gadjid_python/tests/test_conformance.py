@@ -0,0 +1,36 @@
+# SPDX-License-Identifier: MPL-2.0
+"""Checks the bindings against the expected results generated by the Rust test suite,
+see gadjid/src/testdata/conformance.rs for the format of testgraphs/conformance.csv."""
+import csv
+from pathlib import Path
+
+import numpy as np
+
+import gadjid
+
+CORPUS = Path(__file__).parent.parent.parent / "testgraphs" / "conformance.csv"
+
+
+def decode_graph(encoded):
+    return np.array(
+        [[int(entry) for entry in row] for row in encoded.split(";")],
+        dtype=np.int8,
+    )
+
+
+def test_bindings_agree_with_rust():
+    with open(CORPUS, newline="") as corpus:
+        rows = list(csv.DictReader(corpus))
+    assert rows
+
+    for row in rows:
+        distance = getattr(gadjid, row["distance"])
+        g_true = decode_graph(row["g_true"])
+        g_guess = decode_graph(row["g_guess"])
+        normalized, mistakes = distance(
+            g_true, g_guess, edge_direction="from row to column"
+        )
+        assert (normalized, mistakes) == (
+            float(row["normalized"]),
+            int(row["mistakes"]),
+        ), f"{row['distance']} disagrees for {row['case']}"
@@ -0,0 +1,26 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import corpus_graphs, cpdag_of, load_corpus_graph, parent_aid, shd
+
+
+def test_corpus_lists_and_loads_the_testgraphs():
+    names = corpus_graphs()
+    assert len(names) == 40
+    assert "10-node-DAG-10" in names and "100-node-CPDAG-29" in names
+    dag = load_corpus_graph("10-node-DAG-10", "from row to column")
+    cpdag = load_corpus_graph("10-node-CPDAG-10", "from row to column")
+    assert dag.dtype == np.int8 and dag.shape == (10, 10)
+    assert np.array_equal(cpdag_of(dag, "from row to column"), cpdag)
+    assert np.array_equal(load_corpus_graph("10-node-DAG-10", "from column to row"), dag.T)
+    with pytest.raises(ValueError):
+        load_corpus_graph("10-node-DAG-30", "from row to column")
+
+
+def test_corpus_reproduces_reference_numbers():
+    # first row of testgraphs/SID-10-node-DAGs.csv
+    g_true = load_corpus_graph("10-node-DAG-11", "from row to column")
+    g_guess = load_corpus_graph("10-node-DAG-10", "from row to column")
+    assert shd(g_true, g_guess)[1] == 23
+    assert parent_aid(g_true, g_guess, "from row to column")[1] == 35
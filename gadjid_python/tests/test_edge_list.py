@@ -0,0 +1,44 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    EdgeList,
+    InvalidEdgeValueError,
+    NotSimpleGraphError,
+    SizeMismatchError,
+    ancestor_aid,
+    shd,
+)
+
+# 0 -> 1 -> 2, 2 -- 3
+TRUTH = np.array(
+    [[0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 2], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_edge_lists_load_like_adjacency_matrices():
+    edges = EdgeList(np.array([[2, 3, 2], [1, 2, 1], [0, 1, 1]]))
+    assert edges.n_nodes == 4
+    assert len(edges) == 3
+    assert ancestor_aid(edges, TRUTH, "from row to column") == (0.0, 0)
+    # the edge direction only applies to adjacency matrices
+    assert ancestor_aid(edges, TRUTH.T, "from column to row") == (0.0, 0)
+    assert shd(TRUTH, edges) == (0.0, 0)
+
+    # two columns code directed edges, and int32 arrays are accepted
+    directed = EdgeList(np.array([[0, 1], [1, 2]], dtype=np.int32), n_nodes=4)
+    assert shd(directed, TRUTH)[1] == 1
+
+
+def test_invalid_edge_lists_raise():
+    with pytest.raises(SizeMismatchError):
+        EdgeList(np.array([[0, 1, 1, 1]]))
+    with pytest.raises(SizeMismatchError):
+        EdgeList(np.array([[0, 4]]), n_nodes=4)
+    with pytest.raises(SizeMismatchError):
+        EdgeList(np.array([[-1, 2]]))
+    with pytest.raises(InvalidEdgeValueError):
+        EdgeList(np.array([[0, 1, 3]]))
+    with pytest.raises(NotSimpleGraphError):
+        EdgeList(np.array([[0, 1], [0, 1]]))
@@ -0,0 +1,173 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    GadjidError,
+    InvalidPairsError,
+    SizeMismatchError,
+    NotAcyclicError,
+    cluster_graphs,
+    consensus_graph,
+    cross_consistency,
+    edge_frequencies,
+    identification_stability,
+    load_many,
+    shd_barycenter,
+    shd_medoid,
+)
+
+ENSEMBLE = [
+    # 0 -> 1 -> 2
+    np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8),
+    # 0 -> 1 <- 2
+    np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8),
+    # 0 -- 1 -> 2, 0 -> 2
+    np.array([[0, 2, 1], [0, 0, 1], [0, 0, 0]], dtype=np.int8),
+]
+
+
+def test_consensus_graph():
+    consensus = consensus_graph(ENSEMBLE, 0.6, edge_direction="from row to column")
+    assert (consensus == np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]])).all()
+    undirected = consensus_graph(ENSEMBLE, 1.0, edge_direction="from row to column")
+    assert (undirected == np.array([[0, 2, 0], [2, 0, 2], [0, 2, 0]])).all()
+    transposed = consensus_graph(
+        [g.T for g in ENSEMBLE], 0.6, edge_direction="from column to row"
+    )
+    assert (transposed == consensus.T).all()
+
+
+def test_consensus_graph_errors():
+    with pytest.raises(GadjidError):
+        consensus_graph([], 0.5, edge_direction="from row to column")
+    with pytest.raises(GadjidError):
+        consensus_graph(ENSEMBLE, 1.5, edge_direction="from row to column")
+    cycle = [
+        np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8),
+        np.array([[0, 0, 0], [0, 0, 1], [1, 0, 0]], dtype=np.int8),
+        np.array([[0, 1, 0], [0, 0, 0], [1, 0, 0]], dtype=np.int8),
+    ]
+    with pytest.raises(NotAcyclicError):
+        consensus_graph(cycle, 0.6, edge_direction="from row to column")
+
+
+def test_stability():
+    assert edge_frequencies(ENSEMBLE, edge_direction="from row to column") == [
+        (0, 1, 2 / 3, 0.0, 1 / 3),
+        (0, 2, 1 / 3, 0.0, 0.0),
+        (1, 2, 2 / 3, 1 / 3, 0.0),
+    ]
+    stability = identification_stability(
+        ENSEMBLE, [(0, 1), (2, 0)], "oset_aid", edge_direction="from row to column"
+    )
+    assert stability == [1 / 6, 1.0]
+    with pytest.raises(ValueError):
+        identification_stability(
+            ENSEMBLE, [(0, 1)], "shd", edge_direction="from row to column"
+        )
+    with pytest.raises(InvalidPairsError):
+        identification_stability(
+            ENSEMBLE, [(0, 3)], "oset_aid", edge_direction="from row to column"
+        )
+
+
+def test_cross_consistency():
+    distances, (mean, std, smallest, largest, asymmetry) = cross_consistency(
+        ENSEMBLE, "shd", edge_direction="from row to column"
+    )
+    assert np.allclose(
+        distances, [[0, 1 / 3, 2 / 3], [1 / 3, 0, 1], [2 / 3, 1, 0]]
+    )
+    assert np.isclose(mean, 2 / 3) and np.isclose(std, np.sqrt(2 / 27))
+    assert (smallest, largest, asymmetry) == (1 / 3, 1.0, 0.0)
+    with pytest.raises(ValueError):
+        cross_consistency(ENSEMBLE, "sid", edge_direction="from row to column")
+    with pytest.raises(GadjidError):
+        cross_consistency(ENSEMBLE[:1], "shd", edge_direction="from row to column")
+
+
+def test_cluster_graphs():
+    distances = np.array(
+        [
+            [0.0, 0.5, 3.0, 9.0, 9.0],
+            [1.5, 0.0, 0.0, 9.0, 9.0],
+            [3.0, 2.0, 0.0, 9.0, 9.0],
+            [9.0, 9.0, 9.0, 0.0, 1.0],
+            [9.0, 9.0, 9.0, 1.0, 0.0],
+        ]
+    )
+    assert cluster_graphs(distances, 2) == ([0, 0, 0, 1, 1], [1, 3])
+    assert cluster_graphs(distances, 1, linkage="single") == ([0] * 5, [1])
+    with pytest.raises(ValueError):
+        cluster_graphs(distances, 2, linkage="ward")
+    with pytest.raises(GadjidError):
+        cluster_graphs(distances, 6)
+    with pytest.raises(SizeMismatchError):
+        cluster_graphs(distances[:, :4], 2)
+
+
+def test_shd_medoid_and_barycenter():
+    assert shd_medoid(ENSEMBLE, edge_direction="from row to column") == 0
+    barycenter, total = shd_barycenter(ENSEMBLE, edge_direction="from row to column")
+    assert (barycenter == ENSEMBLE[0]).all()
+    assert total == 3
+    transposed, _ = shd_barycenter(
+        [g.T for g in ENSEMBLE], edge_direction="from column to row"
+    )
+    assert (transposed == ENSEMBLE[0].T).all()
+    with pytest.raises(GadjidError):
+        shd_medoid([], edge_direction="from row to column")
+
+
+def test_load_many():
+    collection = load_many(ENSEMBLE, edge_direction="from row to column")
+    assert len(collection) == 3
+    assert collection.n_nodes == 3
+    for graphs in [collection, np.stack(ENSEMBLE)]:
+        consensus = consensus_graph(
+            load_many(graphs, edge_direction="from row to column"),
+            0.6,
+            edge_direction="from row to column",
+        )
+        assert (consensus == np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]])).all()
+    assert shd_medoid(collection, edge_direction="from row to column") == shd_medoid(
+        ENSEMBLE, edge_direction="from row to column"
+    )
+    transposed = load_many(
+        np.stack(ENSEMBLE).transpose(0, 2, 1), edge_direction="from column to row"
+    )
+    assert edge_frequencies(
+        transposed, edge_direction="from row to column"
+    ) == edge_frequencies(ENSEMBLE, edge_direction="from row to column")
+
+
+def test_load_many_errors():
+    with pytest.raises(GadjidError):
+        load_many([], edge_direction="from row to column")
+    with pytest.raises(SizeMismatchError):
+        load_many(
+            [ENSEMBLE[0], np.zeros((2, 2), dtype=np.int8)],
+            edge_direction="from row to column",
+        )
+    cycle = np.array([[0, 1, 0], [0, 0, 1], [1, 0, 0]], dtype=np.int8)
+    with pytest.raises(NotAcyclicError, match="Graph 1"):
+        load_many(np.stack([ENSEMBLE[0], cycle]), edge_direction="from row to column")
+
+
+def test_stacked_ensemble():
+    stacked = np.stack(ENSEMBLE)
+    for graphs in [stacked, np.asfortranarray(stacked)]:
+        assert (
+            consensus_graph(graphs, 0.6, edge_direction="from row to column")
+            == consensus_graph(ENSEMBLE, 0.6, edge_direction="from row to column")
+        ).all()
+        assert shd_medoid(graphs, edge_direction="from row to column") == shd_medoid(
+            ENSEMBLE, edge_direction="from row to column"
+        )
+    transposed = stacked.transpose(0, 2, 1)
+    assert cross_consistency(
+        transposed, "oset_aid", edge_direction="from column to row"
+    )[1] == cross_consistency(ENSEMBLE, "oset_aid", edge_direction="from row to column")[1]
+    with pytest.raises(SizeMismatchError):
+        edge_frequencies(np.zeros((2, 3, 4), dtype=np.int8), edge_direction="from row to column")
@@ -0,0 +1,160 @@
+# SPDX-License-Identifier: MPL-2.0
+import functools
+
+import numpy as np
+import pytest
+import scipy
+
+import gadjid
+from gadjid import (
+    GadjidError,
+    InvalidEdgeValueError,
+    NotAcyclicError,
+    NotSimpleGraphError,
+    SizeMismatchError,
+)
+
+ROW_TO_COL = "from row to column"
+
+DAG = np.array([[0, 1, 1], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+
+
+@pytest.mark.parametrize(
+    "guess, error",
+    [
+        ([[0, 1, 0], [0, 0, 1], [1, 0, 0]], NotAcyclicError),
+        ([[0, 1, 0], [1, 0, 0], [0, 0, 0]], NotSimpleGraphError),
+        ([[1, 0, 0], [0, 0, 0], [0, 0, 0]], NotSimpleGraphError),
+        ([[0, 3, 0], [0, 0, 0], [0, 0, 0]], InvalidEdgeValueError),
+        ([[0, 1], [0, 0]], SizeMismatchError),
+        ([[0, 1, 0], [0, 0, 1]], SizeMismatchError),
+    ],
+)
+def test_typed_errors(guess, error):
+    guess = np.array(guess, dtype=np.int8)
+    for g_guess in [guess, scipy.sparse.csr_matrix(guess)]:
+        with pytest.raises(error):
+            gadjid.parent_aid(DAG, g_guess, edge_direction=ROW_TO_COL)
+        with pytest.raises(GadjidError):
+            gadjid.sid(DAG, g_guess, edge_direction=ROW_TO_COL)
+
+
+def test_sid_rejects_cpdags():
+    cpdag = np.array([[0, 2, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    with pytest.raises(GadjidError):
+        gadjid.sid(DAG, cpdag, edge_direction=ROW_TO_COL)
+
+
+def test_sid_handles_undirected_edges_on_request():
+    # DAG with a stray undirected edge 0 -- 1
+    stray = DAG.copy()
+    stray[0, 1] = 2
+    with pytest.warns(UserWarning, match="guess graph were oriented"):
+        oriented = gadjid.sid(DAG, stray, edge_direction=ROW_TO_COL, undirected="orient")
+    (lower, lower_mistakes), (upper, upper_mistakes) = gadjid.sid(
+        DAG, stray, edge_direction=ROW_TO_COL, undirected="bounds"
+    )
+    assert lower_mistakes <= oriented[1] <= upper_mistakes
+    assert lower_mistakes == 0
+    with pytest.raises(ValueError, match='not "ignore"'):
+        gadjid.sid(DAG, stray, edge_direction=ROW_TO_COL, undirected="ignore")
+
+
+def test_sid_over_mec():
+    # 0 -- 1 is represented by the truth and by its reversal 1 -> 0
+    stray = DAG.copy()
+    stray[0, 1] = 2
+    (lower, lower_mistakes), (upper, upper_mistakes) = gadjid.sid(
+        DAG, stray, edge_direction=ROW_TO_COL, undirected="bounds"
+    )
+    over_mec = gadjid.sid_over_mec(DAG, stray, ROW_TO_COL)
+    (_, least), (_, mean), (_, most) = over_mec.min, over_mec.mean, over_mec.max
+    assert over_mec.n_dags == 2
+    assert lower_mistakes == least == 0
+    assert least <= mean <= most <= upper_mistakes
+    cycle = np.array([[0, 2, 0, 2], [2, 0, 2, 0], [0, 2, 0, 2], [2, 0, 2, 0]], dtype=np.int8)
+    with pytest.raises(GadjidError):
+        gadjid.sid_over_mec(np.zeros((4, 4), dtype=np.int8), cycle, ROW_TO_COL)
+
+
+def sparse_dag(indices, indptr):
+    data = np.ones(len(indices), dtype=np.int8)
+    indices, indptr = np.array(indices, np.int32), np.array(indptr, np.int32)
+    return scipy.sparse.csr_matrix((data, indices, indptr), shape=(3, 3))
+
+
+@pytest.mark.parametrize(
+    "indices, indptr",
+    [
+        # 0 -> 2 before 0 -> 1
+        ([2, 1, 2], [0, 2, 3, 3]),
+        # 0 -> 1 twice
+        ([1, 1, 2], [0, 2, 3, 3]),
+    ],
+)
+def test_unsorted_sparse_indices_raise(indices, indptr):
+    with pytest.raises(GadjidError, match="sum_duplicates"):
+        gadjid.parent_aid(DAG, sparse_dag(indices, indptr), edge_direction=ROW_TO_COL)
+
+
+def test_malformed_sparse_arrays_raise():
+    decreasing = sparse_dag([1, 2, 2], [0, 2, 3, 3])
+    decreasing.indptr = np.array([0, 2, 1, 3], dtype=np.int32)
+    too_short = sparse_dag([1, 2, 2], [0, 2, 3, 3])
+    too_short.indptr = np.array([0, 2, 3], dtype=np.int32)
+    past_the_end = sparse_dag([1, 2, 2], [0, 2, 3, 3])
+    past_the_end.indptr = np.array([0, 2, 3, 4], dtype=np.int32)
+    for g_guess in [decreasing, too_short, past_the_end]:
+        with pytest.raises(GadjidError):
+            gadjid.parent_aid(DAG, g_guess, edge_direction=ROW_TO_COL)
+
+
+@pytest.mark.parametrize(
+    "distance",
+    [
+        gadjid.ancestor_aid,
+        gadjid.oset_aid,
+        gadjid.parent_aid,
+        functools.partial(gadjid.aid_with_stats, distance="ancestor_aid"),
+        gadjid.shd,
+        gadjid.sid,
+        gadjid.parent_set_report,
+    ],
+)
+def test_single_node_graphs_raise(distance):
+    single = np.zeros((1, 1), dtype=np.int8)
+    edge_list = gadjid.EdgeList(np.empty((0, 2), dtype=np.int64), n_nodes=1)
+    for graph in [single, scipy.sparse.csr_matrix(single), edge_list]:
+        with pytest.raises(SizeMismatchError):
+            distance(graph, graph, edge_direction=ROW_TO_COL)
+
+
+def test_single_node_graph_reports_raise():
+    single = np.zeros((1, 1), dtype=np.int8)
+    with pytest.raises(SizeMismatchError):
+        gadjid.identifiability_report(single, edge_direction=ROW_TO_COL)
+    with pytest.raises(SizeMismatchError):
+        gadjid.distance_by_components(single, single, "oset_aid", ROW_TO_COL)
+
+
+def test_invalid_options_list_valid_values():
+    with pytest.raises(TypeError, match='"from column to row", not "row to column"'):
+        gadjid.shd(DAG, DAG, edge_direction="row to column")
+    with pytest.raises(ValueError, match='"parent_aid", or "shd", not "sid"'):
+        gadjid.null_distribution(DAG, DAG, "sid", ROW_TO_COL)
+    with pytest.raises(ValueError, match='"oset_aid", or "parent_aid", not "shd"'):
+        gadjid.identification_stability([DAG, DAG], [(0, 1)], "shd", ROW_TO_COL)
+    with pytest.raises(ValueError, match='either "dag" or "pdag"'):
+        gadjid.expected_random_shd(DAG, 0.5, ROW_TO_COL, random_graphs="cpdag")
+
+
+def test_validate_pair_warns():
+    Gtrue = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    skeleton = np.array([[0, 2, 0], [0, 0, 2], [0, 0, 0]], dtype=np.int8)
+    with pytest.warns(gadjid.GraphPairWarning, match="skeleton"):
+        warnings = gadjid.validate_pair(Gtrue, skeleton, edge_direction=ROW_TO_COL)
+    assert len(warnings) == 1
+    with pytest.warns(gadjid.GraphPairWarning, match="nodes"):
+        gadjid.validate_pair(
+            Gtrue, np.zeros((2, 2), dtype=np.int8), edge_direction=ROW_TO_COL
+        )
@@ -0,0 +1,48 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    Graph,
+    NotAcyclicError,
+    SizeMismatchError,
+    ancestor_aid,
+    oset_aid,
+    parent_aid,
+    shd,
+)
+
+
+def random_dag(size, rng):
+    adj = np.triu(rng.random((size, size)) < 0.3, k=1).astype(np.int8)
+    perm = rng.permutation(size)
+    return adj[perm][:, perm]
+
+
+def test_resident_graphs_agree_with_distances():
+    rng = np.random.default_rng(0)
+    Gtrue = random_dag(12, rng)
+    truth = Graph(Gtrue, edge_direction="from row to column")
+    assert truth.n_nodes == 12
+    for _ in range(20):
+        Gguess = random_dag(12, rng)
+        guess = Graph(Gguess, edge_direction="from row to column")
+        for method, distance in [
+            (truth.ancestor_aid, ancestor_aid),
+            (truth.oset_aid, oset_aid),
+            (truth.parent_aid, parent_aid),
+        ]:
+            assert method(guess) == distance(
+                Gtrue, Gguess, edge_direction="from row to column"
+            )
+        assert truth.shd(guess) == shd(Gtrue, Gguess)
+
+
+def test_invalid_resident_graphs():
+    cycle = np.array([[0, 1, 0], [0, 0, 1], [1, 0, 0]], dtype=np.int8)
+    with pytest.raises(NotAcyclicError):
+        Graph(cycle, "from row to column")
+    small = Graph(np.zeros((3, 3), dtype=np.int8), "from row to column")
+    large = Graph(np.zeros((4, 4), dtype=np.int8), "from row to column")
+    with pytest.raises(SizeMismatchError):
+        small.parent_aid(large)
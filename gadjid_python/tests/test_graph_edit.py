@@ -0,0 +1,19 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import graph_edit_distance, shd
+
+# 0 -> 1, 0 -> 2, 0 -> 3, 1 -> 2
+TRUTH = np.array(
+    [[0, 1, 1, 1], [0, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+# 1 -> 0, 1 -> 2
+GUESS = np.array(
+    [[0, 0, 0, 0], [1, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_graph_edit_distance():
+    assert shd(TRUTH, GUESS)[1] == 3
+    assert graph_edit_distance(TRUTH, GUESS, 2) == (2, [0], True)
+    assert graph_edit_distance(TRUTH, GUESS, 3) == (3, [], True)
@@ -0,0 +1,32 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import edge_influence, shd
+
+ROW_TO_COL = "from row to column"
+
+# 0 -> 1
+TRUTH = np.array([[0, 1, 0], [0, 0, 0], [0, 0, 0]], dtype=np.int8)
+# 0 <- 1, 0 -> 2
+GUESS = np.array([[0, 0, 1], [1, 0, 0], [0, 0, 0]], dtype=np.int8)
+
+
+def test_edge_influence():
+    ranking = edge_influence(TRUTH, GUESS, "shd", edge_direction=ROW_TO_COL)
+    assert ranking == [(0, 2, True, -1, 0), (1, 0, True, 0, -1)]
+
+    # removing the extra edge corrects one mistake of the SHD
+    corrected = GUESS.copy()
+    corrected[0, 2] = 0
+    assert shd(TRUTH, corrected)[1] == shd(TRUTH, GUESS)[1] - 1
+
+
+def test_undirected_edges_are_not_reversed():
+    guess = np.array([[0, 2, 0], [2, 0, 0], [0, 0, 0]], dtype=np.int8)
+    ranking = edge_influence(TRUTH, guess, "oset_aid", edge_direction=ROW_TO_COL)
+    assert [(v, w, directed, reversed) for v, w, directed, _, reversed in ranking] == [
+        (0, 1, False, None)
+    ]
+    with pytest.raises(ValueError):
+        edge_influence(TRUTH, GUESS, "sid", edge_direction=ROW_TO_COL)
@@ -0,0 +1,32 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import InvalidPairsError, ancestor_aid, oset_aid, parent_aid, shd
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 <- 1 -> 2
+GUESS = np.array([[0, 0, 0], [1, 0, 1], [0, 0, 0]], dtype=np.int8)
+
+
+def test_known_edges():
+    assert shd(TRUTH, GUESS, known_edges=[]) == shd(TRUTH, GUESS)
+    # the reversed edge is known, so it is not graded
+    assert shd(TRUTH, GUESS, known_edges=[(1, 0)]) == (0.0, 0)
+    assert shd(TRUTH, GUESS, known_edges=[(1, 2)]) == (0.5, 1)
+
+    for distance in [ancestor_aid, oset_aid, parent_aid]:
+        assert distance(TRUTH, GUESS, "from row to column", known_edges=[]) == distance(
+            TRUTH, GUESS, "from row to column"
+        )
+        normalized, mistakes = distance(
+            TRUTH, GUESS, "from row to column", known_edges=[(0, 1)]
+        )
+        # four of the six (treatment, effect) pairs remain
+        assert normalized == mistakes / 4
+
+    with pytest.raises(InvalidPairsError):
+        shd(TRUTH, GUESS, known_edges=[(0, 3)])
+    with pytest.raises(InvalidPairsError):
+        ancestor_aid(TRUTH, GUESS, "from row to column", known_edges=[(1, 1)])
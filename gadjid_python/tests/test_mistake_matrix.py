@@ -0,0 +1,75 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    aid_detailed,
+    aid_per_treatment,
+    aid_selected_pairs,
+    aid_with_normalization,
+    ancestor_aid,
+    oset_aid,
+    parent_aid,
+)
+
+AIDS = [
+    ("ancestor_aid", ancestor_aid),
+    ("oset_aid", oset_aid),
+    ("parent_aid", parent_aid),
+]
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 -> 1 <- 2
+GUESS = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+
+
+def test_mistake_matrix():
+    for name, distance in AIDS:
+        normalized, mistakes, matrix = aid_detailed(
+            TRUTH, GUESS, name, "from row to column"
+        )
+        assert (normalized, mistakes) == distance(TRUTH, GUESS, "from row to column")
+        assert matrix.dtype == bool and matrix.shape == (3, 3)
+        assert matrix.sum() == mistakes
+        assert not matrix.diagonal().any()
+
+    _, _, matrix = aid_detailed(TRUTH, GUESS, "parent_aid", "from row to column")
+    for t, y in [(t, y) for t in range(3) for y in range(3) if t != y]:
+        _, mistakes, _ = aid_selected_pairs(
+            TRUTH, GUESS, [(t, y)], "parent_aid", "from row to column"
+        )
+        assert matrix[t, y] == (mistakes == 1)
+
+    # rows are treatments irrespective of the edge direction
+    _, _, transposed = aid_detailed(
+        TRUTH.T, GUESS.T, "parent_aid", "from column to row"
+    )
+    assert (transposed == matrix).all()
+
+
+def test_per_treatment_mistakes():
+    for name, _ in AIDS:
+        _, mistakes, matrix = aid_detailed(TRUTH, GUESS, name, "from row to column")
+        per_treatment_mistakes = aid_per_treatment(
+            TRUTH, GUESS, name, "from row to column"
+        )
+        assert per_treatment_mistakes.dtype == np.uint64
+        assert per_treatment_mistakes.sum() == mistakes
+        assert (per_treatment_mistakes == matrix.sum(axis=1)).all()
+
+
+def test_normalization():
+    # 0 -> 1 -> 2, and the guess 0 -- 1 -> 2 is not amenable for (0, 1), (0, 2), and (1, 0)
+    Gcpdag = np.array([[0, 2, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    for name, distance in AIDS:
+        normalized, mistakes, comparisons = aid_with_normalization(
+            TRUTH, Gcpdag, "all_pairs", name, "from row to column"
+        )
+        assert (normalized, mistakes) == distance(TRUTH, Gcpdag, "from row to column")
+        assert comparisons == 6
+        assert aid_with_normalization(
+            TRUTH, Gcpdag, "graded_pairs", name, "from row to column"
+        ) == (0.0, 0, 3)
+        with pytest.raises(ValueError):
+            aid_with_normalization(TRUTH, Gcpdag, "amenable", name, "from row to column")
@@ -0,0 +1,43 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import aid_with_pair_weights, ancestor_aid, oset_aid, parent_aid
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 <- 1 -> 2
+GUESS = np.array([[0, 0, 0], [1, 0, 1], [0, 0, 0]], dtype=np.int8)
+
+
+def test_pair_weights():
+    for name, distance in [
+        ("ancestor_aid", ancestor_aid),
+        ("oset_aid", oset_aid),
+        ("parent_aid", parent_aid),
+    ]:
+        unit = np.ones((3, 3), dtype=np.int64)
+        assert aid_with_pair_weights(
+            TRUTH, GUESS, unit, name, "from row to column"
+        ) == distance(TRUTH, GUESS, "from row to column")
+        _, weighted = aid_with_pair_weights(
+            TRUTH, GUESS, 2 * unit, name, "from row to column"
+        )
+        assert weighted == 2 * distance(TRUTH, GUESS, "from row to column")[1]
+
+    with pytest.raises(ValueError):
+        aid_with_pair_weights(
+            TRUTH,
+            GUESS,
+            -np.ones((3, 3), dtype=np.int64),
+            "oset_aid",
+            "from row to column",
+        )
+    with pytest.raises(ValueError):
+        aid_with_pair_weights(
+            TRUTH,
+            GUESS,
+            np.ones((2, 2), dtype=np.int64),
+            "oset_aid",
+            "from row to column",
+        )
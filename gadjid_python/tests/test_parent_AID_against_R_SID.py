@@ -3,24 +3,11 @@ from pathlib import Path
 
 import numpy as np
 
-from gadjid import parent_aid
-
+from gadjid import load_corpus_graph, parent_aid
 
 TESTGRAPHS_DIR = Path(__file__).parent.parent.parent / "testgraphs"
 
 
-def load_trlpt(name):
-    trplt = np.loadtxt(
-        TESTGRAPHS_DIR / f"100-node-DAG-{name:.0f}.mtx",
-        skiprows=2,
-    )
-    size = 100
-    adj = np.zeros((size, size)).astype(np.int8)
-    for i, j in trplt:
-        adj[int(i) - 1, int(j) - 1] = 1
-    return adj
-
-
 def test_parent_AID_against_R_SID():
     testcases = np.loadtxt(
         TESTGRAPHS_DIR / "SID-100-node-DAGs.csv",
@@ -31,8 +18,8 @@ def test_parent_AID_against_R_SID():
     for true_name, guess_name, _, rsid in testcases:
         true_name = int(true_name)
         guess_name = int(guess_name)
-        Gtrue = load_trlpt(true_name)
-        Gguess = load_trlpt(guess_name)
+        Gtrue = load_corpus_graph(f"100-node-DAG-{true_name}", "from row to column")
+        Gguess = load_corpus_graph(f"100-node-DAG-{guess_name}", "from row to column")
         sid = parent_aid(Gtrue, Gguess, edge_direction="from row to column")
         assert sid[1] == int(rsid), (
             f"failed for sid({true_name}, {guess_name}):"
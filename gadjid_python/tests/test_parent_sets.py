@@ -0,0 +1,25 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import parent_set_report
+
+# 0 -> 1 -> 2, 3 -> 1, 3 -> 2
+TRUTH = np.array(
+    [[0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 0], [0, 1, 1, 0]], dtype=np.int8
+)
+# 0 -> 1 -> 2
+GUESS = np.array(
+    [[0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_parent_set_report():
+    report = parent_set_report(TRUTH, GUESS, "from row to column")
+    assert len(report) == 4
+    # missing the confounder 3 of the effect of 1 on 2
+    assert report[1] == ([0, 3], [0], 0.5, False)
+    assert report[3] == ([], [], 1.0, True)
+
+    # the edge direction determines which nodes are parents
+    report = parent_set_report(TRUTH.T, GUESS.T, "from column to row")
+    assert report[1] == ([0, 3], [0], 0.5, False)
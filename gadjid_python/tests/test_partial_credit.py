@@ -0,0 +1,31 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import aid_partial_credit, ancestor_aid, oset_aid, parent_aid
+
+# 0 -> 1
+TRUTH = np.array([[0, 1], [0, 0]], dtype=np.int8)
+# 0 -- 1
+GUESS = np.array([[0, 1], [1, 0]], dtype=np.int8)
+
+
+def test_partial_credit():
+    for name, distance in [
+        ("ancestor_aid", ancestor_aid),
+        ("oset_aid", oset_aid),
+        ("parent_aid", parent_aid),
+    ]:
+        normalized, mistakes = distance(TRUTH, GUESS, "from row to column")
+        assert aid_partial_credit(TRUTH, GUESS, name, "from row to column", 0.0) == (
+            normalized,
+            float(mistakes),
+        )
+        # the undirected edge is a "don't know" answer for both pairs
+        assert aid_partial_credit(TRUTH, GUESS, name, "from row to column", 0.5) == (
+            0.5,
+            1.0,
+        )
+
+    with pytest.raises(ValueError):
+        aid_partial_credit(TRUTH, GUESS, "ancestor_aid", "from row to column", 1.5)
@@ -0,0 +1,22 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import shd, shd_min_over_permutations
+
+# 0 -> 1 -> 2 -> 3, 0 -> 2
+G1 = np.array(
+    [[0, 1, 1, 0], [0, 0, 1, 0], [0, 0, 0, 1], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_shd_min_over_permutations():
+    labels = np.array([2, 0, 3, 1])
+    # node v of G1 is node labels[v] of G2
+    g2 = np.zeros_like(G1)
+    g2[np.ix_(labels, labels)] = G1
+    assert shd(G1, g2)[1] > 0
+
+    normalized, mistakes, permutation = shd_min_over_permutations(G1, g2)
+    assert (normalized, mistakes) == (0.0, 0)
+    assert permutation == labels.tolist()
+    assert shd(G1, g2[np.ix_(permutation, permutation)]) == (0.0, 0)
@@ -0,0 +1,36 @@
+# SPDX-License-Identifier: MPL-2.0
+import json
+
+import numpy as np
+import pytest
+
+from gadjid import distance_with_provenance, parent_aid
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 -> 1 <- 2
+GUESS = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+
+
+def test_distance_with_provenance():
+    result = distance_with_provenance(
+        TRUTH, GUESS, "parent_aid", "from row to column"
+    )
+    assert result.value() == parent_aid(TRUTH, GUESS, "from row to column")
+    assert result.distance == "parent_aid"
+    assert result.options == {"edge_direction": "from row to column"}
+    assert result.truth_fingerprint != result.guess_fingerprint
+
+    # the fingerprints only depend on the edges, not on how they are coded
+    transposed = distance_with_provenance(
+        TRUTH.T, GUESS.T, "parent_aid", "from column to row"
+    )
+    assert transposed.truth_fingerprint == result.truth_fingerprint
+
+    logged = json.loads(result.to_json())
+    assert logged["mistakes"] == result.mistakes
+    assert logged["provenance"]["gadjid_version"] == result.gadjid_version
+    assert logged["provenance"]["options"] == result.options
+
+    with pytest.raises(ValueError):
+        distance_with_provenance(TRUTH, GUESS, "sid", "from row to column")
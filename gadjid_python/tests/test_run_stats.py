@@ -0,0 +1,29 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import aid_with_stats, ancestor_aid, oset_aid, parent_aid
+
+# 0 -> 1 -> 2, 3 is isolated
+TRUTH = np.array(
+    [[0, 1, 0, 0], [0, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+# 0 <- 1 -> 2
+GUESS = np.array(
+    [[0, 0, 0, 0], [1, 0, 1, 0], [0, 0, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_run_stats():
+    for name, distance in [
+        ("ancestor_aid", ancestor_aid),
+        ("oset_aid", oset_aid),
+        ("parent_aid", parent_aid),
+    ]:
+        result, (wall_time, treatment_times, visited_triplets) = aid_with_stats(
+            TRUTH, GUESS, name, "from row to column"
+        )
+        assert result == distance(TRUTH, GUESS, "from row to column")
+        # the isolated node is not searched from
+        assert [treatment for treatment, _ in treatment_times] == [0, 1, 2]
+        assert all(0 <= time <= wall_time for _, time in treatment_times)
+        assert visited_triplets > 0
@@ -0,0 +1,40 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import sample_configuration, sample_scm_dag
+
+
+def test_sample_configuration():
+    treatments, effect, adjustment_set = sample_configuration(
+        10, n_treatments=3, seed=1
+    )
+    assert len(treatments) == 3 and treatments == sorted(treatments)
+    assert 1 <= len(adjustment_set) <= 6
+    nodes = treatments + [effect] + adjustment_set
+    assert len(set(nodes)) == len(nodes)
+    assert all(0 <= node < 10 for node in nodes)
+    # the same seed gives the same configuration
+    assert sample_configuration(10, n_treatments=3, seed=1) == (
+        treatments,
+        effect,
+        adjustment_set,
+    )
+    with pytest.raises(ValueError):
+        sample_configuration(5, n_treatments=3, adjustment_set_size=2)
+
+
+def test_sample_scm_dag():
+    dag = sample_scm_dag(20, "scale_free", "from row to column", parents_per_node=2)
+    assert dag.shape == (20, 20) and dag.dtype == np.int8
+    # all but the first two variables in the causal order have two parents
+    assert (dag.sum(axis=0) == 2).sum() == 18
+    # the same seed gives the same DAG, coded transposed for the other edge direction
+    transposed = sample_scm_dag(
+        20, "scale_free", "from column to row", parents_per_node=2
+    )
+    assert np.array_equal(dag, transposed.T)
+    dag = sample_scm_dag(10, "erdos_renyi", "from row to column", edge_density=0.0)
+    assert not dag.any()
+    with pytest.raises(ValueError):
+        sample_scm_dag(10, "barabasi", "from row to column")
@@ -0,0 +1,69 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import load_with_convention, resolve_missing, sanitize_adjacency
+
+
+def test_sanitize_adjacency():
+    # 0 -- 1 coded on one side only, and a self-loop at 2, as floats
+    matrix = np.array([[0, 2, 0], [0, 0, 1], [0, 0, 1]], dtype=np.float64)
+    with pytest.warns(UserWarning):
+        fixed, changes = sanitize_adjacency(matrix, "gadjid")
+    assert fixed.dtype == np.int8
+    assert np.array_equal(fixed, np.array([[0, 2, 0], [2, 0, 1], [0, 0, 0]]))
+    assert len(changes) == 3
+    assert changes[0] == "cast matrix of dtype float64 to int8"
+
+
+def test_sanitize_adjacency_rejects_lossy_casts():
+    with pytest.raises(ValueError):
+        sanitize_adjacency(np.array([[0, 0.5], [0, 0]]), "gadjid")
+    with pytest.raises(ValueError):
+        sanitize_adjacency(np.zeros((2, 2), dtype=np.int8), "no convention")
+
+
+def test_load_with_convention():
+    # 0 -> 1 and 1 -- 2 in pcalg amat.cpdag coding, where amat[b, a] = 1 codes a -> b
+    amat = np.array([[0, 0, 0], [1, 0, 1], [0, 1, 0]], dtype=np.int8)
+    expected = np.array([[0, 1, 0], [0, 0, 2], [0, 2, 0]], dtype=np.int8)
+    loaded = load_with_convention(amat, "from row to column", convention="pcalg amat.cpdag")
+    assert loaded.dtype == np.int8
+    assert np.array_equal(loaded, expected)
+    assert np.array_equal(load_with_convention(amat, "from row to column"), expected)
+    assert np.array_equal(
+        load_with_convention(amat, "from column to row", convention="pcalg amat.cpdag"),
+        expected.T,
+    )
+
+    # 0 -- 1 coded on one side only is loaded with a warning
+    with pytest.warns(UserWarning):
+        loaded = load_with_convention(
+            np.array([[0, 2], [0, 0]], dtype=np.int8), "from row to column"
+        )
+    assert np.array_equal(loaded, np.array([[0, 2], [2, 0]]))
+
+    # a single 1 codes 0 -> 1 in gadjid coding but 1 -> 0 in pcalg amat.cpdag coding
+    with pytest.raises(ValueError):
+        load_with_convention(np.array([[0, 1], [0, 0]], dtype=np.int8), "from row to column")
+    with pytest.raises(ValueError):
+        load_with_convention(np.zeros((2, 2), dtype=np.int8), "from row to column", "unknown")
+
+
+def test_resolve_missing():
+    # 0 -> 1, unknown status between 1 and 2, coded as NaN and as -1
+    matrix = np.array([[0, 1, 0], [0, 0, np.nan], [0, -1, 0]])
+    with pytest.warns(UserWarning):
+        resolved, n_missing, n_pairs = resolve_missing(matrix, "undirected", missing_code=-1)
+    assert resolved.dtype == np.int8
+    assert np.array_equal(resolved, np.array([[0, 1, 0], [0, 0, 2], [0, 2, 0]]))
+    assert (n_missing, n_pairs) == (2, 1)
+
+    with pytest.warns(UserWarning):
+        resolved, n_missing, n_pairs = resolve_missing(matrix, "absent", missing_code=-1)
+    assert np.array_equal(resolved, np.array([[0, 1, 0], [0, 0, 0], [0, 0, 0]]))
+
+    with pytest.raises(ValueError):
+        resolve_missing(matrix, "error")
+    with pytest.raises(ValueError):
+        resolve_missing(matrix, "unknown")
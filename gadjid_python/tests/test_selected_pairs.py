@@ -0,0 +1,202 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    GadjidError,
+    InvalidPairsError,
+    SizeMismatchError,
+    aid_selected_effect_sets,
+    aid_selected_pairs,
+    ancestor_aid,
+    conditional_aid,
+    oset_aid,
+    oset_aid_selected_treatment_sets,
+    parent_aid,
+    sid_selected_pairs,
+)
+
+AIDS = ["ancestor_aid", "oset_aid", "parent_aid"]
+
+
+def random_dag(size, rng):
+    adj = np.triu(rng.random((size, size)) < 0.3, k=1).astype(np.int8)
+    perm = rng.permutation(size)
+    return adj[perm][:, perm]
+
+
+def test_selected_pairs_agree_with_all_pairs():
+    rng = np.random.default_rng(0)
+    size = 15
+    all_pairs = [(t, y) for t in range(size) for y in range(size) if t != y]
+    for _ in range(5):
+        Gtrue = random_dag(size, rng)
+        Gguess = random_dag(size, rng)
+        for name, all_pairs_distance in zip(AIDS, [ancestor_aid, oset_aid, parent_aid]):
+            expected = all_pairs_distance(
+                Gtrue, Gguess, edge_direction="from row to column"
+            )
+            assert expected + (len(all_pairs),) == aid_selected_pairs(
+                Gtrue, Gguess, all_pairs, name, edge_direction="from row to column"
+            )
+            mistakes = sum(
+                aid_selected_pairs(
+                    Gtrue, Gguess, [pair], name, edge_direction="from row to column"
+                )[1]
+                for pair in all_pairs
+            )
+            assert mistakes == expected[1]
+            population = sum(
+                aid_selected_pairs(
+                    Gtrue,
+                    Gguess,
+                    [pair],
+                    name,
+                    edge_direction="from row to column",
+                    normalization="population",
+                )[0]
+                for pair in all_pairs
+            )
+            assert population == pytest.approx(expected[0])
+
+
+def test_repeated_and_invalid_pairs():
+    Gtrue = np.array([[0, 1, 1], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    Gguess = Gtrue.T.copy()
+    pairs = [(0, 1), (2, 1), (0, 1)]
+    normalized, mistakes, comparisons = aid_selected_pairs(
+        Gtrue, Gguess, pairs, "parent_aid", edge_direction="from row to column"
+    )
+    assert comparisons == 2
+    assert normalized == mistakes / comparisons
+    for invalid in [[], [(0, 3)], [(1, 1)]]:
+        with pytest.raises(InvalidPairsError):
+            aid_selected_pairs(
+                Gtrue, Gguess, invalid, "parent_aid", edge_direction="from row to column"
+            )
+
+
+def test_sid_selected_pairs_requires_dags():
+    rng = np.random.default_rng(1)
+    Gtrue = random_dag(10, rng)
+    Gguess = random_dag(10, rng)
+    pairs = [(0, 9), (3, 1), (5, 7)]
+    assert sid_selected_pairs(
+        Gtrue, Gguess, pairs, edge_direction="from row to column"
+    ) == aid_selected_pairs(
+        Gtrue, Gguess, pairs, "parent_aid", edge_direction="from row to column"
+    )
+    Gcpdag = np.array([[0, 2, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    with pytest.raises(GadjidError):
+        sid_selected_pairs(
+            Gcpdag, Gcpdag, [(0, 2)], edge_direction="from row to column"
+        )
+    with pytest.raises(InvalidPairsError):
+        sid_selected_pairs(
+            Gtrue, Gguess, [(1, 1)], edge_direction="from row to column"
+        )
+
+
+def test_hand_picked_pairs():
+    # 0 -> 1 -> 2 -> 3, and the guess reverses 1 -> 2
+    Gtrue = np.diag(np.ones(3, dtype=np.int8), k=1)
+    Gguess = Gtrue.copy()
+    Gguess[1, 2], Gguess[2, 1] = 0, 1
+    hand_picked = [(1, 2), (2, 3)]
+    for name in AIDS:
+        normalized, mistakes, comparisons = aid_selected_pairs(
+            Gtrue,
+            Gguess,
+            pairs=hand_picked,
+            distance=name,
+            edge_direction="from row to column",
+        )
+        assert comparisons == 2
+        assert mistakes == sum(
+            aid_selected_pairs(
+                Gtrue, Gguess, [pair], name, edge_direction="from row to column"
+            )[1]
+            for pair in hand_picked
+        )
+
+
+def test_treatment_sets():
+    rng = np.random.default_rng(1)
+    size = 10
+    Gtrue = random_dag(size, rng)
+    Gguess = random_dag(size, rng)
+    pairs = [(t, y) for t in range(size) for y in range(size) if t != y]
+    singletons = [([t], y) for t, y in pairs]
+    assert aid_selected_pairs(
+        Gtrue, Gguess, pairs, "oset_aid", edge_direction="from row to column"
+    ) == oset_aid_selected_treatment_sets(
+        Gtrue, Gguess, singletons, edge_direction="from row to column"
+    )
+    joint = [([0, 1], 2), ([1, 0], 2), ([3, 4, 5], 9)]
+    _, mistakes, comparisons = oset_aid_selected_treatment_sets(
+        Gtrue, Gtrue, joint, edge_direction="from row to column"
+    )
+    assert (mistakes, comparisons) == (0, 2)
+
+
+def test_effect_sets():
+    rng = np.random.default_rng(2)
+    size = 10
+    Gtrue = random_dag(size, rng)
+    Gguess = random_dag(size, rng)
+    pairs = [(t, y) for t in range(size) for y in range(size) if t != y]
+    singletons = [(t, [y]) for t, y in pairs]
+    for name in AIDS:
+        assert aid_selected_pairs(
+            Gtrue, Gguess, pairs, name, edge_direction="from row to column"
+        ) == aid_selected_effect_sets(
+            Gtrue, Gguess, singletons, name, edge_direction="from row to column"
+        )
+        with pytest.raises(InvalidPairsError):
+            aid_selected_effect_sets(
+                Gtrue, Gguess, [(0, [])], name, edge_direction="from row to column"
+            )
+
+
+def test_conditional_aid():
+    rng = np.random.default_rng(3)
+    size = 10
+    Gtrue = random_dag(size, rng)
+    triples = [(0, 1, [2, 3]), (0, 1, [3, 2]), (4, 5, [])]
+    assert conditional_aid(
+        Gtrue, Gtrue, triples, edge_direction="from row to column"
+    ) == (0.0, 0, 2)
+    with pytest.raises(InvalidPairsError):
+        conditional_aid(
+            Gtrue, Gtrue, [(0, 1, [1])], edge_direction="from row to column"
+        )
+
+
+def test_masks_select_pairs():
+    rng = np.random.default_rng(4)
+    size = 10
+    Gtrue = random_dag(size, rng)
+    Gguess = random_dag(size, rng)
+    treatments = np.zeros(size, dtype=bool)
+    treatments[[0, 3]] = True
+    effects = np.zeros(size, dtype=bool)
+    effects[[3, 5, 7]] = True
+    pairs = [(0, 3), (0, 5), (0, 7), (3, 5), (3, 7)]
+    for name in AIDS:
+        assert aid_selected_pairs(
+            Gtrue,
+            Gguess,
+            (treatments, effects),
+            name,
+            edge_direction="from row to column",
+        ) == aid_selected_pairs(
+            Gtrue, Gguess, pairs, name, edge_direction="from row to column"
+        )
+    with pytest.raises(SizeMismatchError):
+        aid_selected_pairs(
+            Gtrue,
+            Gguess,
+            (treatments[:-1], effects),
+            "oset_aid",
+            edge_direction="from row to column",
+        )
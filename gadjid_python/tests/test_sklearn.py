@@ -0,0 +1,45 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+import gadjid
+from gadjid.sklearn import make_scorer
+
+
+ROW_TO_COL = "from row to column"
+
+
+def random_dag(size, rng):
+    adj = np.triu(rng.random((size, size)) < 0.3, k=1).astype(np.int8)
+    perm = rng.permutation(size)
+    return adj[perm][:, perm]
+
+
+@pytest.mark.parametrize(
+    "metric", ["ancestor_aid", "oset_aid", "parent_aid", "shd", "sid"]
+)
+def test_scorers_agree_with_distances(metric):
+    rng = np.random.default_rng(0)
+    Gtrue = random_dag(15, rng)
+    Gguess = random_dag(15, rng)
+    distance = getattr(gadjid, metric)
+    if metric == "shd":
+        expected = distance(Gtrue, Gguess)
+    else:
+        expected = distance(Gtrue, Gguess, edge_direction=ROW_TO_COL)
+
+    scorer = make_scorer(metric, edge_direction=ROW_TO_COL)
+    assert scorer(Gtrue, Gguess) == -expected[0]
+    assert scorer(Gtrue, Gtrue) == 0
+    assert scorer.greater_is_better
+    assert scorer.__name__ == f"neg_{metric}"
+
+    loss = make_scorer(
+        metric, greater_is_better=False, normalized=False, edge_direction=ROW_TO_COL
+    )
+    assert loss(Gtrue, Gguess) == expected[1]
+
+
+def test_unknown_metric():
+    with pytest.raises(ValueError, match='not "aid"'):
+        make_scorer("aid", edge_direction=ROW_TO_COL)
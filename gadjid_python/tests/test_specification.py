@@ -0,0 +1,45 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import InvalidPairsError, specification_violations
+
+# 0 -> 1, 2 -> 1, 2 -- 3
+GUESS = np.array(
+    [[0, 1, 0, 0], [0, 0, 0, 0], [0, 1, 0, 2], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_specification_violations():
+    (normalized, violated), weighted, violations = specification_violations(
+        GUESS, [(0, 1), (1, 2)], [(3, 2)], "from row to column"
+    )
+    assert violations == [("reversed required", 1, 2), ("forbidden undirected", 3, 2)]
+    assert (normalized, violated) == (2 / 3, 2)
+    assert weighted == 2.0
+
+    _, weighted, _ = specification_violations(
+        GUESS,
+        [(0, 1), (1, 2)],
+        [(3, 2)],
+        "from row to column",
+        weights={"forbidden undirected": 0.5},
+    )
+    assert weighted == 1.5
+
+    # read from column to row, the guess has 1 -> 0 and 1 -> 2
+    _, _, violations = specification_violations(
+        GUESS, [(0, 1), (1, 2)], [], "from column to row"
+    )
+    assert violations == [("reversed required", 0, 1)]
+
+
+def test_specification_violations_rejects_invalid_specifications():
+    with pytest.raises(InvalidPairsError):
+        specification_violations(GUESS, [(0, 1)], [(0, 1)], "from row to column")
+    with pytest.raises(InvalidPairsError):
+        specification_violations(GUESS, [(0, 4)], [], "from row to column")
+    with pytest.raises(ValueError):
+        specification_violations(
+            GUESS, [], [], "from row to column", weights={"unknown": 1.0}
+        )
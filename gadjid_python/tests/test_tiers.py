@@ -0,0 +1,39 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import InvalidPairsError, SizeMismatchError, shd, tiered_distance
+
+ROW_TO_COL = "from row to column"
+
+# guess: 0 -> 1, 2 -> 0
+GUESS = np.array([[0, 1, 0], [0, 0, 0], [1, 0, 0]], dtype=np.int8)
+
+
+def test_tiers_and_skeleton():
+    # 0 and 1 precede 2, which is adjacent to both
+    normalized, mistakes, details = tiered_distance(
+        [0, 0, 1], GUESS, edge_direction=ROW_TO_COL, skeleton=[(0, 2), (1, 2)]
+    )
+    assert (normalized, mistakes, details) == (1.0, 3, (1, 1, 1))
+
+    normalized, mistakes, details = tiered_distance(
+        [0, 0, 1], GUESS, edge_direction=ROW_TO_COL
+    )
+    assert (mistakes, details) == (1, (1, 0, 0))
+
+
+def test_full_order_and_skeleton_grade_like_shd():
+    truth = np.array([[0, 1, 1], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+    skeleton = [(0, 1), (0, 2), (1, 2)]
+    _, mistakes, _ = tiered_distance(
+        [0, 1, 2], GUESS, edge_direction=ROW_TO_COL, skeleton=skeleton
+    )
+    assert mistakes == shd(truth, GUESS)[1]
+
+
+def test_invalid_tiered_truths():
+    with pytest.raises(SizeMismatchError):
+        tiered_distance([0, 1], GUESS, edge_direction=ROW_TO_COL)
+    with pytest.raises(InvalidPairsError):
+        tiered_distance([0, 1, 2], GUESS, edge_direction=ROW_TO_COL, skeleton=[(0, 3)])
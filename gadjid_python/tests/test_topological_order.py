@@ -0,0 +1,25 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import topological_order
+
+# 2 -> 0, 1 -> 3, 2 -> 3
+DAG = np.array(
+    [[0, 0, 0, 0], [0, 0, 0, 1], [1, 0, 0, 1], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_topological_order():
+    assert topological_order(DAG, "from row to column") == [1, 2, 0, 3]
+    assert topological_order(DAG, "from row to column", tie_break="degree") == [2, 0, 1, 3]
+    # read from column to row, the edges are 0 -> 2, 3 -> 1, 3 -> 2
+    assert topological_order(DAG, "from column to row") == [0, 3, 1, 2]
+
+    order = topological_order(DAG, "from row to column", tie_break="random", seed=1)
+    assert sorted(order) == [0, 1, 2, 3]
+    assert order.index(2) < order.index(0) and order.index(1) < order.index(3)
+    assert topological_order(DAG, "from row to column", tie_break="random", seed=1) == order
+
+    with pytest.raises(ValueError):
+        topological_order(DAG, "from row to column", tie_break="unknown")
@@ -0,0 +1,74 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    GadjidError,
+    NotAcyclicError,
+    parent_aid,
+    repair_path,
+    trajectory_aid,
+)
+
+ROW_TO_COL = "from row to column"
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 1 -> 0, 1 -> 2
+INITIAL = np.array([[0, 0, 0], [1, 0, 1], [0, 0, 0]], dtype=np.int8)
+
+
+def test_trajectory_matches_snapshots():
+    operations = [("reverse", 1, 0), ("insert", 0, 2), ("delete", 1, 2)]
+    trajectory = trajectory_aid(TRUTH, INITIAL, operations, edge_direction=ROW_TO_COL)
+    assert len(trajectory) == 4
+
+    snapshot = INITIAL.copy()
+    assert trajectory[0] == parent_aid(TRUTH, snapshot, edge_direction=ROW_TO_COL)
+    for (operation, v, w), distance in zip(operations, trajectory[1:]):
+        if operation != "insert":
+            snapshot[v, w] = 0
+        if operation != "delete":
+            snapshot[(v, w) if operation == "insert" else (w, v)] = 1
+        assert distance == parent_aid(TRUTH, snapshot, edge_direction=ROW_TO_COL)
+
+
+def test_invalid_operations():
+    with pytest.raises(ValueError, match="does not apply"):
+        trajectory_aid(TRUTH, INITIAL, [("delete", 0, 1)], edge_direction=ROW_TO_COL)
+    with pytest.raises(ValueError, match='not "flip"'):
+        trajectory_aid(TRUTH, INITIAL, [("flip", 1, 0)], edge_direction=ROW_TO_COL)
+    with pytest.raises(NotAcyclicError):
+        trajectory_aid(
+            TRUTH,
+            TRUTH,
+            [("insert", 2, 0)],
+            edge_direction=ROW_TO_COL,
+        )
+
+
+def test_tidy_trajectory():
+    operations = [("reverse", 1, 0), ("insert", 0, 2)]
+    trajectory = trajectory_aid(TRUTH, INITIAL, operations, edge_direction=ROW_TO_COL)
+    table = trajectory_aid(
+        TRUTH, INITIAL, operations, edge_direction=ROW_TO_COL, tidy=True
+    )
+    assert list(table) == ["distance", "step", "normalized", "mistakes"]
+    assert table["distance"] == ["parent_aid"] * 3
+    assert (table["step"] == [0, 1, 2]).all()
+    assert list(zip(table["normalized"], table["mistakes"])) == trajectory
+
+
+def test_repair_path():
+    # 1 -> 0, 0 -> 2
+    guess = np.array([[0, 0, 1], [1, 0, 0], [0, 0, 0]], dtype=np.int8)
+    operations, shd, distances = repair_path(TRUTH, guess, edge_direction=ROW_TO_COL)
+    assert operations == [("reverse", 1, 0), ("insert", 1, 2), ("delete", 0, 2)]
+    assert shd == [3, 2, 1, 0]
+    assert distances == trajectory_aid(
+        TRUTH, guess, operations, edge_direction=ROW_TO_COL
+    )
+
+    cpdag = np.array([[0, 2, 0], [0, 0, 0], [0, 0, 0]], dtype=np.int8)
+    with pytest.raises(GadjidError):
+        repair_path(TRUTH, cpdag, edge_direction=ROW_TO_COL)
@@ -0,0 +1,42 @@
+# SPDX-License-Identifier: MPL-2.0
+import json
+
+import numpy as np
+import pytest
+
+from gadjid import InvalidPairsError, SizeMismatchError, to_cytoscape_json
+
+# 2 -> 0 -> 1, 2 -> 1, 0 -- 3
+PDAG = np.array(
+    [[0, 1, 0, 2], [0, 0, 0, 0], [1, 1, 0, 0], [0, 0, 0, 0]], dtype=np.int8
+)
+
+
+def test_to_cytoscape_json():
+    elements = json.loads(
+        to_cytoscape_json(
+            PDAG,
+            edge_direction="from row to column",
+            mistakes=[3, 0, 1, 2],
+            not_amenable_from=0,
+            optimal_adjustment_set_for=(0, 1),
+        )
+    )["elements"]
+    assert [node["data"]["id"] for node in elements["nodes"]] == ["0", "1", "2", "3"]
+    assert elements["nodes"][3]["data"] == {
+        "id": "3",
+        "mistakes": 2,
+        "not_amenable": True,
+        "in_optimal_adjustment_set": False,
+    }
+    assert {
+        (edge["data"]["source"], edge["data"]["target"], edge["data"]["directed"])
+        for edge in elements["edges"]
+    } == {("0", "1", True), ("0", "3", False), ("2", "0", True), ("2", "1", True)}
+
+
+def test_to_cytoscape_json_rejects_invalid_layers():
+    with pytest.raises(SizeMismatchError):
+        to_cytoscape_json(PDAG, edge_direction="from row to column", mistakes=[1])
+    with pytest.raises(InvalidPairsError):
+        to_cytoscape_json(PDAG, edge_direction="from row to column", not_amenable_from=4)
@@ -0,0 +1,26 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+
+from gadjid import vstructure_distance
+
+# 0 -> 1 -> 2
+CHAIN = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 <- 1 <- 2, in the same Markov equivalence class
+REVERSED_CHAIN = np.array([[0, 0, 0], [1, 0, 0], [0, 1, 0]], dtype=np.int8)
+# 0 -> 1 <- 2
+COLLIDER = np.array([[0, 1, 0], [0, 0, 0], [0, 1, 0]], dtype=np.int8)
+
+
+def test_vstructure_distance():
+    assert vstructure_distance(CHAIN, REVERSED_CHAIN, "from row to column") == (
+        (0.0, 0),
+        (0, 0, 0),
+    )
+    assert vstructure_distance(CHAIN, COLLIDER, "from row to column") == (
+        (1.0, 1),
+        (0, 1, 0),
+    )
+    assert vstructure_distance(COLLIDER, COLLIDER.T, "from column to row") == (
+        (0.0, 0),
+        (0, 0, 1),
+    )
@@ -0,0 +1,74 @@
+# SPDX-License-Identifier: MPL-2.0
+import numpy as np
+import pytest
+
+from gadjid import (
+    GadjidError,
+    confusion,
+    cpdag_of,
+    min_shd_over_mec,
+    shd,
+    shd_detailed,
+    shd_markov_equivalent,
+    weighted_shd,
+)
+
+# 0 -> 1 -> 2
+TRUTH = np.array([[0, 1, 0], [0, 0, 1], [0, 0, 0]], dtype=np.int8)
+# 0 <- 1 -- 2, 0 -> 2
+GUESS = np.array([[0, 0, 1], [1, 0, 2], [0, 0, 0]], dtype=np.int8)
+
+
+def test_weighted_shd():
+    costs = np.arange(16, dtype=np.float64).reshape(4, 4)
+    cost, counts = weighted_shd(TRUTH, GUESS, costs, "from row to column")
+    # an extra edge, a reversed edge, and an undirected edge instead of a directed one
+    assert cost == costs[0, 1] + costs[1, 2] + costs[1, 3]
+    assert counts.sum() == 3 and counts[0, 1] == counts[1, 2] == counts[1, 3] == 1
+
+    unit = np.ones((4, 4)) - np.eye(4)
+    cost, _ = weighted_shd(TRUTH, GUESS, unit, "from row to column")
+    assert cost == shd(TRUTH, GUESS)[1]
+
+    with pytest.raises(ValueError):
+        weighted_shd(TRUTH, GUESS, np.ones((3, 3)), "from row to column")
+
+
+def test_shd_detailed():
+    # an extra edge, a reversed edge, and an undirected edge instead of a directed one
+    assert shd_detailed(TRUTH, GUESS) == (shd(TRUTH, GUESS), (0, 1, 1, 1))
+    assert shd_detailed(GUESS, TRUTH) == (shd(GUESS, TRUTH), (1, 0, 1, 1))
+
+
+def test_confusion():
+    adjacency, arrowhead = confusion(TRUTH, GUESS, "from row to column")
+    # both edges of the truth are guessed, alongside an extra edge
+    assert adjacency == (2, 1, 0, 2 / 3, 1.0, 0.8)
+    # the arrowheads at 1 and 2 are not guessed, and those at 0 and 2 of the guess are wrong
+    assert arrowhead == (0, 2, 2, 0.0, 0.0, 0.0)
+
+
+def test_shd_markov_equivalent():
+    # 0 <- 1 <- 2 is Markov equivalent to the truth 0 -> 1 -> 2
+    reversed_chain = TRUTH.T.copy()
+    assert shd(TRUTH, reversed_chain) == (2 / 3, 2)
+    assert shd_markov_equivalent(TRUTH, reversed_chain, "from row to column") == (0.0, 0)
+    chain = np.array([[0, 2, 0], [2, 0, 2], [0, 2, 0]], dtype=np.int8)
+    assert np.array_equal(cpdag_of(TRUTH, "from row to column"), chain)
+    with pytest.raises(GadjidError):
+        shd_markov_equivalent(TRUTH, GUESS, "from row to column")
+
+
+def test_min_shd_over_mec():
+    chain = cpdag_of(TRUTH, "from row to column")
+    assert shd(TRUTH, chain) == (2 / 3, 2)
+    closest = min_shd_over_mec(TRUTH, chain, "from row to column")
+    assert closest.value() == (0.0, 0) and closest.exact
+    assert (closest.normalized, closest.mistakes) == (0.0, 0)
+    assert np.array_equal(closest.dag, TRUTH)
+    # the undirected 4-cycle has no orientation without a v-structure
+    cycle = np.array(
+        [[0, 2, 0, 2], [2, 0, 2, 0], [0, 2, 0, 2], [2, 0, 2, 0]], dtype=np.int8
+    )
+    with pytest.raises(GadjidError):
+        min_shd_over_mec(np.zeros((4, 4), dtype=np.int8), cycle, "from row to column")